@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+/// The user that is recorded for operations triggered by the password protected http endpoints.
+pub const HTTP_AUDIT_USER: &str = "http";
+
+/// The user that is recorded for operations triggered by github webhook deliveries.
+pub const GITHUB_AUDIT_USER: &str = "github";
+
+/// Whether or not an audited operation finished without an error.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalAuditStatus {
+    Succeeded,
+    Failed { message: String },
+}
+
+/// A record of who ran an operation, what they ran it with, and how it turned out.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalAuditLogEntry {
+    user: String,
+    operation: String,
+    arguments: String,
+    branch_name: Option<RoswaalOwnedGitBranchName>,
+    status: RoswaalAuditStatus,
+}
+
+impl RoswaalAuditLogEntry {
+    /// Creates a successful entry for an operation that did not create a branch.
+    ///
+    /// `user` is either the id of the slack user, or a constant such as `HTTP_AUDIT_USER` for
+    /// operations that were not run through slack.
+    pub fn new(user: &str, operation: &str, arguments: &str) -> Self {
+        Self {
+            user: user.to_string(),
+            operation: operation.to_string(),
+            arguments: arguments.to_string(),
+            branch_name: None,
+            status: RoswaalAuditStatus::Succeeded,
+        }
+    }
+
+    pub fn with_branch_name(self, branch_name: Option<&RoswaalOwnedGitBranchName>) -> Self {
+        Self {
+            branch_name: branch_name.cloned(),
+            ..self
+        }
+    }
+
+    pub fn with_status(self, status: RoswaalAuditStatus) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Marks this entry as failed if `result` is an error.
+    pub fn with_result<T, E: Display>(self, result: &Result<T, E>) -> Self {
+        match result {
+            Ok(_) => self,
+            Err(error) => self.with_status(RoswaalAuditStatus::Failed {
+                message: error.to_string(),
+            }),
+        }
+    }
+}
+
+impl RoswaalAuditLogEntry {
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    pub fn arguments(&self) -> &str {
+        &self.arguments
+    }
+
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        self.branch_name.as_ref()
+    }
+
+    pub fn status(&self) -> &RoswaalAuditStatus {
+        &self.status
+    }
+}
@@ -0,0 +1,31 @@
+use std::future::Future;
+
+use log::error;
+
+use crate::{utils::db::RoswaalDatabase, with_transaction};
+
+use super::entry::RoswaalAuditLogEntry;
+
+/// A trait for recording operations in the audit log.
+pub trait RoswaalRecordAuditLog {
+    /// Records the specified entry.
+    ///
+    /// Failing to record an entry must not fail the audited operation, so implementations are
+    /// expected to handle their own errors.
+    fn record(&self, entry: RoswaalAuditLogEntry) -> impl Future<Output = ()> + Send;
+}
+
+impl RoswaalRecordAuditLog for RoswaalDatabase {
+    async fn record(&self, entry: RoswaalAuditLogEntry) {
+        let result = async {
+            let mut transaction = self.transaction().await?;
+            with_transaction!(transaction, async {
+                transaction.save_audit_log_entry(&entry).await
+            })
+        }
+        .await;
+        if let Err(err) = result {
+            error!("Failed to record audit log entry {:?}: {}", entry, err)
+        }
+    }
+}
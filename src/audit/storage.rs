@@ -0,0 +1,158 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::{
+    git::branch_name::RoswaalOwnedGitBranchName,
+    utils::db::{
+        query::{query, query_as},
+        RoswaalDatabaseTransaction,
+    },
+};
+
+use super::entry::{RoswaalAuditLogEntry, RoswaalAuditStatus};
+
+/// An audit log entry along with the date that it was recorded.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoswaalStoredAuditLogEntry {
+    entry: RoswaalAuditLogEntry,
+    creation_date: DateTime<Utc>,
+}
+
+impl RoswaalStoredAuditLogEntry {
+    pub fn new(entry: RoswaalAuditLogEntry, creation_date: DateTime<Utc>) -> Self {
+        Self {
+            entry,
+            creation_date,
+        }
+    }
+}
+
+impl RoswaalStoredAuditLogEntry {
+    pub fn entry(&self) -> &RoswaalAuditLogEntry {
+        &self.entry
+    }
+
+    pub fn creation_date(&self) -> &DateTime<Utc> {
+        &self.creation_date
+    }
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    pub async fn save_audit_log_entry(&mut self, entry: &RoswaalAuditLogEntry) -> Result<()> {
+        let error_message = match entry.status() {
+            RoswaalAuditStatus::Succeeded => None,
+            RoswaalAuditStatus::Failed { message } => Some(message),
+        };
+        query(statements::INSERT_AUDIT_LOG_ENTRY)
+            .bind(entry.user())
+            .bind(entry.operation())
+            .bind(entry.arguments())
+            .bind(entry.branch_name())
+            .bind(error_message)
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` of the most recently recorded audit log entries, newest first.
+    pub async fn recent_audit_log_entries(
+        &mut self,
+        limit: u32,
+    ) -> Result<Vec<RoswaalStoredAuditLogEntry>> {
+        let entries = query_as::<SqliteAuditLogEntry>(statements::SELECT_RECENT_AUDIT_LOG)
+            .bind(i64::from(limit))
+            .fetch_all(self.connection())
+            .await?
+            .into_iter()
+            .map(|row| {
+                let status = match row.error_message {
+                    Some(message) => RoswaalAuditStatus::Failed { message },
+                    None => RoswaalAuditStatus::Succeeded,
+                };
+                let entry = RoswaalAuditLogEntry::new(&row.user, &row.operation, &row.arguments)
+                    .with_branch_name(row.branch_name.as_ref())
+                    .with_status(status);
+                RoswaalStoredAuditLogEntry::new(entry, row.creation_date)
+            })
+            .collect();
+        Ok(entries)
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteAuditLogEntry {
+    user: String,
+    operation: String,
+    arguments: String,
+    branch_name: Option<RoswaalOwnedGitBranchName>,
+    error_message: Option<String>,
+    creation_date: DateTime<Utc>,
+}
+
+mod statements {
+    pub const INSERT_AUDIT_LOG_ENTRY: &str = r#"
+INSERT INTO AuditLog (
+    "user",
+    operation,
+    arguments,
+    branch_name,
+    error_message
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?,
+    ?
+);"#;
+
+    pub const SELECT_RECENT_AUDIT_LOG: &str = r#"
+SELECT
+    "user",
+    operation,
+    arguments,
+    branch_name,
+    error_message,
+    creation_date
+FROM AuditLog
+ORDER BY id DESC
+LIMIT ?;
+"#;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        audit::entry::{RoswaalAuditLogEntry, RoswaalAuditStatus},
+        git::branch_name::RoswaalOwnedGitBranchName,
+        utils::db::RoswaalDatabase,
+    };
+
+    #[tokio::test]
+    async fn returns_most_recent_entries_first() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let entries = [
+            RoswaalAuditLogEntry::new("U1", "/view-tests", ""),
+            RoswaalAuditLogEntry::new("U2", "/add-tests", "New Test: A")
+                .with_branch_name(Some(&branch_name)),
+            RoswaalAuditLogEntry::new("http", "POST /merge", "roswaal-test").with_status(
+                RoswaalAuditStatus::Failed {
+                    message: "Oh no".to_string(),
+                },
+            ),
+        ];
+        for entry in entries.iter() {
+            transaction.save_audit_log_entry(entry).await.unwrap();
+        }
+        let stored_entries = transaction
+            .recent_audit_log_entries(2)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.entry().clone())
+            .collect::<Vec<RoswaalAuditLogEntry>>();
+        assert_eq!(stored_entries, vec![entries[2].clone(), entries[1].clone()])
+    }
+}
@@ -0,0 +1,366 @@
+//! A command line tool for compiling test specs and generating their code locally, without going
+//! through Slack or opening any PRs.
+//!
+//! ```sh
+//! cargo run --bin roswaal-cli -- compile specs.md --locations locations.txt
+//! cargo run --bin roswaal-cli -- generate specs.md --locations locations.txt --out ./roswaal
+//! cargo run --bin roswaal-cli -- validate-locations locations.txt
+//! ```
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{anyhow, Result};
+use roswaal::{
+    generation::{
+        interface::{test_directory_name, CodeGeneratorKind, RoswaalTypescriptGenerate},
+        pipeline::{save_generated_test_code, MAX_CONCURRENT_TEST_CODE_SAVES},
+    },
+    language::{
+        ast::{extract_tests_syntax, RoswaalTestSyntax},
+        compilation_results::RoswaalTestCompilationResults,
+        dependencies::RoswaalTestDependencyGraph,
+        test::RoswaalCompiledTest,
+    },
+    location::{
+        location::{RoswaalLocation, RoswaalStringLocations},
+        name::RoswaalLocationName,
+    },
+    slack::add_tests_view::{compilation_error_message, compilation_warning_message},
+    utils::config::RoswaalConfig,
+};
+use tokio::fs::{create_dir_all, read_to_string, write};
+
+const USAGE: &str = "\
+Usage:
+  roswaal-cli compile <spec>... [--locations <file>] [--normalize-steps]
+  roswaal-cli generate <spec>... --out <dir> [--locations <file>] [--normalize-steps]
+  roswaal-cli validate-locations <file>
+
+Each spec file holds the tests in code blocks like the text given to /add-tests, or a single test
+without a code block. The locations file uses the format of /add-locations.";
+
+#[tokio::main]
+async fn main() -> Result<ExitCode> {
+    RoswaalConfig::bootstrap().await?;
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    match CliCommand::parse(&args) {
+        Ok(command) => command.run().await,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum CliCommand {
+    Compile {
+        spec_paths: Vec<PathBuf>,
+        locations_path: Option<PathBuf>,
+        normalizes_step_labels: bool,
+    },
+    Generate {
+        spec_paths: Vec<PathBuf>,
+        locations_path: Option<PathBuf>,
+        normalizes_step_labels: bool,
+        out_dirpath: PathBuf,
+    },
+    ValidateLocations {
+        locations_path: PathBuf,
+    },
+}
+
+impl CliCommand {
+    /// Parses the arguments that follow the name of the binary, returning a message that
+    /// describes the problem if they are invalid.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let Some((subcommand, args)) = args.split_first() else {
+            return Err("No subcommand was given.".to_string());
+        };
+        let mut paths = Vec::<PathBuf>::new();
+        let mut locations_path = None;
+        let mut out_dirpath = None;
+        let mut normalizes_step_labels = false;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--locations" => locations_path = Some(Self::option_path(arg, args.next())?),
+                "--out" => out_dirpath = Some(Self::option_path(arg, args.next())?),
+                "--normalize-steps" => normalizes_step_labels = true,
+                option if option.starts_with("--") => {
+                    return Err(format!("Unknown option `{}`.", option))
+                }
+                path => paths.push(PathBuf::from(path)),
+            }
+        }
+        if paths.is_empty() {
+            return Err(format!("`{}` needs at least 1 file.", subcommand));
+        }
+        match subcommand.as_str() {
+            "compile" if out_dirpath.is_none() => Ok(Self::Compile {
+                spec_paths: paths,
+                locations_path,
+                normalizes_step_labels,
+            }),
+            "generate" => Ok(Self::Generate {
+                spec_paths: paths,
+                locations_path,
+                normalizes_step_labels,
+                out_dirpath: out_dirpath.ok_or("`generate` needs an `--out` directory.")?,
+            }),
+            "validate-locations"
+                if paths.len() == 1
+                    && locations_path.is_none()
+                    && out_dirpath.is_none()
+                    && !normalizes_step_labels =>
+            {
+                Ok(Self::ValidateLocations {
+                    locations_path: paths.remove(0),
+                })
+            }
+            "compile" | "validate-locations" => {
+                Err(format!("Invalid arguments for `{}`.", subcommand))
+            }
+            _ => Err(format!("Unknown subcommand `{}`.", subcommand)),
+        }
+    }
+
+    fn option_path(option: &str, value: Option<&String>) -> Result<PathBuf, String> {
+        value
+            .map(PathBuf::from)
+            .ok_or_else(|| format!("`{}` needs a path.", option))
+    }
+}
+
+impl CliCommand {
+    /// Runs this command, and returns a failure exit code if any of its inputs are invalid.
+    async fn run(self) -> Result<ExitCode> {
+        match self {
+            Self::Compile {
+                spec_paths,
+                locations_path,
+                normalizes_step_labels,
+            } => {
+                let locations = load_locations(locations_path.as_deref()).await?;
+                let specs = read_specs(&spec_paths).await?;
+                let (_, did_compile) = compile_specs(&specs, &locations, normalizes_step_labels);
+                Ok(exit_code(did_compile))
+            }
+            Self::Generate {
+                spec_paths,
+                locations_path,
+                normalizes_step_labels,
+                out_dirpath,
+            } => {
+                let locations = load_locations(locations_path.as_deref()).await?;
+                let specs = read_specs(&spec_paths).await?;
+                let (tests, did_compile) =
+                    compile_specs(&specs, &locations, normalizes_step_labels);
+                create_dir_all(&out_dirpath).await?;
+                let mut file_paths = save_generated_test_code(
+                    CodeGeneratorKind::current(),
+                    tests.iter().filter(|t| !t.is_planned()),
+                    |name| out_dirpath.join(test_directory_name(name)),
+                    MAX_CONCURRENT_TEST_CODE_SAVES,
+                )
+                .await?;
+                if locations_path.is_some() {
+                    let locations_file_path = out_dirpath.join("Locations.ts");
+                    let locations_code = locations
+                        .iter()
+                        .collect::<Vec<&RoswaalLocation>>()
+                        .typescript();
+                    write(&locations_file_path, locations_code).await?;
+                    file_paths.push(locations_file_path);
+                }
+                println!("\nGenerated {} files:", file_paths.len());
+                for path in file_paths {
+                    println!("  {}", path.display());
+                }
+                Ok(exit_code(did_compile))
+            }
+            Self::ValidateLocations { locations_path } => {
+                let locations_str = read_to_string(&locations_path).await?;
+                let string_locations =
+                    RoswaalStringLocations::from_roswaal_locations_str(&locations_str);
+                for location in string_locations.locations() {
+                    let coordinate = location.coordinate();
+                    println!(
+                        "✔ {} ({}, {})",
+                        location.name().raw_name(),
+                        coordinate.latitude(),
+                        coordinate.longitude()
+                    );
+                }
+                for error in string_locations.errors() {
+                    println!(
+                        "✘ {} ({})",
+                        error.raw_associated_location_name(),
+                        error.reason()
+                    );
+                }
+                Ok(exit_code(!string_locations.has_errors()))
+            }
+        }
+    }
+}
+
+fn exit_code(is_success: bool) -> ExitCode {
+    if is_success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Loads the locations in the file at `path`, failing if any of them are invalid.
+async fn load_locations(path: Option<&Path>) -> Result<Vec<RoswaalLocation>> {
+    let Some(path) = path else {
+        return Ok(vec![]);
+    };
+    let string_locations =
+        RoswaalStringLocations::from_roswaal_locations_str(&read_to_string(path).await?);
+    if string_locations.has_errors() {
+        return Err(anyhow!(
+            "{} has invalid locations, run `roswaal-cli validate-locations {}` to list them.",
+            path.display(),
+            path.display()
+        ));
+    }
+    Ok(string_locations.locations())
+}
+
+async fn read_specs(paths: &[PathBuf]) -> Result<Vec<(&PathBuf, String)>> {
+    let mut specs = Vec::new();
+    for path in paths {
+        specs.push((path, read_to_string(path).await?));
+    }
+    Ok(specs)
+}
+
+/// Returns the tests in a spec file, which are either in code blocks, or make up the entire file
+/// when it has no code blocks.
+fn spec_syntax(spec: &str) -> Vec<RoswaalTestSyntax<'_>> {
+    let syntax = extract_tests_syntax(spec);
+    if syntax.is_empty() && !spec.trim().is_empty() {
+        return vec![RoswaalTestSyntax::from(spec.trim())];
+    }
+    syntax
+}
+
+/// Compiles the tests in each spec, and prints the warnings and errors of each test.
+///
+/// Returns the compiled tests, and whether or not every test compiled. Tests cannot require tests
+/// from the database, so `Requires Test` commands always fail to compile.
+fn compile_specs(
+    specs: &[(&PathBuf, String)],
+    locations: &[RoswaalLocation],
+    normalizes_step_labels: bool,
+) -> (Vec<RoswaalCompiledTest>, bool) {
+    let location_names = locations
+        .iter()
+        .map(|l| l.name().clone())
+        .collect::<Vec<RoswaalLocationName>>();
+    let mut tests = Vec::new();
+    let mut did_compile = true;
+    for (path, spec) in specs {
+        let results = RoswaalTestCompilationResults::compile_with_test_dependencies(
+            spec_syntax(spec),
+            &location_names,
+            &vec![],
+            &RoswaalTestDependencyGraph::default(),
+            normalizes_step_labels,
+        );
+        println!("{}", path.display());
+        for test in results.tests() {
+            println!("  ✔ {}", test.name());
+            for warning in test.warnings() {
+                println!(
+                    "    warning (line {}): {}",
+                    warning.line_number(),
+                    compilation_warning_message(warning.code())
+                );
+            }
+        }
+        for failure in results.failures() {
+            println!("  ✘ Test {}", failure.test_number());
+            for error in failure.errors() {
+                println!(
+                    "    error (line {}): {}",
+                    error.line_number(),
+                    compilation_error_message(error)
+                );
+            }
+        }
+        did_compile &= !results.has_non_compiling_tests();
+        tests.append(&mut results.tests());
+    }
+    (tests, did_compile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_subcommands() {
+        let args = [
+            "generate",
+            "a.md",
+            "b.md",
+            "--out",
+            "out",
+            "--normalize-steps",
+        ]
+        .map(String::from);
+        assert_eq!(
+            CliCommand::parse(&args),
+            Ok(CliCommand::Generate {
+                spec_paths: vec![PathBuf::from("a.md"), PathBuf::from("b.md")],
+                locations_path: None,
+                normalizes_step_labels: true,
+                out_dirpath: PathBuf::from("out")
+            })
+        );
+        let args = ["validate-locations", "locations.txt"].map(String::from);
+        assert_eq!(
+            CliCommand::parse(&args),
+            Ok(CliCommand::ValidateLocations {
+                locations_path: PathBuf::from("locations.txt")
+            })
+        )
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let invalid_args = [
+            vec![],
+            vec!["compile"],
+            vec!["compile", "a.md", "--locations"],
+            vec!["compile", "a.md", "--out", "out"],
+            vec!["generate", "a.md"],
+            vec!["validate-locations", "a.txt", "b.txt"],
+            vec!["lint", "a.md"],
+        ];
+        for args in invalid_args {
+            let args = args.into_iter().map(String::from).collect::<Vec<String>>();
+            assert!(CliCommand::parse(&args).is_err(), "{:?}", args)
+        }
+    }
+
+    #[test]
+    fn reads_specs_without_code_blocks_as_a_single_test() {
+        let spec = "New Test: Hello\nStep 1: A\nRequirement 1: B\n";
+        assert_eq!(
+            spec_syntax(spec),
+            vec![RoswaalTestSyntax::from(spec.trim())]
+        );
+        let spec = "```\nNew Test: Hello\n```\n```\nNew Test: World\n```";
+        assert_eq!(spec_syntax(spec).len(), 2);
+        assert_eq!(spec_syntax("  \n"), vec![])
+    }
+}
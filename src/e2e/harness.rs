@@ -0,0 +1,140 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use axum::serve;
+use log::{error, info};
+use reqwest::Client;
+use tokio::{fs::read_to_string, net::TcpListener, spawn};
+
+use crate::{
+    git::{
+        process::delete_remote_branch_with_git_binary, pull_request::GithubPullRequest,
+        repo::RoswaalGitRepositoryClient,
+    },
+    http::{server::roswaal_server, server_environment::ServerEnvironment},
+    with_transaction,
+};
+
+/// A slack command that the harness sends to the server, and the PR that it should create.
+struct E2EScenario {
+    name: &'static str,
+    /// The path to the recorded form fields that slack sent for the command.
+    payload_path: &'static str,
+    /// The start of the title of the PR that the command should create.
+    expected_title_prefix: &'static str,
+}
+
+const E2E_SCENARIOS: [E2EScenario; 2] = [
+    E2EScenario {
+        name: "Add Tests",
+        payload_path: "./e2e-payloads/add-tests.json",
+        expected_title_prefix: "[Test - DO NOT MERGE] Roswaal: Add Tests",
+    },
+    E2EScenario {
+        name: "Add Locations",
+        payload_path: "./e2e-payloads/add-locations.json",
+        expected_title_prefix: "[Test - DO NOT MERGE] Roswaal: Add Locations",
+    },
+];
+
+/// Runs each scenario against a server for the FitnessProjectTest repo, and then deletes the
+/// branches that the scenarios pushed.
+///
+/// Returns an error naming the failed scenarios if any scenario did not create its PR.
+pub async fn run_e2e_harness() -> Result<()> {
+    let environment = Arc::new(ServerEnvironment::e2e().await?);
+    let listener = TcpListener::bind(environment.address()).await?;
+    let router = roswaal_server(environment.clone());
+    let server = spawn(async move { serve(listener, router).await });
+    let client = Client::new();
+    let mut failed_scenario_names = Vec::new();
+    for scenario in E2E_SCENARIOS.iter() {
+        info!("Running E2E scenario \"{}\".", scenario.name);
+        if let Err(error) = scenario.run(&client, &environment).await {
+            error!("E2E scenario \"{}\" failed: {}", scenario.name, error);
+            failed_scenario_names.push(scenario.name);
+        }
+    }
+    server.abort();
+    tear_down_branches(&environment).await?;
+    if failed_scenario_names.is_empty() {
+        info!("All {} E2E scenarios passed.", E2E_SCENARIOS.len());
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "E2E scenarios failed: {}",
+            failed_scenario_names.join(", ")
+        ))
+    }
+}
+
+impl E2EScenario {
+    async fn run(&self, client: &Client, environment: &ServerEnvironment) -> Result<()> {
+        let payload = serde_json::from_str::<HashMap<String, String>>(
+            &read_to_string(self.payload_path).await?,
+        )?;
+        let pull_request_count = pending_pull_requests(environment).await?.len();
+        let response = client
+            .post(format!("http://{}/slack", environment.address()))
+            .form(&payload)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("/slack responded with {}.", response.status()));
+        }
+        // NB: The server responds before the command is handled, so wait for the background work
+        // of the command to finish before checking for its PR.
+        let background_tasks = environment.background_tasks();
+        background_tasks.close();
+        background_tasks.wait().await;
+        background_tasks.reopen();
+        let pull_requests = pending_pull_requests(environment).await?;
+        let pull_request = pull_requests
+            .get(pull_request_count..)
+            .and_then(|new_pull_requests| new_pull_requests.last())
+            .ok_or_else(|| anyhow!("No PR was created."))?;
+        if !pull_request.title().starts_with(self.expected_title_prefix) {
+            return Err(anyhow!(
+                "Expected a PR titled \"{}...\", but got \"{}\".",
+                self.expected_title_prefix,
+                pull_request.title()
+            ));
+        }
+        let transaction = environment.git_repository().transaction().await;
+        let head_branch_name = pull_request.head_branch().to_string();
+        if !transaction
+            .remote_branch_names()
+            .await?
+            .contains(&head_branch_name)
+        {
+            return Err(anyhow!("{} was not pushed.", head_branch_name));
+        }
+        Ok(())
+    }
+}
+
+async fn pending_pull_requests(environment: &ServerEnvironment) -> Result<Vec<GithubPullRequest>> {
+    let database = environment.database();
+    let mut transaction = database.read_transaction().await?;
+    with_transaction!(transaction, async {
+        transaction.pending_pull_requests().await
+    })
+}
+
+/// Deletes the local and remote branch of every PR that the scenarios created.
+///
+/// Deleting the remote branch also closes its PR if it was opened on GitHub.
+async fn tear_down_branches(environment: &ServerEnvironment) -> Result<()> {
+    let transaction = environment.git_repository().transaction().await;
+    let path = transaction.metadata().relative_path(".");
+    transaction
+        .switch_branch(transaction.metadata().base_branch_name())
+        .await?;
+    for pull_request in pending_pull_requests(environment).await? {
+        let branch_name = pull_request.head_branch();
+        info!("Tearing down {}.", branch_name.to_string());
+        transaction.delete_local_branch(branch_name).await?;
+        delete_remote_branch_with_git_binary(&path, branch_name).await?;
+    }
+    Ok(())
+}
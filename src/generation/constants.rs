@@ -1 +1,2 @@
 pub static GENERATED_HEADER: &str = "// Generated by Roswaal, do not touch.\n\n";
+pub static YAML_GENERATED_HEADER: &str = "# Generated by Roswaal, do not touch.\n\n";
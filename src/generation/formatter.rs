@@ -0,0 +1,139 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use tokio::process::Command;
+
+use crate::git::metadata::RoswaalGitRepositoryMetadata;
+
+/// Runs a formatter command (eg. `npx prettier --write`) on generated files, so that they match
+/// the style that a repository enforces in its lint checks.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalCodeFormatter {
+    command: String,
+    working_dir_path: PathBuf,
+}
+
+impl RoswaalCodeFormatter {
+    pub fn new(command: &str, working_dir_path: impl AsRef<Path>) -> Self {
+        Self {
+            command: command.to_string(),
+            working_dir_path: working_dir_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns a formatter that runs from the root of the repository described by `metadata`, or
+    /// None if the repository has no formatter command.
+    pub fn for_repository(metadata: &RoswaalGitRepositoryMetadata) -> Option<Self> {
+        metadata
+            .formatter_command()
+            .map(|command| Self::new(command, metadata.relative_path("")))
+    }
+}
+
+impl RoswaalCodeFormatter {
+    /// Runs the formatter command from the working directory with each of `file_paths` appended
+    /// to its arguments.
+    ///
+    /// The command is split on whitespace instead of being run by a shell. Paths inside of the
+    /// working directory are passed relative to it.
+    pub async fn format(&self, file_paths: &[PathBuf]) -> Result<()> {
+        let mut words = self.command.split_whitespace();
+        let Some(program) = words.next() else {
+            return Ok(());
+        };
+        if file_paths.is_empty() {
+            return Ok(());
+        }
+        let paths = file_paths
+            .iter()
+            .map(|path| path.strip_prefix(&self.working_dir_path).unwrap_or(path));
+        let output = Command::new(program)
+            .args(words)
+            .args(paths)
+            .current_dir(&self.working_dir_path)
+            .kill_on_drop(true)
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(CodeFormatterError {
+                command: self.command.clone(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Formats `file_paths` with the formatter of the repository described by `metadata`, if it has
+/// one.
+///
+/// Unformatted code still runs, so a formatter failure is logged as a warning rather than failing
+/// the edit that generated the code.
+pub async fn format_generated_files(
+    metadata: &RoswaalGitRepositoryMetadata,
+    file_paths: &[PathBuf],
+) {
+    let Some(formatter) = RoswaalCodeFormatter::for_repository(metadata) else {
+        return;
+    };
+    if let Err(error) = formatter.format(file_paths).await {
+        log::warn!(
+            "Failed to format the generated code in {}. {}",
+            metadata.name(),
+            error
+        )
+    }
+}
+
+/// An error that occurs when a formatter command exits unsuccessfully.
+#[derive(Debug)]
+pub struct CodeFormatterError {
+    command: String,
+    stderr: String,
+}
+
+impl Display for CodeFormatterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` failed: {}", self.command, self.stderr)
+    }
+}
+
+impl Error for CodeFormatterError {}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use nanoid::nanoid;
+    use tokio::fs::{create_dir_all, read_to_string, write};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_the_command_on_paths_relative_to_the_working_directory() {
+        let root = env::temp_dir().join(format!("roswaal-{}", nanoid!(10)));
+        create_dir_all(root.join("roswaal")).await.unwrap();
+        let paths = vec![root.join("roswaal").join("TestCase.test.ts")];
+        write(&paths[0], "const a = \"a\"\n").await.unwrap();
+        RoswaalCodeFormatter::new("sed -i s/\"/'/g", &root)
+            .format(&paths)
+            .await
+            .unwrap();
+        assert_eq!(read_to_string(&paths[0]).await.unwrap(), "const a = 'a'\n")
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_command_exits_unsuccessfully() {
+        let root = env::temp_dir();
+        let error = RoswaalCodeFormatter::new("false", &root)
+            .format(&[root.join("TestCase.test.ts")])
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "`false` failed: ")
+    }
+}
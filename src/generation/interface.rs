@@ -1,5 +1,160 @@
+use anyhow::Result;
+use std::{env, path::Path};
+use tokio::{
+    fs::{create_dir_all, read_to_string, try_exists, File},
+    io::AsyncWriteExt,
+};
+
+use crate::{language::test::RoswaalCompiledTest, utils::string::ToAsciiKebabCase};
+
+use super::{maestro::MaestroCodeGenerator, test_case::TypescriptCodeGenerator};
+
 /// A trait for transpiling into a GeneratedTypescript instance.
 pub trait RoswaalTypescriptGenerate<Typescript> {
     /// The associated typescript code for this test command.
     fn typescript(&self) -> Typescript;
 }
+
+/// Returns the name of the directory that holds the generated code of the test with the specified
+/// name.
+///
+/// The directories of all tests share a parent directory, so generated code can refer to the code
+/// of another test through `../<directory name>`.
+pub fn test_directory_name(test_name: &str) -> String {
+    test_name.to_ascii_kebab_case().to_ascii_lowercase()
+}
+
+/// A trait for generating the source files of a compiled test for a specific test framework.
+pub trait CodeGenerator {
+    /// Generates the source files for the specified test.
+    fn generate(&self, test: &RoswaalCompiledTest) -> GeneratedTestCode;
+
+    /// Merges freshly `generated` code with the `existing` files from a previous generation of
+    /// the same test, so that hand written code is not lost when the test is re-added.
+    ///
+    /// By default, the generated code replaces the existing files.
+    fn merge_existing(
+        &self,
+        generated: GeneratedTestCode,
+        _existing: &GeneratedTestCode,
+    ) -> GeneratedTestCode {
+        generated
+    }
+}
+
+/// The source files generated for a single test.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GeneratedTestCode {
+    files: Vec<(String, String)>,
+}
+
+impl GeneratedTestCode {
+    /// Creates an instance from pairs of file paths (relative to the test directory) and their
+    /// contents.
+    pub fn new(files: Vec<(String, String)>) -> Self {
+        Self { files }
+    }
+
+    /// Returns the relative file paths and contents of the generated files.
+    pub fn files(&self) -> &Vec<(String, String)> {
+        &self.files
+    }
+
+    /// Reads the files in the specified dirpath that have the same relative paths as the files in
+    /// this instance, skipping the files that do not exist.
+    pub async fn read_existing_in_dir(&self, dirpath: impl AsRef<Path>) -> Result<Self> {
+        let mut files = Vec::new();
+        for (relative_path, _) in self.files.iter() {
+            let path = dirpath.as_ref().join(relative_path);
+            if try_exists(&path).await? {
+                files.push((relative_path.clone(), read_to_string(&path).await?));
+            }
+        }
+        Ok(Self::new(files))
+    }
+
+    /// Returns the contents of the file at the specified relative path, if it exists.
+    pub fn file(&self, relative_path: &str) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|(path, _)| path == relative_path)
+            .map(|(_, contents)| contents.as_str())
+    }
+
+    /// Saves the generated files in the specified dirpath, creating any intermediate directories.
+    pub async fn save_in_dir(&self, dirpath: impl AsRef<Path>) -> Result<()> {
+        for (relative_path, contents) in self.files.iter() {
+            let path = dirpath.as_ref().join(relative_path);
+            if let Some(parent) = path.parent() {
+                create_dir_all(parent).await?;
+            }
+            let mut file = File::create(&path).await?;
+            file.write_all(contents.as_bytes()).await?;
+            file.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// The test framework that test code is generated for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CodeGeneratorKind {
+    /// Detox tests written in TypeScript for the React Native frontend.
+    Typescript,
+    /// Maestro YAML flows for native mobile apps.
+    Maestro,
+}
+
+impl CodeGeneratorKind {
+    /// Returns the generator kind based on the `ROSWAAL_CODE_GENERATOR` environment variable.
+    ///
+    /// If the variable is "maestro", then Maestro flows are generated. Otherwise, TypeScript is
+    /// generated.
+    pub fn current() -> Self {
+        Self::from_env_value(env::var("ROSWAAL_CODE_GENERATOR").ok().as_deref())
+    }
+
+    fn from_env_value(value: Option<&str>) -> Self {
+        match value {
+            Some("maestro") => Self::Maestro,
+            _ => Self::Typescript,
+        }
+    }
+}
+
+impl CodeGenerator for CodeGeneratorKind {
+    fn generate(&self, test: &RoswaalCompiledTest) -> GeneratedTestCode {
+        match self {
+            Self::Typescript => TypescriptCodeGenerator.generate(test),
+            Self::Maestro => MaestroCodeGenerator.generate(test),
+        }
+    }
+
+    fn merge_existing(
+        &self,
+        generated: GeneratedTestCode,
+        existing: &GeneratedTestCode,
+    ) -> GeneratedTestCode {
+        match self {
+            Self::Typescript => TypescriptCodeGenerator.merge_existing(generated, existing),
+            Self::Maestro => MaestroCodeGenerator.merge_existing(generated, existing),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CodeGeneratorKind;
+
+    #[test]
+    fn code_generator_kind_from_env_value() {
+        assert_eq!(
+            CodeGeneratorKind::from_env_value(Some("maestro")),
+            CodeGeneratorKind::Maestro
+        );
+        assert_eq!(
+            CodeGeneratorKind::from_env_value(None),
+            CodeGeneratorKind::Typescript
+        );
+    }
+}
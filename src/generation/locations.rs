@@ -1,9 +1,55 @@
-use crate::location::location::RoswaalLocation;
+use std::path::PathBuf;
 
-use super::{constants::GENERATED_HEADER, interface::RoswaalTypescriptGenerate};
+use anyhow::Result;
+use tokio::fs::{remove_dir_all, try_exists, write};
+
+use crate::{
+    git::metadata::RoswaalGitRepositoryMetadata,
+    location::{
+        location::{group_by_region, RoswaalLocation},
+        name::RoswaalLocationName,
+    },
+};
+
+use super::{
+    constants::GENERATED_HEADER,
+    interface::{CodeGeneratorKind, RoswaalTypescriptGenerate},
+    maestro,
+};
 
 pub type LocationsTypescript = String;
 
+/// Regenerates the locations code of the repository described by `metadata` from `locations`,
+/// and returns the paths of the generated files.
+///
+/// TypeScript repos get a single locations file, and Maestro repos get a flow for every location.
+/// The Maestro flows directory is recreated from scratch, so the flows of removed locations are
+/// deleted.
+pub async fn save_locations_code(
+    metadata: &RoswaalGitRepositoryMetadata,
+    locations: &[&RoswaalLocation],
+) -> Result<Vec<PathBuf>> {
+    match metadata.code_generator() {
+        CodeGeneratorKind::Typescript => {
+            write(metadata.locations_path(), locations.to_vec().typescript()).await?;
+            Ok(vec![metadata.locations_path().to_path_buf()])
+        }
+        CodeGeneratorKind::Maestro => {
+            let flows_dirpath = metadata.location_flows_dirpath();
+            if try_exists(&flows_dirpath).await? {
+                remove_dir_all(&flows_dirpath).await?;
+            }
+            let code = maestro::locations_code(locations);
+            code.save_in_dir(metadata.test_cases_root_dirpath()).await?;
+            Ok(code
+                .files()
+                .iter()
+                .map(|(path, _)| metadata.test_cases_root_dirpath().join(path))
+                .collect())
+        }
+    }
+}
+
 impl RoswaalTypescriptGenerate<LocationsTypescript> for RoswaalLocation {
     fn typescript(&self) -> LocationsTypescript {
         format!(
@@ -38,17 +84,43 @@ impl RoswaalTypescriptGenerate<LocationsTypescript> for Vec<&RoswaalLocation> {
         ts.push_str(SET_LOCATIONS_FUNCTION);
         ts.push_str(LOCATIONS_NAMESPACE_DECL_START);
         ts.push_str(LOCATION_SPACING);
-        for (i, location_ts) in self.iter().map(|l| l.typescript()).enumerate() {
-            ts.push_str(&location_ts);
-            if i < self.len() - 1 {
-                ts.push_str(LOCATION_SPACING);
+        let mut declarations = Vec::<String>::new();
+        for (region, locations) in group_by_region(self, |l| *l) {
+            match region {
+                Some(region) => declarations.push(region_typescript(region, &locations)),
+                None => declarations.extend(locations.iter().map(|l| l.typescript())),
             }
         }
+        ts.push_str(&declarations.join(LOCATION_SPACING));
         ts.push_str(LOCATIONS_NAMESPACE_DECL_END);
         ts
     }
 }
 
+/// Generates a nested namespace for the locations in `region`.
+///
+/// Each location is also exported outside of the region namespace, so that tests can refer to
+/// locations by name without knowing their region.
+fn region_typescript(region: &RoswaalLocationName, locations: &[&&RoswaalLocation]) -> String {
+    let namespace = region.to_ascii_pascal_case_string();
+    let mut ts = format!("export namespace {} {{\n", namespace);
+    for location in locations {
+        ts.push_str(LOCATION_SPACING);
+        ts.push_str(LOCATION_SPACING);
+        ts.push_str(&location.typescript().replace("\n  ", "\n    "));
+    }
+    ts.push_str(LOCATION_SPACING);
+    ts.push_str("}\n");
+    for location in locations {
+        let name = location.name().to_ascii_pascal_case_string();
+        ts.push_str(&format!(
+            "{}export const {} = {}.{}\n",
+            LOCATION_SPACING, name, namespace, name
+        ));
+    }
+    ts
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -104,6 +176,55 @@ export namespace TestLocations {
     longitude: 60.0000000000000000
   }
 }
+";
+        assert_eq!(locations.typescript(), expected_ts)
+    }
+
+    #[test]
+    fn test_locations_vector_typescript_with_regions() {
+        let region = |name: &str| Some(RoswaalLocationName::from_str(name).unwrap());
+        let location1 = RoswaalLocation::new_without_validation("Oakland", 50.0, 50.0)
+            .with_region(region("Bay Area"));
+        let location2 = RoswaalLocation::new_without_validation("Antarctica", 10.0, 10.0);
+        let location3 = RoswaalLocation::new_without_validation("Manhattan", 60.0, 60.0)
+            .with_region(region("New York"));
+        let location4 = RoswaalLocation::new_without_validation("Berkeley", 40.0, 40.0)
+            .with_region(region("bay area"));
+        let locations = vec![&location1, &location2, &location3, &location4];
+        let expected_ts = "\
+// Generated by Roswaal, do not touch.
+
+import { LocationCoordinate2D } from \"TiFShared/domain-models/LocationCoordinate2D\"
+
+export const setUserLocation = async (coordinate: LocationCoordinate2D) => {
+  await device.setLocation(coordinate.latitude, coordinate.longitude)
+}
+
+export namespace TestLocations {
+  export const Antarctica = {
+    latitude: 10.0000000000000000,
+    longitude: 10.0000000000000000
+  }
+  export namespace BayArea {
+    export const Oakland = {
+      latitude: 50.0000000000000000,
+      longitude: 50.0000000000000000
+    }
+    export const Berkeley = {
+      latitude: 40.0000000000000000,
+      longitude: 40.0000000000000000
+    }
+  }
+  export const Oakland = BayArea.Oakland
+  export const Berkeley = BayArea.Berkeley
+  export namespace NewYork {
+    export const Manhattan = {
+      latitude: 60.0000000000000000,
+      longitude: 60.0000000000000000
+    }
+  }
+  export const Manhattan = NewYork.Manhattan
+}
 ";
         assert_eq!(locations.typescript(), expected_ts)
     }
@@ -0,0 +1,468 @@
+use crate::{
+    language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+    location::location::RoswaalLocation,
+    utils::dedup::DedupIterator,
+};
+
+use super::{
+    constants::YAML_GENERATED_HEADER,
+    interface::{test_directory_name, CodeGenerator, GeneratedTestCode},
+    test_case::step_action_name,
+};
+
+/// A `CodeGenerator` that outputs Maestro YAML flows for native mobile apps.
+///
+/// Each test generates a `TestCase.yaml` flow that runs a subflow in the `actions` directory for
+/// every step. Location changes run the flow for the location in the shared `locations` directory
+/// next to the test directories, which is generated by `locations_code` whenever locations are
+/// added or removed, and required tests run the action flows in the directory of the
+/// required test.
+pub struct MaestroCodeGenerator;
+
+impl CodeGenerator for MaestroCodeGenerator {
+    fn generate(&self, test: &RoswaalCompiledTest) -> GeneratedTestCode {
+        let mut files = vec![("TestCase.yaml".to_string(), test_case_yaml(test))];
+        let action_files = test
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RoswaalCompiledTestCommand::Step {
+                    label: _,
+                    name,
+                    requirement,
+                    retries: _,
+                }
+                | RoswaalCompiledTestCommand::Verify {
+                    label: _,
+                    name,
+                    requirement,
+                } => Some((step_action_name(requirement), name.clone())),
+                RoswaalCompiledTestCommand::BeforeLaunch { name } => {
+                    Some((step_action_name(name), name.clone()))
+                }
+                RoswaalCompiledTestCommand::SetLocation { location_name: _ } => None,
+                RoswaalCompiledTestCommand::Wait { duration: _ } => None,
+                RoswaalCompiledTestCommand::RequiresTest { .. } => None,
+            })
+            .dedup()
+            .map(|(action_name, step_name)| {
+                (
+                    format!("actions/{}.yaml", action_name),
+                    action_yaml(&step_name),
+                )
+            });
+        files.extend(action_files);
+        GeneratedTestCode::new(files)
+    }
+}
+
+/// The name of the directory next to the test directories that holds a flow for every location.
+pub const LOCATIONS_DIR: &str = "locations";
+
+/// Generates a flow that sets the device location for each location in `locations`.
+///
+/// The paths of the flows are relative to the directory that contains the generated code of every
+/// test.
+pub fn locations_code(locations: &[&RoswaalLocation]) -> GeneratedTestCode {
+    GeneratedTestCode::new(
+        locations
+            .iter()
+            .map(|location| {
+                (
+                    format!(
+                        "{}/{}.yaml",
+                        LOCATIONS_DIR,
+                        location.name().to_ascii_pascal_case_string()
+                    ),
+                    location_yaml(location),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn location_yaml(location: &RoswaalLocation) -> String {
+    format!(
+        "\
+{}{}{}# {}
+- setLocation:
+    latitude: {:.16}
+    longitude: {:.16}
+",
+        YAML_GENERATED_HEADER,
+        FLOW_CONFIG,
+        FLOW_CONFIG_END,
+        location.name().raw_name(),
+        location.coordinate().latitude(),
+        location.coordinate().longitude()
+    )
+}
+
+/// Maestro has no plain sleep command, so waits are written as an optional wait for text that
+/// never appears on screen.
+const MAESTRO_WAIT_PLACEHOLDER_TEXT: &str = "__roswaal_wait__";
+const FLOW_CONFIG: &str = "appId: ${APP_ID}\n";
+const FLOW_CONFIG_END: &str = "---\n";
+
+fn test_case_yaml(test: &RoswaalCompiledTest) -> String {
+    let mut yaml = YAML_GENERATED_HEADER.to_string();
+    yaml.push_str(FLOW_CONFIG);
+    yaml.push_str(&format!("name: \"{}\"\n", test.name().replace('"', "\\\"")));
+    yaml.push_str(FLOW_CONFIG_END);
+    yaml.push_str(&before_launch_yaml(test.commands(), ACTIONS_DIR));
+    yaml.push_str("- launchApp\n");
+    yaml.push_str(&commands_yaml(test.commands(), ACTIONS_DIR));
+    yaml
+}
+
+const ACTIONS_DIR: &str = "actions";
+
+/// Returns the path of the actions directory of a required test relative to the test directory.
+fn required_test_actions_dir(test_name: &str) -> String {
+    format!("../{}/{}", test_directory_name(test_name), ACTIONS_DIR)
+}
+
+fn before_launch_yaml(commands: &[RoswaalCompiledTestCommand], actions_dir: &str) -> String {
+    let mut yaml = String::new();
+    for command in commands {
+        match command {
+            RoswaalCompiledTestCommand::BeforeLaunch { name } => yaml.push_str(&format!(
+                "# {}\n- runFlow: {}/{}.yaml\n",
+                name,
+                actions_dir,
+                step_action_name(name)
+            )),
+            RoswaalCompiledTestCommand::RequiresTest {
+                test_name,
+                commands,
+            } => yaml.push_str(&before_launch_yaml(
+                commands,
+                &required_test_actions_dir(test_name),
+            )),
+            _ => {}
+        }
+    }
+    yaml
+}
+
+fn commands_yaml(commands: &[RoswaalCompiledTestCommand], actions_dir: &str) -> String {
+    let mut yaml = String::new();
+    for command in commands {
+        match command {
+            RoswaalCompiledTestCommand::BeforeLaunch { name: _ } => {}
+            RoswaalCompiledTestCommand::Step {
+                label: _,
+                name,
+                requirement,
+                retries: Some(retries),
+            } => yaml.push_str(&format!(
+                "# {}\n- retry:\n    maxRetries: {}\n    commands:\n      - runFlow: {}/{}.yaml\n",
+                name,
+                retries,
+                actions_dir,
+                step_action_name(requirement)
+            )),
+            RoswaalCompiledTestCommand::Step {
+                label: _,
+                name,
+                requirement,
+                retries: None,
+            }
+            | RoswaalCompiledTestCommand::Verify {
+                label: _,
+                name,
+                requirement,
+            } => yaml.push_str(&format!(
+                "# {}\n- runFlow: {}/{}.yaml\n",
+                name,
+                actions_dir,
+                step_action_name(requirement)
+            )),
+            RoswaalCompiledTestCommand::SetLocation { location_name } => yaml.push_str(&format!(
+                "# Set Location to {}\n- runFlow: ../{}/{}.yaml\n",
+                location_name.raw_name(),
+                LOCATIONS_DIR,
+                location_name.to_ascii_pascal_case_string()
+            )),
+            RoswaalCompiledTestCommand::Wait { duration } => yaml.push_str(&format!(
+                "# Wait {}\n- extendedWaitUntil:\n    visible: \"{}\"\n    timeout: {}\n    optional: true\n",
+                duration,
+                MAESTRO_WAIT_PLACEHOLDER_TEXT,
+                duration.millis()
+            )),
+            RoswaalCompiledTestCommand::RequiresTest {
+                test_name,
+                commands,
+            } => {
+                yaml.push_str(&format!("# Requires Test: {}\n", test_name));
+                yaml.push_str(&commands_yaml(
+                    commands,
+                    &required_test_actions_dir(test_name),
+                ))
+            }
+        }
+    }
+    yaml
+}
+
+fn action_yaml(step_name: &str) -> String {
+    format!(
+        "\
+{}{}# {}
+- assertTrue:
+    condition: ${{false}}
+    label: \"TODO\"
+",
+        FLOW_CONFIG, FLOW_CONFIG_END, step_name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::location::{location::RoswaalLocation, name::RoswaalLocationName};
+
+    use super::*;
+
+    #[test]
+    fn test_case_flow_with_steps_and_location_changes() {
+        let test = RoswaalCompiledTest::new(
+            "I am \"Bob\"".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Johnny is signed in".to_string(),
+                    requirement: "Ensure Johnny is signed into his account".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::SetLocation {
+                    location_name: RoswaalLocationName::from_str("Oakland").unwrap(),
+                },
+            ],
+        );
+        let code = MaestroCodeGenerator.generate(&test);
+        let expected_yaml = r#"# Generated by Roswaal, do not touch.
+
+appId: ${APP_ID}
+name: "I am \"Bob\""
+---
+- launchApp
+# Johnny is signed in
+- runFlow: actions/ensureJohnnyIsSignedIntoHisAccount.yaml
+# Set Location to Oakland
+- runFlow: ../locations/Oakland.yaml
+"#;
+        assert_eq!(
+            code.files()[0],
+            ("TestCase.yaml".to_string(), expected_yaml.to_string())
+        )
+    }
+
+    #[test]
+    fn test_case_flow_runs_before_launch_commands_before_launching() {
+        let test = RoswaalCompiledTest::new(
+            "A".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Johnny is signed in".to_string(),
+                    requirement: "Ensure Johnny is signed into his account".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::BeforeLaunch {
+                    name: "Reset permissions".to_string(),
+                },
+            ],
+        );
+        let code = MaestroCodeGenerator.generate(&test);
+        let expected_yaml = r#"# Generated by Roswaal, do not touch.
+
+appId: ${APP_ID}
+name: "A"
+---
+# Reset permissions
+- runFlow: actions/resetPermissions.yaml
+- launchApp
+# Johnny is signed in
+- runFlow: actions/ensureJohnnyIsSignedIntoHisAccount.yaml
+"#;
+        assert_eq!(
+            code.files()[0],
+            ("TestCase.yaml".to_string(), expected_yaml.to_string())
+        );
+        assert_eq!(code.files()[2].0, "actions/resetPermissions.yaml")
+    }
+
+    #[test]
+    fn test_case_flow_with_wait() {
+        let test = RoswaalCompiledTest::new(
+            "A".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Wait {
+                duration: "500ms".parse().unwrap(),
+            }],
+        );
+        let code = MaestroCodeGenerator.generate(&test);
+        let expected_yaml = r#"# Generated by Roswaal, do not touch.
+
+appId: ${APP_ID}
+name: "A"
+---
+- launchApp
+# Wait 500 milliseconds
+- extendedWaitUntil:
+    visible: "__roswaal_wait__"
+    timeout: 500
+    optional: true
+"#;
+        assert_eq!(
+            code.files(),
+            &vec![("TestCase.yaml".to_string(), expected_yaml.to_string())]
+        )
+    }
+
+    #[test]
+    fn test_case_flow_retries_steps_with_retries() {
+        let test = RoswaalCompiledTest::new(
+            "A".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Open the menu".to_string(),
+                requirement: "Tap the button".to_string(),
+                retries: Some(2),
+            }],
+        );
+        let code = MaestroCodeGenerator.generate(&test);
+        let expected_yaml = r#"# Generated by Roswaal, do not touch.
+
+appId: ${APP_ID}
+name: "A"
+---
+- launchApp
+# Open the menu
+- retry:
+    maxRetries: 2
+    commands:
+      - runFlow: actions/tapTheButton.yaml
+"#;
+        assert_eq!(
+            code.files()[0],
+            ("TestCase.yaml".to_string(), expected_yaml.to_string())
+        )
+    }
+
+    #[test]
+    fn test_case_flow_runs_action_flows_of_required_tests() {
+        let test = RoswaalCompiledTest::new(
+            "Join an event".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::RequiresTest {
+                    test_name: "Sign in".to_string(),
+                    commands: vec![
+                        RoswaalCompiledTestCommand::BeforeLaunch {
+                            name: "Reset permissions".to_string(),
+                        },
+                        RoswaalCompiledTestCommand::Step {
+                            label: "Step 1".to_string(),
+                            name: "Johnny is signed in".to_string(),
+                            requirement: "Ensure Johnny is signed into his account".to_string(),
+                            retries: None,
+                        },
+                    ],
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Johnny joins an event".to_string(),
+                    requirement: "Join the first event".to_string(),
+                    retries: None,
+                },
+            ],
+        );
+        let code = MaestroCodeGenerator.generate(&test);
+        let expected_yaml = r#"# Generated by Roswaal, do not touch.
+
+appId: ${APP_ID}
+name: "Join an event"
+---
+# Reset permissions
+- runFlow: ../sign-in/actions/resetPermissions.yaml
+- launchApp
+# Requires Test: Sign in
+# Johnny is signed in
+- runFlow: ../sign-in/actions/ensureJohnnyIsSignedIntoHisAccount.yaml
+# Johnny joins an event
+- runFlow: actions/joinTheFirstEvent.yaml
+"#;
+        assert_eq!(
+            code.files()[0],
+            ("TestCase.yaml".to_string(), expected_yaml.to_string())
+        );
+        assert_eq!(code.files().len(), 2)
+    }
+
+    #[test]
+    fn generates_single_action_flow_for_steps_with_the_same_requirement() {
+        let test = RoswaalCompiledTest::new(
+            "A".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Johnny is bored".to_string(),
+                    requirement: "Ensure that Johnny is not bored".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 2".to_string(),
+                    name: "Johnny is bored".to_string(),
+                    requirement: "Ensure that Johnny is not bored".to_string(),
+                    retries: None,
+                },
+            ],
+        );
+        let code = MaestroCodeGenerator.generate(&test);
+        let expected_yaml = "\
+appId: ${APP_ID}
+---
+# Johnny is bored
+- assertTrue:
+    condition: ${false}
+    label: \"TODO\"
+";
+        assert_eq!(
+            code.files()[1..],
+            vec![(
+                "actions/ensureThatJohnnyIsNotBored.yaml".to_string(),
+                expected_yaml.to_string()
+            )]
+        )
+    }
+
+    #[test]
+    fn location_flows_set_the_coordinate_of_each_location() {
+        let oakland = RoswaalLocation::from_str("Oakland, 37.5, -122.25").unwrap();
+        let code = locations_code(&[&oakland]);
+        let expected_yaml = "\
+# Generated by Roswaal, do not touch.
+
+appId: ${APP_ID}
+---
+# Oakland
+- setLocation:
+    latitude: 37.5000000000000000
+    longitude: -122.2500000000000000
+";
+        assert_eq!(
+            code.files(),
+            &vec![(
+                "locations/Oakland.yaml".to_string(),
+                expected_yaml.to_string()
+            )]
+        )
+    }
+}
@@ -1,4 +1,7 @@
 mod constants;
+pub mod formatter;
 pub mod interface;
 pub mod locations;
+pub mod maestro;
+pub mod pipeline;
 pub mod test_case;
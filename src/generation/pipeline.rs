@@ -0,0 +1,197 @@
+use std::{future::Future, path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::language::test::RoswaalCompiledTest;
+
+use super::interface::CodeGenerator;
+
+/// The maximum number of tests whose generated code is read from and written to disk at the same
+/// time.
+///
+/// Each test can touch several files, so generating code for a large batch of tests all at once
+/// can run out of file handles.
+pub const MAX_CONCURRENT_TEST_CODE_SAVES: usize = 8;
+
+/// Generates the code for each of `tests`, merges it with the existing code in the directory
+/// given by `dirpath`, and saves it in that directory.
+///
+/// The code of at most `max_concurrency` tests is saved at a time. The first error that occurs
+/// cancels the saves that have not finished yet. Returns the paths of the saved files.
+pub async fn save_generated_test_code<'t, Generator>(
+    generator: Generator,
+    tests: impl IntoIterator<Item = &'t RoswaalCompiledTest>,
+    dirpath: impl Fn(&str) -> PathBuf,
+    max_concurrency: usize,
+) -> Result<Vec<PathBuf>>
+where
+    Generator: CodeGenerator + Copy + Send + 'static,
+{
+    let generated_code = tests
+        .into_iter()
+        .map(|test| (generator.generate(test), dirpath(test.name())))
+        .collect::<Vec<_>>();
+    let file_paths = generated_code
+        .iter()
+        .flat_map(|(code, dirpath)| code.files().iter().map(|(path, _)| dirpath.join(path)))
+        .collect::<Vec<PathBuf>>();
+    let saves = generated_code
+        .into_iter()
+        .map(move |(code, dirpath)| async move {
+            let existing_code = code.read_existing_in_dir(&dirpath).await?;
+            generator
+                .merge_existing(code, &existing_code)
+                .save_in_dir(&dirpath)
+                .await
+        });
+    run_with_bounded_concurrency(saves, max_concurrency).await?;
+    Ok(file_paths)
+}
+
+/// Runs each of `futures` on its own task with at most `max_concurrency` tasks running at a time,
+/// and waits for all of them to finish.
+///
+/// Futures are only spawned once a slot frees up, so the number of outstanding tasks stays within
+/// the bound. The first error that occurs aborts the remaining tasks.
+pub async fn run_with_bounded_concurrency<F>(
+    futures: impl IntoIterator<Item = F>,
+    max_concurrency: usize,
+) -> Result<()>
+where
+    F: Future<Output = Result<()>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    for future in futures {
+        let permit = semaphore.clone().acquire_owned().await?;
+        tasks.spawn(async move {
+            let result = future.await;
+            drop(permit);
+            result
+        });
+        while let Some(result) = tasks.try_join_next() {
+            result??
+        }
+    }
+    while let Some(result) = tasks.join_next().await {
+        result??
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use anyhow::anyhow;
+    use nanoid::nanoid;
+    use tokio::{fs::read_to_string, time::sleep};
+
+    use crate::{
+        generation::interface::CodeGeneratorKind,
+        language::{ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_at_most_the_max_concurrency_at_a_time() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let futures = (0..20).map(|_| {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            async move {
+                let count = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(count, Ordering::SeqCst);
+                sleep(Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+        run_with_bounded_concurrency(futures, 3).await.unwrap();
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+        assert_eq!(running.load(Ordering::SeqCst), 0)
+    }
+
+    #[tokio::test]
+    async fn fails_with_the_first_error() {
+        let futures = (0..5).map(|i| async move {
+            if i == 2 {
+                Err(anyhow!("Failed {}", i))
+            } else {
+                Ok(())
+            }
+        });
+        let error = run_with_bounded_concurrency(futures, 2).await.unwrap_err();
+        assert_eq!(error.to_string(), "Failed 2")
+    }
+
+    #[tokio::test]
+    async fn saves_generated_code_of_each_test() {
+        let tests_syntax = vec![
+            RoswaalTestSyntax::from("New Test: Hello\nStep 1: A\nRequirement 1: B"),
+            RoswaalTestSyntax::from("New Test: World\nStep 1: C\nRequirement 1: D"),
+        ];
+        let results = RoswaalTestCompilationResults::compile(tests_syntax, &vec![]);
+        let tests = results.tests();
+        let root = env::temp_dir().join(format!("roswaal-{}", nanoid!(10)));
+        let saved_paths = save_generated_test_code(
+            CodeGeneratorKind::Typescript,
+            tests.iter(),
+            |name| root.join(name),
+            MAX_CONCURRENT_TEST_CODE_SAVES,
+        )
+        .await
+        .unwrap();
+        let mut expected_paths = Vec::<PathBuf>::new();
+        for test in tests {
+            let code = CodeGeneratorKind::Typescript.generate(&test);
+            for (path, contents) in code.files() {
+                let path = root.join(test.name()).join(path);
+                let saved = read_to_string(&path).await.unwrap();
+                assert_eq!(&saved, contents);
+                expected_paths.push(path)
+            }
+        }
+        assert_eq!(saved_paths, expected_paths)
+    }
+
+    /// Compares saving the code of 100 tests one at a time against saving it with the default
+    /// bound. Run with `cargo test benchmark_ -- --ignored --nocapture`.
+    #[tokio::test]
+    #[ignore]
+    async fn benchmark_saving_code_of_100_tests() {
+        let tests_syntax = (0..100)
+            .map(|i| format!("New Test: Test {}\nStep 1: A\nRequirement 1: B", i))
+            .collect::<Vec<_>>();
+        let tests_syntax = tests_syntax
+            .iter()
+            .map(|syntax| RoswaalTestSyntax::from(syntax.as_str()))
+            .collect::<Vec<_>>();
+        let results = RoswaalTestCompilationResults::compile(tests_syntax, &vec![]);
+        let tests = results.tests();
+        for max_concurrency in [1, MAX_CONCURRENT_TEST_CODE_SAVES, tests.len()] {
+            let root = env::temp_dir().join(format!("roswaal-{}", nanoid!(10)));
+            let start = Instant::now();
+            save_generated_test_code(
+                CodeGeneratorKind::Typescript,
+                tests.iter(),
+                |name| root.join(name),
+                max_concurrency,
+            )
+            .await
+            .unwrap();
+            println!(
+                "Saved 100 tests with a max concurrency of {} in {:?}",
+                max_concurrency,
+                start.elapsed()
+            );
+        }
+    }
+}
@@ -1,16 +1,13 @@
-use anyhow::Result;
-use tokio::{
-    fs::{create_dir_all, File},
-    io::AsyncWriteExt,
-};
-
 use crate::{
     is_case,
     language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
-    utils::string::ToAsciiCamelCase,
+    utils::string::{to_camel_case_identifier, UniqueIdentifiers, UppercaseFirstAsciiCharacter},
 };
 
-use super::{constants::GENERATED_HEADER, interface::RoswaalTypescriptGenerate};
+use super::{
+    constants::GENERATED_HEADER,
+    interface::{test_directory_name, CodeGenerator, GeneratedTestCode, RoswaalTypescriptGenerate},
+};
 
 /// An output of generating typescript code.
 pub struct TestCaseTypescript {
@@ -18,69 +15,500 @@ pub struct TestCaseTypescript {
     test_action_code: String,
 }
 
-impl TestCaseTypescript {
-    /// Saves this typescript code in files in the specified dirpath.
-    pub async fn save_in_dir(&self, dirpath: &str) -> Result<()> {
-        create_dir_all(dirpath).await?;
-        let mut file = File::create(format!("{}/TestCase.test.ts", dirpath)).await?;
-        file.write(self.test_case_code.as_bytes()).await?;
-        file = File::create(format!("{}/TestActions.ts", dirpath)).await?;
-        file.write(self.test_action_code.as_bytes()).await?;
-        Ok(())
+/// A `CodeGenerator` that outputs a `TestCase.test.ts` and `TestActions.ts` file for each test.
+pub struct TypescriptCodeGenerator;
+
+impl CodeGenerator for TypescriptCodeGenerator {
+    fn generate(&self, test: &RoswaalCompiledTest) -> GeneratedTestCode {
+        let typescript = test.typescript();
+        GeneratedTestCode::new(vec![
+            ("TestCase.test.ts".to_string(), typescript.test_case_code),
+            (
+                TEST_ACTIONS_FILE_NAME.to_string(),
+                typescript.test_action_code,
+            ),
+        ])
+    }
+
+    /// Keeps the existing `TestActions.ts` file, since its actions may have been implemented by
+    /// hand, and only appends the actions and imports that it does not already have.
+    fn merge_existing(
+        &self,
+        generated: GeneratedTestCode,
+        existing: &GeneratedTestCode,
+    ) -> GeneratedTestCode {
+        let Some(existing_actions) = existing.file(TEST_ACTIONS_FILE_NAME) else {
+            return generated;
+        };
+        let files = generated
+            .files()
+            .iter()
+            .map(|(path, code)| {
+                if path == TEST_ACTIONS_FILE_NAME {
+                    (path.clone(), merge_test_actions(existing_actions, code))
+                } else {
+                    (path.clone(), code.clone())
+                }
+            })
+            .collect();
+        GeneratedTestCode::new(files)
+    }
+}
+
+const TEST_ACTIONS_FILE_NAME: &str = "TestActions.ts";
+
+/// Merges a freshly generated `TestActions.ts` file into an `existing` one.
+///
+/// Every export in the existing file is kept as is, and exports from the generated file that do
+/// not exist in the existing file are appended to the end. Import lines from the generated file
+/// that are missing in the existing file are added to the top. Reserved exports (eg.
+/// `beforeLaunch`) also gain the calls of their generated counterpart that they do not already
+/// make, so that new before launch commands and required tests are still run.
+fn merge_test_actions(existing: &str, generated: &str) -> String {
+    let existing_ts = TestActionsTypescript::parse(existing);
+    let generated_ts = TestActionsTypescript::parse(generated);
+    let mut ts = String::new();
+    for line in generated_ts.preamble.lines() {
+        let is_missing_import =
+            line.starts_with("import ") && !existing_ts.preamble.lines().any(|l| l == line);
+        if is_missing_import {
+            ts.push_str(line);
+            ts.push('\n');
+        }
+    }
+    ts.push_str(existing_ts.preamble);
+    for (name, code) in existing_ts.exports.iter() {
+        let generated_code = generated_ts
+            .exports
+            .iter()
+            .find(|(n, _)| n == name && RESERVED_ACTION_NAMES.contains(n))
+            .map(|(_, code)| *code);
+        match generated_code {
+            Some(generated_code) => ts.push_str(&merge_reserved_export(code, generated_code)),
+            None => ts.push_str(code),
+        }
+    }
+    let new_exports = generated_ts
+        .exports
+        .iter()
+        .filter(|(name, _)| !existing_ts.exports.iter().any(|(n, _)| n == name))
+        .map(|(_, code)| code.trim_end())
+        .collect::<Vec<&str>>();
+    if new_exports.is_empty() {
+        return ts;
+    }
+    if !ts.ends_with('\n') {
+        ts.push('\n');
+    }
+    if !ts.ends_with("\n\n") {
+        ts.push('\n');
+    }
+    ts.push_str(&new_exports.join("\n\n"));
+    ts.push('\n');
+    ts
+}
+
+/// Inserts the `await` calls of a `generated` reserved export that are missing in the `existing`
+/// one before the `return` of the existing export, or before its closing brace if it has no
+/// `return`.
+fn merge_reserved_export(existing: &str, generated: &str) -> String {
+    let missing_calls = generated
+        .lines()
+        .filter(|line| line.trim_start().starts_with("await "))
+        .filter(|line| !existing.lines().any(|l| l.trim() == line.trim()))
+        .collect::<Vec<&str>>();
+    if missing_calls.is_empty() {
+        return existing.to_string();
+    }
+    let lines = existing.split_inclusive('\n').collect::<Vec<&str>>();
+    let insertion_index = lines
+        .iter()
+        .rposition(|line| line.trim_start().starts_with("return"))
+        .or_else(|| lines.iter().rposition(|line| line.starts_with('}')))
+        .unwrap_or(lines.len());
+    let mut ts = lines[..insertion_index].concat();
+    for call in missing_calls {
+        ts.push_str(call);
+        ts.push('\n');
+    }
+    ts.push_str(&lines[insertion_index..].concat());
+    ts
+}
+
+/// The top level exports of a `TestActions.ts` file.
+struct TestActionsTypescript<'a> {
+    /// The code before the first export (eg. imports).
+    preamble: &'a str,
+    /// The name and code of each export in the order they appear.
+    exports: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> TestActionsTypescript<'a> {
+    fn parse(ts: &'a str) -> Self {
+        let mut export_starts = Vec::new();
+        let mut offset = 0;
+        for line in ts.split_inclusive('\n') {
+            if let Some(name) = Self::export_name(line) {
+                export_starts.push((offset, name));
+            }
+            offset += line.len();
+        }
+        let preamble_end = export_starts.first().map(|(i, _)| *i).unwrap_or(ts.len());
+        let exports = export_starts
+            .iter()
+            .enumerate()
+            .map(|(i, (start, name))| {
+                let end = export_starts
+                    .get(i + 1)
+                    .map(|(next_start, _)| *next_start)
+                    .unwrap_or(ts.len());
+                (*name, &ts[*start..end])
+            })
+            .collect();
+        Self {
+            preamble: &ts[..preamble_end],
+            exports,
+        }
+    }
+
+    fn export_name(line: &'a str) -> Option<&'a str> {
+        let declaration = line.strip_prefix("export ")?;
+        let declaration = declaration.strip_prefix("async ").unwrap_or(declaration);
+        let declaration = ["const ", "let ", "function "]
+            .iter()
+            .find_map(|keyword| declaration.strip_prefix(keyword))?;
+        let name_end = declaration
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(declaration.len());
+        Some(&declaration[..name_end]).filter(|name| !name.is_empty())
+    }
+}
+
+/// Returns the name of the generated action for a step with the specified requirement.
+pub(super) fn step_action_name(requirement: &str) -> String {
+    // NB: Placeholder braces separate words so that "{{eventName}}" is camel cased as a word.
+    to_camel_case_identifier(requirement)
+}
+
+/// Returns the name that the `TestActions.ts` module of a required test is imported as.
+fn required_test_module_name(test_name: &str) -> String {
+    format!(
+        "{}TestActions",
+        step_action_name(test_name).uppercase_first_ascii_char()
+    )
+}
+
+/// The exports of a `TestActions.ts` file that are not generated from the commands of a test.
+const RESERVED_ACTION_NAMES: [&str; 2] = ["beforeLaunch", "setUserLocation"];
+
+/// The names of the actions that are generated for the commands of a test.
+///
+/// Commands with the same requirement share an action, but different requirements can still camel
+/// case to the same identifier (eg. "Tap the button" and "Tap the button!"). The actions of those
+/// requirements are given numeric suffixes so that `TestActions.ts` never exports the same name
+/// twice.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoswaalActionNames {
+    names: Vec<Option<String>>,
+    collisions: Vec<RoswaalActionNameCollision>,
+}
+
+/// A requirement whose action was renamed because its name was already taken by the action of a
+/// different requirement in the same test.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalActionNameCollision {
+    requirement: String,
+    colliding_requirement: String,
+    renamed_name: String,
+}
+
+impl RoswaalActionNameCollision {
+    pub fn requirement(&self) -> &str {
+        &self.requirement
+    }
+
+    /// Returns the requirement that first took the action name, or the name itself if it is
+    /// reserved by the generated code (eg. `beforeLaunch`).
+    pub fn colliding_requirement(&self) -> &str {
+        &self.colliding_requirement
+    }
+
+    pub fn renamed_name(&self) -> &str {
+        &self.renamed_name
     }
 }
 
+impl RoswaalActionNames {
+    pub fn new(commands: &[RoswaalCompiledTestCommand]) -> Self {
+        let mut identifiers = UniqueIdentifiers::new();
+        for name in RESERVED_ACTION_NAMES {
+            identifiers.unique(name);
+        }
+        let mut actions = Vec::<(String, String, String)>::new();
+        let mut names = Vec::new();
+        let mut collisions = Vec::new();
+        for command in commands {
+            let Some((requirement, name)) = Self::requirement_and_name(command) else {
+                names.push(None);
+                continue;
+            };
+            if let Some((_, _, unique_name)) = actions.iter().find(|(r, _, _)| *r == requirement) {
+                names.push(Some(unique_name.clone()));
+                continue;
+            }
+            let unique_name = identifiers.unique(&name);
+            if unique_name != name {
+                let colliding_requirement = actions
+                    .iter()
+                    .find(|(_, n, _)| *n == name)
+                    .map(|(r, _, _)| r.clone())
+                    .unwrap_or_else(|| name.clone());
+                collisions.push(RoswaalActionNameCollision {
+                    requirement: requirement.clone(),
+                    colliding_requirement,
+                    renamed_name: unique_name.clone(),
+                });
+            }
+            names.push(Some(unique_name.clone()));
+            actions.push((requirement, name, unique_name));
+        }
+        Self { names, collisions }
+    }
+
+    /// Returns the name of the action for the command at `index`, or None if the command does
+    /// not generate an action.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.names.get(index)?.as_deref()
+    }
+
+    pub fn collisions(&self) -> &[RoswaalActionNameCollision] {
+        &self.collisions
+    }
+
+    fn requirement_and_name(command: &RoswaalCompiledTestCommand) -> Option<(String, String)> {
+        match command {
+            RoswaalCompiledTestCommand::Step { requirement, .. }
+            | RoswaalCompiledTestCommand::Verify { requirement, .. } => {
+                Some((requirement.clone(), step_action_name(requirement)))
+            }
+            RoswaalCompiledTestCommand::BeforeLaunch { name } => {
+                Some((name.clone(), step_action_name(name)))
+            }
+            RoswaalCompiledTestCommand::SetLocation { location_name } => Some((
+                format!("Set Location to {}", location_name.raw_name()),
+                format!(
+                    "setLocationTo{}",
+                    location_name.to_ascii_pascal_case_string()
+                ),
+            )),
+            RoswaalCompiledTestCommand::RequiresTest { test_name, .. } => {
+                let requirement = format!("Require {}", test_name);
+                let name = step_action_name(&requirement);
+                Some((requirement, name))
+            }
+            RoswaalCompiledTestCommand::Wait { .. } => None,
+        }
+    }
+}
+
+/// Returns the parameter list of the action for a command that uses `variable_names`, which is
+/// empty unless the command contains placeholders.
+fn action_parameters(variable_names: &[&str]) -> String {
+    if variable_names.is_empty() {
+        return String::new();
+    }
+    let fields = variable_names
+        .iter()
+        .map(|name| format!("{}: string", name))
+        .collect::<Vec<String>>();
+    format!("params: {{ {} }}", fields.join(", "))
+}
+
+/// Returns the action passed to the test case for a command that uses `variable_names`, which
+/// wraps the action in a closure that passes the `params` of the test when the command contains
+/// placeholders.
+fn test_case_action(function_name: &str, variable_names: &[&str]) -> String {
+    if variable_names.is_empty() {
+        format!("TestActions.{}", function_name)
+    } else {
+        format!("() => TestActions.{}(params)", function_name)
+    }
+}
+
+/// Returns the statement that awaits the action named `function_name` of a command from the
+/// `TestActions.ts` module of a required test, or None if the command runs before launch.
+fn required_test_action_call(
+    module_name: &str,
+    command: &RoswaalCompiledTestCommand,
+    function_name: &str,
+) -> Option<String> {
+    match command {
+        RoswaalCompiledTestCommand::Wait { duration } => {
+            return Some(format!(
+                "  await new Promise((resolve) => setTimeout(resolve, {}))\n",
+                duration.millis()
+            ))
+        }
+        RoswaalCompiledTestCommand::BeforeLaunch { .. } => return None,
+        _ => {}
+    }
+    let arguments = if command.variable_names().is_empty() {
+        ""
+    } else {
+        "params"
+    };
+    Some(format!(
+        "  await {}.{}({})\n",
+        module_name, function_name, arguments
+    ))
+}
+
 impl RoswaalTypescriptGenerate<TestCaseTypescript> for RoswaalCompiledTestCommand {
     fn typescript(&self) -> TestCaseTypescript {
+        let action_names = RoswaalActionNames::new(std::slice::from_ref(self));
+        self.typescript_with_action_name(action_names.name(0).unwrap_or_default())
+    }
+}
+
+impl RoswaalCompiledTestCommand {
+    /// Returns the typescript for this command where its action is exported as `function_name`.
+    fn typescript_with_action_name(&self, function_name: &str) -> TestCaseTypescript {
         match self {
             Self::Step {
                 label: _,
                 name,
-                requirement,
+                requirement: _,
+                retries,
             } => {
-                let mut function_name = requirement.to_ascii_camel_case();
-                function_name.retain(|c| !r#"()$@#*,".;:'!"#.contains(c));
+                let variable_names = self.variable_names();
+                let options = retries
+                    .map(|retries| format!(", {{ retries: {} }}", retries))
+                    .unwrap_or_default();
                 TestCaseTypescript {
                     test_case_code: format!(
                         "\
   // {}
-  testCase.appendAction(TestActions.{})
+  testCase.appendAction({}{})
 ",
-                        name, function_name
+                        name,
+                        test_case_action(function_name, &variable_names),
+                        options
                     ),
                     test_action_code: format!(
                         "\
-export const {} = async () => {{
+export const {} = async ({}) => {{
   // {}
   throw new Error(\"TODO\")
 }}
 ",
-                        function_name, name
+                        function_name,
+                        action_parameters(&variable_names),
+                        name
                     ),
                 }
             }
-            Self::SetLocation { location_name } => {
-                let function_name = format!(
-                    "setLocationTo{}",
+            Self::SetLocation { location_name } => TestCaseTypescript {
+                test_case_code: format!(
+                    "\
+  // Set Location to {}
+  testCase.appendAction(TestActions.{})
+",
+                    location_name.raw_name(),
+                    function_name
+                ),
+                test_action_code: format!(
+                    "\
+export const {} = async () => {{
+  await setUserLocation(TestLocations.{})
+}}
+",
+                    function_name,
                     location_name.to_ascii_pascal_case_string()
-                );
+                ),
+            },
+            Self::Verify {
+                label: _,
+                name,
+                requirement: _,
+            } => {
+                let variable_names = self.variable_names();
                 TestCaseTypescript {
                     test_case_code: format!(
                         "\
-  // Set Location to {}
-  testCase.appendAction(TestActions.{})
+  // Verify {}
+  testCase.appendVerification({})
 ",
-                        location_name.raw_name(),
-                        function_name
+                        name,
+                        test_case_action(function_name, &variable_names)
                     ),
                     test_action_code: format!(
                         "\
+export const {} = async ({}) => {{
+  // Verify {}
+  throw new Error(\"TODO\")
+}}
+",
+                        function_name,
+                        action_parameters(&variable_names),
+                        name
+                    ),
+                }
+            }
+            Self::BeforeLaunch { name } => TestCaseTypescript {
+                test_case_code: String::new(),
+                test_action_code: format!(
+                    "\
 export const {} = async () => {{
-  await setUserLocation(TestLocations.{})
+  // {}
+  throw new Error(\"TODO\")
 }}
+",
+                    function_name, name
+                ),
+            },
+            Self::Wait { duration } => TestCaseTypescript {
+                test_case_code: format!(
+                    "\
+  // Wait {}
+  testCase.appendAction(async () => await testCase.wait({}))
+",
+                    duration,
+                    duration.millis()
+                ),
+                test_action_code: String::new(),
+            },
+            Self::RequiresTest {
+                test_name,
+                commands,
+            } => {
+                let module_name = required_test_module_name(test_name);
+                let variable_names = self.variable_names();
+                let required_action_names = RoswaalActionNames::new(commands);
+                let calls = commands
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, c)| {
+                        let function_name = required_action_names.name(i).unwrap_or_default();
+                        required_test_action_call(&module_name, c, function_name)
+                    })
+                    .collect::<String>();
+                TestCaseTypescript {
+                    test_case_code: format!(
+                        "\
+  // Requires Test: {}
+  testCase.appendAction({})
+",
+                        test_name,
+                        test_case_action(function_name, &variable_names)
+                    ),
+                    test_action_code: format!(
+                        "\
+export const {} = async ({}) => {{
+{}}}
 ",
                         function_name,
-                        location_name.to_ascii_pascal_case_string()
+                        action_parameters(&variable_names),
+                        calls
                     ),
                 }
             }
@@ -98,6 +526,10 @@ export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
   return {}
 }
 ";
+const TEST_ACTIONS_BEFORE_LAUNCH_FUNCTION_START: &str = "\
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+";
+const TEST_ACTIONS_BEFORE_LAUNCH_FUNCTION_END: &str = "  return {}\n}\n";
 const TEST_CASE_IMPORTS: &str = "\
 import * as TestActions from \"./TestActions\"
 import { launchApp } from \"../Launch\"
@@ -135,12 +567,20 @@ impl RoswaalCompiledTest {
     fn test_case_typescript(&self) -> String {
         let mut ts = GENERATED_HEADER.to_string();
         ts.push_str(TEST_CASE_IMPORTS);
+        ts.push_str(&self.platforms_typescript());
+        ts.push_str(&self.params_typescript());
         ts.push_str(&test_case_test_block_start(self.name()));
         ts.push_str(TEST_CASE_APPEND_ACTION_SPACING);
+        let action_names = RoswaalActionNames::new(self.commands());
         for code in self
             .commands()
             .iter()
-            .map(|c| c.typescript().test_case_code)
+            .enumerate()
+            .map(|(i, c)| {
+                c.typescript_with_action_name(action_names.name(i).unwrap_or_default())
+                    .test_case_code
+            })
+            .filter(|code| !code.is_empty())
         {
             ts.push_str(&code);
             ts.push_str(TEST_CASE_APPEND_ACTION_SPACING);
@@ -159,21 +599,108 @@ impl RoswaalCompiledTest {
         if has_location_command {
             ts.push_str(TEST_ACTIONS_LOCATION_IMPORT)
         }
+        for test_name in self.required_test_names() {
+            ts.push_str(&format!(
+                "import * as {} from \"../{}/TestActions\"\n",
+                required_test_module_name(test_name),
+                test_directory_name(test_name)
+            ))
+        }
         ts.push_str("\n");
-        ts.push_str(TEST_ACTIONS_BEFORE_LAUNCH_FUNCTION);
+        let action_names = RoswaalActionNames::new(self.commands());
+        ts.push_str(&self.before_launch_function_typescript(&action_names));
         ts.push_str("\n");
-        self.commands()
+        let mut exported_names = Vec::<&str>::new();
+        let mut action_codes = Vec::<String>::new();
+        for (i, command) in self.commands().iter().enumerate() {
+            let name = action_names.name(i).unwrap_or_default();
+            if !name.is_empty() && exported_names.contains(&name) {
+                continue;
+            }
+            exported_names.push(name);
+            let code = command.typescript_with_action_name(name).test_action_code;
+            if !code.is_empty() {
+                action_codes.push(code);
+            }
+        }
+        ts.push_str(&action_codes.join("\n"));
+        ts
+    }
+
+    /// Returns the `platforms` export that the test runner reads to skip this test on other
+    /// platforms, or an empty string if this test runs on every platform.
+    fn platforms_typescript(&self) -> String {
+        if self.platforms().is_empty() {
+            return String::new();
+        }
+        let platforms = self
+            .platforms()
+            .iter()
+            .map(|platform| format!("\"{}\"", platform.as_str()))
+            .collect::<Vec<String>>();
+        format!("export const platforms = [{}]\n\n", platforms.join(", "))
+    }
+
+    /// Returns the `TestParams` type and the `params` that the actions of this test are called
+    /// with, or an empty string if this test does not declare any variables.
+    fn params_typescript(&self) -> String {
+        if self.variables().is_empty() {
+            return String::new();
+        }
+        let mut ts = "export type TestParams = {\n".to_string();
+        for variable in self.variables() {
+            ts.push_str(&format!("  {}: string\n", variable.name()));
+        }
+        ts.push_str("}\n\nexport const params: TestParams = {\n");
+        for variable in self.variables() {
+            let escaped_value = variable.value().replace('\\', r"\\").replace('"', r#"\""#);
+            ts.push_str(&format!("  {}: \"{}\",\n", variable.name(), escaped_value));
+        }
+        ts.push_str("}\n\n");
+        ts
+    }
+
+    /// Returns the names of the tests required by this test without duplicates.
+    fn required_test_names(&self) -> Vec<&str> {
+        let mut names = Vec::<&str>::new();
+        for command in self.commands() {
+            if let RoswaalCompiledTestCommand::RequiresTest { test_name, .. } = command {
+                if !names.contains(&test_name.as_str()) {
+                    names.push(test_name);
+                }
+            }
+        }
+        names
+    }
+
+    /// Returns the `beforeLaunch` function, which calls the action for each before launch
+    /// command in order, or is left as a stub if the test has no before launch commands.
+    ///
+    /// The `beforeLaunch` function of each required test is also called in order.
+    fn before_launch_function_typescript(&self, action_names: &RoswaalActionNames) -> String {
+        let calls = self
+            .commands()
             .iter()
             .enumerate()
-            .fold(ts, |mut acc, (i, command)| {
-                let suffix = if i < self.commands().len() - 1 {
-                    "\n"
-                } else {
-                    ""
-                };
-                acc.push_str(&(command.typescript().test_action_code + suffix));
-                return acc;
+            .filter_map(|(i, c)| match c {
+                RoswaalCompiledTestCommand::BeforeLaunch { .. } => Some(format!(
+                    "  await {}()\n",
+                    action_names.name(i).unwrap_or_default()
+                )),
+                RoswaalCompiledTestCommand::RequiresTest { test_name, .. } => Some(format!(
+                    "  await {}.beforeLaunch()\n",
+                    required_test_module_name(test_name)
+                )),
+                _ => None,
             })
+            .collect::<Vec<String>>();
+        if calls.is_empty() {
+            return TEST_ACTIONS_BEFORE_LAUNCH_FUNCTION.to_string();
+        }
+        let mut ts = TEST_ACTIONS_BEFORE_LAUNCH_FUNCTION_START.to_string();
+        ts.push_str(&calls.concat());
+        ts.push_str(TEST_ACTIONS_BEFORE_LAUNCH_FUNCTION_END);
+        ts
     }
 }
 
@@ -181,7 +708,13 @@ impl RoswaalCompiledTest {
 mod tests {
     use std::str::FromStr;
 
-    use crate::location::name::RoswaalLocationName;
+    use crate::{
+        language::{
+            duration::RoswaalWaitDuration, platform::RoswaalTestPlatform,
+            variable::RoswaalTestVariable,
+        },
+        location::name::RoswaalLocationName,
+    };
 
     use super::*;
 
@@ -191,6 +724,7 @@ mod tests {
             label: "Step 1".to_string(),
             name: String::from("Anna is about to arrive at an event"),
             requirement: String::from("Mark Anna as being present at an event"),
+            retries: None,
         };
         let ts = command.typescript();
         let expected_ts = "\
@@ -208,6 +742,7 @@ export const markAnnaAsBeingPresentAtAnEvent = async () => {
             label: "Step 1".to_string(),
             name: String::from("Anna is about to arrive at an event"),
             requirement: String::from("Mark Anna as being present at an event"),
+            retries: None,
         };
         let ts = command.typescript();
         let expected_ts = "\
@@ -217,6 +752,40 @@ export const markAnnaAsBeingPresentAtAnEvent = async () => {
         assert_eq!(ts.test_case_code, expected_ts.to_string())
     }
 
+    #[test]
+    fn test_step_command_with_retries_test_case_typescript() {
+        let command = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: String::from("Anna is about to arrive at an event"),
+            requirement: String::from("Mark Anna as being present at an event"),
+            retries: Some(2),
+        };
+        let ts = command.typescript();
+        let expected_ts = "\
+  // Anna is about to arrive at an event
+  testCase.appendAction(TestActions.markAnnaAsBeingPresentAtAnEvent, { retries: 2 })
+";
+        assert_eq!(ts.test_case_code, expected_ts.to_string())
+    }
+
+    #[test]
+    fn test_step_command_with_accented_requirement_action_typescript() {
+        let command = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: String::from("Élodie is about to arrive at an event"),
+            requirement: String::from("Mark Élodie as présent 🎉"),
+            retries: None,
+        };
+        let ts = command.typescript();
+        let expected_ts = "\
+export const markElodieAsPresent = async () => {
+  // Élodie is about to arrive at an event
+  throw new Error(\"TODO\")
+}
+";
+        assert_eq!(ts.test_action_code, expected_ts.to_string())
+    }
+
     #[test]
     fn test_set_location_command_action_typescript() {
         let command = RoswaalCompiledTestCommand::SetLocation {
@@ -244,17 +813,100 @@ export const setLocationToSanFrancisco = async () => {
         assert_eq!(ts.test_case_code, expected_ts.to_string())
     }
 
+    #[test]
+    fn test_wait_command_test_case_typescript() {
+        let command = RoswaalCompiledTestCommand::Wait {
+            duration: "1.5 seconds".parse().unwrap(),
+        };
+        let ts = command.typescript();
+        let expected_ts = "\
+  // Wait 1.5 seconds
+  testCase.appendAction(async () => await testCase.wait(1500))
+";
+        assert_eq!(ts.test_case_code, expected_ts.to_string());
+        assert!(ts.test_action_code.is_empty())
+    }
+
+    #[test]
+    fn test_verify_command_typescript() {
+        let command = RoswaalCompiledTestCommand::Verify {
+            label: "Verify 1".to_string(),
+            name: String::from("Anna is marked as present"),
+            requirement: String::from("Check that Anna has a checkmark"),
+        };
+        let ts = command.typescript();
+        let expected_test_case_ts = "\
+  // Verify Anna is marked as present
+  testCase.appendVerification(TestActions.checkThatAnnaHasACheckmark)
+";
+        let expected_action_ts = "\
+export const checkThatAnnaHasACheckmark = async () => {
+  // Verify Anna is marked as present
+  throw new Error(\"TODO\")
+}
+";
+        assert_eq!(ts.test_case_code, expected_test_case_ts.to_string());
+        assert_eq!(ts.test_action_code, expected_action_ts.to_string())
+    }
+
+    #[test]
+    fn test_generate_test_actions_command_typescript_steps_and_waits() {
+        let step1 = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let wait = RoswaalCompiledTestCommand::Wait {
+            duration: "5 seconds".parse().unwrap(),
+        };
+        let step2 = RoswaalCompiledTestCommand::Step {
+            label: "Step 2".to_string(),
+            name: "Johnny is bored".to_string(),
+            requirement: "Ensure that Johnny is not bored".to_string(),
+            retries: None,
+        };
+        let ts = RoswaalCompiledTest::new(
+            "A".to_string(),
+            None,
+            vec![step1, wait.clone(), step2, wait],
+        )
+        .typescript();
+        let expected_ts = "\
+import { TestAppLaunchConfig } from \"../Launch\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  // Perform any setup work in here, (setting location, reseting device
+  // permissions, etc.)
+  return {}
+}
+
+export const ensureJohnnyIsSignedIntoHisAccount = async () => {
+  // Johnny is signed in
+  throw new Error(\"TODO\")
+}
+
+export const ensureThatJohnnyIsNotBored = async () => {
+  // Johnny is bored
+  throw new Error(\"TODO\")
+}
+";
+        assert_eq!(ts.test_action_code, expected_ts.to_string())
+    }
+
     #[test]
     fn test_generate_test_actions_command_typescript_only_steps() {
         let step1 = RoswaalCompiledTestCommand::Step {
             label: "Step 1".to_string(),
             name: "Johnny is signed in".to_string(),
             requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
         };
         let step2 = RoswaalCompiledTestCommand::Step {
             label: "Step 2".to_string(),
             name: "Johnny is bored".to_string(),
             requirement: "Ensure that Johnny is not bored".to_string(),
+            retries: None,
         };
         let ts = RoswaalCompiledTest::new("A".to_string(), None, vec![step1, step2]).typescript();
         let expected_ts = "\
@@ -279,6 +931,106 @@ export const ensureThatJohnnyIsNotBored = async () => {
         assert_eq!(ts.test_action_code, expected_ts.to_string())
     }
 
+    #[test]
+    fn test_generate_typescript_with_colliding_requirements() {
+        let step1 = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny taps the button".to_string(),
+            requirement: "Tap the button".to_string(),
+            retries: None,
+        };
+        let step2 = RoswaalCompiledTestCommand::Step {
+            label: "Step 2".to_string(),
+            name: "Johnny taps the button again".to_string(),
+            requirement: "Tap the button!".to_string(),
+            retries: None,
+        };
+        let verify = RoswaalCompiledTestCommand::Verify {
+            label: "Step 3".to_string(),
+            name: "The button was tapped".to_string(),
+            requirement: "Tap the button".to_string(),
+        };
+        let ts = RoswaalCompiledTest::new("A".to_string(), None, vec![step1, step2, verify])
+            .typescript();
+        let expected_test_case_ts = "\
+  // Johnny taps the button
+  testCase.appendAction(TestActions.tapTheButton)
+  // Johnny taps the button again
+  testCase.appendAction(TestActions.tapTheButton2)
+  // Verify The button was tapped
+  testCase.appendVerification(TestActions.tapTheButton)
+";
+        assert!(ts.test_case_code.contains(expected_test_case_ts));
+        let expected_action_ts = "\
+export const tapTheButton = async () => {
+  // Johnny taps the button
+  throw new Error(\"TODO\")
+}
+
+export const tapTheButton2 = async () => {
+  // Johnny taps the button again
+  throw new Error(\"TODO\")
+}
+";
+        assert!(ts.test_action_code.ends_with(expected_action_ts))
+    }
+
+    #[test]
+    fn test_action_names_report_collisions_between_different_requirements() {
+        let commands = vec![
+            RoswaalCompiledTestCommand::BeforeLaunch {
+                name: "Before launch".to_string(),
+            },
+            RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "Sign in".to_string(),
+                retries: None,
+            },
+            RoswaalCompiledTestCommand::Wait {
+                duration: "1 second".parse().unwrap(),
+            },
+            RoswaalCompiledTestCommand::Step {
+                label: "Step 2".to_string(),
+                name: "B".to_string(),
+                requirement: "Sign-in".to_string(),
+                retries: None,
+            },
+            RoswaalCompiledTestCommand::Step {
+                label: "Step 3".to_string(),
+                name: "C".to_string(),
+                requirement: "Sign in".to_string(),
+                retries: None,
+            },
+        ];
+        let action_names = RoswaalActionNames::new(&commands);
+        let names = (0..commands.len())
+            .map(|i| action_names.name(i))
+            .collect::<Vec<Option<&str>>>();
+        assert_eq!(
+            names,
+            vec![
+                Some("beforeLaunch2"),
+                Some("signIn"),
+                None,
+                Some("signIn2"),
+                Some("signIn")
+            ]
+        );
+        let collisions = action_names
+            .collisions()
+            .iter()
+            .map(|c| (c.requirement(), c.colliding_requirement(), c.renamed_name()))
+            .collect::<Vec<(&str, &str, &str)>>();
+        assert_eq!(
+            collisions,
+            vec![
+                ("Before launch", "beforeLaunch", "beforeLaunch2"),
+                ("Sign-in", "Sign in", "signIn2")
+            ]
+        )
+    }
+
     #[test]
     fn test_generate_test_actions_command_typescript_steps_and_location_changes() {
         let command1 = RoswaalCompiledTestCommand::Step {
@@ -286,6 +1038,7 @@ export const ensureThatJohnnyIsNotBored = async () => {
             name: "Johnny is signed in".to_string(),
             requirement: "Ensure Johnny is signed into his account,,,, and is (*$)(*)($# alive"
                 .to_string(),
+            retries: None,
         };
         let command2 = RoswaalCompiledTestCommand::SetLocation {
             location_name: RoswaalLocationName::from_str("Oakland").unwrap(),
@@ -321,11 +1074,13 @@ export const setLocationToOakland = async () => {
             name: "Johnny is signed in".to_string(),
             requirement: "Ensure Johnny is signed into his account,,,, and is !!!!(*$)(*)($# alive"
                 .to_string(),
+            retries: None,
         };
         let step2 = RoswaalCompiledTestCommand::Step {
             label: "Step 2".to_string(),
             name: "Johnny is bored".to_string(),
             requirement: "Ensure that Johnny is not bored".to_string(),
+            retries: None,
         };
         let ts = RoswaalCompiledTest::new("B".to_string(), None, vec![step1, step2]).typescript();
         let expected_ts = "\
@@ -354,6 +1109,7 @@ test(\"B\", async () => {
             label: "Step 1".to_string(),
             name: "Johnny is signed in".to_string(),
             requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
         };
         let command2 = RoswaalCompiledTestCommand::SetLocation {
             location_name: RoswaalLocationName::from_str("Oakland").unwrap(),
@@ -379,4 +1135,424 @@ test("I am \"Bob\"", async () => {
 "#;
         assert_eq!(ts.test_case_code, expected_ts.to_string())
     }
+
+    #[test]
+    fn test_generate_test_case_typescript_with_platforms() {
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let ts = RoswaalCompiledTest::new("A".to_string(), None, vec![step])
+            .with_platforms(vec![RoswaalTestPlatform::Ios, RoswaalTestPlatform::Android])
+            .typescript();
+        let expected_ts = r#"// Generated by Roswaal, do not touch.
+
+import * as TestActions from "./TestActions"
+import { launchApp } from "../Launch"
+import { RoswaalTestCase } from "../TestCase"
+import { roswaalClient } from "../Client"
+
+export const platforms = ["ios", "android"]
+
+test("A", async () => {
+  const testCase = new RoswaalTestCase("A", TestActions.beforeLaunch)
+  // Johnny is signed in
+  testCase.appendAction(TestActions.ensureJohnnyIsSignedIntoHisAccount)
+  await roswaalClient.run(testCase)
+})
+"#;
+        assert_eq!(ts.test_case_code, expected_ts.to_string())
+    }
+
+    #[test]
+    fn test_generate_typescript_with_before_launch_commands() {
+        let reset = RoswaalCompiledTestCommand::BeforeLaunch {
+            name: "Reset permissions".to_string(),
+        };
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let sign_out = RoswaalCompiledTestCommand::BeforeLaunch {
+            name: "Sign out".to_string(),
+        };
+        let ts = RoswaalCompiledTest::new("A".to_string(), None, vec![reset, step, sign_out])
+            .typescript();
+        let expected_test_case_ts = r#"// Generated by Roswaal, do not touch.
+
+import * as TestActions from "./TestActions"
+import { launchApp } from "../Launch"
+import { RoswaalTestCase } from "../TestCase"
+import { roswaalClient } from "../Client"
+
+test("A", async () => {
+  const testCase = new RoswaalTestCase("A", TestActions.beforeLaunch)
+  // Johnny is signed in
+  testCase.appendAction(TestActions.ensureJohnnyIsSignedIntoHisAccount)
+  await roswaalClient.run(testCase)
+})
+"#;
+        let expected_test_actions_ts = "\
+import { TestAppLaunchConfig } from \"../Launch\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  await resetPermissions()
+  await signOut()
+  return {}
+}
+
+export const resetPermissions = async () => {
+  // Reset permissions
+  throw new Error(\"TODO\")
+}
+
+export const ensureJohnnyIsSignedIntoHisAccount = async () => {
+  // Johnny is signed in
+  throw new Error(\"TODO\")
+}
+
+export const signOut = async () => {
+  // Sign out
+  throw new Error(\"TODO\")
+}
+";
+        assert_eq!(ts.test_case_code, expected_test_case_ts.to_string());
+        assert_eq!(ts.test_action_code, expected_test_actions_ts.to_string())
+    }
+
+    #[test]
+    fn test_generate_typescript_with_required_tests() {
+        let sign_in = RoswaalCompiledTestCommand::RequiresTest {
+            test_name: "Sign in".to_string(),
+            commands: vec![
+                RoswaalCompiledTestCommand::BeforeLaunch {
+                    name: "Reset permissions".to_string(),
+                },
+                RoswaalCompiledTestCommand::RequiresTest {
+                    test_name: "Open the app".to_string(),
+                    commands: vec![],
+                },
+                RoswaalCompiledTestCommand::SetLocation {
+                    location_name: RoswaalLocationName::from_str("Oakland").unwrap(),
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Johnny is signed in".to_string(),
+                    requirement: "Ensure Johnny is signed into his account".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Wait {
+                    duration: RoswaalWaitDuration::from_str("2 seconds").unwrap(),
+                },
+                RoswaalCompiledTestCommand::Verify {
+                    label: "Verify 1".to_string(),
+                    name: "Johnny sees the home screen".to_string(),
+                    requirement: "Check the home screen is visible".to_string(),
+                },
+            ],
+        };
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny joins an event".to_string(),
+            requirement: "Join the first event".to_string(),
+            retries: None,
+        };
+        let ts = RoswaalCompiledTest::new("A".to_string(), None, vec![sign_in, step]).typescript();
+        let expected_test_case_ts = r#"// Generated by Roswaal, do not touch.
+
+import * as TestActions from "./TestActions"
+import { launchApp } from "../Launch"
+import { RoswaalTestCase } from "../TestCase"
+import { roswaalClient } from "../Client"
+
+test("A", async () => {
+  const testCase = new RoswaalTestCase("A", TestActions.beforeLaunch)
+  // Requires Test: Sign in
+  testCase.appendAction(TestActions.requireSignIn)
+  // Johnny joins an event
+  testCase.appendAction(TestActions.joinTheFirstEvent)
+  await roswaalClient.run(testCase)
+})
+"#;
+        let expected_test_actions_ts = r#"import { TestAppLaunchConfig } from "../Launch"
+import * as SignInTestActions from "../sign-in/TestActions"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  await SignInTestActions.beforeLaunch()
+  return {}
+}
+
+export const requireSignIn = async () => {
+  await SignInTestActions.requireOpenTheApp()
+  await SignInTestActions.setLocationToOakland()
+  await SignInTestActions.ensureJohnnyIsSignedIntoHisAccount()
+  await new Promise((resolve) => setTimeout(resolve, 2000))
+  await SignInTestActions.checkTheHomeScreenIsVisible()
+}
+
+export const joinTheFirstEvent = async () => {
+  // Johnny joins an event
+  throw new Error("TODO")
+}
+"#;
+        assert_eq!(ts.test_case_code, expected_test_case_ts.to_string());
+        assert_eq!(ts.test_action_code, expected_test_actions_ts.to_string())
+    }
+
+    #[test]
+    fn test_generate_typescript_with_variables() {
+        let sign_in = RoswaalCompiledTestCommand::RequiresTest {
+            test_name: "Sign in".to_string(),
+            commands: vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "{{host}} is signed in".to_string(),
+                    requirement: "Sign in as {{host}}".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 2".to_string(),
+                    name: "The home screen is open".to_string(),
+                    requirement: "Open the home screen".to_string(),
+                    retries: None,
+                },
+            ],
+        };
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Join {{ eventName }}".to_string(),
+            requirement: "Tap join on {{eventName}} hosted by {{host}}".to_string(),
+            retries: Some(2),
+        };
+        let verify = RoswaalCompiledTestCommand::Verify {
+            label: "Verify 1".to_string(),
+            name: "{{eventName}} is joined".to_string(),
+            requirement: "Check that {{eventName}} is joined".to_string(),
+        };
+        let ts = RoswaalCompiledTest::new("A".to_string(), None, vec![sign_in, step, verify])
+            .with_variables(vec![
+                RoswaalTestVariable::new("eventName", "Pickup \"Basketball\""),
+                RoswaalTestVariable::new("host", "Roswaal"),
+            ])
+            .typescript();
+        let expected_test_case_ts = r#"// Generated by Roswaal, do not touch.
+
+import * as TestActions from "./TestActions"
+import { launchApp } from "../Launch"
+import { RoswaalTestCase } from "../TestCase"
+import { roswaalClient } from "../Client"
+
+export type TestParams = {
+  eventName: string
+  host: string
+}
+
+export const params: TestParams = {
+  eventName: "Pickup \"Basketball\"",
+  host: "Roswaal",
+}
+
+test("A", async () => {
+  const testCase = new RoswaalTestCase("A", TestActions.beforeLaunch)
+  // Requires Test: Sign in
+  testCase.appendAction(() => TestActions.requireSignIn(params))
+  // Join {{ eventName }}
+  testCase.appendAction(() => TestActions.tapJoinOnEventnameHostedByHost(params), { retries: 2 })
+  // Verify {{eventName}} is joined
+  testCase.appendVerification(() => TestActions.checkThatEventnameIsJoined(params))
+  await roswaalClient.run(testCase)
+})
+"#;
+        let expected_test_actions_ts = r#"import { TestAppLaunchConfig } from "../Launch"
+import * as SignInTestActions from "../sign-in/TestActions"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  await SignInTestActions.beforeLaunch()
+  return {}
+}
+
+export const requireSignIn = async (params: { host: string }) => {
+  await SignInTestActions.signInAsHost(params)
+  await SignInTestActions.openTheHomeScreen()
+}
+
+export const tapJoinOnEventnameHostedByHost = async (params: { eventName: string, host: string }) => {
+  // Join {{ eventName }}
+  throw new Error("TODO")
+}
+
+export const checkThatEventnameIsJoined = async (params: { eventName: string }) => {
+  // Verify {{eventName}} is joined
+  throw new Error("TODO")
+}
+"#;
+        assert_eq!(ts.test_case_code, expected_test_case_ts.to_string());
+        assert_eq!(ts.test_action_code, expected_test_actions_ts.to_string())
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_test_actions_and_appends_new_ones() {
+        let step1 = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let step2 = RoswaalCompiledTestCommand::Step {
+            label: "Step 2".to_string(),
+            name: "Johnny is bored".to_string(),
+            requirement: "Ensure that Johnny is not bored".to_string(),
+            retries: None,
+        };
+        let set_location = RoswaalCompiledTestCommand::SetLocation {
+            location_name: RoswaalLocationName::from_str("Oakland").unwrap(),
+        };
+        let existing_ts = "\
+import { TestAppLaunchConfig } from \"../Launch\"
+import { signIn } from \"../Auth\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  return { isSignedOut: true }
+}
+
+export const ensureJohnnyIsSignedIntoHisAccount = async () => {
+  // Johnny is signed in
+  await signIn(\"johnny\")
+}
+
+export async function helper() {
+  return 1
+}
+";
+        let existing = GeneratedTestCode::new(vec![(
+            "TestActions.ts".to_string(),
+            existing_ts.to_string(),
+        )]);
+        let test =
+            RoswaalCompiledTest::new("A".to_string(), None, vec![step1, step2, set_location]);
+        let generated = TypescriptCodeGenerator.generate(&test);
+        let merged = TypescriptCodeGenerator.merge_existing(generated.clone(), &existing);
+        let expected_ts = "\
+import { TestLocations, setUserLocation } from \"../Locations\"
+import { TestAppLaunchConfig } from \"../Launch\"
+import { signIn } from \"../Auth\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  return { isSignedOut: true }
+}
+
+export const ensureJohnnyIsSignedIntoHisAccount = async () => {
+  // Johnny is signed in
+  await signIn(\"johnny\")
+}
+
+export async function helper() {
+  return 1
+}
+
+export const ensureThatJohnnyIsNotBored = async () => {
+  // Johnny is bored
+  throw new Error(\"TODO\")
+}
+
+export const setLocationToOakland = async () => {
+  await setUserLocation(TestLocations.Oakland)
+}
+";
+        assert_eq!(merged.file("TestActions.ts"), Some(expected_ts));
+        assert_eq!(
+            merged.file("TestCase.test.ts"),
+            generated.file("TestCase.test.ts")
+        )
+    }
+
+    #[test]
+    fn test_merge_without_new_test_actions_keeps_existing_file() {
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let test = RoswaalCompiledTest::new("A".to_string(), None, vec![step]);
+        let existing_ts = test
+            .typescript()
+            .test_action_code
+            .replace("throw new Error(\"TODO\")", "await signIn()");
+        let existing =
+            GeneratedTestCode::new(vec![("TestActions.ts".to_string(), existing_ts.clone())]);
+        let merged = TypescriptCodeGenerator
+            .merge_existing(TypescriptCodeGenerator.generate(&test), &existing);
+        assert_eq!(merged.file("TestActions.ts"), Some(existing_ts.as_str()))
+    }
+
+    #[test]
+    fn test_merge_calls_new_before_launch_actions_from_existing_before_launch() {
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let before_launch = RoswaalCompiledTestCommand::BeforeLaunch {
+            name: "Reset location permissions".to_string(),
+        };
+        let existing_ts = "\
+import { TestAppLaunchConfig } from \"../Launch\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  return { isSignedOut: true }
+}
+
+export const ensureJohnnyIsSignedIntoHisAccount = async () => {
+  // Johnny is signed in
+  await signIn(\"johnny\")
+}
+";
+        let existing = GeneratedTestCode::new(vec![(
+            "TestActions.ts".to_string(),
+            existing_ts.to_string(),
+        )]);
+        let test = RoswaalCompiledTest::new("A".to_string(), None, vec![before_launch, step]);
+        let merged = TypescriptCodeGenerator
+            .merge_existing(TypescriptCodeGenerator.generate(&test), &existing);
+        let expected_ts = "\
+import { TestAppLaunchConfig } from \"../Launch\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  await resetLocationPermissions()
+  return { isSignedOut: true }
+}
+
+export const ensureJohnnyIsSignedIntoHisAccount = async () => {
+  // Johnny is signed in
+  await signIn(\"johnny\")
+}
+
+export const resetLocationPermissions = async () => {
+  // Reset location permissions
+  throw new Error(\"TODO\")
+}
+";
+        assert_eq!(merged.file("TestActions.ts"), Some(expected_ts))
+    }
+
+    #[test]
+    fn test_merge_without_existing_test_actions_uses_generated_code() {
+        let step = RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Johnny is signed in".to_string(),
+            requirement: "Ensure Johnny is signed into his account".to_string(),
+            retries: None,
+        };
+        let test = RoswaalCompiledTest::new("A".to_string(), None, vec![step]);
+        let generated = TypescriptCodeGenerator.generate(&test);
+        let merged = TypescriptCodeGenerator
+            .merge_existing(generated.clone(), &GeneratedTestCode::new(vec![]));
+        assert_eq!(merged, generated)
+    }
 }
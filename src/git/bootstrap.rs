@@ -0,0 +1,165 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use anyhow::Result;
+use git2::{build::RepoBuilder, Cred, FetchOptions, RemoteCallbacks};
+use log::info;
+use tokio::{fs::try_exists, task::spawn_blocking};
+
+use crate::utils::stage::RoswaalOperationStage;
+
+use super::{
+    metadata::{RoswaalGitClientKind, RoswaalGitRepositoryMetadata},
+    process::clone_with_git_binary,
+};
+
+/// Clones the repository described by `metadata` into its root directory if the directory does
+/// not exist yet, so that deployments do not need to clone it by hand before starting the server.
+///
+/// Returns true if the repository was cloned.
+pub async fn clone_if_missing(metadata: &RoswaalGitRepositoryMetadata) -> Result<bool> {
+    let path = metadata.relative_path("");
+    if try_exists(&path).await? {
+        return Ok(false);
+    }
+    let url = metadata
+        .clone_url()
+        .ok_or_else(|| RepositoryNotClonedError(path.clone()))?
+        .to_string();
+    info!(
+        "{} does not exist, cloning it from {}.",
+        path.display(),
+        url
+    );
+    RoswaalOperationStage::Cloning.report();
+    let start = Instant::now();
+    match metadata.client_kind() {
+        RoswaalGitClientKind::LibGit2 => {
+            let ssh_private_key_path = metadata.ssh_private_key_path();
+            let clone_path = path.clone();
+            let clone_url = url.clone();
+            spawn_blocking(move || {
+                clone_with_libgit2(&clone_url, &clone_path, &ssh_private_key_path)
+            })
+            .await??
+        }
+        RoswaalGitClientKind::Process => clone_with_git_binary(&url, &path).await?,
+    }
+    info!(
+        "Cloned {} into {} in {} seconds.",
+        url,
+        path.display(),
+        start.elapsed().as_secs()
+    );
+    Ok(true)
+}
+
+fn clone_with_libgit2(url: &str, path: &Path, ssh_private_key_path: &str) -> Result<()> {
+    let mut progress = CloneProgress::default();
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|_, user, _| {
+        Cred::ssh_key(
+            user.unwrap_or("git"),
+            None,
+            Path::new(ssh_private_key_path),
+            None,
+        )
+    });
+    callbacks.transfer_progress(|stats| {
+        let (received, total) = (stats.received_objects(), stats.total_objects());
+        if let Some(percent) = progress.percent_to_log(received, total) {
+            info!(
+                "Cloning {}: {}% ({}/{} objects).",
+                url, percent, received, total
+            );
+        }
+        true
+    });
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+    RepoBuilder::new()
+        .fetch_options(fetch_options)
+        .clone(url, path)?;
+    Ok(())
+}
+
+/// Tracks the progress of a clone so that it is only logged once every 10 percent.
+#[derive(Debug, Default)]
+struct CloneProgress {
+    last_logged_percent: Option<usize>,
+}
+
+impl CloneProgress {
+    fn percent_to_log(&mut self, received_objects: usize, total_objects: usize) -> Option<usize> {
+        if total_objects == 0 {
+            return None;
+        }
+        let percent = (received_objects * 100 / total_objects) / 10 * 10;
+        if self.last_logged_percent.is_some_and(|last| last >= percent) {
+            return None;
+        }
+        self.last_logged_percent = Some(percent);
+        Some(percent)
+    }
+}
+
+/// An error that occurs when the root directory of a repository does not exist, and the
+/// repository has no url to clone it from.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RepositoryNotClonedError(PathBuf);
+
+impl Display for RepositoryNotClonedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} does not exist, and there is no remote to clone it from.",
+            self.0.display()
+        )
+    }
+}
+
+impl Error for RepositoryNotClonedError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::{
+        metadata::RoswaalGitRepositoryMetadata, test_support::with_clean_test_repo_access,
+    };
+
+    use super::*;
+
+    #[test]
+    fn logs_clone_progress_once_every_10_percent() {
+        let mut progress = CloneProgress::default();
+        let logged = [
+            (0, 0),
+            (0, 200),
+            (5, 200),
+            (19, 200),
+            (20, 200),
+            (150, 200),
+            (200, 200),
+        ]
+        .iter()
+        .map(|(received, total)| progress.percent_to_log(*received, *total))
+        .collect::<Vec<_>>();
+        assert_eq!(
+            logged,
+            vec![None, Some(0), None, None, Some(10), Some(70), Some(100)]
+        )
+    }
+
+    #[tokio::test]
+    async fn does_not_clone_existing_repositories() -> Result<()> {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            assert!(!clone_if_missing(&metadata).await?);
+            Ok(())
+        })
+        .await
+    }
+}
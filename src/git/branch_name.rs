@@ -1,11 +1,14 @@
+use std::{error::Error, fmt::Display};
+
 use nanoid::nanoid;
 use once_cell::sync::Lazy;
-use regex::{Regex, RegexBuilder};
 use serde::{
     de::{Unexpected, Visitor},
     Deserialize, Serialize,
 };
-use sqlx::{sqlite::SqliteTypeInfo, Decode, Encode, Sqlite, Type};
+use sqlx::{postgres::PgTypeInfo, sqlite::SqliteTypeInfo, Decode, Encode, Postgres, Sqlite, Type};
+
+use crate::utils::config::RoswaalConfig;
 
 /// A type for a git branch name that is created by roswaal.
 ///
@@ -15,56 +18,215 @@ use sqlx::{sqlite::SqliteTypeInfo, Decode, Encode, Sqlite, Type};
 pub struct RoswaalOwnedGitBranchName(String);
 
 impl RoswaalOwnedGitBranchName {
+    /// Creates a branch name in the default `roswaal-` namespace.
     pub fn new(name: &str) -> Self {
-        Self(format!("roswaal-{}-{}", name, nanoid!(10)))
+        RoswaalGitBranchNamespace::default().branch_name(name)
     }
 
     pub fn for_adding_tests() -> Self {
-        Self::new("add-tests")
+        Self::new(RoswaalOwnedBranchKind::AddTests.base_name())
     }
 
     pub fn for_removing_tests() -> Self {
-        Self::new("remove-tests")
+        Self::new(RoswaalOwnedBranchKind::RemoveTests.base_name())
     }
 
     pub fn for_adding_locations() -> Self {
-        Self::new("add-locations")
+        Self::new(RoswaalOwnedBranchKind::AddLocations.base_name())
+    }
+
+    pub fn for_removing_locations() -> Self {
+        Self::new(RoswaalOwnedBranchKind::RemoveLocations.base_name())
+    }
+
+    pub fn for_removing_orphaned_tests() -> Self {
+        Self::new(RoswaalOwnedBranchKind::RemoveOrphanedTests.base_name())
     }
 }
 
 /// A specific type of branch created by this tool.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum RoswaalOwnedBranchKind {
     AddLocations,
     AddTests,
     RemoveTests,
+    RemoveLocations,
+    RemoveOrphanedTests,
+}
+
+impl RoswaalOwnedBranchKind {
+    const ALL: [Self; 5] = [
+        Self::AddTests,
+        Self::AddLocations,
+        Self::RemoveTests,
+        Self::RemoveLocations,
+        Self::RemoveOrphanedTests,
+    ];
+
+    /// The base name of the branches of this kind, without the namespace or nano id.
+    pub fn base_name(&self) -> &'static str {
+        match self {
+            Self::AddLocations => "add-locations",
+            Self::AddTests => "add-tests",
+            Self::RemoveTests => "remove-tests",
+            Self::RemoveLocations => "remove-locations",
+            Self::RemoveOrphanedTests => "remove-orphaned-tests",
+        }
+    }
 }
 
 impl RoswaalOwnedGitBranchName {
     /// Returns true if the branch's base name is the specifed name.
     ///
     /// This check does not include any special characters added by this type that can be found
-    /// in the `.to_string()` output.
+    /// in the `.to_string()` output, and it ignores the namespace of the branch as long as it is
+    /// the default namespace or the one in the current `RoswaalConfig`.
     pub fn is_named(&self, name: &str) -> bool {
-        let (start, end) = (8, self.0.len() - 11);
-        &self.0[start..end] == name
+        RoswaalGitBranchNamespace::known()
+            .any(|namespace| namespace.base_name(&self.0) == Some(name))
     }
 
     /// Returns the kind of branch that this name represents, or none if it does not represent a
     /// production purpose of this tool.
     pub fn kind(&self) -> Option<RoswaalOwnedBranchKind> {
-        if self.is_named("add-tests") {
-            Some(RoswaalOwnedBranchKind::AddTests)
-        } else if self.is_named("add-locations") {
-            Some(RoswaalOwnedBranchKind::AddLocations)
-        } else if self.is_named("remove-tests") {
-            Some(RoswaalOwnedBranchKind::RemoveTests)
-        } else {
-            None
+        RoswaalOwnedBranchKind::ALL
+            .into_iter()
+            .find(|kind| self.is_named(kind.base_name()))
+    }
+}
+
+static DEFAULT_NAMESPACE: Lazy<RoswaalGitBranchNamespace> =
+    Lazy::new(RoswaalGitBranchNamespace::default);
+
+/// The prefix and separator that come before the base name of each branch created by this tool.
+///
+/// Branch names take the form `<prefix><separator><name>-<nano_id>`, and the default namespace
+/// gives names like `roswaal-add-tests-<nano_id>`. A separator of `/` groups the branches into a
+/// folder in most git clients (eg. `bots/roswaal/add-tests-<nano_id>`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalGitBranchNamespace {
+    prefix: String,
+    separator: char,
+}
+
+impl Default for RoswaalGitBranchNamespace {
+    fn default() -> Self {
+        Self {
+            prefix: "roswaal".to_string(),
+            separator: '-',
         }
     }
 }
 
+impl RoswaalGitBranchNamespace {
+    /// Creates a namespace with the specified prefix and separator.
+    ///
+    /// The separator must be `-` or `/`, and the prefix must be non-empty and only use ascii
+    /// letters, digits, `-`, `_`, `.`, and `/` in a way that git accepts in a branch name.
+    pub fn new(prefix: &str, separator: char) -> Result<Self, RoswaalGitBranchNamespaceError> {
+        let is_valid_prefix = !prefix.is_empty()
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+            && !prefix.starts_with(['-', '.', '/'])
+            && !prefix.ends_with(['.', '/'])
+            && !prefix.ends_with(".lock")
+            && !prefix.contains("..")
+            && !prefix.contains("//")
+            && !prefix.contains("/.");
+        if !is_valid_prefix {
+            return Err(RoswaalGitBranchNamespaceError::InvalidPrefix(
+                prefix.to_string(),
+            ));
+        }
+        if !matches!(separator, '-' | '/') {
+            return Err(RoswaalGitBranchNamespaceError::InvalidSeparator(separator));
+        }
+        Ok(Self {
+            prefix: prefix.to_string(),
+            separator,
+        })
+    }
+
+    /// Returns a new branch name in this namespace with the specified base name.
+    pub fn branch_name(&self, name: &str) -> RoswaalOwnedGitBranchName {
+        RoswaalOwnedGitBranchName(format!(
+            "{}{}{}-{}",
+            self.prefix,
+            self.separator,
+            name,
+            nanoid!(10)
+        ))
+    }
+
+    /// Returns a new branch name in this namespace for the specified kind of branch.
+    pub fn branch_name_for(&self, kind: RoswaalOwnedBranchKind) -> RoswaalOwnedGitBranchName {
+        self.branch_name(kind.base_name())
+    }
+
+    /// Returns the base name of `branch_name` if it is in this namespace, or None if it is not.
+    pub fn base_name<'a>(&self, branch_name: &'a str) -> Option<&'a str> {
+        let name_and_id = branch_name
+            .strip_prefix(&self.prefix)?
+            .strip_prefix(self.separator)?;
+        let (id_start, _) = name_and_id.char_indices().rev().nth(9)?;
+        name_and_id[..id_start].strip_suffix('-')
+    }
+
+    /// The namespaces that names of branches created by this tool can be in, which are the
+    /// namespace in the current `RoswaalConfig` and the default namespace.
+    ///
+    /// The default namespace is always included so that branches created before the namespace
+    /// was changed can still be merged and closed.
+    fn known() -> impl Iterator<Item = &'static Self> {
+        [
+            RoswaalConfig::current().branch_namespace(),
+            &*DEFAULT_NAMESPACE,
+        ]
+        .into_iter()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RoswaalGitBranchNamespaceError {
+    InvalidPrefix(String),
+    InvalidSeparator(char),
+}
+
+impl Display for RoswaalGitBranchNamespaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPrefix(prefix) => {
+                write!(f, "\"{}\" is not a valid branch name prefix.", prefix)
+            }
+            Self::InvalidSeparator(separator) => write!(
+                f,
+                "\"{}\" is not a valid branch name separator, use \"-\" or \"/\".",
+                separator
+            ),
+        }
+    }
+}
+
+impl Error for RoswaalGitBranchNamespaceError {}
+
+/// An error that is returned when a new branch would have the same name as a branch that already
+/// exists on the remote.
+#[derive(Debug)]
+pub struct RoswaalBranchNameCollisionError(pub RoswaalOwnedGitBranchName);
+
+impl Display for RoswaalBranchNameCollisionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The branch {} already exists on the remote.",
+            self.0.to_string()
+        )
+    }
+}
+
+impl Error for RoswaalBranchNameCollisionError {}
+
 impl ToString for RoswaalOwnedGitBranchName {
     fn to_string(&self) -> String {
         self.0.clone()
@@ -77,6 +239,12 @@ impl Type<Sqlite> for RoswaalOwnedGitBranchName {
     }
 }
 
+impl Type<Postgres> for RoswaalOwnedGitBranchName {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+}
+
 impl<'d> Deserialize<'d> for RoswaalOwnedGitBranchName {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -88,24 +256,18 @@ impl<'d> Deserialize<'d> for RoswaalOwnedGitBranchName {
 
 struct RoswaalGitBranchNameVisitor;
 
-static BRANCH_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
-    RegexBuilder::new(r"^roswaal-.*-.{10}$")
-        .build()
-        .expect("Failed to compile location name regex.")
-});
-
 impl<'de> Visitor<'de> for RoswaalGitBranchNameVisitor {
     type Value = RoswaalOwnedGitBranchName;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("The format roswaal-<name>-<nano_id>.")
+        formatter.write_str("The format <prefix><separator><name>-<nano_id>.")
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        if BRANCH_NAME_REGEX.is_match(v) {
+        if RoswaalGitBranchNamespace::known().any(|namespace| namespace.base_name(v).is_some()) {
             Ok(RoswaalOwnedGitBranchName(v.to_string()))
         } else {
             Err(serde::de::Error::invalid_value(Unexpected::Str(v), &self))
@@ -117,7 +279,9 @@ impl<'de> Visitor<'de> for RoswaalGitBranchNameVisitor {
 mod tests {
     use crate::git::branch_name::RoswaalOwnedBranchKind;
 
-    use super::RoswaalOwnedGitBranchName;
+    use super::{
+        RoswaalGitBranchNamespace, RoswaalGitBranchNamespaceError, RoswaalOwnedGitBranchName,
+    };
 
     #[test]
     fn test_is_named() {
@@ -148,6 +312,14 @@ mod tests {
                 RoswaalOwnedGitBranchName::for_adding_locations(),
                 Some(RoswaalOwnedBranchKind::AddLocations),
             ),
+            (
+                RoswaalOwnedGitBranchName::for_removing_locations(),
+                Some(RoswaalOwnedBranchKind::RemoveLocations),
+            ),
+            (
+                RoswaalOwnedGitBranchName::for_removing_orphaned_tests(),
+                Some(RoswaalOwnedBranchKind::RemoveOrphanedTests),
+            ),
             (RoswaalOwnedGitBranchName::new("i-am-groot"), None),
         ];
         for (name, kind) in names_to_kind {
@@ -177,4 +349,49 @@ mod tests {
             assert!(serde_json::from_str::<RoswaalOwnedGitBranchName>(branch).is_ok())
         }
     }
+
+    #[test]
+    fn custom_namespace_branch_names() {
+        let namespace = RoswaalGitBranchNamespace::new("bots/roswaal", '/').unwrap();
+        let branch_name = namespace.branch_name_for(RoswaalOwnedBranchKind::AddTests);
+        let name = branch_name.to_string();
+        assert!(name.starts_with("bots/roswaal/add-tests-"));
+        assert_eq!(name.len(), "bots/roswaal/add-tests-".len() + 10);
+        assert_eq!(namespace.base_name(&name), Some("add-tests"));
+        assert_eq!(RoswaalGitBranchNamespace::default().base_name(&name), None);
+        assert_eq!(namespace.base_name("bots/roswaal/add-tests"), None);
+        assert_eq!(
+            namespace.base_name("bots/roswaal-add-tests-Lklj839sda"),
+            None
+        );
+        assert_eq!(
+            namespace.base_name("bots/roswaal/remove-tests-G983j839s4"),
+            Some("remove-tests")
+        )
+    }
+
+    #[test]
+    fn invalid_namespaces() {
+        let prefixes = [
+            "",
+            "roswaal bot",
+            "/roswaal",
+            "roswaal/",
+            "bots//roswaal",
+            "a..b",
+            ".a",
+        ];
+        for prefix in prefixes {
+            assert_eq!(
+                RoswaalGitBranchNamespace::new(prefix, '-'),
+                Err(RoswaalGitBranchNamespaceError::InvalidPrefix(
+                    prefix.to_string()
+                ))
+            )
+        }
+        assert_eq!(
+            RoswaalGitBranchNamespace::new("roswaal", '_'),
+            Err(RoswaalGitBranchNamespaceError::InvalidSeparator('_'))
+        )
+    }
 }
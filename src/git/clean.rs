@@ -0,0 +1,296 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use regex::Regex;
+use tokio::fs::{read_to_string, remove_file, symlink_metadata};
+
+use crate::utils::{config::RoswaalConfig, fs::remove_dir_all_empty};
+
+use super::metadata::RoswaalGitRepositoryMetadata;
+
+const BYTES_PER_MEGABYTE: u64 = 1024 * 1024;
+
+/// The path of the file, relative to the root of a repo, that declares the patterns of the files
+/// stored with Git LFS.
+const GIT_ATTRIBUTES_FILE_PATH: &str = ".gitattributes";
+
+/// The rules for which untracked files are deleted when a repository is cleaned.
+///
+/// Untracked files that match an ignore pattern, or a pattern stored with Git LFS in the
+/// `.gitattributes` of the repo, are never deleted. Untracked files larger than the maximum size
+/// are only deleted with `RoswaalLargeFileDeletion::Allow`, since they are more likely to be
+/// assets that were placed in the clone on purpose than leftovers from generating code.
+#[derive(Debug, Clone)]
+pub struct RoswaalGitCleanPolicy {
+    ignore_patterns: Vec<RoswaalGitPathPattern>,
+    max_file_size_bytes: u64,
+}
+
+impl Default for RoswaalGitCleanPolicy {
+    fn default() -> Self {
+        Self::new(&[], 50 * BYTES_PER_MEGABYTE)
+    }
+}
+
+impl RoswaalGitCleanPolicy {
+    /// Creates a policy that keeps the untracked files matching any of `ignore_patterns`.
+    ///
+    /// Patterns follow a subset of the `.gitignore` syntax. A pattern without a `/` matches a
+    /// file or directory with that name at any depth, and other patterns are matched from the root
+    /// of the repo. `*` and `?` match within a single path component, and `**` matches across
+    /// components.
+    pub fn new(ignore_patterns: &[String], max_file_size_bytes: u64) -> Self {
+        Self {
+            ignore_patterns: ignore_patterns
+                .iter()
+                .filter_map(|pattern| RoswaalGitPathPattern::new(pattern))
+                .collect(),
+            max_file_size_bytes,
+        }
+    }
+
+    /// The policy specified by the `[git]` table of the current `RoswaalConfig`.
+    pub fn from_config() -> Self {
+        let config = RoswaalConfig::current();
+        Self::new(
+            config.git_clean_ignore_patterns(),
+            config.max_git_clean_file_size_mb() as u64 * BYTES_PER_MEGABYTE,
+        )
+    }
+}
+
+impl RoswaalGitCleanPolicy {
+    /// Returns the size above which untracked files are not deleted without an explicit
+    /// `RoswaalLargeFileDeletion::Allow`.
+    pub fn max_file_size_bytes(&self) -> u64 {
+        self.max_file_size_bytes
+    }
+
+    /// Returns true if the file at `relative_path` from the root of the repo is kept when
+    /// cleaning.
+    pub fn is_ignored(&self, relative_path: &Path) -> bool {
+        self.ignore_patterns
+            .iter()
+            .any(|pattern| pattern.matches(relative_path))
+    }
+
+    /// Returns this policy with the patterns that `git_attributes` stores with Git LFS added to
+    /// its ignore patterns.
+    fn with_lfs_patterns(mut self, git_attributes: &str) -> Self {
+        let patterns = git_attributes
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                parts
+                    .any(|attribute| attribute == "filter=lfs")
+                    .then_some(pattern)
+            })
+            .filter_map(RoswaalGitPathPattern::new);
+        self.ignore_patterns.extend(patterns);
+        self
+    }
+}
+
+/// Whether cleaning a repository can delete untracked files above the maximum size of its
+/// `RoswaalGitCleanPolicy`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoswaalLargeFileDeletion {
+    /// Fails without deleting anything if a large untracked file would be deleted.
+    Refuse,
+    /// Deletes large untracked files like any other untracked file.
+    Allow,
+}
+
+/// An error that occurs when cleaning a repository would delete untracked files above the
+/// maximum size of its `RoswaalGitCleanPolicy`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LargeUntrackedFilesError {
+    repository_name: String,
+    paths: Vec<String>,
+    max_file_size_bytes: u64,
+}
+
+impl LargeUntrackedFilesError {
+    /// Returns the paths of the large files relative to the root of the repo.
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+}
+
+impl Display for LargeUntrackedFilesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Refusing to delete untracked files larger than {} MB in {}: {}. Move them out of the clone, add them to the clean ignore patterns, or run `/repo-doctor reset --delete-large-files` to delete them.",
+            self.max_file_size_bytes / BYTES_PER_MEGABYTE,
+            self.repository_name,
+            self.paths.join(", ")
+        )
+    }
+}
+
+impl Error for LargeUntrackedFilesError {}
+
+/// Deletes the untracked files at `relative_paths` from the root of the repo described by
+/// `metadata`, and then removes any directories that were left empty.
+///
+/// Files that the `RoswaalGitCleanPolicy` of `metadata` ignores are kept. If any of the remaining
+/// files are above the maximum size of the policy and `deletion` is
+/// `RoswaalLargeFileDeletion::Refuse`, a `LargeUntrackedFilesError` is returned before anything
+/// is deleted.
+pub async fn remove_untracked_files(
+    metadata: &RoswaalGitRepositoryMetadata,
+    relative_paths: Vec<PathBuf>,
+    deletion: RoswaalLargeFileDeletion,
+) -> Result<()> {
+    let git_attributes =
+        match read_to_string(metadata.relative_path(GIT_ATTRIBUTES_FILE_PATH)).await {
+            Ok(git_attributes) => git_attributes,
+            Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+    let policy = metadata
+        .clean_policy()
+        .clone()
+        .with_lfs_patterns(&git_attributes);
+    let relative_paths = relative_paths
+        .into_iter()
+        .filter(|path| !policy.is_ignored(path))
+        .collect::<Vec<PathBuf>>();
+    if deletion == RoswaalLargeFileDeletion::Refuse {
+        let mut large_paths = Vec::new();
+        for path in relative_paths.iter() {
+            let size = symlink_metadata(metadata.relative_path(path))
+                .await
+                .map(|file| file.len())
+                .unwrap_or(0);
+            if size > policy.max_file_size_bytes() {
+                large_paths.push(path.to_string_lossy().to_string())
+            }
+        }
+        if !large_paths.is_empty() {
+            return Err(LargeUntrackedFilesError {
+                repository_name: metadata.name().to_string(),
+                paths: large_paths,
+                max_file_size_bytes: policy.max_file_size_bytes(),
+            }
+            .into());
+        }
+    }
+    let futures = relative_paths
+        .into_iter()
+        .map(|path| tokio::spawn(remove_file(metadata.relative_path(path))));
+    for f in futures {
+        f.await??;
+    }
+    remove_dir_all_empty(metadata.relative_path(".")).await?;
+    Ok(())
+}
+
+/// A `.gitignore` style pattern for the paths of files in a repo.
+#[derive(Debug, Clone)]
+struct RoswaalGitPathPattern(Regex);
+
+impl RoswaalGitPathPattern {
+    /// Parses `pattern`, or returns None if the pattern is blank.
+    fn new(pattern: &str) -> Option<Self> {
+        let pattern = pattern.trim().trim_end_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+        let is_anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        let mut regex = if is_anchored {
+            "^".to_string()
+        } else {
+            "^(?:.*/)?".to_string()
+        };
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        // NB: A pattern that matches a directory matches everything inside of it.
+        regex.push_str("(?:/.*)?$");
+        Regex::new(&regex).ok().map(Self)
+    }
+
+    fn matches(&self, relative_path: &Path) -> bool {
+        let path = relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        self.0.is_match(&path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_ignored_matches_gitignore_style_patterns() {
+        let policy = RoswaalGitCleanPolicy::new(
+            &[
+                "*.mp4".to_string(),
+                "/assets/".to_string(),
+                "roswaal/**/*.png".to_string(),
+                "build".to_string(),
+                " ".to_string(),
+            ],
+            1,
+        );
+        let cases = [
+            ("video.mp4", true),
+            ("roswaal/nested/video.mp4", true),
+            ("video.mp4.txt", false),
+            ("assets/logo.png", true),
+            ("ios/assets/Icon.jpg", false),
+            ("roswaal/logo.png", true),
+            ("roswaal/nested/deeply/logo.png", true),
+            ("other/logo.png", false),
+            ("build", true),
+            ("ios/build/App.ipa", true),
+            ("builds/App.ipa", false),
+            ("test.txt", false),
+        ];
+        for (path, is_ignored) in cases {
+            assert_eq!(policy.is_ignored(Path::new(path)), is_ignored, "{}", path)
+        }
+    }
+
+    #[test]
+    fn with_lfs_patterns_ignores_files_stored_with_lfs() {
+        let git_attributes = "\
+# Binary assets
+*.psd filter=lfs diff=lfs merge=lfs -text
+*.ts text eol=lf
+videos/** filter=lfs diff=lfs merge=lfs -text
+";
+        let policy = RoswaalGitCleanPolicy::default().with_lfs_patterns(git_attributes);
+        assert!(policy.is_ignored(Path::new("design/Logo.psd")));
+        assert!(policy.is_ignored(Path::new("videos/onboarding.mov")));
+        assert!(!policy.is_ignored(Path::new("roswaal/Locations.ts")))
+    }
+}
@@ -2,19 +2,35 @@ use std::future::Future;
 
 use anyhow::Result;
 
+use crate::utils::{
+    config::RoswaalConfig,
+    retry::{retry, RetriesExhaustedError},
+    stage::RoswaalOperationStage,
+};
+
 use super::{
-    branch_name::RoswaalOwnedGitBranchName,
-    pull_request::{GithubPullRequest, GithubPullRequestOpen},
-    repo::{PullBranchStatus, RoswaalGitRepositoryClient, RoswaalGitRepositoryTransaction},
+    branch_name::{RoswaalBranchNameCollisionError, RoswaalOwnedGitBranchName},
+    clean::RoswaalLargeFileDeletion,
+    metadata::RoswaalGitRepositoryMetadata,
+    pull_request::{is_transient_github_error, GithubPullRequest, GithubPullRequestOpen},
+    repo::{
+        is_transient_git_error, PullBranchStatus, RoswaalGitRepository, RoswaalGitRepositoryClient,
+        RoswaalGitRepositoryTransaction,
+    },
 };
 
 /// A status type for creating a new branch, pushing changes, opening a pull request, and
 /// deleting the newly created branch.
 #[derive(Debug, PartialEq, Eq)]
 pub enum EditGitRepositoryStatus<T> {
-    Success { did_delete_branch: bool, value: T },
+    Success {
+        did_delete_branch: bool,
+        value: T,
+    },
     FailedToOpenPullRequest,
     MergeConflict,
+    /// The remote repository or GitHub could not be reached after retrying.
+    RemoteUnavailable,
 }
 
 impl<T> EditGitRepositoryStatus<T> {
@@ -24,41 +40,108 @@ impl<T> EditGitRepositoryStatus<T> {
     /// Any uncomitted changes are reset and cleaned up, and the latest changes from the base
     /// branch are pulled before the edit future is ran. The new branch is deleted on the local
     /// repository after the edit is completed.
+    ///
+    /// Pulling, pushing, and opening the PR are retried with the current `RoswaalRetryPolicy`
+    /// when they fail transiently, and `RemoteUnavailable` is returned if every attempt fails.
+    ///
+    /// Passing a `StagingGithubPullRequestOpen` as `pr_open` still commits and pushes the new
+    /// branch, but defers opening the PR until `/open-pending-prs` is ran when the staged PR mode
+    /// is configured.
+    ///
+    /// A `RoswaalBranchNameCollisionError` is returned without editing anything if the new branch
+    /// already exists on the remote.
     pub async fn from_editing_new_branch(
         new_branch_name: &RoswaalOwnedGitBranchName,
-        transaction: RoswaalGitRepositoryTransaction<'_, impl RoswaalGitRepositoryClient>,
+        transaction: RoswaalGitRepositoryTransaction<impl RoswaalGitRepositoryClient>,
+        pr_open: &impl GithubPullRequestOpen,
+        edit: impl Future<Output = Result<(GithubPullRequest, T)>>,
+    ) -> Result<Self> {
+        let result = Self::edit_new_branch(new_branch_name, transaction, pr_open, edit).await;
+        match result {
+            Err(error) if RetriesExhaustedError::is_exhausted(&error) => {
+                log::error!("The remote is unavailable. {}", error);
+                Ok(Self::RemoteUnavailable)
+            }
+            result => result,
+        }
+    }
+
+    async fn edit_new_branch(
+        new_branch_name: &RoswaalOwnedGitBranchName,
+        transaction: RoswaalGitRepositoryTransaction<impl RoswaalGitRepositoryClient>,
         pr_open: &impl GithubPullRequestOpen,
         edit: impl Future<Output = Result<(GithubPullRequest, T)>>,
     ) -> Result<Self> {
+        let policy = RoswaalConfig::current().retry_policy();
         let base_branch_name = transaction.metadata().base_branch_name();
         transaction.hard_reset_to_head().await?;
-        transaction.clean_all_untracked().await?;
+        transaction
+            .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+            .await?;
         transaction.switch_branch(base_branch_name).await?;
-        let pull_status = transaction.pull_branch(base_branch_name).await?;
+        RoswaalOperationStage::Pulling.report();
+        let pull_status = retry(policy, is_transient_git_error, || {
+            transaction.pull_branch(base_branch_name)
+        })
+        .await?;
         if pull_status == PullBranchStatus::MergeConflict {
             return Ok(Self::MergeConflict);
         }
+        let remote_branch_names = retry(policy, is_transient_git_error, || {
+            transaction.remote_branch_names()
+        })
+        .await?;
+        if remote_branch_names.contains(&new_branch_name.to_string()) {
+            return Err(RoswaalBranchNameCollisionError(new_branch_name.clone()).into());
+        }
         transaction.checkout_new_branch(new_branch_name).await?;
+        let deletion = CancelledBranchDeletion::new(transaction, new_branch_name);
+        let pushed = Self::commit_and_push(deletion.transaction(), new_branch_name, edit).await;
+        drop(deletion.finish());
+        let (pull_request, value, did_delete_branch) = pushed?;
+        RoswaalOperationStage::OpeningPullRequest.report();
+        let did_open = retry(policy, is_transient_github_error, || {
+            pr_open.open(&pull_request)
+        })
+        .await?;
+        if !did_open {
+            Ok(Self::FailedToOpenPullRequest)
+        } else {
+            Ok(Self::Success {
+                did_delete_branch,
+                value,
+            })
+        }
+    }
+
+    /// Performs the edit on the checked out new branch, and then commits and pushes it.
+    ///
+    /// Returns the PR of the edit, its value, and whether the new branch was deleted locally
+    /// after switching back to the base branch.
+    async fn commit_and_push(
+        transaction: &impl RoswaalGitRepositoryClient,
+        new_branch_name: &RoswaalOwnedGitBranchName,
+        edit: impl Future<Output = Result<(GithubPullRequest, T)>>,
+    ) -> Result<(GithubPullRequest, T, bool)> {
+        let policy = RoswaalConfig::current().retry_policy();
+        let base_branch_name = transaction.metadata().base_branch_name();
         match edit.await {
             Ok((pull_request, value)) => {
                 transaction.commit_all(pull_request.title()).await?;
-                transaction.push_changes(new_branch_name).await?;
+                RoswaalOperationStage::Pushing.report();
+                retry(policy, is_transient_git_error, || {
+                    transaction.push_changes(new_branch_name)
+                })
+                .await?;
                 transaction.switch_branch(base_branch_name).await?;
                 let did_delete_branch = transaction.delete_local_branch(new_branch_name).await?;
-                drop(transaction);
-                let did_open = pr_open.open(&pull_request).await?;
-                if !did_open {
-                    Ok(Self::FailedToOpenPullRequest)
-                } else {
-                    Ok(Self::Success {
-                        did_delete_branch,
-                        value,
-                    })
-                }
+                Ok((pull_request, value, did_delete_branch))
             }
             Err(err) => {
                 transaction.hard_reset_to_head().await?;
-                transaction.clean_all_untracked().await?;
+                transaction
+                    .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+                    .await?;
                 transaction.switch_branch(base_branch_name).await?;
                 transaction.delete_local_branch(new_branch_name).await?;
                 Err(err)
@@ -67,13 +150,146 @@ impl<T> EditGitRepositoryStatus<T> {
     }
 }
 
+/// Deletes the new branch of an edit if the edit is cancelled (eg. with `/roswaal-cancel`) while
+/// the branch is being edited, committed, or pushed.
+///
+/// The edit is cancelled by dropping its future, so the deletion runs in the background while
+/// holding the transaction. Once the transaction is released, the pushed branch is left for its PR.
+struct CancelledBranchDeletion<Client: RoswaalGitRepositoryClient> {
+    transaction: Option<RoswaalGitRepositoryTransaction<Client>>,
+    branch_name: RoswaalOwnedGitBranchName,
+}
+
+impl<Client: RoswaalGitRepositoryClient> CancelledBranchDeletion<Client> {
+    fn new(
+        transaction: RoswaalGitRepositoryTransaction<Client>,
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> Self {
+        Self {
+            transaction: Some(transaction),
+            branch_name: branch_name.clone(),
+        }
+    }
+
+    fn transaction(&self) -> &Client {
+        self.transaction.as_ref().unwrap()
+    }
+
+    /// Returns the transaction without deleting the branch, since the edit finished.
+    fn finish(mut self) -> RoswaalGitRepositoryTransaction<Client> {
+        self.transaction.take().unwrap()
+    }
+}
+
+impl<Client: RoswaalGitRepositoryClient> Drop for CancelledBranchDeletion<Client> {
+    fn drop(&mut self) {
+        if let Some(transaction) = self.transaction.take() {
+            Client::delete_cancelled_branch(transaction, self.branch_name.clone())
+        }
+    }
+}
+
+/// The status of mirroring an edit onto an additional target repository.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TargetRepositoryEditStatus {
+    Success { should_warn_undeleted_branch: bool },
+    FailedToOpenPullRequest,
+    MergeConflict,
+    RemoteUnavailable,
+    Failed,
+}
+
+/// The result of mirroring an edit onto a named target repository.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TargetRepositoryEdit {
+    repository_name: String,
+    status: TargetRepositoryEditStatus,
+}
+
+impl TargetRepositoryEdit {
+    pub fn new(repository_name: &str, status: TargetRepositoryEditStatus) -> Self {
+        Self {
+            repository_name: repository_name.to_string(),
+            status,
+        }
+    }
+
+    /// Performs the edit on a new branch in each target repository one after another, and opens
+    /// a PR for each.
+    ///
+    /// An error in one target is logged and reported as `Failed` rather than returned, since the
+    /// edits made to the other repositories cannot be undone.
+    pub async fn from_editing_targets<Client, Edit>(
+        new_branch_name: &RoswaalOwnedGitBranchName,
+        targets: &[RoswaalGitRepository<Client>],
+        pr_open: &impl GithubPullRequestOpen,
+        edit: impl Fn(RoswaalGitRepositoryMetadata) -> Edit,
+    ) -> Vec<Self>
+    where
+        Client: RoswaalGitRepositoryClient,
+        Edit: Future<Output = Result<GithubPullRequest>>,
+    {
+        let mut edits = Vec::new();
+        for target in targets {
+            let transaction = target.transaction().await;
+            let metadata = transaction.metadata().clone();
+            let status = EditGitRepositoryStatus::from_editing_new_branch(
+                new_branch_name,
+                transaction,
+                pr_open,
+                async { edit(metadata.clone()).await.map(|pr| (pr, ())) },
+            )
+            .await;
+            let status = match status {
+                Ok(EditGitRepositoryStatus::Success {
+                    did_delete_branch,
+                    value: _,
+                }) => TargetRepositoryEditStatus::Success {
+                    should_warn_undeleted_branch: !did_delete_branch,
+                },
+                Ok(EditGitRepositoryStatus::FailedToOpenPullRequest) => {
+                    TargetRepositoryEditStatus::FailedToOpenPullRequest
+                }
+                Ok(EditGitRepositoryStatus::MergeConflict) => {
+                    TargetRepositoryEditStatus::MergeConflict
+                }
+                Ok(EditGitRepositoryStatus::RemoteUnavailable) => {
+                    TargetRepositoryEditStatus::RemoteUnavailable
+                }
+                Err(err) => {
+                    log::error!("Failed to edit {}: {}", metadata.name(), err);
+                    TargetRepositoryEditStatus::Failed
+                }
+            };
+            edits.push(Self::new(metadata.name(), status));
+        }
+        edits
+    }
+}
+
+impl TargetRepositoryEdit {
+    pub fn repository_name(&self) -> &str {
+        &self.repository_name
+    }
+
+    pub fn status(&self) -> &TargetRepositoryEditStatus {
+        &self.status
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use tokio::fs::{try_exists, File};
+    use std::{future::pending, path::Path, time::Duration};
+
+    use tokio::{
+        fs::{try_exists, File},
+        time::timeout,
+    };
 
     use super::*;
     use crate::{
         git::{
+            health::RoswaalGitRepositoryHealth,
             metadata::{RoswaalGitRepositoryMetadata, TEST_REPO_BASE_BRANCH_NAME},
             repo::{LibGit2RepositoryClient, PullBranchStatus, RoswaalGitRepository},
             test_support::{
@@ -117,6 +333,59 @@ mod tests {
         .unwrap()
     }
 
+    #[tokio::test]
+    async fn test_cancelling_edit_deletes_new_branch_and_restores_base_branch() {
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let new_branch_name = RoswaalOwnedGitBranchName::new("test-edit");
+            let file_path = metadata.relative_path("test-thing.txt");
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let edit = EditGitRepositoryStatus::<()>::from_editing_new_branch(
+                &new_branch_name,
+                repo.transaction().await,
+                &pr_open,
+                async {
+                    File::create(&file_path).await?;
+                    pending::<Result<(GithubPullRequest, ())>>().await
+                },
+            );
+            assert!(timeout(Duration::from_millis(500), edit).await.is_err());
+
+            let transaction = repo.transaction().await;
+            let local_branch_names = transaction.local_branch_names().await?;
+            assert!(!local_branch_names.contains(&new_branch_name.to_string()));
+            let health = transaction.health().await?;
+            assert_eq!(
+                health,
+                RoswaalGitRepositoryHealth::clean(metadata.base_branch_name())
+            );
+            assert!(!try_exists(&file_path).await?);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_errors_when_new_branch_already_exists_on_remote() {
+        let new_branch_name = RoswaalOwnedGitBranchName::new("test-edit");
+        let repo =
+            RoswaalGitRepository::noop_with_remote_branch_names(&[&new_branch_name.to_string()])
+                .await
+                .unwrap();
+        let result = EditGitRepositoryStatus::from_editing_new_branch(
+            &new_branch_name,
+            repo.transaction().await,
+            &TestGithubPullRequestOpen::new(false),
+            async { Ok((GithubPullRequest::test(&new_branch_name), ())) },
+        )
+        .await;
+        let error = result.unwrap_err();
+        assert!(error
+            .downcast_ref::<RoswaalBranchNameCollisionError>()
+            .is_some())
+    }
+
     #[tokio::test]
     async fn test_returns_pr_open_failed_when_pr_fails_to_open() {
         with_clean_test_repo_access(async {
@@ -135,6 +404,26 @@ mod tests {
         .unwrap()
     }
 
+    #[tokio::test]
+    async fn test_returns_remote_unavailable_when_github_is_down_on_every_attempt() {
+        with_clean_test_repo_access(async {
+            let new_branch_name = RoswaalOwnedGitBranchName::new("test-edit");
+            let pr_open = TestGithubPullRequestOpen::unavailable();
+            let status = EditGitRepositoryStatus::from_editing_new_branch(
+                &new_branch_name,
+                RoswaalGitRepository::noop().await?.transaction().await,
+                &pr_open,
+                async { Ok((GithubPullRequest::test(&new_branch_name), ())) },
+            )
+            .await?;
+            assert_eq!(status, EditGitRepositoryStatus::RemoteUnavailable);
+            assert!(pr_open.most_recent_pr().await.is_some());
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn test_recovers_from_previous_failed_edit() {
         with_clean_test_repo_access(async {
@@ -297,11 +586,67 @@ mod tests {
         .unwrap()
     }
 
+    #[tokio::test]
+    async fn test_editing_targets_reports_status_of_each_target() {
+        with_clean_test_repo_access(async {
+            let new_branch_name = RoswaalOwnedGitBranchName::new("test-edit-targets");
+            let targets = vec![
+                RoswaalGitRepository::noop().await?,
+                RoswaalGitRepository::noop_ensuring_merge_conflicts().await?,
+            ];
+            let edits = TargetRepositoryEdit::from_editing_targets(
+                &new_branch_name,
+                &targets,
+                &TestGithubPullRequestOpen::new(false),
+                |_| async { Ok(GithubPullRequest::test(&new_branch_name)) },
+            )
+            .await;
+            let expected_edits = vec![
+                TargetRepositoryEdit::new(
+                    "FitnessProjectTest",
+                    TargetRepositoryEditStatus::Success {
+                        should_warn_undeleted_branch: false,
+                    },
+                ),
+                TargetRepositoryEdit::new(
+                    "FitnessProjectTest",
+                    TargetRepositoryEditStatus::MergeConflict,
+                ),
+            ];
+            assert_eq!(edits, expected_edits);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_editing_targets_reports_failed_when_edit_errors() {
+        with_clean_test_repo_access(async {
+            let new_branch_name = RoswaalOwnedGitBranchName::new("test-edit-targets");
+            let edits = TargetRepositoryEdit::from_editing_targets(
+                &new_branch_name,
+                &[RoswaalGitRepository::noop().await?],
+                &TestGithubPullRequestOpen::new(false),
+                |_| async { Err(anyhow::Error::new(TestError)) },
+            )
+            .await;
+            let expected_edits = vec![TargetRepositoryEdit::new(
+                "FitnessProjectTest",
+                TargetRepositoryEditStatus::Failed,
+            )];
+            assert_eq!(edits, expected_edits);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
     async fn assert_successful_single_file_created_edit(
         status: &EditGitRepositoryStatus<()>,
         branch_name: &RoswaalOwnedGitBranchName,
         expected_pr: &GithubPullRequest,
-        file_path: &str,
+        file_path: &Path,
         repo: &RoswaalGitRepository<LibGit2RepositoryClient>,
         pr_open: &TestGithubPullRequestOpen,
     ) -> Result<()> {
@@ -0,0 +1,50 @@
+/// A snapshot of the state of a local clone, used to find clones that would break the commands
+/// that operate on them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalGitRepositoryHealth {
+    current_branch_name: Option<String>,
+    dirty_paths: Vec<String>,
+    is_merging: bool,
+}
+
+impl RoswaalGitRepositoryHealth {
+    pub fn new(
+        current_branch_name: Option<String>,
+        dirty_paths: Vec<String>,
+        is_merging: bool,
+    ) -> Self {
+        Self {
+            current_branch_name,
+            dirty_paths,
+            is_merging,
+        }
+    }
+
+    /// A clone that is on `branch_name` with no uncommitted changes.
+    pub fn clean(branch_name: &str) -> Self {
+        Self::new(Some(branch_name.to_string()), vec![], false)
+    }
+}
+
+impl RoswaalGitRepositoryHealth {
+    /// Returns the name of the checked out branch, or None if HEAD is detached.
+    pub fn current_branch_name(&self) -> Option<&str> {
+        self.current_branch_name.as_deref()
+    }
+
+    /// Returns the paths of the files with uncommitted changes, including untracked files.
+    pub fn dirty_paths(&self) -> &[String] {
+        &self.dirty_paths
+    }
+
+    /// Returns true if a merge was started but never committed or aborted.
+    pub fn is_merging(&self) -> bool {
+        self.is_merging
+    }
+
+    /// Returns true if the clone has a branch checked out, no uncommitted changes, and no merge
+    /// in progress.
+    pub fn is_healthy(&self) -> bool {
+        self.current_branch_name.is_some() && self.dirty_paths.is_empty() && !self.is_merging
+    }
+}
@@ -1,44 +1,171 @@
-use std::env;
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
-use super::{branch_name::RoswaalOwnedGitBranchName, pull_request::GithubPullRequest};
+use super::{
+    branch_name::{RoswaalGitBranchNamespace, RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+    clean::RoswaalGitCleanPolicy,
+    pull_request::{GithubPullRequest, GithubPullRequestOptions},
+    pull_request_template::GithubPullRequestBodyTemplate,
+};
 use crate::{
-    language::{ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults},
-    location::location::RoswaalStringLocations,
-    tests_data::query::RoswaalTestNamesString,
-    utils::string::ToAsciiKebabCase,
+    generation::{
+        interface::{test_directory_name, CodeGeneratorKind},
+        maestro,
+    },
+    language::{compilation_results::RoswaalTestCompilationResults, diff::RoswaalCompiledTestDiff},
+    location::{location::RoswaalStringLocations, name::RoswaalLocationName},
+    tests_data::{query::RoswaalTestNamesString, test::RoswaalTest},
+    utils::config::RoswaalConfig,
 };
 
 /// A struct containing neccessary metadata for operating in a roswaal compatible git repo.
 #[derive(Debug, Clone)]
 pub struct RoswaalGitRepositoryMetadata {
+    name: String,
     base_branch_name: String,
-    repo_root_dir_path: String,
+    repo_root_dir_path: PathBuf,
+    /// The url that the repo is cloned from when its root directory does not exist, or None if
+    /// the repo must be cloned manually.
+    clone_url: Option<String>,
     ssh_private_key_home_path: String,
-    test_cases_root_dir_path: String,
+    test_cases_root_dir_path: PathBuf,
     add_test_cases_pr: fn(
         results: &RoswaalTestCompilationResults,
         &RoswaalOwnedGitBranchName,
     ) -> GithubPullRequest,
-    locations_path: String,
+    locations_path: PathBuf,
     add_locations_pr: fn(&RoswaalStringLocations, &RoswaalOwnedGitBranchName) -> GithubPullRequest,
-    remove_tests_pr: fn(&RoswaalTestNamesString, &RoswaalOwnedGitBranchName) -> GithubPullRequest,
+    remove_tests_pr: fn(
+        &RoswaalTestNamesString,
+        &[RoswaalTest],
+        &RoswaalOwnedGitBranchName,
+    ) -> GithubPullRequest,
+    edit_test_pr: fn(&RoswaalCompiledTestDiff, &RoswaalOwnedGitBranchName) -> GithubPullRequest,
+    remove_locations_pr:
+        fn(&[RoswaalLocationName], &RoswaalOwnedGitBranchName) -> GithubPullRequest,
+    remove_orphaned_tests_pr: fn(&[String], &RoswaalOwnedGitBranchName) -> GithubPullRequest,
+    /// The GitHub owner and repo that PRs are opened against instead of the ones chosen by the
+    /// pull request functions.
+    pull_request_repository: Option<(String, String)>,
+    /// The draft state, labels, assignees, and reviewers of the PRs opened for this repo.
+    pull_request_options: GithubPullRequestOptions,
+    /// The template that the bodies of the PRs opened for this repo are rendered with, unless the
+    /// repo has its own template file.
+    pull_request_body_template: GithubPullRequestBodyTemplate,
+    /// The prefix and separator of the names of the branches created in this repo.
+    branch_namespace: RoswaalGitBranchNamespace,
+    client_kind: RoswaalGitClientKind,
+    code_generator: CodeGeneratorKind,
+    /// The command that formats the generated code in this repo, if any.
+    formatter_command: Option<String>,
+    /// The untracked files that are kept when this repo is cleaned.
+    clean_policy: RoswaalGitCleanPolicy,
 }
 
 impl RoswaalGitRepositoryMetadata {
     /// Metadata for the main frontend repo.
+    ///
+    /// The repo is expected to be cloned at `./<repo>`, where `<repo>` is the frontend repo in the
+    /// current `RoswaalConfig`.
     pub fn for_tif_react_frontend() -> Self {
+        let config = RoswaalConfig::current();
+        Self {
+            name: config.frontend_repo().to_string(),
+            base_branch_name: config.frontend_base_branch_name().to_string(),
+            repo_root_dir_path: Path::new(".").join(config.frontend_repo()),
+            clone_url: Some(github_ssh_url(
+                config.frontend_owner(),
+                config.frontend_repo(),
+            )),
+            ssh_private_key_home_path: "./.ssh/id_rsa".to_string(),
+            test_cases_root_dir_path: Path::new(".").join(config.frontend_repo()).join("roswaal"),
+            add_test_cases_pr: GithubPullRequest::for_test_cases_tif_react_frontend,
+            locations_path: Path::new(".")
+                .join(config.frontend_repo())
+                .join("roswaal")
+                .join("Locations.ts"),
+            add_locations_pr: GithubPullRequest::for_locations_tif_react_frontend,
+            remove_tests_pr: GithubPullRequest::for_removing_test_cases_tif_react_frontend,
+            edit_test_pr: GithubPullRequest::for_editing_test_case_tif_react_frontend,
+            remove_locations_pr: GithubPullRequest::for_removing_locations_tif_react_frontend,
+            remove_orphaned_tests_pr:
+                GithubPullRequest::for_removing_orphaned_tests_tif_react_frontend,
+            pull_request_repository: None,
+            pull_request_options: GithubPullRequestOptions::from_config(),
+            pull_request_body_template: GithubPullRequestBodyTemplate::from_config(),
+            branch_namespace: config.branch_namespace().clone(),
+            client_kind: RoswaalGitClientKind::current(),
+            code_generator: CodeGeneratorKind::current(),
+            formatter_command: config.formatter_command().map(str::to_string),
+            clean_policy: RoswaalGitCleanPolicy::from_config(),
+        }
+    }
+
+    /// Metadata for an additional repo that test code is also generated in (eg. the iOS and
+    /// Android wrappers of the frontend).
+    ///
+    /// The repo is expected to be cloned at `./<repo>`, and to share the layout of the main
+    /// frontend repo. PRs are opened against `<owner>/<repo>` on GitHub.
+    pub fn for_test_target(owner: &str, repo: &str, base_branch_name: &str) -> Self {
         Self {
-            base_branch_name: "development".to_string(),
-            repo_root_dir_path: "./FitnessProject".to_string(),
+            name: repo.to_string(),
+            base_branch_name: base_branch_name.to_string(),
+            repo_root_dir_path: Path::new(".").join(repo),
+            clone_url: Some(github_ssh_url(owner, repo)),
             ssh_private_key_home_path: "./.ssh/id_rsa".to_string(),
-            test_cases_root_dir_path: "./FitnessProject/roswaal".to_string(),
+            test_cases_root_dir_path: Path::new(".").join(repo).join("roswaal"),
             add_test_cases_pr: GithubPullRequest::for_test_cases_tif_react_frontend,
-            locations_path: "./FitnessProject/roswaal/Locations.ts".to_string(),
+            locations_path: Path::new(".")
+                .join(repo)
+                .join("roswaal")
+                .join("Locations.ts"),
             add_locations_pr: GithubPullRequest::for_locations_tif_react_frontend,
             remove_tests_pr: GithubPullRequest::for_removing_test_cases_tif_react_frontend,
+            edit_test_pr: GithubPullRequest::for_editing_test_case_tif_react_frontend,
+            remove_locations_pr: GithubPullRequest::for_removing_locations_tif_react_frontend,
+            remove_orphaned_tests_pr:
+                GithubPullRequest::for_removing_orphaned_tests_tif_react_frontend,
+            pull_request_repository: Some((owner.to_string(), repo.to_string())),
+            pull_request_options: GithubPullRequestOptions::from_config(),
+            pull_request_body_template: GithubPullRequestBodyTemplate::from_config(),
+            branch_namespace: RoswaalConfig::current().branch_namespace().clone(),
+            client_kind: RoswaalGitClientKind::current(),
+            code_generator: CodeGeneratorKind::current(),
+            formatter_command: None,
+            clean_policy: RoswaalGitCleanPolicy::from_config(),
         }
     }
 
+    /// Parses a comma separated list of test targets in the form `<owner>/<repo>@<base_branch>`.
+    pub fn test_targets_from_str(value: &str) -> Result<Vec<Self>, TestTargetParsingError> {
+        value
+            .split(',')
+            .map(|target| target.trim())
+            .filter(|target| !target.is_empty())
+            .map(|target| {
+                let (owner_and_repo, base_branch_name) = target
+                    .split_once('@')
+                    .ok_or_else(|| TestTargetParsingError(target.to_string()))?;
+                let (owner, repo) = owner_and_repo
+                    .split_once('/')
+                    .ok_or_else(|| TestTargetParsingError(target.to_string()))?;
+                if owner.is_empty() || repo.is_empty() || base_branch_name.is_empty() {
+                    return Err(TestTargetParsingError(target.to_string()));
+                }
+                Ok(Self::for_test_target(owner, repo, base_branch_name))
+            })
+            .collect()
+    }
+
+    /// Returns the test targets specified by the current `RoswaalConfig`.
+    pub fn test_targets_from_config() -> Result<Vec<Self>, TestTargetParsingError> {
+        Self::test_targets_from_str(&RoswaalConfig::current().test_targets().join(","))
+    }
+
     /// Metadata for a local testing repo.
     pub fn for_testing() -> Self {
         Self::for_testing_with_custom_base_branch(TEST_REPO_BASE_BRANCH_NAME)
@@ -47,39 +174,101 @@ impl RoswaalGitRepositoryMetadata {
     /// Metadata for a local testing repo with a custom base branch name.
     pub fn for_testing_with_custom_base_branch(base_branch_name: &str) -> Self {
         Self {
+            name: "FitnessProjectTest".to_string(),
             base_branch_name: base_branch_name.to_string(),
-            repo_root_dir_path: "./FitnessProjectTest".to_string(),
+            repo_root_dir_path: Path::new(".").join("FitnessProjectTest"),
+            clone_url: None,
             ssh_private_key_home_path: env::var("TEST_SSH_PRIVATE_KEY_HOME_PATH")
                 .expect("Ensure to the set the TEST_SSH_PRIVATE_KEY_HOME_PATH variable in your .env file to the home path of your private ssh-key (Ex. ./.ssh/id_rsa)"),
-            test_cases_root_dir_path: "./FitnessProjectTest/roswaal".to_string(),
+            test_cases_root_dir_path: Path::new(".").join("FitnessProjectTest").join("roswaal"),
             add_test_cases_pr: |cases, head_branch| {
                 GithubPullRequest::for_test_cases_tif_react_frontend(cases, head_branch)
                     .for_testing_do_not_merge()
             },
-            locations_path: "./FitnessProjectTest/roswaal/Locations.ts".to_string(),
+            locations_path: Path::new(".")
+                .join("FitnessProjectTest")
+                .join("roswaal")
+                .join("Locations.ts"),
             add_locations_pr: |locations, head_branch| {
                 GithubPullRequest::for_locations_tif_react_frontend(locations, head_branch)
                     .for_testing_do_not_merge()
             },
-            remove_tests_pr: |test_names, head_branch| {
+            remove_tests_pr: |test_names, removed_tests, head_branch| {
                 GithubPullRequest::for_removing_test_cases_tif_react_frontend(
                     test_names,
-                    head_branch
+                    removed_tests,
+                    head_branch,
+                )
+                .for_testing_do_not_merge()
+            },
+            edit_test_pr: |diff, head_branch| {
+                GithubPullRequest::for_editing_test_case_tif_react_frontend(diff, head_branch)
+                    .for_testing_do_not_merge()
+            },
+            remove_locations_pr: |location_names, head_branch| {
+                GithubPullRequest::for_removing_locations_tif_react_frontend(
+                    location_names,
+                    head_branch,
                 )
                 .for_testing_do_not_merge()
-            }
+            },
+            remove_orphaned_tests_pr: |directory_names, head_branch| {
+                GithubPullRequest::for_removing_orphaned_tests_tif_react_frontend(
+                    directory_names,
+                    head_branch,
+                )
+                .for_testing_do_not_merge()
+            },
+            pull_request_repository: None,
+            pull_request_options: GithubPullRequestOptions::default(),
+            pull_request_body_template: GithubPullRequestBodyTemplate::default(),
+            branch_namespace: RoswaalGitBranchNamespace::default(),
+            client_kind: RoswaalGitClientKind::current(),
+            code_generator: CodeGeneratorKind::current(),
+            formatter_command: None,
+            clean_policy: RoswaalGitCleanPolicy::default(),
         }
     }
 }
 
 pub const TEST_REPO_BASE_BRANCH_NAME: &str = "main";
 
+fn github_ssh_url(owner: &str, repo: &str) -> String {
+    format!("git@github.com:{}/{}.git", owner, repo)
+}
+
+/// An error that occurs when a test target is not in the form `<owner>/<repo>@<base_branch>`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TestTargetParsingError(String);
+
+impl Display for TestTargetParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a valid test target, use the form <owner>/<repo>@<base_branch>.",
+            self.0
+        )
+    }
+}
+
+impl Error for TestTargetParsingError {}
+
 impl RoswaalGitRepositoryMetadata {
+    /// Returns a human readable name for this repository (eg. FitnessProject).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Returns the name of the branch that changes are primarily merged to (eg. development).
     pub fn base_branch_name(&self) -> &str {
         &self.base_branch_name
     }
 
+    /// Returns the url that the repo is cloned from when its root directory does not exist.
+    pub fn clone_url(&self) -> Option<&str> {
+        self.clone_url.as_deref()
+    }
+
     /// Returns a string path to the private ssh key to use when pushing and pulling changes from
     /// the remote repository.
     pub fn ssh_private_key_path(&self) -> String {
@@ -87,22 +276,65 @@ impl RoswaalGitRepositoryMetadata {
         format!("{}/{}", home, self.ssh_private_key_home_path)
     }
 
-    /// Returns a string path relative to the root directory of the repository.
-    pub fn relative_path(&self, path: &str) -> String {
-        format!("{}/{}", self.repo_root_dir_path, path)
+    /// Returns a path relative to the root directory of the repository.
+    ///
+    /// `path` is joined with the separator of the host, so it may contain non-UTF8 file names.
+    pub fn relative_path(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.repo_root_dir_path.join(path)
     }
 
     /// Returns the path to the locations file.
-    pub fn locations_path(&self) -> &str {
+    pub fn locations_path(&self) -> &Path {
         &self.locations_path
     }
 
+    /// Returns the path to the directory that holds a Maestro flow for every location.
+    ///
+    /// Maestro repos have no locations file, and regenerate this directory instead whenever
+    /// locations are added or removed.
+    pub fn location_flows_dirpath(&self) -> PathBuf {
+        self.test_cases_root_dir_path.join(maestro::LOCATIONS_DIR)
+    }
+
+    /// Returns the paths, relative to the root directory of the repository, of the files that are
+    /// entirely generated from the state in sqlite.
+    ///
+    /// Merge conflicts in these files are resolved by taking the incoming version, since the
+    /// operations that change them regenerate them from scratch anyway.
+    pub fn generated_file_paths(&self) -> Vec<PathBuf> {
+        self.locations_path
+            .strip_prefix(&self.repo_root_dir_path)
+            .map(|path| vec![path.to_path_buf()])
+            .unwrap_or_default()
+    }
+
+    /// Returns true if every path in `paths` is a generated file.
+    ///
+    /// `paths` are relative to the root directory of the repository. In Maestro repos, every
+    /// location flow is also a generated file.
+    pub fn are_all_generated_files(&self, paths: &[PathBuf]) -> bool {
+        let generated_paths = self.generated_file_paths();
+        let location_flows_dirpath = self
+            .location_flows_dirpath()
+            .strip_prefix(&self.repo_root_dir_path)
+            .map(|path| path.to_path_buf())
+            .ok();
+        !paths.is_empty()
+            && paths.iter().all(|path| {
+                generated_paths.contains(path)
+                    || (self.code_generator == CodeGeneratorKind::Maestro
+                        && location_flows_dirpath
+                            .as_ref()
+                            .is_some_and(|dirpath| path.starts_with(dirpath)))
+            })
+    }
+
     pub fn add_locations_pull_request(
         &self,
         locations: &RoswaalStringLocations,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> GithubPullRequest {
-        (self.add_locations_pr)(locations, branch_name)
+        self.targeted_pull_request((self.add_locations_pr)(locations, branch_name))
     }
 
     pub fn add_tests_pull_request(
@@ -110,19 +342,308 @@ impl RoswaalGitRepositoryMetadata {
         results: &RoswaalTestCompilationResults,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> GithubPullRequest {
-        (self.add_test_cases_pr)(results, branch_name)
+        self.targeted_pull_request((self.add_test_cases_pr)(results, branch_name))
     }
 
     pub fn remove_tests_pull_request<'a>(
         &self,
         test_names: &RoswaalTestNamesString,
+        removed_tests: &[RoswaalTest],
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> GithubPullRequest {
-        (self.remove_tests_pr)(test_names, branch_name)
+        self.targeted_pull_request((self.remove_tests_pr)(
+            test_names,
+            removed_tests,
+            branch_name,
+        ))
     }
 
-    pub fn test_dirpath(&self, test_name: &str) -> String {
-        let name = test_name.to_ascii_kebab_case().to_ascii_lowercase();
-        format!("{}/{}", self.test_cases_root_dir_path, name)
+    pub fn edit_test_pull_request(
+        &self,
+        diff: &RoswaalCompiledTestDiff,
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> GithubPullRequest {
+        self.targeted_pull_request((self.edit_test_pr)(diff, branch_name))
+    }
+
+    pub fn remove_locations_pull_request(
+        &self,
+        location_names: &[RoswaalLocationName],
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> GithubPullRequest {
+        self.targeted_pull_request((self.remove_locations_pr)(location_names, branch_name))
+    }
+
+    pub fn remove_orphaned_tests_pull_request(
+        &self,
+        directory_names: &[String],
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> GithubPullRequest {
+        self.targeted_pull_request((self.remove_orphaned_tests_pr)(
+            directory_names,
+            branch_name,
+        ))
+    }
+
+    fn targeted_pull_request(&self, pull_request: GithubPullRequest) -> GithubPullRequest {
+        let pull_request = pull_request.with_options(self.pull_request_options.clone());
+        let pull_request = match &self.pull_request_repository {
+            Some((owner, repo)) => pull_request.in_repository(owner, repo, &self.base_branch_name),
+            None => pull_request,
+        };
+        match GithubPullRequestBodyTemplate::from_repository(&self.repo_root_dir_path) {
+            Some(template) => pull_request.with_body_template(&template),
+            None => pull_request.with_body_template(&self.pull_request_body_template),
+        }
+    }
+
+    /// Returns this metadata with the draft state, labels, assignees, and reviewers to open PRs
+    /// with.
+    pub fn with_pull_request_options(self, options: GithubPullRequestOptions) -> Self {
+        Self {
+            pull_request_options: options,
+            ..self
+        }
+    }
+
+    /// Returns this metadata with the template to render the bodies of PRs with when the repo does
+    /// not have its own template file.
+    pub fn with_pull_request_body_template(self, template: GithubPullRequestBodyTemplate) -> Self {
+        Self {
+            pull_request_body_template: template,
+            ..self
+        }
+    }
+
+    /// Returns this metadata with the prefix and separator to name new branches with.
+    pub fn with_branch_namespace(self, namespace: RoswaalGitBranchNamespace) -> Self {
+        Self {
+            branch_namespace: namespace,
+            ..self
+        }
+    }
+
+    /// Returns this metadata with the generator used to produce the test code for this repository.
+    pub fn with_code_generator(self, code_generator: CodeGeneratorKind) -> Self {
+        Self {
+            code_generator,
+            ..self
+        }
+    }
+
+    /// Returns this metadata with the rules for which untracked files are kept when the repo is
+    /// cleaned.
+    pub fn with_clean_policy(self, policy: RoswaalGitCleanPolicy) -> Self {
+        Self {
+            clean_policy: policy,
+            ..self
+        }
+    }
+
+    /// Returns a new name for a branch of the specified kind in the namespace of this repository.
+    pub fn new_branch_name(&self, kind: RoswaalOwnedBranchKind) -> RoswaalOwnedGitBranchName {
+        self.branch_namespace.branch_name_for(kind)
+    }
+
+    /// Returns the kind of git client that should be used to operate on this repository.
+    pub fn client_kind(&self) -> RoswaalGitClientKind {
+        self.client_kind
+    }
+
+    /// Returns the generator used to produce the test code for this repository.
+    pub fn code_generator(&self) -> CodeGeneratorKind {
+        self.code_generator
+    }
+
+    /// Returns the command that formats the generated code in this repo (eg. `npx prettier
+    /// --write`), if any.
+    pub fn formatter_command(&self) -> Option<&str> {
+        self.formatter_command.as_deref()
+    }
+
+    /// Returns the rules for which untracked files are kept when this repo is cleaned.
+    pub fn clean_policy(&self) -> &RoswaalGitCleanPolicy {
+        &self.clean_policy
+    }
+
+    /// Returns the path to the directory that contains the generated code of every test.
+    pub fn test_cases_root_dirpath(&self) -> &Path {
+        &self.test_cases_root_dir_path
+    }
+
+    /// Returns the path to the directory of the generated code for the test named `test_name`.
+    pub fn test_dirpath(&self, test_name: &str) -> PathBuf {
+        self.test_cases_root_dir_path
+            .join(test_directory_name(test_name))
+    }
+}
+
+/// The kind of git client used to operate on a repository.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoswaalGitClientKind {
+    /// Uses libgit2 on a dedicated background thread.
+    LibGit2,
+    /// Shells out to the `git` binary installed on the host.
+    Process,
+}
+
+impl RoswaalGitClientKind {
+    /// Returns the client kind based on the `ROSWAAL_GIT_CLIENT` environment variable.
+    ///
+    /// If the variable is "process", then the `git` binary is used. Otherwise, libgit2 is used.
+    pub fn current() -> Self {
+        Self::from_env_value(env::var("ROSWAAL_GIT_CLIENT").ok().as_deref())
+    }
+
+    fn from_env_value(value: Option<&str>) -> Self {
+        match value {
+            Some("process") => Self::Process,
+            _ => Self::LibGit2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::{Path, PathBuf},
+        str::FromStr,
+    };
+
+    use crate::{
+        generation::interface::CodeGeneratorKind,
+        git::{
+            branch_name::RoswaalOwnedGitBranchName, pull_request::GithubPullRequestOptions,
+            pull_request_template::GithubPullRequestBodyTemplate,
+        },
+        location::name::RoswaalLocationName,
+    };
+
+    use super::{RoswaalGitClientKind, RoswaalGitRepositoryMetadata, TestTargetParsingError};
+
+    #[test]
+    fn location_flows_are_generated_files_only_in_maestro_repos() {
+        let paths = [
+            PathBuf::from("roswaal/locations/Oakland.yaml"),
+            PathBuf::from("roswaal/Locations.ts"),
+        ];
+        let metadata = RoswaalGitRepositoryMetadata::for_testing();
+        assert!(!metadata.are_all_generated_files(&paths));
+        let metadata = metadata.with_code_generator(CodeGeneratorKind::Maestro);
+        assert!(metadata.are_all_generated_files(&paths));
+        assert!(!metadata.are_all_generated_files(&[PathBuf::from("roswaal/a/TestCase.yaml")]));
+    }
+
+    #[test]
+    fn client_kind_from_env_value() {
+        assert_eq!(
+            RoswaalGitClientKind::from_env_value(Some("process")),
+            RoswaalGitClientKind::Process
+        );
+        assert_eq!(
+            RoswaalGitClientKind::from_env_value(Some("libgit2")),
+            RoswaalGitClientKind::LibGit2
+        );
+        assert_eq!(
+            RoswaalGitClientKind::from_env_value(None),
+            RoswaalGitClientKind::LibGit2
+        );
+    }
+
+    #[test]
+    fn test_targets_from_str() {
+        let targets = RoswaalGitRepositoryMetadata::test_targets_from_str(
+            "tifapp/FitnessProjectIOS@main, tifapp/FitnessProjectAndroid@development,",
+        )
+        .unwrap();
+        let targets = targets
+            .iter()
+            .map(|t| (t.name(), t.base_branch_name(), t.relative_path(".")))
+            .collect::<Vec<(&str, &str, PathBuf)>>();
+        assert_eq!(
+            targets,
+            vec![
+                (
+                    "FitnessProjectIOS",
+                    "main",
+                    Path::new(".").join("FitnessProjectIOS").join(".")
+                ),
+                (
+                    "FitnessProjectAndroid",
+                    "development",
+                    Path::new(".").join("FitnessProjectAndroid").join(".")
+                )
+            ]
+        )
+    }
+
+    #[test]
+    fn pull_requests_are_opened_with_pull_request_options() {
+        let options = GithubPullRequestOptions::new(
+            true,
+            vec!["acceptance-tests".to_string()],
+            vec!["roswaaltifbot".to_string()],
+            vec!["mhayes853".to_string()],
+        );
+        let metadata =
+            RoswaalGitRepositoryMetadata::for_test_target("tifapp", "FitnessProjectIOS", "main")
+                .with_pull_request_options(options.clone());
+        let pull_request = metadata.remove_locations_pull_request(
+            &[RoswaalLocationName::from_str("New York").unwrap()],
+            &RoswaalOwnedGitBranchName::for_removing_locations(),
+        );
+        assert_eq!(pull_request.options(), &options);
+        assert_eq!(pull_request.repo(), "FitnessProjectIOS")
+    }
+
+    #[test]
+    fn pull_request_bodies_are_rendered_with_pull_request_body_template() {
+        let template =
+            GithubPullRequestBodyTemplate::new("{{summary}}\n\nOpened in {{repository}}");
+        let metadata =
+            RoswaalGitRepositoryMetadata::for_test_target("tifapp", "FitnessProjectIOS", "main")
+                .with_pull_request_body_template(template);
+        let pull_request = metadata.remove_locations_pull_request(
+            &[RoswaalLocationName::from_str("New York").unwrap()],
+            &RoswaalOwnedGitBranchName::for_removing_locations(),
+        );
+        assert_eq!(
+            pull_request.body(),
+            format!(
+                "{}\n\nOpened in tifapp/FitnessProjectIOS",
+                pull_request.summary()
+            )
+        )
+    }
+
+    #[test]
+    fn test_targets_are_cloned_from_github_over_ssh() {
+        let metadata =
+            RoswaalGitRepositoryMetadata::for_test_target("tifapp", "FitnessProjectIOS", "main");
+        assert_eq!(
+            metadata.clone_url(),
+            Some("git@github.com:tifapp/FitnessProjectIOS.git")
+        )
+    }
+
+    #[test]
+    fn test_targets_from_str_empty() {
+        let targets = RoswaalGitRepositoryMetadata::test_targets_from_str("  ").unwrap();
+        assert!(targets.is_empty())
+    }
+
+    #[test]
+    fn test_targets_from_str_invalid() {
+        let invalid_targets = vec![
+            "tifapp/FitnessProjectIOS",
+            "FitnessProjectIOS@main",
+            "/@main",
+        ];
+        for target in invalid_targets {
+            assert_eq!(
+                RoswaalGitRepositoryMetadata::test_targets_from_str(target).err(),
+                Some(TestTargetParsingError(target.to_string()))
+            )
+        }
     }
 }
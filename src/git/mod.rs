@@ -1,7 +1,15 @@
+pub mod bootstrap;
 pub mod branch_name;
+pub mod clean;
 pub mod edit;
+pub mod health;
 pub mod metadata;
+pub mod pending_pull_request;
+pub mod process;
 pub mod pull_request;
+pub mod pull_request_template;
 pub mod repo;
+pub mod storage;
 #[cfg(test)]
 pub mod test_support;
+pub mod timeout;
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::{
+    utils::{config::RoswaalPullRequestMode, db::RoswaalDatabase},
+    with_transaction,
+};
+
+use super::pull_request::{GithubPullRequest, GithubPullRequestOpen};
+
+/// A `GithubPullRequestOpen` that saves PRs to be opened later with `/open-pending-prs` instead
+/// of opening them when in staged mode.
+///
+/// Branches are still committed and pushed in staged mode, so the generated code can be reviewed
+/// before anyone is asked to review a PR.
+pub struct StagingGithubPullRequestOpen<Open: GithubPullRequestOpen> {
+    open: Arc<Open>,
+    database: Arc<RoswaalDatabase>,
+    mode: RoswaalPullRequestMode,
+}
+
+impl<Open: GithubPullRequestOpen> StagingGithubPullRequestOpen<Open> {
+    pub fn new(
+        open: Arc<Open>,
+        database: Arc<RoswaalDatabase>,
+        mode: RoswaalPullRequestMode,
+    ) -> Self {
+        Self {
+            open,
+            database,
+            mode,
+        }
+    }
+}
+
+impl<Open: GithubPullRequestOpen + Send + Sync> GithubPullRequestOpen
+    for StagingGithubPullRequestOpen<Open>
+{
+    async fn open(&self, pull_request: &GithubPullRequest) -> Result<bool> {
+        match self.mode {
+            RoswaalPullRequestMode::Immediate => self.open.open(pull_request).await,
+            RoswaalPullRequestMode::Staged => {
+                let mut transaction = self.database.transaction().await?;
+                with_transaction!(transaction, async {
+                    transaction.save_pending_pull_request(pull_request).await
+                })?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::{
+        branch_name::RoswaalOwnedGitBranchName, test_support::TestGithubPullRequestOpen,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn opens_pull_requests_immediately_in_immediate_mode() {
+        let database = Arc::new(RoswaalDatabase::in_memory().await.unwrap());
+        let open = Arc::new(TestGithubPullRequestOpen::new(false));
+        let staging = StagingGithubPullRequestOpen::new(
+            open.clone(),
+            database.clone(),
+            RoswaalPullRequestMode::Immediate,
+        );
+        let pr = pull_request();
+        assert!(staging.open(&pr).await.unwrap());
+        assert_eq!(open.most_recent_pr().await, Some(pr));
+        let mut transaction = database.transaction().await.unwrap();
+        assert_eq!(transaction.pending_pull_requests().await.unwrap(), vec![])
+    }
+
+    #[tokio::test]
+    async fn saves_pull_requests_without_opening_them_in_staged_mode() {
+        let database = Arc::new(RoswaalDatabase::in_memory().await.unwrap());
+        let open = Arc::new(TestGithubPullRequestOpen::new(false));
+        let staging = StagingGithubPullRequestOpen::new(
+            open.clone(),
+            database.clone(),
+            RoswaalPullRequestMode::Staged,
+        );
+        let pr = pull_request();
+        assert!(staging.open(&pr).await.unwrap());
+        assert_eq!(open.most_recent_pr().await, None);
+        let mut transaction = database.transaction().await.unwrap();
+        assert_eq!(transaction.pending_pull_requests().await.unwrap(), vec![pr])
+    }
+
+    fn pull_request() -> GithubPullRequest {
+        GithubPullRequest::for_tif_react_frontend(
+            "Add Tests",
+            "Adds some tests",
+            &RoswaalOwnedGitBranchName::for_adding_tests(),
+        )
+    }
+}
@@ -0,0 +1,567 @@
+use std::{
+    error::Error,
+    ffi::OsStr,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::Output,
+    time::Duration,
+};
+
+use anyhow::Result;
+use tokio::{fs::remove_dir_all, process::Command};
+
+use crate::utils::{config::RoswaalConfig, fs::path_from_bytes};
+
+use super::{
+    branch_name::RoswaalOwnedGitBranchName,
+    clean::{remove_untracked_files, RoswaalLargeFileDeletion},
+    health::RoswaalGitRepositoryHealth,
+    metadata::RoswaalGitRepositoryMetadata,
+    repo::{
+        delete_cancelled_branch, PullBranchStatus, RoswaalGitRepositoryClient,
+        RoswaalGitRepositoryTransaction,
+    },
+    timeout::with_git_timeout,
+};
+
+/// A `RoswaalGitRepositoryClient` implementation that shells out to the `git` binary.
+///
+/// Unlike `LibGit2RepositoryClient`, authentication with the remote is handled entirely by the
+/// host's git and ssh configuration (eg. ssh-agent), and shallow clones are supported.
+///
+/// Each git subprocess is killed if it runs for longer than the git operation timeout.
+pub struct ProcessGitRepositoryClient {
+    metadata: RoswaalGitRepositoryMetadata,
+    timeout: Duration,
+}
+
+impl RoswaalGitRepositoryClient for ProcessGitRepositoryClient {
+    async fn try_new(metadata: &RoswaalGitRepositoryMetadata) -> Result<Self> {
+        let client = Self {
+            metadata: metadata.clone(),
+            timeout: RoswaalConfig::current().git_operation_timeout(),
+        };
+        client.run(&["rev-parse", "--git-dir"]).await?;
+        Ok(client)
+    }
+
+    fn metadata(&self) -> &RoswaalGitRepositoryMetadata {
+        &self.metadata
+    }
+
+    async fn hard_reset_to_head(&self) -> Result<()> {
+        self.run(&["reset", "--hard", "HEAD"]).await
+    }
+
+    async fn clean_all_untracked(&self, deletion: RoswaalLargeFileDeletion) -> Result<()> {
+        let args = ["ls-files", "--others", "--exclude-standard", "-z"];
+        let output = self.output(&args).await?;
+        if !output.status.success() {
+            return Err(GitProcessError::new(&args, &output).into());
+        }
+        // NB: Paths are separated by NUL instead of newlines so that file names are not quoted,
+        // and so that file names that are not UTF8 can still be cleaned.
+        let paths = output
+            .stdout
+            .split(|byte| *byte == 0)
+            .filter(|path| !path.is_empty())
+            .map(path_from_bytes)
+            .collect();
+        remove_untracked_files(&self.metadata, paths, deletion).await
+    }
+
+    async fn switch_branch(&self, name: &str) -> Result<()> {
+        self.run(&["switch", "--discard-changes", name]).await
+    }
+
+    async fn pull_branch(&self, name: &str) -> Result<PullBranchStatus> {
+        let output = self
+            .output(&["pull", "--no-rebase", "--no-edit", "origin", name])
+            .await?;
+        if output.status.success() {
+            return Ok(PullBranchStatus::Success);
+        }
+        let conflicts = self
+            .output(&["diff", "--name-only", "-z", "--diff-filter=U"])
+            .await?;
+        if conflicts.stdout.is_empty() {
+            return Err(GitProcessError::new(&["pull", "origin", name], &output).into());
+        }
+        let paths = conflicts
+            .stdout
+            .split(|byte| *byte == 0)
+            .filter(|path| !path.is_empty())
+            .map(path_from_bytes)
+            .collect::<Vec<PathBuf>>();
+        if !self.metadata.are_all_generated_files(&paths) {
+            return Ok(PullBranchStatus::MergeConflict);
+        }
+        // NB: Conflicts in generated files are mechanical, so the incoming version is kept as is and
+        // the merge is committed. The operations that change these files regenerate them from
+        // sqlite before committing.
+        let paths = paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<String>>();
+        let mut checkout_args = vec!["checkout", "--theirs", "--"];
+        checkout_args.extend(paths.iter().map(String::as_str));
+        self.run(&checkout_args).await?;
+        let mut add_args = vec!["add", "--all", "--"];
+        add_args.extend(paths.iter().map(String::as_str));
+        self.run(&add_args).await?;
+        self.run(&["commit", "--no-edit"]).await?;
+        Ok(PullBranchStatus::Success)
+    }
+
+    async fn commit_all(&self, message: &str) -> Result<()> {
+        self.run(&["add", "--all"]).await?;
+        self.run(&["commit", "--allow-empty", "-m", message]).await
+    }
+
+    async fn checkout_new_branch(&self, name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        self.run(&["checkout", "-b", &name.to_string()]).await
+    }
+
+    async fn push_changes(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        self.run(&["push", "origin", &branch_name.to_string()])
+            .await
+    }
+
+    async fn delete_local_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<bool> {
+        let reference = format!("refs/heads/{}", branch_name.to_string());
+        let output = self
+            .output(&["rev-parse", "--verify", "--quiet", &reference])
+            .await?;
+        if !output.status.success() {
+            return Ok(false);
+        }
+        self.run(&["branch", "-D", &branch_name.to_string()])
+            .await?;
+        Ok(true)
+    }
+
+    async fn delete_remote_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        self.run(&["push", "origin", "--delete", &branch_name.to_string()])
+            .await
+    }
+
+    fn delete_cancelled_branch(
+        transaction: RoswaalGitRepositoryTransaction<Self>,
+        branch_name: RoswaalOwnedGitBranchName,
+    ) {
+        tokio::spawn(delete_cancelled_branch(transaction, branch_name));
+    }
+
+    async fn remote_branch_names(&self) -> Result<Vec<String>> {
+        let args = ["ls-remote", "--heads", "origin"];
+        let output = self.output(&args).await?;
+        if !output.status.success() {
+            return Err(GitProcessError::new(&args, &output).into());
+        }
+        let names = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once("refs/heads/"))
+            .map(|(_, name)| name.trim().to_string())
+            .collect::<Vec<String>>();
+        Ok(names)
+    }
+
+    async fn local_branch_names(&self) -> Result<Vec<String>> {
+        let args = ["for-each-ref", "--format=%(refname:short)", "refs/heads"];
+        self.stdout_lines(&args).await
+    }
+
+    async fn health(&self) -> Result<RoswaalGitRepositoryHealth> {
+        let branch = self
+            .output(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+            .await?;
+        let current_branch_name = branch
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&branch.stdout).trim().to_string());
+        let dirty_paths = self
+            .stdout_lines(&["status", "--porcelain", "--untracked-files=all"])
+            .await?
+            .into_iter()
+            .filter_map(|line| line.get(3..).map(|path| path.to_string()))
+            .collect::<Vec<String>>();
+        let is_merging = self
+            .output(&["rev-parse", "--verify", "--quiet", "MERGE_HEAD"])
+            .await?
+            .status
+            .success();
+        Ok(RoswaalGitRepositoryHealth::new(
+            current_branch_name,
+            dirty_paths,
+            is_merging,
+        ))
+    }
+
+    async fn abort_merge(&self) -> Result<()> {
+        self.run(&["merge", "--abort"]).await
+    }
+
+    async fn reclone(&mut self) -> Result<()> {
+        let url = self.stdout_lines(&["remote", "get-url", "origin"]).await?;
+        let url = url.first().ok_or_else(|| GitProcessError {
+            command: "git remote get-url origin".to_string(),
+            stderr: "origin has no url.".to_string(),
+        })?;
+        let path = self.metadata.relative_path("");
+        remove_dir_all(&path).await?;
+        let args = [OsStr::new("clone"), OsStr::new(url), path.as_os_str()];
+        let output = self
+            .command_output(Command::new("git").args(args), &args)
+            .await?;
+        if !output.status.success() {
+            return Err(GitProcessError::new(&args, &output).into());
+        }
+        Ok(())
+    }
+}
+
+impl ProcessGitRepositoryClient {
+    async fn run(&self, args: &[&str]) -> Result<()> {
+        let output = self.output(args).await?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(GitProcessError::new(args, &output).into())
+        }
+    }
+
+    async fn stdout_lines(&self, args: &[&str]) -> Result<Vec<String>> {
+        let output = self.output(args).await?;
+        if !output.status.success() {
+            return Err(GitProcessError::new(args, &output).into());
+        }
+        let lines = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>();
+        Ok(lines)
+    }
+
+    async fn output(&self, args: &[&str]) -> Result<Output> {
+        let mut command = Command::new("git");
+        command
+            .arg("-C")
+            .arg(self.metadata.relative_path("."))
+            .args(args);
+        self.command_output(&mut command, args).await
+    }
+
+    async fn command_output(
+        &self,
+        command: &mut Command,
+        args: &[impl AsRef<OsStr>],
+    ) -> Result<Output> {
+        // NB: The child process is killed when the timeout drops the future that waits on it.
+        let output = command.kill_on_drop(true).output();
+        let name = format!("git {}", joined_args(args));
+        with_git_timeout(&name, self.timeout, async { Ok(output.await?) }).await
+    }
+}
+
+/// Clones `url` into `path` with the `git` binary, authenticating through the host's git and ssh
+/// configuration.
+pub async fn clone_with_git_binary(url: &str, path: &Path) -> Result<()> {
+    let args = [OsStr::new("clone"), OsStr::new(url), path.as_os_str()];
+    let output = Command::new("git")
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(GitProcessError::new(&args, &output).into());
+    }
+    Ok(())
+}
+
+/// Deletes `branch_name` from the `origin` remote of the repo at `path` with the `git` binary.
+#[cfg(feature = "e2e")]
+pub async fn delete_remote_branch_with_git_binary(
+    path: &Path,
+    branch_name: &RoswaalOwnedGitBranchName,
+) -> Result<()> {
+    let branch_name = branch_name.to_string();
+    let args = [
+        OsStr::new("-C"),
+        path.as_os_str(),
+        OsStr::new("push"),
+        OsStr::new("origin"),
+        OsStr::new("--delete"),
+        OsStr::new(&branch_name),
+    ];
+    let output = Command::new("git")
+        .args(args)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(GitProcessError::new(&args, &output).into());
+    }
+    Ok(())
+}
+
+/// An error that occurs when a git subprocess exits unsuccessfully.
+#[derive(Debug)]
+pub struct GitProcessError {
+    command: String,
+    stderr: String,
+}
+
+/// Messages that git prints when it cannot reach the remote.
+const TRANSIENT_STDERR_MESSAGES: [&str; 8] = [
+    "could not resolve host",
+    "connection timed out",
+    "operation timed out",
+    "connection reset",
+    "connection refused",
+    "the remote end hung up unexpectedly",
+    "early eof",
+    "temporary failure",
+];
+
+impl GitProcessError {
+    fn new(args: &[impl AsRef<OsStr>], output: &Output) -> Self {
+        Self {
+            command: format!("git {}", joined_args(args)),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }
+    }
+
+    /// Returns true if the command failed because the remote could not be reached.
+    pub fn is_transient(&self) -> bool {
+        let stderr = self.stderr.to_lowercase();
+        TRANSIENT_STDERR_MESSAGES
+            .iter()
+            .any(|message| stderr.contains(message))
+    }
+}
+
+/// Joins `args` with spaces for logs and error messages, replacing any non-UTF8 characters.
+fn joined_args(args: &[impl AsRef<OsStr>]) -> String {
+    args.iter()
+        .map(|arg| arg.as_ref().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl Display for GitProcessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` failed: {}", self.command, self.stderr)
+    }
+}
+
+impl Error for GitProcessError {}
+
+#[cfg(test)]
+mod tests {
+    use tokio::fs::{create_dir_all, try_exists, File};
+
+    use super::*;
+    use crate::git::{
+        repo::RoswaalGitRepository,
+        test_support::{read_string, with_clean_test_repo_access, write_string},
+    };
+
+    #[test]
+    fn test_unreachable_remote_errors_are_transient() {
+        let error = |stderr: &str| GitProcessError {
+            command: "git push origin main".to_string(),
+            stderr: stderr.to_string(),
+        };
+        let unreachable = error("fatal: unable to access: Could not resolve host: github.com");
+        let rejected = error("! [rejected] main -> main (non-fast-forward)");
+        assert!(unreachable.is_transient());
+        assert!(!rejected.is_transient())
+    }
+
+    #[tokio::test]
+    async fn test_reset_hard_to_head() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            write_string(metadata.locations_path(), "console.log(\"Hello world\")").await?;
+
+            transaction.hard_reset_to_head().await?;
+
+            assert!(read_string(metadata.locations_path()).await?.is_empty());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_clean_all_untracked() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            File::create(metadata.relative_path("test.txt")).await?;
+            create_dir_all(metadata.relative_path("roswaal/nested/test-clean")).await?;
+            File::create(metadata.relative_path("roswaal/nested/test-clean/test2.txt")).await?;
+
+            transaction
+                .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+                .await?;
+
+            assert!(!try_exists(metadata.relative_path("test.txt")).await?);
+            assert!(!try_exists(metadata.relative_path("roswaal/nested")).await?);
+            assert!(try_exists(metadata.relative_path("roswaal")).await?);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_local_branch_that_exists_returns_true_when_deleted_properly() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            let branch_name = RoswaalOwnedGitBranchName::new("test");
+            transaction.checkout_new_branch(&branch_name).await?;
+            transaction.switch_branch("main").await?;
+            assert!(transaction.delete_local_branch(&branch_name).await?);
+            let switch_to_deleted = transaction.switch_branch(&branch_name.to_string()).await;
+            assert!(switch_to_deleted.is_err());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_remote_branch_names_includes_pushed_branch() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            let branch_name = RoswaalOwnedGitBranchName::new("test");
+            transaction.checkout_new_branch(&branch_name).await?;
+            transaction.push_changes(&branch_name).await?;
+            transaction.switch_branch("main").await?;
+            let names = transaction.remote_branch_names().await?;
+            assert!(names.contains(&branch_name.to_string()));
+            assert!(names.contains(&"main".to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_remote_branch_removes_pushed_branch() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            let branch_name = RoswaalOwnedGitBranchName::new("test");
+            transaction.checkout_new_branch(&branch_name).await?;
+            transaction.push_changes(&branch_name).await?;
+            transaction.switch_branch("main").await?;
+            transaction.delete_remote_branch(&branch_name).await?;
+            let names = transaction.remote_branch_names().await?;
+            assert!(!names.contains(&branch_name.to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_local_branch_returns_false_for_non_existent_branch() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            let branch_name = RoswaalOwnedGitBranchName::new("test");
+            assert!(!transaction.delete_local_branch(&branch_name).await?);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+    #[tokio::test]
+    async fn test_abort_merge_after_merge_conflict() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            let b1 = RoswaalOwnedGitBranchName::new("test-process-abort-1");
+            let b2 = RoswaalOwnedGitBranchName::new("test-process-abort-2");
+
+            let path = metadata.relative_path("test.ts");
+            transaction.checkout_new_branch(&b1).await?;
+            write_string(&path, "console.log(\"Hello world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction.switch_branch("main").await?;
+            transaction.checkout_new_branch(&b2).await?;
+            write_string(&path, "console.log(\"Goodbye world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction.push_changes(&b2).await?;
+            transaction.switch_branch(&b1.to_string()).await?;
+            transaction.pull_branch(&b2.to_string()).await?;
+
+            let health = transaction.health().await?;
+            assert!(health.is_merging());
+            assert_eq!(health.dirty_paths().len(), 1);
+
+            transaction.abort_merge().await?;
+
+            let health = transaction.health().await?;
+            assert_eq!(health, RoswaalGitRepositoryHealth::clean(&b1.to_string()));
+            assert!(transaction
+                .local_branch_names()
+                .await?
+                .contains(&b2.to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pull_takes_incoming_version_of_conflicting_generated_locations_file() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let repo = RoswaalGitRepository::<ProcessGitRepositoryClient>::open(&metadata).await?;
+            let transaction = repo.transaction().await;
+
+            let b1 = RoswaalOwnedGitBranchName::new("test-process-generated-1");
+            let b2 = RoswaalOwnedGitBranchName::new("test-process-generated-2");
+
+            transaction.checkout_new_branch(&b1).await?;
+            write_string(metadata.locations_path(), "console.log(\"Hello world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction.switch_branch("main").await?;
+            transaction.checkout_new_branch(&b2).await?;
+            write_string(metadata.locations_path(), "console.log(\"Goodbye world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction.push_changes(&b2).await?;
+            transaction.switch_branch(&b1.to_string()).await?;
+
+            let status = transaction.pull_branch(&b2.to_string()).await?;
+            assert_eq!(status, PullBranchStatus::Success);
+            let content = read_string(metadata.locations_path()).await?;
+            assert_eq!(content, "console.log(\"Goodbye world\")");
+            let health = transaction.health().await?;
+            assert_eq!(health, RoswaalGitRepositoryHealth::clean(&b1.to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+}
@@ -1,58 +1,151 @@
-use std::{env, future::Future};
+use std::{
+    env,
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use reqwest::{
     header::{CONTENT_TYPE, USER_AGENT},
-    Client,
+    Client, Response, StatusCode,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    language::compilation_results::RoswaalTestCompilationResults,
-    location::location::{RoswaalLocationStringError, RoswaalStringLocations},
-    tests_data::query::RoswaalTestNamesString,
+    language::{compilation_results::RoswaalTestCompilationResults, diff::RoswaalCompiledTestDiff},
+    location::{
+        location::{RoswaalLocation, RoswaalStringLocations},
+        name::RoswaalLocationName,
+    },
+    tests_data::{query::RoswaalTestNamesString, test::RoswaalTest},
+    utils::{config::RoswaalConfig, retry::is_transient_io_error},
 };
 
-use super::branch_name::RoswaalOwnedGitBranchName;
+use super::{
+    branch_name::RoswaalOwnedGitBranchName, pull_request_template::GithubPullRequestBodyTemplate,
+};
 
 /// A serializeable type for a pull request on github.
-#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone)]
 pub struct GithubPullRequest {
     title: String,
     body: String,
+    /// The generated description that is rendered into the body by a
+    /// `GithubPullRequestBodyTemplate`.
+    #[serde(skip)]
+    summary: String,
     #[serde(skip)]
     owner: String,
     #[serde(skip)]
     repo: String,
     head: RoswaalOwnedGitBranchName,
     base: String,
+    #[serde(flatten)]
+    options: GithubPullRequestOptions,
 }
 
-impl GithubPullRequest {
-    /// Creates a PR for the main frontend repo.
-    pub fn for_tif_react_frontend(
-        title: &str,
-        body: &str,
-        head_branch: &RoswaalOwnedGitBranchName,
+impl PartialEq for GithubPullRequest {
+    fn eq(&self, other: &Self) -> bool {
+        // NB: The summary is not compared since it is already rendered into the body, and PRs
+        // loaded from the PendingPullRequests table only have their rendered body.
+        self.title == other.title
+            && self.body == other.body
+            && self.owner == other.owner
+            && self.repo == other.repo
+            && self.head == other.head
+            && self.base == other.base
+            && self.options == other.options
+    }
+}
+
+impl Eq for GithubPullRequest {}
+
+/// The draft state, labels, assignees, and reviewers of a pull request on github.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone, Default)]
+pub struct GithubPullRequestOptions {
+    draft: bool,
+    #[serde(skip)]
+    labels: Vec<String>,
+    #[serde(skip)]
+    assignees: Vec<String>,
+    #[serde(skip)]
+    reviewers: Vec<String>,
+}
+
+impl GithubPullRequestOptions {
+    pub fn new(
+        draft: bool,
+        labels: Vec<String>,
+        assignees: Vec<String>,
+        reviewers: Vec<String>,
     ) -> Self {
         Self {
-            body: format!(
-                "{}
+            draft,
+            labels,
+            assignees,
+            reviewers,
+        }
+    }
 
-## Tickets
+    /// The options specified by the `[pull_requests]` table of the current `RoswaalConfig`.
+    pub fn from_config() -> Self {
+        let config = RoswaalConfig::current();
+        Self::new(
+            config.draft_pull_requests(),
+            config.pull_request_labels().to_vec(),
+            config.pull_request_assignees().to_vec(),
+            config.pull_request_reviewers().to_vec(),
+        )
+    }
+}
 
-TASK_UNTRACKED
-",
-                body
-            ),
-            title: format!("Roswaal: {}", title),
-            owner: "tifapp".to_string(),
-            repo: "FitnessProject".to_string(),
-            base: "development".to_string(),
+impl GithubPullRequestOptions {
+    /// Returns true if the PR is opened as a draft.
+    pub fn is_draft(&self) -> bool {
+        self.draft
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn assignees(&self) -> &[String] {
+        &self.assignees
+    }
+
+    pub fn reviewers(&self) -> &[String] {
+        &self.reviewers
+    }
+}
+
+impl GithubPullRequest {
+    /// Creates a PR with the specified title and body for the main frontend repo.
+    pub fn new(title: &str, body: &str, head_branch: &RoswaalOwnedGitBranchName) -> Self {
+        let config = RoswaalConfig::current();
+        Self {
+            title: title.to_string(),
+            body: body.to_string(),
+            summary: body.to_string(),
+            owner: config.frontend_owner().to_string(),
+            repo: config.frontend_repo().to_string(),
+            base: config.frontend_base_branch_name().to_string(),
             head: head_branch.clone(),
+            options: GithubPullRequestOptions::default(),
         }
     }
 
+    /// Creates a PR for the main frontend repo whose body is `summary` rendered with the default
+    /// `GithubPullRequestBodyTemplate`.
+    pub fn for_tif_react_frontend(
+        title: &str,
+        summary: &str,
+        head_branch: &RoswaalOwnedGitBranchName,
+    ) -> Self {
+        Self::new(&format!("Roswaal: {}", title), summary, head_branch)
+            .with_body_template(&GithubPullRequestBodyTemplate::default())
+    }
+
     /// Creates a PR associated with adding new locations to the main frontend repo.
     pub fn for_locations_tif_react_frontend(
         string_locations: &RoswaalStringLocations,
@@ -62,31 +155,60 @@ TASK_UNTRACKED
             "Add Locations ({})",
             string_locations.raw_names().join(", ")
         );
-        let mut body =
-            "Adds the following locations to the acceptance teeeeeeeeeests:\n".to_string();
-        for location in string_locations.locations() {
-            let line = format!(
-                "- **{}** (Latitude: {:.16}, Longitude: {:.16})\n",
-                location.name().raw_name(),
-                location.coordinate().latitude(),
-                location.coordinate().longitude()
-            );
-            body.push_str(&line)
+        let location_lines = |locations: Vec<RoswaalLocation>| {
+            locations
+                .iter()
+                .map(|location| {
+                    let region = location
+                        .region()
+                        .map(|region| format!(", Region: {}", region.raw_name()))
+                        .unwrap_or_default();
+                    format!(
+                        "- **{}** (Latitude: {:.16}, Longitude: {:.16}{})\n",
+                        location.name().raw_name(),
+                        location.coordinate().latitude(),
+                        location.coordinate().longitude(),
+                        region
+                    )
+                })
+                .collect::<String>()
+        };
+        let mut sections = Vec::new();
+        let added_locations = string_locations.added_locations();
+        if !added_locations.is_empty() {
+            sections.push(format!(
+                "Adds the following locations to the acceptance teeeeeeeeeests:\n{}",
+                location_lines(added_locations)
+            ));
+        }
+        let updated_locations = string_locations.updated_locations();
+        if !updated_locations.is_empty() {
+            sections.push(format!(
+                "Updates the following locations that are already in the acceptance teeeeeeeeeests:\n{}",
+                location_lines(updated_locations)
+            ));
+        }
+        let unchanged_locations = string_locations.unchanged_locations();
+        if !unchanged_locations.is_empty() {
+            let names = unchanged_locations
+                .iter()
+                .map(|l| format!("- **{}**\n", l.name().raw_name()))
+                .collect::<String>();
+            sections.push(format!(
+                "The following locations are already in the acceptance tests, and were left unchaaaaaanged:\n{}",
+                names
+            ));
         }
+        let mut body = sections.join("\n");
         let errors = string_locations.errors();
         if !errors.is_empty() {
             body.push_str("\nThe following locations were specified in the slack command, but are invaaaaaaaalid:\n");
             for error in errors {
-                body.push_str(&format!("- **{}** ", error.raw_associated_location_name()));
-                match error {
-                    RoswaalLocationStringError::InvalidName(_, _) => {
-                        body.push_str("(Invalid Name)")
-                    }
-                    RoswaalLocationStringError::InvalidCoordinate { name: _ } => {
-                        body.push_str("(Invalid Coordinate)")
-                    }
-                };
-                body.push_str("\n")
+                body.push_str(&format!(
+                    "- **{}** ({})\n",
+                    error.raw_associated_location_name(),
+                    error.reason()
+                ))
             }
         }
         Self::for_tif_react_frontend(&title, &body, head_branch)
@@ -115,8 +237,12 @@ TASK_UNTRACKED
     }
 
     /// Creates a PR for removing test cases on the frontend repo.
+    ///
+    /// The last known spec of each of `removed_tests` is listed in a collapsible section, so that
+    /// reviewers can see what coverage is lost.
     pub fn for_removing_test_cases_tif_react_frontend(
         test_names: &RoswaalTestNamesString<'_>,
+        removed_tests: &[RoswaalTest],
         head_branch: &RoswaalOwnedGitBranchName,
     ) -> Self {
         let title = format!(
@@ -128,9 +254,83 @@ TASK_UNTRACKED
             .map(|n| format!("- {}", n))
             .collect::<Vec<String>>()
             .join("\n");
-        let body = format!("Removes the following teeeeeeeests!\n{}", test_names_list);
+        let mut body = format!("Removes the following teeeeeeeests!\n{}", test_names_list);
+        if !removed_tests.is_empty() {
+            body.push_str("\n\nThe removed specs were:\n");
+            for test in removed_tests {
+                body.push_str(&format!(
+                    "\n<details>\n<summary>{}</summary>\n\n```\n{}\n```\n\n</details>\n",
+                    test.name(),
+                    test.original_syntax()
+                ))
+            }
+        }
         Self::for_tif_react_frontend(&title, &body, &head_branch)
     }
+
+    /// Creates a PR for removing locations on the frontend repo.
+    pub fn for_removing_locations_tif_react_frontend(
+        location_names: &[RoswaalLocationName],
+        head_branch: &RoswaalOwnedGitBranchName,
+    ) -> Self {
+        let title = format!(
+            "Remove Locations ({})",
+            location_names
+                .iter()
+                .map(|n| n.raw_name())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        );
+        let location_names_list = location_names
+            .iter()
+            .map(|n| format!("- **{}**", n.raw_name()))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let body = format!(
+            "Removes the following locations from the acceptance teeeeeeeeeests:\n{}",
+            location_names_list
+        );
+        Self::for_tif_react_frontend(&title, &body, head_branch)
+    }
+
+    /// Creates a PR for removing generated test directories that do not belong to any stored
+    /// test on the frontend repo.
+    pub fn for_removing_orphaned_tests_tif_react_frontend(
+        directory_names: &[String],
+        head_branch: &RoswaalOwnedGitBranchName,
+    ) -> Self {
+        let title = format!("Remove Orphaned Tests ({})", directory_names.join(", "));
+        let directory_names_list = directory_names
+            .iter()
+            .map(|n| format!("- `{}`", n))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let body = format!(
+            "Removes the following test directories that no longer belong to any teeeeeests:\n{}",
+            directory_names_list
+        );
+        Self::for_tif_react_frontend(&title, &body, head_branch)
+    }
+
+    /// Creates a PR for editing an existing test case on the frontend repo.
+    pub fn for_editing_test_case_tif_react_frontend(
+        diff: &RoswaalCompiledTestDiff,
+        head_branch: &RoswaalOwnedGitBranchName,
+    ) -> Self {
+        let title = format!("Edit Test \"{}\"", diff.test_name());
+        let mut body = format!(
+            "Edits the following teeeeeest!\n\n**{}**\n\n```diff\n{}\n```",
+            diff.test_name(),
+            diff.unified_diff()
+        );
+        if let Some(description) = diff.changed_description() {
+            body.push_str(&format!(
+                "\n\nThe description was changed to: {}",
+                description.map(|d| d.as_str()).unwrap_or("_(None)_")
+            ));
+        }
+        Self::for_tif_react_frontend(&title, &body, head_branch)
+    }
 }
 
 impl GithubPullRequest {
@@ -144,17 +344,72 @@ impl GithubPullRequest {
         &self.head
     }
 
+    /// Returns the body of this PR.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Returns the generated description of this PR without the surrounding template.
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Returns the GitHub owner of the repository that this PR is opened on.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Returns the name of the repository that this PR is opened on.
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    /// Returns the branch that this PR is opened against.
+    pub fn base_branch_name(&self) -> &str {
+        &self.base
+    }
+
+    /// Returns the draft state, labels, assignees, and reviewers of this PR.
+    pub fn options(&self) -> &GithubPullRequestOptions {
+        &self.options
+    }
+
+    /// Returns this PR with the specified draft state, labels, assignees, and reviewers.
+    pub fn with_options(self, options: GithubPullRequestOptions) -> Self {
+        Self { options, ..self }
+    }
+
+    /// Returns this PR with its body rendered from its summary by `template`.
+    ///
+    /// Since the template can refer to the repository of this PR, this should be called after
+    /// `in_repository`.
+    pub fn with_body_template(self, template: &GithubPullRequestBodyTemplate) -> Self {
+        Self {
+            body: template.render(&self),
+            ..self
+        }
+    }
+
+    /// Returns this PR opened against the base branch of the specified GitHub repository.
+    pub fn in_repository(self, owner: &str, repo: &str, base_branch_name: &str) -> Self {
+        Self {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            base: base_branch_name.to_string(),
+            ..self
+        }
+    }
+
     /// Designates this PR specifically for testing and adjusts the title and body to disclaim
     /// that it should not be merged.
     ///
     /// This is useful for E2E tests.
     pub fn for_testing_do_not_merge(self) -> Self {
+        let disclaimer = "This is a test PR, please do not meeeeeeerge!!!\n\n";
         Self {
             title: format!("[Test - DO NOT MERGE] {}", self.title),
-            body: format!(
-                "This is a test PR, please do not meeeeeeerge!!!\n\n{}",
-                self.body
-            ),
+            body: format!("{}{}", disclaimer, self.body),
+            summary: format!("{}{}", disclaimer, self.summary),
             owner: "roswaaltifbot".to_string(),
             repo: "FitnessProjectTest".to_string(),
             base: "main".to_string(),
@@ -165,6 +420,9 @@ impl GithubPullRequest {
 
 pub trait GithubPullRequestOpen {
     /// Opens a PR on github, and returns true if it was created successfully.
+    ///
+    /// An error is returned instead of false when the PR may be opened by trying again, which can
+    /// be checked with `is_transient_github_error`.
     fn open(&self, pull_request: &GithubPullRequest) -> impl Future<Output = Result<bool>> + Send;
 }
 
@@ -183,24 +441,233 @@ impl GithubPullRequestOpen for Client {
             .json(&pull_request)
             .send()
             .await?;
-        if !response.status().is_success() {
-            log::error!("Failed to open PR with status code {}.", response.status());
+        let Some(response) = github_success_response(response, "open PR")? else {
             return Ok(false);
+        };
+        let GithubPullRequestNumber { number } = response.json().await?;
+        // NB: The PR is already open, so failing to apply the remaining options is only logged
+        // since retrying would attempt to open the same PR again.
+        if let Err(error) = apply_pull_request_options(self, pull_request, number).await {
+            log::error!(
+                "Failed to apply the labels, assignees, or reviewers to PR #{}. {}",
+                number,
+                error
+            );
         }
         Ok(true)
     }
 }
 
+pub trait GithubPullRequestComment {
+    /// Comments on the open PR for the head branch of `pull_request`, and returns true if the
+    /// comment was posted successfully.
+    ///
+    /// False is returned when the PR has not been opened. Like `GithubPullRequestOpen`, an error is
+    /// returned instead of false when the comment may be posted by trying again.
+    fn comment(
+        &self,
+        pull_request: &GithubPullRequest,
+        body: &str,
+    ) -> impl Future<Output = Result<bool>> + Send;
+}
+
+#[derive(Deserialize)]
+struct GithubPullRequestNumber {
+    number: u64,
+}
+
+/// Adds the labels, assignees, and reviewers of `pull_request` to the opened PR with `number`.
+///
+/// The draft state is not applied here since it is given when the PR is opened.
+async fn apply_pull_request_options(
+    client: &Client,
+    pull_request: &GithubPullRequest,
+    number: u64,
+) -> Result<()> {
+    let options = &pull_request.options;
+    let repo_url = format!(
+        "https://api.github.com/repos/{}/{}",
+        pull_request.owner, pull_request.repo
+    );
+    if !options.labels.is_empty() || !options.assignees.is_empty() {
+        let response = client
+            .patch(format!("{}/issues/{}", repo_url, number))
+            .bearer_auth(env::var("GITHUB_API_KEY").unwrap())
+            .header(CONTENT_TYPE, "application/json")
+            .header(USER_AGENT, "roswaal-tif-bot")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({
+                "labels": options.labels,
+                "assignees": options.assignees
+            }))
+            .send()
+            .await?;
+        github_success_response(response, "add labels and assignees")?;
+    }
+    if !options.reviewers.is_empty() {
+        let response = client
+            .post(format!("{}/pulls/{}/requested_reviewers", repo_url, number))
+            .bearer_auth(env::var("GITHUB_API_KEY").unwrap())
+            .header(CONTENT_TYPE, "application/json")
+            .header(USER_AGENT, "roswaal-tif-bot")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "reviewers": options.reviewers }))
+            .send()
+            .await?;
+        github_success_response(response, "request reviewers")?;
+    }
+    Ok(())
+}
+
+impl GithubPullRequestComment for Client {
+    async fn comment(&self, pull_request: &GithubPullRequest, body: &str) -> Result<bool> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/pulls",
+            pull_request.owner, pull_request.repo
+        );
+        let head = format!("{}:{}", pull_request.owner, pull_request.head.to_string());
+        let response = self
+            .get(url)
+            .query(&[("head", head.as_str()), ("state", "open")])
+            .bearer_auth(env::var("GITHUB_API_KEY").unwrap())
+            .header(USER_AGENT, "roswaal-tif-bot")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await?;
+        let Some(response) = github_success_response(response, "find PR")? else {
+            return Ok(false);
+        };
+        let numbers = response.json::<Vec<GithubPullRequestNumber>>().await?;
+        let Some(GithubPullRequestNumber { number }) = numbers.first() else {
+            log::error!(
+                "No open PR was found for {} to comment on.",
+                pull_request.head.to_string()
+            );
+            return Ok(false);
+        };
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/issues/{}/comments",
+            pull_request.owner, pull_request.repo, number
+        );
+        let response = self
+            .post(url)
+            .bearer_auth(env::var("GITHUB_API_KEY").unwrap())
+            .header(CONTENT_TYPE, "application/json")
+            .header(USER_AGENT, "roswaal-tif-bot")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await?;
+        Ok(github_success_response(response, "comment on PR")?.is_some())
+    }
+}
+
+pub trait GithubCheckReachability {
+    /// Sends a cheap authenticated request to the GitHub API, and returns an error if it was not
+    /// successful.
+    fn check_reachability(&self) -> impl Future<Output = Result<()>> + Send;
+}
+
+impl GithubCheckReachability for Client {
+    async fn check_reachability(&self) -> Result<()> {
+        // NB: Requests for the rate limit status do not count against the rate limit.
+        let response = self
+            .get("https://api.github.com/rate_limit")
+            .bearer_auth(env::var("GITHUB_API_KEY").unwrap_or_default())
+            .header(USER_AGENT, "roswaal-tif-bot")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await?;
+        let status = response.status();
+        match github_success_response(response, "check the rate limit")? {
+            Some(_) => Ok(()),
+            None => Err(anyhow!(
+                "GitHub rejected the request with status code {}.",
+                status
+            )),
+        }
+    }
+}
+
+/// Returns `response` if it was successful, or None if GitHub rejected the request to `action`.
+///
+/// A `GithubUnavailableError` is returned if GitHub is down or rate limiting requests.
+fn github_success_response(response: Response, action: &str) -> Result<Option<Response>> {
+    let status = response.status();
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(GithubUnavailableError::new(status).into());
+    }
+    if !status.is_success() {
+        log::error!("Failed to {} with status code {}.", action, status);
+        return Ok(None);
+    }
+    Ok(Some(response))
+}
+
+/// An error for a GitHub API request that was rejected because GitHub is down or rate limiting
+/// requests.
+#[derive(Debug)]
+pub struct GithubUnavailableError {
+    status: StatusCode,
+}
+
+impl GithubUnavailableError {
+    pub fn new(status: StatusCode) -> Self {
+        Self { status }
+    }
+}
+
+impl Display for GithubUnavailableError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "GitHub is unavailable with status code {}.", self.status)
+    }
+}
+
+impl Error for GithubUnavailableError {}
+
+/// Returns true if `error` came from a GitHub API request that may succeed if sent again.
+pub fn is_transient_github_error(error: &anyhow::Error) -> bool {
+    is_transient_io_error(error)
+        || error.chain().any(|cause| {
+            if cause.is::<GithubUnavailableError>() {
+                return true;
+            }
+            cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|error| error.is_timeout() || error.is_connect())
+        })
+}
+
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use crate::{
         git::branch_name::{self, RoswaalOwnedGitBranchName},
-        language::{ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults},
-        location::location::RoswaalStringLocations,
+        language::{
+            ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults,
+            test::RoswaalCompiledTestCommand,
+        },
+        location::{
+            location::{RoswaalLocation, RoswaalStringLocations},
+            name::RoswaalLocationName,
+        },
         tests_data::query::RoswaalTestNamesString,
     };
 
-    use super::GithubPullRequest;
+    use super::*;
+
+    #[test]
+    fn test_unavailable_github_errors_are_transient() {
+        let unavailable =
+            anyhow::Error::new(GithubUnavailableError::new(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient_github_error(&unavailable));
+        assert!(is_transient_github_error(
+            &unavailable.context("Opening PR")
+        ));
+        let other = anyhow::Error::new(crate::utils::test_error::TestError);
+        assert!(!is_transient_github_error(&other))
+    }
 
     #[test]
     fn test_do_not_merge_specifies_do_not_merge_in_title_and_body() {
@@ -216,6 +683,41 @@ mod tests {
             .starts_with("This is a test PR, please do not meeeeeeerge!!!\n\n"))
     }
 
+    #[test]
+    fn test_only_draft_option_is_serialized_when_opening() {
+        let pr = GithubPullRequest::for_tif_react_frontend(
+            "Hello",
+            "World",
+            &RoswaalOwnedGitBranchName::new("test-branch"),
+        )
+        .with_options(GithubPullRequestOptions::new(
+            true,
+            vec!["acceptance-tests".to_string()],
+            vec!["roswaaltifbot".to_string()],
+            vec![],
+        ));
+        let json = serde_json::to_value(&pr).unwrap();
+        assert_eq!(json["draft"], serde_json::Value::Bool(true));
+        assert_eq!(json.get("labels"), None);
+        assert_eq!(json.get("assignees"), None);
+        assert_eq!(json["title"], "Roswaal: Hello")
+    }
+
+    #[test]
+    fn test_in_repository_only_changes_repository_and_base_branch() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test-branch");
+        let pr = GithubPullRequest::for_tif_react_frontend("Hello", "World", &branch_name);
+        let moved_pr = pr
+            .clone()
+            .in_repository("tifapp", "FitnessProjectIOS", "main");
+        assert_eq!(moved_pr.owner, "tifapp");
+        assert_eq!(moved_pr.repo, "FitnessProjectIOS");
+        assert_eq!(moved_pr.base, "main");
+        assert_eq!(moved_pr.title, pr.title);
+        assert_eq!(moved_pr.body, pr.body);
+        assert_eq!(moved_pr.head, pr.head)
+    }
+
     #[test]
     fn test_from_string_locations_with_invalid_locations() {
         let locations_str = "
@@ -243,6 +745,22 @@ The following locations were specified in the slack command, but are invaaaaaaaa
         assert!(pr.body.contains(expected_body));
     }
 
+    #[test]
+    fn test_from_string_locations_with_out_of_range_coordinate() {
+        let locations_str = "
+Test 1, 45.0, 4.0
+San Francisco, 122.39, 37.77
+            ";
+        let string_locations = RoswaalStringLocations::from_roswaal_locations_str(locations_str);
+        let branch_name = RoswaalOwnedGitBranchName::new("test-locations-branch");
+        let pr =
+            GithubPullRequest::for_locations_tif_react_frontend(&string_locations, &branch_name);
+        let expected_body = "The following locations were specified in the slack command, but are invaaaaaaaalid:
+- **San Francisco** (Coordinate Out of Range: 122.39, 37.77. Latitude must be between -90 and 90, and longitude must be between -180 and 180)
+";
+        assert!(pr.body.contains(expected_body));
+    }
+
     #[test]
     fn test_from_string_locations_only_valid_locations_omits_invalid_section() {
         let locations_str = "
@@ -264,6 +782,34 @@ Test 2, -78.290782973, 54.309983793
         assert!(pr.body.contains(expected_body));
     }
 
+    #[test]
+    fn test_from_string_locations_separates_updated_and_unchanged_locations() {
+        let locations_str = "
+Test 1, 45.0, 4.0
+Test 2, 10.0, 10.0
+Test 3, 20.0, 20.0
+            ";
+        let merged_locations = [
+            RoswaalLocation::new_without_validation("Test 2", 10.0, 10.0),
+            RoswaalLocation::new_without_validation("Test 3", 30.0, 30.0),
+        ];
+        let string_locations = RoswaalStringLocations::from_roswaal_locations_str(locations_str)
+            .with_merged_locations(&merged_locations.iter().collect::<Vec<&RoswaalLocation>>());
+        let branch_name = RoswaalOwnedGitBranchName::new("test-locations-branch");
+        let pr =
+            GithubPullRequest::for_locations_tif_react_frontend(&string_locations, &branch_name);
+        let expected_body = "Adds the following locations to the acceptance teeeeeeeeeests:
+- **Test 1** (Latitude: 45.0000000000000000, Longitude: 4.0000000000000000)
+
+Updates the following locations that are already in the acceptance teeeeeeeeeests:
+- **Test 3** (Latitude: 20.0000000000000000, Longitude: 20.0000000000000000)
+
+The following locations are already in the acceptance tests, and were left unchaaaaaanged:
+- **Test 2**
+";
+        assert!(pr.body.contains(expected_body));
+    }
+
     #[test]
     fn test_from_multiple_test_cases() {
         let test1 = "New Test: I am the test
@@ -277,7 +823,7 @@ Requirement 3: Do more stuff";
 Step 1: A
 Requirement 1: B";
         let results = RoswaalTestCompilationResults::compile(
-            &vec![
+            vec![
                 RoswaalTestSyntax::from(test1),
                 RoswaalTestSyntax::from(test2),
             ],
@@ -321,6 +867,7 @@ Blob Jr.
         let branch_name = RoswaalOwnedGitBranchName::new("test");
         let pr = GithubPullRequest::for_removing_test_cases_tif_react_frontend(
             &test_names,
+            &[],
             &branch_name,
         );
         assert_eq!(pr.title(), "Roswaal: Remove Tests Blob, Blob Jr.");
@@ -328,6 +875,72 @@ Blob Jr.
 Removes the following teeeeeeeests!
 - Blob
 - Blob Jr.
+";
+        assert!(pr.body.contains(expected_body));
+        assert!(!pr.body.contains("<details>"))
+    }
+
+    #[test]
+    fn remove_test_cases_with_removed_specs() {
+        let test_names = RoswaalTestNamesString::new("Blob\nBlob Jr.");
+        let removed_tests = vec![RoswaalTest::new(
+            "Blob".to_string(),
+            Some("Blob does the thing.".to_string()),
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Tap the thing".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )];
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let pr = GithubPullRequest::for_removing_test_cases_tif_react_frontend(
+            &test_names,
+            &removed_tests,
+            &branch_name,
+        );
+        let expected_body = "\
+Removes the following teeeeeeeests!
+- Blob
+- Blob Jr.
+
+The removed specs were:
+
+<details>
+<summary>Blob</summary>
+
+```
+New Test: Blob
+Abstract: Blob does the thing.
+Step 1: Do the thing
+Requirement 1: Tap the thing
+```
+
+</details>
+";
+        assert!(pr.body.contains(expected_body))
+    }
+
+    #[test]
+    fn remove_locations() {
+        let location_names = vec![
+            RoswaalLocationName::from_str("New York").unwrap(),
+            RoswaalLocationName::from_str("Antarctica").unwrap(),
+        ];
+        let branch_name = RoswaalOwnedGitBranchName::for_removing_locations();
+        let pr = GithubPullRequest::for_removing_locations_tif_react_frontend(
+            &location_names,
+            &branch_name,
+        );
+        assert_eq!(pr.title, "Roswaal: Remove Locations (New York, Antarctica)");
+        let expected_body = "Removes the following locations from the acceptance teeeeeeeeeests:
+- **New York**
+- **Antarctica**
 ";
         assert!(pr.body.contains(expected_body))
     }
@@ -0,0 +1,137 @@
+use std::{fs, path::Path};
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::utils::config::RoswaalConfig;
+
+use super::pull_request::GithubPullRequest;
+
+/// The path of the file, relative to the root of a repo, that overrides the body template of the
+/// PRs opened for that repo.
+pub const PULL_REQUEST_TEMPLATE_FILE_PATH: &str = ".github/roswaal_pull_request_template.md";
+
+/// A template for the body of the PRs opened by this tool.
+///
+/// The template can contain these placeholders, which are filled in when a PR is created:
+/// - `{{title}}`: The title of the PR.
+/// - `{{summary}}`: The generated description of the PR, including the list of tests or
+///   locations that it changes.
+/// - `{{branch}}`: The name of the head branch of the PR.
+/// - `{{branch_url}}`: A link to the head branch of the PR on GitHub.
+/// - `{{repository}}`: The `<owner>/<repo>` that the PR is opened against.
+///
+/// Unknown placeholders are left in the body as-is.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GithubPullRequestBodyTemplate(String);
+
+impl Default for GithubPullRequestBodyTemplate {
+    fn default() -> Self {
+        Self::new(
+            "{{summary}}
+
+## Tickets
+
+TASK_UNTRACKED
+",
+        )
+    }
+}
+
+impl GithubPullRequestBodyTemplate {
+    pub fn new(template: &str) -> Self {
+        Self(template.to_string())
+    }
+
+    /// The template specified by the `[pull_requests]` table of the current `RoswaalConfig`, or
+    /// the default template if none is specified.
+    pub fn from_config() -> Self {
+        RoswaalConfig::current()
+            .pull_request_body_template()
+            .map(Self::new)
+            .unwrap_or_default()
+    }
+
+    /// Loads the template at `PULL_REQUEST_TEMPLATE_FILE_PATH` in the repo at `repo_root_dir_path`,
+    /// or returns None if the repo does not have one.
+    pub fn from_repository(repo_root_dir_path: &Path) -> Option<Self> {
+        fs::read_to_string(repo_root_dir_path.join(PULL_REQUEST_TEMPLATE_FILE_PATH))
+            .ok()
+            .filter(|template| !template.trim().is_empty())
+            .map(|template| Self::new(&template))
+    }
+}
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*(?<name>[a-z_]+)\s*\}\}").unwrap());
+
+impl GithubPullRequestBodyTemplate {
+    /// Renders the body of `pull_request` by filling in the placeholders of this template.
+    pub fn render(&self, pull_request: &GithubPullRequest) -> String {
+        let (owner, repo) = (pull_request.owner(), pull_request.repo());
+        let head_branch = pull_request.head_branch().to_string();
+        PLACEHOLDER_REGEX
+            .replace_all(&self.0, |captures: &Captures| match &captures["name"] {
+                "title" => pull_request.title().to_string(),
+                "summary" => pull_request.summary().to_string(),
+                "branch" => head_branch.clone(),
+                "branch_url" => {
+                    format!("https://github.com/{}/{}/tree/{}", owner, repo, head_branch)
+                }
+                "repository" => format!("{}/{}", owner, repo),
+                _ => captures[0].to_string(),
+            })
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+    use super::*;
+
+    #[test]
+    fn default_template_appends_untracked_tickets_section() {
+        let body = GithubPullRequestBodyTemplate::default().render(&pull_request("Adds tests!"));
+        assert_eq!(body, "Adds tests!\n\n## Tickets\n\nTASK_UNTRACKED\n")
+    }
+
+    #[test]
+    fn render_fills_in_every_placeholder() {
+        let template = GithubPullRequestBodyTemplate::new(
+            "# {{title}}\n{{ summary }}\n[{{branch}}]({{branch_url}}) in {{repository}}",
+        );
+        let pull_request = pull_request("Adds tests!");
+        let branch_name = pull_request.head_branch().to_string();
+        assert_eq!(
+            template.render(&pull_request),
+            format!(
+                "# Roswaal: Add Tests\nAdds tests!\n[{0}](https://github.com/tifapp/FitnessProjectIOS/tree/{0}) in tifapp/FitnessProjectIOS",
+                branch_name
+            )
+        )
+    }
+
+    #[test]
+    fn render_leaves_unknown_placeholders() {
+        let template = GithubPullRequestBodyTemplate::new("{{summary}} {{ticket}}");
+        assert_eq!(template.render(&pull_request("Hello")), "Hello {{ticket}}")
+    }
+
+    #[test]
+    fn from_repository_is_none_when_repo_has_no_template_file() {
+        let template =
+            GithubPullRequestBodyTemplate::from_repository(Path::new("./does-not-exist"));
+        assert_eq!(template, None)
+    }
+
+    fn pull_request(summary: &str) -> GithubPullRequest {
+        GithubPullRequest::for_tif_react_frontend(
+            "Add Tests",
+            summary,
+            &RoswaalOwnedGitBranchName::for_adding_tests(),
+        )
+        .in_repository("tifapp", "FitnessProjectIOS", "main")
+    }
+}
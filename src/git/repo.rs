@@ -1,30 +1,45 @@
 use anyhow::Result;
 use git2::{
-    build::CheckoutBuilder, AnnotatedCommit, BranchType, Cred, FetchOptions, IndexAddOption,
-    PushOptions, RemoteCallbacks, Repository, ResetType,
+    build::{CheckoutBuilder, RepoBuilder},
+    AnnotatedCommit, BranchType, Cred, Direction, ErrorClass, ErrorCode, FetchOptions,
+    IndexAddOption, IndexConflict, PushOptions, RemoteCallbacks, Repository, RepositoryState,
+    ResetType, StatusOptions,
 };
 use std::{
     path::{Path, PathBuf},
     sync::{
-        mpsc::{channel, Receiver, Sender},
+        atomic::{AtomicBool, Ordering},
         Arc,
     },
     thread,
+    time::{Duration, Instant},
 };
 use tokio::{
-    fs::remove_file,
-    spawn,
-    sync::{oneshot, Mutex, MutexGuard},
+    fs::remove_dir_all,
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        oneshot, Mutex, OwnedMutexGuard,
+    },
     task::spawn_blocking,
+    time::sleep,
 };
 
-use crate::utils::fs::remove_dir_all_empty;
+use crate::utils::{config::RoswaalConfig, fs::path_from_bytes, retry::is_transient_io_error};
 
-use super::{branch_name::RoswaalOwnedGitBranchName, metadata::RoswaalGitRepositoryMetadata};
+use super::{
+    bootstrap::clone_if_missing,
+    branch_name::RoswaalOwnedGitBranchName,
+    clean::{remove_untracked_files, RoswaalLargeFileDeletion},
+    health::RoswaalGitRepositoryHealth,
+    metadata::{RoswaalGitClientKind, RoswaalGitRepositoryMetadata},
+    process::{GitProcessError, ProcessGitRepositoryClient},
+    timeout::{with_git_timeout, GitOperationStillRunning},
+};
 
 /// A wrapper for a git repository that serializes access to an underlying git client.
 pub struct RoswaalGitRepository<Client> {
     mutex: Arc<Mutex<Client>>,
+    metadata: RoswaalGitRepositoryMetadata,
 }
 
 impl<Client> RoswaalGitRepository<Client>
@@ -36,11 +51,21 @@ where
         let client = Client::try_new(metadata).await?;
         Ok(Self {
             mutex: Arc::new(Mutex::new(client)),
+            metadata: metadata.clone(),
         })
     }
+
+    /// Clones the repository with the specified metadata if it has not been cloned yet, and then
+    /// opens it.
+    pub async fn clone_if_missing_and_open(
+        metadata: &RoswaalGitRepositoryMetadata,
+    ) -> Result<Self> {
+        clone_if_missing(metadata).await?;
+        Self::open(metadata).await
+    }
 }
 
-pub type RoswaalGitRepositoryTransaction<'a, Client> = MutexGuard<'a, Client>;
+pub type RoswaalGitRepositoryTransaction<Client> = OwnedMutexGuard<Client>;
 
 impl<Client> RoswaalGitRepository<Client>
 where
@@ -48,7 +73,22 @@ where
 {
     /// Starts a transaction to this repository.
     pub async fn transaction(&self) -> RoswaalGitRepositoryTransaction<Client> {
-        self.mutex.lock().await
+        self.mutex.clone().lock_owned().await
+    }
+
+    /// Returns the name of the repository in its metadata.
+    pub fn name(&self) -> &str {
+        self.metadata.name()
+    }
+
+    /// Checks that the local clone can still be opened.
+    ///
+    /// The clone is opened separately from the client, so this does not wait for a transaction
+    /// that is in progress.
+    pub async fn check_openable(&self) -> Result<()> {
+        let path = self.metadata.relative_path(".");
+        spawn_blocking(move || Repository::open(path).map(|_| ())).await??;
+        Ok(())
     }
 }
 
@@ -60,6 +100,80 @@ pub enum PullBranchStatus {
 
 type MergeBranchStatus = PullBranchStatus;
 
+/// Returns true if `error` came from a git operation that failed because the remote could not be
+/// reached, and may succeed if attempted again.
+///
+/// Authentication failures are not transient, since trying again will never fix them.
+pub fn is_transient_git_error(error: &anyhow::Error) -> bool {
+    is_transient_io_error(error)
+        || error.chain().any(|cause| {
+            if let Some(error) = cause.downcast_ref::<git2::Error>() {
+                let is_network_class = matches!(
+                    error.class(),
+                    ErrorClass::Net | ErrorClass::Http | ErrorClass::Ssh
+                );
+                is_network_class
+                    && !matches!(error.code(), ErrorCode::Auth | ErrorCode::Certificate)
+            } else if let Some(error) = cause.downcast_ref::<GitProcessError>() {
+                error.is_transient()
+            } else {
+                false
+            }
+        })
+}
+
+/// The longest time to wait for a cancelled libgit2 request to give control back before deleting
+/// the branch of a cancelled edit.
+const CANCELLED_REQUEST_WAIT_LIMIT: Duration = Duration::from_secs(30);
+
+/// Deletes the local and remote branch named `branch_name` after the edit that created it was
+/// cancelled, logging any error since nothing is waiting on the result.
+pub(super) async fn delete_cancelled_branch<Client: RoswaalGitRepositoryClient>(
+    transaction: RoswaalGitRepositoryTransaction<Client>,
+    branch_name: RoswaalOwnedGitBranchName,
+) {
+    let result = async {
+        // NB: A libgit2 request that was running when the edit was cancelled makes later requests
+        // fail fast until libgit2 checks for the cancellation.
+        let start = Instant::now();
+        loop {
+            match transaction.hard_reset_to_head().await {
+                Err(error)
+                    if GitOperationStillRunning::from_error(&error).is_some()
+                        && start.elapsed() < CANCELLED_REQUEST_WAIT_LIMIT =>
+                {
+                    sleep(Duration::from_millis(100)).await
+                }
+                result => break result,
+            }
+        }?;
+        transaction
+            .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+            .await?;
+        transaction
+            .switch_branch(transaction.metadata().base_branch_name())
+            .await?;
+        transaction.delete_local_branch(&branch_name).await?;
+        let remote_branch_names = transaction.remote_branch_names().await?;
+        if remote_branch_names.contains(&branch_name.to_string()) {
+            transaction.delete_remote_branch(&branch_name).await?;
+        }
+        anyhow::Ok(())
+    }
+    .await;
+    match result {
+        Ok(()) => log::info!(
+            "Deleted the branch of a cancelled edit {}.",
+            branch_name.to_string()
+        ),
+        Err(error) => log::error!(
+            "Failed to delete the branch of a cancelled edit {}. {}",
+            branch_name.to_string(),
+            error
+        ),
+    }
+}
+
 /// A git client trait.
 pub trait RoswaalGitRepositoryClient: Sized {
     /// Attempts to create this client from metadata.
@@ -71,8 +185,12 @@ pub trait RoswaalGitRepositoryClient: Sized {
     /// Performs the equivalent of a `git reset --hard HEAD`.
     async fn hard_reset_to_head(&self) -> Result<()>;
 
-    /// Performs the equivalent of a `git clean -fd`.
-    async fn clean_all_untracked(&self) -> Result<()>;
+    /// Performs the equivalent of a `git clean -fd`, while keeping the untracked files that the
+    /// `RoswaalGitCleanPolicy` of the metadata ignores.
+    ///
+    /// Fails without deleting anything if an untracked file is larger than the maximum size of the
+    /// policy, unless `deletion` is `RoswaalLargeFileDeletion::Allow`.
+    async fn clean_all_untracked(&self, deletion: RoswaalLargeFileDeletion) -> Result<()>;
 
     /// Performs the equivalent of a `git switch <branch>`.
     async fn switch_branch(&self, name: &str) -> Result<()>;
@@ -93,12 +211,86 @@ pub trait RoswaalGitRepositoryClient: Sized {
     ///
     /// Returns true if the deletion was successful.
     async fn delete_local_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<bool>;
+
+    /// Performs the equivalent of a `git push origin --delete <branch>`.
+    async fn delete_remote_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()>;
+
+    /// Deletes the new branch of an edit that was cancelled before it finished in the background,
+    /// both locally and on the remote, and switches back to the base branch.
+    ///
+    /// The edit is cancelled by dropping its future, so `transaction` is held until the branch is
+    /// deleted in order to keep other operations from seeing the half finished edit.
+    fn delete_cancelled_branch(
+        transaction: RoswaalGitRepositoryTransaction<Self>,
+        branch_name: RoswaalOwnedGitBranchName,
+    );
+
+    /// Performs the equivalent of a `git ls-remote --heads origin`.
+    ///
+    /// Returns the name of each branch on the remote without the `refs/heads/` prefix.
+    async fn remote_branch_names(&self) -> Result<Vec<String>>;
+
+    /// Performs the equivalent of a `git branch --list`.
+    async fn local_branch_names(&self) -> Result<Vec<String>>;
+
+    /// Performs the equivalent of a `git status`, and checks for a `MERGE_HEAD`.
+    async fn health(&self) -> Result<RoswaalGitRepositoryHealth>;
+
+    /// Performs the equivalent of a `git merge --abort`.
+    async fn abort_merge(&self) -> Result<()>;
+
+    /// Deletes the local clone, and clones it again from the `origin` remote.
+    ///
+    /// Any local branches or uncommitted changes are lost.
+    async fn reclone(&mut self) -> Result<()>;
 }
 
 /// A `RoswaalGitRepositoryClient` implementation using lib2git and the git2 crate.
+///
+/// Requests that run for longer than the git operation timeout are cancelled, and later requests
+/// fail fast with a `GitOperationStillRunning` error until libgit2 gives control back.
 pub struct LibGit2RepositoryClient {
-    sender: Sender<LibGit2ThreadRequest>,
+    sender: UnboundedSender<LibGit2ThreadMessage>,
+    /// Whether the background thread is working on a request, which is only the case between
+    /// requests when a request timed out and libgit2 has not given control back yet.
+    is_busy: Arc<AtomicBool>,
     metadata: RoswaalGitRepositoryMetadata,
+    timeout: Duration,
+}
+
+struct LibGit2ThreadMessage {
+    request: LibGit2ThreadRequest,
+    cancellation: LibGit2Cancellation,
+}
+
+/// A flag that tells the background thread to stop working on a request that timed out.
+///
+/// libgit2 checks the flag through the progress callbacks of remote operations, and requests
+/// that were cancelled before the thread reached them are skipped.
+#[derive(Clone, Default)]
+struct LibGit2Cancellation {
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl LibGit2Cancellation {
+    fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::SeqCst)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Cancels a request when the future waiting on it finishes or is dropped.
+///
+/// Cancelling a request that already finished has no effect.
+struct LibGit2CancellationGuard(LibGit2Cancellation);
+
+impl Drop for LibGit2CancellationGuard {
+    fn drop(&mut self) {
+        self.0.cancel()
+    }
 }
 
 enum LibGit2ThreadRequest {
@@ -132,21 +324,45 @@ enum LibGit2ThreadRequest {
         name: RoswaalOwnedGitBranchName,
         sender: oneshot::Sender<Result<bool>>,
     },
+    DeleteRemoteBranch {
+        name: RoswaalOwnedGitBranchName,
+        sender: oneshot::Sender<Result<()>>,
+    },
+    RemoteBranchNames {
+        sender: oneshot::Sender<Result<Vec<String>>>,
+    },
+    LocalBranchNames {
+        sender: oneshot::Sender<Result<Vec<String>>>,
+    },
+    Health {
+        sender: oneshot::Sender<Result<RoswaalGitRepositoryHealth>>,
+    },
+    AbortMerge {
+        sender: oneshot::Sender<Result<()>>,
+    },
+    RemoteUrl {
+        sender: oneshot::Sender<Result<String>>,
+    },
+    #[cfg(test)]
+    Sleep {
+        duration: Duration,
+        sender: oneshot::Sender<Result<()>>,
+    },
 }
 
 struct LibGit2StatusEntry {
+    /// The path of the file relative to the root of the repo.
     path: PathBuf,
 }
 
 impl RoswaalGitRepositoryClient for LibGit2RepositoryClient {
     async fn try_new(metadata: &RoswaalGitRepositoryMetadata) -> Result<Self> {
-        let m1 = metadata.clone();
-        let (tx, rx) = channel::<LibGit2ThreadRequest>();
-        let repo = spawn_blocking(move || Repository::open(m1.relative_path("."))).await??;
-        Self::thread(repo, metadata, rx);
+        let is_busy = Arc::new(AtomicBool::new(false));
         Ok(Self {
-            sender: tx,
+            sender: Self::spawn_thread(metadata, is_busy.clone()).await?,
+            is_busy,
             metadata: metadata.clone(),
+            timeout: RoswaalConfig::current().git_operation_timeout(),
         })
     }
 
@@ -155,79 +371,197 @@ impl RoswaalGitRepositoryClient for LibGit2RepositoryClient {
     }
 
     async fn hard_reset_to_head(&self) -> Result<()> {
-        let (sender, receiver) = oneshot::channel::<Result<()>>();
-        self.sender
-            .send(LibGit2ThreadRequest::HardResetToHead { sender })?;
-        receiver.await?
+        self.request("git reset --hard HEAD", |sender| {
+            LibGit2ThreadRequest::HardResetToHead { sender }
+        })
+        .await
     }
 
     async fn switch_branch(&self, name: &str) -> Result<()> {
-        let (sender, receiver) = oneshot::channel::<Result<()>>();
-        self.sender.send(LibGit2ThreadRequest::SwitchBranch {
-            name: name.to_string(),
-            sender,
-        })?;
-        receiver.await?
+        self.request(&format!("git switch {}", name), |sender| {
+            LibGit2ThreadRequest::SwitchBranch {
+                name: name.to_string(),
+                sender,
+            }
+        })
+        .await
     }
 
     async fn pull_branch(&self, name: &str) -> Result<PullBranchStatus> {
-        let (sender, receiver) = oneshot::channel::<Result<PullBranchStatus>>();
-        self.sender.send(LibGit2ThreadRequest::PullBranch {
-            name: name.to_string(),
-            sender,
-        })?;
-        receiver.await?
+        self.request(&format!("git pull origin {}", name), |sender| {
+            LibGit2ThreadRequest::PullBranch {
+                name: name.to_string(),
+                sender,
+            }
+        })
+        .await
     }
 
     async fn commit_all(&self, message: &str) -> Result<()> {
-        let (sender, receiver) = oneshot::channel::<Result<()>>();
-        self.sender.send(LibGit2ThreadRequest::CommitAll {
+        self.request("git commit -am", |sender| LibGit2ThreadRequest::CommitAll {
             message: message.to_string(),
             sender,
-        })?;
-        receiver.await?
+        })
+        .await
     }
 
     async fn checkout_new_branch(&self, name: &RoswaalOwnedGitBranchName) -> Result<()> {
-        let (sender, receiver) = oneshot::channel::<Result<()>>();
-        self.sender.send(LibGit2ThreadRequest::CheckoutNewBranch {
-            name: name.clone(),
-            sender,
-        })?;
-        receiver.await?
+        self.request(&format!("git checkout -b {}", name.to_string()), |sender| {
+            LibGit2ThreadRequest::CheckoutNewBranch {
+                name: name.clone(),
+                sender,
+            }
+        })
+        .await
     }
 
     async fn push_changes(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()> {
-        let (sender, receiver) = oneshot::channel::<Result<()>>();
-        self.sender.send(LibGit2ThreadRequest::PushChanges {
+        let operation = format!("git push origin {}", branch_name.to_string());
+        self.request(&operation, |sender| LibGit2ThreadRequest::PushChanges {
             name: branch_name.clone(),
             sender,
-        })?;
-        receiver.await?
+        })
+        .await
     }
 
-    async fn clean_all_untracked(&self) -> Result<()> {
-        let (sender, receiver) = oneshot::channel::<Result<Vec<LibGit2StatusEntry>>>();
-        self.sender
-            .send(LibGit2ThreadRequest::Statuses { sender })?;
-        let entries = receiver.await??;
-        let futures = entries
-            .iter()
-            .map(|entry| spawn(remove_file(entry.path.clone())));
-        for f in futures {
-            f.await??;
-        }
-        remove_dir_all_empty(self.metadata.relative_path(".")).await?;
-        Ok(())
+    async fn clean_all_untracked(&self, deletion: RoswaalLargeFileDeletion) -> Result<()> {
+        let entries = self
+            .request("git status", |sender| LibGit2ThreadRequest::Statuses {
+                sender,
+            })
+            .await?;
+        let paths = entries.into_iter().map(|entry| entry.path).collect();
+        remove_untracked_files(&self.metadata, paths, deletion).await
     }
 
     async fn delete_local_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<bool> {
-        let (sender, receiver) = oneshot::channel::<Result<bool>>();
-        self.sender.send(LibGit2ThreadRequest::DeleteLocalBranch {
-            name: branch_name.clone(),
+        let operation = format!("git branch -d {}", branch_name.to_string());
+        self.request(&operation, |sender| {
+            LibGit2ThreadRequest::DeleteLocalBranch {
+                name: branch_name.clone(),
+                sender,
+            }
+        })
+        .await
+    }
+
+    async fn delete_remote_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        let operation = format!("git push origin --delete {}", branch_name.to_string());
+        self.request(&operation, |sender| {
+            LibGit2ThreadRequest::DeleteRemoteBranch {
+                name: branch_name.clone(),
+                sender,
+            }
+        })
+        .await
+    }
+
+    fn delete_cancelled_branch(
+        transaction: RoswaalGitRepositoryTransaction<Self>,
+        branch_name: RoswaalOwnedGitBranchName,
+    ) {
+        tokio::spawn(delete_cancelled_branch(transaction, branch_name));
+    }
+
+    async fn remote_branch_names(&self) -> Result<Vec<String>> {
+        self.request("git ls-remote --heads origin", |sender| {
+            LibGit2ThreadRequest::RemoteBranchNames { sender }
+        })
+        .await
+    }
+
+    async fn local_branch_names(&self) -> Result<Vec<String>> {
+        self.request("git branch --list", |sender| {
+            LibGit2ThreadRequest::LocalBranchNames { sender }
+        })
+        .await
+    }
+
+    async fn health(&self) -> Result<RoswaalGitRepositoryHealth> {
+        self.request("git status", |sender| LibGit2ThreadRequest::Health {
             sender,
-        })?;
-        receiver.await?
+        })
+        .await
+    }
+
+    async fn abort_merge(&self) -> Result<()> {
+        self.request("git merge --abort", |sender| {
+            LibGit2ThreadRequest::AbortMerge { sender }
+        })
+        .await
+    }
+
+    async fn reclone(&mut self) -> Result<()> {
+        if self.is_busy.load(Ordering::SeqCst) {
+            return Err(GitOperationStillRunning::new("git clone").into());
+        }
+        let url = self
+            .request("git remote get-url origin", |sender| {
+                LibGit2ThreadRequest::RemoteUrl { sender }
+            })
+            .await?;
+        let path = self.metadata.relative_path("");
+        remove_dir_all(&path).await?;
+        let metadata = self.metadata.clone();
+        let cancellation = LibGit2Cancellation::default();
+        let clone_cancellation = cancellation.clone();
+        let operation = format!("git clone {}", url);
+        let clone = spawn_blocking(move || {
+            let mut fetch_options = FetchOptions::new();
+            fetch_options.remote_callbacks(metadata.remote_callbacks(&clone_cancellation));
+            RepoBuilder::new()
+                .fetch_options(fetch_options)
+                .clone(&url, Path::new(&path))
+        });
+        let result = with_git_timeout(&operation, self.timeout, async { Ok(clone.await??) }).await;
+        if result.is_err() {
+            cancellation.cancel();
+        }
+        result?;
+        // NB: Dropping the old sender ends the thread that owns the deleted repository.
+        *self = Self::try_new(&self.metadata).await?;
+        Ok(())
+    }
+}
+
+impl LibGit2RepositoryClient {
+    /// Sends a request to the background thread, and waits for its response until the git
+    /// operation timeout.
+    ///
+    /// When the request times out, it is cancelled, but libgit2 only checks for cancellation in
+    /// the progress callbacks of remote operations, so the thread may keep working on it. Later
+    /// requests fail with `GitOperationStillRunning` until the thread finishes, rather than
+    /// touching the working tree and index at the same time as the timed out request.
+    async fn request<T>(
+        &self,
+        operation: &str,
+        request: impl FnOnce(oneshot::Sender<Result<T>>) -> LibGit2ThreadRequest,
+    ) -> Result<T> {
+        if self.is_busy.load(Ordering::SeqCst) {
+            return Err(GitOperationStillRunning::new(operation).into());
+        }
+        let (sender, receiver) = oneshot::channel::<Result<T>>();
+        let cancellation = LibGit2Cancellation::default();
+        let message = LibGit2ThreadMessage {
+            request: request(sender),
+            cancellation: cancellation.clone(),
+        };
+        // NB: The request is also cancelled when this future is dropped before it finishes (eg.
+        // by `/roswaal-cancel`), so that a push does not land after its operation was cancelled.
+        let _guard = LibGit2CancellationGuard(cancellation);
+        self.sender.send(message)?;
+        with_git_timeout(operation, self.timeout, async { receiver.await? }).await
+    }
+
+    async fn spawn_thread(
+        metadata: &RoswaalGitRepositoryMetadata,
+        is_busy: Arc<AtomicBool>,
+    ) -> Result<UnboundedSender<LibGit2ThreadMessage>> {
+        let m1 = metadata.clone();
+        let (tx, rx) = unbounded_channel::<LibGit2ThreadMessage>();
+        let repo = spawn_blocking(move || Repository::open(m1.relative_path("."))).await??;
+        Self::thread(repo, metadata, rx, is_busy);
+        Ok(tx)
     }
 }
 
@@ -238,49 +572,112 @@ impl LibGit2RepositoryClient {
     fn thread(
         repo: Repository,
         metadata: &RoswaalGitRepositoryMetadata,
-        receiver: Receiver<LibGit2ThreadRequest>,
+        mut receiver: UnboundedReceiver<LibGit2ThreadMessage>,
+        is_busy: Arc<AtomicBool>,
     ) {
         let metadata = metadata.clone();
         thread::spawn(move || {
-            for request in receiver {
+            while let Some(LibGit2ThreadMessage {
+                request,
+                cancellation,
+            }) = receiver.blocking_recv()
+            {
+                if cancellation.is_cancelled() {
+                    continue;
+                }
+                is_busy.store(true, Ordering::SeqCst);
                 match request {
                     LibGit2ThreadRequest::HardResetToHead { sender } => {
-                        _ = sender.send(Self::hard_reset_to_head(&repo));
+                        Self::respond(sender, Self::hard_reset_to_head(&repo), &is_busy);
                     }
                     LibGit2ThreadRequest::SwitchBranch { name, sender } => {
-                        _ = sender.send(Self::switch_branch(&repo, &name));
+                        Self::respond(sender, Self::switch_branch(&repo, &name), &is_busy);
                     }
                     LibGit2ThreadRequest::PullBranch { name, sender } => {
-                        _ = sender.send(Self::pull_branch(
-                            &repo,
-                            &name,
-                            metadata.remote_callbacks(),
-                        ));
+                        Self::respond(
+                            sender,
+                            Self::pull_branch(
+                                &repo,
+                                &name,
+                                &metadata,
+                                metadata.remote_callbacks(&cancellation),
+                            ),
+                            &is_busy,
+                        );
                     }
                     LibGit2ThreadRequest::CommitAll { message, sender } => {
-                        _ = sender.send(Self::commit_all(&repo, &message));
+                        Self::respond(sender, Self::commit_all(&repo, &message), &is_busy);
                     }
                     LibGit2ThreadRequest::CheckoutNewBranch { name, sender } => {
-                        _ = sender.send(Self::checkout_new_branch(&repo, &name));
+                        Self::respond(sender, Self::checkout_new_branch(&repo, &name), &is_busy);
                     }
                     LibGit2ThreadRequest::PushChanges { name, sender } => {
-                        _ = sender.send(Self::push_changes(
-                            &repo,
-                            &name,
-                            metadata.remote_callbacks(),
-                        ));
+                        Self::respond(
+                            sender,
+                            Self::push_changes(
+                                &repo,
+                                &name,
+                                metadata.remote_callbacks(&cancellation),
+                            ),
+                            &is_busy,
+                        );
                     }
                     LibGit2ThreadRequest::Statuses { sender } => {
-                        _ = sender.send(Self::statuses(&repo));
+                        Self::respond(sender, Self::statuses(&repo), &is_busy);
                     }
                     LibGit2ThreadRequest::DeleteLocalBranch { name, sender } => {
-                        _ = sender.send(Self::delete_local_branch(&repo, &name));
+                        Self::respond(sender, Self::delete_local_branch(&repo, &name), &is_busy);
+                    }
+                    LibGit2ThreadRequest::DeleteRemoteBranch { name, sender } => {
+                        Self::respond(
+                            sender,
+                            Self::delete_remote_branch(
+                                &repo,
+                                &name,
+                                metadata.remote_callbacks(&cancellation),
+                            ),
+                            &is_busy,
+                        );
+                    }
+                    LibGit2ThreadRequest::RemoteBranchNames { sender } => {
+                        Self::respond(
+                            sender,
+                            Self::remote_branch_names(
+                                &repo,
+                                metadata.remote_callbacks(&cancellation),
+                            ),
+                            &is_busy,
+                        );
+                    }
+                    LibGit2ThreadRequest::LocalBranchNames { sender } => {
+                        Self::respond(sender, Self::local_branch_names(&repo), &is_busy);
+                    }
+                    LibGit2ThreadRequest::Health { sender } => {
+                        Self::respond(sender, Self::health(&repo), &is_busy);
+                    }
+                    LibGit2ThreadRequest::AbortMerge { sender } => {
+                        Self::respond(sender, Self::abort_merge(&repo), &is_busy);
+                    }
+                    LibGit2ThreadRequest::RemoteUrl { sender } => {
+                        Self::respond(sender, Self::remote_url(&repo), &is_busy);
+                    }
+                    #[cfg(test)]
+                    LibGit2ThreadRequest::Sleep { duration, sender } => {
+                        thread::sleep(duration);
+                        Self::respond(sender, Ok(()), &is_busy);
                     }
                 }
             }
         });
     }
 
+    /// Marks the thread as no longer busy before sending `result`, so that the next request does
+    /// not see the thread as busy after it receives the result.
+    fn respond<T>(sender: oneshot::Sender<Result<T>>, result: Result<T>, is_busy: &AtomicBool) {
+        is_busy.store(false, Ordering::SeqCst);
+        _ = sender.send(result);
+    }
+
     fn hard_reset_to_head(repo: &Repository) -> Result<()> {
         let obj = repo.revparse_single("HEAD")?;
         repo.reset(&obj, ResetType::Hard, None)?;
@@ -297,9 +694,10 @@ impl LibGit2RepositoryClient {
     fn pull_branch(
         repo: &Repository,
         name: &str,
+        metadata: &RoswaalGitRepositoryMetadata,
         callbacks: RemoteCallbacks,
     ) -> Result<PullBranchStatus> {
-        Self::merge(repo, name, &Self::fetch(repo, name, callbacks)?)
+        Self::merge(repo, name, metadata, &Self::fetch(repo, name, callbacks)?)
     }
 
     fn fetch<'a>(
@@ -318,21 +716,101 @@ impl LibGit2RepositoryClient {
     fn merge(
         repo: &Repository,
         branch_name: &str,
+        metadata: &RoswaalGitRepositoryMetadata,
         commit: &AnnotatedCommit,
     ) -> Result<MergeBranchStatus> {
         let (analysis, _) = repo.merge_analysis(&[commit])?;
         if analysis.is_fast_forward() {
             Self::merge_fast_forward(repo, branch_name, commit)
         } else if analysis.is_normal() {
-            Self::merge_normal(repo, commit)
+            Self::merge_normal(repo, branch_name, metadata, commit)
         } else {
             Ok(MergeBranchStatus::Success)
         }
     }
 
-    fn merge_normal(repo: &Repository, commit: &AnnotatedCommit) -> Result<MergeBranchStatus> {
+    /// Merges `commit` into HEAD, and commits the merge like `git pull --no-rebase` does unless a
+    /// conflict remains.
+    fn merge_normal(
+        repo: &Repository,
+        branch_name: &str,
+        metadata: &RoswaalGitRepositoryMetadata,
+        commit: &AnnotatedCommit,
+    ) -> Result<MergeBranchStatus> {
         repo.merge(&[&commit], None, None)?;
-        Self::current_merge_status(repo)
+        Self::take_theirs_in_generated_file_conflicts(repo, metadata)?;
+        if repo.index()?.has_conflicts() {
+            return Ok(MergeBranchStatus::MergeConflict);
+        }
+        Self::commit_merge(repo, branch_name, commit)?;
+        Ok(MergeBranchStatus::Success)
+    }
+
+    fn commit_merge(repo: &Repository, branch_name: &str, commit: &AnnotatedCommit) -> Result<()> {
+        let mut index = repo.index()?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+        let signature = repo.signature()?;
+        let head_commit = repo.head()?.peel_to_commit()?;
+        let their_commit = repo.find_commit(commit.id())?;
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &format!("Merge branch '{}' of origin", branch_name),
+            &tree,
+            &[&head_commit, &their_commit],
+        )?;
+        repo.cleanup_state()?;
+        Ok(())
+    }
+
+    /// Resolves the merge conflicts in the index by taking the incoming version of each
+    /// conflicting file, but only if every conflicting file is a generated file.
+    ///
+    /// Conflicts in generated files (eg. Locations.ts) are mechanical, so the incoming version,
+    /// which is what was merged on the remote, is kept as is. The file is not regenerated from
+    /// sqlite here, but the operations that change it regenerate it from sqlite before committing.
+    fn take_theirs_in_generated_file_conflicts(
+        repo: &Repository,
+        metadata: &RoswaalGitRepositoryMetadata,
+    ) -> Result<()> {
+        let mut index = repo.index()?;
+        if !index.has_conflicts() {
+            return Ok(());
+        }
+        let conflicts = index
+            .conflicts()?
+            .collect::<std::result::Result<Vec<IndexConflict>, git2::Error>>()?;
+        let paths = conflicts
+            .iter()
+            .filter_map(|conflict| {
+                conflict
+                    .their
+                    .as_ref()
+                    .or(conflict.our.as_ref())
+                    .or(conflict.ancestor.as_ref())
+                    .map(|entry| path_from_bytes(&entry.path))
+            })
+            .collect::<Vec<PathBuf>>();
+        if !metadata.are_all_generated_files(&paths) {
+            return Ok(());
+        }
+        for (conflict, path) in conflicts.iter().zip(paths.iter()) {
+            if conflict.their.is_some() {
+                let mut checkout_builder = CheckoutBuilder::new();
+                checkout_builder.path(path).use_theirs(true).force();
+                repo.checkout_index(Some(&mut index), Some(&mut checkout_builder))?;
+                index.add_path(path)?;
+            } else {
+                let workdir_path = metadata.relative_path(path);
+                if workdir_path.exists() {
+                    std::fs::remove_file(workdir_path)?;
+                }
+                index.remove_path(path)?;
+            }
+        }
+        index.write()?;
+        Ok(())
     }
 
     fn merge_fast_forward(
@@ -448,31 +926,244 @@ impl LibGit2RepositoryClient {
         }
     }
 
+    fn delete_remote_branch(
+        repo: &Repository,
+        branch_name: &RoswaalOwnedGitBranchName,
+        callbacks: RemoteCallbacks,
+    ) -> Result<()> {
+        let mut remote = repo.find_remote("origin")?;
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        remote.push(
+            &[format!(":refs/heads/{}", branch_name.to_string())],
+            Some(&mut push_options),
+        )?;
+        Ok(())
+    }
+
+    fn remote_branch_names(repo: &Repository, callbacks: RemoteCallbacks) -> Result<Vec<String>> {
+        let mut remote = repo.find_remote("origin")?;
+        let connection = remote.connect_auth(Direction::Fetch, Some(callbacks), None)?;
+        let names = connection
+            .list()?
+            .iter()
+            .filter_map(|head| head.name().strip_prefix("refs/heads/"))
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+        Ok(names)
+    }
+
+    fn local_branch_names(repo: &Repository) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        for branch in repo.branches(Some(BranchType::Local))? {
+            if let Some(name) = branch?.0.name()? {
+                names.push(name.to_string())
+            }
+        }
+        Ok(names)
+    }
+
+    fn health(repo: &Repository) -> Result<RoswaalGitRepositoryHealth> {
+        let current_branch_name = match repo.head() {
+            Ok(head) if head.is_branch() => head.shorthand().map(|name| name.to_string()),
+            _ => None,
+        };
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        let dirty_paths = repo
+            .statuses(Some(&mut options))?
+            .iter()
+            .filter(|entry| !entry.status().is_ignored())
+            .filter_map(|entry| entry.path().map(|path| path.to_string()))
+            .collect::<Vec<String>>();
+        let is_merging = repo.state() == RepositoryState::Merge;
+        Ok(RoswaalGitRepositoryHealth::new(
+            current_branch_name,
+            dirty_paths,
+            is_merging,
+        ))
+    }
+
+    fn abort_merge(repo: &Repository) -> Result<()> {
+        repo.cleanup_state()?;
+        Self::hard_reset_to_head(repo)
+    }
+
+    fn remote_url(repo: &Repository) -> Result<String> {
+        let remote = repo.find_remote("origin")?;
+        let url = remote
+            .url()
+            .ok_or_else(|| git2::Error::from_str("origin has no url."))?;
+        Ok(url.to_string())
+    }
+
     fn statuses(repo: &Repository) -> Result<Vec<LibGit2StatusEntry>> {
         let statuses = repo
             .statuses(None)?
             .iter()
-            .filter_map(|entry| {
-                if let (Some(entry_path), true) = (entry.path(), entry.status().is_wt_new()) {
-                    Some(LibGit2StatusEntry {
-                        path: repo.workdir().unwrap().join(entry_path),
-                    })
-                } else {
-                    None
-                }
+            .filter(|entry| entry.status().is_wt_new())
+            .map(|entry| LibGit2StatusEntry {
+                // NB: entry.path() is None for file names that are not UTF8, and those files
+                // still need to be cleaned.
+                path: path_from_bytes(entry.path_bytes()),
             })
             .collect::<Vec<LibGit2StatusEntry>>();
         Ok(statuses)
     }
 }
 
+/// A `RoswaalGitRepositoryClient` that delegates to the client specified by the
+/// `RoswaalGitClientKind` of its metadata.
+pub enum AnyGitRepositoryClient {
+    LibGit2(LibGit2RepositoryClient),
+    Process(ProcessGitRepositoryClient),
+}
+
+impl RoswaalGitRepositoryClient for AnyGitRepositoryClient {
+    async fn try_new(metadata: &RoswaalGitRepositoryMetadata) -> Result<Self> {
+        match metadata.client_kind() {
+            RoswaalGitClientKind::LibGit2 => Ok(Self::LibGit2(
+                LibGit2RepositoryClient::try_new(metadata).await?,
+            )),
+            RoswaalGitClientKind::Process => Ok(Self::Process(
+                ProcessGitRepositoryClient::try_new(metadata).await?,
+            )),
+        }
+    }
+
+    fn metadata(&self) -> &RoswaalGitRepositoryMetadata {
+        match self {
+            Self::LibGit2(client) => client.metadata(),
+            Self::Process(client) => client.metadata(),
+        }
+    }
+
+    async fn hard_reset_to_head(&self) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.hard_reset_to_head().await,
+            Self::Process(client) => client.hard_reset_to_head().await,
+        }
+    }
+
+    async fn clean_all_untracked(&self, deletion: RoswaalLargeFileDeletion) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.clean_all_untracked(deletion).await,
+            Self::Process(client) => client.clean_all_untracked(deletion).await,
+        }
+    }
+
+    async fn switch_branch(&self, name: &str) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.switch_branch(name).await,
+            Self::Process(client) => client.switch_branch(name).await,
+        }
+    }
+
+    async fn pull_branch(&self, name: &str) -> Result<PullBranchStatus> {
+        match self {
+            Self::LibGit2(client) => client.pull_branch(name).await,
+            Self::Process(client) => client.pull_branch(name).await,
+        }
+    }
+
+    async fn commit_all(&self, message: &str) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.commit_all(message).await,
+            Self::Process(client) => client.commit_all(message).await,
+        }
+    }
+
+    async fn checkout_new_branch(&self, name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.checkout_new_branch(name).await,
+            Self::Process(client) => client.checkout_new_branch(name).await,
+        }
+    }
+
+    async fn push_changes(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.push_changes(branch_name).await,
+            Self::Process(client) => client.push_changes(branch_name).await,
+        }
+    }
+
+    async fn delete_local_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<bool> {
+        match self {
+            Self::LibGit2(client) => client.delete_local_branch(branch_name).await,
+            Self::Process(client) => client.delete_local_branch(branch_name).await,
+        }
+    }
+
+    async fn delete_remote_branch(&self, branch_name: &RoswaalOwnedGitBranchName) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.delete_remote_branch(branch_name).await,
+            Self::Process(client) => client.delete_remote_branch(branch_name).await,
+        }
+    }
+
+    fn delete_cancelled_branch(
+        transaction: RoswaalGitRepositoryTransaction<Self>,
+        branch_name: RoswaalOwnedGitBranchName,
+    ) {
+        tokio::spawn(delete_cancelled_branch(transaction, branch_name));
+    }
+
+    async fn remote_branch_names(&self) -> Result<Vec<String>> {
+        match self {
+            Self::LibGit2(client) => client.remote_branch_names().await,
+            Self::Process(client) => client.remote_branch_names().await,
+        }
+    }
+
+    async fn local_branch_names(&self) -> Result<Vec<String>> {
+        match self {
+            Self::LibGit2(client) => client.local_branch_names().await,
+            Self::Process(client) => client.local_branch_names().await,
+        }
+    }
+
+    async fn health(&self) -> Result<RoswaalGitRepositoryHealth> {
+        match self {
+            Self::LibGit2(client) => client.health().await,
+            Self::Process(client) => client.health().await,
+        }
+    }
+
+    async fn abort_merge(&self) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.abort_merge().await,
+            Self::Process(client) => client.abort_merge().await,
+        }
+    }
+
+    async fn reclone(&mut self) -> Result<()> {
+        match self {
+            Self::LibGit2(client) => client.reclone().await,
+            Self::Process(client) => client.reclone().await,
+        }
+    }
+}
+
 impl RoswaalGitRepositoryMetadata {
-    fn remote_callbacks(&self) -> RemoteCallbacks {
+    fn remote_callbacks<'a>(
+        &'a self,
+        cancellation: &'a LibGit2Cancellation,
+    ) -> RemoteCallbacks<'a> {
         let mut callbacks = RemoteCallbacks::new();
         callbacks.credentials(|_, user, _| {
             let path = self.ssh_private_key_path();
             Cred::ssh_key(user.unwrap(), None, Path::new(&path), None)
         });
+        // NB: Returning false from a progress callback makes libgit2 abort the operation.
+        callbacks.transfer_progress(|_| !cancellation.is_cancelled());
+        callbacks.sideband_progress(|_| !cancellation.is_cancelled());
+        callbacks.push_negotiation(|_| {
+            if cancellation.is_cancelled() {
+                Err(git2::Error::from_str("The push was cancelled."))
+            } else {
+                Ok(())
+            }
+        });
         callbacks
     }
 }
@@ -480,15 +1171,61 @@ impl RoswaalGitRepositoryMetadata {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tokio::fs::{create_dir_all, try_exists, File};
+    use tokio::fs::{create_dir_all, remove_file, try_exists, File};
 
     use crate::git::{
         branch_name::RoswaalOwnedGitBranchName,
+        clean::{LargeUntrackedFilesError, RoswaalGitCleanPolicy},
         test_support::{
             read_string, repo_with_test_metadata, with_clean_test_repo_access, write_string,
         },
+        timeout::GitOperationTimedOut,
     };
 
+    #[test]
+    fn test_network_errors_are_transient() {
+        let network = git2::Error::new(ErrorCode::GenericError, ErrorClass::Net, "timed out");
+        let auth = git2::Error::new(ErrorCode::Auth, ErrorClass::Ssh, "bad credentials");
+        let missing = git2::Error::new(ErrorCode::NotFound, ErrorClass::Reference, "no branch");
+        assert!(is_transient_git_error(&anyhow::Error::new(network)));
+        assert!(!is_transient_git_error(&anyhow::Error::new(auth)));
+        assert!(!is_transient_git_error(&anyhow::Error::new(missing)))
+    }
+
+    #[tokio::test]
+    async fn test_requests_fail_fast_until_timed_out_request_finishes() {
+        with_clean_test_repo_access(async {
+            let (_, metadata) = repo_with_test_metadata().await?;
+            let mut client = LibGit2RepositoryClient::try_new(&metadata).await?;
+            client.timeout = Duration::from_millis(50);
+            let error = client
+                .request("sleep", |sender| LibGit2ThreadRequest::Sleep {
+                    duration: Duration::from_millis(500),
+                    sender,
+                })
+                .await
+                .unwrap_err();
+            assert_eq!(
+                GitOperationTimedOut::from_error(&error),
+                Some(&GitOperationTimedOut::new(
+                    "sleep",
+                    Duration::from_millis(50)
+                ))
+            );
+            let error = client.health().await.unwrap_err();
+            assert_eq!(
+                GitOperationStillRunning::from_error(&error),
+                Some(&GitOperationStillRunning::new("git status"))
+            );
+            sleep(Duration::from_millis(600)).await;
+            client.timeout = Duration::from_secs(10);
+            assert!(client.health().await?.is_healthy());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_add_commit_push_pull() {
         with_clean_test_repo_access(async {
@@ -520,6 +1257,56 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_remote_branch_names_includes_pushed_branch() {
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let transaction = repo.transaction().await;
+            let branch_name = RoswaalOwnedGitBranchName::new("test");
+            transaction.checkout_new_branch(&branch_name).await?;
+            transaction.push_changes(&branch_name).await?;
+            transaction
+                .switch_branch(metadata.base_branch_name())
+                .await?;
+
+            let names = transaction.remote_branch_names().await?;
+            assert!(names.contains(&branch_name.to_string()));
+            assert!(names.contains(&metadata.base_branch_name().to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_deleting_cancelled_branch_deletes_local_and_pushed_branch() {
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let transaction = repo.transaction().await;
+            let branch_name = RoswaalOwnedGitBranchName::new("test");
+            transaction.checkout_new_branch(&branch_name).await?;
+            write_string(&metadata.relative_path("test.txt"), "Cancelled").await?;
+            transaction.commit_all("I like this!").await?;
+            transaction.push_changes(&branch_name).await?;
+            write_string(&metadata.relative_path("test.txt"), "Uncommitted").await?;
+            LibGit2RepositoryClient::delete_cancelled_branch(transaction, branch_name.clone());
+
+            let transaction = repo.transaction().await;
+            let remote_branch_names = transaction.remote_branch_names().await?;
+            assert!(!remote_branch_names.contains(&branch_name.to_string()));
+            let local_branch_names = transaction.local_branch_names().await?;
+            assert!(!local_branch_names.contains(&branch_name.to_string()));
+            let health = transaction.health().await?;
+            assert_eq!(
+                health,
+                RoswaalGitRepositoryHealth::clean(metadata.base_branch_name())
+            );
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_reset_hard_to_head() {
         with_clean_test_repo_access(async {
@@ -547,7 +1334,9 @@ mod tests {
             create_dir_all(metadata.relative_path("roswaal/nested/test-clean")).await?;
             File::create(metadata.relative_path("roswaal/nested/test-clean/test2.txt")).await?;
 
-            transaction.clean_all_untracked().await?;
+            transaction
+                .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+                .await?;
 
             assert!(!try_exists(metadata.relative_path("test.txt")).await?);
             assert!(
@@ -562,6 +1351,61 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_clean_all_untracked_keeps_ignored_files_and_refuses_to_delete_large_files() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing()
+                .with_clean_policy(RoswaalGitCleanPolicy::new(&["*.mp4".to_string()], 1));
+            let repo = RoswaalGitRepository::<LibGit2RepositoryClient>::open(&metadata).await?;
+            write_string(metadata.relative_path("video.mp4"), "Large Video").await?;
+            write_string(metadata.relative_path("App.ipa"), "Large Build").await?;
+            File::create(metadata.relative_path("test.txt")).await?;
+
+            let transaction = repo.transaction().await;
+            let error = transaction
+                .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+                .await
+                .unwrap_err();
+            let error = error.downcast_ref::<LargeUntrackedFilesError>().unwrap();
+            assert_eq!(error.paths(), &["App.ipa".to_string()]);
+            assert!(try_exists(metadata.relative_path("App.ipa")).await?);
+            assert!(try_exists(metadata.relative_path("test.txt")).await?);
+
+            transaction
+                .clean_all_untracked(RoswaalLargeFileDeletion::Allow)
+                .await?;
+            assert!(!try_exists(metadata.relative_path("App.ipa")).await?);
+            assert!(!try_exists(metadata.relative_path("test.txt")).await?);
+            assert!(try_exists(metadata.relative_path("video.mp4")).await?);
+            remove_file(metadata.relative_path("video.mp4")).await?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_clean_all_untracked_removes_files_with_non_utf8_names() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let transaction = repo.transaction().await;
+            let file_path = metadata.relative_path(OsStr::from_bytes(b"test-\xff.txt"));
+            File::create(&file_path).await?;
+
+            transaction
+                .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+                .await?;
+
+            assert!(!try_exists(&file_path).await?);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn test_delete_local_branch_that_exists_returns_true_when_deleted_properly() {
         with_clean_test_repo_access(async {
@@ -609,7 +1453,8 @@ mod tests {
 
             transaction.checkout_new_branch(&b1).await?;
 
-            write_string(metadata.locations_path(), "console.log(\"Hello world\")").await?;
+            let path = metadata.relative_path("test.ts");
+            write_string(&path, "console.log(\"Hello world\")").await?;
 
             transaction.commit_all("console.log").await?;
             transaction
@@ -617,7 +1462,7 @@ mod tests {
                 .await?;
             transaction.checkout_new_branch(&b2).await?;
 
-            write_string(metadata.locations_path(), "console.log(\"Goodbye world\")").await?;
+            write_string(&path, "console.log(\"Goodbye world\")").await?;
 
             transaction.commit_all("console.log").await?;
             transaction.push_changes(&b2).await?;
@@ -631,4 +1476,98 @@ mod tests {
         .await
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_pull_takes_incoming_version_of_conflicting_generated_locations_file() {
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let transaction = repo.transaction().await;
+
+            let b1 = RoswaalOwnedGitBranchName::new("test-generated-84731");
+            let b2 = RoswaalOwnedGitBranchName::new("test-generated-29104");
+
+            transaction.checkout_new_branch(&b1).await?;
+            write_string(metadata.locations_path(), "console.log(\"Hello world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction
+                .switch_branch(metadata.base_branch_name())
+                .await?;
+            transaction.checkout_new_branch(&b2).await?;
+            write_string(metadata.locations_path(), "console.log(\"Goodbye world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction.push_changes(&b2).await?;
+            transaction.switch_branch(&b1.to_string()).await?;
+
+            let status = transaction.pull_branch(&b2.to_string()).await?;
+            assert_eq!(status, PullBranchStatus::Success);
+            let content = read_string(metadata.locations_path()).await?;
+            assert_eq!(content, "console.log(\"Goodbye world\")");
+            let health = transaction.health().await?;
+            assert_eq!(health, RoswaalGitRepositoryHealth::clean(&b1.to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_branch_and_dirty_paths() {
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let transaction = repo.transaction().await;
+
+            let health = transaction.health().await?;
+            assert_eq!(health, RoswaalGitRepositoryHealth::clean("main"));
+
+            write_string(&metadata.relative_path("test.txt"), "Dirty").await?;
+            write_string(metadata.locations_path(), "console.log(\"Hello world\")").await?;
+
+            let health = transaction.health().await?;
+            assert_eq!(health.current_branch_name(), Some("main"));
+            assert!(health.dirty_paths().contains(&"test.txt".to_string()));
+            assert_eq!(health.dirty_paths().len(), 2);
+            assert!(!health.is_healthy());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_abort_merge_after_merge_conflict() {
+        with_clean_test_repo_access(async {
+            let (repo, metadata) = repo_with_test_metadata().await?;
+            let transaction = repo.transaction().await;
+
+            let b1 = RoswaalOwnedGitBranchName::new("test-abort-1");
+            let b2 = RoswaalOwnedGitBranchName::new("test-abort-2");
+
+            let path = metadata.relative_path("test.ts");
+            transaction.checkout_new_branch(&b1).await?;
+            write_string(&path, "console.log(\"Hello world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction
+                .switch_branch(metadata.base_branch_name())
+                .await?;
+            transaction.checkout_new_branch(&b2).await?;
+            write_string(&path, "console.log(\"Goodbye world\")").await?;
+            transaction.commit_all("console.log").await?;
+            transaction.push_changes(&b2).await?;
+            transaction.switch_branch(&b1.to_string()).await?;
+            transaction.pull_branch(&b2.to_string()).await?;
+
+            assert!(transaction.health().await?.is_merging());
+
+            transaction.abort_merge().await?;
+
+            let health = transaction.health().await?;
+            assert_eq!(health, RoswaalGitRepositoryHealth::clean(&b1.to_string()));
+            let names = transaction.local_branch_names().await?;
+            assert!(names.contains(&b1.to_string()));
+            assert!(names.contains(&b2.to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
 }
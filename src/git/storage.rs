@@ -0,0 +1,313 @@
+use anyhow::Result;
+use sqlx::FromRow;
+
+use crate::utils::db::{
+    query::{query, query_as},
+    RoswaalDatabaseTransaction,
+};
+
+use super::{
+    branch_name::RoswaalOwnedGitBranchName,
+    pull_request::{GithubPullRequest, GithubPullRequestOptions},
+};
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Returns the names of all branches that have unmerged tests, locations, or staged removals
+    /// in alphabetical order.
+    pub async fn unmerged_branch_names(&mut self) -> Result<Vec<RoswaalOwnedGitBranchName>> {
+        let rows = query_as::<SqliteBranchName>(statements::SELECT_UNMERGED_BRANCH_NAMES)
+            .fetch_all(self.connection())
+            .await?;
+        Ok(rows.into_iter().map(|r| r.unmerged_branch_name).collect())
+    }
+
+    /// Saves a PR to be opened later, replacing any pending PR for the same branch and repository.
+    pub async fn save_pending_pull_request(
+        &mut self,
+        pull_request: &GithubPullRequest,
+    ) -> Result<()> {
+        query(statements::INSERT_PENDING_PULL_REQUEST)
+            .bind(pull_request.owner())
+            .bind(pull_request.repo())
+            .bind(pull_request.head_branch())
+            .bind(pull_request.base_branch_name())
+            .bind(pull_request.title())
+            .bind(pull_request.body())
+            .bind(pull_request.options().is_draft())
+            .bind(pull_request.options().labels().join("\n"))
+            .bind(pull_request.options().assignees().join("\n"))
+            .bind(pull_request.options().reviewers().join("\n"))
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the PRs that have not been opened yet in the order that they were saved.
+    pub async fn pending_pull_requests(&mut self) -> Result<Vec<GithubPullRequest>> {
+        let rows = query_as::<SqlitePendingPullRequest>(statements::SELECT_PENDING_PULL_REQUESTS)
+            .fetch_all(self.connection())
+            .await?;
+        let pull_requests = rows
+            .into_iter()
+            .map(|row| {
+                let options = GithubPullRequestOptions::new(
+                    row.is_draft,
+                    lines(&row.labels),
+                    lines(&row.assignees),
+                    lines(&row.reviewers),
+                );
+                GithubPullRequest::new(&row.title, &row.body, &row.head_branch_name)
+                    .in_repository(&row.owner, &row.repo, &row.base_branch_name)
+                    .with_options(options)
+            })
+            .collect();
+        Ok(pull_requests)
+    }
+
+    /// Removes a PR from the pending PRs once it has been opened.
+    pub async fn remove_pending_pull_request(
+        &mut self,
+        pull_request: &GithubPullRequest,
+    ) -> Result<()> {
+        query(statements::DELETE_PENDING_PULL_REQUEST)
+            .bind(pull_request.owner())
+            .bind(pull_request.repo())
+            .bind(pull_request.head_branch())
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+}
+
+mod statements {
+    use crate::utils::db::query::RoswaalSql;
+
+    pub const SELECT_UNMERGED_BRANCH_NAMES: &str = "
+SELECT unmerged_branch_name FROM Tests WHERE unmerged_branch_name IS NOT NULL
+UNION
+SELECT unmerged_branch_name FROM Locations WHERE unmerged_branch_name IS NOT NULL
+UNION
+SELECT unmerged_branch_name FROM StagedTestRemovals
+UNION
+SELECT unmerged_branch_name FROM StagedLocationRemovals
+ORDER BY unmerged_branch_name;
+";
+
+    pub const INSERT_PENDING_PULL_REQUEST: RoswaalSql = RoswaalSql::new(
+        "
+INSERT OR REPLACE INTO PendingPullRequests (
+    owner,
+    repo,
+    head_branch_name,
+    base_branch_name,
+    title,
+    body,
+    is_draft,
+    labels,
+    assignees,
+    reviewers
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
+",
+        "
+INSERT INTO PendingPullRequests (
+    owner,
+    repo,
+    head_branch_name,
+    base_branch_name,
+    title,
+    body,
+    is_draft,
+    labels,
+    assignees,
+    reviewers
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT (owner, repo, head_branch_name) DO UPDATE SET
+    id = DEFAULT,
+    base_branch_name = excluded.base_branch_name,
+    title = excluded.title,
+    body = excluded.body,
+    is_draft = excluded.is_draft,
+    labels = excluded.labels,
+    assignees = excluded.assignees,
+    reviewers = excluded.reviewers,
+    creation_date = DEFAULT;
+",
+    );
+
+    pub const SELECT_PENDING_PULL_REQUESTS: RoswaalSql = RoswaalSql::new(
+        "
+SELECT
+    owner,
+    repo,
+    head_branch_name,
+    base_branch_name,
+    title,
+    body,
+    is_draft,
+    labels,
+    assignees,
+    reviewers
+FROM PendingPullRequests
+ORDER BY creation_date, rowid;
+",
+        "
+SELECT
+    owner,
+    repo,
+    head_branch_name,
+    base_branch_name,
+    title,
+    body,
+    is_draft,
+    labels,
+    assignees,
+    reviewers
+FROM PendingPullRequests
+ORDER BY creation_date, id;
+",
+    );
+
+    pub const DELETE_PENDING_PULL_REQUEST: &str =
+        "DELETE FROM PendingPullRequests WHERE owner = ? AND repo = ? AND head_branch_name = ?;";
+}
+
+#[derive(FromRow)]
+struct SqliteBranchName {
+    unmerged_branch_name: RoswaalOwnedGitBranchName,
+}
+
+#[derive(FromRow)]
+struct SqlitePendingPullRequest {
+    owner: String,
+    repo: String,
+    head_branch_name: RoswaalOwnedGitBranchName,
+    base_branch_name: String,
+    title: String,
+    body: String,
+    is_draft: bool,
+    labels: String,
+    assignees: String,
+    reviewers: String,
+}
+
+fn lines(str: &str) -> Vec<String> {
+    str.lines().map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        git::{
+            branch_name::RoswaalOwnedGitBranchName,
+            pull_request::{GithubPullRequest, GithubPullRequestOptions},
+        },
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        location::{location::RoswaalLocation, name::RoswaalLocationName},
+        utils::db::RoswaalDatabase,
+    };
+
+    #[tokio::test]
+    async fn unmerged_branch_names_is_empty_by_default() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let names = transaction.unmerged_branch_names().await.unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(names, vec![])
+    }
+
+    #[tokio::test]
+    async fn unmerged_branch_names_includes_each_unmerged_branch_once() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let tests_branch = RoswaalOwnedGitBranchName::for_adding_tests();
+        let merged_tests_branch = RoswaalOwnedGitBranchName::for_adding_tests();
+        let locations_branch = RoswaalOwnedGitBranchName::for_adding_locations();
+        let location_removals_branch = RoswaalOwnedGitBranchName::for_removing_locations();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![compiled_test("Test 1"), compiled_test("Test 2")];
+        transaction.save_tests(&tests, &tests_branch).await.unwrap();
+        transaction
+            .save_tests(&tests, &merged_tests_branch)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&merged_tests_branch)
+            .await
+            .unwrap();
+        let locations = vec![RoswaalLocation::new_without_validation("Test", 50.0, 50.0)];
+        transaction
+            .save_locations(&locations, &locations_branch)
+            .await
+            .unwrap();
+        transaction
+            .stage_location_removals(
+                &vec![RoswaalLocationName::from_str("Test").unwrap()],
+                &location_removals_branch,
+            )
+            .await
+            .unwrap();
+        let names = transaction.unmerged_branch_names().await.unwrap();
+        transaction.commit().await.unwrap();
+        let mut expected_names = vec![tests_branch, locations_branch, location_removals_branch];
+        expected_names.sort_by_key(|n| n.to_string());
+        assert_eq!(names, expected_names)
+    }
+
+    #[tokio::test]
+    async fn saves_and_removes_pending_pull_requests_in_order() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests_pr = GithubPullRequest::for_tif_react_frontend(
+            "Add Tests",
+            "Adds tests",
+            &RoswaalOwnedGitBranchName::for_adding_tests(),
+        );
+        let locations_pr = GithubPullRequest::for_tif_react_frontend(
+            "Add Locations",
+            "Adds locations",
+            &RoswaalOwnedGitBranchName::for_adding_locations(),
+        )
+        .in_repository("tifapp", "FitnessProjectIOS", "main")
+        .with_options(GithubPullRequestOptions::new(
+            true,
+            vec!["acceptance-tests".to_string(), "roswaal".to_string()],
+            vec![],
+            vec!["mhayes853".to_string()],
+        ));
+        transaction
+            .save_pending_pull_request(&tests_pr)
+            .await
+            .unwrap();
+        transaction
+            .save_pending_pull_request(&locations_pr)
+            .await
+            .unwrap();
+        transaction
+            .save_pending_pull_request(&tests_pr)
+            .await
+            .unwrap();
+        let prs = transaction.pending_pull_requests().await.unwrap();
+        assert_eq!(prs, vec![locations_pr.clone(), tests_pr.clone()]);
+        transaction
+            .remove_pending_pull_request(&tests_pr)
+            .await
+            .unwrap();
+        let prs = transaction.pending_pull_requests().await.unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(prs, vec![locations_pr])
+    }
+
+    fn compiled_test(name: &str) -> RoswaalCompiledTest {
+        RoswaalCompiledTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
 use dotenv::dotenv;
 use once_cell::sync::Lazy;
+use reqwest::StatusCode;
 use std::future::Future;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -12,10 +14,16 @@ use tokio::sync::Mutex;
 
 use super::{
     branch_name::RoswaalOwnedGitBranchName,
+    clean::RoswaalLargeFileDeletion,
+    health::RoswaalGitRepositoryHealth,
     metadata::{self, RoswaalGitRepositoryMetadata},
-    pull_request::{GithubPullRequest, GithubPullRequestOpen},
+    pull_request::{
+        GithubCheckReachability, GithubPullRequest, GithubPullRequestComment,
+        GithubPullRequestOpen, GithubUnavailableError,
+    },
     repo::{
-        LibGit2RepositoryClient, PullBranchStatus, RoswaalGitRepository, RoswaalGitRepositoryClient,
+        delete_cancelled_branch, LibGit2RepositoryClient, PullBranchStatus, RoswaalGitRepository,
+        RoswaalGitRepositoryClient, RoswaalGitRepositoryTransaction,
     },
 };
 
@@ -23,6 +31,7 @@ use super::{
 pub struct TestGithubPullRequestOpen {
     mutex: Arc<Mutex<Option<GithubPullRequest>>>,
     should_fail: bool,
+    is_unavailable: bool,
 }
 
 #[cfg(test)]
@@ -31,6 +40,15 @@ impl TestGithubPullRequestOpen {
         Self {
             mutex: Arc::new(Mutex::new(None)),
             should_fail,
+            is_unavailable: false,
+        }
+    }
+
+    /// Returns a `GithubPullRequestOpen` that fails as if GitHub is down on every attempt.
+    pub fn unavailable() -> Self {
+        Self {
+            is_unavailable: true,
+            ..Self::new(true)
         }
     }
 }
@@ -54,21 +72,96 @@ impl GithubPullRequestOpen for TestGithubPullRequestOpen {
     async fn open(&self, pull_request: &GithubPullRequest) -> Result<bool> {
         let mut pr = self.mutex.lock().await;
         *pr = Some(pull_request.clone());
+        if self.is_unavailable {
+            return Err(GithubUnavailableError::new(StatusCode::SERVICE_UNAVAILABLE).into());
+        }
         Ok(!self.should_fail)
     }
 }
 
+/// A `GithubPullRequestComment` that records each comment that it is given.
+#[cfg(test)]
+pub struct TestGithubPullRequestComment {
+    comments: Mutex<Vec<(GithubPullRequest, String)>>,
+    should_fail: bool,
+}
+
+#[cfg(test)]
+impl TestGithubPullRequestComment {
+    pub fn new(should_fail: bool) -> Self {
+        Self {
+            comments: Mutex::new(vec![]),
+            should_fail,
+        }
+    }
+
+    pub async fn comments(&self) -> Vec<(GithubPullRequest, String)> {
+        self.comments.lock().await.clone()
+    }
+}
+
+#[cfg(test)]
+impl GithubPullRequestComment for TestGithubPullRequestComment {
+    async fn comment(&self, pull_request: &GithubPullRequest, body: &str) -> Result<bool> {
+        if self.should_fail {
+            return Ok(false);
+        }
+        let mut comments = self.comments.lock().await;
+        comments.push((pull_request.clone(), body.to_string()));
+        Ok(true)
+    }
+}
+
+/// A `GithubCheckReachability` that succeeds or fails without sending a request.
+#[cfg(test)]
+pub struct TestGithubCheckReachability {
+    is_reachable: bool,
+}
+
+#[cfg(test)]
+impl TestGithubCheckReachability {
+    pub fn new(is_reachable: bool) -> Self {
+        Self { is_reachable }
+    }
+}
+
+#[cfg(test)]
+impl GithubCheckReachability for TestGithubCheckReachability {
+    async fn check_reachability(&self) -> Result<()> {
+        if self.is_reachable {
+            Ok(())
+        } else {
+            Err(GithubUnavailableError::new(StatusCode::SERVICE_UNAVAILABLE).into())
+        }
+    }
+}
+
 /// A `RoswaalGitRepositoryClient` implementation suitable for test-stubbing.
 #[cfg(test)]
 pub struct NoopGitRepositoryClient {
     metadata: RoswaalGitRepositoryMetadata,
     should_merge_conflict: bool,
+    remote_branch_names: Vec<String>,
+    local_branch_names: Vec<String>,
+    health: RoswaalGitRepositoryHealth,
 }
 
 impl NoopGitRepositoryClient {
     pub fn ensure_merge_conflict(&mut self) {
         self.should_merge_conflict = true
     }
+
+    pub fn set_remote_branch_names(&mut self, names: Vec<String>) {
+        self.remote_branch_names = names
+    }
+
+    pub fn set_local_branch_names(&mut self, names: Vec<String>) {
+        self.local_branch_names = names
+    }
+
+    pub fn set_health(&mut self, health: RoswaalGitRepositoryHealth) {
+        self.health = health
+    }
 }
 
 #[cfg(test)]
@@ -77,6 +170,9 @@ impl RoswaalGitRepositoryClient for NoopGitRepositoryClient {
         Ok(Self {
             metadata: metadata.clone(),
             should_merge_conflict: false,
+            remote_branch_names: vec![],
+            local_branch_names: vec![],
+            health: RoswaalGitRepositoryHealth::clean(metadata.base_branch_name()),
         })
     }
 
@@ -112,13 +208,44 @@ impl RoswaalGitRepositoryClient for NoopGitRepositoryClient {
         Ok(())
     }
 
-    async fn clean_all_untracked(&self) -> Result<()> {
+    async fn clean_all_untracked(&self, _: RoswaalLargeFileDeletion) -> Result<()> {
         Ok(())
     }
 
     async fn delete_local_branch(&self, _: &RoswaalOwnedGitBranchName) -> Result<bool> {
         Ok(true)
     }
+
+    async fn delete_remote_branch(&self, _: &RoswaalOwnedGitBranchName) -> Result<()> {
+        Ok(())
+    }
+
+    fn delete_cancelled_branch(
+        transaction: RoswaalGitRepositoryTransaction<Self>,
+        branch_name: RoswaalOwnedGitBranchName,
+    ) {
+        tokio::spawn(delete_cancelled_branch(transaction, branch_name));
+    }
+
+    async fn remote_branch_names(&self) -> Result<Vec<String>> {
+        Ok(self.remote_branch_names.clone())
+    }
+
+    async fn local_branch_names(&self) -> Result<Vec<String>> {
+        Ok(self.local_branch_names.clone())
+    }
+
+    async fn health(&self) -> Result<RoswaalGitRepositoryHealth> {
+        Ok(self.health.clone())
+    }
+
+    async fn abort_merge(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn reclone(&mut self) -> Result<()> {
+        Ok(())
+    }
 }
 
 impl RoswaalGitRepository<NoopGitRepositoryClient> {
@@ -133,6 +260,30 @@ impl RoswaalGitRepository<NoopGitRepositoryClient> {
         drop(transaction);
         Ok(repo)
     }
+
+    pub async fn noop_with_remote_branch_names(names: &[&str]) -> Result<Self> {
+        let repo = Self::open(&RoswaalGitRepositoryMetadata::for_testing()).await?;
+        let mut transaction = repo.transaction().await;
+        transaction.set_remote_branch_names(names.iter().map(|n| n.to_string()).collect());
+        drop(transaction);
+        Ok(repo)
+    }
+
+    pub async fn noop_with_local_branch_names(names: &[&str]) -> Result<Self> {
+        let repo = Self::open(&RoswaalGitRepositoryMetadata::for_testing()).await?;
+        let mut transaction = repo.transaction().await;
+        transaction.set_local_branch_names(names.iter().map(|n| n.to_string()).collect());
+        drop(transaction);
+        Ok(repo)
+    }
+
+    pub async fn noop_with_health(health: RoswaalGitRepositoryHealth) -> Result<Self> {
+        let repo = Self::open(&RoswaalGitRepositoryMetadata::for_testing()).await?;
+        let mut transaction = repo.transaction().await;
+        transaction.set_health(health);
+        drop(transaction);
+        Ok(repo)
+    }
 }
 
 #[cfg(test)]
@@ -146,7 +297,7 @@ pub async fn repo_with_test_metadata() -> Result<(
 }
 
 #[cfg(test)]
-pub async fn write_string(path: &str, contents: &str) -> Result<()> {
+pub async fn write_string(path: impl AsRef<Path>, contents: &str) -> Result<()> {
     let mut file = File::create(path).await?;
     file.write(contents.as_bytes()).await?;
     file.flush().await?;
@@ -154,7 +305,7 @@ pub async fn write_string(path: &str, contents: &str) -> Result<()> {
 }
 
 #[cfg(test)]
-pub async fn read_string(path: &str) -> Result<String> {
+pub async fn read_string(path: impl AsRef<Path>) -> Result<String> {
     let mut file = File::open(path).await?;
     let mut contents = String::new();
     file.read_to_string(&mut contents).await?;
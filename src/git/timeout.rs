@@ -0,0 +1,137 @@
+use std::{error::Error, fmt::Display, future::Future, time::Duration};
+
+use anyhow::Result;
+use tokio::time::timeout;
+
+/// An error that occurs when a git operation does not finish within the git operation timeout.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GitOperationTimedOut {
+    operation: String,
+    timeout: Duration,
+}
+
+impl GitOperationTimedOut {
+    pub fn new(operation: &str, timeout: Duration) -> Self {
+        Self {
+            operation: operation.to_string(),
+            timeout,
+        }
+    }
+
+    /// Returns the timeout error in the chain of `error`, if any.
+    pub fn from_error(error: &anyhow::Error) -> Option<&Self> {
+        error.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+}
+
+impl GitOperationTimedOut {
+    /// A description of the operation that timed out (eg. `git push origin main`).
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl Display for GitOperationTimedOut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` did not finish within {} seconds.",
+            self.operation,
+            self.timeout.as_secs()
+        )
+    }
+}
+
+impl Error for GitOperationTimedOut {}
+
+/// An error that occurs when a git operation cannot start because an earlier operation on the
+/// same clone timed out and is still running.
+///
+/// Starting another operation on the clone would race the earlier one for the working tree and
+/// index, so operations fail fast until the earlier operation gives control back.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GitOperationStillRunning {
+    operation: String,
+}
+
+impl GitOperationStillRunning {
+    pub fn new(operation: &str) -> Self {
+        Self {
+            operation: operation.to_string(),
+        }
+    }
+
+    /// Returns the still running error in the chain of `error`, if any.
+    pub fn from_error(error: &anyhow::Error) -> Option<&Self> {
+        error.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+}
+
+impl Display for GitOperationStillRunning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` could not start because a git operation that timed out is still running.",
+            self.operation
+        )
+    }
+}
+
+impl Error for GitOperationStillRunning {}
+
+/// Runs `operation`, and fails with `GitOperationTimedOut` if it does not finish within
+/// `duration`.
+///
+/// `operation` is dropped when it times out, so it must release anything it holds (eg. by
+/// killing a child process) when dropped for the timeout to cancel the underlying work.
+pub async fn with_git_timeout<T>(
+    operation_name: &str,
+    duration: Duration,
+    operation: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout(duration, operation).await {
+        Ok(result) => result,
+        Err(_) => {
+            log::error!(
+                "Cancelled `{}` after {} seconds.",
+                operation_name,
+                duration.as_secs()
+            );
+            Err(GitOperationTimedOut::new(operation_name, duration).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::sleep;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_result_of_operations_that_finish_in_time() {
+        let result = with_git_timeout("git status", Duration::from_secs(1), async { Ok(1) }).await;
+        assert_eq!(result.unwrap(), 1)
+    }
+
+    #[tokio::test]
+    async fn fails_operations_that_do_not_finish_in_time() {
+        let result = with_git_timeout("git push", Duration::from_millis(10), async {
+            sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await;
+        let error = result.unwrap_err();
+        assert_eq!(
+            GitOperationTimedOut::from_error(&error),
+            Some(&GitOperationTimedOut::new(
+                "git push",
+                Duration::from_millis(10)
+            ))
+        )
+    }
+}
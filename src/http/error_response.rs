@@ -0,0 +1,208 @@
+use std::{error::Error, fmt::Display};
+
+use axum::{
+    extract::rejection::{JsonRejection, QueryRejection},
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::error;
+use nanoid::nanoid;
+use reqwest::StatusCode;
+use serde::Serialize;
+use sqlx::{error::ErrorKind, postgres::PgDatabaseError, sqlite::SqliteError};
+
+use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+/// A code that identifies why a request to an endpoint failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoswaalHttpErrorCode {
+    UnknownBranchKind,
+    InvalidPayload,
+    StorageConflict,
+    Internal,
+}
+
+impl RoswaalHttpErrorCode {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::UnknownBranchKind | Self::InvalidPayload => StatusCode::BAD_REQUEST,
+            Self::StorageConflict => StatusCode::CONFLICT,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// An error that is returned as a JSON body by the `/merge`, `/close`, and `/progress` endpoints.
+///
+/// Each error has a unique correlation id that is also logged alongside the error, so that a
+/// failure seen by the CI runner can be found in the logs of this tool.
+#[derive(Debug, Serialize)]
+pub struct RoswaalHttpError {
+    code: RoswaalHttpErrorCode,
+    message: String,
+    correlation_id: String,
+}
+
+impl RoswaalHttpError {
+    pub fn new(code: RoswaalHttpErrorCode, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+            correlation_id: nanoid!(),
+        }
+    }
+
+    pub fn unknown_branch_kind(branch_name: &RoswaalOwnedGitBranchName) -> Self {
+        Self::new(
+            RoswaalHttpErrorCode::UnknownBranchKind,
+            &format!(
+                "The branch {} was not created by any known command.",
+                branch_name.to_string()
+            ),
+        )
+    }
+
+    /// Classifies `error` as a storage conflict if it was caused by a unique constraint violation
+    /// or a locked database, and as an internal error otherwise.
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        let is_storage_conflict = error
+            .chain()
+            .filter_map(|e| e.downcast_ref::<sqlx::Error>())
+            .any(is_storage_conflict);
+        let code = if is_storage_conflict {
+            RoswaalHttpErrorCode::StorageConflict
+        } else {
+            RoswaalHttpErrorCode::Internal
+        };
+        Self::new(code, &error.to_string())
+    }
+}
+
+impl RoswaalHttpError {
+    pub fn code(&self) -> RoswaalHttpErrorCode {
+        self.code
+    }
+
+    pub fn correlation_id(&self) -> &str {
+        &self.correlation_id
+    }
+}
+
+/// The primary result codes of `SQLITE_BUSY` and `SQLITE_LOCKED`.
+const SQLITE_BUSY_RESULT_CODES: [i32; 2] = [5, 6];
+
+/// The Postgres error codes of serialization failures, deadlocks, and unavailable locks.
+const POSTGRES_CONFLICT_CODES: [&str; 3] = ["40001", "40P01", "55P03"];
+
+fn is_storage_conflict(error: &sqlx::Error) -> bool {
+    let sqlx::Error::Database(database_error) = error else {
+        return false;
+    };
+    if let ErrorKind::UniqueViolation = database_error.kind() {
+        return true;
+    }
+    if let Some(error) = database_error.try_downcast_ref::<PgDatabaseError>() {
+        return POSTGRES_CONFLICT_CODES.contains(&error.code());
+    }
+    if database_error.try_downcast_ref::<SqliteError>().is_none() {
+        return false;
+    }
+    // NB: SQLite reports extended result codes, the primary result code is in the lowest byte.
+    database_error
+        .code()
+        .and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| SQLITE_BUSY_RESULT_CODES.contains(&(code & 0xff)))
+}
+
+impl From<QueryRejection> for RoswaalHttpError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::new(RoswaalHttpErrorCode::InvalidPayload, &rejection.body_text())
+    }
+}
+
+impl From<JsonRejection> for RoswaalHttpError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(RoswaalHttpErrorCode::InvalidPayload, &rejection.body_text())
+    }
+}
+
+impl Display for RoswaalHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl Error for RoswaalHttpError {}
+
+impl IntoResponse for RoswaalHttpError {
+    fn into_response(self) -> Response {
+        error!("Request {} failed with {}.", self.correlation_id, self);
+        (self.code.status_code(), Json(self)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::{
+        db::{query::query, RoswaalDatabase},
+        test_error::TestError,
+    };
+    use axum::body::to_bytes;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn into_response_renders_json_body() {
+        let error = RoswaalHttpError::new(RoswaalHttpErrorCode::InvalidPayload, "Missing branch");
+        let correlation_id = error.correlation_id().to_string();
+        let resp = error.into_response();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({
+                "code": "invalid_payload",
+                "message": "Missing branch",
+                "correlation_id": correlation_id
+            })
+        )
+    }
+
+    #[test]
+    fn each_error_has_a_unique_correlation_id() {
+        let error1 = RoswaalHttpError::new(RoswaalHttpErrorCode::Internal, "Test");
+        let error2 = RoswaalHttpError::new(RoswaalHttpErrorCode::Internal, "Test");
+        assert_ne!(error1.correlation_id(), error2.correlation_id())
+    }
+
+    #[test]
+    fn from_error_is_internal_for_non_storage_errors() {
+        let error = RoswaalHttpError::from_error(&anyhow::Error::new(TestError));
+        assert_eq!(error.code(), RoswaalHttpErrorCode::Internal);
+        assert_eq!(
+            error.code().status_code(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        )
+    }
+
+    #[tokio::test]
+    async fn from_error_is_storage_conflict_for_unique_violations() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        query("CREATE TABLE Conflicts (name TEXT NOT NULL UNIQUE)")
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        let insert = "INSERT INTO Conflicts (name) VALUES ('Test')";
+        query(insert)
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        let result = query(insert).execute(transaction.connection()).await;
+        let error = RoswaalHttpError::from_error(&result.unwrap_err().into());
+        assert_eq!(error.code(), RoswaalHttpErrorCode::StorageConflict);
+        assert_eq!(error.code().status_code(), StatusCode::CONFLICT)
+    }
+}
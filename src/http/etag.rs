@@ -0,0 +1,106 @@
+use std::{fmt::Display, future::Future};
+
+use anyhow::Result;
+use axum::{
+    http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderMap,
+    },
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+
+use crate::{utils::db::RoswaalDatabase, with_transaction};
+
+/// An entity tag for the responses of endpoints that only change when the stored tests or
+/// locations change.
+///
+/// The tag is the data revision of the database, so a client that sends the tag of its last
+/// response in `If-None-Match` gets a `304 Not Modified` until a test or location changes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RoswaalRevisionETag(i64);
+
+impl RoswaalRevisionETag {
+    pub fn new(revision: i64) -> Self {
+        Self(revision)
+    }
+
+    /// Loads the tag for the current data revision of `database`.
+    ///
+    /// This should be loaded before the data in the response, so that a change that is saved in
+    /// between is picked up by the next request instead of being hidden behind a stale tag.
+    pub async fn current(database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        let revision = with_transaction!(transaction, async { transaction.data_revision().await })?;
+        Ok(Self::new(revision))
+    }
+}
+
+impl RoswaalRevisionETag {
+    /// Returns true if the `If-None-Match` header in `headers` contains this tag.
+    pub fn is_matched_by(&self, headers: &HeaderMap) -> bool {
+        let tag = self.to_string();
+        headers
+            .get_all(IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|value| value.trim())
+            .any(|value| value == "*" || value.trim_start_matches("W/") == tag)
+    }
+}
+
+/// Responds with `304 Not Modified` if `headers` match the current `RoswaalRevisionETag` of
+/// `database`, or with the response from `make_response` otherwise.
+///
+/// The tag is included in the headers of both responses, and `make_response` is only awaited when
+/// the client does not have the current data.
+pub async fn respond_with_revision_etag<Body: IntoResponse>(
+    database: &RoswaalDatabase,
+    headers: &HeaderMap,
+    make_response: impl Future<Output = Body>,
+) -> Response {
+    let Ok(etag) = RoswaalRevisionETag::current(database).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let response = if etag.is_matched_by(headers) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        make_response.await.into_response()
+    };
+    ([(ETAG, etag.to_string())], response).into_response()
+}
+
+impl Display for RoswaalRevisionETag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\"", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn is_matched_by_tags_in_if_none_match() {
+        let etag = RoswaalRevisionETag::new(12);
+        let cases = [
+            (None, false),
+            (Some("\"12\""), true),
+            (Some("W/\"12\""), true),
+            (Some("\"11\", \"12\""), true),
+            (Some("*"), true),
+            (Some("\"120\""), false),
+            (Some("12"), false),
+        ];
+        for (value, is_matched) in cases {
+            let mut headers = HeaderMap::new();
+            if let Some(value) = value {
+                headers.insert(IF_NONE_MATCH, HeaderValue::from_static(value));
+            }
+            assert_eq!(etag.is_matched_by(&headers), is_matched, "{:?}", value)
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::env;
+
+use axum::http::HeaderMap;
+use openssl::{hash::MessageDigest, memcmp, pkey::PKey, sign::Signer};
+use serde::Deserialize;
+
+use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+pub const DEV_GITHUB_WEBHOOK_SECRET: &str = "he's not the strongest because he's the honored one";
+
+const SIGNATURE_HEADER: &str = "X-Hub-Signature-256";
+const EVENT_HEADER: &str = "X-GitHub-Event";
+
+/// The secret used to sign the payloads of the Github webhook.
+///
+/// Github signs each webhook delivery with an HMAC SHA256 hex digest of the raw request body, and
+/// sends it in the `X-Hub-Signature-256` header as `sha256=<digest>`. Deliveries with a missing or
+/// incorrect signature are rejected so that only Github can merge or close branches.
+#[derive(Debug, Clone)]
+pub struct GithubWebhookSecret {
+    secret: String,
+}
+
+impl GithubWebhookSecret {
+    /// Returns the secret set in `GITHUB_WEBHOOK_SECRET`, or `None` if the webhook is not
+    /// configured.
+    pub fn prod() -> Option<Self> {
+        env::var("GITHUB_WEBHOOK_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty())
+            .map(|secret| Self { secret })
+    }
+
+    pub fn dev() -> Self {
+        Self {
+            secret: DEV_GITHUB_WEBHOOK_SECRET.to_string(),
+        }
+    }
+}
+
+impl GithubWebhookSecret {
+    /// Returns the value of the `X-Hub-Signature-256` header for the specified body.
+    pub fn signature(&self, body: &[u8]) -> String {
+        let key = PKey::hmac(self.secret.as_bytes()).expect("Failed to create HMAC key.");
+        let mut signer =
+            Signer::new(MessageDigest::sha256(), &key).expect("Failed to create HMAC signer.");
+        let digest = signer
+            .sign_oneshot_to_vec(body)
+            .expect("Failed to compute HMAC digest.");
+        let hex = digest
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        format!("sha256={}", hex)
+    }
+
+    /// Returns true if the headers contain a valid signature for the specified body.
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> bool {
+        let signature = match headers.get(SIGNATURE_HEADER) {
+            Some(value) => value.as_bytes(),
+            None => return false,
+        };
+        let expected_signature = self.signature(body);
+        signature.len() == expected_signature.len()
+            && memcmp::eq(signature, expected_signature.as_bytes())
+    }
+}
+
+/// An action that this tool takes in response to a Github webhook delivery.
+#[derive(Debug, PartialEq, Eq)]
+pub enum GithubWebhookAction {
+    /// A pull request from a branch owned by this tool was merged.
+    MergeBranch(RoswaalOwnedGitBranchName),
+    /// A pull request from a branch owned by this tool was closed without merging.
+    CloseBranch(RoswaalOwnedGitBranchName),
+    /// The delivery does not require any action.
+    Ignore,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestEvent {
+    action: String,
+    pull_request: PullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    merged: bool,
+    head: PullRequestHead,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestHead {
+    #[serde(rename = "ref")]
+    branch_name: String,
+}
+
+impl GithubWebhookAction {
+    /// Determines the action for a delivery from its headers and raw body.
+    ///
+    /// Only closed `pull_request` events from branches owned by this tool result in an action, all
+    /// other events (including the initial `ping` event) are ignored. An error is returned if the
+    /// body of a `pull_request` event cannot be parsed.
+    pub fn from_delivery(headers: &HeaderMap, body: &[u8]) -> serde_json::Result<Self> {
+        let event = headers
+            .get(EVENT_HEADER)
+            .and_then(|value| value.to_str().ok());
+        if event != Some("pull_request") {
+            return Ok(Self::Ignore);
+        }
+        let event = serde_json::from_slice::<PullRequestEvent>(body)?;
+        if event.action != "closed" {
+            return Ok(Self::Ignore);
+        }
+        let branch_name = match serde_json::from_value::<RoswaalOwnedGitBranchName>(
+            serde_json::Value::String(event.pull_request.head.branch_name),
+        ) {
+            Ok(name) => name,
+            Err(_) => return Ok(Self::Ignore),
+        };
+        if event.pull_request.merged {
+            Ok(Self::MergeBranch(branch_name))
+        } else {
+            Ok(Self::CloseBranch(branch_name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn signature_matches_github_example() {
+        // NB: Taken from https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries
+        let secret = GithubWebhookSecret {
+            secret: "It's a Secret to Everybody".to_string(),
+        };
+        assert_eq!(
+            secret.signature(b"Hello, World!"),
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        )
+    }
+
+    #[test]
+    fn verify_returns_false_when_signature_is_missing_or_incorrect() {
+        let secret = GithubWebhookSecret::dev();
+        let body = b"{}";
+        assert!(!secret.verify(&HeaderMap::new(), body));
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_static("sha256=abc"));
+        assert!(!secret.verify(&headers, body));
+        let wrong_secret = GithubWebhookSecret {
+            secret: "wrong".to_string(),
+        };
+        let signature = HeaderValue::from_str(&wrong_secret.signature(body)).unwrap();
+        headers.insert(SIGNATURE_HEADER, signature);
+        assert!(!secret.verify(&headers, body));
+    }
+
+    #[test]
+    fn verify_returns_true_when_signature_is_correct() {
+        let secret = GithubWebhookSecret::dev();
+        let body = b"{\"hello\": \"world\"}";
+        let mut headers = HeaderMap::new();
+        let signature = HeaderValue::from_str(&secret.signature(body)).unwrap();
+        headers.insert(SIGNATURE_HEADER, signature);
+        assert!(secret.verify(&headers, body))
+    }
+
+    #[test]
+    fn action_ignores_non_pull_request_events() {
+        let headers = event_headers("ping");
+        let action = GithubWebhookAction::from_delivery(&headers, b"{\"zen\": \"hi\"}").unwrap();
+        assert_eq!(action, GithubWebhookAction::Ignore)
+    }
+
+    #[test]
+    fn action_ignores_pull_requests_that_are_not_closed() {
+        let body = pull_request_body("opened", false, "roswaal-add-tests-1234567890");
+        let action = GithubWebhookAction::from_delivery(&event_headers("pull_request"), &body);
+        assert_eq!(action.unwrap(), GithubWebhookAction::Ignore)
+    }
+
+    #[test]
+    fn action_ignores_branches_not_owned_by_roswaal() {
+        let body = pull_request_body("closed", true, "feature/cool-stuff");
+        let action = GithubWebhookAction::from_delivery(&event_headers("pull_request"), &body);
+        assert_eq!(action.unwrap(), GithubWebhookAction::Ignore)
+    }
+
+    #[test]
+    fn action_merges_branch_when_pull_request_merged() {
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+        let body = pull_request_body("closed", true, &branch_name.to_string());
+        let action = GithubWebhookAction::from_delivery(&event_headers("pull_request"), &body);
+        assert_eq!(
+            action.unwrap(),
+            GithubWebhookAction::MergeBranch(branch_name)
+        )
+    }
+
+    #[test]
+    fn action_closes_branch_when_pull_request_closed_without_merging() {
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_locations();
+        let body = pull_request_body("closed", false, &branch_name.to_string());
+        let action = GithubWebhookAction::from_delivery(&event_headers("pull_request"), &body);
+        assert_eq!(
+            action.unwrap(),
+            GithubWebhookAction::CloseBranch(branch_name)
+        )
+    }
+
+    #[test]
+    fn action_errors_when_pull_request_body_is_malformed() {
+        let action = GithubWebhookAction::from_delivery(&event_headers("pull_request"), b"{}");
+        assert!(action.is_err())
+    }
+
+    fn event_headers(event: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(EVENT_HEADER, HeaderValue::from_static(event));
+        headers
+    }
+
+    fn pull_request_body(action: &str, merged: bool, branch_name: &str) -> Vec<u8> {
+        json!({
+            "action": action,
+            "pull_request": {
+                "merged": merged,
+                "head": { "ref": branch_name }
+            }
+        })
+        .to_string()
+        .into_bytes()
+    }
+}
@@ -1,4 +1,9 @@
+pub mod error_response;
+pub mod etag;
+pub mod github_webhook;
 pub mod password;
+pub mod rate_limit;
 pub mod response_result;
 pub mod server;
 pub mod server_environment;
+pub mod shutdown;
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Form, Json,
+};
+use http_body_util::LengthLimitError;
+
+use crate::slack::{
+    handler::RoswaalSlackRequest, message_view::MessageView, slow_down_view::SlowDownView,
+    ui_lib::slack_view::render_slack_view,
+};
+
+use super::server::SlackResponse;
+
+/// The largest slack command body that is read, which is far larger than any command that slack
+/// sends.
+const MAX_SLACK_COMMAND_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// A per-user token bucket rate limiter for slack commands.
+///
+/// Each user can run up to `capacity` commands in a burst, and regains the ability to run 1
+/// command every `refill_interval`. This prevents a runaway script from spamming PR creation.
+/// The buckets are only stored in memory, so they are reset when the server restarts.
+#[derive(Debug, Clone)]
+pub struct SlackCommandRateLimiter {
+    capacity: f64,
+    refill_interval: Duration,
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl SlackCommandRateLimiter {
+    pub fn new(capacity: u32, refill_interval: Duration) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_interval,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl SlackCommandRateLimiter {
+    /// Takes a token from the bucket of the specified user.
+    ///
+    /// Returns the duration that the user must wait before running another command if their
+    /// bucket is empty.
+    pub fn try_acquire(&self, user_id: &str) -> Result<(), Duration> {
+        self.try_acquire_at(user_id, Instant::now())
+    }
+
+    fn try_acquire_at(&self, user_id: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        // NB: Full buckets are indistinguishable from new buckets, so drop them to avoid keeping
+        // an entry for every user that has ever run a command.
+        buckets.retain(|_, bucket| self.refilled_tokens(bucket, now) < self.capacity);
+        let bucket = buckets.entry(user_id.to_string()).or_insert(TokenBucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+        bucket.tokens = self.refilled_tokens(bucket, now);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(self.refill_interval.mul_f64(1.0 - bucket.tokens))
+        }
+    }
+
+    fn refilled_tokens(&self, bucket: &TokenBucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        let refilled = elapsed.as_secs_f64() / self.refill_interval.as_secs_f64();
+        (bucket.tokens + refilled).min(self.capacity)
+    }
+}
+
+/// Middleware to rate limit slack commands by the id of the user that sent them.
+///
+/// When the user has run too many commands, a `SlowDownView` is sent as the response instead of
+/// running the command. Requests that are not valid slack commands are passed through so that
+/// the handler can reject them. Bodies larger than 2 MB are rejected with a 413 before they are
+/// fully read.
+pub async fn rate_limit_slack_commands_middleware(
+    req: Request,
+    next: Next,
+    rate_limiter: SlackCommandRateLimiter,
+) -> Response {
+    let (parts, body) = req.into_parts();
+    let bytes = match to_bytes(body, MAX_SLACK_COMMAND_BODY_SIZE).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            let status = if error.into_inner().is::<LengthLimitError>() {
+                StatusCode::PAYLOAD_TOO_LARGE
+            } else {
+                StatusCode::BAD_REQUEST
+            };
+            return status.into_response();
+        }
+    };
+    let form_req = Request::from_parts(parts.clone(), Body::from(bytes.clone()));
+    let req = Request::from_parts(parts, Body::from(bytes));
+    let Ok(Form(slack_request)) = Form::<RoswaalSlackRequest>::from_request(form_req, &()).await
+    else {
+        return next.run(req).await;
+    };
+    match rate_limiter.try_acquire(slack_request.user_id()) {
+        Ok(()) => next.run(req).await,
+        Err(retry_after) => {
+            let view = SlowDownView::new(retry_after);
+            Json(SlackResponse::new(render_slack_view(&MessageView::new(
+                &view,
+            ))))
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use axum::{middleware::from_fn, response::IntoResponse, routing::post, Router};
+    use axum_test::TestServer;
+    use serde_json::Value;
+
+    use crate::slack::{command::RoswaalSlackCommand, handler::RoswaalSlackRequest};
+
+    use super::*;
+
+    #[test]
+    fn allows_bursts_up_to_capacity() {
+        let limiter = SlackCommandRateLimiter::new(2, Duration::from_secs(10));
+        let now = Instant::now();
+        assert_eq!(limiter.try_acquire_at("bob", now), Ok(()));
+        assert_eq!(limiter.try_acquire_at("bob", now), Ok(()));
+        assert_eq!(
+            limiter.try_acquire_at("bob", now),
+            Err(Duration::from_secs(10))
+        );
+    }
+
+    #[test]
+    fn limits_each_user_separately() {
+        let limiter = SlackCommandRateLimiter::new(1, Duration::from_secs(10));
+        let now = Instant::now();
+        assert_eq!(limiter.try_acquire_at("bob", now), Ok(()));
+        assert!(limiter.try_acquire_at("bob", now).is_err());
+        assert_eq!(limiter.try_acquire_at("alice", now), Ok(()));
+    }
+
+    #[test]
+    fn refills_tokens_over_time() {
+        let limiter = SlackCommandRateLimiter::new(1, Duration::from_secs(10));
+        let now = Instant::now();
+        assert_eq!(limiter.try_acquire_at("bob", now), Ok(()));
+        assert_eq!(
+            limiter.try_acquire_at("bob", now + Duration::from_secs(4)),
+            Err(Duration::from_secs(6))
+        );
+        assert_eq!(
+            limiter.try_acquire_at("bob", now + Duration::from_secs(10)),
+            Ok(())
+        );
+    }
+
+    #[tokio::test]
+    async fn responds_with_slow_down_view_when_rate_limited() {
+        let limiter = SlackCommandRateLimiter::new(1, Duration::from_secs(60));
+        let f = from_fn(move |req, next| {
+            rate_limit_slack_commands_middleware(req, next, limiter.clone())
+        });
+        let router = Router::new().route("/", post(endpoint).layer(f));
+        let server = TestServer::new(router).unwrap();
+        let form = RoswaalSlackRequest::new(
+            "channel".to_string(),
+            "bob".to_string(),
+            String::new(),
+            RoswaalSlackCommand::ViewLocations,
+            "https://api.slack.com/chat.postMessage".to_string(),
+        );
+        let resp = server.post("/").form(&form).await;
+        resp.assert_text(RESPONSE_STR);
+        let resp = server.post("/").form(&form).await;
+        let text = resp.json::<Value>()["blocks"].to_string();
+        assert!(text.contains("Slow dooooown!"))
+    }
+
+    #[tokio::test]
+    async fn rejects_bodies_larger_than_the_limit() {
+        let limiter = SlackCommandRateLimiter::new(1, Duration::from_secs(60));
+        let f = from_fn(move |req, next| {
+            rate_limit_slack_commands_middleware(req, next, limiter.clone())
+        });
+        let router = Router::new().route("/", post(endpoint).layer(f));
+        let server = TestServer::new(router).unwrap();
+        let body = vec![b'a'; MAX_SLACK_COMMAND_BODY_SIZE + 1];
+        let resp = server.post("/").bytes(body.into()).await;
+        resp.assert_status(StatusCode::PAYLOAD_TOO_LARGE);
+        let body = vec![b'a'; MAX_SLACK_COMMAND_BODY_SIZE];
+        let resp = server.post("/").bytes(body.into()).await;
+        resp.assert_text(RESPONSE_STR)
+    }
+
+    const RESPONSE_STR: &str = "Ran the command";
+
+    async fn endpoint() -> impl IntoResponse {
+        RESPONSE_STR
+    }
+}
@@ -1,80 +1,290 @@
 use std::sync::Arc;
 
 use anyhow::Error;
+use axum::body::Bytes;
+use axum::http::{header::CONTENT_TYPE, HeaderMap};
 use axum::Form;
 use axum::{
-    extract::Query, http::StatusCode, middleware::from_fn, response::IntoResponse, routing::post,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        Path, Query,
+    },
+    http::StatusCode,
+    middleware::from_fn,
+    response::{IntoResponse, Response},
+    routing::{get, post},
     serve, Json, Router,
 };
 #[cfg(test)]
 use axum_test::TestServer;
+use chrono::Utc;
+use log::{error, info};
 use serde::{Deserialize, Serialize};
 use tokio::net::TcpListener;
+use tokio_util::task::TaskTracker;
 
 use crate::slack::ui_lib::blocks::SlackBlocks;
 use crate::{
+    audit::{
+        entry::{RoswaalAuditLogEntry, GITHUB_AUDIT_USER, HTTP_AUDIT_USER},
+        recorder::RoswaalRecordAuditLog,
+    },
     git::branch_name::RoswaalOwnedGitBranchName,
+    language::platform::RoswaalTestPlatform,
+    location::{geojson::RoswaalLocationsGeoJson, storage::LoadLocationsFilter},
     operations::{
-        add_locations::AddLocationsStatus, add_tests::AddTestsStatus,
-        close_branch::CloseBranchStatus, load_all_locations::LoadAllLocationsStatus,
-        merge_branch::MergeBranchStatus, remove_tests::RemoveTestsStatus,
-        save_progress::save_test_progress, search_tests::SearchTestsStatus,
+        add_location_alias::AddLocationAliasStatus,
+        add_locations::AddLocationsStatus,
+        add_tests::AddTestsStatus,
+        assign_failure::AssignFailureStatus,
+        check_permission::CheckPermissionStatus,
+        close_branch::CloseBranchStatus,
+        deprecate_tests::DeprecateTestsStatus,
+        edit_roster::EditRosterStatus,
+        edit_tests::EditTestStatus,
+        export_tests::ExportTestsStatus,
+        flaky_tests::FlakyTestsStatus,
+        grant_command::GrantCommandStatus,
+        health_check::{RoswaalHealthReport, RoswaalHealthStatus},
+        home_summary::RoswaalHomeSummary,
+        lease_tests::{claim_tests, release_tests},
+        lint_tests::LintTestsStatus,
+        list_branches::ListBranchesStatus,
+        load_all_locations::LoadAllLocationsStatus,
+        merge_branch::MergeBranchStatus,
+        my_failed_tests::MyFailedTestsStatus,
+        open_pending_prs::OpenPendingPullRequestsStatus,
+        reconcile::ReconcileTestsStatus,
+        remove_locations::RemoveLocationsStatus,
+        remove_tests::RemoveTestsStatus,
+        repo_doctor::RepoDoctorStatus,
+        report_test_run::ReportTestRunStatus,
+        reset_test_progress::ResetTestProgressStatus,
+        restore_test::RestoreTestStatus,
+        save_progress::{notify_test_failures, save_test_progress},
+        search_tests::SearchTestsStatus,
+        select_options::load_select_option_names,
+        usage_stats::load_usage_stats,
+        view_audit_log::ViewAuditLogStatus,
+        view_suites::ViewSuitesStatus,
     },
     slack::{
+        add_location_alias_view::AddLocationAliasView,
         add_locations_view::AddLocationsView,
         add_tests_view::AddTestsView,
+        assign_failure_view::AssignFailureView,
+        audit_log_view::AuditLogView,
+        cancel_view::CancelView,
+        close_branch_view::CloseBranchView,
         command::RoswaalSlackCommand,
-        handler::{handle_slack_request, RoswaalSlackHandler, RoswaalSlackRequest},
+        deprecate_tests_view::DeprecateTestsView,
+        edit_test_view::EditTestView,
+        error_view::ErrorView,
+        export_tests_view::ExportTestsView,
+        file::SlackFetchFile,
+        flaky_tests_view::FlakyTestsView,
+        grant_view::GrantView,
+        handler::{
+            handle_slack_interaction, handle_slack_modal_submission, handle_slack_request,
+            RoswaalSlackCommandResponse, RoswaalSlackHandler, RoswaalSlackRequest,
+        },
+        help_view::HelpView,
+        home::{RoswaalSlackEvent, SlackPublishHomeTab},
+        home_view::HomeView,
+        in_flight::RoswaalInFlightOperations,
+        interaction::{RoswaalSlackAction, RoswaalSlackInteraction, RoswaalSlackInteractionForm},
+        lint_tests_view::LintTestsView,
+        list_branches_view::ListBranchesView,
         locations_list_view::LocationsListView,
-        message::SlackSendMessage,
+        message::{SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL},
+        message_view::MessageView,
+        modal::{RoswaalSlackModalSubmission, SlackOpenModal},
+        my_failed_tests_view::MyFailedTestsView,
+        open_pending_prs_view::OpenPendingPullRequestsView,
+        reconcile_view::ReconcileTestsView,
+        remove_locations_view::RemoveLocationsView,
         remove_tests_view::RemoveTestsView,
+        repo_doctor_view::RepoDoctorView,
+        report::truncate_to_slack_message,
+        reset_test_progress_view::ResetTestProgressView,
+        restore_test_view::RestoreTestView,
+        roster_view::RosterView,
         search_tests_view::SearchTestsView,
-        ui_lib::slack_view::SlackView,
+        select_menu::{select_options_from_names, RoswaalSlackSelectOptionsRequest},
+        suites_view::SuitesView,
+        test_failure_view::TestFailureMuteView,
+        test_run_view::TestRunView,
+        ui_lib::{
+            home_tab::SlackHomeTab,
+            slack_view::{render_slack_view, SlackView},
+        },
+        users::RoswaalSlackUserRoster,
     },
-    tests_data::progress::RoswaalTestProgressUpload,
-    utils::sqlite::RoswaalSqlite,
+    tests_data::{
+        estimate::{RoswaalSuiteRuntimeEstimate, RoswaalTestRuntimeEstimator},
+        progress::{
+            RoswaalTestProgressUpload, RoswaalTestProgressUploadOutcome,
+            RoswaalTestProgressUploadResult,
+        },
+        test::RoswaalTest,
+    },
+    utils::{config::RoswaalConfig, db::RoswaalDatabase},
+    with_transaction,
 };
 
 use super::{
-    password::check_password_middleware, response_result::ResponseResult,
+    error_response::{RoswaalHttpError, RoswaalHttpErrorCode},
+    etag::respond_with_revision_etag,
+    github_webhook::{GithubWebhookAction, GithubWebhookSecret},
+    password::check_password_middleware,
+    rate_limit::rate_limit_slack_commands_middleware,
+    response_result::ResponseResult,
     server_environment::ServerEnvironment,
+    shutdown::shutdown_signal,
 };
 
 /// Runs this tool as an http server using the specified `ServerEnvironment`.
+///
+/// When a shutdown signal is received, the server stops accepting new connections and waits for
+/// in-flight requests and background work (eg. git edits from long running slack commands) to
+/// finish before returning.
 pub async fn run_http_server(environment: Arc<ServerEnvironment>) -> anyhow::Result<()> {
     let server = roswaal_server(environment.clone());
     let listener = TcpListener::bind(environment.address()).await?;
-    Ok(serve(listener, server).await?)
+    serve(listener, server)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    let background_tasks = environment.background_tasks();
+    background_tasks.close();
+    info!(
+        "Waiting for {} background task(s) to finish.",
+        background_tasks.len()
+    );
+    background_tasks.wait().await;
+    Ok(())
 }
 
-fn roswaal_server(environment: Arc<ServerEnvironment>) -> Router<()> {
+/// Returns the routes of this tool for the specified `ServerEnvironment`.
+pub fn roswaal_server(environment: Arc<ServerEnvironment>) -> Router<()> {
     let slack_handler = Arc::new(HTTPSlackHandler {
         environment: environment.clone(),
     });
+    let slack_interaction_handler = slack_handler.clone();
     let messenger = environment.slack_messenger();
+    let interaction_messenger = environment.slack_messenger();
+    let slack_tasks = environment.background_tasks();
+    let interaction_tasks = environment.background_tasks();
+    let slack_in_flight = environment.in_flight_operations();
+    let interaction_in_flight = environment.in_flight_operations();
+    let interaction_audit = environment.database();
     let password = environment.password();
     let password_protection =
         from_fn(move |req, next| check_password_middleware(req, next, password.clone()));
-    let sqlite_close = environment.sqlite();
-    let sqlite_progress = environment.sqlite();
-    let sqlite_merge = environment.sqlite();
+    let database_close = environment.database();
+    let database_reset_progress = environment.database();
+    let database_claim_tests = environment.database();
+    let database_release_tests = environment.database();
+    let progress_environment = environment.clone();
+    let merge_environment = environment.clone();
+    let webhook_environment = environment.clone();
+    let test_run_environment = environment.clone();
+    let events_environment = environment.clone();
+    let health_environment = environment.clone();
+    let database_tests = environment.database();
+    let database_stats = environment.database();
+    let database_locations = environment.database();
+    let database_audit = environment.database();
+    let database_select_options = environment.database();
+    let database_reports = environment.database();
+    let github_webhook_secret = environment.github_webhook_secret();
+    let slack_rate_limiter = environment.slack_rate_limiter();
+    let slack_rate_limiting = from_fn(move |req, next| {
+        rate_limit_slack_commands_middleware(req, next, slack_rate_limiter.clone())
+    });
     Router::new()
         .route(
             "/merge",
-            post(move |query| post_merge_branch(query, sqlite_merge)),
+            post(move |query| post_merge_branch(query, merge_environment)),
         )
         .route(
             "/close",
-            post(move |query| post_close_branch(query, sqlite_close)),
+            post(move |query| post_close_branch(query, database_close)),
         )
         .route(
             "/progress",
-            post(move |body| post_progess(body, sqlite_progress)),
+            post(move |body| post_progess(body, progress_environment)),
+        )
+        .route(
+            "/progress/reset",
+            post(move |query| post_reset_progress(query, database_reset_progress)),
+        )
+        .route(
+            "/tests",
+            get(move |query, headers| get_tests(query, headers, database_tests)),
+        )
+        .route(
+            "/tests/claim",
+            post(move |body| post_claim_tests(body, database_claim_tests)),
+        )
+        .route(
+            "/tests/release",
+            post(move |body| post_release_tests(body, database_release_tests)),
+        )
+        .route("/stats", get(move || get_stats(database_stats)))
+        .route(
+            "/locations.geojson",
+            get(move |query, headers| get_locations_geojson(query, headers, database_locations)),
+        )
+        .route(
+            "/test-runs/callback",
+            post(move |body| post_test_run_callback(body, test_run_environment)),
         )
         .route_layer(password_protection)
         .route(
             "/slack",
-            post(move |body| post_slack_request(body, slack_handler, messenger)),
+            post(move |body| {
+                post_slack_request(
+                    body,
+                    slack_handler,
+                    messenger,
+                    database_audit,
+                    slack_tasks,
+                    slack_in_flight,
+                )
+            })
+            .layer(slack_rate_limiting),
+        )
+        .route(
+            "/slack/interactions",
+            post(move |body| {
+                post_slack_interaction(
+                    body,
+                    slack_interaction_handler,
+                    interaction_messenger,
+                    interaction_audit,
+                    interaction_tasks,
+                    interaction_in_flight,
+                )
+            }),
+        )
+        .route(
+            "/slack/options",
+            post(move |body| post_slack_select_options(body, database_select_options)),
+        )
+        .route("/health", get(move || get_health(health_environment)))
+        .route(
+            "/reports/:id",
+            get(move |path| get_slack_report(path, database_reports)),
+        )
+        .route(
+            "/slack/events",
+            post(move |body| post_slack_event(body, events_environment)),
+        )
+        .route(
+            "/github/webhook",
+            post(move |headers, body| {
+                post_github_webhook(headers, body, github_webhook_secret, webhook_environment)
+            }),
         )
 }
 
@@ -83,14 +293,301 @@ struct ProgressUpload {
     results: Vec<RoswaalTestProgressUpload>,
 }
 
+#[derive(Debug, Serialize)]
+struct ProgressUploadResponse {
+    results: Vec<RoswaalTestProgressUploadResult>,
+}
+
 async fn post_progess(
-    Json(upload): Json<ProgressUpload>,
-    sqlite: Arc<RoswaalSqlite>,
-) -> impl IntoResponse {
-    let result = save_test_progress(&upload.results, sqlite.as_ref())
+    upload: Result<Json<ProgressUpload>, JsonRejection>,
+    environment: Arc<ServerEnvironment>,
+) -> Result<Response, RoswaalHttpError> {
+    let Json(upload) = upload?;
+    let database = environment.database();
+    let result = save_test_progress(&upload.results, database.as_ref())
         .await
-        .map(|_| StatusCode::NO_CONTENT);
-    ResponseResult::new(result)
+        .map(|results| {
+            let is_all_updated = results
+                .iter()
+                .all(|r| r.outcome() == RoswaalTestProgressUploadOutcome::Updated);
+            if is_all_updated {
+                StatusCode::NO_CONTENT.into_response()
+            } else {
+                (
+                    StatusCode::MULTI_STATUS,
+                    Json(ProgressUploadResponse { results }),
+                )
+                    .into_response()
+            }
+        })
+        .map_err(|error| RoswaalHttpError::from_error(&error));
+    if let (Ok(_), Some(channel_id)) = (&result, environment.test_failure_channel_id()) {
+        let messenger = environment.slack_messenger();
+        let notify_result =
+            notify_test_failures(&upload.results, channel_id, &database, messenger.as_ref()).await;
+        if let Err(err) = notify_result {
+            error!("Failed to post the test failures {}.", err);
+        }
+    }
+    let test_names = upload
+        .results
+        .iter()
+        .map(|r| r.test_name())
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let entry = RoswaalAuditLogEntry::new(HTTP_AUDIT_USER, "POST /progress", &test_names)
+        .with_result(&result);
+    database.record(entry).await;
+    result
+}
+
+#[derive(Debug, Deserialize)]
+struct ResetProgressQueryParameters {
+    /// The names of the tests to reset separated by newlines, or all tests if omitted.
+    names: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResetProgressResponse {
+    reset_test_names: Vec<String>,
+}
+
+async fn post_reset_progress(
+    query: Result<Query<ResetProgressQueryParameters>, QueryRejection>,
+    database: Arc<RoswaalDatabase>,
+) -> Result<Json<ResetProgressResponse>, RoswaalHttpError> {
+    let Query(query) = query?;
+    let names = query.names.unwrap_or_default();
+    let result = ResetTestProgressStatus::from_resetting_progress(&names, database.as_ref())
+        .await
+        .map(|status| {
+            let reset_test_names = match status {
+                ResetTestProgressStatus::Success { reset_test_names } => reset_test_names,
+                ResetTestProgressStatus::NoTestsReset => vec![],
+            };
+            Json(ResetProgressResponse { reset_test_names })
+        })
+        .map_err(|error| RoswaalHttpError::from_error(&error));
+    let entry = RoswaalAuditLogEntry::new(HTTP_AUDIT_USER, "POST /progress/reset", &names)
+        .with_result(&result);
+    database.record(entry).await;
+    result
+}
+
+#[derive(Debug, Deserialize)]
+struct TestsQueryParameters {
+    /// The names of the tests to return separated by newlines, or all tests if omitted.
+    names: Option<String>,
+    /// The platform that the test runner is running on, which skips the tests that are restricted
+    /// to other platforms.
+    platform: Option<RoswaalTestPlatform>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TestsResponse {
+    tests: Vec<RoswaalTest>,
+    /// The estimated runtime of each returned test and of all of them together, which is only
+    /// included when listing tests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    runtime_estimate: Option<RoswaalSuiteRuntimeEstimate>,
+}
+
+async fn get_tests(
+    Query(query): Query<TestsQueryParameters>,
+    headers: HeaderMap,
+    database: Arc<RoswaalDatabase>,
+) -> Response {
+    let names = query.names.unwrap_or_default();
+    respond_with_revision_etag(database.as_ref(), &headers, async {
+        let result = SearchTestsStatus::from_searching_tests(&names, database.as_ref())
+            .await
+            .map(|status| {
+                let tests = match status {
+                    SearchTestsStatus::Success(tests) => tests,
+                    SearchTestsStatus::NoTests => vec![],
+                };
+                // NB: Deprecated tests stay in the frontend repo until their grace period ends,
+                // but the test runner should skip them right away.
+                let tests = tests
+                    .into_iter()
+                    .filter(|t| !t.is_deprecated())
+                    .filter(|t| query.platform.is_none_or(|p| t.runs_on(p)))
+                    .collect::<Vec<RoswaalTest>>();
+                let estimate = RoswaalTestRuntimeEstimator::new(&tests).suite_estimate(&tests);
+                Json(TestsResponse {
+                    tests,
+                    runtime_estimate: Some(estimate),
+                })
+            });
+        ResponseResult::new(result)
+    })
+    .await
+}
+
+/// How long a claimed test stays leased to its runner when the claim does not specify a duration.
+const DEFAULT_TEST_LEASE_SECONDS: u32 = 30 * 60;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClaimTestsRequest {
+    /// The id of the test runner that will run the claimed tests.
+    runner_id: String,
+    /// The maximum number of tests to claim.
+    count: usize,
+    /// The platform that the test runner is running on, which skips the tests that are restricted
+    /// to other platforms.
+    platform: Option<RoswaalTestPlatform>,
+    /// How long the claimed tests stay leased to the runner.
+    lease_seconds: Option<u32>,
+}
+
+async fn post_claim_tests(
+    request: Result<Json<ClaimTestsRequest>, JsonRejection>,
+    database: Arc<RoswaalDatabase>,
+) -> Result<Json<TestsResponse>, RoswaalHttpError> {
+    let Json(request) = request?;
+    if request.runner_id.trim().is_empty() {
+        return Err(RoswaalHttpError::new(
+            RoswaalHttpErrorCode::InvalidPayload,
+            "The runnerId must not be empty.",
+        ));
+    }
+    let lease_seconds = request.lease_seconds.unwrap_or(DEFAULT_TEST_LEASE_SECONDS);
+    let result = claim_tests(
+        &request.runner_id,
+        request.count,
+        request.platform,
+        chrono::Duration::seconds(lease_seconds.into()),
+        Utc::now(),
+        database.as_ref(),
+    )
+    .await
+    .map(|tests| {
+        Json(TestsResponse {
+            tests,
+            runtime_estimate: None,
+        })
+    })
+    .map_err(|error| RoswaalHttpError::from_error(&error));
+    // NB: The runner id is recorded on the first line, followed by the names of the claimed tests.
+    let mut arguments = vec![request.runner_id.as_str()];
+    if let Ok(Json(response)) = &result {
+        arguments.extend(response.tests.iter().map(|t| t.name()));
+    }
+    let entry =
+        RoswaalAuditLogEntry::new(HTTP_AUDIT_USER, "POST /tests/claim", &arguments.join("\n"))
+            .with_result(&result);
+    database.record(entry).await;
+    result
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseTestsRequest {
+    /// The id of the test runner that claimed the tests.
+    runner_id: String,
+    /// The names of the tests to release, or every test claimed by the runner if omitted.
+    #[serde(default)]
+    names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReleaseTestsResponse {
+    released_test_names: Vec<String>,
+}
+
+async fn post_release_tests(
+    request: Result<Json<ReleaseTestsRequest>, JsonRejection>,
+    database: Arc<RoswaalDatabase>,
+) -> Result<Json<ReleaseTestsResponse>, RoswaalHttpError> {
+    let Json(request) = request?;
+    let names = request.names.join("\n");
+    let result = release_tests(&request.runner_id, &names, database.as_ref())
+        .await
+        .map(|released_test_names| {
+            Json(ReleaseTestsResponse {
+                released_test_names,
+            })
+        })
+        .map_err(|error| RoswaalHttpError::from_error(&error));
+    let arguments = format!("{}\n{}", request.runner_id, names);
+    let entry = RoswaalAuditLogEntry::new(HTTP_AUDIT_USER, "POST /tests/release", arguments.trim())
+        .with_result(&result);
+    database.record(entry).await;
+    result
+}
+
+async fn get_stats(database: Arc<RoswaalDatabase>) -> impl IntoResponse {
+    ResponseResult::new(load_usage_stats(database.as_ref()).await.map(Json))
+}
+
+async fn get_health(environment: Arc<ServerEnvironment>) -> Response {
+    let repositories = std::iter::once(environment.git_repository())
+        .chain(environment.test_target_repositories())
+        .collect::<Vec<_>>();
+    let report = RoswaalHealthReport::check(
+        environment.database().as_ref(),
+        &repositories,
+        environment.github_reachability_check(),
+    )
+    .await;
+    let status = match report.status() {
+        RoswaalHealthStatus::Ok => StatusCode::OK,
+        RoswaalHealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(report)).into_response()
+}
+
+async fn get_slack_report(Path(id): Path<String>, database: Arc<RoswaalDatabase>) -> Response {
+    let result = async {
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async { transaction.slack_report(&id).await })
+    }
+    .await;
+    let response = result.map(|blocks| match blocks {
+        Some(blocks) => (
+            [(CONTENT_TYPE, "text/plain; charset=utf-8")],
+            blocks.transcript(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    });
+    ResponseResult::new(response).into_response()
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationsQueryParameters {
+    /// Whether to include the locations that were added by branches which are not merged yet.
+    #[serde(default)]
+    include_unmerged: bool,
+}
+
+async fn get_locations_geojson(
+    Query(query): Query<LocationsQueryParameters>,
+    headers: HeaderMap,
+    database: Arc<RoswaalDatabase>,
+) -> Response {
+    let filter = if query.include_unmerged {
+        LoadLocationsFilter::All
+    } else {
+        LoadLocationsFilter::MergedOnly
+    };
+    respond_with_revision_etag(database.as_ref(), &headers, async {
+        let result = async {
+            let mut transaction = database.read_transaction().await?;
+            let locations = with_transaction!(transaction, async {
+                transaction.locations_in_alphabetical_order(filter).await
+            })?;
+            let geojson = serde_json::to_string(&RoswaalLocationsGeoJson::new(&locations))?;
+            Ok::<_, Error>(([(CONTENT_TYPE, "application/geo+json")], geojson))
+        }
+        .await;
+        ResponseResult::new(result)
+    })
+    .await
 }
 
 #[derive(Debug, Deserialize)]
@@ -99,119 +596,696 @@ struct BranchQueryParameters {
 }
 
 async fn post_merge_branch(
-    Query(query): Query<BranchQueryParameters>,
-    sqlite: Arc<RoswaalSqlite>,
-) -> impl IntoResponse {
-    let result = MergeBranchStatus::from_merging_branch_with_name(&query.branch, sqlite.as_ref())
+    query: Result<Query<BranchQueryParameters>, QueryRejection>,
+    environment: Arc<ServerEnvironment>,
+) -> Result<StatusCode, RoswaalHttpError> {
+    let Query(query) = query?;
+    let database = environment.database();
+    let result = MergeBranchStatus::from_merging_branch_with_name(
+        &query.branch,
+        database.as_ref(),
+        environment.test_runner(),
+    )
+    .await
+    .map_err(|error| RoswaalHttpError::from_error(&error))
+    .and_then(|s| match s {
+        MergeBranchStatus::Merged(_) | MergeBranchStatus::MergedAndQueuedTestRun(_) => {
+            Ok(StatusCode::NO_CONTENT)
+        }
+        MergeBranchStatus::UnknownBranchKind(branch_name) => {
+            Err(RoswaalHttpError::unknown_branch_kind(branch_name))
+        }
+    });
+    let entry = RoswaalAuditLogEntry::new(HTTP_AUDIT_USER, "POST /merge", "")
+        .with_branch_name(Some(&query.branch))
+        .with_result(&result);
+    database.record(entry).await;
+    result
+}
+
+async fn post_close_branch(
+    query: Result<Query<BranchQueryParameters>, QueryRejection>,
+    database: Arc<RoswaalDatabase>,
+) -> Result<StatusCode, RoswaalHttpError> {
+    let Query(query) = query?;
+    let result = CloseBranchStatus::from_closing_branch(&query.branch, database.as_ref())
         .await
-        .map(|s| match s {
-            MergeBranchStatus::Merged(_) => StatusCode::NO_CONTENT,
-            MergeBranchStatus::UnknownBranchKind(_) => StatusCode::BAD_REQUEST,
+        .map_err(|error| RoswaalHttpError::from_error(&error))
+        .and_then(|s| match s {
+            CloseBranchStatus::Closed(_) => Ok(StatusCode::NO_CONTENT),
+            CloseBranchStatus::UnknownBranchKind(branch_name) => {
+                Err(RoswaalHttpError::unknown_branch_kind(branch_name))
+            }
         });
+    let entry = RoswaalAuditLogEntry::new(HTTP_AUDIT_USER, "POST /close", "")
+        .with_branch_name(Some(&query.branch))
+        .with_result(&result);
+    database.record(entry).await;
+    result
+}
+
+async fn post_github_webhook(
+    headers: HeaderMap,
+    body: Bytes,
+    secret: Option<GithubWebhookSecret>,
+    environment: Arc<ServerEnvironment>,
+) -> impl IntoResponse {
+    let Some(secret) = secret else {
+        return ResponseResult::new(Ok(StatusCode::NOT_FOUND));
+    };
+    if !secret.verify(&headers, &body) {
+        return ResponseResult::new(Ok(StatusCode::FORBIDDEN));
+    }
+    let action = match GithubWebhookAction::from_delivery(&headers, &body) {
+        Ok(action) => action,
+        Err(_) => return ResponseResult::new(Ok(StatusCode::BAD_REQUEST)),
+    };
+    let database = environment.database();
+    let (result, arguments, branch_name) = match action {
+        GithubWebhookAction::MergeBranch(branch_name) => (
+            MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                database.as_ref(),
+                environment.test_runner(),
+            )
+            .await
+            .map(|_| StatusCode::NO_CONTENT),
+            "merged",
+            branch_name,
+        ),
+        GithubWebhookAction::CloseBranch(branch_name) => (
+            CloseBranchStatus::from_closing_branch(&branch_name, database.as_ref())
+                .await
+                .map(|_| StatusCode::NO_CONTENT),
+            "closed",
+            branch_name,
+        ),
+        GithubWebhookAction::Ignore => return ResponseResult::new(Ok(StatusCode::NO_CONTENT)),
+    };
+    let entry = RoswaalAuditLogEntry::new(GITHUB_AUDIT_USER, "POST /github/webhook", arguments)
+        .with_branch_name(Some(&branch_name))
+        .with_result(&result);
+    database.record(entry).await;
     ResponseResult::new(result)
 }
 
-async fn post_close_branch(
-    Query(query): Query<BranchQueryParameters>,
-    sqlite: Arc<RoswaalSqlite>,
+#[derive(Debug, Deserialize)]
+struct TestRunCallback {
+    run_id: i64,
+    url: String,
+}
+
+async fn post_test_run_callback(
+    Json(callback): Json<TestRunCallback>,
+    environment: Arc<ServerEnvironment>,
 ) -> impl IntoResponse {
-    let result = CloseBranchStatus::from_closing_branch(&query.branch, sqlite.as_ref())
-        .await
-        .map(|s| match s {
-            CloseBranchStatus::Closed(_) => StatusCode::NO_CONTENT,
-            CloseBranchStatus::UnknownBranchKind(_) => StatusCode::BAD_REQUEST,
-        });
+    let database = environment.database();
+    let status = ReportTestRunStatus::from_runner_callback(
+        callback.run_id,
+        &callback.url,
+        database.as_ref(),
+    )
+    .await;
+    let (result, branch_name) = match status {
+        Ok(ReportTestRunStatus::Reported(run)) => {
+            let branch_name = run.branch_name().clone();
+            let message = SlackMessage::new(
+                RoswaalConfig::current().pull_request_channel_id(),
+                &TestRunView::new(run),
+                SLACK_POST_MESSAGE_URL,
+            );
+            let result = environment.slack_messenger().send(&message).await;
+            (result.map(|_| StatusCode::NO_CONTENT), Some(branch_name))
+        }
+        Ok(ReportTestRunStatus::UnknownRun(_)) => (Ok(StatusCode::NOT_FOUND), None),
+        Err(error) => (Err(error), None),
+    };
+    let entry = RoswaalAuditLogEntry::new(
+        HTTP_AUDIT_USER,
+        "POST /test-runs/callback",
+        &callback.run_id.to_string(),
+    )
+    .with_branch_name(branch_name.as_ref())
+    .with_result(&result);
+    database.record(entry).await;
     ResponseResult::new(result)
 }
 
 #[derive(Serialize)]
-struct SlackResponse {
+pub(super) struct SlackResponse {
     blocks: SlackBlocks,
 }
 
+impl SlackResponse {
+    pub(super) fn new(blocks: SlackBlocks) -> Self {
+        Self { blocks }
+    }
+}
+
 async fn post_slack_request(
     Form(request): Form<RoswaalSlackRequest>,
     slack_handler: Arc<HTTPSlackHandler>,
     messenger: Arc<impl SlackSendMessage + Send + Sync + 'static>,
+    audit_log: Arc<RoswaalDatabase>,
+    tasks: TaskTracker,
+    in_flight: RoswaalInFlightOperations,
+) -> Response {
+    if let Some((modal, trigger_id)) = request.modal_to_open() {
+        let result = slack_handler
+            .environment
+            .slack_modal_open()
+            .open_modal(trigger_id, &modal)
+            .await;
+        return match result {
+            Ok(_) => StatusCode::OK.into_response(),
+            Err(error) => Json(SlackResponse::new(render_slack_view(&MessageView::new(
+                &ErrorView::new(error),
+            ))))
+            .into_response(),
+        };
+    }
+    let database = audit_log.clone();
+    match handle_slack_request(
+        slack_handler,
+        request,
+        messenger,
+        audit_log,
+        &tasks,
+        &in_flight,
+    )
+    .await
+    {
+        Some(blocks) => {
+            let blocks = truncate_to_slack_message(&blocks, &database)
+                .await
+                .unwrap_or(blocks);
+            Json(SlackResponse::new(blocks)).into_response()
+        }
+        None => StatusCode::OK.into_response(),
+    }
+}
+
+async fn post_slack_interaction(
+    Form(form): Form<RoswaalSlackInteractionForm>,
+    slack_handler: Arc<HTTPSlackHandler>,
+    messenger: Arc<impl SlackSendMessage + Send + Sync + 'static>,
+    audit_log: Arc<RoswaalDatabase>,
+    tasks: TaskTracker,
+    in_flight: RoswaalInFlightOperations,
 ) -> impl IntoResponse {
-    Json(SlackResponse {
-        blocks: handle_slack_request(slack_handler, request, messenger).await,
-    })
+    match RoswaalSlackModalSubmission::from_form(&form) {
+        Ok(Some(submission)) => {
+            handle_slack_modal_submission(
+                slack_handler,
+                submission,
+                messenger,
+                audit_log,
+                &tasks,
+                &in_flight,
+            );
+            return StatusCode::OK;
+        }
+        Ok(None) => {}
+        Err(_) => return StatusCode::BAD_REQUEST,
+    }
+    match RoswaalSlackInteraction::from_form(&form) {
+        Ok(Some(interaction)) => {
+            handle_slack_interaction(slack_handler, interaction, messenger, audit_log, &tasks);
+            StatusCode::OK
+        }
+        Ok(None) => StatusCode::OK,
+        Err(_) => StatusCode::BAD_REQUEST,
+    }
+}
+
+async fn post_slack_select_options(
+    Form(form): Form<RoswaalSlackInteractionForm>,
+    database: Arc<RoswaalDatabase>,
+) -> Response {
+    let request = match RoswaalSlackSelectOptionsRequest::from_form(&form) {
+        Ok(Some(request)) => request,
+        Ok(None) => return Json(select_options_from_names(&[])).into_response(),
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    };
+    match load_select_option_names(request.menu(), request.query(), database.as_ref()).await {
+        Ok(names) => Json(select_options_from_names(&names)).into_response(),
+        Err(error) => {
+            error!(
+                "Failed to load the options of {}. {}",
+                request.menu(),
+                error
+            );
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SlackUrlVerificationResponse {
+    challenge: String,
+}
+
+async fn post_slack_event(body: String, environment: Arc<ServerEnvironment>) -> Response {
+    match RoswaalSlackEvent::from_json(&body) {
+        Ok(Some(RoswaalSlackEvent::UrlVerification { challenge })) => {
+            Json(SlackUrlVerificationResponse { challenge }).into_response()
+        }
+        Ok(Some(RoswaalSlackEvent::AppHomeOpened { user_id })) => {
+            // NB: Slack retries events that are not acknowledged within 3 seconds, so the home
+            // tab is published after responding.
+            environment.background_tasks().spawn(async move {
+                if let Err(error) = publish_home_tab(&user_id, &environment).await {
+                    log::error!("Failed to publish the home tab for {}. {}", user_id, error)
+                }
+            });
+            StatusCode::OK.into_response()
+        }
+        Ok(None) => StatusCode::OK.into_response(),
+        Err(_) => StatusCode::BAD_REQUEST.into_response(),
+    }
+}
+
+async fn publish_home_tab(user_id: &str, environment: &ServerEnvironment) -> Result<(), Error> {
+    let summary = RoswaalHomeSummary::load(environment.database().as_ref()).await?;
+    let home_tab = SlackHomeTab::new(&HomeView::new(summary));
+    environment
+        .slack_home_tab_publish()
+        .publish_home_tab(user_id, &home_tab)
+        .await
 }
 
 struct HTTPSlackHandler {
     environment: Arc<ServerEnvironment>,
 }
 
+impl SlackFetchFile for HTTPSlackHandler {
+    async fn fetch_file(&self, file_id: &str) -> Result<String, Error> {
+        self.environment
+            .slack_file_fetch()
+            .fetch_file(file_id)
+            .await
+    }
+}
+
 impl RoswaalSlackHandler for HTTPSlackHandler {
     async fn handle_command(
         &self,
         command: &RoswaalSlackCommand,
         command_text: &str,
-    ) -> Result<impl SlackView, Error> {
-        match command {
+        user_id: &str,
+    ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+        let (branch_name, view) = match command {
             RoswaalSlackCommand::ViewTests => {
                 let status = SearchTestsStatus::from_searching_tests(
                     command_text,
-                    self.environment.sqlite().as_ref(),
+                    self.environment.database().as_ref(),
                 )
                 .await?;
-                Ok(SearchTestsView::new(status).erase_to_any_view())
+                (None, SearchTestsView::new(status).erase_to_any_view())
             }
             RoswaalSlackCommand::AddTests => {
                 let status = AddTestsStatus::from_adding_tests(
                     command_text,
-                    self.environment.sqlite().as_ref(),
+                    self.environment.database().as_ref(),
                     self.environment.github_pull_request_open(),
                     self.environment.git_repository(),
+                    self.environment.test_target_repositories(),
+                    Some(user_id),
+                )
+                .await?;
+                status
+                    .comment_compilation_summary(
+                        self.environment.git_repository(),
+                        self.environment.github_pull_request_comment(),
+                    )
+                    .await;
+                (
+                    status.branch_name().cloned(),
+                    AddTestsView::new(status)
+                        .recompilable_from(command_text)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::LintTests => {
+                let status = LintTestsStatus::from_linting_tests(
+                    command_text,
+                    self.environment.database().as_ref(),
                 )
                 .await?;
-                Ok(AddTestsView::new(status).erase_to_any_view())
+                (None, LintTestsView::new(status).erase_to_any_view())
             }
             RoswaalSlackCommand::RemoveTests => {
                 let status = RemoveTestsStatus::from_removing_tests(
                     command_text,
-                    self.environment.sqlite().as_ref(),
+                    self.environment.database().as_ref(),
                     self.environment.git_repository(),
                     self.environment.github_pull_request_open(),
+                    self.environment.test_target_repositories(),
+                )
+                .await?;
+                (
+                    status.branch_name().cloned(),
+                    RemoveTestsView::new(status)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::RestoreTest => {
+                let archived_test = RestoreTestStatus::archived_test(
+                    command_text,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                let status = RestoreTestStatus::from_restoring_test(
+                    command_text,
+                    archived_test.as_ref(),
+                    self.environment.database().as_ref(),
+                    self.environment.github_pull_request_open(),
+                    self.environment.git_repository(),
+                    self.environment.test_target_repositories(),
+                    Some(user_id),
+                )
+                .await?;
+                if let Some(status) = status.add_tests_status() {
+                    status
+                        .comment_compilation_summary(
+                            self.environment.git_repository(),
+                            self.environment.github_pull_request_comment(),
+                        )
+                        .await;
+                }
+                (
+                    status.branch_name().cloned(),
+                    RestoreTestView::new(status)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::DeprecateTests => {
+                let status = DeprecateTestsStatus::from_deprecating_tests(
+                    command_text,
+                    self.environment.database().as_ref(),
+                    Utc::now(),
+                    RoswaalConfig::current().deprecation_grace_period(),
+                )
+                .await?;
+                (None, DeprecateTestsView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::ResetTestProgress => {
+                let status = ResetTestProgressStatus::from_resetting_progress(
+                    command_text,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, ResetTestProgressView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::AssignFailure => {
+                let status = AssignFailureStatus::from_assigning(
+                    command_text,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, AssignFailureView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::EditTest => {
+                let status = EditTestStatus::from_editing_test(
+                    command_text,
+                    self.environment.database().as_ref(),
+                    self.environment.github_pull_request_open(),
+                    self.environment.git_repository(),
+                    self.environment.test_target_repositories(),
                 )
                 .await?;
-                Ok(RemoveTestsView::new(status).erase_to_any_view())
+                (
+                    status.branch_name().cloned(),
+                    EditTestView::new(status)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
             }
             RoswaalSlackCommand::ViewLocations => {
                 let status = LoadAllLocationsStatus::from_stored_locations(
-                    self.environment.sqlite().as_ref(),
+                    self.environment.database().as_ref(),
                 )
                 .await?;
-                Ok(LocationsListView::new(status).erase_to_any_view())
+                (None, LocationsListView::new(status).erase_to_any_view())
             }
             RoswaalSlackCommand::AddLocations => {
-                let status = AddLocationsStatus::from_adding_locations(
+                let status = AddLocationsStatus::from_importing_locations(
                     command_text,
+                    self.environment.slack_file_fetch(),
                     self.environment.git_repository(),
-                    self.environment.sqlite().as_ref(),
+                    self.environment.database().as_ref(),
                     self.environment.github_pull_request_open(),
                 )
                 .await?;
-                Ok(AddLocationsView::new(status).erase_to_any_view())
+                (
+                    status.branch_name().cloned(),
+                    AddLocationsView::new(status)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::RemoveLocations => {
+                let status = RemoveLocationsStatus::from_removing_locations(
+                    command_text,
+                    self.environment.git_repository(),
+                    self.environment.database().as_ref(),
+                    self.environment.github_pull_request_open(),
+                )
+                .await?;
+                (
+                    status.branch_name().cloned(),
+                    RemoveLocationsView::new(status)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::AddLocationAlias => {
+                let status = AddLocationAliasStatus::from_adding_alias(
+                    command_text,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, AddLocationAliasView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::ListBranches => {
+                let status = ListBranchesStatus::from_listing_branches(
+                    self.environment.database().as_ref(),
+                    self.environment.git_repository(),
+                )
+                .await?;
+                (None, ListBranchesView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::OpenPendingPullRequests => {
+                let status = OpenPendingPullRequestsStatus::from_opening_pending_pull_requests(
+                    self.environment.database().as_ref(),
+                    self.environment.pending_pull_requests_open(),
+                )
+                .await?;
+                (
+                    None,
+                    OpenPendingPullRequestsView::new(status).erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::MyFailedTests => {
+                let status = MyFailedTestsStatus::from_recent_failures(
+                    user_id,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, MyFailedTestsView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::ViewAuditLog => {
+                let status =
+                    ViewAuditLogStatus::from_recent_entries(self.environment.database().as_ref())
+                        .await?;
+                (None, AuditLogView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::EditRoster => {
+                let status = EditRosterStatus::from_editing_roster(
+                    command_text,
+                    user_id,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, RosterView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::GrantCommand => {
+                let status = GrantCommandStatus::from_granting_command(
+                    command_text,
+                    user_id,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, GrantView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::ViewSuites => {
+                let status = ViewSuitesStatus::from_viewing_suites(
+                    command_text,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, SuitesView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::FlakyTests => {
+                let status =
+                    FlakyTestsStatus::from_ranking_tests(self.environment.database().as_ref())
+                        .await?;
+                (None, FlakyTestsView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::ExportTests => {
+                let status = ExportTestsStatus::from_exporting_tests(
+                    command_text,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                (None, ExportTestsView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::RepoDoctor => {
+                let status = RepoDoctorStatus::from_running_doctor(
+                    command_text,
+                    user_id,
+                    self.environment.database().as_ref(),
+                    self.environment.git_repository(),
+                    self.environment.test_target_repositories(),
+                )
+                .await?;
+                (None, RepoDoctorView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackCommand::ReconcileTests => {
+                let status = ReconcileTestsStatus::from_reconciling_tests(
+                    command_text,
+                    self.environment.database().as_ref(),
+                    self.environment.git_repository(),
+                    self.environment.github_pull_request_open(),
+                )
+                .await?;
+                (
+                    status.branch_name().cloned(),
+                    ReconcileTestsView::new(status)
+                        .with_roster(&self.slack_user_roster().await?)
+                        .erase_to_any_view(),
+                )
+            }
+            RoswaalSlackCommand::Help => (
+                None,
+                HelpView::from_command_text(command_text).erase_to_any_view(),
+            ),
+            // NB: Cancelling needs the channel of the request, so `handle_slack_request` handles
+            // the command before it reaches this handler.
+            RoswaalSlackCommand::Cancel => (None, CancelView::new(None).erase_to_any_view()),
+        };
+        Ok(RoswaalSlackCommandResponse::new(view).with_branch_name(branch_name.as_ref()))
+    }
+
+    async fn check_permission(
+        &self,
+        command: &RoswaalSlackCommand,
+        user_id: &str,
+    ) -> Result<CheckPermissionStatus, Error> {
+        CheckPermissionStatus::from_checking_permission(
+            *command,
+            user_id,
+            self.environment.database().as_ref(),
+            self.environment.slack_user_group_fetch(),
+        )
+        .await
+    }
+
+    async fn handle_action(
+        &self,
+        action: &RoswaalSlackAction,
+        value: &str,
+    ) -> Result<impl SlackView, Error> {
+        match action {
+            RoswaalSlackAction::CloseBranch => {
+                let branch_name = serde_json::from_value::<RoswaalOwnedGitBranchName>(
+                    serde_json::Value::String(value.to_string()),
+                )?;
+                let status = CloseBranchStatus::from_closing_branch(
+                    &branch_name,
+                    self.environment.database().as_ref(),
+                )
+                .await?;
+                Ok(CloseBranchView::new(status).erase_to_any_view())
+            }
+            RoswaalSlackAction::RecompileTests => {
+                let status = AddTestsStatus::from_adding_tests(
+                    value,
+                    self.environment.database().as_ref(),
+                    self.environment.github_pull_request_open(),
+                    self.environment.git_repository(),
+                    self.environment.test_target_repositories(),
+                    None,
+                )
+                .await?;
+                status
+                    .comment_compilation_summary(
+                        self.environment.git_repository(),
+                        self.environment.github_pull_request_comment(),
+                    )
+                    .await;
+                Ok(AddTestsView::new(status)
+                    .recompilable_from(value)
+                    .with_roster(&self.slack_user_roster().await?)
+                    .erase_to_any_view())
+            }
+            RoswaalSlackAction::MuteTestFailures => {
+                let database = self.environment.database();
+                let mut transaction = database.transaction().await?;
+                with_transaction!(transaction, async {
+                    transaction.mute_test_failures(value).await
+                })?;
+                Ok(TestFailureMuteView::new(value, true).erase_to_any_view())
+            }
+            RoswaalSlackAction::UnmuteTestFailures => {
+                let database = self.environment.database();
+                let mut transaction = database.transaction().await?;
+                with_transaction!(transaction, async {
+                    transaction.unmute_test_failures(value).await
+                })?;
+                Ok(TestFailureMuteView::new(value, false).erase_to_any_view())
             }
         }
     }
 }
 
+impl HTTPSlackHandler {
+    async fn slack_user_roster(&self) -> Result<RoswaalSlackUserRoster, Error> {
+        let database = self.environment.database();
+        let mut transaction = database.transaction().await?;
+        with_transaction!(transaction, async { transaction.slack_user_roster().await })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
 
+    use axum::http::{
+        header::{ETAG, IF_NONE_MATCH},
+        HeaderName, HeaderValue,
+    };
     use axum_test::TestResponse;
     use dotenv::dotenv;
     use serde_json::{json, Value};
-    use sqlx::{prelude::FromRow, query_as, Sqlite};
+    use sqlx::prelude::FromRow;
     use tokio::{fs::remove_file, time::sleep};
 
     use crate::{
         git::{branch_name::RoswaalOwnedGitBranchName, test_support::with_clean_test_repo_access},
         http::password::DEV_RAW_ENDPOINT_PASSWORD,
+        language::{
+            ast::RoswaalTestSyntax,
+            compilation_results::RoswaalTestCompilationResults,
+            test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        },
+        location::location::RoswaalLocation,
+        slack::ui_lib::block_kit_views::SlackSection,
+        tests_data::query::RoswaalTestNamesString,
+        utils::{config::RoswaalConfig, db::query::query_as},
         with_transaction,
     };
 
@@ -262,15 +1336,678 @@ mod tests {
         .unwrap()
     }
 
+    #[tokio::test]
+    async fn merge_close_and_progress_respond_with_typed_errors() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            for path in ["/merge", "/close"] {
+                let resp = app
+                    .server
+                    .post(path)
+                    .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                    .add_query_param("branch", "roswaal-add-unicorns-Lklj839sda")
+                    .await;
+                resp.assert_status_bad_request();
+                assert_eq!(resp.json::<Value>()["code"], "unknown_branch_kind");
+                let resp = app
+                    .server
+                    .post(path)
+                    .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                    .await;
+                resp.assert_status_bad_request();
+                assert_eq!(resp.json::<Value>()["code"], "invalid_payload");
+            }
+            let resp = app.upload_progress(&json!({ "results": 1 })).await;
+            resp.assert_status_bad_request();
+            let body = resp.json::<Value>();
+            assert_eq!(body["code"], "invalid_payload");
+            assert!(body["correlation_id"].is_string());
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn progress_reports_entries_that_were_not_updated() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let resp = app
+                .upload_progress(&json!({
+                    "results": [{ "testName": "Progress Unknown Test" }]
+                }))
+                .await;
+            resp.assert_status(StatusCode::MULTI_STATUS);
+            resp.assert_json(&json!({
+                "results": [{ "testName": "Progress Unknown Test", "outcome": "unknown_test" }]
+            }));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn github_webhook_rejects_invalid_signatures_and_ignores_pings() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let body = "{\"zen\": \"Keep it logically awesome.\"}";
+            let resp = app
+                .server
+                .post("/github/webhook")
+                .add_header(
+                    HeaderName::from_static("x-github-event"),
+                    HeaderValue::from_static("ping"),
+                )
+                .add_header(
+                    HeaderName::from_static("x-hub-signature-256"),
+                    HeaderValue::from_static("sha256=invalid"),
+                )
+                .text(body)
+                .await;
+            resp.assert_status_forbidden();
+            let signature = GithubWebhookSecret::dev().signature(body.as_bytes());
+            let resp = app
+                .server
+                .post("/github/webhook")
+                .add_header(
+                    HeaderName::from_static("x-github-event"),
+                    HeaderValue::from_static("ping"),
+                )
+                .add_header(
+                    HeaderName::from_static("x-hub-signature-256"),
+                    HeaderValue::from_str(&signature)?,
+                )
+                .text(body)
+                .await;
+            resp.assert_status(StatusCode::NO_CONTENT);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn github_webhook_is_not_found_without_a_secret() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let resp = post_github_webhook(
+                HeaderMap::new(),
+                Bytes::new(),
+                None,
+                app.environment.clone(),
+            )
+            .await
+            .into_response();
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn slack_events_responds_to_url_verification() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let resp = app
+                .server
+                .post("/slack/events")
+                .json(&json!({
+                    "type": "url_verification",
+                    "token": "abc",
+                    "challenge": "the-challenge"
+                }))
+                .await;
+            resp.assert_status_ok();
+            resp.assert_json(&json!({ "challenge": "the-challenge" }));
+            let resp = app.server.post("/slack/events").text("explode").await;
+            resp.assert_status(StatusCode::BAD_REQUEST);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn slack_options_returns_merged_test_names_matching_the_typed_text() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+            let tests = vec![RoswaalCompiledTest::new(
+                "Select Options Test".to_string(),
+                None,
+                vec![],
+            )];
+            transaction.save_tests(&tests, &branch_name).await?;
+            transaction.merge_unmerged_tests(&branch_name).await?;
+            transaction.commit().await?;
+            let payload = json!({
+                "type": "block_suggestion",
+                "action_id": "test-names",
+                "value": "select options"
+            });
+            let resp = app
+                .server
+                .post("/slack/options")
+                .form(&[("payload", payload.to_string())])
+                .await;
+            resp.assert_status_ok();
+            resp.assert_json(&json!({
+                "options": [{
+                    "text": { "text": "Select Options Test", "type": "plain_text" },
+                    "value": "Select Options Test"
+                }]
+            }));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_tests_returns_tests_filtered_by_names() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            app.server.get("/tests").await.assert_status_forbidden();
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let tests = ["Get Tests A", "Get Tests B"]
+                .iter()
+                .map(|name| {
+                    RoswaalCompiledTest::new(
+                        name.to_string(),
+                        None,
+                        vec![RoswaalCompiledTestCommand::Step {
+                            label: "Step 1".to_string(),
+                            name: "Thing".to_string(),
+                            requirement: "Thing".to_string(),
+                            retries: None,
+                        }],
+                    )
+                })
+                .collect();
+            transaction
+                .save_tests(&tests, &RoswaalOwnedGitBranchName::for_adding_tests())
+                .await?;
+            transaction.commit().await?;
+            let resp = app
+                .server
+                .get("/tests")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .add_query_param("names", "get tests b")
+                .await;
+            resp.assert_status_ok();
+            let json = resp.json::<Value>();
+            let names = json["tests"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|test| test["name"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>();
+            assert_eq!(names, vec!["Get Tests B"]);
+            assert_eq!(
+                json["runtimeEstimate"],
+                json!({
+                    "totalMilliseconds": 10000,
+                    "tests": [{ "name": "Get Tests B", "milliseconds": 10000, "isMeasured": false }]
+                })
+            );
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_tests_is_not_modified_until_tests_change() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let get_tests = |etag: Option<&str>| {
+                let request = app
+                    .server
+                    .get("/tests")
+                    .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD);
+                match etag {
+                    Some(etag) => {
+                        request.add_header(IF_NONE_MATCH, HeaderValue::from_str(etag).unwrap())
+                    }
+                    None => request,
+                }
+            };
+            let resp = get_tests(None).await;
+            resp.assert_status_ok();
+            let etag = resp.header(ETAG).to_str()?.to_string();
+            let resp = get_tests(Some(&etag)).await;
+            resp.assert_status(StatusCode::NOT_MODIFIED);
+            assert_eq!(resp.header(ETAG).to_str()?, etag);
+
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            transaction
+                .save_tests(
+                    &vec![RoswaalCompiledTest::new(
+                        "Get Tests ETag".to_string(),
+                        None,
+                        vec![],
+                    )],
+                    &RoswaalOwnedGitBranchName::for_adding_tests(),
+                )
+                .await?;
+            transaction.commit().await?;
+            let resp = get_tests(Some(&etag)).await;
+            resp.assert_status_ok();
+            assert_ne!(resp.header(ETAG).to_str()?, etag);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reset_progress_clears_the_failures_of_named_tests() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+            let tests = vec![RoswaalCompiledTest::new(
+                "Reset Progress Test".to_string(),
+                None,
+                vec![],
+            )];
+            transaction.save_tests(&tests, &branch_name).await?;
+            transaction.merge_unmerged_tests(&branch_name).await?;
+            transaction.commit().await?;
+            app.upload_progress(&json!({
+                "results": [{ "testName": "Reset Progress Test", "commandFailureOrdinal": 0 }]
+            }))
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+
+            let resp = app
+                .server
+                .post("/progress/reset")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .add_query_param("names", "Reset Progress Test\nReset Progress Unknown Test")
+                .await;
+            resp.assert_status_ok();
+            assert_eq!(
+                resp.json::<Value>(),
+                json!({ "resetTestNames": ["Reset Progress Test"] })
+            );
+            let resp = app
+                .server
+                .get("/tests")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .add_query_param("names", "Reset Progress Test")
+                .await;
+            let tests = resp.json::<Value>();
+            assert_eq!(tests["tests"][0]["commandFailureOrdinal"], Value::Null);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn claimed_tests_are_leased_to_one_runner_until_released() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+            let tests = vec![RoswaalCompiledTest::new(
+                "Claim Tests Test".to_string(),
+                None,
+                vec![],
+            )];
+            transaction.save_tests(&tests, &branch_name).await?;
+            transaction.merge_unmerged_tests(&branch_name).await?;
+            transaction.commit().await?;
+            let claimed_names = |resp: TestResponse| {
+                resp.assert_status_ok();
+                resp.json::<Value>()["tests"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|test| test["name"].as_str().unwrap().to_string())
+                    .collect::<Vec<String>>()
+            };
+
+            let names = claimed_names(app.claim_tests("claim-runner-1").await);
+            assert!(names.contains(&"Claim Tests Test".to_string()));
+            let names = claimed_names(app.claim_tests("claim-runner-2").await);
+            assert!(!names.contains(&"Claim Tests Test".to_string()));
+
+            let resp = app
+                .server
+                .post("/tests/release")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .json(&json!({ "runnerId": "claim-runner-1", "names": ["Claim Tests Test"] }))
+                .await;
+            resp.assert_status_ok();
+            assert_eq!(
+                resp.json::<Value>(),
+                json!({ "releasedTestNames": ["claim tests test"] })
+            );
+            let names = claimed_names(app.claim_tests("claim-runner-2").await);
+            assert_eq!(names, vec!["Claim Tests Test".to_string()]);
+
+            let resp = app
+                .server
+                .post("/tests/claim")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .json(&json!({ "runnerId": " ", "count": 1 }))
+                .await;
+            resp.assert_status(StatusCode::BAD_REQUEST);
+            for runner_id in ["claim-runner-1", "claim-runner-2"] {
+                app.server
+                    .post("/tests/release")
+                    .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                    .json(&json!({ "runnerId": runner_id }))
+                    .await
+                    .assert_status_ok();
+            }
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_tests_skips_deprecated_tests() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+            let tests = ["Get Deprecated Tests A", "Get Deprecated Tests B"]
+                .iter()
+                .map(|name| {
+                    RoswaalCompiledTest::new(
+                        name.to_string(),
+                        None,
+                        vec![RoswaalCompiledTestCommand::Step {
+                            label: "Step 1".to_string(),
+                            name: "Thing".to_string(),
+                            requirement: "Thing".to_string(),
+                            retries: None,
+                        }],
+                    )
+                })
+                .collect();
+            transaction.save_tests(&tests, &branch_name).await?;
+            transaction.merge_unmerged_tests(&branch_name).await?;
+            transaction
+                .deprecate_tests(
+                    &RoswaalTestNamesString::new("Get Deprecated Tests A"),
+                    Utc::now(),
+                )
+                .await?;
+            transaction.commit().await?;
+            let resp = app
+                .server
+                .get("/tests")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .add_query_param("names", "get deprecated tests a\nget deprecated tests b")
+                .await;
+            resp.assert_status_ok();
+            let names = resp.json::<Value>()["tests"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|test| test["name"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>();
+            assert_eq!(names, vec!["Get Deprecated Tests B"]);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_tests_filters_tests_by_platform() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+            let tests = [
+                ("Get Platform Tests iOS", vec![RoswaalTestPlatform::Ios]),
+                (
+                    "Get Platform Tests Android",
+                    vec![RoswaalTestPlatform::Android],
+                ),
+                ("Get Platform Tests All", vec![]),
+            ]
+            .into_iter()
+            .map(|(name, platforms)| {
+                RoswaalCompiledTest::new(
+                    name.to_string(),
+                    None,
+                    vec![RoswaalCompiledTestCommand::Step {
+                        label: "Step 1".to_string(),
+                        name: "Thing".to_string(),
+                        requirement: "Thing".to_string(),
+                        retries: None,
+                    }],
+                )
+                .with_platforms(platforms)
+            })
+            .collect();
+            transaction.save_tests(&tests, &branch_name).await?;
+            transaction.merge_unmerged_tests(&branch_name).await?;
+            transaction.commit().await?;
+            let names =
+                "get platform tests ios\nget platform tests android\nget platform tests all";
+            let resp = app
+                .server
+                .get("/tests")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .add_query_param("names", names)
+                .add_query_param("platform", "ios")
+                .await;
+            resp.assert_status_ok();
+            let tests = resp.json::<Value>()["tests"].as_array().unwrap().clone();
+            let names = tests
+                .iter()
+                .map(|test| test["name"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>();
+            assert_eq!(
+                names,
+                vec!["Get Platform Tests All", "Get Platform Tests iOS"]
+            );
+            assert_eq!(tests[1]["platforms"], json!(["ios"]));
+            app.server
+                .get("/tests")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .add_query_param("platform", "windows phone")
+                .await
+                .assert_status_bad_request();
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_health_checks_dependencies_without_a_password() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let resp = app.server.get("/health").await;
+            let report = resp.json::<Value>();
+            let checks = report["checks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|check| check["name"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>();
+            assert_eq!(checks, vec!["database", "git/FitnessProjectTest", "github"]);
+            assert_eq!(report["checks"][0]["status"], json!("ok"));
+            assert_eq!(report["checks"][1]["status"], json!("ok"));
+            // NB: GitHub may not be reachable from the machine running the tests.
+            if report["status"] == json!("ok") {
+                resp.assert_status_ok()
+            } else {
+                resp.assert_status(StatusCode::SERVICE_UNAVAILABLE)
+            }
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_slack_report_returns_the_transcript_of_the_report_without_a_password() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            let blocks = render_slack_view(&SlackSection::from_markdown("*Error 1* in `Join`"));
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let id = with_transaction!(transaction, async {
+                transaction.save_slack_report(&blocks).await
+            })?;
+            let resp = app.server.get(&format!("/reports/{}", id)).await;
+            resp.assert_status_ok();
+            resp.assert_text("Error 1 in Join");
+            app.server
+                .get("/reports/unknown")
+                .await
+                .assert_status(StatusCode::NOT_FOUND);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_stats_returns_usage_stats() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            app.server.get("/stats").await.assert_status_forbidden();
+            let submission_count = app.stats().await["submission_count"].as_u64().unwrap();
+            let tests_syntax = vec![RoswaalTestSyntax::from("New Test: Get Stats")];
+            let results = RoswaalTestCompilationResults::compile(tests_syntax, &vec![]);
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            transaction.save_test_submission(&results).await?;
+            transaction.commit().await?;
+            let stats = app.stats().await;
+            assert_eq!(stats["submission_count"], submission_count + 1);
+            assert!(stats["average_compilation_errors_per_submission"].is_f64());
+            assert!(stats["weeks"].is_array());
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_locations_geojson_returns_merged_locations_unless_unmerged_are_included() {
+        with_clean_test_repo_access(async {
+            let app = test_app().await;
+            app.server
+                .get("/locations.geojson")
+                .await
+                .assert_status_forbidden();
+            let database = app.environment.database();
+            let mut transaction = database.transaction().await?;
+            let merged_branch_name = RoswaalOwnedGitBranchName::for_adding_locations();
+            transaction
+                .save_locations(
+                    &vec![RoswaalLocation::new_without_validation(
+                        "GeoJSON Merged",
+                        12.5,
+                        -45.25,
+                    )],
+                    &merged_branch_name,
+                )
+                .await?;
+            transaction
+                .merge_unmerged_locations(&merged_branch_name)
+                .await?;
+            let unmerged_branch_name = RoswaalOwnedGitBranchName::for_adding_locations();
+            transaction
+                .save_locations(
+                    &vec![RoswaalLocation::new_without_validation(
+                        "GeoJSON Unmerged",
+                        10.0,
+                        10.0,
+                    )],
+                    &unmerged_branch_name,
+                )
+                .await?;
+            transaction.commit().await?;
+            for (include_unmerged, expected_names) in [
+                ("false", vec!["GeoJSON Merged"]),
+                ("true", vec!["GeoJSON Merged", "GeoJSON Unmerged"]),
+            ] {
+                let resp = app
+                    .server
+                    .get("/locations.geojson")
+                    .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                    .add_query_param("include_unmerged", include_unmerged)
+                    .await;
+                resp.assert_status_ok();
+                assert_eq!(resp.header(CONTENT_TYPE), "application/geo+json");
+                let geojson = resp.json::<Value>();
+                assert_eq!(geojson["type"], "FeatureCollection");
+                let features = geojson["features"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter(|f| {
+                        f["properties"]["name"]
+                            .as_str()
+                            .unwrap()
+                            .starts_with("GeoJSON")
+                    })
+                    .collect::<Vec<&Value>>();
+                let names = features
+                    .iter()
+                    .map(|f| f["properties"]["name"].as_str().unwrap())
+                    .collect::<Vec<&str>>();
+                assert_eq!(names, expected_names);
+                assert_eq!(
+                    features[0]["geometry"],
+                    json!({ "type": "Point", "coordinates": [-45.25, 12.5] })
+                );
+            }
+            let mut transaction = database.transaction().await?;
+            transaction
+                .close_add_locations_branch(&unmerged_branch_name)
+                .await?;
+            transaction.commit().await
+        })
+        .await
+        .unwrap()
+    }
+
     struct TestApp {
         server: TestServer,
         environment: Arc<ServerEnvironment>,
     }
 
     impl TestApp {
+        async fn stats(&self) -> Value {
+            let resp = self
+                .server
+                .get("/stats")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .await;
+            resp.assert_status_ok();
+            resp.json::<Value>()
+        }
+
         async fn add_tests(&self, tests_str: &str) {
             let form_data = RoswaalSlackRequest::new(
                 ACCEPTANCE_TEST_CHANNEL_ID.to_string(),
+                RoswaalConfig::current()
+                    .maintainer_slack_user_id()
+                    .to_string(),
                 tests_str.to_string(),
                 RoswaalSlackCommand::AddTests,
                 SLACK_RESPONSE_URL.to_string(),
@@ -297,11 +2034,19 @@ mod tests {
                 .await
         }
 
+        async fn claim_tests(&self, runner_id: &str) -> TestResponse {
+            self.server
+                .post("/tests/claim")
+                .add_query_param("password", DEV_RAW_ENDPOINT_PASSWORD)
+                .json(&json!({ "runnerId": runner_id, "count": 1000 }))
+                .await
+        }
+
         async fn most_recent_branch_name(&self) -> anyhow::Result<RoswaalOwnedGitBranchName> {
-            let sqlite = self.environment.sqlite();
-            let mut transaction = sqlite.transaction().await?;
+            let database = self.environment.database();
+            let mut transaction = database.transaction().await?;
             with_transaction!(transaction, async {
-                let branch_name = query_as::<Sqlite, SqliteBranchName>(
+                let branch_name = query_as::<SqliteBranchName>(
                     "SELECT unmerged_branch_name FROM Tests ORDER BY creation_date DESC",
                 )
                 .fetch_one(transaction.connection())
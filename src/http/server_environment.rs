@@ -1,53 +1,174 @@
-use std::{env, sync::Arc};
+use std::{sync::Arc, time::Duration};
 
+#[cfg(feature = "e2e")]
+use crate::utils::config::RoswaalPullRequestMode;
 use crate::{
     git::{
         metadata::RoswaalGitRepositoryMetadata,
-        pull_request::GithubPullRequestOpen,
-        repo::{LibGit2RepositoryClient, RoswaalGitRepository},
+        pending_pull_request::StagingGithubPullRequestOpen,
+        pull_request::{GithubCheckReachability, GithubPullRequestComment, GithubPullRequestOpen},
+        repo::{AnyGitRepositoryClient, RoswaalGitRepository},
+    },
+    runner::webhook::{RoswaalEnqueueTestRun, RoswaalTestRunnerWebhook},
+    scheduler::schedule::RoswaalDailySchedule,
+    slack::{
+        file::SlackFetchFile, home::SlackPublishHomeTab, in_flight::RoswaalInFlightOperations,
+        message::SlackSendMessage, modal::SlackOpenModal, permissions::SlackFetchUserGroupMembers,
+        report::TruncatingSlackSendMessage,
+    },
+    utils::{
+        config::RoswaalConfig, database_url::RoswaalDatabaseUrl, db::RoswaalDatabase,
+        env::RoswaalEnvironement,
     },
-    slack::message::SlackSendMessage,
-    utils::{env::RoswaalEnvironement, sqlite::RoswaalSqlite},
 };
 use anyhow::Result;
 use log::info;
 use reqwest::Client;
+use tokio_util::task::TaskTracker;
+
+use super::{
+    github_webhook::GithubWebhookSecret, password::EndpointPassword,
+    rate_limit::SlackCommandRateLimiter,
+};
 
-use super::password::EndpointPassword;
+/// The number of slack commands that a user can run in a burst before being rate limited.
+const SLACK_COMMAND_BURST_LIMIT: u32 = 10;
+
+/// The time it takes for a rate limited user to regain the ability to run 1 slack command.
+const SLACK_COMMAND_REFILL_INTERVAL: Duration = Duration::from_secs(30);
 
 /// A data type containing necessary structs for server operations.
 pub struct ServerEnvironment {
-    git_repository: RoswaalGitRepository<LibGit2RepositoryClient>,
+    git_repository: RoswaalGitRepository<AnyGitRepositoryClient>,
+    test_target_repositories: Vec<RoswaalGitRepository<AnyGitRepositoryClient>>,
     http_client: Arc<Client>,
-    sqlite: Arc<RoswaalSqlite>,
+    slack_messenger: Arc<TruncatingSlackSendMessage<Client>>,
+    pull_request_open: StagingGithubPullRequestOpen<Client>,
+    test_runner: Option<RoswaalTestRunnerWebhook>,
+    database: Arc<RoswaalDatabase>,
     address: &'static str,
     password: EndpointPassword,
+    github_webhook_secret: Option<GithubWebhookSecret>,
+    test_digest_schedule: RoswaalDailySchedule,
+    deprecated_tests_schedule: RoswaalDailySchedule,
+    test_digest_channel_id: Option<String>,
+    test_failure_channel_id: Option<String>,
+    background_tasks: TaskTracker,
+    in_flight_operations: RoswaalInFlightOperations,
+    slack_rate_limiter: SlackCommandRateLimiter,
 }
 
 impl ServerEnvironment {
     /// The production environment.
     pub async fn prod() -> Result<Self> {
+        let http_client = Arc::new(Client::new());
+        let database = Arc::new(
+            RoswaalDatabaseUrl::from_env_or_sqlite("./roswaal.sqlite")?
+                .open()
+                .await?,
+        );
         Ok(Self {
-            git_repository: RoswaalGitRepository::open(
+            git_repository: RoswaalGitRepository::clone_if_missing_and_open(
                 &RoswaalGitRepositoryMetadata::for_tif_react_frontend(),
             )
             .await?,
-            http_client: Arc::new(Client::new()),
-            sqlite: Arc::new(RoswaalSqlite::open("./roswaal.sqlite").await?),
+            test_target_repositories: Self::open_test_target_repositories().await?,
+            test_runner: Self::test_runner_webhook(&http_client),
+            pull_request_open: Self::staging_pull_request_open(&http_client, &database),
+            slack_messenger: Self::truncating_slack_messenger(&http_client, &database),
+            http_client,
+            database,
             address: "0.0.0.0:8080",
             password: EndpointPassword::prod(),
+            github_webhook_secret: GithubWebhookSecret::prod(),
+            test_digest_schedule: RoswaalDailySchedule::utc(4, 0),
+            deprecated_tests_schedule: RoswaalDailySchedule::utc(5, 0),
+            test_digest_channel_id: RoswaalConfig::current()
+                .test_digest_channel_id()
+                .map(str::to_string),
+            test_failure_channel_id: RoswaalConfig::current()
+                .test_failure_channel_id()
+                .map(str::to_string),
+            background_tasks: TaskTracker::new(),
+            in_flight_operations: RoswaalInFlightOperations::default(),
+            slack_rate_limiter: SlackCommandRateLimiter::new(
+                SLACK_COMMAND_BURST_LIMIT,
+                SLACK_COMMAND_REFILL_INTERVAL,
+            ),
         })
     }
 
     /// The development environment.
     pub async fn dev() -> Result<Self> {
+        let http_client = Arc::new(Client::new());
+        let database = Arc::new(
+            RoswaalDatabaseUrl::from_env_or_sqlite("./roswaal-dev.sqlite")?
+                .open()
+                .await?,
+        );
         Ok(Self {
             git_repository:
                 RoswaalGitRepository::open(&RoswaalGitRepositoryMetadata::for_testing()).await?,
-            http_client: Arc::new(Client::new()),
-            sqlite: Arc::new(RoswaalSqlite::open("./roswaal-dev.sqlite").await?),
+            test_target_repositories: vec![],
+            test_runner: Self::test_runner_webhook(&http_client),
+            pull_request_open: Self::staging_pull_request_open(&http_client, &database),
+            slack_messenger: Self::truncating_slack_messenger(&http_client, &database),
+            http_client,
+            database,
             address: "127.0.0.1:8082",
             password: EndpointPassword::dev(),
+            github_webhook_secret: Some(GithubWebhookSecret::dev()),
+            test_digest_schedule: RoswaalDailySchedule::utc(4, 0),
+            deprecated_tests_schedule: RoswaalDailySchedule::utc(5, 0),
+            test_digest_channel_id: RoswaalConfig::current()
+                .test_digest_channel_id()
+                .map(str::to_string),
+            test_failure_channel_id: RoswaalConfig::current()
+                .test_failure_channel_id()
+                .map(str::to_string),
+            background_tasks: TaskTracker::new(),
+            in_flight_operations: RoswaalInFlightOperations::default(),
+            slack_rate_limiter: SlackCommandRateLimiter::new(
+                SLACK_COMMAND_BURST_LIMIT,
+                SLACK_COMMAND_REFILL_INTERVAL,
+            ),
+        })
+    }
+
+    /// The environment for the end-to-end harness.
+    ///
+    /// PRs are saved to an in-memory database instead of being opened, so that the harness can
+    /// check them without asking anyone on GitHub to review a test PR.
+    #[cfg(feature = "e2e")]
+    pub async fn e2e() -> Result<Self> {
+        let http_client = Arc::new(Client::new());
+        let database = Arc::new(RoswaalDatabase::in_memory().await?);
+        Ok(Self {
+            git_repository:
+                RoswaalGitRepository::open(&RoswaalGitRepositoryMetadata::for_testing()).await?,
+            test_target_repositories: vec![],
+            test_runner: None,
+            pull_request_open: StagingGithubPullRequestOpen::new(
+                http_client.clone(),
+                database.clone(),
+                RoswaalPullRequestMode::Staged,
+            ),
+            slack_messenger: Self::truncating_slack_messenger(&http_client, &database),
+            http_client,
+            database,
+            address: "127.0.0.1:8083",
+            password: EndpointPassword::dev(),
+            github_webhook_secret: Some(GithubWebhookSecret::dev()),
+            test_digest_schedule: RoswaalDailySchedule::utc(4, 0),
+            deprecated_tests_schedule: RoswaalDailySchedule::utc(5, 0),
+            test_digest_channel_id: None,
+            test_failure_channel_id: None,
+            background_tasks: TaskTracker::new(),
+            in_flight_operations: RoswaalInFlightOperations::default(),
+            slack_rate_limiter: SlackCommandRateLimiter::new(
+                SLACK_COMMAND_BURST_LIMIT,
+                SLACK_COMMAND_REFILL_INTERVAL,
+            ),
         })
     }
 
@@ -64,23 +185,101 @@ impl ServerEnvironment {
             Self::prod().await
         }
     }
+
+    fn test_runner_webhook(http_client: &Arc<Client>) -> Option<RoswaalTestRunnerWebhook> {
+        RoswaalConfig::current()
+            .test_runner_webhook_url()
+            .map(|url| RoswaalTestRunnerWebhook::new(http_client.clone(), url))
+    }
+
+    fn staging_pull_request_open(
+        http_client: &Arc<Client>,
+        database: &Arc<RoswaalDatabase>,
+    ) -> StagingGithubPullRequestOpen<Client> {
+        StagingGithubPullRequestOpen::new(
+            http_client.clone(),
+            database.clone(),
+            RoswaalConfig::current().pull_request_mode(),
+        )
+    }
+
+    fn truncating_slack_messenger(
+        http_client: &Arc<Client>,
+        database: &Arc<RoswaalDatabase>,
+    ) -> Arc<TruncatingSlackSendMessage<Client>> {
+        Arc::new(TruncatingSlackSendMessage::new(
+            http_client.clone(),
+            database.clone(),
+        ))
+    }
+
+    async fn open_test_target_repositories(
+    ) -> Result<Vec<RoswaalGitRepository<AnyGitRepositoryClient>>> {
+        let mut repositories = Vec::new();
+        for metadata in RoswaalGitRepositoryMetadata::test_targets_from_config()? {
+            info!("Opening test target repository {}.", metadata.name());
+            repositories.push(RoswaalGitRepository::clone_if_missing_and_open(&metadata).await?);
+        }
+        Ok(repositories)
+    }
 }
 
 impl ServerEnvironment {
-    pub fn git_repository(&self) -> &RoswaalGitRepository<LibGit2RepositoryClient> {
+    pub fn git_repository(&self) -> &RoswaalGitRepository<AnyGitRepositoryClient> {
         &self.git_repository
     }
 
+    /// The additional repositories that test code is also added to and removed from.
+    pub fn test_target_repositories(&self) -> &[RoswaalGitRepository<AnyGitRepositoryClient>] {
+        &self.test_target_repositories
+    }
+
+    /// Opens the PRs for edits, or defers them when the staged PR mode is configured.
     pub fn github_pull_request_open(&self) -> &impl GithubPullRequestOpen {
+        &self.pull_request_open
+    }
+
+    /// Opens PRs on GitHub regardless of the PR mode, which is used for opening deferred PRs.
+    pub fn pending_pull_requests_open(&self) -> &impl GithubPullRequestOpen {
+        self.http_client.as_ref()
+    }
+
+    pub fn github_pull_request_comment(&self) -> &impl GithubPullRequestComment {
+        self.http_client.as_ref()
+    }
+
+    pub fn github_reachability_check(&self) -> &impl GithubCheckReachability {
+        self.http_client.as_ref()
+    }
+
+    pub fn slack_file_fetch(&self) -> &impl SlackFetchFile {
+        self.http_client.as_ref()
+    }
+
+    pub fn slack_user_group_fetch(&self) -> &impl SlackFetchUserGroupMembers {
+        self.http_client.as_ref()
+    }
+
+    pub fn slack_modal_open(&self) -> &impl SlackOpenModal {
+        self.http_client.as_ref()
+    }
+
+    pub fn slack_home_tab_publish(&self) -> &impl SlackPublishHomeTab {
         self.http_client.as_ref()
     }
 
+    /// The CI runner that runs the tests added by merged branches, if one is configured.
+    pub fn test_runner(&self) -> Option<&impl RoswaalEnqueueTestRun> {
+        self.test_runner.as_ref()
+    }
+
+    /// Sends slack messages, truncating the messages that have more blocks than slack allows.
     pub fn slack_messenger(&self) -> Arc<impl SlackSendMessage + Send + Sync + 'static> {
-        self.http_client.clone()
+        self.slack_messenger.clone()
     }
 
-    pub fn sqlite(&self) -> Arc<RoswaalSqlite> {
-        self.sqlite.clone()
+    pub fn database(&self) -> Arc<RoswaalDatabase> {
+        self.database.clone()
     }
 
     pub fn address(&self) -> String {
@@ -90,4 +289,46 @@ impl ServerEnvironment {
     pub fn password(&self) -> EndpointPassword {
         self.password.clone()
     }
+
+    /// The secret of the Github webhook, if the webhook is configured.
+    pub fn github_webhook_secret(&self) -> Option<GithubWebhookSecret> {
+        self.github_webhook_secret.clone()
+    }
+
+    /// The schedule for sending the digest of failing and idle tests.
+    pub fn test_digest_schedule(&self) -> RoswaalDailySchedule {
+        self.test_digest_schedule
+    }
+
+    /// The schedule for removing the deprecated tests whose grace period has ended.
+    pub fn deprecated_tests_schedule(&self) -> RoswaalDailySchedule {
+        self.deprecated_tests_schedule
+    }
+
+    /// The tracker for background work (eg. long running slack commands) that must finish before
+    /// the server shuts down.
+    pub fn background_tasks(&self) -> TaskTracker {
+        self.background_tasks.clone()
+    }
+
+    /// The registry of slack commands being handled in the background that can be cancelled
+    /// with `/roswaal-cancel`.
+    pub fn in_flight_operations(&self) -> RoswaalInFlightOperations {
+        self.in_flight_operations.clone()
+    }
+
+    /// The per-user rate limiter for slack commands.
+    pub fn slack_rate_limiter(&self) -> SlackCommandRateLimiter {
+        self.slack_rate_limiter.clone()
+    }
+
+    /// The id of the slack channel to send the test digest to, if any.
+    pub fn test_digest_channel_id(&self) -> Option<&str> {
+        self.test_digest_channel_id.as_deref()
+    }
+
+    /// The id of the slack channel to post the failures reported to `/progress` in, if any.
+    pub fn test_failure_channel_id(&self) -> Option<&str> {
+        self.test_failure_channel_id.as_deref()
+    }
 }
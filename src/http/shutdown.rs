@@ -0,0 +1,28 @@
+use log::info;
+use tokio::signal;
+
+/// Completes when the process receives a ctrl-c, or a SIGTERM signal on unix platforms.
+///
+/// Deploys send a SIGTERM before killing the process, so the server uses this signal to stop
+/// accepting new requests and finish any in-flight work before exiting.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("Failed to install the ctrl-c signal handler.")
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to install the SIGTERM signal handler.")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    info!("Received shutdown signal, shutting down gracefully.");
+}
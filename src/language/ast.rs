@@ -1,9 +1,14 @@
-use std::str::FromStr;
+use std::{ops::Range, str::FromStr};
 
 use once_cell::sync::Lazy;
 use regex::{Regex, RegexBuilder};
 
-use crate::location::name::{RoswaalLocationName, RoswaalLocationParsingResult};
+use crate::{
+    location::name::{RoswaalLocationName, RoswaalLocationParsingResult},
+    utils::string::levenshtein_distance,
+};
+
+use super::duration::{RoswaalWaitDuration, RoswaalWaitDurationParsingResult};
 
 /// A token of roswaal test syntax.
 ///
@@ -11,54 +16,250 @@ use crate::location::name::{RoswaalLocationName, RoswaalLocationParsingResult};
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RoswaalTestSyntaxCommand<'a> {
     /// A line denoting a "Step" command without its matching "Requirement"
-    /// command.
-    Step { label: &'a str },
+    /// command, and the number of retries from its optional "(retries: n)"
+    /// annotation.
+    Step {
+        label: &'a str,
+        retries: Option<u32>,
+    },
     /// A line denoting the "Abstract" command.
     Abstract,
     /// A line denoting the "New Test" command.
     NewTest,
+    /// A line denoting the "Planned Test" command, which names a test that only has an abstract
+    /// until its steps are written.
+    PlannedTest,
     /// A line denoting the "Set Location" command.
     SetLocation {
         parse_result: RoswaalLocationParsingResult,
     },
+    /// A line denoting the "Wait" command.
+    Wait {
+        parse_result: RoswaalWaitDurationParsingResult,
+    },
     /// A line denoting the "Requirement" command that is to be paired with a
     /// respective step command.
     Requirement { label: &'a str },
+    /// A line denoting the "Tags" command, which contains a comma separated
+    /// list of labels for the test.
+    Tags { tags: Vec<&'a str> },
+    /// A line denoting the "Suite" command, which contains the name of the
+    /// suite that the test belongs to.
+    Suite { name: &'a str },
+    /// A line denoting the "Platforms" command, which contains a comma
+    /// separated list of the platforms that the test runs on.
+    Platforms { platforms: Vec<&'a str> },
+    /// A line denoting the "Variables" command, which contains a comma
+    /// separated list of `name = value` declarations.
+    Variables { variables: Vec<&'a str> },
+    /// A line denoting the "Verify" command, which describes an outcome that
+    /// the test should check for rather than an action to perform.
+    Verify { label: &'a str },
+    /// A line denoting the "Using" command that is optionally paired with a
+    /// respective verify command.
+    Using { label: &'a str },
+    /// A line denoting the "Before Launch" command, which describes setup work
+    /// to perform before the app is launched.
+    BeforeLaunch,
+    /// A line denoting the "Requires Test" command, which names another test
+    /// that must run before this test.
+    RequiresTest { test_name: &'a str },
     /// A line which has proper command syntax, but the command is not known.
     UnknownCommand,
 }
 
 static KNOWN_COMMANDS_REGEX: Lazy<Regex> = Lazy::new(|| {
-    let regex = r"^ *(?:(?<setlocation>set +location)|(?<step>step)|(?<newtest>new +test)|(?<requirement>requirement)|(?<abstract>abstract))(?<label>.*)";
+    let regex = r"^ *(?:(?<setlocation>set +location)|(?<step>step)|(?<newtest>new +test)|(?<plannedtest>planned +test)|(?<requirestest>requires? +tests?)|(?<requirement>requirement)|(?<abstract>abstract)|(?<wait>wait)|(?<tags>tags?)|(?<suite>suite)|(?<platforms>platforms?)|(?<variables>variables?)|(?<verify>verify)|(?<using>using)|(?<beforelaunch>before +launch))(?<label>.*)";
     RegexBuilder::new(regex)
         .case_insensitive(true)
         .build()
         .expect("Failed to compile known commands regex.")
 });
 
+/// The names of the known commands as they are written in the documentation.
+const KNOWN_COMMAND_NAMES: [&str; 15] = [
+    "New Test",
+    "Planned Test",
+    "Abstract",
+    "Before Launch",
+    "Requires Test",
+    "Step",
+    "Requirement",
+    "Set Location",
+    "Wait",
+    "Tags",
+    "Suite",
+    "Platforms",
+    "Variables",
+    "Verify",
+    "Using",
+];
+
+/// Returns the known command name that is closest to the name of an unknown command, or None if
+/// no known command name is close enough to be a likely typo.
+///
+/// Only the leading words of `name` are compared, so labels are ignored (eg. "Steb 1" suggests
+/// "Step").
+pub fn suggested_command_name(name: &str) -> Option<&'static str> {
+    let words = name.split_whitespace().collect::<Vec<&str>>();
+    KNOWN_COMMAND_NAMES
+        .iter()
+        .filter_map(|command_name| {
+            let word_count = command_name.split_whitespace().count();
+            if words.len() < word_count {
+                return None;
+            }
+            let leading_words = words[..word_count].join(" ").to_lowercase();
+            let distance = levenshtein_distance(&leading_words, &command_name.to_lowercase());
+            let max_distance = (command_name.len() / 3).max(1);
+            (distance <= max_distance).then_some((distance, *command_name))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, command_name)| command_name)
+}
+
+static STEP_RETRIES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    let regex = r"^(?<description>.*?)\s*\(\s*retries\s*:\s*(?<retries>\d+)\s*\)\s*$";
+    RegexBuilder::new(regex)
+        .case_insensitive(true)
+        .build()
+        .expect("Failed to compile step retries regex.")
+});
+
+/// Splits a trailing "(retries: n)" annotation from a step description.
+fn split_step_retries(description: &str) -> (&str, Option<u32>) {
+    STEP_RETRIES_REGEX
+        .captures(description)
+        .and_then(|captures| {
+            let retries = captures["retries"].parse::<u32>().ok()?;
+            Some((captures.name("description")?.as_str(), Some(retries)))
+        })
+        .unwrap_or((description, None))
+}
+
+/// Removes the quotes around a label written as `"sign in flow"`.
+///
+/// Labels without surrounding quotes are returned as is, so `Step "1"` and `Step 1` have the
+/// same label.
+fn unquoted_label(label: &str) -> &str {
+    label
+        .strip_prefix('"')
+        .and_then(|label| label.strip_suffix('"'))
+        .map(str::trim)
+        .unwrap_or(label)
+}
+
+/// Returns `label` as it is written after the name of a command, which quotes the label if it
+/// contains a ":" that would otherwise end the command name.
+pub fn label_source_code(label: &str) -> String {
+    if label.contains(':') {
+        format!("\"{}\"", label)
+    } else {
+        label.to_string()
+    }
+}
+
+/// Splits a line into the name of its command and its description at the first ":" that is not
+/// inside of a quoted label (eg. `Step "sign in: email": Sign in`).
+///
+/// A quote without a closing quote does not begin a quoted label.
+fn split_command_line(line: &str) -> Option<(&str, &str)> {
+    let colon_index = line.find(':')?;
+    let quoted_label_end = line[..colon_index]
+        .find('"')
+        .and_then(|open_index| {
+            let close_index = line[open_index + 1..].find('"')?;
+            Some(open_index + close_index + 2)
+        })
+        .unwrap_or(0);
+    let colon_index = quoted_label_end + line[quoted_label_end..].find(':')?;
+    Some((&line[..colon_index], &line[colon_index + 1..]))
+}
+
 impl<'a> RoswaalTestSyntaxCommand<'a> {
+    /// Returns true if this command is a part of what a test does when it runs, rather than a
+    /// description of the test.
+    pub fn is_runnable(&self) -> bool {
+        !matches!(
+            self,
+            Self::NewTest
+                | Self::PlannedTest
+                | Self::Abstract
+                | Self::Tags { .. }
+                | Self::Suite { .. }
+                | Self::Platforms { .. }
+                | Self::UnknownCommand
+        )
+    }
+
     fn new(name: &'a str, description: &'a str) -> Self {
         let captures = match KNOWN_COMMANDS_REGEX.captures(name) {
             Some(c) => c,
             None => return RoswaalTestSyntaxCommand::UnknownCommand,
         };
-        let label = captures
-            .name("label")
-            .map(|lmatch| lmatch.as_str())
-            .unwrap_or("")
-            .trim();
+        let label = unquoted_label(
+            captures
+                .name("label")
+                .map(|lmatch| lmatch.as_str())
+                .unwrap_or("")
+                .trim(),
+        );
         if captures.name("setlocation").is_some() {
-            return RoswaalTestSyntaxCommand::SetLocation {
+            RoswaalTestSyntaxCommand::SetLocation {
                 parse_result: RoswaalLocationName::from_str(description.trim()),
-            };
+            }
         } else if captures.name("step").is_some() {
-            return RoswaalTestSyntaxCommand::Step { label };
+            RoswaalTestSyntaxCommand::Step {
+                label,
+                retries: split_step_retries(description.trim()).1,
+            }
         } else if captures.name("newtest").is_some() {
-            return RoswaalTestSyntaxCommand::NewTest;
+            RoswaalTestSyntaxCommand::NewTest
+        } else if captures.name("plannedtest").is_some() {
+            RoswaalTestSyntaxCommand::PlannedTest
+        } else if captures.name("requirestest").is_some() {
+            RoswaalTestSyntaxCommand::RequiresTest {
+                test_name: description.trim(),
+            }
         } else if captures.name("requirement").is_some() {
-            return RoswaalTestSyntaxCommand::Requirement { label };
+            RoswaalTestSyntaxCommand::Requirement { label }
+        } else if captures.name("wait").is_some() {
+            RoswaalTestSyntaxCommand::Wait {
+                parse_result: RoswaalWaitDuration::from_str(description.trim()),
+            }
+        } else if captures.name("tags").is_some() {
+            let tags = description
+                .split(',')
+                .map(|tag| tag.trim())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+            RoswaalTestSyntaxCommand::Tags { tags }
+        } else if captures.name("suite").is_some() {
+            RoswaalTestSyntaxCommand::Suite {
+                name: description.trim(),
+            }
+        } else if captures.name("platforms").is_some() {
+            let platforms = description
+                .split(',')
+                .map(|platform| platform.trim())
+                .filter(|platform| !platform.is_empty())
+                .collect();
+            RoswaalTestSyntaxCommand::Platforms { platforms }
+        } else if captures.name("variables").is_some() {
+            let variables = description
+                .split(',')
+                .map(|variable| variable.trim())
+                .filter(|variable| !variable.is_empty())
+                .collect();
+            RoswaalTestSyntaxCommand::Variables { variables }
+        } else if captures.name("verify").is_some() {
+            RoswaalTestSyntaxCommand::Verify { label }
+        } else if captures.name("using").is_some() {
+            RoswaalTestSyntaxCommand::Using { label }
+        } else if captures.name("beforelaunch").is_some() {
+            RoswaalTestSyntaxCommand::BeforeLaunch
         } else {
-            return RoswaalTestSyntaxCommand::Abstract;
+            RoswaalTestSyntaxCommand::Abstract
         }
     }
 }
@@ -80,16 +281,61 @@ impl<'a> RoswaalTestSyntaxCommand<'a> {
 ///
 /// Other semantic tokens exist that will generate common code used in tests
 /// like "Set Location" which sets the device's location to the area specified
-/// by the token.
+/// by the token, and "Wait" which pauses the test for the specified duration.
+/// The "Tags" token labels the test with a comma separated list of tags that
+/// can be used to search for the test, and the "Suite" token groups the test
+/// with other tests in a named suite. The "Platforms" token restricts the
+/// test to a comma separated list of platforms (eg. "ios, android"), and a
+/// test without it runs on every platform.
+///
+/// A "Variables" token declares a comma separated list of `name = value`
+/// variables, and each `{{name}}` placeholder in a step, requirement, verify,
+/// or required test description is replaced with the value of the variable
+/// when the test runs. Every placeholder must be declared by the test.
+///
+/// A "Verify" token describes an outcome that the test checks for instead of
+/// an action that it performs. It can be paired with a "Using" token with the
+/// same label to explain how to check the outcome, otherwise the verify
+/// description is used as the name of the generated verification function.
+///
+/// A "Before Launch" token describes setup work (eg. resetting device
+/// permissions) that runs before the app is launched, and generates a call in
+/// the test's "beforeLaunch" function.
+///
+/// A "Requires Test" token names another test whose steps must run first,
+/// which lets common setup (eg. signing in) be written once and reused. The
+/// generated code runs the actions of the required test before the steps of
+/// the test that requires it.
+///
+/// A step description can end with a "(retries: n)" annotation to retry a
+/// flaky step up to n times before the test fails.
+///
+/// The label of a step, requirement, verify, or using token can be wrapped
+/// in quotes (eg. `Step "sign in: email":`) so that it can contain a ":".
+/// Quoted and unquoted labels with the same text are paired with each other.
+///
+/// A test can be declared with "Planned Test" instead of "New Test" to record
+/// the idea for a test before its steps are written. A planned test can only
+/// have an abstract, tags, a suite, and platforms, and no code is generated
+/// for it.
 ///
 /// Example Syntax (creating a test specification):
-/// ```
+/// ```text
 /// New Test: My cool test
+/// Before Launch: Reset permissions
+/// Requires Test: Sign in
 /// Step 1: I am a step
-/// Step 2: This is another step
+/// Step 2: This is another step (retries: 2)
 /// Set Location: Antarctica
+/// Wait: 5 seconds
+/// Tags: events, smoke
+/// Suite: Events
+/// Platforms: ios, android
+/// Variables: eventName = Pickup Basketball
 /// Requirement 1: I am a requirement that matches step 1.
 /// Requirement 2: I am a requirement that is paired with step 2.
+/// Verify 1: The step worked
+/// Using 1: Check that the cool thing is on screen.
 /// ```
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RoswaalTestSyntax<'a> {
@@ -98,16 +344,24 @@ pub struct RoswaalTestSyntax<'a> {
 
 impl<'a> RoswaalTestSyntax<'a> {
     /// Returns an iterator of syntax tokens for each line in the source code.
-    pub fn lines(&self) -> impl Iterator<Item = RoswaalTestSyntaxLine> {
-        self.source_code
-            .lines()
-            .enumerate()
-            .filter_map(|(i, line)| {
-                RoswaalTestSyntaxLineContent::from(line).map(|content| RoswaalTestSyntaxLine {
-                    line_number: (i + 1) as u32,
-                    content,
-                })
-            })
+    pub fn lines(&self) -> RoswaalTestSyntaxLines<'a> {
+        RoswaalTestSyntaxLines {
+            source_code: self.source_code,
+            lines: self.source_code.lines(),
+            line_count: 0,
+        }
+    }
+
+    /// Returns the span of the content on the line with `line_number`.
+    ///
+    /// If this syntax does not have the line, an empty span at the end of the source code is
+    /// returned.
+    pub fn line_span(&self, line_number: u32) -> RoswaalTestSyntaxSpan {
+        let line_index = line_number.saturating_sub(1) as usize;
+        match self.source_code.lines().nth(line_index) {
+            Some(line) => RoswaalTestSyntaxSpan::of_line(self.source_code, line, line_number),
+            None => RoswaalTestSyntaxSpan::end_of(self.source_code, line_number),
+        }
     }
 
     /// Returns the last line number of this syntax.
@@ -144,21 +398,123 @@ impl<'a> From<&'a str> for RoswaalTestSyntax<'a> {
     }
 }
 
+/// An iterator of syntax tokens for each non-empty line of a `RoswaalTestSyntax`.
+///
+/// Each line is only tokenized when the iterator reaches it, so the tokens of a test are never
+/// all held at once. The compiled commands of the test are still kept in memory.
+#[derive(Debug, Clone)]
+pub struct RoswaalTestSyntaxLines<'a> {
+    source_code: &'a str,
+    lines: std::str::Lines<'a>,
+    line_count: u32,
+}
+
+impl<'a> RoswaalTestSyntaxLines<'a> {
+    /// Returns the number of the last line that this iterator has read, including empty lines.
+    ///
+    /// Once the iterator is exhausted, this is the same as `RoswaalTestSyntax::last_line_number`
+    /// without needing a second pass over the source code. If no lines were read, line 1 is
+    /// returned.
+    pub fn last_line_number(&self) -> u32 {
+        self.line_count.max(1)
+    }
+}
+
+impl<'a> Iterator for RoswaalTestSyntaxLines<'a> {
+    type Item = RoswaalTestSyntaxLine<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in self.lines.by_ref() {
+            self.line_count += 1;
+            if let Some(content) = RoswaalTestSyntaxLineContent::from(line) {
+                return Some(RoswaalTestSyntaxLine {
+                    span: RoswaalTestSyntaxSpan::of_line(self.source_code, line, self.line_count),
+                    content,
+                });
+            }
+        }
+        None
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct RoswaalTestSyntaxLine<'a> {
-    line_number: u32,
+    span: RoswaalTestSyntaxSpan,
     content: RoswaalTestSyntaxLineContent<'a>,
 }
 
 impl<'a> RoswaalTestSyntaxLine<'a> {
     pub fn line_number(&self) -> u32 {
-        self.line_number
+        self.span.line_number()
+    }
+    pub fn span(&self) -> &RoswaalTestSyntaxSpan {
+        &self.span
     }
     pub fn content(&self) -> &RoswaalTestSyntaxLineContent<'a> {
         &self.content
     }
 }
 
+/// The location of the content of a line in the source code of a `RoswaalTestSyntax`.
+///
+/// Byte offsets are relative to the start of the source code, and columns count characters from 1
+/// like line numbers do. Both ranges exclude the whitespace around the content of the line.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalTestSyntaxSpan {
+    line_number: u32,
+    byte_range: Range<usize>,
+    column_range: Range<u32>,
+}
+
+impl RoswaalTestSyntaxSpan {
+    pub fn new(line_number: u32, byte_range: Range<usize>, column_range: Range<u32>) -> Self {
+        Self {
+            line_number,
+            byte_range,
+            column_range,
+        }
+    }
+
+    /// Returns the span of `line`, which must be a slice of `source_code`.
+    fn of_line(source_code: &str, line: &str, line_number: u32) -> Self {
+        let line_start = line.as_ptr() as usize - source_code.as_ptr() as usize;
+        let content = line.trim();
+        let leading_whitespace = &line[..line.len() - line.trim_start().len()];
+        let start_column = leading_whitespace.chars().count() as u32 + 1;
+        let start = line_start + leading_whitespace.len();
+        Self::new(
+            line_number,
+            start..start + content.len(),
+            start_column..start_column + content.chars().count() as u32,
+        )
+    }
+
+    fn end_of(source_code: &str, line_number: u32) -> Self {
+        let end = source_code.len();
+        let column = source_code
+            .rsplit('\n')
+            .next()
+            .map(|line| line.chars().count() as u32 + 1)
+            .unwrap_or(1);
+        Self::new(line_number, end..end, column..column)
+    }
+}
+
+impl RoswaalTestSyntaxSpan {
+    pub fn line_number(&self) -> u32 {
+        self.line_number
+    }
+
+    pub fn byte_range(&self) -> &Range<usize> {
+        &self.byte_range
+    }
+
+    /// The range of character columns, starting from 1, that this span covers on its line.
+    pub fn column_range(&self) -> &Range<u32> {
+        &self.column_range
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RoswaalTestSyntaxLineContent<'a> {
     Unknown(&'a str),
@@ -171,7 +527,7 @@ pub enum RoswaalTestSyntaxLineContent<'a> {
 
 impl<'a> RoswaalTestSyntaxLineContent<'a> {
     pub fn from(line: &'a str) -> Option<Self> {
-        let (name, description) = match line.split_once(":") {
+        let (name, description) = match split_command_line(line) {
             Some(split) => split,
             None => {
                 return if line.is_empty() {
@@ -181,10 +537,18 @@ impl<'a> RoswaalTestSyntaxLineContent<'a> {
                 }
             }
         };
+        let command = RoswaalTestSyntaxCommand::new(name, description);
+        let description = match command {
+            RoswaalTestSyntaxCommand::Step {
+                label: _,
+                retries: Some(_),
+            } => split_step_retries(description.trim()).0,
+            _ => description.trim(),
+        };
         let content = Self::Command {
             name,
-            description: description.trim(),
-            command: RoswaalTestSyntaxCommand::new(name, description),
+            description,
+            command,
         };
         Some(content)
     }
@@ -201,14 +565,19 @@ static EXTRACT_REGEX: Lazy<Regex> = Lazy::new(|| {
 ///
 /// Each test should be placed between 2 "```\n" sequences in the string.
 pub fn extract_tests_syntax<'a>(text: &'a str) -> Vec<RoswaalTestSyntax<'a>> {
-    EXTRACT_REGEX
-        .captures_iter(text)
-        .filter_map(|captures| {
-            captures
-                .name("test")
-                .map(|m| RoswaalTestSyntax::from(m.as_str().trim()))
-        })
-        .collect()
+    tests_syntax(text).collect()
+}
+
+/// Returns an iterator that extracts each `RoswaalTestSyntax` from a block of multiline text
+/// only when it is reached.
+///
+/// See `extract_tests_syntax`.
+pub fn tests_syntax<'a>(text: &'a str) -> impl Iterator<Item = RoswaalTestSyntax<'a>> {
+    EXTRACT_REGEX.captures_iter(text).filter_map(|captures| {
+        captures
+            .name("test")
+            .map(|m| RoswaalTestSyntax::from(m.as_str().trim()))
+    })
 }
 
 #[cfg(test)]
@@ -281,6 +650,42 @@ Requirement 1: Awesome"
             assert_eq!(content, Some(RoswaalTestSyntaxLineContent::Unknown(source)))
         }
 
+        #[test]
+        fn test_from_string_splits_retries_annotation_from_step_description() {
+            let assert_retries = |line: &str, description: &str, retries: Option<u32>| {
+                assert_command(
+                    line,
+                    "Step 1",
+                    description,
+                    RoswaalTestSyntaxCommand::Step {
+                        label: "1",
+                        retries,
+                    },
+                )
+            };
+            assert_retries(
+                "Step 1: Tap the button (retries: 2)",
+                "Tap the button",
+                Some(2),
+            );
+            assert_retries(
+                "Step 1: Tap the button(RETRIES:3)  ",
+                "Tap the button",
+                Some(3),
+            );
+            assert_retries("Step 1: (retries: 2)", "", Some(2));
+            assert_retries(
+                "Step 1: Tap the button (retries: many)",
+                "Tap the button (retries: many)",
+                None,
+            );
+            assert_retries(
+                "Step 1: Tap (retries: 2) the button",
+                "Tap (retries: 2) the button",
+                None,
+            );
+        }
+
         #[test]
         fn test_from_string_returns_step_for_simple_step_commands() {
             fn assert_step_description(line: &str, name: &str, description: &str, label: &str) {
@@ -288,7 +693,10 @@ Requirement 1: Awesome"
                     line,
                     name,
                     description,
-                    RoswaalTestSyntaxCommand::Step { label },
+                    RoswaalTestSyntaxCommand::Step {
+                        label,
+                        retries: None,
+                    },
                 )
             }
             assert_step_description(
@@ -350,6 +758,100 @@ Requirement 1: Awesome"
             assert_set_location_with_error("set location:", RoswaalLocationNameParsingError::Empty)
         }
 
+        #[test]
+        fn test_from_string_returns_wait_for_wait_commands() {
+            fn assert_wait(line: &str, command_name: &str, description: &str) {
+                let command = RoswaalTestSyntaxCommand::Wait {
+                    parse_result: RoswaalWaitDuration::from_str(description),
+                };
+                assert_command(line, command_name, description, command)
+            }
+
+            assert_wait("Wait: 5 seconds", "Wait", "5 seconds");
+            assert_wait("  wait   : 500ms", "  wait   ", "500ms");
+            assert_wait("Wait: forever", "Wait", "forever")
+        }
+
+        #[test]
+        fn test_from_string_returns_tags_for_tags_commands() {
+            fn assert_tags(line: &str, command_name: &str, description: &str, tags: Vec<&str>) {
+                let command = RoswaalTestSyntaxCommand::Tags { tags };
+                assert_command(line, command_name, description, command)
+            }
+
+            assert_tags(
+                "Tags: events, smoke",
+                "Tags",
+                "events, smoke",
+                vec!["events", "smoke"],
+            );
+            assert_tags("  tag : smoke", "  tag ", "smoke", vec!["smoke"]);
+            assert_tags("Tags: a,, b ,", "Tags", "a,, b ,", vec!["a", "b"])
+        }
+
+        #[test]
+        fn test_from_string_returns_suite_for_suite_commands() {
+            fn assert_suite(line: &str, command_name: &str, description: &str, name: &str) {
+                let command = RoswaalTestSyntaxCommand::Suite { name };
+                assert_command(line, command_name, description, command)
+            }
+
+            assert_suite("Suite: Onboarding", "Suite", "Onboarding", "Onboarding");
+            assert_suite(
+                "  suite : Event Details",
+                "  suite ",
+                "Event Details",
+                "Event Details",
+            )
+        }
+
+        #[test]
+        fn test_from_string_returns_platforms_for_platforms_commands() {
+            fn assert_platforms(
+                line: &str,
+                command_name: &str,
+                description: &str,
+                platforms: Vec<&str>,
+            ) {
+                let command = RoswaalTestSyntaxCommand::Platforms { platforms };
+                assert_command(line, command_name, description, command)
+            }
+
+            assert_platforms(
+                "Platforms: ios, android",
+                "Platforms",
+                "ios, android",
+                vec!["ios", "android"],
+            );
+            assert_platforms("  platform : iOS,", "  platform ", "iOS,", vec!["iOS"])
+        }
+
+        #[test]
+        fn test_from_string_returns_variables_for_variables_commands() {
+            fn assert_variables(
+                line: &str,
+                command_name: &str,
+                description: &str,
+                variables: Vec<&str>,
+            ) {
+                let command = RoswaalTestSyntaxCommand::Variables { variables };
+                assert_command(line, command_name, description, command)
+            }
+
+            assert_variables(
+                "Variables: eventName = Pickup Basketball, host = Roswaal",
+                "Variables",
+                "eventName = Pickup Basketball, host = Roswaal",
+                vec!["eventName = Pickup Basketball", "host = Roswaal"],
+            );
+            assert_variables(
+                "variable:  time = 10:00 AM,",
+                "variable",
+                "time = 10:00 AM,",
+                vec!["time = 10:00 AM"],
+            )
+        }
+
         #[test]
         fn test_from_string_returns_unknown_command_for_random_commands() {
             fn assert_unknown_command(line: &str, name: &str, description: &str) {
@@ -376,6 +878,16 @@ Requirement 1: Awesome"
             assert_new_test(" new    tESt    : weird  ", " new    tESt    ", "weird")
         }
 
+        #[test]
+        fn test_from_string_returns_planned_test_for_planned_test_command() {
+            fn assert_planned_test(line: &str, name: &str, test_name: &str) {
+                assert_command(line, name, test_name, RoswaalTestSyntaxCommand::PlannedTest)
+            }
+
+            assert_planned_test("Planned Test: Hello world", "Planned Test", "Hello world");
+            assert_planned_test("planned  test: test", "planned  test", "test")
+        }
+
         #[test]
         fn test_from_string_returns_requirement_for_requirement_command() {
             fn assert_requirement(line: &str, name: &str, description: &str, label: &str) {
@@ -408,6 +920,114 @@ Requirement 1: Awesome"
             )
         }
 
+        #[test]
+        fn test_from_string_returns_verify_and_using_for_verification_commands() {
+            assert_command(
+                "Verify 1: It worked",
+                "Verify 1",
+                "It worked",
+                RoswaalTestSyntaxCommand::Verify { label: "1" },
+            );
+            assert_command(
+                "  verify  : it worked",
+                "  verify  ",
+                "it worked",
+                RoswaalTestSyntaxCommand::Verify { label: "" },
+            );
+            assert_command(
+                "Using 1: Check the screen",
+                "Using 1",
+                "Check the screen",
+                RoswaalTestSyntaxCommand::Using { label: "1" },
+            );
+            assert_command(
+                " using one: weird  ",
+                " using one",
+                "weird",
+                RoswaalTestSyntaxCommand::Using { label: "one" },
+            )
+        }
+
+        #[test]
+        fn test_from_string_returns_unquoted_labels_for_quoted_label_commands() {
+            assert_command(
+                "Step \"sign in: email\": Sign in with my email",
+                "Step \"sign in: email\"",
+                "Sign in with my email",
+                RoswaalTestSyntaxCommand::Step {
+                    label: "sign in: email",
+                    retries: None,
+                },
+            );
+            assert_command(
+                " requirement  \" sign in flow \" : Tap sign in",
+                " requirement  \" sign in flow \" ",
+                "Tap sign in",
+                RoswaalTestSyntaxCommand::Requirement {
+                    label: "sign in flow",
+                },
+            );
+            assert_command(
+                "Verify \"1\": It worked",
+                "Verify \"1\"",
+                "It worked",
+                RoswaalTestSyntaxCommand::Verify { label: "1" },
+            );
+            assert_command(
+                "Step \"unclosed: label",
+                "Step \"unclosed",
+                "label",
+                RoswaalTestSyntaxCommand::Step {
+                    label: "\"unclosed",
+                    retries: None,
+                },
+            );
+            assert_command(
+                "Step 1: Say \"hello: world\"",
+                "Step 1",
+                "Say \"hello: world\"",
+                RoswaalTestSyntaxCommand::Step {
+                    label: "1",
+                    retries: None,
+                },
+            )
+        }
+
+        #[test]
+        fn test_from_string_returns_before_launch_for_before_launch_command() {
+            fn assert_before_launch(line: &str, name: &str, description: &str) {
+                assert_command(
+                    line,
+                    name,
+                    description,
+                    RoswaalTestSyntaxCommand::BeforeLaunch,
+                )
+            }
+
+            assert_before_launch(
+                "Before Launch: reset permissions",
+                "Before Launch",
+                "reset permissions",
+            );
+            assert_before_launch(" before   launch : weird  ", " before   launch ", "weird")
+        }
+
+        #[test]
+        fn test_from_string_returns_requires_test_for_requires_test_command() {
+            fn assert_requires_test(line: &str, name: &str, test_name: &str) {
+                assert_command(
+                    line,
+                    name,
+                    test_name,
+                    RoswaalTestSyntaxCommand::RequiresTest { test_name },
+                )
+            }
+
+            assert_requires_test("Requires Test: Sign in", "Requires Test", "Sign in");
+            assert_requires_test("require tests:  Sign in ", "require tests", "Sign in");
+            assert_requires_test(" REQUIRES   TEST : Join", " REQUIRES   TEST ", "Join")
+        }
+
         #[test]
         fn test_from_string_returns_abstract_for_abstract_command() {
             fn assert_abstract(line: &str, name: &str, description: &str) {
@@ -442,7 +1062,9 @@ Requirement 1: Awesome"
         use std::str::FromStr;
 
         use crate::{
-            language::ast::{RoswaalTestSyntaxLine, RoswaalTestSyntaxLineContent},
+            language::ast::{
+                RoswaalTestSyntaxLine, RoswaalTestSyntaxLineContent, RoswaalTestSyntaxSpan,
+            },
             location::name::RoswaalLocationName,
         };
 
@@ -473,6 +1095,43 @@ Requirement 2: Make sure that \"even Krillin\" can't be stopped by the dreadful
             assert_eq!(syntax.last_line_number(), 5)
         }
 
+        #[test]
+        fn test_lines_iterator_tracks_last_line_number_including_empty_lines() {
+            let syntax = RoswaalTestSyntax::from("New Test: Hello\n\nStep 1: A\n\n");
+            let mut lines = syntax.lines();
+            assert_eq!(lines.last_line_number(), 1);
+            assert_eq!(lines.next().map(|l| l.line_number()), Some(1));
+            assert_eq!(lines.next().map(|l| l.line_number()), Some(3));
+            assert_eq!(lines.next(), None);
+            assert_eq!(lines.last_line_number(), syntax.last_line_number())
+        }
+
+        #[test]
+        fn test_line_spans_skip_surrounding_whitespace_and_count_characters() {
+            let syntax = RoswaalTestSyntax::from("New Test: Café\r\n  Step 1: Ünïcode  \n");
+            let spans = syntax
+                .lines()
+                .map(|line| line.span().clone())
+                .collect::<Vec<RoswaalTestSyntaxSpan>>();
+            assert_eq!(
+                spans,
+                vec![
+                    RoswaalTestSyntaxSpan::new(1, 0..15, 1..15),
+                    RoswaalTestSyntaxSpan::new(2, 19..36, 3..18)
+                ]
+            );
+            let source_code = syntax.source_code();
+            assert_eq!(
+                &source_code[spans[1].byte_range().clone()],
+                "Step 1: Ünïcode"
+            );
+            assert_eq!(syntax.line_span(2), spans[1]);
+            assert_eq!(
+                syntax.line_span(3),
+                RoswaalTestSyntaxSpan::new(3, 39..39, 1..1)
+            )
+        }
+
         #[test]
         fn test_token_lines_iterator() {
             let test = "\
@@ -491,7 +1150,7 @@ Requirement 2: Do the other thing
                 tokens,
                 vec!(
                     RoswaalTestSyntaxLine {
-                        line_number: 1,
+                        span: RoswaalTestSyntaxSpan::new(1, 0..24, 1..25),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "New Test",
                             description: "Something cool",
@@ -499,23 +1158,29 @@ Requirement 2: Do the other thing
                         }
                     },
                     RoswaalTestSyntaxLine {
-                        line_number: 2,
+                        span: RoswaalTestSyntaxSpan::new(2, 25..45, 1..21),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "Step 1",
                             description: "Write a step",
-                            command: RoswaalTestSyntaxCommand::Step { label: "1" }
+                            command: RoswaalTestSyntaxCommand::Step {
+                                label: "1",
+                                retries: None
+                            }
                         }
                     },
                     RoswaalTestSyntaxLine {
-                        line_number: 3,
+                        span: RoswaalTestSyntaxSpan::new(3, 46..66, 1..21),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "Step 2",
                             description: "Another step",
-                            command: RoswaalTestSyntaxCommand::Step { label: "2" }
+                            command: RoswaalTestSyntaxCommand::Step {
+                                label: "2",
+                                retries: None
+                            }
                         }
                     },
                     RoswaalTestSyntaxLine {
-                        line_number: 4,
+                        span: RoswaalTestSyntaxSpan::new(4, 67..87, 1..21),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "Set Location",
                             description: "Europe",
@@ -525,7 +1190,7 @@ Requirement 2: Do the other thing
                         }
                     },
                     RoswaalTestSyntaxLine {
-                        line_number: 5,
+                        span: RoswaalTestSyntaxSpan::new(5, 88..100, 1..13),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "Big",
                             description: "chungus",
@@ -533,7 +1198,7 @@ Requirement 2: Do the other thing
                         }
                     },
                     RoswaalTestSyntaxLine {
-                        line_number: 7,
+                        span: RoswaalTestSyntaxSpan::new(7, 102..129, 1..28),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "Requirement 1",
                             description: "Do the thing",
@@ -541,7 +1206,7 @@ Requirement 2: Do the other thing
                         }
                     },
                     RoswaalTestSyntaxLine {
-                        line_number: 8,
+                        span: RoswaalTestSyntaxSpan::new(8, 130..163, 1..34),
                         content: RoswaalTestSyntaxLineContent::Command {
                             name: "Requirement 2",
                             description: "Do the other thing",
@@ -552,4 +1217,28 @@ Requirement 2: Do the other thing
             )
         }
     }
+
+    #[cfg(test)]
+    mod suggested_command_name_tests {
+        use crate::language::ast::suggested_command_name;
+
+        #[test]
+        fn test_suggests_closest_command_name_ignoring_labels_and_case() {
+            assert_eq!(suggested_command_name("Steb 1"), Some("Step"));
+            assert_eq!(
+                suggested_command_name("requirment one"),
+                Some("Requirement")
+            );
+            assert_eq!(suggested_command_name("Set Locaton"), Some("Set Location"));
+            assert_eq!(suggested_command_name("  NEW  TETS "), Some("New Test"));
+            assert_eq!(suggested_command_name("Sute"), Some("Suite"))
+        }
+
+        #[test]
+        fn test_does_not_suggest_command_names_that_are_not_close() {
+            assert_eq!(suggested_command_name("Big"), None);
+            assert_eq!(suggested_command_name(""), None);
+            assert_eq!(suggested_command_name("Hello world"), None)
+        }
+    }
 }
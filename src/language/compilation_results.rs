@@ -1,8 +1,9 @@
-use crate::location::name::RoswaalLocationName;
+use crate::location::{alias::RoswaalLocationAlias, name::RoswaalLocationName};
 
 use super::{
     ast::RoswaalTestSyntax,
     compiler::{RoswaalCompilationError, RoswaalCompile, RoswaalCompileContext},
+    dependencies::RoswaalTestDependencyGraph,
     test::RoswaalCompiledTest,
 };
 
@@ -10,6 +11,7 @@ use super::{
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RoswaalTestCompilationResults<'a> {
     results: Vec<(
+        usize,
         Result<RoswaalCompiledTest, Vec<RoswaalCompilationError>>,
         RoswaalTestSyntax<'a>,
     )>,
@@ -17,17 +19,68 @@ pub struct RoswaalTestCompilationResults<'a> {
 
 impl<'a> RoswaalTestCompilationResults<'a> {
     pub fn compile(
-        syntax: &Vec<RoswaalTestSyntax<'a>>,
+        syntax: impl IntoIterator<Item = RoswaalTestSyntax<'a>>,
         location_names: &Vec<RoswaalLocationName>,
+    ) -> Self {
+        Self::compile_with_test_dependencies(
+            syntax,
+            location_names,
+            &vec![],
+            &RoswaalTestDependencyGraph::default(),
+            false,
+        )
+    }
+
+    /// Compiles each test in `syntax` with `test_dependencies` as the tests that it can require.
+    ///
+    /// Each test is compiled as soon as `syntax` yields it, so a lazy iterator (eg.
+    /// `ast::tests_syntax`) never materializes the syntax of the whole submission up front. The
+    /// syntax of each test is moved into its result rather than copied.
+    ///
+    /// Locations can be set through any of `location_aliases`. See
+    /// `RoswaalCompileContext::with_location_aliases`.
+    ///
+    /// The step labels of each test are renumbered when `normalizes_step_labels` is true. See
+    /// `RoswaalCompileContext::with_step_label_normalization`.
+    pub fn compile_with_test_dependencies(
+        syntax: impl IntoIterator<Item = RoswaalTestSyntax<'a>>,
+        location_names: &Vec<RoswaalLocationName>,
+        location_aliases: &Vec<RoswaalLocationAlias>,
+        test_dependencies: &RoswaalTestDependencyGraph,
+        normalizes_step_labels: bool,
     ) -> Self {
         Self {
             results: syntax
+                .into_iter()
+                .enumerate()
+                .map(|(i, syntax)| {
+                    let mut compile_context = RoswaalCompileContext::new(location_names)
+                        .with_location_aliases(location_aliases)
+                        .with_test_dependencies(test_dependencies);
+                    if normalizes_step_labels {
+                        compile_context = compile_context.with_step_label_normalization();
+                    }
+                    let result = RoswaalCompiledTest::compile_syntax(&syntax, compile_context)
+                        .map(|test| test.with_source_code(syntax.source_code()));
+                    (i, result, syntax)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns these results without the compiled tests that have any of the specified names.
+    ///
+    /// Tests that did not compile are kept, and keep their original test numbers.
+    pub fn without_tests_named(&self, names: &[String]) -> Self {
+        Self {
+            results: self
+                .results
                 .iter()
-                .map(|syntax| {
-                    let compile_context = RoswaalCompileContext::new(&location_names);
-                    let result = RoswaalCompiledTest::compile_syntax(syntax, compile_context);
-                    (result, syntax.clone())
+                .filter(|r| match &r.1 {
+                    Ok(test) => !names.iter().any(|name| name == test.name()),
+                    Err(_) => true,
                 })
+                .cloned()
                 .collect(),
         }
     }
@@ -37,15 +90,15 @@ impl<'a> RoswaalTestCompilationResults<'a> {
     pub fn tests(&self) -> Vec<RoswaalCompiledTest> {
         self.results
             .iter()
-            .filter_map(|r| r.0.clone().ok())
+            .filter_map(|r| r.1.clone().ok())
             .collect()
     }
 
     pub fn tests_with_syntax(&self) -> Vec<(RoswaalCompiledTest, RoswaalTestSyntax<'a>)> {
         self.results
             .iter()
-            .filter_map(|r| match r.0.clone() {
-                Ok(test) => Some((test, r.1.clone())),
+            .filter_map(|r| match r.1.clone() {
+                Ok(test) => Some((test, r.2.clone())),
                 Err(_) => None,
             })
             .collect()
@@ -54,29 +107,28 @@ impl<'a> RoswaalTestCompilationResults<'a> {
     pub fn failures(&self) -> Vec<RoswaalTestCompilationFailure<'a>> {
         self.results
             .iter()
-            .enumerate()
-            .filter_map(|(i, r)| match r.0.clone() {
+            .filter_map(|r| match r.1.clone() {
                 Ok(_) => None,
                 Err(errors) => Some(RoswaalTestCompilationFailure {
-                    i,
+                    i: r.0,
                     errors,
-                    syntax: r.1.clone(),
+                    syntax: r.2.clone(),
                 }),
             })
             .collect()
     }
 
     pub fn has_compiling_tests(&self) -> bool {
-        self.results.iter().filter(|r| r.0.is_ok()).count() > 0
+        self.results.iter().filter(|r| r.1.is_ok()).count() > 0
     }
 
     pub fn has_non_compiling_tests(&self) -> bool {
-        self.results.iter().filter(|r| r.0.is_err()).count() > 0
+        self.results.iter().filter(|r| r.1.is_err()).count() > 0
     }
 }
 
 /// A test compilation failure.
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RoswaalTestCompilationFailure<'a> {
     i: usize,
     errors: Vec<RoswaalCompilationError>,
@@ -103,7 +155,7 @@ impl<'a> RoswaalTestCompilationFailure<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::language::ast::RoswaalTestSyntax;
+    use crate::language::ast::{tests_syntax, RoswaalTestSyntax};
 
     use super::RoswaalTestCompilationResults;
 
@@ -113,7 +165,7 @@ mod tests {
             RoswaalTestSyntax::from(""),
             RoswaalTestSyntax::from("New Test: Test\nStep 1: Test\nRequirement 1: Test"),
         ];
-        let results = RoswaalTestCompilationResults::compile(&syntax, &vec![]);
+        let results = RoswaalTestCompilationResults::compile(syntax, &vec![]);
         assert_eq!(results.tests().len(), 1);
         assert_eq!(results.tests()[0].name(), "Test");
         assert_eq!(results.failures().len(), 1);
@@ -127,15 +179,45 @@ mod tests {
         let syntax = vec![RoswaalTestSyntax::from(
             "New Test: Test\nStep 1: Test\nRequirement 1: Test",
         )];
-        let results = RoswaalTestCompilationResults::compile(&syntax, &vec![]);
+        let results = RoswaalTestCompilationResults::compile(syntax, &vec![]);
         assert!(results.has_compiling_tests());
         assert!(!results.has_non_compiling_tests())
     }
 
+    #[test]
+    fn without_tests_named_keeps_failure_test_numbers() {
+        let syntax = vec![
+            RoswaalTestSyntax::from("New Test: A\nStep 1: Test\nRequirement 1: Test"),
+            RoswaalTestSyntax::from("New Test: B\nStep 1: Test\nRequirement 1: Test"),
+            RoswaalTestSyntax::from(""),
+        ];
+        let results = RoswaalTestCompilationResults::compile(syntax, &vec![])
+            .without_tests_named(&["A".to_string()]);
+        assert_eq!(results.tests().len(), 1);
+        assert_eq!(results.tests()[0].name(), "B");
+        assert_eq!(results.failures()[0].test_number(), 3)
+    }
+
+    #[test]
+    fn compile_tests_syntax_as_it_is_extracted() {
+        let mut submission = String::new();
+        for i in 1..=1_000 {
+            submission.push_str(&format!(
+                "```\nNew Test: Test {}\nStep 1: A\nRequirement 1: B\n```\n",
+                i
+            ));
+        }
+        submission.push_str("```\nNew Test: Broken\n```\n");
+        let results = RoswaalTestCompilationResults::compile(tests_syntax(&submission), &vec![]);
+        assert_eq!(results.tests().len(), 1_000);
+        assert_eq!(results.tests()[999].name(), "Test 1000");
+        assert_eq!(results.failures()[0].test_number(), 1_001)
+    }
+
     #[test]
     fn compile_raw_results_all_failures() {
         let syntax = vec![RoswaalTestSyntax::from("")];
-        let results = RoswaalTestCompilationResults::compile(&syntax, &vec![]);
+        let results = RoswaalTestCompilationResults::compile(syntax, &vec![]);
         assert!(!results.has_compiling_tests());
         assert!(results.has_non_compiling_tests())
     }
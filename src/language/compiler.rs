@@ -1,10 +1,20 @@
 use std::collections::HashMap;
 
-use crate::location::name::{RoswaalLocationName, RoswaalLocationNameParsingError};
+use crate::location::{
+    alias::RoswaalLocationAlias,
+    name::{RoswaalLocationName, RoswaalLocationNameParsingError},
+};
 
 use super::{
-    ast::{RoswaalTestSyntax, RoswaalTestSyntaxCommand, RoswaalTestSyntaxLineContent},
+    ast::{
+        label_source_code, suggested_command_name, RoswaalTestSyntax, RoswaalTestSyntaxCommand,
+        RoswaalTestSyntaxLine, RoswaalTestSyntaxLineContent, RoswaalTestSyntaxSpan,
+    },
+    dependencies::RoswaalTestDependencyGraph,
+    duration::{RoswaalWaitDuration, RoswaalWaitDurationParsingError},
+    platform::RoswaalTestPlatform,
     test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+    variable::RoswaalTestVariable,
 };
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -44,31 +54,177 @@ pub enum RoswaalCompilationErrorCode {
         requirement_name: String,
         requirement_description: String,
     },
+    NoUsingVerification {
+        using_name: String,
+        using_description: String,
+    },
     UnknownLocationName(String),
     InvalidLocationName(String, RoswaalLocationNameParsingError),
-    InvalidCommandName(String),
+    InvalidWaitDuration(String, RoswaalWaitDurationParsingError),
+    UnknownPlatform(String),
+    /// A declaration in the "Variables" command that is not written as `name = value`.
+    InvalidVariable(String),
+    /// A `{{name}}` placeholder whose variable was not declared by the test.
+    UndeclaredVariable(String),
+    InvalidCommandName {
+        name: String,
+        /// The known command name that `name` is most likely a typo of.
+        did_you_mean: Option<String>,
+    },
     Duplicate {
         name: String,
         code: RoswaalCompilationDuplicateErrorCode,
     },
     TestNameAlreadyDeclared,
+    UnknownRequiredTest(String),
+    CircularTestDependency(String),
+    /// A command that runs as part of a test was found in a planned test.
+    PlannedTestCommand {
+        command_name: String,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RoswaalCompilationDuplicateErrorCode {
     StepLabel,
     RequirementLabel,
+    VerifyLabel,
+    UsingLabel,
+}
+
+/// The maximum number of characters that a step description can have before a warning is
+/// produced.
+pub const MAX_STEP_DESCRIPTION_LENGTH: usize = 100;
+
+/// A non-fatal issue found while compiling a test.
+///
+/// Unlike a `RoswaalCompilationError`, a warning does not prevent a test from compiling.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalCompilationWarning {
+    line_number: u32,
+    code: RoswaalCompilationWarningCode,
+}
+
+impl RoswaalCompilationWarning {
+    pub fn new(line_number: u32, code: RoswaalCompilationWarningCode) -> Self {
+        Self { line_number, code }
+    }
+}
+
+impl RoswaalCompilationWarning {
+    pub fn line_number(&self) -> u32 {
+        self.line_number
+    }
+
+    pub fn code(&self) -> &RoswaalCompilationWarningCode {
+        &self.code
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalCompilationWarningCode {
+    LongStepDescription {
+        step_name: String,
+        length: usize,
+    },
+    AbstractOverwritten,
+    SuiteOverwritten,
+    LocationNameCasingMismatch {
+        name: String,
+        known_name: String,
+    },
+    /// A step whose numeric label was out of sequence, or repeated the label of an earlier step,
+    /// and was renumbered to match its position in the test.
+    StepLabelRenumbered {
+        step_name: String,
+        renumbered_name: String,
+    },
+}
+
+/// An error or warning from compiling a test, along with the span of the line that it refers to.
+///
+/// Unlike the line numbers of errors and warnings, spans can be used directly by editor tooling to
+/// highlight the offending source code.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalCompilationDiagnostic {
+    span: RoswaalTestSyntaxSpan,
+    kind: RoswaalCompilationDiagnosticKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalCompilationDiagnosticKind {
+    Error(RoswaalCompilationErrorCode),
+    Warning(RoswaalCompilationWarningCode),
+}
+
+impl RoswaalCompilationDiagnostic {
+    pub fn new(span: RoswaalTestSyntaxSpan, kind: RoswaalCompilationDiagnosticKind) -> Self {
+        Self { span, kind }
+    }
+}
+
+impl RoswaalCompilationDiagnostic {
+    pub fn span(&self) -> &RoswaalTestSyntaxSpan {
+        &self.span
+    }
+
+    pub fn kind(&self) -> &RoswaalCompilationDiagnosticKind {
+        &self.kind
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.kind, RoswaalCompilationDiagnosticKind::Error(_))
+    }
+}
+
+/// The syntax lines of a test, and the result of compiling them with a span for each diagnostic.
+///
+/// See `compile_with_spans`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoswaalSpannedCompilation<'a> {
+    lines: Vec<RoswaalTestSyntaxLine<'a>>,
+    result: Result<RoswaalCompiledTest, Vec<RoswaalCompilationError>>,
+    diagnostics: Vec<RoswaalCompilationDiagnostic>,
+}
+
+impl<'a> RoswaalSpannedCompilation<'a> {
+    /// The non-empty lines of the source code, each with its span.
+    pub fn lines(&self) -> &[RoswaalTestSyntaxLine<'a>] {
+        &self.lines
+    }
+
+    pub fn result(&self) -> &Result<RoswaalCompiledTest, Vec<RoswaalCompilationError>> {
+        &self.result
+    }
+
+    /// The errors, or the warnings if the test compiled, in the order of their spans.
+    pub fn diagnostics(&self) -> &[RoswaalCompilationDiagnostic] {
+        &self.diagnostics
+    }
 }
 
 /// A struct that holds compilation information on a roswaal test script.
 pub struct RoswaalCompileContext<'a> {
     location_names: Option<&'a Vec<RoswaalLocationName>>,
+    location_aliases: Option<&'a Vec<RoswaalLocationAlias>>,
+    test_dependencies: Option<&'a RoswaalTestDependencyGraph>,
     errors: Vec<RoswaalCompilationError>,
+    warnings: Vec<RoswaalCompilationWarning>,
     test_name: Option<String>,
     test_description: Option<String>,
     matchable_steps: HashMap<String, MatchableCommandInfo>,
     matchable_requirements: HashMap<String, MatchableCommandInfo>,
+    verifications: HashMap<String, MatchableCommandInfo>,
+    usings: HashMap<String, MatchableCommandInfo>,
     commands: Vec<CompiledCommand>,
+    tags: Vec<String>,
+    suite: Option<String>,
+    platforms: Vec<RoswaalTestPlatform>,
+    variables: Vec<RoswaalTestVariable>,
+    required_tests: Vec<(u32, String)>,
+    normalizes_step_labels: bool,
+    is_planned: bool,
+    runnable_command_names: Vec<(u32, String)>,
 }
 
 impl<'a> RoswaalCompileContext<'a> {
@@ -76,12 +232,25 @@ impl<'a> RoswaalCompileContext<'a> {
     pub fn empty() -> Self {
         Self {
             location_names: None,
+            location_aliases: None,
+            test_dependencies: None,
             errors: vec![],
+            warnings: vec![],
             test_name: None,
             test_description: None,
             matchable_steps: HashMap::new(),
             matchable_requirements: HashMap::new(),
+            verifications: HashMap::new(),
+            usings: HashMap::new(),
             commands: vec![],
+            tags: vec![],
+            suite: None,
+            platforms: vec![],
+            variables: vec![],
+            required_tests: vec![],
+            normalizes_step_labels: false,
+            is_planned: false,
+            runnable_command_names: vec![],
         }
     }
 
@@ -89,14 +258,59 @@ impl<'a> RoswaalCompileContext<'a> {
     pub fn new(location_names: &'a Vec<RoswaalLocationName>) -> Self {
         Self {
             location_names: Some(location_names),
+            location_aliases: None,
+            test_dependencies: None,
             errors: vec![],
+            warnings: vec![],
             test_name: None,
             test_description: None,
             matchable_steps: HashMap::new(),
             matchable_requirements: HashMap::new(),
+            verifications: HashMap::new(),
+            usings: HashMap::new(),
             commands: vec![],
+            tags: vec![],
+            suite: None,
+            platforms: vec![],
+            variables: vec![],
+            required_tests: vec![],
+            normalizes_step_labels: false,
+            is_planned: false,
+            runnable_command_names: vec![],
         }
     }
+
+    /// Returns this context with the aliases that tests can use instead of a location name.
+    ///
+    /// A location set through an alias is compiled with the name of the location that the alias
+    /// refers to, and is unknown if that location is not in the location names of this context.
+    pub fn with_location_aliases(
+        mut self,
+        location_aliases: &'a Vec<RoswaalLocationAlias>,
+    ) -> Self {
+        self.location_aliases = Some(location_aliases);
+        self
+    }
+
+    /// Returns this context with the stored tests that the compiled test can require.
+    pub fn with_test_dependencies(
+        mut self,
+        test_dependencies: &'a RoswaalTestDependencyGraph,
+    ) -> Self {
+        self.test_dependencies = Some(test_dependencies);
+        self
+    }
+
+    /// Returns this context with step label normalization turned on.
+    ///
+    /// When turned on, steps with repeated numeric labels are paired with the requirements that
+    /// have the same label in the order that they appear instead of failing to compile. The
+    /// steps of a test with only numeric step labels are then renumbered from 1 in the order
+    /// that they appear, and a warning is produced for each step whose label changed.
+    pub fn with_step_label_normalization(mut self) -> Self {
+        self.normalizes_step_labels = true;
+        self
+    }
 }
 
 /// A trait for self-initializing by compiling roswaal test syntax.
@@ -119,7 +333,8 @@ impl RoswaalCompile for RoswaalCompiledTest {
         syntax: &RoswaalTestSyntax,
         mut ctx: RoswaalCompileContext,
     ) -> Result<Self, Vec<RoswaalCompilationError>> {
-        for line in syntax.lines() {
+        let mut lines = syntax.lines();
+        for line in lines.by_ref() {
             let line_number = line.line_number();
             match line.content() {
                 RoswaalTestSyntaxLineContent::Command {
@@ -134,12 +349,22 @@ impl RoswaalCompile for RoswaalCompiledTest {
                         ctx.append_error(line_number, code);
                         continue;
                     }
+                    if command.is_runnable() {
+                        ctx.runnable_command_names
+                            .push((line_number, name.trim().to_string()));
+                    }
                     match command {
                         RoswaalTestSyntaxCommand::NewTest => {
                             ctx.try_set_test_name(line_number, description);
                         }
+                        RoswaalTestSyntaxCommand::PlannedTest => {
+                            if ctx.test_name.is_none() {
+                                ctx.is_planned = true;
+                            }
+                            ctx.try_set_test_name(line_number, description);
+                        }
                         RoswaalTestSyntaxCommand::Abstract => {
-                            ctx.test_description = Some(description.to_string())
+                            ctx.set_test_description(line_number, description);
                         }
                         RoswaalTestSyntaxCommand::SetLocation { parse_result } => {
                             match parse_result {
@@ -155,33 +380,77 @@ impl RoswaalCompile for RoswaalCompiledTest {
                                 }
                             }
                         }
+                        RoswaalTestSyntaxCommand::Wait { parse_result } => match parse_result {
+                            Ok(duration) => ctx.append_wait(line_number, *duration),
+                            Err(err) => {
+                                let code = RoswaalCompilationErrorCode::InvalidWaitDuration(
+                                    description.to_string(),
+                                    *err,
+                                );
+                                ctx.append_error(line_number, code)
+                            }
+                        },
                         RoswaalTestSyntaxCommand::UnknownCommand => {
-                            let code =
-                                RoswaalCompilationErrorCode::InvalidCommandName(name.to_string());
+                            let code = RoswaalCompilationErrorCode::InvalidCommandName {
+                                name: name.to_string(),
+                                did_you_mean: suggested_command_name(name).map(str::to_string),
+                            };
                             ctx.append_error(line_number, code);
                         }
-                        RoswaalTestSyntaxCommand::Step { label } => {
-                            ctx.append_step(line_number, name, description, label);
+                        RoswaalTestSyntaxCommand::Step { label, retries } => {
+                            ctx.append_step(line_number, name, description, label, *retries);
                         }
                         RoswaalTestSyntaxCommand::Requirement { label } => {
                             ctx.append_requirment(line_number, name, description, label);
                         }
+                        RoswaalTestSyntaxCommand::Tags { tags } => ctx.append_tags(tags),
+                        RoswaalTestSyntaxCommand::Suite { name } => {
+                            ctx.set_suite(line_number, name);
+                        }
+                        RoswaalTestSyntaxCommand::Platforms { platforms } => {
+                            ctx.append_platforms(line_number, platforms);
+                        }
+                        RoswaalTestSyntaxCommand::Variables { variables } => {
+                            ctx.append_variables(line_number, variables);
+                        }
+                        RoswaalTestSyntaxCommand::Verify { label } => {
+                            ctx.append_verify(line_number, name, description, label);
+                        }
+                        RoswaalTestSyntaxCommand::Using { label } => {
+                            ctx.append_using(line_number, name, description, label);
+                        }
+                        RoswaalTestSyntaxCommand::BeforeLaunch => {
+                            ctx.append_before_launch(line_number, description);
+                        }
+                        RoswaalTestSyntaxCommand::RequiresTest { test_name } => {
+                            ctx.required_tests
+                                .push((line_number, test_name.to_string()));
+                        }
                     }
                 }
                 RoswaalTestSyntaxLineContent::Unknown(content) => {
-                    let code = RoswaalCompilationErrorCode::InvalidCommandName(content.to_string());
+                    let code = RoswaalCompilationErrorCode::InvalidCommandName {
+                        name: content.to_string(),
+                        did_you_mean: None,
+                    };
                     ctx.append_error(line_number, code);
                 }
             }
         }
+        ctx.append_verifications();
+        ctx.append_required_tests();
+        ctx.check_declared_variables();
+        ctx.normalize_step_labels();
         if ctx.test_name.is_none() {
             ctx.append_error(
-                syntax.last_line_number(),
+                lines.last_line_number(),
                 RoswaalCompilationErrorCode::NoTestName,
             );
-        } else if ctx.commands.is_empty() {
+        } else if ctx.is_planned {
+            ctx.reject_runnable_commands();
+        } else if ctx.commands.iter().all(|c| c.command.is_before_launch()) {
             ctx.append_error(
-                syntax.last_line_number(),
+                lines.last_line_number(),
                 RoswaalCompilationErrorCode::NoTestSteps,
             );
         }
@@ -204,29 +473,168 @@ impl RoswaalCompile for RoswaalCompiledTest {
             };
             errors.append_error(requirement_info.line_number, code);
         }
+        for using_info in ctx.usings.values().filter(|info| !info.did_match) {
+            let code = RoswaalCompilationErrorCode::NoUsingVerification {
+                using_name: using_info.name.clone(),
+                using_description: using_info.description.clone(),
+            };
+            errors.append_error(using_info.line_number, code);
+        }
 
         ctx.errors.append(&mut errors);
         ctx.finalize()
     }
 }
 
+/// Compiles `syntax` with `ctx`, and returns its syntax lines alongside the compiled test.
+///
+/// This is meant for editor tooling that needs the byte and column ranges of each diagnostic
+/// rather than only their line numbers.
+pub fn compile_with_spans<'a>(
+    syntax: &RoswaalTestSyntax<'a>,
+    ctx: RoswaalCompileContext,
+) -> RoswaalSpannedCompilation<'a> {
+    let lines = syntax.lines().collect::<Vec<RoswaalTestSyntaxLine<'a>>>();
+    let result = RoswaalCompiledTest::compile_syntax(syntax, ctx);
+    let span = |line_number: u32| {
+        lines
+            .binary_search_by_key(&line_number, |line| line.line_number())
+            .map(|index| lines[index].span().clone())
+            .unwrap_or_else(|_| syntax.line_span(line_number))
+    };
+    let mut diagnostics = match &result {
+        Ok(test) => test
+            .warnings()
+            .iter()
+            .map(|warning| {
+                RoswaalCompilationDiagnostic::new(
+                    span(warning.line_number()),
+                    RoswaalCompilationDiagnosticKind::Warning(warning.code().clone()),
+                )
+            })
+            .collect::<Vec<RoswaalCompilationDiagnostic>>(),
+        Err(errors) => errors
+            .iter()
+            .map(|error| {
+                RoswaalCompilationDiagnostic::new(
+                    span(error.line_number()),
+                    RoswaalCompilationDiagnosticKind::Error(error.code().clone()),
+                )
+            })
+            .collect(),
+    };
+    diagnostics.sort_by_key(|d| d.span().byte_range().start);
+    RoswaalSpannedCompilation {
+        lines,
+        result,
+        diagnostics,
+    }
+}
+
 impl<'a> RoswaalCompileContext<'a> {
     fn append_location(&mut self, line_number: u32, location_name: RoswaalLocationName) {
         let error_code =
             RoswaalCompilationErrorCode::UnknownLocationName(location_name.raw_name().to_string());
-        if !self
+        let known_name = self
             .location_names
-            .unwrap_or(&vec![])
-            .iter()
-            .any(|name| name.matches(&location_name))
-        {
-            self.append_error(line_number, error_code)
-        } else {
+            .and_then(|names| names.iter().find(|name| name.matches(&location_name)));
+        if let Some(known_name) = known_name {
+            if known_name.raw_name() != location_name.raw_name() {
+                self.append_warning(
+                    line_number,
+                    RoswaalCompilationWarningCode::LocationNameCasingMismatch {
+                        name: location_name.raw_name().to_string(),
+                        known_name: known_name.raw_name().to_string(),
+                    },
+                );
+            }
             let command = CompiledCommand {
                 line_number,
                 command: RoswaalCompiledTestCommand::SetLocation { location_name },
             };
             self.commands.push(command);
+        } else if let Some(aliased_name) = self.aliased_location_name(&location_name) {
+            let command = CompiledCommand {
+                line_number,
+                command: RoswaalCompiledTestCommand::SetLocation {
+                    location_name: aliased_name.clone(),
+                },
+            };
+            self.commands.push(command);
+        } else {
+            self.append_error(line_number, error_code)
+        }
+    }
+
+    fn aliased_location_name(&self, name: &RoswaalLocationName) -> Option<&'a RoswaalLocationName> {
+        let alias = self
+            .location_aliases?
+            .iter()
+            .find(|alias| alias.alias().matches(name))?;
+        self.location_names?
+            .iter()
+            .find(|name| name.matches(alias.location_name()))
+    }
+
+    fn append_wait(&mut self, line_number: u32, duration: RoswaalWaitDuration) {
+        let command = CompiledCommand {
+            line_number,
+            command: RoswaalCompiledTestCommand::Wait { duration },
+        };
+        self.commands.push(command);
+    }
+
+    fn append_before_launch(&mut self, line_number: u32, description: &str) {
+        let command = CompiledCommand {
+            line_number,
+            command: RoswaalCompiledTestCommand::BeforeLaunch {
+                name: description.to_string(),
+            },
+        };
+        self.commands.push(command);
+    }
+
+    fn append_platforms(&mut self, line_number: u32, platforms: &[&str]) {
+        for name in platforms {
+            match name.parse::<RoswaalTestPlatform>() {
+                Ok(platform) if !self.platforms.contains(&platform) => {
+                    self.platforms.push(platform)
+                }
+                Ok(_) => {}
+                Err(_) => self.append_error(
+                    line_number,
+                    RoswaalCompilationErrorCode::UnknownPlatform(name.to_string()),
+                ),
+            }
+        }
+    }
+
+    fn append_variables(&mut self, line_number: u32, variables: &[&str]) {
+        for declaration in variables {
+            let Ok(variable) = declaration.parse::<RoswaalTestVariable>() else {
+                self.append_error(
+                    line_number,
+                    RoswaalCompilationErrorCode::InvalidVariable(declaration.to_string()),
+                );
+                continue;
+            };
+            match self
+                .variables
+                .iter_mut()
+                .find(|v| v.name() == variable.name())
+            {
+                Some(declared) => *declared = variable,
+                None => self.variables.push(variable),
+            }
+        }
+    }
+
+    fn append_tags(&mut self, tags: &[&str]) {
+        for tag in tags {
+            let tag = tag.to_lowercase();
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
         }
     }
 
@@ -234,6 +642,28 @@ impl<'a> RoswaalCompileContext<'a> {
         self.errors.append_error(line_number, code)
     }
 
+    fn append_warning(&mut self, line_number: u32, code: RoswaalCompilationWarningCode) {
+        self.warnings
+            .push(RoswaalCompilationWarning { line_number, code })
+    }
+
+    fn set_test_description(&mut self, line_number: u32, description: &str) {
+        if self.test_description.is_some() {
+            self.append_warning(
+                line_number,
+                RoswaalCompilationWarningCode::AbstractOverwritten,
+            );
+        }
+        self.test_description = Some(description.to_string())
+    }
+
+    fn set_suite(&mut self, line_number: u32, name: &str) {
+        if self.suite.is_some() {
+            self.append_warning(line_number, RoswaalCompilationWarningCode::SuiteOverwritten);
+        }
+        self.suite = Some(name.to_string())
+    }
+
     fn try_set_test_name(&mut self, line_number: u32, name: &str) {
         let name = name.to_string();
         if self.test_name.is_some() {
@@ -246,8 +676,15 @@ impl<'a> RoswaalCompileContext<'a> {
         }
     }
 
-    fn append_step(&mut self, line_number: u32, name: &str, description: &str, label: &str) {
-        let label_key = label.to_string();
+    fn append_step(
+        &mut self,
+        line_number: u32,
+        name: &str,
+        description: &str,
+        label: &str,
+        retries: Option<u32>,
+    ) {
+        let label_key = self.matchable_label_key(label, &self.matchable_steps);
         if self.matchable_steps.contains_key(&label_key) {
             self.append_error(
                 line_number,
@@ -258,12 +695,23 @@ impl<'a> RoswaalCompileContext<'a> {
             );
             return;
         }
+        let length = description.chars().count();
+        if length > MAX_STEP_DESCRIPTION_LENGTH {
+            self.append_warning(
+                line_number,
+                RoswaalCompilationWarningCode::LongStepDescription {
+                    step_name: name.to_string(),
+                    length,
+                },
+            );
+        }
         let mut did_match = false;
         if let Some(requirement_info) = self.matchable_requirements.get_mut(&label_key) {
             let command = RoswaalCompiledTestCommand::Step {
                 label: step_label_name(label),
                 name: description.to_string(),
                 requirement: requirement_info.description.clone(),
+                retries,
             };
             self.commands.push(CompiledCommand {
                 line_number,
@@ -277,12 +725,13 @@ impl<'a> RoswaalCompileContext<'a> {
             name: name.to_string(),
             description: description.to_string(),
             did_match,
+            retries,
         };
         self.matchable_steps.insert(label_key, info);
     }
 
     fn append_requirment(&mut self, line_number: u32, name: &str, description: &str, label: &str) {
-        let label_key = label.to_string();
+        let label_key = self.matchable_label_key(label, &self.matchable_requirements);
         if self.matchable_requirements.contains_key(&label_key) {
             self.append_error(
                 line_number,
@@ -299,6 +748,7 @@ impl<'a> RoswaalCompileContext<'a> {
                 label: step_label_name(label),
                 name: step_info.description.clone(),
                 requirement: description.to_string(),
+                retries: step_info.retries,
             };
             self.commands.push(CompiledCommand {
                 line_number: step_info.line_number,
@@ -312,10 +762,220 @@ impl<'a> RoswaalCompileContext<'a> {
             name: name.to_string(),
             description: description.to_string(),
             did_match,
+            retries: None,
         };
         self.matchable_requirements.insert(label_key, info);
     }
 
+    fn append_verify(&mut self, line_number: u32, name: &str, description: &str, label: &str) {
+        let label_key = label.to_string();
+        if self.verifications.contains_key(&label_key) {
+            self.append_error(
+                line_number,
+                RoswaalCompilationErrorCode::Duplicate {
+                    name: name.to_string(),
+                    code: RoswaalCompilationDuplicateErrorCode::VerifyLabel,
+                },
+            );
+            return;
+        }
+        let length = description.chars().count();
+        if length > MAX_STEP_DESCRIPTION_LENGTH {
+            self.append_warning(
+                line_number,
+                RoswaalCompilationWarningCode::LongStepDescription {
+                    step_name: name.to_string(),
+                    length,
+                },
+            );
+        }
+        let info = MatchableCommandInfo {
+            line_number,
+            name: name.to_string(),
+            description: description.to_string(),
+            did_match: false,
+            retries: None,
+        };
+        self.verifications.insert(label_key, info);
+    }
+
+    fn append_using(&mut self, line_number: u32, name: &str, description: &str, label: &str) {
+        let label_key = label.to_string();
+        if self.usings.contains_key(&label_key) {
+            self.append_error(
+                line_number,
+                RoswaalCompilationErrorCode::Duplicate {
+                    name: name.to_string(),
+                    code: RoswaalCompilationDuplicateErrorCode::UsingLabel,
+                },
+            );
+            return;
+        }
+        let info = MatchableCommandInfo {
+            line_number,
+            name: name.to_string(),
+            description: description.to_string(),
+            did_match: false,
+            retries: None,
+        };
+        self.usings.insert(label_key, info);
+    }
+
+    /// Returns the key that a step or requirement with `label` is stored under in `infos`.
+    ///
+    /// When step labels are normalized, each repeat of a numeric label is given its own key so
+    /// that the nth step with a label is paired with the nth requirement with that label.
+    fn matchable_label_key(
+        &self,
+        label: &str,
+        infos: &HashMap<String, MatchableCommandInfo>,
+    ) -> String {
+        if !self.normalizes_step_labels || label.parse::<u32>().is_err() {
+            return label.to_string();
+        }
+        let mut label_key = label.to_string();
+        let mut occurrence = 1;
+        while infos.contains_key(&label_key) {
+            occurrence += 1;
+            label_key = format!("{}#{}", label, occurrence);
+        }
+        label_key
+    }
+
+    /// Renumbers the labels of the compiled steps from 1 in the order that they appear once all
+    /// lines have been read, if step label normalization is turned on and every step label is
+    /// numeric.
+    fn normalize_step_labels(&mut self) {
+        let has_only_numeric_labels = self.commands.iter().all(|c| match &c.command {
+            RoswaalCompiledTestCommand::Step { label, .. } => label
+                .strip_prefix("Step ")
+                .is_some_and(|number| number.parse::<u32>().is_ok()),
+            _ => true,
+        });
+        if !self.normalizes_step_labels || !has_only_numeric_labels {
+            return;
+        }
+        self.commands.sort_by_key(|c| c.line_number);
+        let mut step_number = 0;
+        let mut warnings = Vec::new();
+        for compiled_command in self.commands.iter_mut() {
+            let RoswaalCompiledTestCommand::Step { label, .. } = &mut compiled_command.command
+            else {
+                continue;
+            };
+            step_number += 1;
+            let renumbered_label = step_label_name(&step_number.to_string());
+            if *label != renumbered_label {
+                let code = RoswaalCompilationWarningCode::StepLabelRenumbered {
+                    step_name: std::mem::replace(label, renumbered_label.clone()),
+                    renumbered_name: renumbered_label,
+                };
+                warnings.push(RoswaalCompilationWarning {
+                    line_number: compiled_command.line_number,
+                    code,
+                });
+            }
+        }
+        self.warnings.append(&mut warnings);
+    }
+
+    /// Appends a command for each verification once all lines have been read, since the "Using"
+    /// command for a verification is optional and may appear after it.
+    fn append_verifications(&mut self) {
+        for (label, verify_info) in self.verifications.iter() {
+            let requirement = match self.usings.get_mut(label) {
+                Some(using_info) => {
+                    using_info.did_match = true;
+                    using_info.description.clone()
+                }
+                None => verify_info.description.clone(),
+            };
+            let command = RoswaalCompiledTestCommand::Verify {
+                label: verify_label_name(label),
+                name: verify_info.description.clone(),
+                requirement,
+            };
+            self.commands.push(CompiledCommand {
+                line_number: verify_info.line_number,
+                command,
+            });
+        }
+    }
+
+    /// Appends a command for each required test once all lines have been read, since a test can
+    /// only be checked for circular dependencies once its name is known.
+    fn append_required_tests(&mut self) {
+        for (line_number, name) in std::mem::take(&mut self.required_tests) {
+            let required_test = self
+                .test_dependencies
+                .and_then(|dependencies| dependencies.test(&name));
+            let Some((required_test_name, commands)) = required_test else {
+                self.append_error(
+                    line_number,
+                    RoswaalCompilationErrorCode::UnknownRequiredTest(name),
+                );
+                continue;
+            };
+            let is_circular = self.test_name.as_ref().is_some_and(|test_name| {
+                test_name.eq_ignore_ascii_case(required_test_name)
+                    || self
+                        .test_dependencies
+                        .is_some_and(|d| d.requires(required_test_name, test_name))
+            });
+            if is_circular {
+                self.append_error(
+                    line_number,
+                    RoswaalCompilationErrorCode::CircularTestDependency(
+                        required_test_name.to_string(),
+                    ),
+                );
+                continue;
+            }
+            let command = RoswaalCompiledTestCommand::RequiresTest {
+                test_name: required_test_name.to_string(),
+                commands: commands.to_vec(),
+            };
+            self.commands.push(CompiledCommand {
+                line_number,
+                command,
+            });
+        }
+    }
+
+    /// Reports each placeholder that names a variable which was not declared by the test.
+    ///
+    /// This runs once all lines have been read since variables can be declared after the steps
+    /// that use them, and required tests contain the placeholders of their own steps.
+    fn check_declared_variables(&mut self) {
+        let mut errors = Vec::new();
+        for command in self.commands.iter() {
+            for name in command.command.variable_names() {
+                if !self.variables.iter().any(|v| v.name() == name) {
+                    let code = RoswaalCompilationErrorCode::UndeclaredVariable(name.to_string());
+                    errors.append_error(command.line_number, code);
+                }
+            }
+        }
+        self.errors.append(&mut errors);
+    }
+
+    /// Reports each command that runs as part of a test as an error, since planned tests only
+    /// describe a test whose steps have not been written yet.
+    ///
+    /// The steps and requirements of a planned test are not matched with each other, so only this
+    /// error is reported for them.
+    fn reject_runnable_commands(&mut self) {
+        for (line_number, command_name) in std::mem::take(&mut self.runnable_command_names) {
+            self.append_error(
+                line_number,
+                RoswaalCompilationErrorCode::PlannedTestCommand { command_name },
+            );
+        }
+        self.matchable_steps.clear();
+        self.matchable_requirements.clear();
+        self.usings.clear();
+    }
+
     fn finalize(mut self) -> Result<RoswaalCompiledTest, Vec<RoswaalCompilationError>> {
         self.errors.sort_by_key(|e| e.line_number());
         let test_name = match self.test_name {
@@ -327,11 +987,18 @@ impl<'a> RoswaalCompileContext<'a> {
         }
         self.commands
             .sort_by(|a, b| a.line_number.cmp(&b.line_number));
+        self.warnings.sort_by_key(|w| w.line_number());
         return Ok(RoswaalCompiledTest::new(
             test_name,
             self.test_description,
             self.commands.iter().map(|c| c.command.clone()).collect(),
-        ));
+        )
+        .with_tags(self.tags)
+        .with_suite(self.suite)
+        .with_platforms(self.platforms)
+        .with_variables(self.variables)
+        .with_warnings(self.warnings)
+        .with_planned(self.is_planned));
     }
 }
 
@@ -341,6 +1008,8 @@ struct MatchableCommandInfo {
     name: String,
     description: String,
     did_match: bool,
+    /// The retries annotation of a step, which is always None for other commands.
+    retries: Option<u32>,
 }
 
 trait AppendCompililationError {
@@ -360,7 +1029,11 @@ struct CompiledCommand {
 }
 
 fn step_label_name(label: &str) -> String {
-    format!("Step {}", label)
+    format!("Step {}", label_source_code(label))
+}
+
+fn verify_label_name(label: &str) -> String {
+    format!("Verify {}", label_source_code(label))
 }
 
 #[cfg(test)]
@@ -399,7 +1072,10 @@ mod tests {
         let errors = vec![
             RoswaalCompilationError {
                 line_number: 1,
-                code: RoswaalCompilationErrorCode::InvalidCommandName(code.to_string()),
+                code: RoswaalCompilationErrorCode::InvalidCommandName {
+                    name: code.to_string(),
+                    did_you_mean: None,
+                },
             },
             RoswaalCompilationError {
                 line_number: 1,
@@ -451,9 +1127,10 @@ lsjkhadjkhasdfjkhasdjkfhkjsd
         let errors = vec![
             RoswaalCompilationError {
                 line_number: 2,
-                code: RoswaalCompilationErrorCode::InvalidCommandName(
-                    "lsjkhadjkhasdfjkhasdjkfhkjsd".to_string(),
-                ),
+                code: RoswaalCompilationErrorCode::InvalidCommandName {
+                    name: "lsjkhadjkhasdfjkhasdjkfhkjsd".to_string(),
+                    did_you_mean: None,
+                },
             },
             RoswaalCompilationError {
                 line_number: 2,
@@ -500,7 +1177,27 @@ passo 1: mamma mia
         let step_name = "passo 1".to_string();
         let error = RoswaalCompilationError {
             line_number: 2,
-            code: RoswaalCompilationErrorCode::InvalidCommandName(step_name),
+            code: RoswaalCompilationErrorCode::InvalidCommandName {
+                name: step_name,
+                did_you_mean: None,
+            },
+        };
+        assert_contains_compile_error(&result, &error);
+    }
+
+    #[test]
+    fn test_parse_returns_invalid_command_name_with_suggestion_when_command_is_misspelled() {
+        let test = "\
+new test: Hello world
+Steb 1: Do the thing
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 2,
+            code: RoswaalCompilationErrorCode::InvalidCommandName {
+                name: "Steb 1".to_string(),
+                did_you_mean: Some("Step".to_string()),
+            },
         };
         assert_contains_compile_error(&result, &error);
     }
@@ -575,22 +1272,108 @@ Requirement 1: sure, do the thing
     }
 
     #[test]
-    fn test_parse_returns_no_step_requirement_when_step_does_not_have_requirement() {
+    fn test_parse_returns_invalid_wait_duration_when_duration_is_poorly_formatted() {
         let test = "\
-New Test: I am a test
-Step 1: Jump in the air
+New test: This is an acceptance test
+Step 1: do the thing
+Wait: forever
+Wait: 0 seconds
+Wait: 10 minutes
+Requirement 1: sure, do the thing
 ";
         let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
-        let error = RoswaalCompilationError {
-            line_number: 2,
-            code: RoswaalCompilationErrorCode::NoStepRequirement {
-                step_name: "Step 1".to_string(),
-                step_description: "Jump in the air".to_string(),
-            },
-        };
-        assert_contains_compile_error(&result, &error);
-    }
-
+        let errors = [
+            (3, "forever", RoswaalWaitDurationParsingError::InvalidFormat),
+            (4, "0 seconds", RoswaalWaitDurationParsingError::Zero),
+            (5, "10 minutes", RoswaalWaitDurationParsingError::TooLong),
+        ];
+        for (line_number, description, err) in errors {
+            let error = RoswaalCompilationError {
+                line_number,
+                code: RoswaalCompilationErrorCode::InvalidWaitDuration(
+                    description.to_string(),
+                    err,
+                ),
+            };
+            assert_contains_compile_error(&result, &error);
+        }
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_steps_and_wait_commands() {
+        let test = "\
+New Test: I am waiting
+Step 1: Open the app
+Wait: 5 seconds
+Requirement 1: Launch the app
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "I am waiting".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Open the app".to_string(),
+                    requirement: "Launch the app".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Wait {
+                    duration: "5 seconds".parse().unwrap(),
+                },
+            ],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_steps_with_retries() {
+        let test = "\
+New Test: I am flaky
+Requirement 1: Tap the button
+Step 1: Open the menu (retries: 2)
+Step 2: Close the menu
+Requirement 2: Tap the close button
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "I am flaky".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Open the menu".to_string(),
+                    requirement: "Tap the button".to_string(),
+                    retries: Some(2),
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 2".to_string(),
+                    name: "Close the menu".to_string(),
+                    requirement: "Tap the close button".to_string(),
+                    retries: None,
+                },
+            ],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_no_step_requirement_when_step_does_not_have_requirement() {
+        let test = "\
+New Test: I am a test
+Step 1: Jump in the air
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 2,
+            code: RoswaalCompilationErrorCode::NoStepRequirement {
+                step_name: "Step 1".to_string(),
+                step_description: "Jump in the air".to_string(),
+            },
+        };
+        assert_contains_compile_error(&result, &error);
+    }
+
     #[test]
     fn test_parse_returns_no_requirement_step_when_requirement_does_not_have_step() {
         let test = "\
@@ -703,6 +1486,7 @@ Requirement 1: Have Piccolo charge his special-beam-cannon
                 label: "Step 1".to_string(),
                 name: "Piccolo can use special-beam-cannon".to_string(),
                 requirement: "Have Piccolo charge his special-beam-cannon".to_string(),
+                retries: None,
             }],
         );
         assert_eq!(result, expected_test)
@@ -728,11 +1512,13 @@ Requirement 2: What???
                     requirement:
                         "Have the guy dying on the floor clarify that the other guy means Saiyan"
                             .to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "I'm gonna deck you in the shnaz".to_string(),
                     requirement: "What???".to_string(),
+                    retries: None,
                 },
             ],
         );
@@ -759,11 +1545,13 @@ Requirement 1: Have the guy dying on the floor clarify that the other guy means
                     requirement:
                         "Have the guy dying on the floor clarify that the other guy means Saiyan"
                             .to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "I'm gonna deck you in the shnaz".to_string(),
                     requirement: "What???".to_string(),
+                    retries: None,
                 },
             ],
         );
@@ -790,11 +1578,13 @@ Requirement 1: Have the guy dying on the floor clarify that the other guy means
                     requirement:
                         "Have the guy dying on the floor clarify that the other guy means Saiyan"
                             .to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "I'm gonna deck you in the shnaz".to_string(),
                     requirement: "What???".to_string(),
+                    retries: None,
                 },
             ],
         );
@@ -825,6 +1615,7 @@ Requirement 1: Have the guy dying on the floor ask why he didn't block that
                     name: "Why didn't you block that".to_string(),
                     requirement: "Have the guy dying on the floor ask why he didn't block that"
                         .to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::SetLocation {
                     location_name: "New York".parse().unwrap(),
@@ -833,9 +1624,17 @@ Requirement 1: Have the guy dying on the floor ask why he didn't block that
                     label: "Step 2".to_string(),
                     name: "I thought you had it".to_string(),
                     requirement: "NAAAAHHHH".to_string(),
+                    retries: None,
                 },
             ],
-        );
+        )
+        .with_warnings(vec![RoswaalCompilationWarning::new(
+            4,
+            RoswaalCompilationWarningCode::LocationNameCasingMismatch {
+                name: "New York".to_string(),
+                known_name: "new york".to_string(),
+            },
+        )]);
         assert_eq!(result, expected_test)
     }
 
@@ -859,17 +1658,424 @@ Requirement 2: D
                     label: "Step 1".to_string(),
                     name: "A".to_string(),
                     requirement: "B".to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "C".to_string(),
                     requirement: "D".to_string(),
+                    retries: None,
                 },
             ],
-        );
+        )
+        .with_warnings(vec![RoswaalCompilationWarning::new(
+            3,
+            RoswaalCompilationWarningCode::AbstractOverwritten,
+        )]);
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_last_suite() {
+        let test = "\
+New Test: A really cool test.
+Suite: Events
+Step 1: A
+Requirement 1: B
+suite: Event Details
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+        .with_suite(Some("Event Details".to_string()))
+        .with_warnings(vec![RoswaalCompilationWarning::new(
+            5,
+            RoswaalCompilationWarningCode::SuiteOverwritten,
+        )]);
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_lowercased_deduplicated_tags() {
+        let test = "\
+New Test: A really cool test.
+Tags: Events, smoke
+Step 1: A
+Requirement 1: B
+tag: SMOKE, onboarding
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+        .with_tags(vec![
+            "events".to_string(),
+            "smoke".to_string(),
+            "onboarding".to_string(),
+        ]);
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_deduplicated_platforms() {
+        let test = "\
+New Test: A really cool test.
+Platforms: iOS, android
+Step 1: A
+Requirement 1: B
+platform: IOS
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+        .with_platforms(vec![RoswaalTestPlatform::Ios, RoswaalTestPlatform::Android]);
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_unknown_platform_for_unsupported_platforms() {
+        let test = "\
+New Test: A really cool test.
+Platforms: ios, windows phone
+Step 1: A
+Requirement 1: B
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let errors = vec![RoswaalCompilationError {
+            line_number: 2,
+            code: RoswaalCompilationErrorCode::UnknownPlatform("windows phone".to_string()),
+        }];
+        assert_eq!(result, Err(errors))
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_redeclared_variables_replacing_their_values() {
+        let test = "\
+New Test: A really cool test.
+Step 1: Join {{ eventName }}
+Requirement 1: Tap join on {{eventName}} hosted by {{host}}
+Variables: eventName = Pickup Basketball, host = Roswaal
+variable: eventName = Pickup Soccer
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Join {{ eventName }}".to_string(),
+                requirement: "Tap join on {{eventName}} hosted by {{host}}".to_string(),
+                retries: None,
+            }],
+        )
+        .with_variables(vec![
+            RoswaalTestVariable::new("eventName", "Pickup Soccer"),
+            RoswaalTestVariable::new("host", "Roswaal"),
+        ]);
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_errors_for_invalid_and_undeclared_variables() {
+        let test = "\
+New Test: A really cool test.
+Variables: eventName, host = Roswaal
+Step 1: Join {{eventName}}
+Requirement 1: Tap join
+Verify 1: {{host}} sees {{attendee}}
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let errors = vec![
+            RoswaalCompilationError {
+                line_number: 2,
+                code: RoswaalCompilationErrorCode::InvalidVariable("eventName".to_string()),
+            },
+            RoswaalCompilationError {
+                line_number: 3,
+                code: RoswaalCompilationErrorCode::UndeclaredVariable("eventName".to_string()),
+            },
+            RoswaalCompilationError {
+                line_number: 5,
+                code: RoswaalCompilationErrorCode::UndeclaredVariable("attendee".to_string()),
+            },
+        ];
+        assert_eq!(result, Err(errors))
+    }
+
+    #[test]
+    fn test_parse_renumbers_out_of_sequence_and_repeated_step_labels_when_normalizing() {
+        let test = "\
+New Test: A really cool test.
+Step 1: A
+Step 3: B
+Step 3: C
+Requirement 3: E
+Requirement 1: D
+Requirement 3: F
+";
+        let result = RoswaalCompiledTest::compile(
+            test,
+            RoswaalCompileContext::empty().with_step_label_normalization(),
+        )
+        .unwrap();
+        let step = |label: &str, name: &str, requirement: &str| RoswaalCompiledTestCommand::Step {
+            label: label.to_string(),
+            name: name.to_string(),
+            requirement: requirement.to_string(),
+            retries: None,
+        };
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![
+                step("Step 1", "A", "D"),
+                step("Step 2", "B", "E"),
+                step("Step 3", "C", "F"),
+            ],
+        )
+        .with_warnings(vec![RoswaalCompilationWarning::new(
+            3,
+            RoswaalCompilationWarningCode::StepLabelRenumbered {
+                step_name: "Step 3".to_string(),
+                renumbered_name: "Step 2".to_string(),
+            },
+        )]);
         assert_eq!(result, expected_test)
     }
 
+    #[test]
+    fn test_parse_does_not_renumber_non_numeric_step_labels_when_normalizing() {
+        let test = "\
+New Test: A really cool test.
+Step 1: A
+Step B: B
+Requirement 1: C
+Requirement B: D
+";
+        let result = RoswaalCompiledTest::compile(
+            test,
+            RoswaalCompileContext::empty().with_step_label_normalization(),
+        )
+        .unwrap();
+        let labels = result
+            .commands()
+            .iter()
+            .filter_map(|c| match c {
+                RoswaalCompiledTestCommand::Step { label, .. } => Some(label.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<&str>>();
+        assert_eq!(labels, vec!["Step 1", "Step B"]);
+        assert_eq!(result.warnings(), &[])
+    }
+
+    #[test]
+    fn test_parse_reports_repeated_step_labels_when_not_normalizing() {
+        let test = "\
+New Test: A really cool test.
+Step 1: A
+Step 1: B
+Requirement 1: C
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 3,
+            code: RoswaalCompilationErrorCode::Duplicate {
+                name: "Step 1".to_string(),
+                code: RoswaalCompilationDuplicateErrorCode::StepLabel,
+            },
+        };
+        assert_eq!(result, Err(vec![error]))
+    }
+
+    #[test]
+    fn test_compile_with_spans_locates_errors_by_line_content() {
+        let test = "\
+New Test: A really cool test.
+  Steb 1: A
+
+Requirement 1: B
+";
+        let syntax = RoswaalTestSyntax::from(test);
+        let compilation = compile_with_spans(&syntax, RoswaalCompileContext::empty());
+        assert_eq!(compilation.lines().len(), 3);
+        assert!(compilation.result().is_err());
+        let diagnostics = compilation
+            .diagnostics()
+            .iter()
+            .map(|d| (d.span().clone(), d.is_error()))
+            .collect::<Vec<(RoswaalTestSyntaxSpan, bool)>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (RoswaalTestSyntaxSpan::new(2, 32..41, 3..12), true),
+                (RoswaalTestSyntaxSpan::new(4, 43..59, 1..17), true),
+                (RoswaalTestSyntaxSpan::new(4, 43..59, 1..17), true)
+            ]
+        );
+        assert_eq!(
+            compilation.diagnostics()[0].kind(),
+            &RoswaalCompilationDiagnosticKind::Error(
+                RoswaalCompilationErrorCode::InvalidCommandName {
+                    name: "  Steb 1".to_string(),
+                    did_you_mean: Some("Step".to_string())
+                }
+            )
+        )
+    }
+
+    #[test]
+    fn test_compile_with_spans_reports_warnings_of_compiled_tests() {
+        let test = "\
+New Test: A really cool test.
+Abstract: Cool.
+Abstract: Cooler.
+Step 1: A
+Requirement 1: B
+";
+        let syntax = RoswaalTestSyntax::from(test);
+        let compilation = compile_with_spans(&syntax, RoswaalCompileContext::empty());
+        assert!(compilation.result().is_ok());
+        let expected_diagnostic = RoswaalCompilationDiagnostic::new(
+            RoswaalTestSyntaxSpan::new(3, 46..63, 1..18),
+            RoswaalCompilationDiagnosticKind::Warning(
+                RoswaalCompilationWarningCode::AbstractOverwritten,
+            ),
+        );
+        assert_eq!(compilation.diagnostics(), &[expected_diagnostic])
+    }
+
+    #[test]
+    fn test_parse_has_no_warnings_for_clean_test() {
+        let test = "\
+New Test: A really cool test.
+Abstract: Cool.
+Step 1: A
+Requirement 1: B
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        assert_eq!(result.warnings(), &[])
+    }
+
+    #[test]
+    fn test_parse_warns_for_long_step_descriptions() {
+        let long_description = "a".repeat(MAX_STEP_DESCRIPTION_LENGTH + 1);
+        let test = format!(
+            "\
+New Test: A really cool test.
+Step 1: {}
+Requirement 1: B
+Step 2: {}
+Requirement 2: C
+",
+            long_description,
+            "a".repeat(MAX_STEP_DESCRIPTION_LENGTH)
+        );
+        let result = RoswaalCompiledTest::compile(&test, RoswaalCompileContext::empty()).unwrap();
+        let warning = RoswaalCompilationWarning::new(
+            2,
+            RoswaalCompilationWarningCode::LongStepDescription {
+                step_name: "Step 1".to_string(),
+                length: MAX_STEP_DESCRIPTION_LENGTH + 1,
+            },
+        );
+        assert_eq!(result.warnings(), &[warning])
+    }
+
+    #[test]
+    fn test_parse_warns_for_location_name_casing_mismatch() {
+        let test = "\
+New Test: A really cool test.
+Set Location: new york
+Step 1: A
+Requirement 1: B
+Set Location: New York
+";
+        let location_names = vec![RoswaalLocationName::from_str("New York").unwrap()];
+        let result =
+            RoswaalCompiledTest::compile(test, RoswaalCompileContext::new(&location_names))
+                .unwrap();
+        let warning = RoswaalCompilationWarning::new(
+            2,
+            RoswaalCompilationWarningCode::LocationNameCasingMismatch {
+                name: "new york".to_string(),
+                known_name: "New York".to_string(),
+            },
+        );
+        assert_eq!(result.warnings(), &[warning])
+    }
+
+    #[test]
+    fn test_parse_sets_aliased_locations_to_the_known_location_name() {
+        let test = "\
+New Test: A really cool test.
+Set Location: sf
+Step 1: A
+Requirement 1: B
+Set Location: NYC
+";
+        let location_names = vec![RoswaalLocationName::from_str("San Francisco").unwrap()];
+        let location_aliases = vec![
+            RoswaalLocationAlias::new(
+                RoswaalLocationName::from_str("SF").unwrap(),
+                RoswaalLocationName::from_str("san francisco").unwrap(),
+            ),
+            RoswaalLocationAlias::new(
+                RoswaalLocationName::from_str("NYC").unwrap(),
+                RoswaalLocationName::from_str("New York").unwrap(),
+            ),
+        ];
+        let ctx =
+            RoswaalCompileContext::new(&location_names).with_location_aliases(&location_aliases);
+        let result = RoswaalCompiledTest::compile(test, ctx);
+        assert_eq!(
+            result,
+            Err(vec![RoswaalCompilationError {
+                line_number: 5,
+                code: RoswaalCompilationErrorCode::UnknownLocationName("NYC".to_string()),
+            }])
+        );
+        let test = "\
+New Test: A really cool test.
+Set Location: sf
+Step 1: A
+Requirement 1: B
+";
+        let ctx =
+            RoswaalCompileContext::new(&location_names).with_location_aliases(&location_aliases);
+        let test = RoswaalCompiledTest::compile(test, ctx).unwrap();
+        assert_eq!(
+            test.commands()[0],
+            RoswaalCompiledTestCommand::SetLocation {
+                location_name: location_names[0].clone()
+            }
+        );
+        assert!(test.warnings().is_empty())
+    }
+
     #[test]
     fn test_parse_errors_for_duplicate_step_and_requirement_labels() {
         let test = "\
@@ -901,6 +2107,365 @@ Requirement 1: D
         )
     }
 
+    #[test]
+    fn test_parse_pairs_quoted_step_labels_with_requirement_labels() {
+        let test = "\
+New Test: A really cool test.
+Step \"sign in: email\": A
+Step \"sign in flow\": B
+Requirement \"sign in: email\": C
+Requirement sign in flow: D
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step \"sign in: email\"".to_string(),
+                    name: "A".to_string(),
+                    requirement: "C".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step sign in flow".to_string(),
+                    name: "B".to_string(),
+                    requirement: "D".to_string(),
+                    retries: None,
+                },
+            ],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_errors_for_duplicate_quoted_and_unquoted_step_labels() {
+        let test = "\
+New Test: A really cool test.
+Step \"sign in flow\": A
+Step sign in flow: B
+Requirement sign in flow: C
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 3,
+            code: RoswaalCompilationErrorCode::Duplicate {
+                name: "Step sign in flow".to_string(),
+                code: RoswaalCompilationDuplicateErrorCode::StepLabel,
+            },
+        };
+        assert_eq!(result, Err(vec![error]))
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_verifications() {
+        let test = "\
+New Test: A really cool test.
+Step 1: A
+Verify 1: It worked
+Verify 2: It still worked
+Requirement 1: B
+Using 1: Check that the thing is on screen
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "A".to_string(),
+                    requirement: "B".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Verify {
+                    label: "Verify 1".to_string(),
+                    name: "It worked".to_string(),
+                    requirement: "Check that the thing is on screen".to_string(),
+                },
+                RoswaalCompiledTestCommand::Verify {
+                    label: "Verify 2".to_string(),
+                    name: "It still worked".to_string(),
+                    requirement: "It still worked".to_string(),
+                },
+            ],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_only_verifications() {
+        let test = "\
+New Test: A really cool test.
+Verify 1: It worked
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Verify {
+                label: "Verify 1".to_string(),
+                name: "It worked".to_string(),
+                requirement: "It worked".to_string(),
+            }],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_before_launch_commands() {
+        let test = "\
+New Test: A really cool test.
+Before Launch: Reset permissions
+Step 1: A
+Requirement 1: B
+before launch: Sign out
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "A really cool test.".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::BeforeLaunch {
+                    name: "Reset permissions".to_string(),
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "A".to_string(),
+                    requirement: "B".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::BeforeLaunch {
+                    name: "Sign out".to_string(),
+                },
+            ],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_returns_no_steps_when_test_only_has_before_launch_commands() {
+        let test = "\
+New Test: A really cool test.
+Before Launch: Reset permissions
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 2,
+            code: RoswaalCompilationErrorCode::NoTestSteps,
+        };
+        assert_contains_compile_error(&result, &error)
+    }
+
+    #[test]
+    fn test_parse_returns_test_with_required_test_commands() {
+        let sign_in_commands = vec![RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Sign in".to_string(),
+            requirement: "Sign in as Johnny".to_string(),
+            retries: None,
+        }];
+        let dependencies = RoswaalTestDependencyGraph::new(vec![(
+            "Sign in".to_string(),
+            sign_in_commands.clone(),
+        )]);
+        let test = "\
+New Test: Join an event
+Requires Test: sign in
+Step 1: A
+Requirement 1: B
+";
+        let ctx = RoswaalCompileContext::empty().with_test_dependencies(&dependencies);
+        let result = RoswaalCompiledTest::compile(test, ctx).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "Join an event".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::RequiresTest {
+                    test_name: "Sign in".to_string(),
+                    commands: sign_in_commands,
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "A".to_string(),
+                    requirement: "B".to_string(),
+                    retries: None,
+                },
+            ],
+        );
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_errors_for_unknown_required_test() {
+        let dependencies = RoswaalTestDependencyGraph::new(vec![("Sign in".to_string(), vec![])]);
+        let test = "\
+New Test: Join an event
+Requires Test: Sign up
+Step 1: A
+Requirement 1: B
+";
+        let ctx = RoswaalCompileContext::empty().with_test_dependencies(&dependencies);
+        let result = RoswaalCompiledTest::compile(test, ctx);
+        let error = RoswaalCompilationError {
+            line_number: 2,
+            code: RoswaalCompilationErrorCode::UnknownRequiredTest("Sign up".to_string()),
+        };
+        assert_eq!(result, Err(vec![error]))
+    }
+
+    #[test]
+    fn test_parse_errors_for_undeclared_variables_of_required_tests() {
+        let sign_in_commands = vec![RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Sign in as {{user}}".to_string(),
+            requirement: "Sign in".to_string(),
+            retries: None,
+        }];
+        let dependencies =
+            RoswaalTestDependencyGraph::new(vec![("Sign in".to_string(), sign_in_commands)]);
+        let test = "\
+New Test: Join an event
+Requires Test: Sign in
+Step 1: A
+Requirement 1: B
+";
+        let ctx = RoswaalCompileContext::empty().with_test_dependencies(&dependencies);
+        let result = RoswaalCompiledTest::compile(test, ctx);
+        let error = RoswaalCompilationError {
+            line_number: 2,
+            code: RoswaalCompilationErrorCode::UndeclaredVariable("user".to_string()),
+        };
+        assert_eq!(result, Err(vec![error]))
+    }
+
+    #[test]
+    fn test_parse_errors_for_circular_test_dependencies() {
+        let requires = |test_name: &str| RoswaalCompiledTestCommand::RequiresTest {
+            test_name: test_name.to_string(),
+            commands: vec![],
+        };
+        let dependencies = RoswaalTestDependencyGraph::new(vec![
+            ("Sign in".to_string(), vec![requires("Join an event")]),
+            ("Join an event".to_string(), vec![]),
+            ("Leave an event".to_string(), vec![requires("Sign in")]),
+        ]);
+        let test = "\
+New Test: Join an event
+Requires Test: Leave an event
+Requires Test: Join an event
+Step 1: A
+Requirement 1: B
+";
+        let ctx = RoswaalCompileContext::empty().with_test_dependencies(&dependencies);
+        let result = RoswaalCompiledTest::compile(test, ctx);
+        let errors = vec![
+            RoswaalCompilationError {
+                line_number: 2,
+                code: RoswaalCompilationErrorCode::CircularTestDependency(
+                    "Leave an event".to_string(),
+                ),
+            },
+            RoswaalCompilationError {
+                line_number: 3,
+                code: RoswaalCompilationErrorCode::CircularTestDependency(
+                    "Join an event".to_string(),
+                ),
+            },
+        ];
+        assert_eq!(result, Err(errors))
+    }
+
+    #[test]
+    fn test_parse_errors_for_unmatched_and_duplicate_verification_commands() {
+        let test = "\
+New Test: A really cool test.
+Verify 1: A
+Verify 1: B
+Using 2: C
+Using 1: D
+Using 1: E
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        assert_contains_compile_errors(
+            &result,
+            &vec![
+                RoswaalCompilationError {
+                    line_number: 3,
+                    code: RoswaalCompilationErrorCode::Duplicate {
+                        name: "Verify 1".to_string(),
+                        code: RoswaalCompilationDuplicateErrorCode::VerifyLabel,
+                    },
+                },
+                RoswaalCompilationError {
+                    line_number: 4,
+                    code: RoswaalCompilationErrorCode::NoUsingVerification {
+                        using_name: "Using 2".to_string(),
+                        using_description: "C".to_string(),
+                    },
+                },
+                RoswaalCompilationError {
+                    line_number: 6,
+                    code: RoswaalCompilationErrorCode::Duplicate {
+                        name: "Using 1".to_string(),
+                        code: RoswaalCompilationDuplicateErrorCode::UsingLabel,
+                    },
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_parse_returns_planned_test_without_steps() {
+        let test = "\
+Planned Test: Join an event from the map
+Abstract: Users should be able to join events without leaving the map.
+Tags: events
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty()).unwrap();
+        let expected_test = RoswaalCompiledTest::new(
+            "Join an event from the map".to_string(),
+            Some("Users should be able to join events without leaving the map.".to_string()),
+            vec![],
+        )
+        .with_tags(vec!["events".to_string()])
+        .with_planned(true);
+        assert_eq!(result, expected_test)
+    }
+
+    #[test]
+    fn test_parse_errors_for_runnable_commands_in_planned_test() {
+        let test = "\
+Planned Test: Join an event from the map
+Step 1: Tap the event
+Wait: 5 seconds
+Requirement 1: Tap the event marker
+Suite: Events
+";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let errors = vec![
+            RoswaalCompilationError {
+                line_number: 2,
+                code: RoswaalCompilationErrorCode::PlannedTestCommand {
+                    command_name: "Step 1".to_string(),
+                },
+            },
+            RoswaalCompilationError {
+                line_number: 3,
+                code: RoswaalCompilationErrorCode::PlannedTestCommand {
+                    command_name: "Wait".to_string(),
+                },
+            },
+            RoswaalCompilationError {
+                line_number: 4,
+                code: RoswaalCompilationErrorCode::PlannedTestCommand {
+                    command_name: "Requirement 1".to_string(),
+                },
+            },
+        ];
+        assert_eq!(result, Err(errors))
+    }
+
     #[test]
     fn test_compile_errors_are_sorted_by_line_number() {
         let test = "\
@@ -932,6 +2497,49 @@ Step 1: Big 2
         assert_eq!(result, Err(errors))
     }
 
+    #[test]
+    fn test_reports_no_test_steps_on_last_line_after_trailing_empty_lines() {
+        let test = "New Test: Hello\n\n\n";
+        let result = RoswaalCompiledTest::compile(test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 3,
+            code: RoswaalCompilationErrorCode::NoTestSteps,
+        };
+        assert_eq!(result, Err(vec![error]))
+    }
+
+    #[test]
+    fn test_compiles_10k_line_test() {
+        let mut test = "New Test: Large\n".to_string();
+        for i in 1..=5_000 {
+            test.push_str(&format!("Step {}: Do thing {}\n", i, i));
+        }
+        for i in 1..5_000 {
+            test.push_str(&format!("Requirement {}: Check thing {}\n", i, i));
+        }
+        let result = RoswaalCompiledTest::compile(&test, RoswaalCompileContext::empty());
+        let error = RoswaalCompilationError {
+            line_number: 5_001,
+            code: RoswaalCompilationErrorCode::NoStepRequirement {
+                step_name: "Step 5000".to_string(),
+                step_description: "Do thing 5000".to_string(),
+            },
+        };
+        assert_eq!(result, Err(vec![error]));
+        test.push_str("Requirement 5000: Check thing 5000\n");
+        let test = RoswaalCompiledTest::compile(&test, RoswaalCompileContext::empty()).unwrap();
+        assert_eq!(test.commands().len(), 5_000);
+        assert_eq!(
+            test.commands().last(),
+            Some(&RoswaalCompiledTestCommand::Step {
+                label: "Step 5000".to_string(),
+                name: "Do thing 5000".to_string(),
+                requirement: "Check thing 5000".to_string(),
+                retries: None,
+            })
+        )
+    }
+
     fn assert_contains_compile_error(
         result: &Result<RoswaalCompiledTest, Vec<RoswaalCompilationError>>,
         error: &RoswaalCompilationError,
@@ -0,0 +1,88 @@
+use super::test::RoswaalCompiledTestCommand;
+
+/// The stored tests that other tests can require, along with their commands.
+///
+/// A test requires another test by having a `RequiresTest` command in its commands.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RoswaalTestDependencyGraph {
+    tests: Vec<(String, Vec<RoswaalCompiledTestCommand>)>,
+}
+
+impl RoswaalTestDependencyGraph {
+    pub fn new(tests: Vec<(String, Vec<RoswaalCompiledTestCommand>)>) -> Self {
+        Self { tests }
+    }
+}
+
+impl RoswaalTestDependencyGraph {
+    /// Returns the name and commands of the test with the specified name ignoring casing.
+    pub fn test(&self, name: &str) -> Option<(&str, &[RoswaalCompiledTestCommand])> {
+        self.tests
+            .iter()
+            .find(|(test_name, _)| test_name.eq_ignore_ascii_case(name))
+            .map(|(test_name, commands)| (test_name.as_str(), commands.as_slice()))
+    }
+
+    /// Returns true if the test named `test_name` requires the test named `required_test_name`
+    /// either directly, or through one of the tests that it requires.
+    pub fn requires(&self, test_name: &str, required_test_name: &str) -> bool {
+        let mut visited = Vec::<&str>::new();
+        let mut pending = vec![test_name];
+        while let Some(name) = pending.pop() {
+            let Some((name, commands)) = self.test(name) else {
+                continue;
+            };
+            if visited.contains(&name) {
+                continue;
+            }
+            visited.push(name);
+            for command in commands {
+                if let RoswaalCompiledTestCommand::RequiresTest { test_name, .. } = command {
+                    if test_name.eq_ignore_ascii_case(required_test_name) {
+                        return true;
+                    }
+                    pending.push(test_name);
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_tests_ignoring_casing() {
+        let graph = RoswaalTestDependencyGraph::new(vec![("Sign in".to_string(), vec![])]);
+        assert_eq!(graph.test("sign IN").map(|(name, _)| name), Some("Sign in"));
+        assert_eq!(graph.test("Sign out"), None)
+    }
+
+    #[test]
+    fn requires_direct_and_transitive_dependencies() {
+        let graph = RoswaalTestDependencyGraph::new(vec![
+            ("Sign in".to_string(), vec![]),
+            ("Join an event".to_string(), vec![requires("Sign in")]),
+            (
+                "Leave an event".to_string(),
+                vec![requires("join an event")],
+            ),
+            ("A".to_string(), vec![requires("B")]),
+            ("B".to_string(), vec![requires("A")]),
+        ]);
+        assert!(graph.requires("Join an event", "Sign in"));
+        assert!(graph.requires("Leave an event", "Sign in"));
+        assert!(!graph.requires("Sign in", "Leave an event"));
+        assert!(!graph.requires("A", "Sign in"));
+        assert!(!graph.requires("Unknown", "Sign in"))
+    }
+
+    fn requires(test_name: &str) -> RoswaalCompiledTestCommand {
+        RoswaalCompiledTestCommand::RequiresTest {
+            test_name: test_name.to_string(),
+            commands: vec![],
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use super::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand};
+
+/// A change to a single command between 2 versions of a test.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalTestCommandChange {
+    Unchanged(RoswaalCompiledTestCommand),
+    Added(RoswaalCompiledTestCommand),
+    Removed(RoswaalCompiledTestCommand),
+}
+
+impl RoswaalTestCommandChange {
+    /// Returns a single line describing this change, prefixed in the style of a unified diff.
+    pub fn diff_line(&self) -> String {
+        match self {
+            Self::Unchanged(command) => format!("  {}", command_description(command)),
+            Self::Added(command) => format!("+ {}", command_description(command)),
+            Self::Removed(command) => format!("- {}", command_description(command)),
+        }
+    }
+}
+
+fn command_description(command: &RoswaalCompiledTestCommand) -> String {
+    match command {
+        RoswaalCompiledTestCommand::Step {
+            label,
+            name,
+            requirement,
+            retries: None,
+        } => format!("{}: {} ({})", label, name, requirement),
+        RoswaalCompiledTestCommand::Step {
+            label,
+            name,
+            requirement,
+            retries: Some(retries),
+        } => format!(
+            "{}: {} ({}) (retries: {})",
+            label, name, requirement, retries
+        ),
+        RoswaalCompiledTestCommand::SetLocation { location_name } => {
+            format!("Set Location: {}", location_name.raw_name())
+        }
+        RoswaalCompiledTestCommand::Wait { duration } => format!("Wait: {}", duration),
+        RoswaalCompiledTestCommand::BeforeLaunch { name } => format!("Before Launch: {}", name),
+        RoswaalCompiledTestCommand::RequiresTest { test_name, .. } => {
+            format!("Requires Test: {}", test_name)
+        }
+        RoswaalCompiledTestCommand::Verify {
+            label,
+            name,
+            requirement,
+        } => format!("{}: {} ({})", label, name, requirement),
+    }
+}
+
+/// The step-level differences between the stored version of a test and an edited version.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalCompiledTestDiff {
+    test_name: String,
+    old_description: Option<String>,
+    new_description: Option<String>,
+    changes: Vec<RoswaalTestCommandChange>,
+}
+
+impl RoswaalCompiledTestDiff {
+    /// Computes the diff between the old description and commands of a test, and its edited
+    /// version.
+    ///
+    /// Steps and verifications are compared by their contents rather than their labels, so
+    /// renumbering a step by inserting or removing steps before it does not count as a change.
+    pub fn new(
+        old_description: Option<&String>,
+        old_commands: &[RoswaalCompiledTestCommand],
+        edited_test: &RoswaalCompiledTest,
+    ) -> Self {
+        Self {
+            test_name: edited_test.name().to_string(),
+            old_description: old_description.cloned(),
+            new_description: edited_test.description().cloned(),
+            changes: command_changes(old_commands, edited_test.commands()),
+        }
+    }
+}
+
+impl RoswaalCompiledTestDiff {
+    pub fn test_name(&self) -> &str {
+        &self.test_name
+    }
+
+    pub fn changes(&self) -> &[RoswaalTestCommandChange] {
+        &self.changes
+    }
+
+    /// Returns the edited description if it differs from the old description.
+    pub fn changed_description(&self) -> Option<Option<&String>> {
+        if self.old_description == self.new_description {
+            None
+        } else {
+            Some(self.new_description.as_ref())
+        }
+    }
+
+    /// Returns true if either the description or any command was changed.
+    pub fn has_changes(&self) -> bool {
+        self.changed_description().is_some()
+            || self
+                .changes
+                .iter()
+                .any(|c| !matches!(c, RoswaalTestCommandChange::Unchanged(_)))
+    }
+
+    /// Returns the command changes formatted as the body of a unified diff.
+    pub fn unified_diff(&self) -> String {
+        self.changes
+            .iter()
+            .map(|c| c.diff_line())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Computes the changes between 2 command lists using their longest common subsequence.
+fn command_changes(
+    old_commands: &[RoswaalCompiledTestCommand],
+    new_commands: &[RoswaalCompiledTestCommand],
+) -> Vec<RoswaalTestCommandChange> {
+    let (old_len, new_len) = (old_commands.len(), new_commands.len());
+    let mut lcs_lengths = vec![vec![0usize; new_len + 1]; old_len + 1];
+    for i in (0..old_len).rev() {
+        for j in (0..new_len).rev() {
+            lcs_lengths[i][j] = if is_same_command(&old_commands[i], &new_commands[j]) {
+                lcs_lengths[i + 1][j + 1] + 1
+            } else {
+                lcs_lengths[i + 1][j].max(lcs_lengths[i][j + 1])
+            }
+        }
+    }
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old_len && j < new_len {
+        if is_same_command(&old_commands[i], &new_commands[j]) {
+            changes.push(RoswaalTestCommandChange::Unchanged(new_commands[j].clone()));
+            i += 1;
+            j += 1;
+        } else if lcs_lengths[i + 1][j] >= lcs_lengths[i][j + 1] {
+            changes.push(RoswaalTestCommandChange::Removed(old_commands[i].clone()));
+            i += 1;
+        } else {
+            changes.push(RoswaalTestCommandChange::Added(new_commands[j].clone()));
+            j += 1;
+        }
+    }
+    changes.extend(
+        old_commands[i..]
+            .iter()
+            .map(|c| RoswaalTestCommandChange::Removed(c.clone())),
+    );
+    changes.extend(
+        new_commands[j..]
+            .iter()
+            .map(|c| RoswaalTestCommandChange::Added(c.clone())),
+    );
+    changes
+}
+
+fn is_same_command(a: &RoswaalCompiledTestCommand, b: &RoswaalCompiledTestCommand) -> bool {
+    match (a, b) {
+        (
+            RoswaalCompiledTestCommand::Step {
+                label: _,
+                name: a_name,
+                requirement: a_requirement,
+                retries: a_retries,
+            },
+            RoswaalCompiledTestCommand::Step {
+                label: _,
+                name: b_name,
+                requirement: b_requirement,
+                retries: b_retries,
+            },
+        ) => a_name == b_name && a_requirement == b_requirement && a_retries == b_retries,
+        (
+            RoswaalCompiledTestCommand::Verify {
+                label: _,
+                name: a_name,
+                requirement: a_requirement,
+            },
+            RoswaalCompiledTestCommand::Verify {
+                label: _,
+                name: b_name,
+                requirement: b_requirement,
+            },
+        ) => a_name == b_name && a_requirement == b_requirement,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        location::name::RoswaalLocationName,
+    };
+
+    use super::{RoswaalCompiledTestDiff, RoswaalTestCommandChange};
+
+    #[test]
+    fn identical_tests_have_no_changes() {
+        let commands = vec![step(1, "A"), step(2, "B")];
+        let test = RoswaalCompiledTest::new("Test".to_string(), None, commands.clone());
+        let diff = RoswaalCompiledTestDiff::new(None, &commands, &test);
+        assert!(!diff.has_changes());
+        assert_eq!(
+            diff.changes(),
+            &[
+                RoswaalTestCommandChange::Unchanged(step(1, "A")),
+                RoswaalTestCommandChange::Unchanged(step(2, "B"))
+            ]
+        )
+    }
+
+    #[test]
+    fn inserting_a_step_does_not_change_renumbered_steps() {
+        let old_commands = vec![step(1, "A"), step(2, "C")];
+        let test = RoswaalCompiledTest::new(
+            "Test".to_string(),
+            None,
+            vec![step(1, "A"), step(2, "B"), step(3, "C")],
+        );
+        let diff = RoswaalCompiledTestDiff::new(None, &old_commands, &test);
+        assert!(diff.has_changes());
+        assert_eq!(
+            diff.changes(),
+            &[
+                RoswaalTestCommandChange::Unchanged(step(1, "A")),
+                RoswaalTestCommandChange::Added(step(2, "B")),
+                RoswaalTestCommandChange::Unchanged(step(3, "C"))
+            ]
+        )
+    }
+
+    #[test]
+    fn replaced_and_removed_commands() {
+        let location = RoswaalCompiledTestCommand::SetLocation {
+            location_name: RoswaalLocationName::from_str("Oakland").unwrap(),
+        };
+        let wait = RoswaalCompiledTestCommand::Wait {
+            duration: "5 seconds".parse().unwrap(),
+        };
+        let old_commands = vec![location.clone(), step(1, "A"), wait];
+        let test = RoswaalCompiledTest::new(
+            "Test".to_string(),
+            None,
+            vec![location.clone(), step(1, "B")],
+        );
+        let diff = RoswaalCompiledTestDiff::new(None, &old_commands, &test);
+        // NB: The leading whitespace of the first line would be eaten by a line continuation.
+        let expected_diff = "  Set Location: Oakland
+- Step 1: A (Do A)
+- Wait: 5 seconds
++ Step 1: B (Do B)";
+        assert_eq!(diff.unified_diff(), expected_diff)
+    }
+
+    #[test]
+    fn changing_retries_replaces_the_step() {
+        let mut retried_step = step(1, "A");
+        if let RoswaalCompiledTestCommand::Step { retries, .. } = &mut retried_step {
+            *retries = Some(3);
+        }
+        let test = RoswaalCompiledTest::new("Test".to_string(), None, vec![retried_step]);
+        let diff = RoswaalCompiledTestDiff::new(None, &[step(1, "A")], &test);
+        let expected_diff = "- Step 1: A (Do A)
++ Step 1: A (Do A) (retries: 3)";
+        assert_eq!(diff.unified_diff(), expected_diff)
+    }
+
+    #[test]
+    fn description_only_change() {
+        let commands = vec![step(1, "A")];
+        let test = RoswaalCompiledTest::new(
+            "Test".to_string(),
+            Some("New description".to_string()),
+            commands.clone(),
+        );
+        let old_description = "Old description".to_string();
+        let diff = RoswaalCompiledTestDiff::new(Some(&old_description), &commands, &test);
+        assert!(diff.has_changes());
+        assert_eq!(
+            diff.changed_description(),
+            Some(Some(&"New description".to_string()))
+        )
+    }
+
+    fn step(number: usize, name: &str) -> RoswaalCompiledTestCommand {
+        RoswaalCompiledTestCommand::Step {
+            label: format!("Step {}", number),
+            name: name.to_string(),
+            requirement: format!("Do {}", name),
+            retries: None,
+        }
+    }
+}
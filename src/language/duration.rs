@@ -0,0 +1,171 @@
+use std::{fmt::Display, str::FromStr};
+
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoswaalWaitDurationParsingError {
+    Empty,
+    InvalidFormat,
+    Zero,
+    TooLong,
+}
+
+pub type RoswaalWaitDurationParsingResult =
+    Result<RoswaalWaitDuration, RoswaalWaitDurationParsingError>;
+
+/// A positive amount of time that a test should wait for before running its next command.
+///
+/// Durations are written as an amount followed by a unit (eg. "5 seconds", "500ms", "1.5 min"),
+/// and cannot be longer than `RoswaalWaitDuration::MAX_MILLIS`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct RoswaalWaitDuration {
+    millis: u64,
+}
+
+impl RoswaalWaitDuration {
+    /// The longest allowed wait duration, which is 5 minutes.
+    pub const MAX_MILLIS: u64 = 5 * 60 * 1000;
+
+    pub fn millis(&self) -> u64 {
+        self.millis
+    }
+}
+
+static WAIT_DURATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    let regex = r"^(?<amount>[0-9]+(?:\.[0-9]+)?) *(?:(?<ms>ms|millis|milliseconds?)|(?<s>s|secs?|seconds?)|(?<m>m|mins?|minutes?))$";
+    RegexBuilder::new(regex)
+        .case_insensitive(true)
+        .build()
+        .expect("Failed to compile wait duration regex.")
+});
+
+impl FromStr for RoswaalWaitDuration {
+    type Err = RoswaalWaitDurationParsingError;
+
+    fn from_str(s: &str) -> RoswaalWaitDurationParsingResult {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(RoswaalWaitDurationParsingError::Empty);
+        }
+        let captures = WAIT_DURATION_REGEX
+            .captures(s)
+            .ok_or(RoswaalWaitDurationParsingError::InvalidFormat)?;
+        let amount = captures["amount"]
+            .parse::<f64>()
+            .map_err(|_| RoswaalWaitDurationParsingError::InvalidFormat)?;
+        let unit_millis = if captures.name("ms").is_some() {
+            1.0
+        } else if captures.name("s").is_some() {
+            1000.0
+        } else {
+            60.0 * 1000.0
+        };
+        let millis = (amount * unit_millis).round();
+        if millis < 1.0 {
+            Err(RoswaalWaitDurationParsingError::Zero)
+        } else if millis > Self::MAX_MILLIS as f64 {
+            Err(RoswaalWaitDurationParsingError::TooLong)
+        } else {
+            Ok(Self {
+                millis: millis as u64,
+            })
+        }
+    }
+}
+
+impl Display for RoswaalWaitDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.millis < 1000 {
+            let unit = if self.millis == 1 {
+                "millisecond"
+            } else {
+                "milliseconds"
+            };
+            write!(f, "{} {}", self.millis, unit)
+        } else if self.millis == 1000 {
+            write!(f, "1 second")
+        } else {
+            write!(f, "{} seconds", self.millis as f64 / 1000.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_returns_error_when_empty() {
+        let duration = RoswaalWaitDuration::from_str("  ");
+        assert_eq!(duration, Err(RoswaalWaitDurationParsingError::Empty))
+    }
+
+    #[test]
+    fn test_from_str_returns_error_when_invalid_format() {
+        let strings = [
+            "5",
+            "five seconds",
+            "5 hours",
+            "-5 seconds",
+            "5 seconds please",
+        ];
+        for str in strings {
+            let duration = RoswaalWaitDuration::from_str(str);
+            assert_eq!(
+                duration,
+                Err(RoswaalWaitDurationParsingError::InvalidFormat)
+            )
+        }
+    }
+
+    #[test]
+    fn test_from_str_returns_error_when_zero() {
+        let strings = ["0 seconds", "0ms", "0.0001 seconds"];
+        for str in strings {
+            let duration = RoswaalWaitDuration::from_str(str);
+            assert_eq!(duration, Err(RoswaalWaitDurationParsingError::Zero))
+        }
+    }
+
+    #[test]
+    fn test_from_str_returns_error_when_longer_than_max() {
+        let strings = ["6 minutes", "300001 ms", "5.5 mins"];
+        for str in strings {
+            let duration = RoswaalWaitDuration::from_str(str);
+            assert_eq!(duration, Err(RoswaalWaitDurationParsingError::TooLong))
+        }
+    }
+
+    #[test]
+    fn test_from_str_returns_millis_when_valid() {
+        let strings = [
+            ("5 seconds", 5000),
+            ("1 Second", 1000),
+            ("500ms", 500),
+            ("1.5 secs", 1500),
+            ("  2 min ", 120000),
+            ("5 minutes", 300000),
+        ];
+        for (str, millis) in strings {
+            let duration = RoswaalWaitDuration::from_str(str).unwrap();
+            assert_eq!(duration.millis(), millis)
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let strings = [
+            ("1ms", "1 millisecond"),
+            ("500ms", "500 milliseconds"),
+            ("1 second", "1 second"),
+            ("1.5 seconds", "1.5 seconds"),
+            ("2 minutes", "120 seconds"),
+        ];
+        for (str, display) in strings {
+            let duration = RoswaalWaitDuration::from_str(str).unwrap();
+            assert_eq!(duration.to_string(), display)
+        }
+    }
+}
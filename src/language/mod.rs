@@ -1,4 +1,10 @@
 pub mod ast;
 pub mod compilation_results;
 pub mod compiler;
+pub mod dependencies;
+pub mod diff;
+pub mod duration;
+pub mod platform;
+pub mod storage;
 pub mod test;
+pub mod variable;
@@ -0,0 +1,64 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// A platform that a test can be restricted to with the "Platforms" command.
+///
+/// A test without any platforms runs on every platform.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoswaalTestPlatform {
+    Ios,
+    Android,
+}
+
+impl RoswaalTestPlatform {
+    /// Returns the lowercased name of this platform, which is how it is written in generated code
+    /// and in the `platform` query parameter of `GET /tests`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ios => "ios",
+            Self::Android => "android",
+        }
+    }
+}
+
+impl FromStr for RoswaalTestPlatform {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "ios" => Ok(Self::Ios),
+            "android" => Ok(Self::Android),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for RoswaalTestPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ios => write!(f, "iOS"),
+            Self::Android => write!(f, "Android"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_is_case_insensitive() {
+        let cases = [
+            ("ios", Ok(RoswaalTestPlatform::Ios)),
+            (" iOS ", Ok(RoswaalTestPlatform::Ios)),
+            ("Android", Ok(RoswaalTestPlatform::Android)),
+            ("web", Err(())),
+            ("", Err(())),
+        ];
+        for (str, platform) in cases {
+            assert_eq!(RoswaalTestPlatform::from_str(str), platform, "{}", str)
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::utils::db::{
+    query::{query, query_as},
+    RoswaalDatabaseTransaction,
+};
+
+/// The maximum number of compilation failures that are kept for each user.
+///
+/// Older failures are deleted when a new failure is saved, since only the most recent attempts
+/// are useful for fixing a test.
+const MAX_STORED_COMPILATION_FAILURES_PER_USER: u32 = 20;
+
+/// The source code of a test that failed to compile, along with the messages of its compilation
+/// errors at the time that it was submitted.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalStoredCompilationFailure {
+    syntax: String,
+    error_messages: Vec<String>,
+    creation_date: DateTime<Utc>,
+}
+
+impl RoswaalStoredCompilationFailure {
+    pub fn new(syntax: &str, error_messages: Vec<String>, creation_date: DateTime<Utc>) -> Self {
+        Self {
+            syntax: syntax.to_string(),
+            error_messages,
+            creation_date,
+        }
+    }
+}
+
+impl RoswaalStoredCompilationFailure {
+    pub fn syntax(&self) -> &str {
+        &self.syntax
+    }
+
+    pub fn error_messages(&self) -> &[String] {
+        &self.error_messages
+    }
+
+    pub fn creation_date(&self) -> &DateTime<Utc> {
+        &self.creation_date
+    }
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Saves the source code and error messages of a test that the slack user with
+    /// `slack_user_id` failed to compile.
+    ///
+    /// Only the `MAX_STORED_COMPILATION_FAILURES_PER_USER` most recent failures of the user are
+    /// kept.
+    pub async fn save_compilation_failure(
+        &mut self,
+        slack_user_id: &str,
+        syntax: &str,
+        error_messages: &[String],
+    ) -> Result<()> {
+        query(statements::INSERT_COMPILATION_FAILURE)
+            .bind(slack_user_id)
+            .bind(syntax)
+            .bind(serde_json::to_string(error_messages)?)
+            .execute(self.connection())
+            .await?;
+        query(statements::DELETE_OLD_COMPILATION_FAILURES)
+            .bind(slack_user_id)
+            .bind(slack_user_id)
+            .bind(i64::from(MAX_STORED_COMPILATION_FAILURES_PER_USER))
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` of the most recent compilation failures of the slack user with
+    /// `slack_user_id`, newest first.
+    pub async fn recent_compilation_failures(
+        &mut self,
+        slack_user_id: &str,
+        limit: u32,
+    ) -> Result<Vec<RoswaalStoredCompilationFailure>> {
+        query_as::<SqliteCompilationFailure>(statements::SELECT_RECENT_COMPILATION_FAILURES)
+            .bind(slack_user_id)
+            .bind(i64::from(limit))
+            .fetch_all(self.connection())
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(RoswaalStoredCompilationFailure::new(
+                    &row.syntax,
+                    serde_json::from_str(&row.error_messages)?,
+                    row.creation_date,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteCompilationFailure {
+    syntax: String,
+    error_messages: String,
+    creation_date: DateTime<Utc>,
+}
+
+mod statements {
+    pub const INSERT_COMPILATION_FAILURE: &str = "
+INSERT INTO CompilationFailures (slack_user_id, syntax, error_messages) VALUES (?, ?, ?);
+";
+
+    pub const DELETE_OLD_COMPILATION_FAILURES: &str = "
+DELETE FROM CompilationFailures
+WHERE slack_user_id = ? AND id NOT IN (
+    SELECT id FROM CompilationFailures WHERE slack_user_id = ? ORDER BY id DESC LIMIT ?
+);
+";
+
+    pub const SELECT_RECENT_COMPILATION_FAILURES: &str = "
+SELECT syntax, error_messages, creation_date
+FROM CompilationFailures
+WHERE slack_user_id = ?
+ORDER BY id DESC
+LIMIT ?;
+";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db::RoswaalDatabase;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_most_recent_failures_of_the_user_first() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let failures = [
+            ("U1", "New Test: A", vec!["No steps".to_string()]),
+            ("U2", "New Test: B", vec!["No steps".to_string()]),
+            (
+                "U1",
+                "New Test: C\nStep 1: Thing",
+                vec!["No requirement".to_string(), "Oh no".to_string()],
+            ),
+        ];
+        for (user_id, syntax, error_messages) in failures.iter() {
+            transaction
+                .save_compilation_failure(user_id, syntax, error_messages)
+                .await
+                .unwrap();
+        }
+        let failures = transaction
+            .recent_compilation_failures("U1", 5)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.syntax().to_string(), f.error_messages().to_vec()))
+            .collect::<Vec<(String, Vec<String>)>>();
+        assert_eq!(
+            failures,
+            vec![
+                (
+                    "New Test: C\nStep 1: Thing".to_string(),
+                    vec!["No requirement".to_string(), "Oh no".to_string()]
+                ),
+                ("New Test: A".to_string(), vec!["No steps".to_string()])
+            ]
+        )
+    }
+
+    #[tokio::test]
+    async fn only_keeps_the_most_recent_failures_of_each_user() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_compilation_failure("U2", "New Test: Other", &[])
+            .await
+            .unwrap();
+        for i in 0..(MAX_STORED_COMPILATION_FAILURES_PER_USER + 5) {
+            transaction
+                .save_compilation_failure("U1", &format!("New Test: {}", i), &[])
+                .await
+                .unwrap();
+        }
+        let failures = transaction
+            .recent_compilation_failures("U1", MAX_STORED_COMPILATION_FAILURES_PER_USER + 5)
+            .await
+            .unwrap();
+        assert_eq!(
+            failures.len(),
+            MAX_STORED_COMPILATION_FAILURES_PER_USER as usize
+        );
+        assert_eq!(failures.last().unwrap().syntax(), "New Test: 5");
+        let failures = transaction
+            .recent_compilation_failures("U2", 5)
+            .await
+            .unwrap();
+        assert_eq!(failures.len(), 1)
+    }
+}
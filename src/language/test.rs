@@ -1,12 +1,26 @@
 use serde::{Deserialize, Serialize};
 
-use crate::location::name::RoswaalLocationName;
+use crate::{is_case, location::name::RoswaalLocationName, utils::dedup::DedupIterator};
+
+use super::{
+    compiler::RoswaalCompilationWarning,
+    duration::RoswaalWaitDuration,
+    platform::RoswaalTestPlatform,
+    variable::{placeholder_names, RoswaalTestVariable},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RoswaalCompiledTest {
     name: String,
     description: Option<String>,
     commands: Vec<RoswaalCompiledTestCommand>,
+    tags: Vec<String>,
+    suite: Option<String>,
+    platforms: Vec<RoswaalTestPlatform>,
+    variables: Vec<RoswaalTestVariable>,
+    warnings: Vec<RoswaalCompilationWarning>,
+    is_planned: bool,
+    source_code: Option<String>,
 }
 
 impl RoswaalCompiledTest {
@@ -19,8 +33,57 @@ impl RoswaalCompiledTest {
             name,
             description,
             commands,
+            tags: vec![],
+            suite: None,
+            platforms: vec![],
+            variables: vec![],
+            warnings: vec![],
+            is_planned: false,
+            source_code: None,
         }
     }
+
+    /// Returns this test labeled with the specified tags.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Returns this test grouped into the specified suite.
+    pub fn with_suite(mut self, suite: Option<String>) -> Self {
+        self.suite = suite;
+        self
+    }
+
+    /// Returns this test restricted to the specified platforms.
+    pub fn with_platforms(mut self, platforms: Vec<RoswaalTestPlatform>) -> Self {
+        self.platforms = platforms;
+        self
+    }
+
+    /// Returns this test with the variables that replace the placeholders in its steps.
+    pub fn with_variables(mut self, variables: Vec<RoswaalTestVariable>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Returns this test with the specified warnings that were found while compiling it.
+    pub fn with_warnings(mut self, warnings: Vec<RoswaalCompilationWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Returns this test marked as a planned test, which has no commands and no generated code.
+    pub fn with_planned(mut self, is_planned: bool) -> Self {
+        self.is_planned = is_planned;
+        self
+    }
+
+    /// Returns this test with the source code that it was compiled from.
+    pub fn with_source_code(mut self, source_code: &str) -> Self {
+        self.source_code = Some(source_code.to_string());
+        self
+    }
 }
 
 impl RoswaalCompiledTest {
@@ -35,6 +98,45 @@ impl RoswaalCompiledTest {
     pub fn description(&self) -> Option<&String> {
         self.description.as_ref()
     }
+
+    /// Returns the lowercased tags of this test in the order they were declared.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns the name of the suite that this test belongs to, if any.
+    pub fn suite(&self) -> Option<&str> {
+        self.suite.as_deref()
+    }
+
+    /// Returns the platforms that this test runs on in the order they were declared, or an empty
+    /// slice if the test runs on every platform.
+    pub fn platforms(&self) -> &[RoswaalTestPlatform] {
+        &self.platforms
+    }
+
+    /// Returns the variables declared by this test in the order they were declared.
+    pub fn variables(&self) -> &[RoswaalTestVariable] {
+        &self.variables
+    }
+
+    /// Returns the source code that this test was compiled from, if known.
+    ///
+    /// Tests compiled through `RoswaalTestCompilationResults` always have their source code.
+    pub fn source_code(&self) -> Option<&str> {
+        self.source_code.as_deref()
+    }
+
+    /// Returns the warnings found while compiling this test sorted by line number.
+    pub fn warnings(&self) -> &[RoswaalCompilationWarning] {
+        &self.warnings
+    }
+
+    /// Returns true if this test was declared with "Planned Test", and only describes a test
+    /// whose steps have not been written yet.
+    pub fn is_planned(&self) -> bool {
+        self.is_planned
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -43,8 +145,90 @@ pub enum RoswaalCompiledTestCommand {
         label: String,
         name: String,
         requirement: String,
+        /// The number of times to retry the step if it fails, if specified in the test syntax.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retries: Option<u32>,
     },
     SetLocation {
         location_name: RoswaalLocationName,
     },
+    Wait {
+        duration: RoswaalWaitDuration,
+    },
+    Verify {
+        label: String,
+        name: String,
+        requirement: String,
+    },
+    BeforeLaunch {
+        name: String,
+    },
+    /// Runs the commands of another test before the commands that come after it.
+    RequiresTest {
+        test_name: String,
+        /// The commands of the required test at the time that this command was compiled.
+        commands: Vec<RoswaalCompiledTestCommand>,
+    },
+}
+
+impl RoswaalCompiledTestCommand {
+    /// Returns true if this command runs inside the before launch command of a test rather than
+    /// as its own command.
+    pub fn is_before_launch(&self) -> bool {
+        is_case!(self, RoswaalCompiledTestCommand::BeforeLaunch)
+    }
+
+    /// Returns the names of the variables in the placeholders of this command without
+    /// duplicates, in the order that they appear.
+    ///
+    /// Only steps and verifications can contain placeholders, and a required test contains the
+    /// placeholders of its commands.
+    pub fn variable_names(&self) -> Vec<&str> {
+        match self {
+            Self::Step {
+                name, requirement, ..
+            }
+            | Self::Verify {
+                name, requirement, ..
+            } => placeholder_names(name)
+                .into_iter()
+                .chain(placeholder_names(requirement))
+                .dedup()
+                .collect(),
+            Self::RequiresTest { commands, .. } => commands
+                .iter()
+                .flat_map(|c| c.variable_names())
+                .dedup()
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns true if this command does the same thing as `other`, regardless of the labels that
+    /// are renumbered when steps are reordered and the number of times a step is retried.
+    pub fn is_same_command_as(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Step {
+                    name, requirement, ..
+                },
+                Self::Step {
+                    name: other_name,
+                    requirement: other_requirement,
+                    ..
+                },
+            )
+            | (
+                Self::Verify {
+                    name, requirement, ..
+                },
+                Self::Verify {
+                    name: other_name,
+                    requirement: other_requirement,
+                    ..
+                },
+            ) => name == other_name && requirement == other_requirement,
+            _ => self == other,
+        }
+    }
 }
@@ -0,0 +1,101 @@
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+static VARIABLE_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap());
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{ *(?<name>[^{}]*?) *\}\}").unwrap());
+
+/// A variable declared with the "Variables" command, which replaces each `{{name}}` placeholder
+/// in the steps of a test with its value when the test runs.
+///
+/// Variables are always strings in the generated code.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct RoswaalTestVariable {
+    name: String,
+    value: String,
+}
+
+impl RoswaalTestVariable {
+    pub fn new(name: &str, value: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+impl RoswaalTestVariable {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the declaration of this variable as it is written in the "Variables" command.
+    pub fn syntax(&self) -> String {
+        format!("{} = {}", self.name, self.value)
+    }
+}
+
+impl FromStr for RoswaalTestVariable {
+    type Err = ();
+
+    /// Parses a declaration written as `name = value`, where the name must be a valid identifier.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s.split_once('=').ok_or(())?;
+        let name = name.trim();
+        if !VARIABLE_NAME_REGEX.is_match(name) {
+            return Err(());
+        }
+        Ok(Self::new(name, value.trim()))
+    }
+}
+
+/// Returns the names inside of each `{{name}}` placeholder in `text` in the order that they
+/// appear, including names that are not valid variable names.
+pub fn placeholder_names(text: &str) -> Vec<&str> {
+    PLACEHOLDER_REGEX
+        .captures_iter(text)
+        .filter_map(|captures| captures.name("name"))
+        .map(|name| name.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_parses_name_and_value() {
+        let cases = [
+            (
+                "eventName = Pickup Basketball",
+                Ok(RoswaalTestVariable::new("eventName", "Pickup Basketball")),
+            ),
+            (
+                " host_1=Roswaal = Mathers ",
+                Ok(RoswaalTestVariable::new("host_1", "Roswaal = Mathers")),
+            ),
+            ("count =", Ok(RoswaalTestVariable::new("count", ""))),
+            ("eventName", Err(())),
+            ("event name = Basketball", Err(())),
+            ("1st = Basketball", Err(())),
+        ];
+        for (str, variable) in cases {
+            assert_eq!(RoswaalTestVariable::from_str(str), variable, "{}", str)
+        }
+    }
+
+    #[test]
+    fn placeholder_names_finds_each_placeholder() {
+        let names = placeholder_names("Join {{eventName}} with {{ host }} and {{}}, {not}");
+        assert_eq!(names, vec!["eventName", "host", ""])
+    }
+}
@@ -0,0 +1,19 @@
+// NB: The traits with async methods are only implemented and called by this crate and its
+// binaries, which never need to name the Send bounds of the returned futures.
+#![allow(async_fn_in_trait)]
+
+pub mod audit;
+#[cfg(feature = "e2e")]
+pub mod e2e;
+pub mod generation;
+pub mod git;
+pub mod http;
+pub mod language;
+pub mod location;
+pub mod operations;
+pub mod runner;
+pub mod scheduler;
+pub mod slack;
+pub mod stats;
+pub mod tests_data;
+pub mod utils;
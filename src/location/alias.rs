@@ -0,0 +1,31 @@
+use super::name::RoswaalLocationName;
+
+/// An alternate name that tests can use to refer to a stored location.
+///
+/// Ex. `SF` can be an alias for `San Francisco`, and tests that set their location to `SF` are
+/// compiled as if they were set to `San Francisco`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalLocationAlias {
+    alias: RoswaalLocationName,
+    location_name: RoswaalLocationName,
+}
+
+impl RoswaalLocationAlias {
+    pub fn new(alias: RoswaalLocationName, location_name: RoswaalLocationName) -> Self {
+        Self {
+            alias,
+            location_name,
+        }
+    }
+}
+
+impl RoswaalLocationAlias {
+    pub fn alias(&self) -> &RoswaalLocationName {
+        &self.alias
+    }
+
+    /// The name of the location that this alias refers to.
+    pub fn location_name(&self) -> &RoswaalLocationName {
+        &self.location_name
+    }
+}
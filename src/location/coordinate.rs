@@ -11,7 +11,7 @@ impl LocationCoordinate2D {
     ///
     /// The latitude must be in \[-90, 90\], and longitude in \[-180, 180\].
     pub fn try_new(latitude: f32, longitude: f32) -> Option<Self> {
-        if !(-90.0..90.0).contains(&latitude) || !(-180.0..180.0).contains(&longitude) {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
             None
         } else {
             Some(Self {
@@ -53,4 +53,13 @@ mod test {
             })
         )
     }
+
+    #[test]
+    fn test_coordinate_creation_includes_bounds() {
+        assert!(LocationCoordinate2D::try_new(90.0, 180.0).is_some());
+        assert!(LocationCoordinate2D::try_new(-90.0, -180.0).is_some());
+        assert_eq!(LocationCoordinate2D::try_new(122.39, 37.77), None);
+        assert_eq!(LocationCoordinate2D::try_new(f32::NAN, 0.0), None);
+        assert_eq!(LocationCoordinate2D::try_new(0.0, f32::INFINITY), None)
+    }
 }
@@ -0,0 +1,120 @@
+use serde::Serialize;
+
+use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+use super::storage::RoswaalStoredLocation;
+
+/// A GeoJSON `FeatureCollection` of stored locations, where each location is a `Point` feature.
+///
+/// GeoJSON orders the coordinate of a point as `[longitude, latitude]`, which is the reverse of
+/// the order used by the `/add-locations` command. The name, region, and unmerged branch of each
+/// location are included as the properties of its feature.
+#[derive(Debug, Serialize)]
+pub struct RoswaalLocationsGeoJson<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    features: Vec<GeoJsonFeature<'a>>,
+}
+
+impl<'a> RoswaalLocationsGeoJson<'a> {
+    pub fn new(locations: &'a [RoswaalStoredLocation]) -> Self {
+        Self {
+            kind: "FeatureCollection",
+            features: locations.iter().map(GeoJsonFeature::new).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct GeoJsonFeature<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    geometry: GeoJsonPoint,
+    properties: GeoJsonLocationProperties<'a>,
+}
+
+impl<'a> GeoJsonFeature<'a> {
+    fn new(stored_location: &'a RoswaalStoredLocation) -> Self {
+        let location = stored_location.location();
+        let coordinate = location.coordinate();
+        Self {
+            kind: "Feature",
+            geometry: GeoJsonPoint {
+                kind: "Point",
+                coordinates: [coordinate.longitude(), coordinate.latitude()],
+            },
+            properties: GeoJsonLocationProperties {
+                name: &location.name().raw_value,
+                region: location.region().map(|region| region.raw_value.as_str()),
+                unmerged_branch_name: stored_location.unmerged_branch_name(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct GeoJsonPoint {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    coordinates: [f32; 2],
+}
+
+#[derive(Debug, Serialize)]
+struct GeoJsonLocationProperties<'a> {
+    name: &'a str,
+    region: Option<&'a str>,
+    unmerged_branch_name: Option<&'a RoswaalOwnedGitBranchName>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::location::{location::RoswaalLocation, name::RoswaalLocationName};
+
+    use super::*;
+
+    #[test]
+    fn serializes_locations_as_point_features() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let region = RoswaalLocationName {
+            raw_value: "Bay Area".to_string(),
+        };
+        let locations = vec![
+            RoswaalStoredLocation::new(
+                RoswaalLocation::new_without_validation("Oakland", 37.5, -122.25)
+                    .with_region(Some(region)),
+                None,
+            ),
+            RoswaalStoredLocation::new(
+                RoswaalLocation::new_without_validation("Antarctica", -80.0, 45.5),
+                Some(branch_name.clone()),
+            ),
+        ];
+        let geojson = serde_json::to_value(RoswaalLocationsGeoJson::new(&locations)).unwrap();
+        let expected_geojson = json!({
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [-122.25, 37.5] },
+                    "properties": {
+                        "name": "Oakland",
+                        "region": "Bay Area",
+                        "unmerged_branch_name": null
+                    }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [45.5, -80.0] },
+                    "properties": {
+                        "name": "Antarctica",
+                        "region": null,
+                        "unmerged_branch_name": branch_name
+                    }
+                }
+            ]
+        });
+        assert_eq!(geojson, expected_geojson)
+    }
+}
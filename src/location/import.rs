@@ -0,0 +1,348 @@
+use anyhow::Result;
+use csv::{ReaderBuilder, Trim};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use crate::slack::file::{slack_file_id, slack_link_url, SlackFetchFile};
+
+use super::location::{RoswaalLocationLine, RoswaalStringLocations};
+
+/// An error that occurs when a locations file referenced by a roswaal locations string could not
+/// be downloaded or read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalLocationsImportError {
+    url: String,
+}
+
+impl RoswaalLocationsImportError {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl RoswaalStringLocations {
+    /// Parses a roswaal locations string where any line can also be a link to a file uploaded to
+    /// slack.
+    ///
+    /// Each file is fetched by its id, and the rows of CSV files or the waypoints of GPX files
+    /// are parsed in place of the line that linked to it. The lines of any other file are parsed
+    /// as is.
+    pub async fn from_importing_roswaal_locations_str(
+        str: &str,
+        file_fetch: &impl SlackFetchFile,
+    ) -> Result<Self, RoswaalLocationsImportError> {
+        let mut lines = Vec::<RoswaalLocationLine>::new();
+        for line in str.lines() {
+            let Some(file_id) = slack_file_id(line) else {
+                lines.push(RoswaalLocationLine::Text(line.to_string()));
+                continue;
+            };
+            let url = slack_link_url(line);
+            let format = RoswaalLocationsFileFormat::from_url(url);
+            let file_lines = file_fetch
+                .fetch_file(file_id)
+                .await
+                .and_then(|contents| format.roswaal_location_lines(&contents));
+            match file_lines {
+                Ok(file_lines) => lines.extend(file_lines),
+                Err(err) => {
+                    log::error!("Failed to import locations file at {}: {}", url, err);
+                    return Err(RoswaalLocationsImportError {
+                        url: url.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(Self::from_roswaal_location_lines(lines))
+    }
+}
+
+/// The file formats that locations can be imported from.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum RoswaalLocationsFileFormat {
+    Csv,
+    Gpx,
+    Text,
+}
+
+impl RoswaalLocationsFileFormat {
+    fn from_url(url: &str) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+        if path.ends_with(".csv") {
+            Self::Csv
+        } else if path.ends_with(".gpx") {
+            Self::Gpx
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Converts the contents of a file in this format into lines of a roswaal locations string.
+    fn roswaal_location_lines(&self, contents: &str) -> Result<Vec<RoswaalLocationLine>> {
+        match self {
+            Self::Csv => csv_location_lines(contents),
+            Self::Gpx => gpx_location_lines(contents),
+            Self::Text => Ok(contents
+                .lines()
+                .map(|line| RoswaalLocationLine::Text(line.to_string()))
+                .collect()),
+        }
+    }
+}
+
+/// Converts CSV rows into location lines.
+///
+/// The name, latitude, and longitude are read from the first 3 columns, unless the first row is
+/// a header that names them in a different order.
+fn csv_location_lines(contents: &str) -> Result<Vec<RoswaalLocationLine>> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(contents.as_bytes());
+    let mut rows = reader
+        .records()
+        .filter(|row| !matches!(row, Ok(row) if row.iter().all(str::is_empty)))
+        .peekable();
+    let mut columns = (0, 1, 2);
+    if let Some(Ok(header)) = rows.peek() {
+        if header.get(1).map(|f| f.parse::<f32>().is_err()) == Some(true) {
+            let column = |names: &[&str]| {
+                header
+                    .iter()
+                    .position(|f| names.contains(&f.to_lowercase().as_str()))
+            };
+            columns = (
+                column(&["name"]).unwrap_or(columns.0),
+                column(&["latitude", "lat"]).unwrap_or(columns.1),
+                column(&["longitude", "lon", "lng"]).unwrap_or(columns.2),
+            );
+            rows.next();
+        }
+    }
+    rows.map(|row| {
+        let row = row?;
+        let field = |index: usize| row.get(index).map(str::to_string);
+        Ok(RoswaalLocationLine::Fields {
+            name: field(columns.0).unwrap_or_default(),
+            latitude: field(columns.1),
+            longitude: field(columns.2),
+        })
+    })
+    .collect()
+}
+
+/// Converts GPX waypoints into location lines.
+fn gpx_location_lines(contents: &str) -> Result<Vec<RoswaalLocationLine>> {
+    let mut reader = Reader::from_str(contents);
+    reader.config_mut().trim_text(true);
+    let mut lines = Vec::<RoswaalLocationLine>::new();
+    // NB: The name of a waypoint is its direct <name> child, and the depth is tracked so that
+    // the names of nested elements like <link> are ignored.
+    let mut waypoint: Option<RoswaalLocationLine> = None;
+    let mut depth = 0;
+    let mut is_reading_name = false;
+    loop {
+        match reader.read_event()? {
+            Event::Start(element)
+                if waypoint.is_none() && element.local_name().as_ref() == b"wpt" =>
+            {
+                waypoint = Some(waypoint_fields(&element)?);
+                depth = 0;
+            }
+            Event::Empty(element)
+                if waypoint.is_none() && element.local_name().as_ref() == b"wpt" =>
+            {
+                lines.push(waypoint_fields(&element)?);
+            }
+            Event::Start(_) | Event::Empty(_) if waypoint.is_none() => {}
+            Event::Start(element) => {
+                depth += 1;
+                is_reading_name = depth == 1 && element.local_name().as_ref() == b"name";
+            }
+            Event::Text(text) if is_reading_name => {
+                if let Some(RoswaalLocationLine::Fields { name, .. }) = waypoint.as_mut() {
+                    name.push_str(text.unescape()?.trim());
+                }
+            }
+            Event::CData(text) if is_reading_name => {
+                if let Some(RoswaalLocationLine::Fields { name, .. }) = waypoint.as_mut() {
+                    name.push_str(String::from_utf8_lossy(&text).trim());
+                }
+            }
+            Event::End(_) if depth > 0 => {
+                depth -= 1;
+                is_reading_name = false;
+            }
+            Event::End(_) => lines.extend(waypoint.take()),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(lines)
+}
+
+/// Returns the fields of a `<wpt>` element without its name, which is read from its children.
+fn waypoint_fields(element: &BytesStart) -> Result<RoswaalLocationLine> {
+    let attribute = |name: &str| -> Result<Option<String>> {
+        match element.try_get_attribute(name)? {
+            Some(attribute) => Ok(Some(attribute.unescape_value()?.to_string())),
+            None => Ok(None),
+        }
+    };
+    Ok(RoswaalLocationLine::Fields {
+        name: String::new(),
+        latitude: attribute("lat")?,
+        longitude: attribute("lon")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        location::{
+            location::{RoswaalLocation, RoswaalLocationStringError, RoswaalStringLocations},
+            name::RoswaalLocationNameParsingError,
+        },
+        slack::file::test_support::TestSlackFileFetch,
+    };
+
+    #[tokio::test]
+    async fn test_imports_csv_rows_in_place_of_the_link() {
+        let csv =
+            "name,latitude,longitude\nNew York,45.0,45.0\n\n\"San Francisco, CA\", 12.5, 122.5\n";
+        let fetch = TestSlackFileFetch::new(&[("F0123ABCD", csv)]);
+        let str = "Antarctica, 50.0, 50.0\n<https://tif.slack.com/files/U01234567/F0123ABCD/a.csv|a.csv>\nTest, 1.0, 1.0";
+        let locations = RoswaalStringLocations::from_importing_roswaal_locations_str(str, &fetch)
+            .await
+            .unwrap();
+        let expected_locations = vec![
+            Ok(RoswaalLocation::new_without_validation(
+                "Antarctica",
+                50.0,
+                50.0,
+            )),
+            Ok(RoswaalLocation::new_without_validation(
+                "New York", 45.0, 45.0,
+            )),
+            Err(RoswaalLocationStringError::InvalidName(
+                "San Francisco, CA".to_string(),
+                RoswaalLocationNameParsingError::InvalidFormat,
+            )),
+            Ok(RoswaalLocation::new_without_validation("Test", 1.0, 1.0)),
+        ];
+        assert_eq!(locations.results(), &expected_locations)
+    }
+
+    #[tokio::test]
+    async fn test_imports_csv_columns_in_header_order_and_without_header() {
+        let fetch = TestSlackFileFetch::new(&[
+            ("F0000000A", "Lng, Lat, Name\n45.0, 40.0, New York"),
+            ("F0000000B", "Oakland, 37.8, -122.3\nOops, 1.0"),
+        ]);
+        let str = "https://files.slack.com/files-pri/T0123-F0000000A/a.CSV\nhttps://tif.slack.com/files/U01234567/F0000000B/b.csv";
+        let locations = RoswaalStringLocations::from_importing_roswaal_locations_str(str, &fetch)
+            .await
+            .unwrap();
+        let expected_locations = vec![
+            Ok(RoswaalLocation::new_without_validation(
+                "New York", 40.0, 45.0,
+            )),
+            Ok(RoswaalLocation::new_without_validation(
+                "Oakland", 37.8, -122.3,
+            )),
+            Err(RoswaalLocationStringError::InvalidCoordinate {
+                name: "Oops".to_string(),
+            }),
+        ];
+        assert_eq!(locations.results(), &expected_locations)
+    }
+
+    #[tokio::test]
+    async fn test_imports_gpx_waypoints() {
+        let gpx = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<gpx version=\"1.1\" creator=\"Test\">
+  <metadata><name>Ignored</name></metadata>
+  <wpt
+\tlat=\"37.8044\"
+\tlon=\"-122.2712\">
+    <ele>13</ele>
+    <name>Oakland</name>
+    <link href=\"https://example.com\"><text>Ignored</text></link>
+  </wpt>
+  <wpt lon='-73.9857' lat='40.7484'><name>Empire &amp; State</name></wpt>
+  <wpt lat=\"1.0\" lon=\"2.0\"/>
+</gpx>";
+        let fetch = TestSlackFileFetch::new(&[("F0123ABCD", gpx)]);
+        let locations = RoswaalStringLocations::from_importing_roswaal_locations_str(
+            "<https://tif.slack.com/files/U01234567/F0123ABCD/a.gpx>",
+            &fetch,
+        )
+        .await
+        .unwrap();
+        let expected_locations = vec![
+            Ok(RoswaalLocation::new_without_validation(
+                "Oakland", 37.8044, -122.2712,
+            )),
+            Err(RoswaalLocationStringError::InvalidName(
+                "Empire & State".to_string(),
+                RoswaalLocationNameParsingError::InvalidFormat,
+            )),
+            Err(RoswaalLocationStringError::InvalidName(
+                "".to_string(),
+                RoswaalLocationNameParsingError::Empty,
+            )),
+        ];
+        assert_eq!(locations.results(), &expected_locations)
+    }
+
+    #[tokio::test]
+    async fn test_imports_lines_of_other_slack_files_as_is() {
+        let fetch = TestSlackFileFetch::new(&[("F0123ABCD", "Oakland, 37.8, -122.3")]);
+        let locations = RoswaalStringLocations::from_importing_roswaal_locations_str(
+            "https://tif.slack.com/files/U01234567/F0123ABCD/locations.txt",
+            &fetch,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            locations.locations(),
+            vec![RoswaalLocation::new_without_validation(
+                "Oakland", 37.8, -122.3
+            )]
+        )
+    }
+
+    #[tokio::test]
+    async fn test_does_not_download_files_outside_of_slack() {
+        let fetch = TestSlackFileFetch::new(&[]);
+        let locations = RoswaalStringLocations::from_importing_roswaal_locations_str(
+            "https://example.com/files/U01234567/F0123ABCD/a.csv",
+            &fetch,
+        )
+        .await
+        .unwrap();
+        assert_eq!(locations.locations(), vec![]);
+        assert!(locations.has_errors())
+    }
+
+    #[tokio::test]
+    async fn test_returns_error_when_file_cannot_be_read() {
+        let fetch = TestSlackFileFetch::new(&[("F0000000B", "<gpx><wpt lat=\"1.0\"></gpx>")]);
+        let missing_url = "https://tif.slack.com/files/U01234567/F0000000A/a.gpx";
+        let invalid_url = "https://tif.slack.com/files/U01234567/F0000000B/b.gpx";
+        for url in [missing_url, invalid_url] {
+            let result = RoswaalStringLocations::from_importing_roswaal_locations_str(
+                &format!("Test, 1.0, 1.0\n{}", url),
+                &fetch,
+            )
+            .await;
+            assert_eq!(
+                result.map_err(|err| err.url().to_string()),
+                Err(url.to_string())
+            );
+        }
+    }
+}
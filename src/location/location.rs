@@ -1,22 +1,41 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, str::FromStr};
+
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
 
 use super::{
     coordinate::LocationCoordinate2D,
     name::{RoswaalLocationName, RoswaalLocationNameParsingError, RoswaalLocationParsingResult},
 };
 
-/// A location with a name and coordinate.
+/// A location with a name and coordinate, and optionally the name of the region that it is
+/// grouped under.
 #[derive(Debug, PartialEq, Clone)]
 pub struct RoswaalLocation {
     name: RoswaalLocationName,
     coordinate: LocationCoordinate2D,
+    region: Option<RoswaalLocationName>,
 }
 
 impl RoswaalLocation {
     pub fn new(name: RoswaalLocationName, coordinate: LocationCoordinate2D) -> Self {
-        Self { name, coordinate }
+        Self {
+            name,
+            coordinate,
+            region: None,
+        }
+    }
+
+    /// Groups this location under `region`, or removes it from its region if None.
+    pub fn with_region(self, region: Option<RoswaalLocationName>) -> Self {
+        Self { region, ..self }
     }
 
+    /// Creates a location without validating its name or coordinate.
+    ///
+    /// Only use this for values that have already been validated, such as locations loaded from
+    /// storage. User input should be parsed with `RoswaalLocation::from_str`, which rejects
+    /// coordinates that are out of range.
     pub fn new_without_validation(name: &str, latitude: f32, longitude: f32) -> Self {
         let name = RoswaalLocationName {
             raw_value: name.to_string(),
@@ -37,12 +56,60 @@ impl RoswaalLocation {
     pub fn coordinate(&self) -> LocationCoordinate2D {
         self.coordinate
     }
+
+    pub fn region(&self) -> Option<&RoswaalLocationName> {
+        self.region.as_ref()
+    }
+}
+
+/// Groups `items` by the region of their location.
+///
+/// Items without a region come first in a group with no region, and are followed by a group for
+/// each region in alphabetical order. Region names with the same `PascalCase` spelling (eg.
+/// "Bay Area" and "bay area") share a group, and items keep their relative order within each
+/// group.
+pub fn group_by_region<'a, T>(
+    items: &'a [T],
+    location: impl Fn(&'a T) -> &'a RoswaalLocation,
+) -> Vec<(Option<&'a RoswaalLocationName>, Vec<&'a T>)> {
+    let mut ungrouped = Vec::new();
+    let mut regions = BTreeMap::<String, (&RoswaalLocationName, Vec<&T>)>::new();
+    for item in items {
+        match location(item).region() {
+            Some(region) => regions
+                .entry(region.to_ascii_pascal_case_string())
+                .or_insert_with(|| (region, Vec::new()))
+                .1
+                .push(item),
+            None => ungrouped.push(item),
+        }
+    }
+    let ungrouped = Some((None, ungrouped)).filter(|(_, items)| !items.is_empty());
+    ungrouped
+        .into_iter()
+        .chain(
+            regions
+                .into_values()
+                .map(|(region, items)| (Some(region), items)),
+        )
+        .collect()
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RoswaalLocationStringError {
     InvalidName(String, RoswaalLocationNameParsingError),
-    InvalidCoordinate { name: String },
+    /// The name in a `Region:` header is not a valid name.
+    InvalidRegionName(String),
+    InvalidCoordinate {
+        name: String,
+    },
+    /// The coordinate was parsed, but the latitude is not in \[-90, 90\] or the longitude is not
+    /// in \[-180, 180\].
+    CoordinateOutOfRange {
+        name: String,
+        latitude: String,
+        longitude: String,
+    },
 }
 
 impl RoswaalLocationStringError {
@@ -50,7 +117,30 @@ impl RoswaalLocationStringError {
     pub fn raw_associated_location_name(&self) -> &str {
         match self {
             Self::InvalidName(name, _) => name,
+            Self::InvalidRegionName(name) => name,
             Self::InvalidCoordinate { name } => name,
+            Self::CoordinateOutOfRange {
+                name,
+                latitude: _,
+                longitude: _,
+            } => name,
+        }
+    }
+
+    /// Returns a short human readable description of this error.
+    pub fn reason(&self) -> String {
+        match self {
+            Self::InvalidName(_, _) => "Invalid Name".to_string(),
+            Self::InvalidRegionName(_) => "Invalid Region Name".to_string(),
+            Self::InvalidCoordinate { name: _ } => "Invalid Coordinate".to_string(),
+            Self::CoordinateOutOfRange {
+                name: _,
+                latitude,
+                longitude,
+            } => format!(
+                "Coordinate Out of Range: {}, {}. Latitude must be between -90 and 90, and longitude must be between -180 and 180",
+                latitude, longitude
+            ),
         }
     }
 }
@@ -59,57 +149,160 @@ impl FromStr for RoswaalLocation {
     type Err = RoswaalLocationStringError;
 
     fn from_str(l: &str) -> Result<Self, Self::Err> {
-        let splits = l.splitn(3, ",").collect::<Vec<&str>>();
-        let raw_name = splits[0];
+        let mut splits = l.splitn(3, ",");
+        let raw_name = splits.next().unwrap_or_default();
+        Self::from_fields(raw_name, splits.next(), splits.next())
+    }
+}
+
+impl RoswaalLocation {
+    /// Parses a location from its separate name, latitude, and longitude fields, such as the
+    /// columns of a CSV row.
+    pub fn from_fields(
+        raw_name: &str,
+        raw_latitude: Option<&str>,
+        raw_longitude: Option<&str>,
+    ) -> Result<Self, RoswaalLocationStringError> {
+        type Err = RoswaalLocationStringError;
         let name = RoswaalLocationName::from_str(raw_name);
         if let Err(err) = name {
-            return Err(Self::Err::InvalidName(raw_name.to_string(), err));
+            return Err(Err::InvalidName(raw_name.to_string(), err));
         }
-        if splits.len() < 3 {
-            return Err(Self::Err::InvalidCoordinate {
+        let (Some(raw_latitude), Some(raw_longitude)) = (raw_latitude, raw_longitude) else {
+            return Err(Err::InvalidCoordinate {
                 name: raw_name.to_string(),
             });
-        }
-        let latitude = splits[1].trim().parse::<f32>();
-        let longitude = splits[2].trim().parse::<f32>();
+        };
+        let (raw_latitude, raw_longitude) = (raw_latitude.trim(), raw_longitude.trim());
+        let latitude = raw_latitude.parse::<f32>();
+        let longitude = raw_longitude.parse::<f32>();
         match (name, latitude, longitude) {
-            (Ok(name), Ok(lat), Ok(lng)) => {
+            (Ok(name), Ok(lat), Ok(lng)) if lat.is_finite() && lng.is_finite() => {
                 if let Some(coordinate) = LocationCoordinate2D::try_new(lat, lng) {
                     Ok(RoswaalLocation::new(name, coordinate))
                 } else {
-                    Err(Self::Err::InvalidCoordinate {
+                    Err(Err::CoordinateOutOfRange {
                         name: raw_name.to_string(),
+                        latitude: raw_latitude.to_string(),
+                        longitude: raw_longitude.to_string(),
                     })
                 }
             }
-            _ => Err(Self::Err::InvalidCoordinate {
+            _ => Err(Err::InvalidCoordinate {
                 name: raw_name.to_string(),
             }),
         }
     }
 }
 
+static REGION_HEADER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    RegexBuilder::new(r"^\s*region\s*:(?<name>.*)$")
+        .case_insensitive(true)
+        .build()
+        .expect("Failed to compile region header regex.")
+});
+
 /// A type for parsing a user input string of roswaal locations.
 ///
 /// A roswaal locations string is a new line-separated string that looks like so:
-/// ```
+/// ```text
+/// <location name>, <latitude>, <longitude>
+/// Region: <region name>
 /// <location name>, <latitude>, <longitude>
 /// ```
 ///
-/// Empty lines are ignored.
+/// Locations below a `Region:` header are grouped under that region until the next header, and
+/// an empty `Region:` header ends the grouping. Empty lines are ignored.
 #[derive(Debug, PartialEq)]
 pub struct RoswaalStringLocations {
     results: Vec<Result<RoswaalLocation, RoswaalLocationStringError>>,
+    merged_locations: Vec<RoswaalLocation>,
+}
+
+/// A line of a roswaal locations string, or a location with separate fields that was imported
+/// in place of a line.
+#[derive(Debug, PartialEq, Clone)]
+pub enum RoswaalLocationLine {
+    Text(String),
+    Fields {
+        name: String,
+        latitude: Option<String>,
+        longitude: Option<String>,
+    },
 }
 
 impl RoswaalStringLocations {
     pub fn from_roswaal_locations_str(str: &str) -> Self {
-        let results = str
-            .lines()
-            .filter(|l| !l.trim().is_empty())
-            .map(RoswaalLocation::from_str)
-            .collect::<Vec<Result<RoswaalLocation, RoswaalLocationStringError>>>();
-        Self { results }
+        Self::from_roswaal_location_lines(
+            str.lines()
+                .map(|line| RoswaalLocationLine::Text(line.to_string())),
+        )
+    }
+
+    /// Parses each of `lines`, where the fields of imported locations are grouped under the
+    /// region of the `Region:` header above them like any other line.
+    pub fn from_roswaal_location_lines(
+        lines: impl IntoIterator<Item = RoswaalLocationLine>,
+    ) -> Self {
+        let mut results = Vec::new();
+        let mut region = None;
+        for line in lines {
+            let line = match line {
+                RoswaalLocationLine::Text(line) => line,
+                RoswaalLocationLine::Fields {
+                    name,
+                    latitude,
+                    longitude,
+                } => {
+                    results.push(
+                        RoswaalLocation::from_fields(
+                            &name,
+                            latitude.as_deref(),
+                            longitude.as_deref(),
+                        )
+                        .map(|location| location.with_region(region.clone())),
+                    );
+                    continue;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(captures) = REGION_HEADER_REGEX.captures(&line) {
+                let name = captures["name"].trim();
+                region = None;
+                if name.is_empty() {
+                    continue;
+                }
+                match RoswaalLocationName::from_str(name) {
+                    Ok(name) => region = Some(name),
+                    Err(_) => results.push(Err(RoswaalLocationStringError::InvalidRegionName(
+                        name.to_string(),
+                    ))),
+                }
+                continue;
+            }
+            results.push(
+                RoswaalLocation::from_str(&line)
+                    .map(|location| location.with_region(region.clone())),
+            );
+        }
+        Self {
+            results,
+            merged_locations: vec![],
+        }
+    }
+
+    /// Returns these locations compared against `merged_locations`, so that a location with the
+    /// same name as a merged location is reported as updated or unchanged instead of added.
+    pub fn with_merged_locations(mut self, merged_locations: &[&RoswaalLocation]) -> Self {
+        let locations = self.locations();
+        self.merged_locations = merged_locations
+            .iter()
+            .filter(|merged| locations.iter().any(|l| l.name() == merged.name()))
+            .map(|merged| (*merged).clone())
+            .collect();
+        self
     }
 }
 
@@ -138,6 +331,46 @@ impl RoswaalStringLocations {
     }
 }
 
+impl RoswaalStringLocations {
+    /// Returns the successfully parsed locations that do not have the name of a merged location.
+    pub fn added_locations(&self) -> Vec<RoswaalLocation> {
+        self.locations()
+            .into_iter()
+            .filter(|l| self.merged_location(l).is_none())
+            .collect()
+    }
+
+    /// Returns the successfully parsed locations that change the coordinate or region of the
+    /// merged location with the same name.
+    pub fn updated_locations(&self) -> Vec<RoswaalLocation> {
+        self.locations()
+            .into_iter()
+            .filter(|l| self.merged_location(l).is_some_and(|merged| merged != l))
+            .collect()
+    }
+
+    /// Returns the successfully parsed locations that are identical to a merged location.
+    pub fn unchanged_locations(&self) -> Vec<RoswaalLocation> {
+        self.locations()
+            .into_iter()
+            .filter(|l| self.merged_location(l) == Some(l))
+            .collect()
+    }
+
+    /// Returns true if any location is added or updated.
+    pub fn has_changes(&self) -> bool {
+        self.locations()
+            .iter()
+            .any(|l| self.merged_location(l) != Some(l))
+    }
+
+    fn merged_location(&self, location: &RoswaalLocation) -> Option<&RoswaalLocation> {
+        self.merged_locations
+            .iter()
+            .find(|merged| merged.name() == location.name())
+    }
+}
+
 impl RoswaalStringLocations {
     pub fn has_errors(&self) -> bool {
         self.results.iter().find(|r| r.is_err()).is_some()
@@ -164,9 +397,11 @@ impl RoswaalStringLocations {
 #[cfg(test)]
 mod tests {
     mod from_str_tests {
+        use std::str::FromStr;
+
         use crate::location::{
             location::{RoswaalLocation, RoswaalLocationStringError, RoswaalStringLocations},
-            name::RoswaalLocationNameParsingError,
+            name::{RoswaalLocationName, RoswaalLocationNameParsingError},
         };
 
         #[test]
@@ -219,6 +454,7 @@ New York
 Test 4, hello, 0.0
 Test 5, -80.0, world
 Test 6, -400.0, 400
+Test 7, NaN, inf
                 ";
             let locations = RoswaalStringLocations::from_roswaal_locations_str(str);
             let expected_locations = vec![
@@ -240,11 +476,111 @@ Test 6, -400.0, 400
                 Err(RoswaalLocationStringError::InvalidCoordinate {
                     name: "Test 5".to_string(),
                 }),
-                Err(RoswaalLocationStringError::InvalidCoordinate {
+                Err(RoswaalLocationStringError::CoordinateOutOfRange {
                     name: "Test 6".to_string(),
+                    latitude: "-400.0".to_string(),
+                    longitude: "400".to_string(),
+                }),
+                Err(RoswaalLocationStringError::InvalidCoordinate {
+                    name: "Test 7".to_string(),
                 }),
             ];
             assert_eq!(locations.results(), &expected_locations)
         }
+
+        #[test]
+        fn test_groups_locations_below_region_headers() {
+            let str = "
+Antarctica, 50.0, 50.0
+Region: Bay Area
+Oakland, 37.8, -122.3
+San Francisco, 37.7, -122.4
+  region  :  New York
+Manhattan, 40.7, -74.0
+Region:
+Test, 0.0, 0.0
+Region: Bay-Area
+Berkeley, 37.9, -122.3
+                ";
+            let locations = RoswaalStringLocations::from_roswaal_locations_str(str);
+            let region = |name: &str| Some(RoswaalLocationName::from_str(name).unwrap());
+            let expected_locations = vec![
+                Ok(RoswaalLocation::new_without_validation(
+                    "Antarctica",
+                    50.0,
+                    50.0,
+                )),
+                Ok(
+                    RoswaalLocation::new_without_validation("Oakland", 37.8, -122.3)
+                        .with_region(region("Bay Area")),
+                ),
+                Ok(
+                    RoswaalLocation::new_without_validation("San Francisco", 37.7, -122.4)
+                        .with_region(region("Bay Area")),
+                ),
+                Ok(
+                    RoswaalLocation::new_without_validation("Manhattan", 40.7, -74.0)
+                        .with_region(region("New York")),
+                ),
+                Ok(RoswaalLocation::new_without_validation("Test", 0.0, 0.0)),
+                Err(RoswaalLocationStringError::InvalidRegionName(
+                    "Bay-Area".to_string(),
+                )),
+                Ok(RoswaalLocation::new_without_validation(
+                    "Berkeley", 37.9, -122.3,
+                )),
+            ];
+            assert_eq!(locations.results(), &expected_locations)
+        }
+
+        #[test]
+        fn test_returns_out_of_range_error_when_latitude_and_longitude_are_swapped() {
+            let locations =
+                RoswaalStringLocations::from_roswaal_locations_str("San Francisco, 122.39, 37.77");
+            let expected_locations = vec![Err(RoswaalLocationStringError::CoordinateOutOfRange {
+                name: "San Francisco".to_string(),
+                latitude: "122.39".to_string(),
+                longitude: "37.77".to_string(),
+            })];
+            assert_eq!(locations.results(), &expected_locations)
+        }
+    }
+
+    mod merged_locations_tests {
+        use crate::location::location::{RoswaalLocation, RoswaalStringLocations};
+
+        #[test]
+        fn test_splits_locations_into_added_updated_and_unchanged() {
+            let merged = [
+                RoswaalLocation::new_without_validation("Oakland", 37.8, -122.3),
+                RoswaalLocation::new_without_validation("Berkeley", 37.9, -122.3),
+                RoswaalLocation::new_without_validation("Manhattan", 40.7, -74.0),
+            ];
+            let locations = RoswaalStringLocations::from_roswaal_locations_str(
+                "Oakland, 37.8, -122.3\nBerkeley, 37.8, -122.2\nTest, 50.0, 50.0",
+            )
+            .with_merged_locations(&merged.iter().collect::<Vec<&RoswaalLocation>>());
+            let names = |locations: Vec<RoswaalLocation>| {
+                locations
+                    .iter()
+                    .map(|l| l.name().raw_name().to_string())
+                    .collect::<Vec<String>>()
+            };
+            assert_eq!(names(locations.added_locations()), vec!["Test"]);
+            assert_eq!(names(locations.updated_locations()), vec!["Berkeley"]);
+            assert_eq!(names(locations.unchanged_locations()), vec!["Oakland"]);
+            assert!(locations.has_changes())
+        }
+
+        #[test]
+        fn test_has_no_changes_when_every_location_is_unchanged() {
+            let merged = [RoswaalLocation::new_without_validation(
+                "Oakland", 37.8, -122.3,
+            )];
+            let locations =
+                RoswaalStringLocations::from_roswaal_locations_str("Oakland, 37.8, -122.3\n9879")
+                    .with_merged_locations(&merged.iter().collect::<Vec<&RoswaalLocation>>());
+            assert!(!locations.has_changes())
+        }
     }
 }
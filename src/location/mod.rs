@@ -1,4 +1,7 @@
+pub mod alias;
 pub mod coordinate;
+pub mod geojson;
+pub mod import;
 pub mod location;
 pub mod name;
 pub mod storage;
@@ -1,12 +1,18 @@
 use anyhow::Result;
-use sqlx::{query, query_as, FromRow, Sqlite};
+use sqlx::FromRow;
 
 use crate::{
     git::branch_name::{self, RoswaalOwnedGitBranchName},
-    utils::sqlite::{sqlite_repeat, RoswaalSqliteTransaction},
+    utils::{
+        db::{
+            query::{query, query_as},
+            repeat, RoswaalDatabaseTransaction,
+        },
+        normalize::RoswaalNormalize,
+    },
 };
 
-use super::{location::RoswaalLocation, name::RoswaalLocationName};
+use super::{alias::RoswaalLocationAlias, location::RoswaalLocation, name::RoswaalLocationName};
 
 #[derive(Debug, PartialEq)]
 pub struct RoswaalStoredLocation {
@@ -57,29 +63,73 @@ impl LoadLocationsFilter {
     }
 }
 
-impl<'a> RoswaalSqliteTransaction<'a> {
+impl<'a> RoswaalDatabaseTransaction<'a> {
     pub async fn close_add_locations_branch(
         &mut self,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        query::<Sqlite>(statements::DELETE_LOCATIONS_WITH_BRANCH)
+        query(statements::DELETE_LOCATIONS_WITH_BRANCH)
             .bind(branch_name)
             .execute(self.connection())
             .await?;
         Ok(())
     }
 
+    pub async fn close_remove_locations_branch(
+        &mut self,
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> Result<()> {
+        query(statements::DELETE_STAGED_LOCATION_REMOVALS_WITH_BRANCH)
+            .bind(branch_name)
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn stage_location_removals(
+        &mut self,
+        names: &Vec<RoswaalLocationName>,
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> Result<()> {
+        if names.is_empty() {
+            return Ok(());
+        }
+        repeat(statements::INSERT_STAGED_LOCATION_REMOVAL, names)
+            .bind_to_query(|q, name| Ok(q.bind(name.raw_name()).bind(branch_name)))?
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn merge_location_removals(
+        &mut self,
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> Result<()> {
+        let sqlite_location_names =
+            query_as::<SqliteLocationName>(statements::SELECT_STAGED_LOCATION_REMOVAL_NAMES)
+                .bind(branch_name)
+                .fetch_all(self.connection())
+                .await?;
+        if !sqlite_location_names.is_empty() {
+            repeat(statements::DELETE_MERGED_LOCATION, &sqlite_location_names)
+                .bind_to_query(|q, sqlite_name| Ok(q.bind(sqlite_name.name.clone())))?
+                .execute(self.connection())
+                .await?;
+        }
+        self.close_remove_locations_branch(branch_name).await?;
+        Ok(())
+    }
+
     pub async fn merge_unmerged_locations(
         &mut self,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        let sqlite_location_names = query_as::<Sqlite, SqliteLocationName>(
-            statements::SELECT_UNMERGED_LOCATION_NAMES_WITH_BRANCH,
-        )
-        .bind(branch_name)
-        .fetch_all(self.connection())
-        .await?;
-        sqlite_repeat(statements::MERGE_UNMERGED_LOCATION, &sqlite_location_names)
+        let sqlite_location_names =
+            query_as::<SqliteLocationName>(statements::SELECT_UNMERGED_LOCATION_NAMES_WITH_BRANCH)
+                .bind(branch_name)
+                .fetch_all(self.connection())
+                .await?;
+        repeat(statements::MERGE_UNMERGED_LOCATION, &sqlite_location_names)
             .bind_to_query(|q, sqlite_name| {
                 Ok(q.bind(sqlite_name.name.clone())
                     .bind(branch_name)
@@ -95,12 +145,13 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         locations: &Vec<RoswaalLocation>,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        sqlite_repeat::<RoswaalLocation>(statements::INSERT_OR_REPLACE_LOCATION, locations)
+        repeat::<RoswaalLocation>(statements::INSERT_OR_REPLACE_LOCATION, locations)
             .bind_to_query(|q, location| {
                 Ok(q.bind(location.coordinate().latitude())
                     .bind(location.coordinate().longitude())
                     .bind(&location.name().raw_value)
-                    .bind(branch_name))
+                    .bind(branch_name)
+                    .bind(location.region().map(|region| &region.raw_value)))
             })?
             .execute(self.connection())
             .await?;
@@ -111,12 +162,15 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         &mut self,
         filter: LoadLocationsFilter,
     ) -> Result<Vec<RoswaalStoredLocation>> {
-        let locations = query_as::<Sqlite, SqliteLocation>(filter.full_select_statement())
+        let locations = query_as::<SqliteLocation>(filter.full_select_statement())
             .fetch_all(self.connection())
             .await?
             .iter()
             .map(|l| RoswaalStoredLocation {
-                location: RoswaalLocation::new_without_validation(&l.name, l.latitude, l.longitude),
+                location: RoswaalLocation::new_without_validation(&l.name, l.latitude, l.longitude)
+                    .with_region(l.region.as_ref().map(|region| RoswaalLocationName {
+                        raw_value: region.clone(),
+                    })),
                 unmerged_branch_name: l.unmerged_branch_name.clone(),
             })
             .collect();
@@ -127,7 +181,7 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         &mut self,
         filter: LoadLocationsFilter,
     ) -> Result<Vec<RoswaalLocationName>> {
-        let locations = query_as::<Sqlite, SqliteLocationName>(filter.name_select_statement())
+        let locations = query_as::<SqliteLocationName>(filter.name_select_statement())
             .fetch_all(self.connection())
             .await?
             .iter()
@@ -137,21 +191,57 @@ impl<'a> RoswaalSqliteTransaction<'a> {
             .collect();
         Ok(locations)
     }
+
+    /// Saves `alias`, replacing any existing alias with the same name case and whitespace
+    /// insensitive.
+    pub async fn save_location_alias(&mut self, alias: &RoswaalLocationAlias) -> Result<()> {
+        query(statements::INSERT_OR_REPLACE_LOCATION_ALIAS)
+            .bind(alias.alias().raw_name().roswaal_normalize())
+            .bind(alias.alias().raw_name())
+            .bind(alias.location_name().raw_name())
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    pub async fn location_aliases_in_alphabetical_order(
+        &mut self,
+    ) -> Result<Vec<RoswaalLocationAlias>> {
+        let aliases = query_as::<SqliteLocationAlias>(statements::SELECT_LOCATION_ALIASES)
+            .fetch_all(self.connection())
+            .await?
+            .into_iter()
+            .map(|a| {
+                RoswaalLocationAlias::new(
+                    RoswaalLocationName { raw_value: a.alias },
+                    RoswaalLocationName {
+                        raw_value: a.location_name,
+                    },
+                )
+            })
+            .collect();
+        Ok(aliases)
+    }
 }
 
 mod statements {
     pub const INSERT_OR_REPLACE_LOCATION: &str = "
-INSERT OR REPLACE INTO Locations (
+INSERT INTO Locations (
     latitude,
     longitude,
     name,
-    unmerged_branch_name
+    unmerged_branch_name,
+    region
 ) VALUES (
     ?,
     ?,
     ?,
+    ?,
     ?
-);";
+) ON CONFLICT (name, unmerged_branch_name) DO UPDATE SET
+    latitude = excluded.latitude,
+    longitude = excluded.longitude,
+    region = excluded.region;";
 
     pub const MERGE_UNMERGED_LOCATION: &str = "
 DELETE FROM Locations WHERE name = ? AND unmerged_branch_name IS NULL;
@@ -159,7 +249,7 @@ UPDATE Locations SET unmerged_branch_name = NULL WHERE unmerged_branch_name = ?
 ";
 
     pub const SELECT_UNMERGED_LOCATION_NAMES_WITH_BRANCH: &str =
-        "SELECT name FROM Locations WHERE unmerged_branch_name = ?;";
+        "SELECT name FROM Locations WHERE unmerged_branch_name = ? ORDER BY name;";
 
     pub const SELECT_ALL_LOCATION_NAMES: &str =
         "SELECT name FROM Locations ORDER BY name, latitude";
@@ -174,6 +264,41 @@ UPDATE Locations SET unmerged_branch_name = NULL WHERE unmerged_branch_name = ?
 
     pub const DELETE_LOCATIONS_WITH_BRANCH: &str =
         "DELETE FROM Locations WHERE unmerged_branch_name = ?;";
+
+    pub const INSERT_STAGED_LOCATION_REMOVAL: &str = "
+INSERT INTO StagedLocationRemovals (
+    name,
+    unmerged_branch_name
+) VALUES (
+    ?,
+    ?
+) ON CONFLICT (name, unmerged_branch_name) DO NOTHING;
+";
+
+    pub const SELECT_STAGED_LOCATION_REMOVAL_NAMES: &str =
+        "SELECT name FROM StagedLocationRemovals WHERE unmerged_branch_name = ?;";
+
+    pub const DELETE_MERGED_LOCATION: &str =
+        "DELETE FROM Locations WHERE name = ? AND unmerged_branch_name IS NULL;";
+
+    pub const DELETE_STAGED_LOCATION_REMOVALS_WITH_BRANCH: &str =
+        "DELETE FROM StagedLocationRemovals WHERE unmerged_branch_name = ?;";
+
+    pub const INSERT_OR_REPLACE_LOCATION_ALIAS: &str = "
+INSERT INTO LocationAliases (
+    normalized_alias,
+    alias,
+    location_name
+) VALUES (
+    ?,
+    ?,
+    ?
+) ON CONFLICT (normalized_alias) DO UPDATE SET
+    alias = excluded.alias,
+    location_name = excluded.location_name;";
+
+    pub const SELECT_LOCATION_ALIASES: &str =
+        "SELECT alias, location_name FROM LocationAliases ORDER BY alias;";
 }
 
 #[derive(FromRow, Debug)]
@@ -181,12 +306,19 @@ struct SqliteLocationName {
     name: String,
 }
 
+#[derive(FromRow, Debug)]
+struct SqliteLocationAlias {
+    alias: String,
+    location_name: String,
+}
+
 #[derive(FromRow, Clone)]
 struct SqliteLocation {
     latitude: f32,
     longitude: f32,
     name: String,
     unmerged_branch_name: Option<RoswaalOwnedGitBranchName>,
+    region: Option<String>,
 }
 
 #[cfg(test)]
@@ -195,14 +327,14 @@ mod tests {
     use crate::{
         git::branch_name::{self, RoswaalOwnedGitBranchName},
         location::{location::RoswaalLocation, storage::RoswaalStoredLocation},
-        utils::sqlite::RoswaalSqlite,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
     async fn test_add_and_load_locations_no_prior_locations() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let locations = vec![
             RoswaalLocation::new_without_validation("Antarctica", 32.29873932, 122.3939839),
             RoswaalLocation::new_without_validation("New York", 45.0, 45.0),
@@ -225,11 +357,43 @@ mod tests {
         assert_eq!(saved_locations, expected_locations)
     }
 
+    #[tokio::test]
+    async fn test_merged_locations_keep_their_regions() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let region = RoswaalLocationName {
+            raw_value: "Bay Area".to_string(),
+        };
+        let locations = vec![
+            RoswaalLocation::new_without_validation("Antarctica", 32.5, 122.5),
+            RoswaalLocation::new_without_validation("Oakland", 37.8, -122.3)
+                .with_region(Some(region)),
+        ];
+        transaction
+            .save_locations(&locations, &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_locations(&branch_name)
+            .await
+            .unwrap();
+        let saved_locations = transaction
+            .locations_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
+            .await
+            .unwrap();
+        let expected_locations = vec![
+            RoswaalStoredLocation::new(locations[0].clone(), None),
+            RoswaalStoredLocation::new(locations[1].clone(), None),
+        ];
+        assert_eq!(saved_locations, expected_locations)
+    }
+
     #[tokio::test]
     async fn test_add_same_locations_on_same_branch_replaces_previous() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut locations = vec![RoswaalLocation::new_without_validation(
             "Antarctica",
             32.29873932,
@@ -256,8 +420,8 @@ mod tests {
     #[tokio::test]
     async fn test_add_and_load_locations_on_different_branches_adds_new_record() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut locations = vec![
             RoswaalLocation::new_without_validation("Antarctica", 32.29873932, 122.3939839),
             RoswaalLocation::new_without_validation("New York", 45.0, 45.0),
@@ -301,8 +465,8 @@ mod tests {
     #[tokio::test]
     async fn save_and_merge_locations_removes_unmerged_branch_name() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let locations = vec![
             RoswaalLocation::new_without_validation("Antarctica", 32.29873932, 122.3939839),
             RoswaalLocation::new_without_validation("New York", 45.0, 45.0),
@@ -334,8 +498,8 @@ mod tests {
 
     #[tokio::test]
     async fn save_and_merge_existing_location_overrides_previous_merged_location() {
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
 
         let mut branch_name = RoswaalOwnedGitBranchName::new("test");
         let mut locations = vec![RoswaalLocation::new_without_validation(
@@ -378,8 +542,8 @@ mod tests {
 
     #[tokio::test]
     async fn loads_only_merged_locations_with_merge_only_filter() {
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
 
         let mut branch_name = RoswaalOwnedGitBranchName::new("test");
         let new_york = RoswaalLocation::new_without_validation("New York", 45.0, 45.0);
@@ -417,8 +581,8 @@ mod tests {
 
     #[tokio::test]
     async fn save_and_close_location_branch_removes_location() {
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
 
         let branch_name = RoswaalOwnedGitBranchName::new("test");
         let locations = vec![RoswaalLocation::new_without_validation(
@@ -440,4 +604,151 @@ mod tests {
         let expected_locations = vec![];
         assert_eq!(saved_locations, expected_locations)
     }
+
+    #[tokio::test]
+    async fn stage_location_removals_does_not_remove_locations() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let new_york = RoswaalLocation::new_without_validation("New York", 45.0, 45.0);
+        let locations = vec![new_york.clone()];
+        transaction
+            .save_locations(&locations, &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_locations(&branch_name)
+            .await
+            .unwrap();
+        transaction
+            .stage_location_removals(
+                &vec![new_york.name().clone()],
+                &RoswaalOwnedGitBranchName::for_removing_locations(),
+            )
+            .await
+            .unwrap();
+
+        let saved_locations = transaction
+            .locations_in_alphabetical_order(LoadLocationsFilter::All)
+            .await
+            .unwrap();
+        let expected_locations = vec![RoswaalStoredLocation {
+            location: new_york,
+            unmerged_branch_name: None,
+        }];
+        assert_eq!(saved_locations, expected_locations)
+    }
+
+    #[tokio::test]
+    async fn merge_location_removals_only_removes_merged_locations() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let mut branch_name = RoswaalOwnedGitBranchName::new("test");
+        let locations = vec![
+            RoswaalLocation::new_without_validation("Antarctica", 32.5, 122.5),
+            RoswaalLocation::new_without_validation("New York", 45.0, 45.0),
+        ];
+        transaction
+            .save_locations(&locations, &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_locations(&branch_name)
+            .await
+            .unwrap();
+        branch_name = RoswaalOwnedGitBranchName::new("test-2");
+        let unmerged_new_york = RoswaalLocation::new_without_validation("New York", 50.0, 50.0);
+        transaction
+            .save_locations(&vec![unmerged_new_york.clone()], &branch_name)
+            .await
+            .unwrap();
+
+        let removal_branch_name = RoswaalOwnedGitBranchName::for_removing_locations();
+        transaction
+            .stage_location_removals(&vec![locations[1].name().clone()], &removal_branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_location_removals(&removal_branch_name)
+            .await
+            .unwrap();
+
+        let saved_locations = transaction
+            .locations_in_alphabetical_order(LoadLocationsFilter::All)
+            .await
+            .unwrap();
+        let expected_locations = vec![
+            RoswaalStoredLocation {
+                location: locations[0].clone(),
+                unmerged_branch_name: None,
+            },
+            RoswaalStoredLocation {
+                location: unmerged_new_york,
+                unmerged_branch_name: Some(branch_name),
+            },
+        ];
+        assert_eq!(saved_locations, expected_locations)
+    }
+
+    #[tokio::test]
+    async fn close_remove_locations_branch_keeps_locations() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let new_york = RoswaalLocation::new_without_validation("New York", 45.0, 45.0);
+        transaction
+            .save_locations(&vec![new_york.clone()], &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_locations(&branch_name)
+            .await
+            .unwrap();
+
+        let removal_branch_name = RoswaalOwnedGitBranchName::for_removing_locations();
+        transaction
+            .stage_location_removals(&vec![new_york.name().clone()], &removal_branch_name)
+            .await
+            .unwrap();
+        transaction
+            .close_remove_locations_branch(&removal_branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_location_removals(&removal_branch_name)
+            .await
+            .unwrap();
+
+        let saved_locations = transaction
+            .locations_in_alphabetical_order(LoadLocationsFilter::All)
+            .await
+            .unwrap();
+        let expected_locations = vec![RoswaalStoredLocation {
+            location: new_york,
+            unmerged_branch_name: None,
+        }];
+        assert_eq!(saved_locations, expected_locations)
+    }
+
+    #[tokio::test]
+    async fn saving_an_alias_replaces_the_alias_with_the_same_normalized_name() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let name = |s: &str| RoswaalLocationName {
+            raw_value: s.to_string(),
+        };
+        let aliases = [
+            RoswaalLocationAlias::new(name("SF"), name("South Florida")),
+            RoswaalLocationAlias::new(name("NYC"), name("New York")),
+            RoswaalLocationAlias::new(name("sf"), name("San Francisco")),
+        ];
+        for alias in aliases.iter() {
+            transaction.save_location_alias(alias).await.unwrap();
+        }
+        let saved_aliases = transaction
+            .location_aliases_in_alphabetical_order()
+            .await
+            .unwrap();
+        assert_eq!(saved_aliases, vec![aliases[1].clone(), aliases[2].clone()])
+    }
 }
@@ -1,23 +1,27 @@
-mod generation;
-mod git;
-mod http;
-mod language;
-mod location;
-mod operations;
-mod slack;
-mod tests_data;
-mod utils;
-
 use std::sync::Arc;
 
 use anyhow::Result;
 use dotenv::dotenv;
-use http::{server::run_http_server, server_environment::ServerEnvironment};
-use utils::log::bootstrap_logging;
+use roswaal::{
+    http::{server::run_http_server, server_environment::ServerEnvironment},
+    scheduler::{
+        deprecated_tests::run_deprecated_tests_scheduler, test_digest::run_test_digest_scheduler,
+    },
+    utils::{config::RoswaalConfig, log::bootstrap_logging},
+};
+use tokio::spawn;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenv()?;
     bootstrap_logging();
-    run_http_server(Arc::new(ServerEnvironment::current().await?)).await
+    RoswaalConfig::bootstrap().await?;
+    #[cfg(feature = "e2e")]
+    if std::env::args().nth(1).as_deref() == Some("e2e") {
+        return roswaal::e2e::harness::run_e2e_harness().await;
+    }
+    let environment = Arc::new(ServerEnvironment::current().await?);
+    spawn(run_test_digest_scheduler(environment.clone()));
+    spawn(run_deprecated_tests_scheduler(environment.clone()));
+    run_http_server(environment).await
 }
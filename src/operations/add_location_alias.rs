@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::{
+    location::{
+        alias::RoswaalLocationAlias, name::RoswaalLocationName, storage::LoadLocationsFilter,
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AddLocationAliasStatus {
+    Success(RoswaalLocationAlias),
+    InvalidFormat,
+    InvalidAliasName(String),
+    UnknownLocationName(String),
+    /// The alias is already the name of a merged location.
+    AliasIsLocationName(RoswaalLocationName),
+}
+
+impl AddLocationAliasStatus {
+    /// Adds an alias from a string in the form `<alias>, <location name>`.
+    ///
+    /// The location must be merged, and the alias cannot be the name of a merged location. An
+    /// existing alias with the same name is pointed to the new location instead.
+    pub async fn from_adding_alias(alias_str: &str, database: &RoswaalDatabase) -> Result<Self> {
+        let Some((alias_name, location_name)) = alias_str.split_once(',') else {
+            return Ok(Self::InvalidFormat);
+        };
+        let (alias_name, location_name) = (alias_name.trim(), location_name.trim());
+        let Ok(alias_name) = RoswaalLocationName::from_str(alias_name) else {
+            return Ok(Self::InvalidAliasName(alias_name.to_string()));
+        };
+        let mut transaction = database.transaction().await?;
+        with_transaction!(transaction, async {
+            let location_names = transaction
+                .location_names_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
+                .await?;
+            if let Some(name) = location_names.iter().find(|n| n.matches(&alias_name)) {
+                return Ok(Self::AliasIsLocationName(name.clone()));
+            }
+            let known_name = RoswaalLocationName::from_str(location_name)
+                .ok()
+                .and_then(|name| location_names.into_iter().find(|n| n.matches(&name)));
+            let Some(known_name) = known_name else {
+                return Ok(Self::UnknownLocationName(location_name.to_string()));
+            };
+            let alias = RoswaalLocationAlias::new(alias_name, known_name);
+            transaction.save_location_alias(&alias).await?;
+            Ok(Self::Success(alias))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{git::branch_name::RoswaalOwnedGitBranchName, location::location::RoswaalLocation};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn invalid_format_when_no_location_name() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = AddLocationAliasStatus::from_adding_alias("SF", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, AddLocationAliasStatus::InvalidFormat)
+    }
+
+    #[tokio::test]
+    async fn invalid_alias_name_when_alias_is_not_a_location_name() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = AddLocationAliasStatus::from_adding_alias("(SF), San Francisco", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            AddLocationAliasStatus::InvalidAliasName("(SF)".to_string())
+        )
+    }
+
+    #[tokio::test]
+    async fn unknown_location_name_when_location_is_not_merged() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_locations(
+                &vec![RoswaalLocation::new_without_validation(
+                    "San Francisco",
+                    37.7,
+                    -122.4,
+                )],
+                &RoswaalOwnedGitBranchName::new("test"),
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let status = AddLocationAliasStatus::from_adding_alias("SF, San Francisco", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            AddLocationAliasStatus::UnknownLocationName("San Francisco".to_string())
+        )
+    }
+
+    #[tokio::test]
+    async fn adds_alias_to_the_merged_location_name() {
+        let database = save_merged_locations(&["San Francisco", "Oakland"]).await;
+        let status = AddLocationAliasStatus::from_adding_alias(" SF ,  san francisco ", &database)
+            .await
+            .unwrap();
+        let alias = RoswaalLocationAlias::new(
+            RoswaalLocationName::from_str("SF").unwrap(),
+            RoswaalLocationName::from_str("San Francisco").unwrap(),
+        );
+        assert_eq!(status, AddLocationAliasStatus::Success(alias.clone()));
+        let mut transaction = database.transaction().await.unwrap();
+        let aliases = transaction
+            .location_aliases_in_alphabetical_order()
+            .await
+            .unwrap();
+        assert_eq!(aliases, vec![alias])
+    }
+
+    #[tokio::test]
+    async fn alias_is_location_name_when_alias_matches_a_merged_location() {
+        let database = save_merged_locations(&["San Francisco", "Oakland"]).await;
+        let status = AddLocationAliasStatus::from_adding_alias("oakland, San Francisco", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            AddLocationAliasStatus::AliasIsLocationName(
+                RoswaalLocationName::from_str("Oakland").unwrap()
+            )
+        )
+    }
+
+    async fn save_merged_locations(names: &[&str]) -> RoswaalDatabase {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let locations = names
+            .iter()
+            .map(|name| RoswaalLocation::new_without_validation(name, 10.0, 10.0))
+            .collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_locations(&locations, &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_locations(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        database
+    }
+}
@@ -1,10 +1,12 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
-use tokio::{fs::File, io::AsyncWriteExt};
 
 use crate::{
-    generation::interface::RoswaalTypescriptGenerate,
+    generation::{formatter::format_generated_files, locations::save_locations_code},
     git::{
-        branch_name::RoswaalOwnedGitBranchName,
+        metadata::RoswaalGitRepositoryMetadata,
+        branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
         edit::EditGitRepositoryStatus,
         pull_request::GithubPullRequestOpen,
         repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
@@ -13,53 +15,112 @@ use crate::{
         location::{RoswaalLocation, RoswaalStringLocations},
         storage::{LoadLocationsFilter, RoswaalStoredLocation},
     },
-    utils::sqlite::RoswaalSqlite,
+    slack::file::SlackFetchFile,
+    utils::{db::RoswaalDatabase, stage::RoswaalOperationStage},
     with_transaction,
 };
 
+use super::resource_coordinator::ResourceCoordinator;
+
 #[derive(Debug, PartialEq)]
 pub enum AddLocationsStatus {
     Success {
         locations: RoswaalStringLocations,
         did_delete_branch: bool,
+        branch_name: RoswaalOwnedGitBranchName,
     },
     NoLocationsAdded,
+    /// Every valid location is identical to a merged location, so no branch was created.
+    NoChanges {
+        locations: RoswaalStringLocations,
+    },
+    /// The locations file at `url` in the command text could not be downloaded.
+    FailedToImportFile {
+        url: String,
+    },
     FailedToOpenPullRequest,
     MergeConflict,
+    RemoteUnavailable,
+}
+
+impl AddLocationsStatus {
+    /// Returns the branch that the locations were added on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::Success { branch_name, .. } => Some(branch_name),
+            _ => None,
+        }
+    }
 }
 
 impl AddLocationsStatus {
     pub async fn from_adding_locations(
         locations_str: &str,
         git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
         pr_open: &impl GithubPullRequestOpen,
     ) -> Result<Self> {
         if locations_str.is_empty() {
             return Ok(Self::NoLocationsAdded);
         }
         let string_locations = RoswaalStringLocations::from_roswaal_locations_str(locations_str);
-        let branch_name = RoswaalOwnedGitBranchName::for_adding_locations();
-        let mut transaction = sqlite.transaction().await?;
-        let (stored_locations, git_transaction) = with_transaction!(transaction, async {
-            let locations = transaction
+        Self::from_adding_string_locations(string_locations, git_repository, database, pr_open).await
+    }
+
+    /// Adds the locations in `locations_str`, where lines can also be links to CSV or GPX files
+    /// uploaded to slack containing locations.
+    pub async fn from_importing_locations(
+        locations_str: &str,
+        file_fetch: &impl SlackFetchFile,
+        git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+        database: &RoswaalDatabase,
+        pr_open: &impl GithubPullRequestOpen,
+    ) -> Result<Self> {
+        let string_locations = match RoswaalStringLocations::from_importing_roswaal_locations_str(
+            locations_str,
+            file_fetch
+        )
+        .await {
+            Ok(string_locations) => string_locations,
+            Err(err) => return Ok(Self::FailedToImportFile { url: err.url().to_string() })
+        };
+        if string_locations.results().is_empty() {
+            return Ok(Self::NoLocationsAdded);
+        }
+        Self::from_adding_string_locations(string_locations, git_repository, database, pr_open).await
+    }
+
+    async fn from_adding_string_locations(
+        string_locations: RoswaalStringLocations,
+        git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+        database: &RoswaalDatabase,
+        pr_open: &impl GithubPullRequestOpen,
+    ) -> Result<Self> {
+        let mut resources = ResourceCoordinator::new(database, git_repository);
+        let (git_transaction, mut transaction) = resources.git_and_database().await?;
+        let stored_locations = with_transaction!(transaction, async {
+            transaction
                 .locations_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
-                .await?;
-            Ok((locations, git_repository.transaction().await))
+                .await
         })?;
+        let string_locations = string_locations.with_merged_locations(
+            &stored_locations.iter().map(|l| l.location()).collect::<Vec<&RoswaalLocation>>()
+        );
+        if !string_locations.has_changes() {
+            return Ok(Self::NoChanges { locations: string_locations });
+        }
 
         let metadata = git_transaction.metadata().clone();
+        let branch_name = metadata.new_branch_name(RoswaalOwnedBranchKind::AddLocations);
         let edit_status = EditGitRepositoryStatus::from_editing_new_branch(
             &branch_name,
             git_transaction,
             pr_open,
             async {
-                Self::generate_locations_code(
-                    &string_locations,
-                    &stored_locations,
-                    metadata.locations_path(),
-                )
-                .await?;
+                let paths =
+                    Self::generate_locations_code(&string_locations, &stored_locations, &metadata)
+                        .await?;
+                format_generated_files(&metadata, &paths).await;
                 Ok((
                     metadata.add_locations_pull_request(&string_locations, &branch_name),
                     (),
@@ -73,38 +134,44 @@ impl AddLocationsStatus {
                 did_delete_branch,
                 value: _,
             } => {
-                transaction = sqlite.transaction().await?;
+                transaction = resources.database().await?;
                 with_transaction!(transaction, async {
-                    transaction
-                        .save_locations(&string_locations.locations(), &branch_name)
-                        .await?;
+                    let changed_locations = [
+                        string_locations.added_locations(),
+                        string_locations.updated_locations()
+                    ]
+                    .concat();
+                    transaction.save_locations(&changed_locations, &branch_name).await?;
                     Ok(Self::Success {
                         locations: string_locations,
                         did_delete_branch,
+                        branch_name,
                     })
                 })
             }
             EditGitRepositoryStatus::FailedToOpenPullRequest => Ok(Self::FailedToOpenPullRequest),
             EditGitRepositoryStatus::MergeConflict => Ok(Self::MergeConflict),
+            EditGitRepositoryStatus::RemoteUnavailable => Ok(Self::RemoteUnavailable),
         }
     }
 
     async fn generate_locations_code(
         string_locations: &RoswaalStringLocations,
         stored_locations: &Vec<RoswaalStoredLocation>,
-        path: &str,
-    ) -> Result<()> {
-        let locations_code = stored_locations
+        metadata: &RoswaalGitRepositoryMetadata,
+    ) -> Result<Vec<PathBuf>> {
+        RoswaalOperationStage::Generating.report();
+        // NB: Merged locations with the name of a new location are replaced in place, so that
+        // updating a location does not generate a duplicate export.
+        let new_locations = string_locations.locations();
+        let added_locations = string_locations.added_locations();
+        let locations = stored_locations
             .iter()
             .map(|l| l.location())
-            .chain(string_locations.locations().iter())
-            .collect::<Vec<&RoswaalLocation>>()
-            .typescript();
-        let mut file = File::create(path).await?;
-        file.write(locations_code.as_bytes()).await?;
-        file.flush().await?;
-        drop(file);
-        Ok(())
+            .map(|stored| new_locations.iter().find(|l| l.name() == stored.name()).unwrap_or(stored))
+            .chain(added_locations.iter())
+            .collect::<Vec<&RoswaalLocation>>();
+        save_locations_code(metadata, &locations).await
     }
 }
 
@@ -119,19 +186,22 @@ mod tests {
         is_case,
         location::location::RoswaalStringLocations,
         operations::{add_locations::AddLocationsStatus, merge_branch::MergeBranchStatus},
-        utils::sqlite::RoswaalSqlite,
+        runner::webhook::test_support::NO_TEST_RUNNER,
+        slack::file::test_support::TestSlackFileFetch,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
     async fn test_success_when_adding_locations_smoothly() {
         with_clean_test_repo_access(async {
             let str = "Test, 50.0, 50.0";
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
             let result = AddLocationsStatus::from_adding_locations(
                 str,
                 &RoswaalGitRepository::noop().await?,
-                &sqlite,
-                &TestGithubPullRequestOpen::new(false),
+                &database,
+                &pr_open,
             )
             .await?;
             let str_locations = RoswaalStringLocations::from_roswaal_locations_str(str);
@@ -139,7 +209,8 @@ mod tests {
                 result,
                 AddLocationsStatus::Success {
                     locations: str_locations,
-                    did_delete_branch: true
+                    did_delete_branch: true,
+                    branch_name: pr_open.most_recent_head_branch_name().await.unwrap()
                 }
             );
             Ok(())
@@ -152,12 +223,13 @@ mod tests {
     async fn test_success_mixes_proper_and_invalid_locations() {
         with_clean_test_repo_access(async {
             let str = "Test, 50.0, 50.0\n29879";
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
+            let pr_open = TestGithubPullRequestOpen::new(false);
             let result = AddLocationsStatus::from_adding_locations(
                 str,
                 &RoswaalGitRepository::noop().await.unwrap(),
-                &sqlite,
-                &TestGithubPullRequestOpen::new(false),
+                &database,
+                &pr_open,
             )
             .await?;
             let str_locations = RoswaalStringLocations::from_roswaal_locations_str(str);
@@ -165,7 +237,8 @@ mod tests {
                 result,
                 AddLocationsStatus::Success {
                     locations: str_locations,
-                    did_delete_branch: true
+                    did_delete_branch: true,
+                    branch_name: pr_open.most_recent_head_branch_name().await.unwrap()
                 }
             );
             Ok(())
@@ -177,11 +250,11 @@ mod tests {
     #[tokio::test]
     async fn test_no_locations_added_when_empty_vector() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let result = AddLocationsStatus::from_adding_locations(
                 "",
                 &RoswaalGitRepository::noop().await.unwrap(),
-                &sqlite,
+                &database,
                 &TestGithubPullRequestOpen::new(false),
             )
             .await;
@@ -196,29 +269,34 @@ mod tests {
     async fn test_creates_code_file_with_existing_merged_locations() {
         with_clean_test_repo_access(async {
             let metadata = RoswaalGitRepositoryMetadata::for_testing();
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop().await?;
             _ = AddLocationsStatus::from_adding_locations(
                 "Hello, 50.0, 50.0",
                 &repo,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await;
             _ = AddLocationsStatus::from_adding_locations(
                 "Test, 50.0, 50.0",
                 &repo,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await;
             let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            _ = MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
+            _ = MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
             _ = AddLocationsStatus::from_adding_locations(
                 "Test 2, 45.0, 45.0",
                 &repo,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await;
@@ -250,15 +328,86 @@ export namespace TestLocations {
         .unwrap();
     }
 
+    #[tokio::test]
+    async fn test_no_changes_when_adding_identical_merged_locations() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let repo = RoswaalGitRepository::noop().await?;
+            _ = AddLocationsStatus::from_adding_locations("Test, 50.0, 50.0", &repo, &database, &pr_open)
+                .await?;
+            let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
+            _ = MergeBranchStatus::from_merging_branch_with_name(&branch_name, &database, NO_TEST_RUNNER)
+                .await?;
+            let str = "Test, 50.0, 50.0\n29879";
+            let result = AddLocationsStatus::from_adding_locations(str, &repo, &database, &pr_open)
+                .await?;
+            assert!(is_case!(result, AddLocationsStatus::NoChanges));
+            assert_eq!(pr_open.most_recent_head_branch_name().await, Some(branch_name));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replaces_merged_locations_when_updating_them() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let database = RoswaalDatabase::in_memory().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let repo = RoswaalGitRepository::noop().await?;
+            _ = AddLocationsStatus::from_adding_locations(
+                "Test, 50.0, 50.0\nOther, 10.0, 10.0",
+                &repo,
+                &database,
+                &pr_open
+            )
+            .await?;
+            let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
+            _ = MergeBranchStatus::from_merging_branch_with_name(&branch_name, &database, NO_TEST_RUNNER)
+                .await?;
+            _ = AddLocationsStatus::from_adding_locations("Test, 45.0, 45.0", &repo, &database, &pr_open)
+                .await?;
+            let pr = pr_open.most_recent_pr().await.unwrap();
+            assert!(pr.body().contains("Updates the following locations"));
+            let content = read_string(metadata.locations_path()).await?;
+            let expected_content = "\
+// Generated by Roswaal, do not touch.
+
+import { LocationCoordinate2D } from \"TiFShared/domain-models/LocationCoordinate2D\"
+
+export const setUserLocation = async (coordinate: LocationCoordinate2D) => {
+  await device.setLocation(coordinate.latitude, coordinate.longitude)
+}
+
+export namespace TestLocations {
+  export const Other = {
+    latitude: 10.0000000000000000,
+    longitude: 10.0000000000000000
+  }
+  export const Test = {
+    latitude: 45.0000000000000000,
+    longitude: 45.0000000000000000
+  }
+}
+";
+            assert_eq!(&content, expected_content);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn test_opens_pr_with_new_locations() {
         with_clean_test_repo_access(async {
             let pr_open = TestGithubPullRequestOpen::new(false);
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             _ = AddLocationsStatus::from_adding_locations(
                 "Test, 50.0, 50.0",
                 &RoswaalGitRepository::noop().await.unwrap(),
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await?;
@@ -274,11 +423,11 @@ export namespace TestLocations {
     async fn test_returns_pr_failed_status_when_pr_open_fails() {
         with_clean_test_repo_access(async {
             let pr_open = TestGithubPullRequestOpen::new(true);
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let result = AddLocationsStatus::from_adding_locations(
                 "Test, 50.0, 50.0",
                 &RoswaalGitRepository::noop().await.unwrap(),
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await?;
@@ -293,7 +442,7 @@ export namespace TestLocations {
     async fn test_returns_merge_conflict_status_when_merge_conflict_occurs() {
         with_clean_test_repo_access(async {
             let pr_open = TestGithubPullRequestOpen::new(true);
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let repo = RoswaalGitRepository::noop().await?;
             let mut transaction = repo.transaction().await;
             transaction.ensure_merge_conflict();
@@ -301,7 +450,7 @@ export namespace TestLocations {
             let result = AddLocationsStatus::from_adding_locations(
                 "Test, 50.0, 50.0",
                 &repo,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await?;
@@ -311,4 +460,61 @@ export namespace TestLocations {
         .await
         .unwrap()
     }
+
+    #[tokio::test]
+    async fn test_success_when_importing_locations_from_a_file() {
+        with_clean_test_repo_access(async {
+            let csv = "Name, Latitude, Longitude\nTest, 50.0, 50.0";
+            let fetch = TestSlackFileFetch::new(&[("F0123ABCD", csv)]);
+            let database = RoswaalDatabase::in_memory().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let result = AddLocationsStatus::from_importing_locations(
+                "<https://tif.slack.com/files/U01234567/F0123ABCD/a.csv>\nTest 2, 45.0, 45.0",
+                &fetch,
+                &RoswaalGitRepository::noop().await?,
+                &database,
+                &pr_open,
+            )
+            .await?;
+            let str_locations =
+                RoswaalStringLocations::from_roswaal_locations_str("Test, 50.0, 50.0\nTest 2, 45.0, 45.0");
+            assert_eq!(
+                result,
+                AddLocationsStatus::Success {
+                    locations: str_locations,
+                    did_delete_branch: true,
+                    branch_name: pr_open.most_recent_head_branch_name().await.unwrap()
+                }
+            );
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_returns_failed_to_import_file_status_when_download_fails() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let result = AddLocationsStatus::from_importing_locations(
+                "https://tif.slack.com/files/U01234567/F0123ABCD/a.gpx",
+                &TestSlackFileFetch::new(&[]),
+                &RoswaalGitRepository::noop().await?,
+                &database,
+                &pr_open,
+            )
+            .await?;
+            assert_eq!(
+                result,
+                AddLocationsStatus::FailedToImportFile {
+                    url: "https://tif.slack.com/files/U01234567/F0123ABCD/a.gpx".to_string()
+                }
+            );
+            assert!(pr_open.most_recent_pr().await.is_none());
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
 }
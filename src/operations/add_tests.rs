@@ -1,59 +1,194 @@
+use std::time::Duration;
+
 use anyhow::Result;
-use tokio::spawn;
 
 use crate::{
-    generation::interface::RoswaalTypescriptGenerate,
+    generation::{
+        formatter::format_generated_files,
+        pipeline::{save_generated_test_code, MAX_CONCURRENT_TEST_CODE_SAVES},
+    },
     git::{
-        branch_name::RoswaalOwnedGitBranchName,
-        edit::EditGitRepositoryStatus,
+        branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+        edit::{EditGitRepositoryStatus, TargetRepositoryEdit},
         metadata::RoswaalGitRepositoryMetadata,
-        pull_request::GithubPullRequestOpen,
+        pull_request::{
+            is_transient_github_error, GithubPullRequestComment, GithubPullRequestOpen,
+        },
         repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
     },
-    language::{ast::extract_tests_syntax, compilation_results::RoswaalTestCompilationResults},
+    language::{ast::tests_syntax, compilation_results::RoswaalTestCompilationResults},
     location::storage::LoadLocationsFilter,
-    utils::sqlite::RoswaalSqlite,
+    slack::add_tests_view::{compilation_error_line_message, compilation_error_message},
+    tests_data::{
+        estimate::{RoswaalSuiteRuntimeOverrun, RoswaalTestRuntimeEstimator},
+        query::RoswaalSearchTestsQuery,
+        test::RoswaalTest,
+    },
+    utils::{
+        config::{RoswaalConfig, RoswaalPullRequestMode},
+        db::RoswaalDatabase,
+        retry::retry,
+        stage::RoswaalOperationStage,
+    },
     with_transaction,
 };
 
+use super::resource_coordinator::ResourceCoordinator;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum AddTestsStatus<'r> {
     Success {
         results: RoswaalTestCompilationResults<'r>,
         should_warn_undeleted_branch: bool,
+        /// The branch that the compiled tests were added on, or None if no tests compiled.
+        branch_name: Option<RoswaalOwnedGitBranchName>,
+        /// The results of adding the compiled tests to each additional target repository.
+        target_edits: Vec<TargetRepositoryEdit>,
+        /// The names of compiled tests that were not added because a merged test already has
+        /// the same name.
+        name_conflicts: Vec<String>,
+        /// The estimated runtime of the suite once the added tests are merged, if it exceeds the
+        /// configured suite time budget.
+        suite_runtime_overrun: Option<RoswaalSuiteRuntimeOverrun>,
     },
     NoTestsFound,
     MergeConflict,
+    RemoteUnavailable,
     FailedToOpenPullRequest,
 }
 
+/// A flag that can be placed outside of the code blocks in the text given to `/add-tests` to
+/// replace merged tests with the same name instead of skipping them.
+pub const OVERWRITE_FLAG: &str = "--overwrite";
+
+/// A flag that can be placed outside of the code blocks in the text given to `/add-tests`,
+/// `/edit-test`, or `/lint-tests` to renumber out of sequence or repeated step labels instead of
+/// failing to compile them.
+pub const NORMALIZE_STEPS_FLAG: &str = "--normalize-steps";
+
+impl<'r> AddTestsStatus<'r> {
+    /// Returns the branch that the compiled tests were added on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::Success { branch_name, .. } => branch_name.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 impl<'r> AddTestsStatus<'r> {
-    pub async fn from_adding_tests(
+    /// Compiles the tests in `tests_str`, and opens a PR that adds the code for the compiled
+    /// tests to `git_repository`.
+    ///
+    /// If the PR is opened, the same code is then added to each of the `target_repositories` on
+    /// a branch with the same name.
+    ///
+    /// Compiled tests with the same name as a merged test are not added unless `tests_str`
+    /// contains the `--overwrite` flag, since merging them would silently replace the merged test.
+    ///
+    /// The added tests are attributed to the slack user with `added_by_slack_user_id`, unless they
+    /// replace a merged test that is already attributed to someone.
+    ///
+    /// The tests that did not compile are saved for the same slack user, so that they can be
+    /// viewed with `/my-failed-tests` after the slack message is gone.
+    pub async fn from_adding_tests<Client: RoswaalGitRepositoryClient>(
         tests_str: &'r str,
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
         pr_open: &impl GithubPullRequestOpen,
-        git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+        git_repository: &RoswaalGitRepository<Client>,
+        target_repositories: &[RoswaalGitRepository<Client>],
+        added_by_slack_user_id: Option<&str>,
     ) -> Result<Self> {
-        let tests_syntax = extract_tests_syntax(tests_str);
-        if tests_syntax.is_empty() {
+        if tests_syntax(tests_str).next().is_none() {
             return Ok(AddTestsStatus::NoTestsFound);
         }
 
-        let mut transaction = sqlite.transaction().await?;
-        let (location_names, git_transaction) = with_transaction!(transaction, async {
+        let suite_time_budget = RoswaalConfig::current().suite_time_budget();
+        let mut resources = ResourceCoordinator::new(database, git_repository);
+        let (git_transaction, mut transaction) = resources.git_and_database().await?;
+        let (
+            location_names,
+            location_aliases,
+            merged_test_names,
+            test_dependencies,
+            merged_tests,
+        ) = with_transaction!(transaction, async {
             let location_names = transaction
                 .location_names_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
                 .await?;
-            Ok((location_names, git_repository.transaction().await))
+            let location_aliases = transaction.location_aliases_in_alphabetical_order().await?;
+            let merged_test_names = transaction.merged_test_names().await?;
+            let test_dependencies = transaction.merged_test_dependency_graph().await?;
+            // NB: The merged tests are only needed to estimate the runtime of the suite.
+            let merged_tests = if suite_time_budget.is_some() {
+                transaction
+                    .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                    .await?
+                    .into_iter()
+                    .filter(|t| t.unmerged_branch_name().is_none())
+                    .collect::<Vec<RoswaalTest>>()
+            } else {
+                vec![]
+            };
+            Ok((
+                location_names,
+                location_aliases,
+                merged_test_names,
+                test_dependencies,
+                merged_tests,
+            ))
         })?;
 
         let metadata = git_transaction.metadata().clone();
-        let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
-        let results = RoswaalTestCompilationResults::compile(&tests_syntax, &location_names);
+        let branch_name = metadata.new_branch_name(RoswaalOwnedBranchKind::AddTests);
+        RoswaalOperationStage::Compiling.report();
+        let results = RoswaalTestCompilationResults::compile_with_test_dependencies(
+            tests_syntax(tests_str),
+            &location_names,
+            &location_aliases,
+            &test_dependencies,
+            has_flag(tests_str, NORMALIZE_STEPS_FLAG),
+        );
+        transaction = resources.database().await?;
+        with_transaction!(transaction, async {
+            transaction.save_test_submission(&results).await?;
+            let Some(slack_user_id) = added_by_slack_user_id else {
+                return Ok(());
+            };
+            for failure in results.failures() {
+                let error_messages = failure
+                    .errors()
+                    .iter()
+                    .map(compilation_error_line_message)
+                    .collect::<Vec<String>>();
+                transaction
+                    .save_compilation_failure(
+                        slack_user_id,
+                        failure.syntax().source_code(),
+                        &error_messages,
+                    )
+                    .await?;
+            }
+            Ok(())
+        })?;
+        let mut name_conflicts = Vec::<String>::new();
+        if !has_flag(tests_str, OVERWRITE_FLAG) {
+            for test in results.tests() {
+                let name = test.name().to_string();
+                if merged_test_names.contains(&name) && !name_conflicts.contains(&name) {
+                    name_conflicts.push(name)
+                }
+            }
+        }
+        let results = results.without_tests_named(&name_conflicts);
         if !results.has_compiling_tests() {
             return Ok(Self::Success {
                 results,
                 should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts,
+                suite_runtime_overrun: None,
             });
         }
 
@@ -62,7 +197,7 @@ impl<'r> AddTestsStatus<'r> {
             git_transaction,
             pr_open,
             async {
-                Self::generate_typescript(&results, &metadata).await?;
+                Self::generate_code(&results, &metadata).await?;
                 Ok((metadata.add_tests_pull_request(&results, &branch_name), ()))
             },
         )
@@ -73,55 +208,190 @@ impl<'r> AddTestsStatus<'r> {
                 did_delete_branch,
                 value: _,
             } => {
-                transaction = sqlite.transaction().await?;
+                transaction = resources.database().await?;
                 with_transaction!(transaction, async {
                     transaction
-                        .save_tests(&results.tests(), &branch_name)
-                        .await?;
-                    Ok(Self::Success {
-                        results,
-                        should_warn_undeleted_branch: !did_delete_branch,
-                    })
+                        .save_tests_added_by(
+                            &results.tests(),
+                            &branch_name,
+                            added_by_slack_user_id,
+                        )
+                        .await
+                })?;
+                let target_edits = TargetRepositoryEdit::from_editing_targets(
+                    &branch_name,
+                    target_repositories,
+                    pr_open,
+                    |metadata| {
+                        let (results, branch_name) = (&results, &branch_name);
+                        async move {
+                            Self::generate_code(results, &metadata).await?;
+                            Ok(metadata.add_tests_pull_request(results, branch_name))
+                        }
+                    },
+                )
+                .await;
+                let suite_runtime_overrun = suite_time_budget.and_then(|budget| {
+                    suite_runtime_overrun(&merged_tests, &results, budget)
+                });
+                Ok(Self::Success {
+                    results,
+                    should_warn_undeleted_branch: !did_delete_branch,
+                    branch_name: Some(branch_name),
+                    target_edits,
+                    name_conflicts,
+                    suite_runtime_overrun,
                 })
             }
             EditGitRepositoryStatus::FailedToOpenPullRequest => Ok(Self::FailedToOpenPullRequest),
             EditGitRepositoryStatus::MergeConflict => Ok(Self::MergeConflict),
+            EditGitRepositoryStatus::RemoteUnavailable => Ok(Self::RemoteUnavailable),
+        }
+    }
+
+    /// Comments on the PR that added the compiled tests with the names of the compiled tests, and
+    /// the errors of the tests that did not compile, so that reviewers see the same results as
+    /// the slack message.
+    ///
+    /// Nothing is commented when no PR was opened, including when PRs are staged for later.
+    /// Failing to comment is logged rather than returned since the tests were already added.
+    pub async fn comment_compilation_summary<Client: RoswaalGitRepositoryClient>(
+        &self,
+        git_repository: &RoswaalGitRepository<Client>,
+        pr_comment: &impl GithubPullRequestComment,
+    ) {
+        let Self::Success {
+            results,
+            branch_name: Some(branch_name),
+            ..
+        } = self
+        else {
+            return;
+        };
+        let config = RoswaalConfig::current();
+        if config.pull_request_mode() == RoswaalPullRequestMode::Staged {
+            return;
+        }
+        let metadata = git_repository.transaction().await.metadata().clone();
+        let pull_request = metadata.add_tests_pull_request(results, branch_name);
+        let body = compilation_summary_comment(results);
+        let result = retry(config.retry_policy(), is_transient_github_error, || {
+            pr_comment.comment(&pull_request, &body)
+        })
+        .await;
+        match result {
+            Ok(true) => {}
+            Ok(false) => log::error!(
+                "Failed to comment the compilation summary on the PR for {}.",
+                branch_name.to_string()
+            ),
+            Err(error) => log::error!(
+                "Failed to comment the compilation summary on the PR for {}. {}",
+                branch_name.to_string(),
+                error
+            ),
         }
     }
 
-    async fn generate_typescript(
+    async fn generate_code(
         results: &RoswaalTestCompilationResults<'r>,
         metadata: &RoswaalGitRepositoryMetadata,
     ) -> Result<()> {
+        RoswaalOperationStage::Generating.report();
         let mut tests = results.tests();
         tests.dedup_by(|t1, t2| t1.name() == t2.name());
-        let futures = tests.iter().map(|test| {
-            let test = test.clone();
-            let dir_path = metadata.test_dirpath(&test.name());
-            spawn(async move { test.typescript().save_in_dir(&dir_path).await })
-        });
-        for future in futures {
-            future.await??
-        }
+        let file_paths = save_generated_test_code(
+            metadata.code_generator(),
+            tests.iter().filter(|t| !t.is_planned()),
+            |name| metadata.test_dirpath(name),
+            MAX_CONCURRENT_TEST_CODE_SAVES,
+        )
+        .await?;
+        format_generated_files(metadata, &file_paths).await;
         Ok(())
     }
 }
 
+fn compilation_summary_comment(results: &RoswaalTestCompilationResults) -> String {
+    let mut body = "## Compilation Summary\n\nThe following teeeeeests compiled successfully:\n".to_string();
+    for test in results.tests() {
+        body.push_str(&format!("- {}\n", test.name()))
+    }
+    let failures = results.failures();
+    if !failures.is_empty() {
+        body.push_str(
+            "\nThe following tests did not compile, and were not added. They are only listed by the number based on the compilation oooooooooorder!\n"
+        );
+    }
+    for failure in failures {
+        body.push_str(&format!(
+            "\n**Test {}**\n\n```\n{}\n```\n\n",
+            failure.test_number(),
+            failure.syntax().source_code()
+        ));
+        for error in failure.errors() {
+            body.push_str(&format!(
+                "- {} _(Line: {})_\n",
+                compilation_error_message(error),
+                error.line_number()
+            ))
+        }
+    }
+    body
+}
+
+/// Returns true if `flag` appears outside of the code blocks in `tests_str`.
+pub fn has_flag(tests_str: &str, flag: &str) -> bool {
+    // NB: Every other chunk is inside of a code block, so only the chunks outside of them are
+    // checked for the flag.
+    tests_str
+        .split("```")
+        .step_by(2)
+        .flat_map(|text| text.split_whitespace())
+        .any(|word| word == flag)
+}
+
+/// Returns the estimated runtime of the suite of `merged_tests` once the compiled tests in
+/// `results` are merged alongside them, if it exceeds `budget`.
+///
+/// Planned tests are not run, so they never count towards the budget.
+fn suite_runtime_overrun(
+    merged_tests: &[RoswaalTest],
+    results: &RoswaalTestCompilationResults,
+    budget: Duration,
+) -> Option<RoswaalSuiteRuntimeOverrun> {
+    let estimator = RoswaalTestRuntimeEstimator::new(merged_tests);
+    results
+        .tests()
+        .iter()
+        .filter(|test| !test.is_planned())
+        .fold(estimator.suite_estimate(merged_tests), |estimate, test| {
+            estimate.with_test(estimator.compiled_test_estimate(test))
+        })
+        .overrun(budget)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         git::{
+            branch_name::RoswaalGitBranchNamespace,
             metadata::RoswaalGitRepositoryMetadata,
             repo::RoswaalGitRepository,
-            test_support::{read_string, with_clean_test_repo_access, TestGithubPullRequestOpen},
+            test_support::{
+                read_string, with_clean_test_repo_access, write_string, NoopGitRepositoryClient,
+                TestGithubPullRequestComment, TestGithubPullRequestOpen,
+            },
         },
         language::{
             compiler::{RoswaalCompilationError, RoswaalCompilationErrorCode},
             test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
         },
         operations::{add_locations::AddLocationsStatus, merge_branch::MergeBranchStatus},
-        utils::sqlite::RoswaalSqlite,
+        runner::webhook::test_support::NO_TEST_RUNNER,
+        tests_data::query::RoswaalSearchTestsQuery,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
@@ -150,9 +420,11 @@ This is like an invalid test or something...
 ";
             let status = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &RoswaalSqlite::in_memory().await?,
+                &RoswaalDatabase::in_memory().await?,
                 &TestGithubPullRequestOpen::new(false),
-                 &RoswaalGitRepository::noop().await?
+                 &RoswaalGitRepository::noop().await?,
+                 &[],
+                 None,
             ).await?;
             let expected_compiled_test = RoswaalCompiledTest::new(
                 "Basic Leave Event through Exploration as Attendee".to_string(),
@@ -161,41 +433,48 @@ This is like an invalid test or something...
                     RoswaalCompiledTestCommand::Step {
                         label: "Step 1".to_string(),
                         name: "Justin is signed in".to_string(),
-                        requirement: "Ensure Justin has signed into his account".to_string()
+                        requirement: "Ensure Justin has signed into his account".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::Step {
                         label: "Step 2".to_string(),
                         name: "Justin wants to find the nearest event".to_string(),
-                        requirement: "Search for the nearest events, and go to the details for the nearest one".to_string()
+                        requirement: "Search for the nearest events, and go to the details for the nearest one".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::Step {
                         label: "Step 3".to_string(),
                         name: "After finding an event, Justin wants to join it".to_string(),
-                        requirement: "Have Justin join the event".to_string()
+                        requirement: "Have Justin join the event".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::Step {
                         label: "Step 4".to_string(),
                         name: "After some pondering, Justin decides that he is not interested in the event and wants to leave".to_string(),
-                        requirement: "Have Justin leave the event".to_string()
+                        requirement: "Have Justin leave the event".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::Step {
                         label: "Step 5".to_string(),
                         name: "Justin has now left the event".to_string(),
-                        requirement: "Ensure that Justin has left the event successfully".to_string()
+                        requirement: "Ensure that Justin has left the event successfully".to_string(),
+                        retries: None,
                     }
                 ]
-            );
+            )
+            .with_source_code(tests_str.split("```").nth(1).unwrap().trim());
             let expected_compiler_errors = vec![
                 RoswaalCompilationError::new(
                     1,
-                    RoswaalCompilationErrorCode::InvalidCommandName(
-                        "This is like an invalid test or something...".to_string()
-                    )
+                    RoswaalCompilationErrorCode::InvalidCommandName {
+                        name: "This is like an invalid test or something...".to_string(),
+                        did_you_mean: None
+                    }
                 ),
                 RoswaalCompilationError::new(1, RoswaalCompilationErrorCode::NoTestName)
             ];
             match status {
-                AddTestsStatus::Success { results, should_warn_undeleted_branch } => {
+                AddTestsStatus::Success { results, should_warn_undeleted_branch, branch_name: _, target_edits: _, name_conflicts: _, suite_runtime_overrun: _ } => {
                     assert_eq!(results.tests(), vec![expected_compiled_test]);
                     assert_eq!(results.failures()[0].errors(), expected_compiler_errors);
                     assert!(!should_warn_undeleted_branch)
@@ -226,36 +505,47 @@ Requirement 1: Do the thing
 Set Location: Test
 ```
 ";
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let repo = RoswaalGitRepository::noop().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             _ = AddLocationsStatus::from_adding_locations(
                 "Test, 5.0, 5.0",
                 &repo,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await?;
             _ = AddLocationsStatus::from_adding_locations(
                 "Test 2, 5.0, 5.0",
                 &repo,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await?;
             let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            _ = MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
+            _ = MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
             let status = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &TestGithubPullRequestOpen::new(false),
                 &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
             )
             .await?;
             match status {
                 AddTestsStatus::Success {
                     results,
                     should_warn_undeleted_branch: _,
+                    branch_name: _,
+                    target_edits: _,
+                    name_conflicts: _,
+                    suite_runtime_overrun: _,
                 } => {
                     assert_eq!(results.tests()[0].name(), "ABC");
                     assert_eq!(results.failures().len(), 1);
@@ -279,12 +569,14 @@ Requirement 1: Do the thing
 ```
 ";
             let metadata = RoswaalGitRepositoryMetadata::for_testing();
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             _ = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &TestGithubPullRequestOpen::new(false),
                 &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
             )
             .await?;
             let test_case_code =
@@ -328,6 +620,144 @@ export const doTheThing = async () => {
         .unwrap()
     }
 
+    #[tokio::test]
+    async fn does_not_generate_code_for_planned_tests() {
+        with_clean_test_repo_access(async {
+            let tests_str = "\
+```
+Planned Test: Join an event from the map
+Abstract: Users should be able to join events without leaving the map.
+```
+
+```
+New Test: ABC 123
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let database = RoswaalDatabase::in_memory().await?;
+            let status = AddTestsStatus::from_adding_tests(
+                tests_str,
+                &database,
+                &TestGithubPullRequestOpen::new(false),
+                &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
+            )
+            .await?;
+            let planned_dirpath = metadata.test_dirpath("Join an event from the map");
+            assert!(!tokio::fs::try_exists(&planned_dirpath).await?);
+            assert!(tokio::fs::try_exists(&metadata.test_dirpath("ABC 123")).await?);
+            let mut transaction = database.read_transaction().await?;
+            let tests = transaction
+                .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                .await?;
+            transaction.commit().await?;
+            let planned_tests = tests
+                .iter()
+                .map(|t| (t.name(), t.is_planned()))
+                .collect::<Vec<(&str, bool)>>();
+            assert_eq!(
+                planned_tests,
+                vec![("ABC 123", false), ("Join an event from the map", true)]
+            );
+            assert!(status.branch_name().is_some());
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn names_branch_in_namespace_of_repository() {
+        with_clean_test_repo_access(async {
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            let namespace = RoswaalGitBranchNamespace::new("bots/roswaal", '/')?;
+            let metadata =
+                RoswaalGitRepositoryMetadata::for_testing().with_branch_namespace(namespace);
+            let repo = RoswaalGitRepository::<NoopGitRepositoryClient>::open(&metadata).await?;
+            let status = AddTestsStatus::from_adding_tests(
+                tests_str,
+                &RoswaalDatabase::in_memory().await?,
+                &TestGithubPullRequestOpen::new(false),
+                &repo,
+                &[],
+                None,
+            ).await?;
+            let branch_name = status.branch_name().unwrap().to_string();
+            assert!(branch_name.starts_with("bots/roswaal/add-tests-"));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn re_adding_test_keeps_implemented_test_actions() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            _ = AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
+            let test_actions_path = metadata.relative_path("roswaal/abc-123/TestActions.ts");
+            let implemented_code = read_string(&test_actions_path)
+                .await?
+                .replace("throw new Error(\"TODO\")", "await doTheThing()");
+            write_string(&test_actions_path, &implemented_code).await?;
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the thing
+Step 2: Do the other thing
+Requirement 1: Do the thing
+Requirement 2: Do the other thing
+```
+";
+            _ = AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
+            let test_action_code = read_string(&test_actions_path).await?;
+            let expected_test_actions_code = "\
+import { TestAppLaunchConfig } from \"../Launch\"
+
+export const beforeLaunch = async (): Promise<TestAppLaunchConfig> => {
+  // Perform any setup work in here, (setting location, reseting device
+  // permissions, etc.)
+  return {}
+}
+
+export const doTheThing = async () => {
+  // Do the thing
+  await doTheThing()
+}
+
+export const doTheOtherThing = async () => {
+  // Do the other thing
+  throw new Error(\"TODO\")
+}
+";
+            assert_eq!(test_action_code, expected_test_actions_code);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn opens_pr_for_adding_new_tests() {
         with_clean_test_repo_access(async {
@@ -343,13 +773,15 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             _ = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &pr_open,
                 &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
             )
             .await?;
             let pr = pr_open.most_recent_pr().await.unwrap();
@@ -372,13 +804,15 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(true);
             let status = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &pr_open,
                 &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
             )
             .await?;
             assert_eq!(status, AddTestsStatus::FailedToOpenPullRequest);
@@ -398,13 +832,15 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(true);
             let status = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &pr_open,
                 &RoswaalGitRepository::noop_ensuring_merge_conflicts().await?,
+                &[],
+                None,
             )
             .await?;
             assert_eq!(status, AddTestsStatus::MergeConflict);
@@ -417,15 +853,17 @@ Requirement 1: Do the thing
     #[tokio::test]
     async fn reports_no_tests_found_status_when_empty_tests_string() {
         let tests_str = "";
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
         let pr_open = TestGithubPullRequestOpen::new(true);
         let status = AddTestsStatus::from_adding_tests(
             tests_str,
-            &sqlite,
+            &database,
             &pr_open,
             &RoswaalGitRepository::noop_ensuring_merge_conflicts()
                 .await
                 .unwrap(),
+                &[],
+                None,
         )
         .await
         .unwrap();
@@ -442,13 +880,15 @@ dlkjldjlkdjlkjdlkdj
 lkjdlkjlkjalkjslkdjdflkj
 ```
 ";
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
         let pr_open = TestGithubPullRequestOpen::new(true);
         let status = AddTestsStatus::from_adding_tests(
             tests_str,
-            &sqlite,
+            &database,
             &pr_open,
             &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+            None,
         )
         .await
         .unwrap();
@@ -456,8 +896,13 @@ lkjdlkjlkjalkjslkdjdflkj
             AddTestsStatus::Success {
                 results: _,
                 should_warn_undeleted_branch,
+                branch_name,
+                target_edits: _,
+                name_conflicts: _,
+                suite_runtime_overrun: _,
             } => {
-                assert!(!should_warn_undeleted_branch)
+                assert!(!should_warn_undeleted_branch);
+                assert_eq!(branch_name, None)
             }
             _ => panic!(),
         }
@@ -465,6 +910,39 @@ lkjdlkjlkjalkjslkdjdflkj
         assert_eq!(pr, None)
     }
 
+    #[tokio::test]
+    async fn saves_non_compiling_tests_for_the_submitting_user() {
+        let tests_str = "\
+```
+New Test: Bob
+```
+```
+New Test: Alice
+Step 1: A
+Requirement 1: B
+```
+";
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let pr_open = TestGithubPullRequestOpen::new(true);
+        AddTestsStatus::from_adding_tests(
+            tests_str,
+            &database,
+            &pr_open,
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+            Some("U1"),
+        )
+        .await
+        .unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let failures = transaction.recent_compilation_failures("U1", 5).await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].syntax().trim(), "New Test: Bob");
+        assert_eq!(failures[0].error_messages().len(), 1);
+        let failures = transaction.recent_compilation_failures("U2", 5).await.unwrap();
+        assert_eq!(failures, vec![])
+    }
+
     #[tokio::test]
     async fn ensure_no_conflicts_when_generating_code_with_duplicate_test_names() {
         with_clean_test_repo_access(async {
@@ -482,9 +960,11 @@ Requirement 1: D
 ";
             AddTestsStatus::from_adding_tests(
                 tests_str,
-                &RoswaalSqlite::in_memory().await?,
+                &RoswaalDatabase::in_memory().await?,
                 &TestGithubPullRequestOpen::new(false),
                 &RoswaalGitRepository::noop().await.unwrap(),
+                &[],
+                None,
             )
             .await?;
             let expected_test_case_code = "\
@@ -513,4 +993,192 @@ test(\"Bob\", async () => {
         .await
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn does_not_add_tests_with_the_same_name_as_merged_tests() {
+        with_clean_test_repo_access(async {
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            _ = AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
+            let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
+            _ = MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the other thing
+Requirement 1: Do the other thing
+```
+```
+New Test: I am the strong
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            let status =
+                AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                    .await?;
+            match status {
+                AddTestsStatus::Success {
+                    results,
+                    name_conflicts,
+                    ..
+                } => {
+                    assert_eq!(name_conflicts, vec!["ABC 123".to_string()]);
+                    assert_eq!(results.tests().len(), 1);
+                    assert_eq!(results.tests()[0].name(), "I am the strong");
+                }
+                _ => panic!(),
+            }
+            let pr = pr_open.most_recent_pr().await.unwrap();
+            assert!(pr.title().contains("Add Tests \"I am the strong\""));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn replaces_merged_tests_with_the_same_name_when_overwriting() {
+        with_clean_test_repo_access(async {
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            _ = AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
+            let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
+            _ = MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
+            let tests_str = "\
+--overwrite
+```
+New Test: ABC 123
+Step 1: Do the other thing
+Requirement 1: Do the other thing
+```
+";
+            let status =
+                AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                    .await?;
+            match status {
+                AddTestsStatus::Success {
+                    results,
+                    name_conflicts,
+                    ..
+                } => {
+                    assert_eq!(name_conflicts, Vec::<String>::new());
+                    assert_eq!(results.tests()[0].name(), "ABC 123");
+                }
+                _ => panic!(),
+            }
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn comments_compilation_summary_on_the_opened_pr() {
+        with_clean_test_repo_access(async {
+            let tests_str = "\
+```
+New Test: ABC 123
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+```
+New Test: Bob
+Step 1: A
+```
+";
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let pr_comment = TestGithubPullRequestComment::new(false);
+            let status = AddTestsStatus::from_adding_tests(
+                tests_str,
+                &RoswaalDatabase::in_memory().await?,
+                &pr_open,
+                &repo,
+                &[],
+                None,
+            )
+            .await?;
+            status.comment_compilation_summary(&repo, &pr_comment).await;
+            let comments = pr_comment.comments().await;
+            assert_eq!(comments.len(), 1);
+            assert_eq!(Some(&comments[0].0), pr_open.most_recent_pr().await.as_ref());
+            let expected_body = "\
+## Compilation Summary
+
+The following teeeeeests compiled successfully:
+- ABC 123
+
+The following tests did not compile, and were not added. They are only listed by the number based on the compilation oooooooooorder!
+
+**Test 2**
+
+```
+New Test: Bob
+Step 1: A
+```
+
+- No test steps were speeeeeeeecified. _(Line: 2)_
+- \"Step 1: A\" has no matching requiremeeeeeeeeeeent. _(Line: 2)_
+";
+            assert_eq!(comments[0].1, expected_body);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_not_comment_when_no_pr_was_opened() {
+        let repo = RoswaalGitRepository::noop().await.unwrap();
+        let pr_comment = TestGithubPullRequestComment::new(false);
+        let status = AddTestsStatus::from_adding_tests(
+            "```\nlkjdlkjlkjalkjslkdjdflkj\n```",
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+            &repo,
+            &[],
+            None,
+        )
+        .await
+        .unwrap();
+        status.comment_compilation_summary(&repo, &pr_comment).await;
+        assert!(pr_comment.comments().await.is_empty())
+    }
+
+    #[test]
+    fn overwrite_flag_is_only_detected_outside_of_code_blocks() {
+        assert!(has_flag("--overwrite\n```\nNew Test: A\n```", OVERWRITE_FLAG));
+        assert!(has_flag("```\nNew Test: A\n``` --overwrite", OVERWRITE_FLAG));
+        assert!(!has_flag("```\nNew Test: --overwrite\n```", OVERWRITE_FLAG));
+        assert!(!has_flag("--overwrite-all ```\nNew Test: A\n```", OVERWRITE_FLAG));
+    }
 }
@@ -0,0 +1,180 @@
+use anyhow::Result;
+
+use crate::{
+    slack::users::slack_user_id_from_mention,
+    tests_data::{
+        query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+        test::RoswaalTestProgressStatus,
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AssignFailureStatus {
+    Success {
+        test_name: String,
+        assignee_slack_user_id: String,
+    },
+    MissingTestName,
+    InvalidUser(String),
+    UnknownTest(String),
+    TestNotFailing(String),
+}
+
+impl AssignFailureStatus {
+    /// Assigns the user mentioned at the end of `command_str` to triage the failure of the merged
+    /// test named by the rest of `command_str`.
+    ///
+    /// The assignment is cleared automatically once a passing run of the test is uploaded.
+    pub async fn from_assigning(command_str: &str, database: &RoswaalDatabase) -> Result<Self> {
+        let Some((test_name, mention)) = command_str.trim().rsplit_once(char::is_whitespace) else {
+            return match slack_user_id_from_mention(command_str.trim()) {
+                Some(_) => Ok(Self::MissingTestName),
+                None => Ok(Self::InvalidUser(command_str.trim().to_string())),
+            };
+        };
+        let Some(assignee_slack_user_id) = slack_user_id_from_mention(mention) else {
+            return Ok(Self::InvalidUser(mention.to_string()));
+        };
+        let test_name = test_name.trim();
+        let mut transaction = database.transaction().await?;
+        with_transaction!(transaction, async {
+            let query = RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new(test_name));
+            let tests = transaction.tests_in_alphabetical_order(&query).await?;
+            let Some(test) = tests
+                .into_iter()
+                .find(|t| t.unmerged_branch_name().is_none())
+            else {
+                return Ok(Self::UnknownTest(test_name.to_string()));
+            };
+            if test.progress_status() != RoswaalTestProgressStatus::Failed {
+                return Ok(Self::TestNotFailing(test.name().to_string()));
+            }
+            transaction
+                .assign_test_failure(test.name(), &assignee_slack_user_id)
+                .await?;
+            Ok(Self::Success {
+                test_name: test.name().to_string(),
+                assignee_slack_user_id,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::test::RoswaalCompiledTest,
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_invalid_user_when_the_mention_is_not_a_user() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = AssignFailureStatus::from_assigning("Blob @someone", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            AssignFailureStatus::InvalidUser("@someone".to_string())
+        );
+        let status = AssignFailureStatus::from_assigning("<@U123>", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, AssignFailureStatus::MissingTestName)
+    }
+
+    #[tokio::test]
+    async fn reports_unknown_test_when_no_merged_test_has_the_name() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = AssignFailureStatus::from_assigning("Missing Test <@U123>", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            AssignFailureStatus::UnknownTest("Missing Test".to_string())
+        )
+    }
+
+    #[tokio::test]
+    async fn only_assigns_failing_tests() {
+        let database = setup_failing_test().await;
+        let status = AssignFailureStatus::from_assigning("other <@U123>", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            AssignFailureStatus::TestNotFailing("Other".to_string())
+        );
+        let status = AssignFailureStatus::from_assigning("blob test <@U123|roswaal>", &database)
+            .await
+            .unwrap();
+        let expected_status = AssignFailureStatus::Success {
+            test_name: "Blob Test".to_string(),
+            assignee_slack_user_id: "U123".to_string(),
+        };
+        assert_eq!(status, expected_status);
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let assignees = tests
+            .iter()
+            .map(|t| t.failure_assignee_slack_user_id().cloned())
+            .collect::<Vec<Option<String>>>();
+        assert_eq!(assignees, vec![Some("U123".to_string()), None])
+    }
+
+    #[tokio::test]
+    async fn clears_the_assignment_when_the_test_passes() {
+        let database = setup_failing_test().await;
+        AssignFailureStatus::from_assigning("Blob Test <@U123>", &database)
+            .await
+            .unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let progress = vec![RoswaalTestProgressUpload::new(
+            "Blob Test".to_string(),
+            None,
+            None,
+        )];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert!(tests
+            .iter()
+            .all(|t| t.failure_assignee_slack_user_id().is_none()))
+    }
+
+    async fn setup_failing_test() -> RoswaalDatabase {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = vec![
+            RoswaalCompiledTest::new("Blob Test".to_string(), None, vec![]),
+            RoswaalCompiledTest::new("Other".to_string(), None, vec![]),
+        ];
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let progress = vec![
+            RoswaalTestProgressUpload::new(
+                "Blob Test".to_string(),
+                Some(RoswaalTestCommandOrdinal::new(0)),
+                None,
+            ),
+            RoswaalTestProgressUpload::new("Other".to_string(), None, None),
+        ];
+        transaction.save_test_progess(&progress).await.unwrap();
+        transaction.commit().await.unwrap();
+        database
+    }
+}
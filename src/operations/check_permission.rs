@@ -0,0 +1,159 @@
+use anyhow::Result;
+
+use crate::{
+    slack::{
+        command::RoswaalSlackCommand,
+        permissions::{RoswaalSlackPermissionSubject, SlackFetchUserGroupMembers},
+        users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CheckPermissionStatus {
+    Allowed,
+    Denied {
+        command: RoswaalSlackCommand,
+        subjects: Vec<RoswaalSlackPermissionSubject>,
+        roster: RoswaalSlackUserRoster,
+    },
+}
+
+impl CheckPermissionStatus {
+    /// Checks whether the user with `user_id` is allowed to run `command`.
+    ///
+    /// Infra owners, and users that were granted `command` directly, are allowed without looking
+    /// up any user groups. Otherwise, the members of each user group granted `command` are
+    /// fetched until one of them contains the user.
+    pub async fn from_checking_permission(
+        command: RoswaalSlackCommand,
+        user_id: &str,
+        database: &RoswaalDatabase,
+        user_groups: &impl SlackFetchUserGroupMembers,
+    ) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        let (permissions, roster) = with_transaction!(transaction, async {
+            let permissions = transaction.slack_command_permissions().await?;
+            let roster = transaction.slack_user_roster().await?;
+            Ok((permissions, roster))
+        })?;
+        let subjects = permissions.subjects(command);
+        if subjects.is_empty() || roster.has_role(user_id, RoswaalSlackUserRole::InfraOwner) {
+            return Ok(Self::Allowed);
+        }
+        let is_granted_directly = subjects
+            .iter()
+            .any(|s| *s == RoswaalSlackPermissionSubject::User(user_id.to_string()));
+        if is_granted_directly {
+            return Ok(Self::Allowed);
+        }
+        for subject in subjects {
+            let RoswaalSlackPermissionSubject::UserGroup(group_id) = subject else {
+                continue;
+            };
+            let members = user_groups.fetch_user_group_members(group_id).await?;
+            if members.iter().any(|member| member == user_id) {
+                return Ok(Self::Allowed);
+            }
+        }
+        Ok(Self::Denied {
+            command,
+            subjects: subjects.to_vec(),
+            roster,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        slack::permissions::test_support::TestSlackUserGroupFetch, utils::config::RoswaalConfig,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn allows_anyone_to_run_commands_without_subjects() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = CheckPermissionStatus::from_checking_permission(
+            RoswaalSlackCommand::RemoveTests,
+            "U1",
+            &database,
+            &TestSlackUserGroupFetch::new(&[]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, CheckPermissionStatus::Allowed)
+    }
+
+    #[tokio::test]
+    async fn allows_granted_users_members_of_granted_groups_and_infra_owners() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let subjects = vec![
+            RoswaalSlackPermissionSubject::User("U1".to_string()),
+            RoswaalSlackPermissionSubject::UserGroup("S1".to_string()),
+        ];
+        grant(&database, &subjects).await;
+        let user_groups = TestSlackUserGroupFetch::new(&[("S1", &["U2"])]);
+        let maintainer_id = RoswaalConfig::current().maintainer_slack_user_id();
+        for user_id in ["U1", "U2", maintainer_id] {
+            let status = CheckPermissionStatus::from_checking_permission(
+                RoswaalSlackCommand::RemoveTests,
+                user_id,
+                &database,
+                &user_groups,
+            )
+            .await
+            .unwrap();
+            assert_eq!(status, CheckPermissionStatus::Allowed)
+        }
+        let status = CheckPermissionStatus::from_checking_permission(
+            RoswaalSlackCommand::RemoveTests,
+            "U3",
+            &database,
+            &user_groups,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            CheckPermissionStatus::Denied {
+                command: RoswaalSlackCommand::RemoveTests,
+                subjects,
+                roster: RoswaalSlackUserRoster::default()
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn does_not_fetch_groups_for_directly_granted_users() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        grant(
+            &database,
+            &[
+                RoswaalSlackPermissionSubject::UserGroup("S1".to_string()),
+                RoswaalSlackPermissionSubject::User("U1".to_string()),
+            ],
+        )
+        .await;
+        let status = CheckPermissionStatus::from_checking_permission(
+            RoswaalSlackCommand::RemoveTests,
+            "U1",
+            &database,
+            &TestSlackUserGroupFetch::new(&[]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, CheckPermissionStatus::Allowed)
+    }
+
+    async fn grant(database: &RoswaalDatabase, subjects: &[RoswaalSlackPermissionSubject]) {
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .set_slack_command_subjects(RoswaalSlackCommand::RemoveTests, subjects)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap()
+    }
+}
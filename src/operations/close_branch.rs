@@ -1,6 +1,6 @@
 use crate::{
     git::branch_name::{self, RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
-    utils::sqlite::RoswaalSqlite,
+    utils::db::RoswaalDatabase,
     with_transaction,
 };
 use anyhow::Result;
@@ -13,11 +13,11 @@ pub enum CloseBranchStatus<'a> {
 impl<'a> CloseBranchStatus<'a> {
     pub async fn from_closing_branch(
         branch_name: &'a RoswaalOwnedGitBranchName,
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
     ) -> Result<Self> {
         match branch_name.kind() {
             Some(kind) => {
-                let mut transaction = sqlite.transaction().await?;
+                let mut transaction = database.transaction().await?;
                 with_transaction!(transaction, async {
                     match kind {
                         RoswaalOwnedBranchKind::AddTests => {
@@ -29,6 +29,12 @@ impl<'a> CloseBranchStatus<'a> {
                         RoswaalOwnedBranchKind::RemoveTests => {
                             transaction.close_remove_tests_branch(branch_name).await?;
                         }
+                        RoswaalOwnedBranchKind::RemoveLocations => {
+                            transaction
+                                .close_remove_locations_branch(branch_name)
+                                .await?;
+                        }
+                        RoswaalOwnedBranchKind::RemoveOrphanedTests => {}
                     };
                     Ok(Self::Closed(kind))
                 })
@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    git::{
+        pull_request::GithubPullRequestOpen,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    tests_data::query::RoswaalTestNamesString,
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+use super::remove_tests::RemoveTestsStatus;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeprecateTestsStatus {
+    Success {
+        deprecated_test_names: Vec<String>,
+        /// The earliest date that the code of the deprecated tests will be removed on.
+        removal_date: DateTime<Utc>,
+    },
+    NoTestsDeprecated,
+}
+
+impl DeprecateTestsStatus {
+    /// Marks each merged test named in `query_str` as deprecated at `now`.
+    ///
+    /// Deprecated tests are hidden from the test runner right away, but their code is only
+    /// removed once `grace_period` has passed so that the deprecation can be undone by adding the
+    /// test again.
+    pub async fn from_deprecating_tests(
+        query_str: &str,
+        database: &RoswaalDatabase,
+        now: DateTime<Utc>,
+        grace_period: Duration,
+    ) -> Result<Self> {
+        let test_names = RoswaalTestNamesString::new(query_str);
+        let mut transaction = database.transaction().await?;
+        let deprecated_test_names = with_transaction!(transaction, async {
+            transaction.deprecate_tests(&test_names, now).await
+        })?;
+        if deprecated_test_names.is_empty() {
+            Ok(Self::NoTestsDeprecated)
+        } else {
+            Ok(Self::Success {
+                deprecated_test_names,
+                removal_date: now + chrono::Duration::from_std(grace_period)?,
+            })
+        }
+    }
+}
+
+/// Opens a PR that removes the code of each test whose deprecation `grace_period` ended before
+/// `now`, and returns the status of the removal.
+///
+/// Returns None without touching `git_repository` if no grace periods have ended.
+pub async fn remove_expired_deprecated_tests<Client: RoswaalGitRepositoryClient>(
+    database: &RoswaalDatabase,
+    git_repository: &RoswaalGitRepository<Client>,
+    pr_open: &impl GithubPullRequestOpen,
+    target_repositories: &[RoswaalGitRepository<Client>],
+    now: DateTime<Utc>,
+    grace_period: Duration,
+) -> Result<Option<RemoveTestsStatus>> {
+    let cutoff_date = now - chrono::Duration::from_std(grace_period)?;
+    let mut transaction = database.read_transaction().await?;
+    let test_names = with_transaction!(transaction, async {
+        transaction.expired_deprecated_test_names(cutoff_date).await
+    })?;
+    if test_names.is_empty() {
+        return Ok(None);
+    }
+    let status = RemoveTestsStatus::from_removing_tests(
+        &test_names.join("\n"),
+        database,
+        git_repository,
+        pr_open,
+        target_repositories,
+    )
+    .await?;
+    Ok(Some(status))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::{
+            branch_name::RoswaalOwnedGitBranchName,
+            metadata::RoswaalGitRepositoryMetadata,
+            test_support::{with_clean_test_repo_access, TestGithubPullRequestOpen},
+        },
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        operations::{add_tests::AddTestsStatus, merge_branch::MergeBranchStatus},
+        runner::webhook::test_support::NO_TEST_RUNNER,
+        tests_data::query::RoswaalSearchTestsQuery,
+    };
+
+    use super::*;
+
+    const GRACE_PERIOD: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+    #[tokio::test]
+    async fn reports_no_tests_deprecated_when_no_merged_tests_match() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = DeprecateTestsStatus::from_deprecating_tests(
+            "Blob",
+            &database,
+            Utc::now(),
+            GRACE_PERIOD,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, DeprecateTestsStatus::NoTestsDeprecated)
+    }
+
+    #[tokio::test]
+    async fn deprecates_merged_tests_until_the_end_of_the_grace_period() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_test(&database, "Blob").await.unwrap();
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let status = DeprecateTestsStatus::from_deprecating_tests(
+            "blob\nMissing",
+            &database,
+            now,
+            GRACE_PERIOD,
+        )
+        .await
+        .unwrap();
+        let expected_status = DeprecateTestsStatus::Success {
+            deprecated_test_names: vec!["Blob".to_string()],
+            removal_date: DateTime::from_timestamp(1_701_209_600, 0).unwrap(),
+        };
+        assert_eq!(status, expected_status)
+    }
+
+    #[tokio::test]
+    async fn does_not_remove_tests_before_the_grace_period_ends() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            save_merged_test(&database, "Blob").await?;
+            let now = Utc::now();
+            DeprecateTestsStatus::from_deprecating_tests("Blob", &database, now, GRACE_PERIOD)
+                .await?;
+            let status = remove_expired_deprecated_tests(
+                &database,
+                &RoswaalGitRepository::noop().await?,
+                &TestGithubPullRequestOpen::new(false),
+                &[],
+                now + chrono::Duration::days(13),
+                GRACE_PERIOD,
+            )
+            .await?;
+            assert_eq!(status, None);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn removes_tests_after_the_grace_period_ends() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            let tests_str = "\
+```
+New Test: Blob
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+            AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
+            MergeBranchStatus::from_merging_branch_with_name(
+                &pr_open.most_recent_head_branch_name().await.unwrap(),
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
+            let deprecation_date = Utc::now() - chrono::Duration::days(15);
+            DeprecateTestsStatus::from_deprecating_tests(
+                "Blob",
+                &database,
+                deprecation_date,
+                GRACE_PERIOD,
+            )
+            .await?;
+            let mut transaction = database.read_transaction().await?;
+            let deprecated_tests = with_transaction!(transaction, async {
+                transaction
+                    .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                    .await
+            })?;
+            let status = remove_expired_deprecated_tests(
+                &database,
+                &repo,
+                &pr_open,
+                &[],
+                Utc::now(),
+                GRACE_PERIOD,
+            )
+            .await?;
+            let expected_status = RemoveTestsStatus::Success {
+                removed_test_names: vec!["Blob".to_string()],
+                removed_tests: deprecated_tests,
+                should_warn_undeleted_branch: false,
+                branch_name: pr_open.most_recent_head_branch_name().await,
+                target_edits: vec![],
+            };
+            assert_eq!(status, Some(expected_status));
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            assert!(!tokio::fs::try_exists(metadata.relative_path("roswaal/blob")).await?);
+            let status = remove_expired_deprecated_tests(
+                &database,
+                &repo,
+                &pr_open,
+                &[],
+                Utc::now(),
+                GRACE_PERIOD,
+            )
+            .await?;
+            assert_eq!(status, None);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn save_merged_test(database: &RoswaalDatabase, name: &str) -> Result<()> {
+        let test = RoswaalCompiledTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        );
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let mut transaction = database.transaction().await?;
+        transaction.save_tests(&vec![test], &branch_name).await?;
+        transaction.merge_unmerged_tests(&branch_name).await?;
+        transaction.commit().await
+    }
+}
@@ -0,0 +1,146 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::{
+    slack::users::{slack_user_id_from_mention, RoswaalSlackUserRole, RoswaalSlackUserRoster},
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EditRosterStatus {
+    /// No edit was requested, so the current roster is shown.
+    Roster(RoswaalSlackUserRoster),
+    Success {
+        role: RoswaalSlackUserRole,
+        roster: RoswaalSlackUserRoster,
+    },
+    InvalidRole(String),
+    InvalidUser(String),
+    NotAuthorized {
+        roster: RoswaalSlackUserRoster,
+    },
+}
+
+impl EditRosterStatus {
+    /// Edits the roster from a command string in the form `<role> <@user> <@user>...`, where the
+    /// mentioned users replace the existing users of the role.
+    ///
+    /// Mentioning no users resets the role to the maintainer in the current config. An empty
+    /// command string only loads the current roster. Only infra owners are allowed to edit the
+    /// roster.
+    pub async fn from_editing_roster(
+        roster_str: &str,
+        user_id: &str,
+        database: &RoswaalDatabase,
+    ) -> Result<Self> {
+        let mut words = roster_str.split_whitespace();
+        let role = match words.next() {
+            Some(name) => match RoswaalSlackUserRole::from_str(name) {
+                Ok(role) => Some(role),
+                Err(_) => return Ok(Self::InvalidRole(name.to_string())),
+            },
+            None => None,
+        };
+        let mut user_ids = Vec::<String>::new();
+        for mention in words {
+            match slack_user_id_from_mention(mention) {
+                Some(id) => user_ids.push(id),
+                None => return Ok(Self::InvalidUser(mention.to_string())),
+            }
+        }
+        let mut transaction = database.transaction().await?;
+        with_transaction!(transaction, async {
+            let roster = transaction.slack_user_roster().await?;
+            let Some(role) = role else {
+                return Ok(Self::Roster(roster));
+            };
+            if !roster.has_role(user_id, RoswaalSlackUserRole::InfraOwner) {
+                return Ok(Self::NotAuthorized { roster });
+            }
+            transaction.set_slack_user_ids(role, &user_ids).await?;
+            let roster = transaction.slack_user_roster().await?;
+            Ok(Self::Success { role, roster })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::config::RoswaalConfig;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_roster_for_empty_string() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = EditRosterStatus::from_editing_roster("  ", "U1", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            EditRosterStatus::Roster(RoswaalSlackUserRoster::default())
+        )
+    }
+
+    #[tokio::test]
+    async fn maintainer_can_hand_off_roles() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let maintainer_id = RoswaalConfig::current().maintainer_slack_user_id();
+        let status = EditRosterStatus::from_editing_roster(
+            "infra-owner <@U1|justin> U2",
+            maintainer_id,
+            &database,
+        )
+        .await
+        .unwrap();
+        let roster = RoswaalSlackUserRoster::default()
+            .with_user_ids(RoswaalSlackUserRole::InfraOwner, &["U1", "U2"]);
+        assert_eq!(
+            status,
+            EditRosterStatus::Success {
+                role: RoswaalSlackUserRole::InfraOwner,
+                roster: roster.clone()
+            }
+        );
+
+        let status = EditRosterStatus::from_editing_roster(
+            "merge-conflict-owner <@U3>",
+            maintainer_id,
+            &database,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, EditRosterStatus::NotAuthorized { roster });
+
+        let status = EditRosterStatus::from_editing_roster("infra-owner", "U2", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            EditRosterStatus::Success {
+                role: RoswaalSlackUserRole::InfraOwner,
+                roster: RoswaalSlackUserRoster::default()
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_roles_and_users() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let maintainer_id = RoswaalConfig::current().maintainer_slack_user_id();
+        let status = EditRosterStatus::from_editing_roster("owner <@U1>", maintainer_id, &database)
+            .await
+            .unwrap();
+        assert_eq!(status, EditRosterStatus::InvalidRole("owner".to_string()));
+        let status = EditRosterStatus::from_editing_roster(
+            "infra-owner <@U1> @justin",
+            maintainer_id,
+            &database,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, EditRosterStatus::InvalidUser("@justin".to_string()))
+    }
+}
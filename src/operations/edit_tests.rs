@@ -0,0 +1,422 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::{
+    generation::{
+        formatter::format_generated_files,
+        interface::{CodeGenerator, GeneratedTestCode},
+    },
+    git::{
+        branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+        edit::{EditGitRepositoryStatus, TargetRepositoryEdit},
+        metadata::RoswaalGitRepositoryMetadata,
+        pull_request::GithubPullRequestOpen,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    language::{
+        ast::extract_tests_syntax,
+        compilation_results::{RoswaalTestCompilationFailure, RoswaalTestCompilationResults},
+        diff::RoswaalCompiledTestDiff,
+        test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+    },
+    location::storage::LoadLocationsFilter,
+    tests_data::query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+    utils::{db::RoswaalDatabase, stage::RoswaalOperationStage},
+    with_transaction,
+};
+
+use super::{
+    add_tests::{has_flag, NORMALIZE_STEPS_FLAG},
+    resource_coordinator::ResourceCoordinator,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EditTestStatus<'r> {
+    Success {
+        diff: RoswaalCompiledTestDiff,
+        should_warn_undeleted_branch: bool,
+        branch_name: RoswaalOwnedGitBranchName,
+        /// The results of editing the test in each additional target repository.
+        target_edits: Vec<TargetRepositoryEdit>,
+    },
+    NoTestsFound,
+    MultipleTestsFound,
+    CompilationFailed(RoswaalTestCompilationFailure<'r>),
+    TestNotFound {
+        name: String,
+    },
+    NoChanges,
+    MergeConflict,
+    RemoteUnavailable,
+    FailedToOpenPullRequest,
+}
+
+impl<'r> EditTestStatus<'r> {
+    /// Returns the branch that the test was edited on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::Success { branch_name, .. } => Some(branch_name),
+            _ => None,
+        }
+    }
+}
+
+impl<'r> EditTestStatus<'r> {
+    /// Compiles the single test in `test_str`, and opens a PR that updates the code of the stored
+    /// test with the same name to match it.
+    ///
+    /// The edited test is diffed step by step against the stored test, and only the generated
+    /// files whose contents change are written. The edit is stored on an add tests branch, so
+    /// merging or closing its PR replaces or discards the edited test like any other added test.
+    pub async fn from_editing_test<Client: RoswaalGitRepositoryClient>(
+        test_str: &'r str,
+        database: &RoswaalDatabase,
+        pr_open: &impl GithubPullRequestOpen,
+        git_repository: &RoswaalGitRepository<Client>,
+        target_repositories: &[RoswaalGitRepository<Client>],
+    ) -> Result<Self> {
+        let tests_syntax = extract_tests_syntax(test_str);
+        match tests_syntax.len() {
+            0 => return Ok(Self::NoTestsFound),
+            1 => {}
+            _ => return Ok(Self::MultipleTestsFound),
+        }
+
+        let mut resources = ResourceCoordinator::new(database, git_repository);
+        let mut transaction = resources.database().await?;
+        let (location_names, location_aliases, test_dependencies) =
+            with_transaction!(transaction, async {
+                let location_names = transaction
+                    .location_names_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
+                    .await?;
+                Ok((
+                    location_names,
+                    transaction.location_aliases_in_alphabetical_order().await?,
+                    transaction.merged_test_dependency_graph().await?,
+                ))
+            })?;
+        RoswaalOperationStage::Compiling.report();
+        let results = RoswaalTestCompilationResults::compile_with_test_dependencies(
+            tests_syntax,
+            &location_names,
+            &location_aliases,
+            &test_dependencies,
+            has_flag(test_str, NORMALIZE_STEPS_FLAG),
+        );
+        if let Some(failure) = results.failures().into_iter().next() {
+            return Ok(Self::CompilationFailed(failure));
+        }
+        let test = results.tests().remove(0);
+
+        let query = RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new(test.name()));
+        transaction = resources.database().await?;
+        let stored_tests = with_transaction!(transaction, async {
+            transaction.tests_in_alphabetical_order(&query).await
+        })?;
+        let stored_test = stored_tests
+            .iter()
+            .find(|t| t.unmerged_branch_name().is_none())
+            .or(stored_tests.first());
+        let Some(stored_test) = stored_test else {
+            return Ok(Self::TestNotFound {
+                name: test.name().to_string(),
+            });
+        };
+        let stored_commands = stored_test
+            .commands()
+            .iter()
+            .map(|c| c.compiled_command().clone())
+            .collect::<Vec<RoswaalCompiledTestCommand>>();
+        let diff = RoswaalCompiledTestDiff::new(stored_test.description(), &stored_commands, &test);
+        if !diff.has_changes() {
+            return Ok(Self::NoChanges);
+        }
+
+        let git_transaction = resources.git().await;
+        let metadata = git_transaction.metadata().clone();
+        let branch_name = metadata.new_branch_name(RoswaalOwnedBranchKind::AddTests);
+        let edit_status = EditGitRepositoryStatus::from_editing_new_branch(
+            &branch_name,
+            git_transaction,
+            pr_open,
+            async {
+                Self::regenerate_code(&test, &metadata).await?;
+                Ok((metadata.edit_test_pull_request(&diff, &branch_name), ()))
+            },
+        )
+        .await?;
+
+        match edit_status {
+            EditGitRepositoryStatus::Success {
+                did_delete_branch,
+                value: _,
+            } => {
+                transaction = resources.database().await?;
+                with_transaction!(transaction, async {
+                    transaction
+                        .save_tests(&vec![test.clone()], &branch_name)
+                        .await
+                })?;
+                let target_edits = TargetRepositoryEdit::from_editing_targets(
+                    &branch_name,
+                    target_repositories,
+                    pr_open,
+                    |metadata| {
+                        let (test, diff, branch_name) = (&test, &diff, &branch_name);
+                        async move {
+                            Self::regenerate_code(test, &metadata).await?;
+                            Ok(metadata.edit_test_pull_request(diff, branch_name))
+                        }
+                    },
+                )
+                .await;
+                Ok(Self::Success {
+                    diff,
+                    should_warn_undeleted_branch: !did_delete_branch,
+                    branch_name,
+                    target_edits,
+                })
+            }
+            EditGitRepositoryStatus::FailedToOpenPullRequest => Ok(Self::FailedToOpenPullRequest),
+            EditGitRepositoryStatus::MergeConflict => Ok(Self::MergeConflict),
+            EditGitRepositoryStatus::RemoteUnavailable => Ok(Self::RemoteUnavailable),
+        }
+    }
+
+    /// Writes the generated files for `test` whose contents differ from the existing files,
+    /// keeping any hand written code that the generator knows how to preserve.
+    ///
+    /// Nothing is written for planned tests.
+    async fn regenerate_code(
+        test: &RoswaalCompiledTest,
+        metadata: &RoswaalGitRepositoryMetadata,
+    ) -> Result<()> {
+        if test.is_planned() {
+            return Ok(());
+        }
+        RoswaalOperationStage::Generating.report();
+        let generator = metadata.code_generator();
+        let dir_path = metadata.test_dirpath(test.name());
+        let code = generator.generate(test);
+        let existing_code = code.read_existing_in_dir(&dir_path).await?;
+        let merged_code = generator.merge_existing(code, &existing_code);
+        let changed_files = merged_code
+            .files()
+            .iter()
+            .filter(|(path, contents)| existing_code.file(path) != Some(contents.as_str()))
+            .cloned()
+            .collect::<Vec<(String, String)>>();
+        let changed_paths = changed_files
+            .iter()
+            .map(|(path, _)| dir_path.join(path))
+            .collect::<Vec<PathBuf>>();
+        GeneratedTestCode::new(changed_files)
+            .save_in_dir(&dir_path)
+            .await?;
+        format_generated_files(metadata, &changed_paths).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        git::test_support::{
+            read_string, with_clean_test_repo_access, write_string, TestGithubPullRequestOpen,
+        },
+        language::diff::RoswaalTestCommandChange,
+        operations::{add_tests::AddTestsStatus, merge_branch::MergeBranchStatus},
+        runner::webhook::test_support::NO_TEST_RUNNER,
+    };
+
+    const ORIGINAL_TEST: &str = "\
+```
+New Test: Edit Me
+Step 1: Do the thing
+Step 2: Do the last thing
+Requirement 1: Do the thing
+Requirement 2: Do the last thing
+```
+";
+
+    #[tokio::test]
+    async fn reports_no_tests_found_when_no_tests_in_string() {
+        let status = EditTestStatus::from_editing_test(
+            "",
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, EditTestStatus::NoTestsFound)
+    }
+
+    #[tokio::test]
+    async fn reports_multiple_tests_found_when_editing_more_than_one_test() {
+        let tests_str = format!("{}{}", ORIGINAL_TEST, ORIGINAL_TEST);
+        let status = EditTestStatus::from_editing_test(
+            &tests_str,
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, EditTestStatus::MultipleTestsFound)
+    }
+
+    #[tokio::test]
+    async fn reports_test_not_found_when_test_is_not_stored() {
+        let status = EditTestStatus::from_editing_test(
+            ORIGINAL_TEST,
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            EditTestStatus::TestNotFound {
+                name: "Edit Me".to_string()
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn reports_compilation_failure_when_edited_test_does_not_compile() {
+        let tests_str = "\
+```
+New Test: Edit Me
+Step 1: Do the thing
+```
+";
+        let status = EditTestStatus::from_editing_test(
+            tests_str,
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+        )
+        .await
+        .unwrap();
+        assert!(matches!(status, EditTestStatus::CompilationFailed(_)))
+    }
+
+    #[tokio::test]
+    async fn reports_no_changes_when_edited_test_is_identical() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_original_test(&database, &pr_open, &repo).await?;
+            let status =
+                EditTestStatus::from_editing_test(ORIGINAL_TEST, &database, &pr_open, &repo, &[])
+                    .await?;
+            assert_eq!(status, EditTestStatus::NoChanges);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn opens_pr_with_diff_and_keeps_implemented_actions() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_original_test(&database, &pr_open, &repo).await?;
+            let test_actions_path = metadata.relative_path("roswaal/edit-me/TestActions.ts");
+            let implemented_code = read_string(&test_actions_path)
+                .await?
+                .replace("throw new Error(\"TODO\")", "await doTheThing()");
+            write_string(&test_actions_path, &implemented_code).await?;
+            let edited_test = "\
+```
+New Test: Edit Me
+Step 1: Do the thing
+Step 2: Do the new thing
+Step 3: Do the last thing
+Requirement 1: Do the thing
+Requirement 2: Do the new thing
+Requirement 3: Do the last thing
+```
+";
+            let status =
+                EditTestStatus::from_editing_test(edited_test, &database, &pr_open, &repo, &[])
+                    .await?;
+            let EditTestStatus::Success {
+                diff,
+                should_warn_undeleted_branch,
+                branch_name,
+                target_edits,
+            } = status
+            else {
+                panic!("Expected success, got {:?}", status)
+            };
+            assert!(!should_warn_undeleted_branch);
+            assert_eq!(target_edits, vec![]);
+            let added_changes = diff
+                .changes()
+                .iter()
+                .filter(|c| matches!(c, RoswaalTestCommandChange::Added(_)))
+                .count();
+            assert_eq!(added_changes, 1);
+            let pr = pr_open.most_recent_pr().await.unwrap();
+            assert_eq!(pr.head_branch(), &branch_name);
+            assert!(pr.title().contains("Edit Test \"Edit Me\""));
+            let test_actions_code = read_string(&test_actions_path).await?;
+            assert!(test_actions_code.starts_with(&implemented_code));
+            assert!(test_actions_code.contains("export const doTheNewThing"));
+            let test_case_code =
+                read_string(&metadata.relative_path("roswaal/edit-me/TestCase.test.ts")).await?;
+            assert!(test_case_code.contains("TestActions.doTheNewThing"));
+            let mut transaction = database.transaction().await?;
+            let query = RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new("Edit Me"));
+            let stored_tests = transaction.tests_in_alphabetical_order(&query).await?;
+            let source_codes = stored_tests
+                .iter()
+                .map(|t| (t.unmerged_branch_name(), t.source_code()))
+                .collect::<Vec<(Option<&RoswaalOwnedGitBranchName>, Option<&str>)>>();
+            let expected_source_codes = vec![
+                (None, Some(ORIGINAL_TEST.trim().trim_matches('`').trim())),
+                (
+                    Some(&branch_name),
+                    Some(edited_test.trim().trim_matches('`').trim()),
+                ),
+            ];
+            assert_eq!(source_codes, expected_source_codes);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn add_and_merge_original_test(
+        database: &RoswaalDatabase,
+        pr_open: &TestGithubPullRequestOpen,
+        repo: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+    ) -> Result<()> {
+        let status =
+            AddTestsStatus::from_adding_tests(ORIGINAL_TEST, database, pr_open, repo, &[], None)
+                .await?;
+        let AddTestsStatus::Success {
+            branch_name: Some(branch_name),
+            ..
+        } = status
+        else {
+            panic!("Expected the original test to be added.")
+        };
+        MergeBranchStatus::from_merging_branch_with_name(&branch_name, database, NO_TEST_RUNNER)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+use anyhow::Result;
+
+use crate::{
+    tests_data::{query::RoswaalSearchTestsQuery, test::RoswaalTest},
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExportTestsStatus {
+    /// The roswaal syntax of each exported test in alphabetical order.
+    Success(Vec<String>),
+    NoTests,
+}
+
+impl ExportTestsStatus {
+    /// Returns the roswaal syntax of the merged tests matching `query_str` exactly as it was
+    /// written when the tests were added or last edited.
+    ///
+    /// Tests saved before their source code was stored have their syntax reconstructed from their
+    /// commands instead. Tags and suites are not stored alongside the commands of a test, so they
+    /// are not included in the reconstructed syntax.
+    pub async fn from_exporting_tests(query_str: &str, database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        let tests = with_transaction!(transaction, async {
+            let query = RoswaalSearchTestsQuery::new(query_str);
+            transaction.tests_in_alphabetical_order(&query).await
+        })?;
+        let syntaxes = tests
+            .iter()
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .map(RoswaalTest::original_syntax)
+            .collect::<Vec<String>>();
+        if syntaxes.is_empty() {
+            Ok(Self::NoTests)
+        } else {
+            Ok(Self::Success(syntaxes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::{
+            compiler::{RoswaalCompile, RoswaalCompileContext},
+            test::RoswaalCompiledTest,
+        },
+        location::name::RoswaalLocationName,
+    };
+
+    use super::*;
+
+    const TEST_SYNTAX: &str = "\
+New Test: Join an event
+Abstract: Users should be able to join events near them.
+Before Launch: Reset location permissions
+Step 1: Open the app (retries: 2)
+Set Location: Antarctica
+Wait: 1.5 seconds
+Step A: Join the event
+Verify 1: The event shows up in my events
+Verify 2: The host is notified
+Requirement 1: Launch the app
+Requirement A: Tap the join button
+Using 2: Check that a notification was sent";
+
+    #[tokio::test]
+    async fn reports_no_tests_when_no_merged_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(
+                &vec![compile(TEST_SYNTAX)],
+                &RoswaalOwnedGitBranchName::new("test"),
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let status = ExportTestsStatus::from_exporting_tests("", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, ExportTestsStatus::NoTests)
+    }
+
+    #[tokio::test]
+    async fn exports_syntax_that_compiles_to_the_stored_test() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let test = compile(TEST_SYNTAX);
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(&vec![test.clone()], &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let status = ExportTestsStatus::from_exporting_tests("Join an event", &database)
+            .await
+            .unwrap();
+        let ExportTestsStatus::Success(syntaxes) = status else {
+            panic!("Expected the test to be exported.")
+        };
+        assert_eq!(syntaxes, vec![TEST_SYNTAX.to_string()]);
+        assert_eq!(compile(&syntaxes[0]), test)
+    }
+
+    #[tokio::test]
+    async fn exports_the_stored_source_code_as_it_was_written() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let source_code = "\
+New Test: Join an event
+Step 1: Open the app
+Requirement 1: Launch the app
+Tags: Events
+Step 2: Join the event
+Requirement 2: Tap the join button";
+        let test = compile(source_code).with_source_code(source_code);
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(&vec![test], &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let status = ExportTestsStatus::from_exporting_tests("Join an event", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            ExportTestsStatus::Success(vec![source_code.to_string()])
+        )
+    }
+
+    fn compile(syntax: &str) -> RoswaalCompiledTest {
+        let location_names = vec![RoswaalLocationName::from_str("Antarctica").unwrap()];
+        RoswaalCompiledTest::compile(syntax, RoswaalCompileContext::new(&location_names)).unwrap()
+    }
+}
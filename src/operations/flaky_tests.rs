@@ -0,0 +1,145 @@
+use anyhow::Result;
+
+use crate::{
+    tests_data::{
+        query::{RoswaalSearchTestsQuery, RoswaalTestsOrdering},
+        test::RoswaalTest,
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// The maximum number of tests that are listed as flaky.
+pub const FLAKY_TESTS_LIMIT: usize = 10;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FlakyTestsStatus {
+    Success(Vec<RoswaalTest>),
+    NoFlakyTests,
+    NoTests,
+}
+
+impl FlakyTestsStatus {
+    /// Ranks the merged tests that failed during their recent runs from the flakiest to the least
+    /// flaky, keeping at most `FLAKY_TESTS_LIMIT` tests.
+    pub async fn from_ranking_tests(database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        let tests = with_transaction!(transaction, async {
+            transaction
+                .tests_in_order(
+                    &RoswaalSearchTestsQuery::AllTests,
+                    RoswaalTestsOrdering::MostFlaky,
+                )
+                .await
+        })?;
+        let merged_tests = tests
+            .into_iter()
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .collect::<Vec<RoswaalTest>>();
+        if merged_tests.is_empty() {
+            return Ok(Self::NoTests);
+        }
+        let flaky_tests = merged_tests
+            .into_iter()
+            .filter(|t| t.flakiness().failure_count() > 0)
+            .take(FLAKY_TESTS_LIMIT)
+            .collect::<Vec<RoswaalTest>>();
+        if flaky_tests.is_empty() {
+            Ok(Self::NoFlakyTests)
+        } else {
+            Ok(Self::Success(flaky_tests))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_no_tests_when_no_merged_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = FlakyTestsStatus::from_ranking_tests(&database)
+            .await
+            .unwrap();
+        assert_eq!(status, FlakyTestsStatus::NoTests)
+    }
+
+    #[tokio::test]
+    async fn reports_no_flaky_tests_when_no_runs_failed() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Passing", "Never Ran"]).await;
+        upload_progress(&database, "Passing", None).await;
+        let status = FlakyTestsStatus::from_ranking_tests(&database)
+            .await
+            .unwrap();
+        assert_eq!(status, FlakyTestsStatus::NoFlakyTests)
+    }
+
+    #[tokio::test]
+    async fn ranks_tests_with_failed_runs_by_flakiness() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Broken", "Flaky", "Passing"]).await;
+        let failure = Some(RoswaalTestCommandOrdinal::new(0));
+        for _ in 0..2 {
+            upload_progress(&database, "Broken", failure).await;
+            upload_progress(&database, "Broken", failure).await;
+            upload_progress(&database, "Flaky", failure).await;
+            upload_progress(&database, "Flaky", None).await;
+            upload_progress(&database, "Passing", None).await;
+        }
+        let status = FlakyTestsStatus::from_ranking_tests(&database)
+            .await
+            .unwrap();
+        let FlakyTestsStatus::Success(tests) = status else {
+            panic!("Expected flaky tests.")
+        };
+        assert_eq!(
+            tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["Flaky", "Broken"]
+        )
+    }
+
+    async fn save_merged_tests(database: &RoswaalDatabase, names: &[&str]) {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = names
+            .iter()
+            .map(|name| {
+                RoswaalCompiledTest::new(
+                    name.to_string(),
+                    None,
+                    vec![RoswaalCompiledTestCommand::Step {
+                        label: "Step 1".to_string(),
+                        name: "A".to_string(),
+                        requirement: "B".to_string(),
+                        retries: None,
+                    }],
+                )
+            })
+            .collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+    }
+
+    async fn upload_progress(
+        database: &RoswaalDatabase,
+        name: &str,
+        failure_ordinal: Option<RoswaalTestCommandOrdinal>,
+    ) {
+        let upload = RoswaalTestProgressUpload::new(name.to_string(), failure_ordinal, None);
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_test_progess(&vec![upload]).await.unwrap();
+        transaction.commit().await.unwrap();
+    }
+}
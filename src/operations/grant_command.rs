@@ -0,0 +1,180 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+
+use crate::{
+    slack::{
+        command::RoswaalSlackCommand,
+        permissions::{RoswaalSlackCommandPermissions, RoswaalSlackPermissionSubject},
+        users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GrantCommandStatus {
+    /// No grant was requested, so the current permissions are shown.
+    Permissions(RoswaalSlackCommandPermissions),
+    Success {
+        command: RoswaalSlackCommand,
+        permissions: RoswaalSlackCommandPermissions,
+    },
+    InvalidCommand(String),
+    InvalidSubject(String),
+    NotAuthorized {
+        roster: RoswaalSlackUserRoster,
+    },
+}
+
+impl GrantCommandStatus {
+    /// Grants a command from a command string in the form `<command> <@user> <@group>...`, where
+    /// the mentioned users and user groups replace the existing subjects of the command.
+    ///
+    /// The command may be written with or without its leading `/`. Mentioning nobody allows
+    /// anyone to run the command again. An empty command string only loads the current
+    /// permissions. Only infra owners are allowed to grant commands, and `/roswaal-grant` itself
+    /// cannot be granted.
+    pub async fn from_granting_command(
+        grant_str: &str,
+        user_id: &str,
+        database: &RoswaalDatabase,
+    ) -> Result<Self> {
+        let mut words = grant_str.split_whitespace();
+        let command = match words.next() {
+            Some(name) => match Self::grantable_command(name) {
+                Some(command) => Some(command),
+                None => return Ok(Self::InvalidCommand(name.to_string())),
+            },
+            None => None,
+        };
+        let mut subjects = Vec::<RoswaalSlackPermissionSubject>::new();
+        for mention in words {
+            match RoswaalSlackPermissionSubject::from_mention(mention) {
+                Some(subject) => subjects.push(subject),
+                None => return Ok(Self::InvalidSubject(mention.to_string())),
+            }
+        }
+        let mut transaction = database.transaction().await?;
+        with_transaction!(transaction, async {
+            let Some(command) = command else {
+                let permissions = transaction.slack_command_permissions().await?;
+                return Ok(Self::Permissions(permissions));
+            };
+            let roster = transaction.slack_user_roster().await?;
+            if !roster.has_role(user_id, RoswaalSlackUserRole::InfraOwner) {
+                return Ok(Self::NotAuthorized { roster });
+            }
+            transaction
+                .set_slack_command_subjects(command, &subjects)
+                .await?;
+            let permissions = transaction.slack_command_permissions().await?;
+            Ok(Self::Success {
+                command,
+                permissions,
+            })
+        })
+    }
+
+    fn grantable_command(name: &str) -> Option<RoswaalSlackCommand> {
+        let name = format!("/{}", name.trim_start_matches('/'));
+        RoswaalSlackCommand::from_str(&name)
+            .ok()
+            .filter(|command| *command != RoswaalSlackCommand::GrantCommand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::config::RoswaalConfig;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_permissions_for_empty_string() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = GrantCommandStatus::from_granting_command("", "U1", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            GrantCommandStatus::Permissions(RoswaalSlackCommandPermissions::default())
+        )
+    }
+
+    #[tokio::test]
+    async fn infra_owners_can_grant_and_reopen_commands() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let maintainer_id = RoswaalConfig::current().maintainer_slack_user_id();
+        let status = GrantCommandStatus::from_granting_command(
+            "remove-tests <@U1|justin> <!subteam^S1|@qa>",
+            maintainer_id,
+            &database,
+        )
+        .await
+        .unwrap();
+        let permissions = RoswaalSlackCommandPermissions::default().with_subjects(
+            RoswaalSlackCommand::RemoveTests,
+            vec![
+                RoswaalSlackPermissionSubject::User("U1".to_string()),
+                RoswaalSlackPermissionSubject::UserGroup("S1".to_string()),
+            ],
+        );
+        assert_eq!(
+            status,
+            GrantCommandStatus::Success {
+                command: RoswaalSlackCommand::RemoveTests,
+                permissions
+            }
+        );
+
+        let status = GrantCommandStatus::from_granting_command("/remove-tests", "U1", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            GrantCommandStatus::NotAuthorized {
+                roster: RoswaalSlackUserRoster::default()
+            }
+        );
+
+        let status =
+            GrantCommandStatus::from_granting_command("/remove-tests", maintainer_id, &database)
+                .await
+                .unwrap();
+        assert_eq!(
+            status,
+            GrantCommandStatus::Success {
+                command: RoswaalSlackCommand::RemoveTests,
+                permissions: RoswaalSlackCommandPermissions::default()
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_commands_and_subjects() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let maintainer_id = RoswaalConfig::current().maintainer_slack_user_id();
+        for name in ["/remove-test", "/roswaal-grant"] {
+            let status = GrantCommandStatus::from_granting_command(
+                &format!("{} <@U1>", name),
+                maintainer_id,
+                &database,
+            )
+            .await
+            .unwrap();
+            assert_eq!(status, GrantCommandStatus::InvalidCommand(name.to_string()))
+        }
+        let status = GrantCommandStatus::from_granting_command(
+            "/remove-tests <@U1> @qa",
+            maintainer_id,
+            &database,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            GrantCommandStatus::InvalidSubject("@qa".to_string())
+        )
+    }
+}
@@ -0,0 +1,189 @@
+use std::{future::Future, time::Duration};
+
+use anyhow::Result;
+use serde::Serialize;
+use tokio::{join, time::timeout};
+
+use crate::{
+    git::{
+        pull_request::GithubCheckReachability,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// How long each dependency has to respond before it is reported as unhealthy.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoswaalHealthStatus {
+    Ok,
+    Unhealthy,
+}
+
+/// The result of checking a single dependency of this tool.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct RoswaalDependencyCheck {
+    name: String,
+    status: RoswaalHealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl RoswaalDependencyCheck {
+    async fn new(name: &str, check: impl Future<Output = Result<()>>) -> Self {
+        let error = match timeout(HEALTH_CHECK_TIMEOUT, check).await {
+            Ok(Ok(())) => None,
+            Ok(Err(error)) => Some(error.to_string()),
+            Err(_) => Some(format!(
+                "Timed out after {} seconds.",
+                HEALTH_CHECK_TIMEOUT.as_secs()
+            )),
+        };
+        Self {
+            name: name.to_string(),
+            status: if error.is_none() {
+                RoswaalHealthStatus::Ok
+            } else {
+                RoswaalHealthStatus::Unhealthy
+            },
+            error,
+        }
+    }
+}
+
+impl RoswaalDependencyCheck {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn status(&self) -> RoswaalHealthStatus {
+        self.status
+    }
+}
+
+/// A document describing whether this tool can reach the dependencies that it needs to handle
+/// requests, which is served to load balancers and uptime checks.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct RoswaalHealthReport {
+    status: RoswaalHealthStatus,
+    checks: Vec<RoswaalDependencyCheck>,
+}
+
+impl RoswaalHealthReport {
+    /// Checks that the database can be read from, that each local clone in `repositories` can be
+    /// opened, and that the GitHub API accepts an authenticated request.
+    ///
+    /// Git repositories are reported as `git/<repository name>`.
+    pub async fn check(
+        database: &RoswaalDatabase,
+        repositories: &[&RoswaalGitRepository<impl RoswaalGitRepositoryClient>],
+        github: &impl GithubCheckReachability,
+    ) -> Self {
+        let database_check = RoswaalDependencyCheck::new("database", async {
+            let mut transaction = database.read_transaction().await?;
+            with_transaction!(transaction, async { transaction.data_revision().await })?;
+            Ok(())
+        });
+        let git_checks = async {
+            let mut checks = Vec::new();
+            for repository in repositories {
+                let name = format!("git/{}", repository.name());
+                checks.push(RoswaalDependencyCheck::new(&name, repository.check_openable()).await);
+            }
+            checks
+        };
+        let github_check = RoswaalDependencyCheck::new("github", github.check_reachability());
+        let (database_check, git_checks, github_check) =
+            join!(database_check, git_checks, github_check);
+        let mut checks = vec![database_check];
+        checks.extend(git_checks);
+        checks.push(github_check);
+        let is_healthy = checks.iter().all(|c| c.status == RoswaalHealthStatus::Ok);
+        Self {
+            status: if is_healthy {
+                RoswaalHealthStatus::Ok
+            } else {
+                RoswaalHealthStatus::Unhealthy
+            },
+            checks,
+        }
+    }
+}
+
+impl RoswaalHealthReport {
+    pub fn status(&self) -> RoswaalHealthStatus {
+        self.status
+    }
+
+    pub fn checks(&self) -> &[RoswaalDependencyCheck] {
+        &self.checks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::{
+        metadata::RoswaalGitRepositoryMetadata,
+        test_support::{NoopGitRepositoryClient, TestGithubCheckReachability},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_each_dependency_in_order() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let repository = RoswaalGitRepository::<NoopGitRepositoryClient>::open(
+            &RoswaalGitRepositoryMetadata::for_test_target("roswaal", "MissingClone", "main"),
+        )
+        .await
+        .unwrap();
+        let report = RoswaalHealthReport::check(
+            &database,
+            &[&repository],
+            &TestGithubCheckReachability::new(true),
+        )
+        .await;
+        let checks = report
+            .checks()
+            .iter()
+            .map(|c| (c.name(), c.status()))
+            .collect::<Vec<(&str, RoswaalHealthStatus)>>();
+        let expected_checks = vec![
+            ("database", RoswaalHealthStatus::Ok),
+            ("git/MissingClone", RoswaalHealthStatus::Unhealthy),
+            ("github", RoswaalHealthStatus::Ok),
+        ];
+        assert_eq!(checks, expected_checks);
+        assert_eq!(report.status(), RoswaalHealthStatus::Unhealthy)
+    }
+
+    #[tokio::test]
+    async fn serializes_errors_only_for_unhealthy_dependencies() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let repositories: [&RoswaalGitRepository<NoopGitRepositoryClient>; 0] = [];
+        let report = RoswaalHealthReport::check(
+            &database,
+            &repositories,
+            &TestGithubCheckReachability::new(false),
+        )
+        .await;
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "status": "unhealthy",
+                "checks": [
+                    { "name": "database", "status": "ok" },
+                    {
+                        "name": "github",
+                        "status": "unhealthy",
+                        "error": "GitHub is unavailable with status code 503 Service Unavailable."
+                    }
+                ]
+            })
+        )
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+
+use crate::{
+    git::branch_name::RoswaalOwnedGitBranchName,
+    tests_data::{
+        query::RoswaalSearchTestsQuery,
+        test::{RoswaalTest, RoswaalTestProgressStatus},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// The maximum number of failing tests that are listed on the home tab.
+pub const MAX_RECENTLY_FAILED_TESTS: usize = 5;
+
+/// An overview of the merged tests and unmerged branches that is shown on the home tab.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoswaalHomeSummary {
+    passed_count: usize,
+    failed_count: usize,
+    idle_count: usize,
+    recently_failed_tests: Vec<RoswaalTest>,
+    unmerged_branch_names: Vec<RoswaalOwnedGitBranchName>,
+}
+
+impl RoswaalHomeSummary {
+    pub fn new(
+        tests: &[RoswaalTest],
+        unmerged_branch_names: Vec<RoswaalOwnedGitBranchName>,
+    ) -> Self {
+        let count = |status: RoswaalTestProgressStatus| {
+            tests
+                .iter()
+                .filter(|t| t.progress_status() == status)
+                .count()
+        };
+        let mut recently_failed_tests = tests
+            .iter()
+            .filter(|t| t.progress_status() == RoswaalTestProgressStatus::Failed)
+            .cloned()
+            .collect::<Vec<RoswaalTest>>();
+        recently_failed_tests.sort_by_key(|t| std::cmp::Reverse(t.last_run_date()));
+        recently_failed_tests.truncate(MAX_RECENTLY_FAILED_TESTS);
+        Self {
+            passed_count: count(RoswaalTestProgressStatus::Passed),
+            failed_count: count(RoswaalTestProgressStatus::Failed),
+            idle_count: count(RoswaalTestProgressStatus::Idle),
+            recently_failed_tests,
+            unmerged_branch_names,
+        }
+    }
+
+    /// Summarizes the progress of the merged tests, and lists the branches with unmerged tests,
+    /// locations, or staged removals.
+    pub async fn load(database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async {
+            let tests = transaction
+                .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                .await?
+                .into_iter()
+                .filter(|t| t.unmerged_branch_name().is_none())
+                .collect::<Vec<RoswaalTest>>();
+            let unmerged_branch_names = transaction.unmerged_branch_names().await?;
+            Ok(Self::new(&tests, unmerged_branch_names))
+        })
+    }
+}
+
+impl RoswaalHomeSummary {
+    pub fn passed_count(&self) -> usize {
+        self.passed_count
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.failed_count
+    }
+
+    pub fn idle_count(&self) -> usize {
+        self.idle_count
+    }
+
+    /// The most recently ran failing tests, up to `MAX_RECENTLY_FAILED_TESTS`.
+    pub fn recently_failed_tests(&self) -> &[RoswaalTest] {
+        &self.recently_failed_tests
+    }
+
+    pub fn unmerged_branch_names(&self) -> &[RoswaalOwnedGitBranchName] {
+        &self.unmerged_branch_names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn summarizes_merged_tests_and_unmerged_branches() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let merged_branch_name = RoswaalOwnedGitBranchName::new("merged");
+        let unmerged_branch_name = RoswaalOwnedGitBranchName::new("unmerged");
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(
+                &vec![
+                    compiled_test("Passing"),
+                    compiled_test("Failing"),
+                    compiled_test("Idle"),
+                ],
+                &merged_branch_name,
+            )
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&merged_branch_name)
+            .await
+            .unwrap();
+        transaction
+            .save_tests(&vec![compiled_test("Unmerged")], &unmerged_branch_name)
+            .await
+            .unwrap();
+        transaction
+            .save_test_progess(&vec![
+                RoswaalTestProgressUpload::new("Passing".to_string(), None, None),
+                RoswaalTestProgressUpload::new(
+                    "Failing".to_string(),
+                    Some(RoswaalTestCommandOrdinal::new(0)),
+                    None,
+                ),
+            ])
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let summary = RoswaalHomeSummary::load(&database).await.unwrap();
+        assert_eq!(
+            (
+                summary.passed_count(),
+                summary.failed_count(),
+                summary.idle_count()
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(
+            summary
+                .recently_failed_tests()
+                .iter()
+                .map(|t| t.name())
+                .collect::<Vec<&str>>(),
+            vec!["Failing"]
+        );
+        assert_eq!(summary.unmerged_branch_names(), &[unmerged_branch_name])
+    }
+
+    fn compiled_test(name: &str) -> RoswaalCompiledTest {
+        RoswaalCompiledTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    language::platform::RoswaalTestPlatform,
+    tests_data::{
+        query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+        test::RoswaalTest,
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// Leases up to `count` idle tests to the runner with `runner_id` for `lease_duration`, and
+/// returns the leased tests in alphabetical order.
+///
+/// A test is idle when it is merged, not deprecated or planned, runs on `platform` (if specified),
+/// and is not leased to any runner at `now`. Tests stay leased after their progress is uploaded,
+/// so each test is claimed by exactly one runner until its lease expires or is released.
+pub async fn claim_tests(
+    runner_id: &str,
+    count: usize,
+    platform: Option<RoswaalTestPlatform>,
+    lease_duration: Duration,
+    now: DateTime<Utc>,
+    database: &RoswaalDatabase,
+) -> Result<Vec<RoswaalTest>> {
+    let mut transaction = database.transaction().await?;
+    with_transaction!(transaction, async {
+        let leased_names = transaction.leased_test_names(now).await?;
+        let tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await?
+            .into_iter()
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .filter(|t| !t.is_deprecated() && !t.is_planned())
+            .filter(|t| platform.is_none_or(|p| t.runs_on(p)))
+            .filter(|t| !leased_names.contains(&t.name().to_lowercase()))
+            .take(count)
+            .collect::<Vec<RoswaalTest>>();
+        let names = tests.iter().map(|t| t.name()).collect::<Vec<&str>>();
+        transaction
+            .lease_tests(&names, runner_id, now + lease_duration)
+            .await?;
+        Ok(tests)
+    })
+}
+
+/// Releases the leases held by the runner with `runner_id` on each test named in `names_str`, or
+/// on every test that it holds if `names_str` is empty, so that other runners can claim them.
+///
+/// Returns the lowercased names of the released tests.
+pub async fn release_tests(
+    runner_id: &str,
+    names_str: &str,
+    database: &RoswaalDatabase,
+) -> Result<Vec<String>> {
+    let test_names = RoswaalTestNamesString::new(names_str);
+    let mut transaction = database.transaction().await?;
+    with_transaction!(transaction, async {
+        transaction
+            .release_test_leases(runner_id, &test_names)
+            .await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{git::branch_name::RoswaalOwnedGitBranchName, language::test::RoswaalCompiledTest};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn claims_each_idle_test_once() {
+        let database = setup_merged_tests().await;
+        let now = Utc::now();
+        let lease_duration = Duration::minutes(30);
+        let tests = claim_tests("runner-1", 2, None, lease_duration, now, &database)
+            .await
+            .unwrap();
+        assert_eq!(test_names(&tests), vec!["Blob", "Other"]);
+        let tests = claim_tests("runner-2", 2, None, lease_duration, now, &database)
+            .await
+            .unwrap();
+        assert_eq!(test_names(&tests), vec!["Third"]);
+        let tests = claim_tests("runner-3", 2, None, lease_duration, now, &database)
+            .await
+            .unwrap();
+        assert!(tests.is_empty());
+        let later = now + lease_duration + Duration::seconds(1);
+        let tests = claim_tests("runner-3", 5, None, lease_duration, later, &database)
+            .await
+            .unwrap();
+        assert_eq!(test_names(&tests), vec!["Blob", "Other", "Third"])
+    }
+
+    #[tokio::test]
+    async fn released_tests_can_be_claimed_again() {
+        let database = setup_merged_tests().await;
+        let now = Utc::now();
+        let lease_duration = Duration::minutes(30);
+        claim_tests("runner-1", 3, None, lease_duration, now, &database)
+            .await
+            .unwrap();
+        let names = release_tests("runner-2", "", &database).await.unwrap();
+        assert!(names.is_empty());
+        let names = release_tests("runner-1", "Other\nThird", &database)
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["other".to_string(), "third".to_string()]);
+        let tests = claim_tests("runner-2", 3, None, lease_duration, now, &database)
+            .await
+            .unwrap();
+        assert_eq!(test_names(&tests), vec!["Other", "Third"])
+    }
+
+    #[tokio::test]
+    async fn skips_tests_that_do_not_run_on_the_platform() {
+        let database = setup_merged_tests().await;
+        let tests = claim_tests(
+            "runner-1",
+            3,
+            Some(RoswaalTestPlatform::Android),
+            Duration::minutes(30),
+            Utc::now(),
+            &database,
+        )
+        .await
+        .unwrap();
+        assert_eq!(test_names(&tests), vec!["Blob", "Third"])
+    }
+
+    async fn setup_merged_tests() -> RoswaalDatabase {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = vec![
+            RoswaalCompiledTest::new("Blob".to_string(), None, vec![]),
+            RoswaalCompiledTest::new("Other".to_string(), None, vec![])
+                .with_platforms(vec![RoswaalTestPlatform::Ios]),
+            RoswaalCompiledTest::new("Third".to_string(), None, vec![]),
+        ];
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let unmerged_branch_name = RoswaalOwnedGitBranchName::new("unmerged");
+        let unmerged_tests = vec![RoswaalCompiledTest::new(
+            "Unmerged".to_string(),
+            None,
+            vec![],
+        )];
+        transaction
+            .save_tests(&unmerged_tests, &unmerged_branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        database
+    }
+
+    fn test_names(tests: &[RoswaalTest]) -> Vec<&str> {
+        tests.iter().map(|t| t.name()).collect()
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+
+use crate::{
+    language::{ast::tests_syntax, compilation_results::RoswaalTestCompilationResults},
+    location::storage::LoadLocationsFilter,
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+use super::add_tests::{has_flag, NORMALIZE_STEPS_FLAG};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LintTestsStatus<'r> {
+    Success {
+        results: RoswaalTestCompilationResults<'r>,
+    },
+    NoTestsFound,
+}
+
+impl<'r> LintTestsStatus<'r> {
+    /// Compiles the tests in the specified string against the merged locations without
+    /// generating any code, opening a pull request, or saving the tests.
+    pub async fn from_linting_tests(
+        tests_str: &'r str,
+        database: &RoswaalDatabase,
+    ) -> Result<Self> {
+        if tests_syntax(tests_str).next().is_none() {
+            return Ok(Self::NoTestsFound);
+        }
+        let mut transaction = database.transaction().await?;
+        let (location_names, location_aliases, test_dependencies) =
+            with_transaction!(transaction, async {
+                let location_names = transaction
+                    .location_names_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
+                    .await?;
+                Ok((
+                    location_names,
+                    transaction.location_aliases_in_alphabetical_order().await?,
+                    transaction.merged_test_dependency_graph().await?,
+                ))
+            })?;
+        let results = RoswaalTestCompilationResults::compile_with_test_dependencies(
+            tests_syntax(tests_str),
+            &location_names,
+            &location_aliases,
+            &test_dependencies,
+            has_flag(tests_str, NORMALIZE_STEPS_FLAG),
+        );
+        Ok(Self::Success { results })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::compiler::{RoswaalCompilationError, RoswaalCompilationErrorCode},
+        location::location::RoswaalLocation,
+        tests_data::query::RoswaalSearchTestsQuery,
+    };
+
+    #[tokio::test]
+    async fn reports_no_tests_found_when_no_tests_in_string() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = LintTestsStatus::from_linting_tests("", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, LintTestsStatus::NoTestsFound)
+    }
+
+    #[tokio::test]
+    async fn compiles_tests_against_merged_locations_only() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_locations();
+        transaction
+            .save_locations(
+                &vec![RoswaalLocation::from_str("Antarctica, 50.0, 50.0").unwrap()],
+                &branch_name,
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let tests_str = "\
+```
+New Test: Go to Antarctica
+Set Location: Antarctica
+Step 1: Freeze
+Requirement 1: Be frozen
+```
+";
+        let status = LintTestsStatus::from_linting_tests(tests_str, &database)
+            .await
+            .unwrap();
+        let errors = match status {
+            LintTestsStatus::Success { results } => results
+                .failures()
+                .iter()
+                .flat_map(|f| f.errors().to_vec())
+                .collect::<Vec<RoswaalCompilationError>>(),
+            _ => panic!(),
+        };
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].code(),
+            RoswaalCompilationErrorCode::UnknownLocationName(_)
+        ))
+    }
+
+    #[tokio::test]
+    async fn renumbers_step_labels_with_normalize_steps_flag() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let tests_str = "\
+--normalize-steps
+```
+New Test: Blob
+Step 1: Do the thing
+Step 1: Do the other thing
+Requirement 1: Do the thing
+Requirement 1: Do the other thing
+```
+";
+        let status = LintTestsStatus::from_linting_tests(tests_str, &database)
+            .await
+            .unwrap();
+        let tests = match status {
+            LintTestsStatus::Success { results } => results.tests(),
+            _ => panic!(),
+        };
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].warnings().len(), 1)
+    }
+
+    #[tokio::test]
+    async fn does_not_save_compiled_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let tests_str = "\
+```
+New Test: Blob
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+        let status = LintTestsStatus::from_linting_tests(tests_str, &database)
+            .await
+            .unwrap();
+        match status {
+            LintTestsStatus::Success { results } => assert!(results.has_compiling_tests()),
+            _ => panic!(),
+        }
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert!(tests.is_empty())
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::Result;
+
+use crate::{
+    git::{
+        branch_name::RoswaalOwnedGitBranchName,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+use super::resource_coordinator::ResourceCoordinator;
+
+/// An unmerged branch created by this tool.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoswaalListedBranch {
+    name: RoswaalOwnedGitBranchName,
+    is_stored: bool,
+    is_on_remote: bool,
+}
+
+impl RoswaalListedBranch {
+    pub fn new(name: RoswaalOwnedGitBranchName, is_stored: bool, is_on_remote: bool) -> Self {
+        Self {
+            name,
+            is_stored,
+            is_on_remote,
+        }
+    }
+}
+
+impl RoswaalListedBranch {
+    pub fn name(&self) -> &RoswaalOwnedGitBranchName {
+        &self.name
+    }
+
+    /// Returns true if unmerged tests, locations, or staged removals reference this branch.
+    pub fn is_stored(&self) -> bool {
+        self.is_stored
+    }
+
+    /// Returns true if this branch still exists on the remote repository.
+    pub fn is_on_remote(&self) -> bool {
+        self.is_on_remote
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ListBranchesStatus {
+    Success { branches: Vec<RoswaalListedBranch> },
+    NoBranches,
+}
+
+impl ListBranchesStatus {
+    /// Lists the unmerged branches created by this tool in alphabetical order.
+    ///
+    /// A branch is listed if it is referenced by any unmerged data in the database, or if it exists
+    /// on the remote repository. Remote branches that were not created by this tool are ignored.
+    pub async fn from_listing_branches(
+        database: &RoswaalDatabase,
+        git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+    ) -> Result<Self> {
+        let mut resources = ResourceCoordinator::new(database, git_repository);
+        let mut transaction = resources.database().await?;
+        let stored_names = with_transaction!(transaction, async {
+            transaction.unmerged_branch_names().await
+        })?;
+        let remote_names = resources
+            .git()
+            .await
+            .remote_branch_names()
+            .await?
+            .into_iter()
+            .filter_map(|name| {
+                serde_json::from_value::<RoswaalOwnedGitBranchName>(serde_json::Value::String(name))
+                    .ok()
+            })
+            .collect::<Vec<RoswaalOwnedGitBranchName>>();
+        let mut branches = stored_names
+            .iter()
+            .map(|name| RoswaalListedBranch::new(name.clone(), true, remote_names.contains(name)))
+            .collect::<Vec<RoswaalListedBranch>>();
+        for name in remote_names {
+            if !stored_names.contains(&name) {
+                branches.push(RoswaalListedBranch::new(name, false, true));
+            }
+        }
+        if branches.is_empty() {
+            return Ok(Self::NoBranches);
+        }
+        branches.sort_by_key(|b| b.name.to_string());
+        Ok(Self::Success { branches })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::{
+            branch_name::RoswaalOwnedGitBranchName, repo::RoswaalGitRepository,
+            test_support::NoopGitRepositoryClient,
+        },
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        operations::list_branches::{ListBranchesStatus, RoswaalListedBranch},
+        utils::db::RoswaalDatabase,
+    };
+
+    #[tokio::test]
+    async fn reports_no_branches_when_nothing_is_unmerged() {
+        let status = ListBranchesStatus::from_listing_branches(
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &RoswaalGitRepository::<NoopGitRepositoryClient>::noop_with_remote_branch_names(&[
+                "main",
+                "feature/cool-stuff",
+            ])
+            .await
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, ListBranchesStatus::NoBranches)
+    }
+
+    #[tokio::test]
+    async fn lists_stored_and_remote_branches_in_alphabetical_order() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let stored_only_name = save_tests_on_new_branch(&database).await;
+        let stored_and_remote_name = save_tests_on_new_branch(&database).await;
+        let remote_only_name = "roswaal-remove-tests-0123456789";
+        let repo = RoswaalGitRepository::noop_with_remote_branch_names(&[
+            "main",
+            remote_only_name,
+            &stored_and_remote_name.to_string(),
+        ])
+        .await
+        .unwrap();
+        let status = ListBranchesStatus::from_listing_branches(&database, &repo)
+            .await
+            .unwrap();
+        let mut expected_branches = vec![
+            RoswaalListedBranch::new(stored_only_name, true, false),
+            RoswaalListedBranch::new(stored_and_remote_name, true, true),
+            RoswaalListedBranch::new(
+                serde_json::from_str(&format!("\"{}\"", remote_only_name)).unwrap(),
+                false,
+                true,
+            ),
+        ];
+        expected_branches.sort_by_key(|b| b.name().to_string());
+        assert_eq!(
+            status,
+            ListBranchesStatus::Success {
+                branches: expected_branches
+            }
+        )
+    }
+
+    async fn save_tests_on_new_branch(database: &RoswaalDatabase) -> RoswaalOwnedGitBranchName {
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+        let test = RoswaalCompiledTest::new(
+            "Test".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        );
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(&vec![test], &branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        branch_name
+    }
+}
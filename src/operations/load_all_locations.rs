@@ -1,6 +1,6 @@
 use crate::{
     location::storage::{LoadLocationsFilter, RoswaalStoredLocation},
-    utils::sqlite::RoswaalSqlite,
+    utils::db::RoswaalDatabase,
     with_transaction,
 };
 use anyhow::Result;
@@ -12,8 +12,8 @@ pub enum LoadAllLocationsStatus {
 }
 
 impl LoadAllLocationsStatus {
-    pub async fn from_stored_locations(sqlite: &RoswaalSqlite) -> Result<Self> {
-        let mut transaction = sqlite.transaction().await?;
+    pub async fn from_stored_locations(database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
         with_transaction!(transaction, async {
             transaction
                 .locations_in_alphabetical_order(LoadLocationsFilter::All)
@@ -29,6 +29,29 @@ impl LoadAllLocationsStatus {
     }
 }
 
+impl LoadAllLocationsStatus {
+    /// Returns the number of locations that have been merged.
+    pub fn merged_count(&self) -> usize {
+        self.locations_iter()
+            .filter(|l| l.unmerged_branch_name().is_none())
+            .count()
+    }
+
+    /// Returns the number of locations that are pending on an unmerged branch.
+    pub fn pending_count(&self) -> usize {
+        self.locations_iter()
+            .filter(|l| l.unmerged_branch_name().is_some())
+            .count()
+    }
+
+    fn locations_iter(&self) -> impl Iterator<Item = &RoswaalStoredLocation> {
+        match self {
+            Self::Success(locations) => locations.iter(),
+            Self::NoLocations => [].iter(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -43,13 +66,13 @@ mod tests {
             add_locations::AddLocationsStatus, close_branch::CloseBranchStatus,
             load_all_locations::LoadAllLocationsStatus,
         },
-        utils::sqlite::RoswaalSqlite,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
     async fn test_returns_no_locations_when_no_saved_locations() {
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let status = LoadAllLocationsStatus::from_stored_locations(&sqlite)
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = LoadAllLocationsStatus::from_stored_locations(&database)
             .await
             .unwrap();
         assert_eq!(status, LoadAllLocationsStatus::NoLocations)
@@ -58,7 +81,7 @@ mod tests {
     #[tokio::test]
     async fn test_returns_locations_from_add_operation() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let expected_locations = vec![
                 RoswaalLocation::new_without_validation("Test 1", 50.0, 50.0),
                 RoswaalLocation::new_without_validation("Test 2", -5.0, 5.0),
@@ -72,15 +95,16 @@ Test 2, -5.0, 5.0
             _ = AddLocationsStatus::from_adding_locations(
                 &locations_str,
                 &RoswaalGitRepository::noop().await?,
-                &sqlite,
+                &database,
                 &TestGithubPullRequestOpen::new(false),
             )
             .await?;
-            let status = LoadAllLocationsStatus::from_stored_locations(&sqlite)
+            let status = LoadAllLocationsStatus::from_stored_locations(&database)
                 .await
                 .unwrap();
             assert!(is_case!(status, LoadAllLocationsStatus::Success));
             assert_eq!(status.locations(), expected_locations);
+            assert_eq!((status.merged_count(), status.pending_count()), (0, 2));
             Ok(())
         })
         .await
@@ -90,7 +114,7 @@ Test 2, -5.0, 5.0
     #[tokio::test]
     async fn test_returns_no_locations_when_opening_add_locations_pr_fails() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let locations_str = "
 Test 1, 50.0, 50.0
 Test 2, -5.0, 5.0
@@ -99,11 +123,11 @@ Test 2, -5.0, 5.0
             _ = AddLocationsStatus::from_adding_locations(
                 &locations_str,
                 &RoswaalGitRepository::noop().await?,
-                &sqlite,
+                &database,
                 &pr_open,
             )
             .await?;
-            let status = LoadAllLocationsStatus::from_stored_locations(&sqlite)
+            let status = LoadAllLocationsStatus::from_stored_locations(&database)
                 .await
                 .unwrap();
             assert_eq!(status, LoadAllLocationsStatus::NoLocations);
@@ -116,7 +140,7 @@ Test 2, -5.0, 5.0
     #[tokio::test]
     async fn test_returns_no_locations_when_add_locations_merge_conflict_occurs() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let locations_str = "
 Test 1, 50.0, 50.0
 Test 2, -5.0, 5.0
@@ -126,9 +150,14 @@ Test 2, -5.0, 5.0
             transaction.ensure_merge_conflict();
             drop(transaction);
             let pr_open = TestGithubPullRequestOpen::new(false);
-            _ = AddLocationsStatus::from_adding_locations(&locations_str, &repo, &sqlite, &pr_open)
-                .await?;
-            let status = LoadAllLocationsStatus::from_stored_locations(&sqlite)
+            _ = AddLocationsStatus::from_adding_locations(
+                locations_str,
+                &repo,
+                &database,
+                &pr_open,
+            )
+            .await?;
+            let status = LoadAllLocationsStatus::from_stored_locations(&database)
                 .await
                 .unwrap();
             assert_eq!(status, LoadAllLocationsStatus::NoLocations);
@@ -141,18 +170,18 @@ Test 2, -5.0, 5.0
     #[tokio::test]
     async fn reports_no_loactions_when_no_merged_locations_and_all_unmerged_locations_are_closed() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let locations_str = "
 Test 1, 50.0, 50.0
 Test 2, -5.0, 5.0
                 ";
             let repo = RoswaalGitRepository::noop().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
-            AddLocationsStatus::from_adding_locations(&locations_str, &repo, &sqlite, &pr_open)
+            AddLocationsStatus::from_adding_locations(locations_str, &repo, &database, &pr_open)
                 .await?;
             let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            CloseBranchStatus::from_closing_branch(&branch_name, &sqlite).await?;
-            let status = LoadAllLocationsStatus::from_stored_locations(&sqlite)
+            CloseBranchStatus::from_closing_branch(&branch_name, &database).await?;
+            let status = LoadAllLocationsStatus::from_stored_locations(&database)
                 .await
                 .unwrap();
             assert_eq!(status, LoadAllLocationsStatus::NoLocations);
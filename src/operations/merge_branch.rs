@@ -1,6 +1,7 @@
 use crate::{
     git::branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
-    utils::sqlite::{self, RoswaalSqlite},
+    runner::{run::RoswaalTestRun, webhook::RoswaalEnqueueTestRun},
+    utils::db::RoswaalDatabase,
     with_transaction,
 };
 use anyhow::Result;
@@ -8,33 +9,177 @@ use anyhow::Result;
 #[derive(Debug, PartialEq, Eq)]
 pub enum MergeBranchStatus<'a> {
     Merged(RoswaalOwnedBranchKind),
+    MergedAndQueuedTestRun(RoswaalTestRun),
     UnknownBranchKind(&'a RoswaalOwnedGitBranchName),
 }
 
 impl<'a> MergeBranchStatus<'a> {
+    /// Merges the changes that were staged on the specified branch.
+    ///
+    /// When a `test_runner` is given, a run of the tests that were added by the branch is
+    /// enqueued on it. The branch is still considered merged if the run cannot be enqueued.
     pub async fn from_merging_branch_with_name(
         branch_name: &'a RoswaalOwnedGitBranchName,
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
+        test_runner: Option<&impl RoswaalEnqueueTestRun>,
     ) -> Result<Self> {
-        match branch_name.kind() {
-            Some(kind) => {
-                let mut transaction = sqlite.transaction().await?;
-                with_transaction!(transaction, async {
-                    match kind {
-                        RoswaalOwnedBranchKind::AddTests => {
-                            transaction.merge_unmerged_tests(&branch_name).await?;
-                        }
-                        RoswaalOwnedBranchKind::AddLocations => {
-                            transaction.merge_unmerged_locations(&branch_name).await?;
-                        }
-                        RoswaalOwnedBranchKind::RemoveTests => {
-                            transaction.merge_test_removals(&branch_name).await?;
-                        }
+        let Some(kind) = branch_name.kind() else {
+            return Ok(Self::UnknownBranchKind(branch_name));
+        };
+        let mut transaction = database.transaction().await?;
+        let merged_test_names = with_transaction!(transaction, async {
+            match kind {
+                RoswaalOwnedBranchKind::AddTests => {
+                    return transaction.merge_unmerged_tests(branch_name).await;
+                }
+                RoswaalOwnedBranchKind::AddLocations => {
+                    transaction.merge_unmerged_locations(branch_name).await?;
+                }
+                RoswaalOwnedBranchKind::RemoveTests => {
+                    transaction.merge_test_removals(branch_name).await?;
+                }
+                RoswaalOwnedBranchKind::RemoveLocations => {
+                    transaction.merge_location_removals(branch_name).await?;
+                }
+                // NB: Orphaned test directories have no stored tests to merge.
+                RoswaalOwnedBranchKind::RemoveOrphanedTests => {}
+            }
+            Ok(vec![])
+        })?;
+        match test_runner {
+            Some(runner) if !merged_test_names.is_empty() => {
+                let result =
+                    Self::enqueue_test_run(branch_name, &merged_test_names, database, runner).await;
+                match result {
+                    Ok(run) => Ok(Self::MergedAndQueuedTestRun(run)),
+                    Err(error) => {
+                        log::error!(
+                            "Failed to enqueue a test run for {}. {}",
+                            branch_name.to_string(),
+                            error
+                        );
+                        Ok(Self::Merged(kind))
                     }
-                    Ok(Self::Merged(kind))
-                })
+                }
             }
-            None => Ok(Self::UnknownBranchKind(branch_name)),
+            _ => Ok(Self::Merged(kind)),
         }
     }
+
+    async fn enqueue_test_run(
+        branch_name: &RoswaalOwnedGitBranchName,
+        test_names: &[String],
+        database: &RoswaalDatabase,
+        runner: &impl RoswaalEnqueueTestRun,
+    ) -> Result<RoswaalTestRun> {
+        let mut transaction = database.transaction().await?;
+        let run = with_transaction!(transaction, async {
+            transaction.save_test_run(branch_name, test_names).await
+        })?;
+        runner.enqueue(&run).await?;
+        Ok(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        runner::webhook::test_support::TestRoswaalTestRunner,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn enqueues_run_of_tests_added_by_merged_branch() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+        save_tests(
+            &database,
+            &branch_name,
+            &["Join an event", "Leave an event"],
+        )
+        .await;
+        let runner = TestRoswaalTestRunner::new(false);
+        let status = MergeBranchStatus::from_merging_branch_with_name(
+            &branch_name,
+            &database,
+            Some(&runner),
+        )
+        .await
+        .unwrap();
+        let runs = runner.runs().await;
+        assert_eq!(
+            status,
+            MergeBranchStatus::MergedAndQueuedTestRun(runs[0].clone())
+        );
+        assert_eq!(runs[0].branch_name(), &branch_name);
+        assert_eq!(runs[0].test_names(), &["Join an event", "Leave an event"])
+    }
+
+    #[tokio::test]
+    async fn merges_branch_when_run_cannot_be_enqueued() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+        save_tests(&database, &branch_name, &["Join an event"]).await;
+        let runner = TestRoswaalTestRunner::new(true);
+        let status = MergeBranchStatus::from_merging_branch_with_name(
+            &branch_name,
+            &database,
+            Some(&runner),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            MergeBranchStatus::Merged(RoswaalOwnedBranchKind::AddTests)
+        );
+        let mut transaction = database.transaction().await.unwrap();
+        let merged_names = transaction.merged_test_names().await.unwrap();
+        assert_eq!(merged_names, vec!["Join an event".to_string()])
+    }
+
+    #[tokio::test]
+    async fn does_not_enqueue_run_for_branches_without_new_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_locations();
+        let runner = TestRoswaalTestRunner::new(false);
+        let status = MergeBranchStatus::from_merging_branch_with_name(
+            &branch_name,
+            &database,
+            Some(&runner),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            MergeBranchStatus::Merged(RoswaalOwnedBranchKind::AddLocations)
+        );
+        assert!(runner.runs().await.is_empty())
+    }
+
+    async fn save_tests(
+        database: &RoswaalDatabase,
+        branch_name: &RoswaalOwnedGitBranchName,
+        names: &[&str],
+    ) {
+        let tests = names
+            .iter()
+            .map(|name| {
+                RoswaalCompiledTest::new(
+                    name.to_string(),
+                    None,
+                    vec![RoswaalCompiledTestCommand::Step {
+                        label: "1".to_string(),
+                        name: "Thing".to_string(),
+                        requirement: "Do the thing".to_string(),
+                        retries: None,
+                    }],
+                )
+            })
+            .collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, branch_name).await.unwrap();
+        transaction.commit().await.unwrap()
+    }
 }
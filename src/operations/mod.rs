@@ -1,8 +1,36 @@
+pub mod add_location_alias;
 pub mod add_locations;
 pub mod add_tests;
+pub mod assign_failure;
+pub mod check_permission;
 pub mod close_branch;
+pub mod deprecate_tests;
+pub mod edit_roster;
+pub mod edit_tests;
+pub mod export_tests;
+pub mod flaky_tests;
+pub mod grant_command;
+pub mod health_check;
+pub mod lease_tests;
+pub mod home_summary;
+pub mod lint_tests;
+pub mod list_branches;
 pub mod load_all_locations;
 pub mod merge_branch;
+pub mod my_failed_tests;
+pub mod open_pending_prs;
+pub mod reconcile;
+pub mod remove_locations;
 pub mod remove_tests;
+pub mod repo_doctor;
+pub mod report_test_run;
+pub mod reset_test_progress;
+pub mod resource_coordinator;
+pub mod restore_test;
 pub mod save_progress;
 pub mod search_tests;
+pub mod select_options;
+pub mod test_digest;
+pub mod usage_stats;
+pub mod view_audit_log;
+pub mod view_suites;
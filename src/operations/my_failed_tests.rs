@@ -0,0 +1,79 @@
+use anyhow::Result;
+
+use crate::{
+    language::storage::RoswaalStoredCompilationFailure, utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// The maximum number of failures shown by `/my-failed-tests`.
+const MAX_SHOWN_COMPILATION_FAILURES: u32 = 5;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum MyFailedTestsStatus {
+    Success(Vec<RoswaalStoredCompilationFailure>),
+    NoFailedTests,
+}
+
+impl MyFailedTestsStatus {
+    /// Loads the tests that most recently failed to compile when the slack user with
+    /// `slack_user_id` gave them to `/add-tests`, newest first.
+    pub async fn from_recent_failures(
+        slack_user_id: &str,
+        database: &RoswaalDatabase,
+    ) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async {
+            transaction
+                .recent_compilation_failures(slack_user_id, MAX_SHOWN_COMPILATION_FAILURES)
+                .await
+                .map(|failures| {
+                    if failures.is_empty() {
+                        Self::NoFailedTests
+                    } else {
+                        Self::Success(failures)
+                    }
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_no_failed_tests_when_the_user_has_none() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_compilation_failure("U2", "New Test: A", &[])
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let status = MyFailedTestsStatus::from_recent_failures("U1", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, MyFailedTestsStatus::NoFailedTests)
+    }
+
+    #[tokio::test]
+    async fn only_loads_the_most_recent_failures() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        for i in 0..(MAX_SHOWN_COMPILATION_FAILURES + 2) {
+            transaction
+                .save_compilation_failure("U1", &format!("New Test: {}", i), &[])
+                .await
+                .unwrap();
+        }
+        transaction.commit().await.unwrap();
+        let status = MyFailedTestsStatus::from_recent_failures("U1", &database)
+            .await
+            .unwrap();
+        let MyFailedTestsStatus::Success(failures) = status else {
+            panic!()
+        };
+        assert_eq!(failures.len(), MAX_SHOWN_COMPILATION_FAILURES as usize);
+        assert_eq!(failures[0].syntax(), "New Test: 6")
+    }
+}
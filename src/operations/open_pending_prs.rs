@@ -0,0 +1,141 @@
+use anyhow::Result;
+
+use crate::{
+    git::pull_request::{is_transient_github_error, GithubPullRequest, GithubPullRequestOpen},
+    utils::{config::RoswaalConfig, db::RoswaalDatabase, retry::retry},
+    with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OpenPendingPullRequestsStatus {
+    Opened {
+        opened: Vec<GithubPullRequest>,
+        failed: Vec<GithubPullRequest>,
+    },
+    NoPendingPullRequests,
+}
+
+impl OpenPendingPullRequestsStatus {
+    /// Opens each PR that was deferred by the staged PR mode in the order that they were saved.
+    ///
+    /// Opened PRs are no longer pending, whereas PRs that fail to open remain pending so that
+    /// they can be opened by running this operation again.
+    pub async fn from_opening_pending_pull_requests(
+        database: &RoswaalDatabase,
+        pr_open: &impl GithubPullRequestOpen,
+    ) -> Result<Self> {
+        let mut transaction = database.transaction().await?;
+        let pull_requests = with_transaction!(transaction, async {
+            transaction.pending_pull_requests().await
+        })?;
+        if pull_requests.is_empty() {
+            return Ok(Self::NoPendingPullRequests);
+        }
+        let policy = RoswaalConfig::current().retry_policy();
+        let (mut opened, mut failed) = (Vec::new(), Vec::new());
+        for pull_request in pull_requests {
+            let result = retry(policy, is_transient_github_error, || {
+                pr_open.open(&pull_request)
+            })
+            .await;
+            match result {
+                Ok(true) => {
+                    let mut transaction = database.transaction().await?;
+                    with_transaction!(transaction, async {
+                        transaction.remove_pending_pull_request(&pull_request).await
+                    })?;
+                    opened.push(pull_request)
+                }
+                Ok(false) => failed.push(pull_request),
+                Err(error) => {
+                    log::error!(
+                        "Failed to open pending PR for {}. {}",
+                        pull_request.head_branch().to_string(),
+                        error
+                    );
+                    failed.push(pull_request)
+                }
+            }
+        }
+        Ok(Self::Opened { opened, failed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::{
+        branch_name::RoswaalOwnedGitBranchName, test_support::TestGithubPullRequestOpen,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_no_pending_pull_requests_when_none_were_saved() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let pr_open = TestGithubPullRequestOpen::new(false);
+        let status =
+            OpenPendingPullRequestsStatus::from_opening_pending_pull_requests(&database, &pr_open)
+                .await
+                .unwrap();
+        assert_eq!(status, OpenPendingPullRequestsStatus::NoPendingPullRequests);
+        assert_eq!(pr_open.most_recent_pr().await, None)
+    }
+
+    #[tokio::test]
+    async fn opens_and_removes_pending_pull_requests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let pr = save_pending_pull_request(&database).await;
+        let pr_open = TestGithubPullRequestOpen::new(false);
+        let status =
+            OpenPendingPullRequestsStatus::from_opening_pending_pull_requests(&database, &pr_open)
+                .await
+                .unwrap();
+        assert_eq!(
+            status,
+            OpenPendingPullRequestsStatus::Opened {
+                opened: vec![pr.clone()],
+                failed: vec![]
+            }
+        );
+        assert_eq!(pr_open.most_recent_pr().await, Some(pr));
+        let mut transaction = database.transaction().await.unwrap();
+        assert_eq!(transaction.pending_pull_requests().await.unwrap(), vec![])
+    }
+
+    #[tokio::test]
+    async fn keeps_pull_requests_that_fail_to_open_pending() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let pr = save_pending_pull_request(&database).await;
+        for pr_open in [
+            TestGithubPullRequestOpen::new(true),
+            TestGithubPullRequestOpen::unavailable(),
+        ] {
+            let status = OpenPendingPullRequestsStatus::from_opening_pending_pull_requests(
+                &database, &pr_open,
+            )
+            .await
+            .unwrap();
+            assert_eq!(
+                status,
+                OpenPendingPullRequestsStatus::Opened {
+                    opened: vec![],
+                    failed: vec![pr.clone()]
+                }
+            );
+        }
+        let mut transaction = database.transaction().await.unwrap();
+        assert_eq!(transaction.pending_pull_requests().await.unwrap(), vec![pr])
+    }
+
+    async fn save_pending_pull_request(database: &RoswaalDatabase) -> GithubPullRequest {
+        let pr = GithubPullRequest::for_tif_react_frontend(
+            "Add Tests",
+            "Adds some tests",
+            &RoswaalOwnedGitBranchName::for_adding_tests(),
+        );
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_pending_pull_request(&pr).await.unwrap();
+        transaction.commit().await.unwrap();
+        pr
+    }
+}
@@ -0,0 +1,425 @@
+use std::{error::Error, fmt::Display, io::ErrorKind};
+
+use anyhow::Result;
+use tokio::fs::{read_dir, remove_dir_all};
+
+use crate::{
+    generation::interface::test_directory_name,
+    git::{
+        branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+        clean::RoswaalLargeFileDeletion,
+        edit::EditGitRepositoryStatus,
+        metadata::RoswaalGitRepositoryMetadata,
+        pull_request::GithubPullRequestOpen,
+        repo::{
+            is_transient_git_error, PullBranchStatus, RoswaalGitRepository,
+            RoswaalGitRepositoryClient, RoswaalGitRepositoryTransaction,
+        },
+    },
+    tests_data::{query::RoswaalSearchTestsQuery, test::RoswaalTest},
+    utils::{
+        config::RoswaalConfig,
+        db::RoswaalDatabase,
+        retry::{retry, RetriesExhaustedError},
+    },
+    with_transaction,
+};
+
+use super::resource_coordinator::ResourceCoordinator;
+
+/// The action that opens a PR to remove the orphaned test directories.
+pub const RECONCILE_TESTS_CLEANUP_ACTION: &str = "cleanup";
+
+/// A comparison between the generated test directories of a repository and the stored tests.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RoswaalTestDirectoryReconciliation {
+    orphaned_directory_names: Vec<String>,
+    missing_test_names: Vec<String>,
+}
+
+impl RoswaalTestDirectoryReconciliation {
+    /// Compares the names of the generated test directories against the directories that the
+    /// code of `tests` is generated in.
+    ///
+    /// A directory is orphaned when no merged or unmerged test generates code in it. Only merged
+    /// tests are reported as missing their directory, since the directory of an unmerged test
+    /// only exists on its branch. Planned tests have no generated code, so they are ignored.
+    pub fn new(directory_names: &[String], tests: &[RoswaalTest]) -> Self {
+        let generated_tests = tests.iter().filter(|t| !t.is_planned());
+        let test_directory_names = generated_tests
+            .clone()
+            .map(|t| test_directory_name(t.name()))
+            .collect::<Vec<String>>();
+        let orphaned_directory_names = directory_names
+            .iter()
+            .filter(|name| !test_directory_names.contains(name))
+            .cloned()
+            .collect();
+        let missing_test_names = generated_tests
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .filter(|t| !directory_names.contains(&test_directory_name(t.name())))
+            .map(|t| t.name().to_string())
+            .collect();
+        Self {
+            orphaned_directory_names,
+            missing_test_names,
+        }
+    }
+
+    /// Reads the names of the generated test directories in the repository of `metadata`, and
+    /// compares them against `tests`.
+    ///
+    /// Only directories are read, so other files alongside the tests (eg. `Locations.ts`) are
+    /// never considered orphans.
+    pub async fn from_reading_repository(
+        metadata: &RoswaalGitRepositoryMetadata,
+        tests: &[RoswaalTest],
+    ) -> Result<Self> {
+        let mut directory_names = Vec::new();
+        let mut entries = match read_dir(metadata.test_cases_root_dirpath()).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Self::new(&[], tests)),
+            Err(error) => return Err(error.into()),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_type().await?.is_dir() {
+                directory_names.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        directory_names.sort();
+        Ok(Self::new(&directory_names, tests))
+    }
+}
+
+impl RoswaalTestDirectoryReconciliation {
+    /// Returns the names of the directories that do not belong to any stored test.
+    pub fn orphaned_directory_names(&self) -> &[String] {
+        &self.orphaned_directory_names
+    }
+
+    /// Returns the names of the merged tests that have no generated directory.
+    pub fn missing_test_names(&self) -> &[String] {
+        &self.missing_test_names
+    }
+
+    /// Returns true if every directory belongs to a test, and every merged test has a directory.
+    pub fn is_reconciled(&self) -> bool {
+        self.orphaned_directory_names.is_empty() && self.missing_test_names.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReconcileTestsStatus {
+    Report(RoswaalTestDirectoryReconciliation),
+    CleanedUp {
+        reconciliation: RoswaalTestDirectoryReconciliation,
+        should_warn_undeleted_branch: bool,
+        branch_name: RoswaalOwnedGitBranchName,
+    },
+    InvalidAction(String),
+    FailedToOpenPullRequest,
+    MergeConflict,
+    RemoteUnavailable,
+}
+
+impl ReconcileTestsStatus {
+    /// Returns the branch that the orphaned directories were removed on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::CleanedUp { branch_name, .. } => Some(branch_name),
+            _ => None,
+        }
+    }
+}
+
+impl ReconcileTestsStatus {
+    /// Compares the generated test directories on the base branch of `git_repository` against
+    /// the stored tests.
+    ///
+    /// An empty action string only reports the orphaned and missing directories. The `cleanup`
+    /// action also opens a PR that removes the orphaned directories, unless there are none.
+    pub async fn from_reconciling_tests<Client: RoswaalGitRepositoryClient>(
+        action_str: &str,
+        database: &RoswaalDatabase,
+        git_repository: &RoswaalGitRepository<Client>,
+        pr_open: &impl GithubPullRequestOpen,
+    ) -> Result<Self> {
+        let action_str = action_str.trim();
+        let is_cleanup = match action_str {
+            "" => false,
+            RECONCILE_TESTS_CLEANUP_ACTION => true,
+            _ => return Ok(Self::InvalidAction(action_str.to_string())),
+        };
+        let tests = Self::stored_tests(database).await?;
+        let resources = ResourceCoordinator::new(database, git_repository);
+        let transaction = resources.git().await;
+        let metadata = transaction.metadata().clone();
+        if !is_cleanup {
+            return Self::from_reporting(transaction, &metadata, &tests).await;
+        }
+        let branch_name = metadata.new_branch_name(RoswaalOwnedBranchKind::RemoveOrphanedTests);
+        let edit_result = EditGitRepositoryStatus::from_editing_new_branch(
+            &branch_name,
+            transaction,
+            pr_open,
+            async {
+                let reconciliation =
+                    RoswaalTestDirectoryReconciliation::from_reading_repository(&metadata, &tests)
+                        .await?;
+                let orphaned_directory_names = reconciliation.orphaned_directory_names();
+                if orphaned_directory_names.is_empty() {
+                    return Err(NoOrphanedTestsError(reconciliation).into());
+                }
+                for name in orphaned_directory_names {
+                    remove_dir_all(metadata.test_cases_root_dirpath().join(name)).await?;
+                }
+                let pr = metadata
+                    .remove_orphaned_tests_pull_request(orphaned_directory_names, &branch_name);
+                Ok((pr, reconciliation))
+            },
+        )
+        .await;
+        match edit_result {
+            Ok(EditGitRepositoryStatus::Success {
+                did_delete_branch,
+                value: reconciliation,
+            }) => Ok(Self::CleanedUp {
+                reconciliation,
+                should_warn_undeleted_branch: !did_delete_branch,
+                branch_name,
+            }),
+            Ok(EditGitRepositoryStatus::MergeConflict) => Ok(Self::MergeConflict),
+            Ok(EditGitRepositoryStatus::RemoteUnavailable) => Ok(Self::RemoteUnavailable),
+            Ok(EditGitRepositoryStatus::FailedToOpenPullRequest) => {
+                Ok(Self::FailedToOpenPullRequest)
+            }
+            Err(error) => {
+                let NoOrphanedTestsError(reconciliation) = error.downcast()?;
+                Ok(Self::Report(reconciliation))
+            }
+        }
+    }
+
+    async fn from_reporting(
+        transaction: RoswaalGitRepositoryTransaction<impl RoswaalGitRepositoryClient>,
+        metadata: &RoswaalGitRepositoryMetadata,
+        tests: &[RoswaalTest],
+    ) -> Result<Self> {
+        let base_branch_name = metadata.base_branch_name();
+        transaction.hard_reset_to_head().await?;
+        transaction
+            .clean_all_untracked(RoswaalLargeFileDeletion::Refuse)
+            .await?;
+        transaction.switch_branch(base_branch_name).await?;
+        let pull_result = retry(
+            RoswaalConfig::current().retry_policy(),
+            is_transient_git_error,
+            || transaction.pull_branch(base_branch_name),
+        )
+        .await;
+        match pull_result {
+            Ok(PullBranchStatus::Success) => Ok(Self::Report(
+                RoswaalTestDirectoryReconciliation::from_reading_repository(metadata, tests)
+                    .await?,
+            )),
+            Ok(PullBranchStatus::MergeConflict) => Ok(Self::MergeConflict),
+            Err(error) if RetriesExhaustedError::is_exhausted(&error) => {
+                log::error!("The remote is unavailable. {}", error);
+                Ok(Self::RemoteUnavailable)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn stored_tests(database: &RoswaalDatabase) -> Result<Vec<RoswaalTest>> {
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async {
+            transaction
+                .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                .await
+        })
+    }
+}
+
+#[derive(Debug)]
+struct NoOrphanedTestsError(RoswaalTestDirectoryReconciliation);
+
+impl Display for NoOrphanedTestsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NoOrphanedTestsError")
+    }
+}
+
+impl Error for NoOrphanedTestsError {}
+
+#[cfg(test)]
+mod tests {
+    use tokio::fs::{create_dir_all, try_exists};
+
+    use super::*;
+    use crate::{
+        git::test_support::{
+            with_clean_test_repo_access, NoopGitRepositoryClient, TestGithubPullRequestOpen,
+        },
+        operations::{add_tests::AddTestsStatus, merge_branch::MergeBranchStatus},
+        runner::webhook::test_support::NO_TEST_RUNNER,
+    };
+
+    #[test]
+    fn reports_orphaned_directories_and_merged_tests_missing_directories() {
+        let tests = vec![
+            mock_test("Join an event", None),
+            mock_test("Leave an event", None),
+            mock_test(
+                "Edit an event",
+                Some(RoswaalOwnedGitBranchName::for_adding_tests()),
+            ),
+            mock_test("Delete an event", None).with_planned(true),
+        ];
+        let directory_names = vec![
+            "join-an-event".to_string(),
+            "edit-an-event".to_string(),
+            "view-the-map".to_string(),
+        ];
+        let reconciliation = RoswaalTestDirectoryReconciliation::new(&directory_names, &tests);
+        assert_eq!(reconciliation.orphaned_directory_names(), &["view-the-map"]);
+        assert_eq!(reconciliation.missing_test_names(), &["Leave an event"]);
+        assert!(!reconciliation.is_reconciled())
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_action() {
+        let status = ReconcileTestsStatus::from_reconciling_tests(
+            "delete-everything",
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            ReconcileTestsStatus::InvalidAction("delete-everything".to_string())
+        )
+    }
+
+    #[tokio::test]
+    async fn reports_orphaned_directories_without_opening_pr() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            create_dir_all(metadata.test_dirpath("Zanza The Divine")).await?;
+            let status =
+                ReconcileTestsStatus::from_reconciling_tests("", &database, &repo, &pr_open)
+                    .await?;
+            let ReconcileTestsStatus::Report(reconciliation) = status else {
+                panic!("Expected a report, got {:?}", status)
+            };
+            assert_eq!(
+                reconciliation.orphaned_directory_names(),
+                &["zanza-the-divine"]
+            );
+            assert!(reconciliation.missing_test_names().is_empty());
+            assert!(try_exists(metadata.test_dirpath("Zanza The Divine")).await?);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cleanup_removes_orphaned_directories_in_pr() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            create_dir_all(metadata.test_dirpath("Zanza The Divine")).await?;
+            let status =
+                ReconcileTestsStatus::from_reconciling_tests("cleanup", &database, &repo, &pr_open)
+                    .await?;
+            let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
+            assert_eq!(
+                branch_name.kind(),
+                Some(RoswaalOwnedBranchKind::RemoveOrphanedTests)
+            );
+            assert_eq!(status.branch_name(), Some(&branch_name));
+            assert!(!try_exists(metadata.test_dirpath("Zanza The Divine")).await?);
+            assert!(try_exists(metadata.test_dirpath("Blob")).await?);
+            let pr = pr_open.most_recent_pr().await.unwrap();
+            assert!(pr
+                .title()
+                .contains("Remove Orphaned Tests (zanza-the-divine)"));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cleanup_does_not_open_pr_when_no_orphaned_directories() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            let add_tests_branch_name = pr_open.most_recent_head_branch_name().await;
+            let status =
+                ReconcileTestsStatus::from_reconciling_tests("cleanup", &database, &repo, &pr_open)
+                    .await?;
+            assert_eq!(
+                status,
+                ReconcileTestsStatus::Report(RoswaalTestDirectoryReconciliation::default())
+            );
+            assert_eq!(
+                pr_open.most_recent_head_branch_name().await,
+                add_tests_branch_name
+            );
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    fn mock_test(
+        name: &str,
+        unmerged_branch_name: Option<RoswaalOwnedGitBranchName>,
+    ) -> RoswaalTest {
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            vec![],
+            None,
+            None,
+            None,
+            unmerged_branch_name,
+            None,
+        )
+    }
+
+    async fn add_and_merge_blob(
+        database: &RoswaalDatabase,
+        repo: &RoswaalGitRepository<NoopGitRepositoryClient>,
+        pr_open: &TestGithubPullRequestOpen,
+    ) -> Result<()> {
+        let tests_str = "\
+```
+New Test: Blob
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+";
+        AddTestsStatus::from_adding_tests(tests_str, database, pr_open, repo, &[], None).await?;
+        MergeBranchStatus::from_merging_branch_with_name(
+            &pr_open.most_recent_head_branch_name().await.unwrap(),
+            database,
+            NO_TEST_RUNNER,
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,378 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::Result;
+
+use crate::{
+    generation::{formatter::format_generated_files, locations::save_locations_code},
+    git::{
+        branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+        edit::EditGitRepositoryStatus,
+        metadata::RoswaalGitRepositoryMetadata,
+        pull_request::GithubPullRequestOpen,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    location::{
+        location::RoswaalLocation,
+        name::RoswaalLocationName,
+        storage::{LoadLocationsFilter, RoswaalStoredLocation},
+    },
+    utils::{db::RoswaalDatabase, stage::RoswaalOperationStage},
+    with_transaction,
+};
+
+use super::resource_coordinator::ResourceCoordinator;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RemoveLocationsStatus {
+    Success {
+        removed_location_names: Vec<RoswaalLocationName>,
+        should_warn_undeleted_branch: bool,
+        branch_name: RoswaalOwnedGitBranchName,
+    },
+    NoLocationsRemoved,
+    FailedToOpenPullRequest,
+    MergeConflict,
+    RemoteUnavailable,
+}
+
+impl RemoveLocationsStatus {
+    /// Returns the branch that the locations were removed on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::Success { branch_name, .. } => Some(branch_name),
+            _ => None,
+        }
+    }
+}
+
+impl RemoveLocationsStatus {
+    /// Removes the merged locations named on each line of `names_str`.
+    ///
+    /// Names are matched case and whitespace insensitively, and names that do not match any
+    /// merged location are ignored. The locations code file is regenerated without the removed
+    /// locations, and the removals are staged until the PR is merged.
+    pub async fn from_removing_locations(
+        names_str: &str,
+        git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+        database: &RoswaalDatabase,
+        pr_open: &impl GithubPullRequestOpen,
+    ) -> Result<Self> {
+        let names = names_str
+            .lines()
+            .filter_map(|line| RoswaalLocationName::from_str(line.trim()).ok())
+            .collect::<Vec<RoswaalLocationName>>();
+        if names.is_empty() {
+            return Ok(Self::NoLocationsRemoved);
+        }
+        let mut resources = ResourceCoordinator::new(database, git_repository);
+        let (git_transaction, mut transaction) = resources.git_and_database().await?;
+        let stored_locations = with_transaction!(transaction, async {
+            transaction
+                .locations_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
+                .await
+        })?;
+        let (removed_locations, remaining_locations): (Vec<_>, Vec<_>) = stored_locations
+            .iter()
+            .partition(|l| names.iter().any(|n| l.location().name().matches(n)));
+        if removed_locations.is_empty() {
+            return Ok(Self::NoLocationsRemoved);
+        }
+        let removed_location_names = removed_locations
+            .iter()
+            .map(|l| l.location().name().clone())
+            .collect::<Vec<RoswaalLocationName>>();
+
+        let metadata = git_transaction.metadata().clone();
+        let branch_name = metadata.new_branch_name(RoswaalOwnedBranchKind::RemoveLocations);
+        let edit_status = EditGitRepositoryStatus::from_editing_new_branch(
+            &branch_name,
+            git_transaction,
+            pr_open,
+            async {
+                let paths = Self::generate_locations_code(&remaining_locations, &metadata).await?;
+                format_generated_files(&metadata, &paths).await;
+                Ok((
+                    metadata.remove_locations_pull_request(&removed_location_names, &branch_name),
+                    (),
+                ))
+            },
+        )
+        .await?;
+
+        match edit_status {
+            EditGitRepositoryStatus::Success {
+                did_delete_branch,
+                value: _,
+            } => {
+                transaction = resources.database().await?;
+                with_transaction!(transaction, async {
+                    transaction
+                        .stage_location_removals(&removed_location_names, &branch_name)
+                        .await?;
+                    Ok(Self::Success {
+                        removed_location_names,
+                        should_warn_undeleted_branch: !did_delete_branch,
+                        branch_name,
+                    })
+                })
+            }
+            EditGitRepositoryStatus::FailedToOpenPullRequest => Ok(Self::FailedToOpenPullRequest),
+            EditGitRepositoryStatus::MergeConflict => Ok(Self::MergeConflict),
+            EditGitRepositoryStatus::RemoteUnavailable => Ok(Self::RemoteUnavailable),
+        }
+    }
+
+    async fn generate_locations_code(
+        remaining_locations: &[&RoswaalStoredLocation],
+        metadata: &RoswaalGitRepositoryMetadata,
+    ) -> Result<Vec<PathBuf>> {
+        RoswaalOperationStage::Generating.report();
+        let locations = remaining_locations
+            .iter()
+            .map(|l| l.location())
+            .collect::<Vec<&RoswaalLocation>>();
+        save_locations_code(metadata, &locations).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use tokio::fs::try_exists;
+
+    use crate::{
+        generation::interface::CodeGeneratorKind,
+        git::{
+            metadata::RoswaalGitRepositoryMetadata,
+            repo::RoswaalGitRepository,
+            test_support::{
+                read_string, with_clean_test_repo_access, NoopGitRepositoryClient,
+                TestGithubPullRequestOpen,
+            },
+        },
+        location::{name::RoswaalLocationName, storage::LoadLocationsFilter},
+        operations::{
+            add_locations::AddLocationsStatus, merge_branch::MergeBranchStatus,
+            remove_locations::RemoveLocationsStatus,
+        },
+        runner::webhook::test_support::NO_TEST_RUNNER,
+        utils::db::RoswaalDatabase,
+    };
+
+    #[tokio::test]
+    async fn reports_no_locations_removed_when_empty_string() {
+        let status = RemoveLocationsStatus::from_removing_locations(
+            "",
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &RoswaalDatabase::in_memory().await.unwrap(),
+            &TestGithubPullRequestOpen::new(false),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, RemoveLocationsStatus::NoLocationsRemoved)
+    }
+
+    #[tokio::test]
+    async fn reports_no_locations_removed_and_does_not_open_pr_when_no_names_match() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_locations(&database, &repo, &pr_open).await?;
+            let status =
+                RemoveLocationsStatus::from_removing_locations("Namek", &repo, &database, &pr_open)
+                    .await?;
+            assert_eq!(status, RemoveLocationsStatus::NoLocationsRemoved);
+            assert!(pr_open
+                .most_recent_pr()
+                .await
+                .unwrap()
+                .title()
+                .contains("Add Locations"));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_removed_location_names_and_opens_pr() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_locations(&database, &repo, &pr_open).await?;
+            let status = RemoveLocationsStatus::from_removing_locations(
+                "  test  \nNamek\nTEST",
+                &repo,
+                &database,
+                &pr_open,
+            )
+            .await?;
+            let expected_status = RemoveLocationsStatus::Success {
+                removed_location_names: vec![RoswaalLocationName::from_str("Test").unwrap()],
+                should_warn_undeleted_branch: false,
+                branch_name: pr_open.most_recent_head_branch_name().await.unwrap(),
+            };
+            assert_eq!(status, expected_status);
+            assert!(pr_open
+                .most_recent_pr()
+                .await
+                .unwrap()
+                .title()
+                .contains("Remove Locations (Test)"));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn regenerates_code_file_without_removed_locations() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing();
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_locations(&database, &repo, &pr_open).await?;
+            _ = RemoveLocationsStatus::from_removing_locations("Test", &repo, &database, &pr_open)
+                .await?;
+            let content = read_string(metadata.locations_path()).await?;
+            let expected_content = "\
+// Generated by Roswaal, do not touch.
+
+import { LocationCoordinate2D } from \"TiFShared/domain-models/LocationCoordinate2D\"
+
+export const setUserLocation = async (coordinate: LocationCoordinate2D) => {
+  await device.setLocation(coordinate.latitude, coordinate.longitude)
+}
+
+export namespace TestLocations {
+  export const Test2 = {
+    latitude: 45.0000000000000000,
+    longitude: 45.0000000000000000
+  }
+}
+";
+            assert_eq!(&content, expected_content);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn regenerates_location_flows_without_removed_locations_in_maestro_repos() {
+        with_clean_test_repo_access(async {
+            let metadata = RoswaalGitRepositoryMetadata::for_testing()
+                .with_code_generator(CodeGeneratorKind::Maestro);
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::open(&metadata).await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_locations(&database, &repo, &pr_open).await?;
+            let flows_dirpath = metadata.location_flows_dirpath();
+            assert!(read_string(flows_dirpath.join("Test.yaml"))
+                .await?
+                .contains("- setLocation:\n    latitude: 50.0000000000000000\n"));
+            _ = RemoveLocationsStatus::from_removing_locations("Test", &repo, &database, &pr_open)
+                .await?;
+            assert!(!try_exists(flows_dirpath.join("Test.yaml")).await?);
+            assert!(read_string(flows_dirpath.join("Test2.yaml"))
+                .await?
+                .contains("- setLocation:\n    latitude: 45.0000000000000000\n"));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn merging_removal_branch_removes_stored_locations() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_locations(&database, &repo, &pr_open).await?;
+            _ = RemoveLocationsStatus::from_removing_locations("Test", &repo, &database, &pr_open)
+                .await?;
+            let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
+            MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
+            let mut transaction = database.transaction().await?;
+            let names = transaction
+                .location_names_in_alphabetical_order(LoadLocationsFilter::All)
+                .await?;
+            transaction.commit().await?;
+            assert_eq!(
+                names,
+                vec![RoswaalLocationName::from_str("Test 2").unwrap()]
+            );
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_pr_open_failed_status_when_failing_to_open_pr() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            add_and_merge_locations(&database, &repo, &TestGithubPullRequestOpen::new(false))
+                .await?;
+            let status = RemoveLocationsStatus::from_removing_locations(
+                "Test",
+                &repo,
+                &database,
+                &TestGithubPullRequestOpen::new(true),
+            )
+            .await?;
+            assert_eq!(status, RemoveLocationsStatus::FailedToOpenPullRequest);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn reports_merge_conflict_status_when_merge_conflict_occurs() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_locations(&database, &RoswaalGitRepository::noop().await?, &pr_open)
+                .await?;
+            let repo = RoswaalGitRepository::noop_ensuring_merge_conflicts().await?;
+            let status =
+                RemoveLocationsStatus::from_removing_locations("Test", &repo, &database, &pr_open)
+                    .await?;
+            assert_eq!(status, RemoveLocationsStatus::MergeConflict);
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn add_and_merge_locations(
+        database: &RoswaalDatabase,
+        repo: &RoswaalGitRepository<NoopGitRepositoryClient>,
+        pr_open: &TestGithubPullRequestOpen,
+    ) -> anyhow::Result<()> {
+        let locations_str = "\
+Test, 50.0, 50.0
+Test 2, 45.0, 45.0
+";
+        AddLocationsStatus::from_adding_locations(locations_str, repo, database, pr_open).await?;
+        MergeBranchStatus::from_merging_branch_with_name(
+            &pr_open.most_recent_head_branch_name().await.unwrap(),
+            database,
+            NO_TEST_RUNNER,
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -5,51 +5,83 @@ use tokio::{fs::remove_dir_all, spawn};
 
 use crate::{
     git::{
-        branch_name::RoswaalOwnedGitBranchName,
-        edit::EditGitRepositoryStatus,
+        branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+        edit::{EditGitRepositoryStatus, TargetRepositoryEdit},
         metadata::RoswaalGitRepositoryMetadata,
         pull_request::GithubPullRequestOpen,
         repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
     },
-    tests_data::query::RoswaalTestNamesString,
-    utils::{dedup::DedupIterator, sqlite::RoswaalSqlite},
+    tests_data::{
+        query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+        test::RoswaalTest,
+    },
+    utils::{db::RoswaalDatabase, dedup::DedupIterator},
     with_transaction,
 };
 
+use super::resource_coordinator::ResourceCoordinator;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RemoveTestsStatus {
     Success {
         removed_test_names: Vec<String>,
+        /// The last known version of each removed test that was merged.
+        removed_tests: Vec<RoswaalTest>,
         should_warn_undeleted_branch: bool,
+        /// The branch that the tests were removed on, or None if no tests were removed.
+        branch_name: Option<RoswaalOwnedGitBranchName>,
+        /// The results of removing the tests from each additional target repository.
+        target_edits: Vec<TargetRepositoryEdit>,
     },
     NoTestsRemoved,
     FailedToOpenPullRequest,
     MergeConflict,
+    RemoteUnavailable,
+}
+
+impl RemoveTestsStatus {
+    /// Returns the branch that the tests were removed on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::Success { branch_name, .. } => branch_name.as_ref(),
+            _ => None,
+        }
+    }
 }
 
 impl RemoveTestsStatus {
-    pub async fn from_removing_tests(
+    /// Opens a PR that removes the code for each test named in `query_str` from
+    /// `git_repository`, and stages the removals until the PR is merged.
+    ///
+    /// If the PR is opened, the tests are then removed from each of the `target_repositories` on
+    /// a branch with the same name.
+    pub async fn from_removing_tests<Client: RoswaalGitRepositoryClient>(
         query_str: &str,
-        sqlite: &RoswaalSqlite,
-        git_repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+        database: &RoswaalDatabase,
+        git_repository: &RoswaalGitRepository<Client>,
         pr_open: &impl GithubPullRequestOpen,
+        target_repositories: &[RoswaalGitRepository<Client>],
     ) -> Result<Self> {
         let test_names = RoswaalTestNamesString::new(query_str);
         if test_names.is_empty() {
             return Ok(Self::NoTestsRemoved);
         }
 
-        let transaction = git_repository.transaction().await;
-        let branch_name = RoswaalOwnedGitBranchName::for_removing_tests();
+        let merged_tests = Self::merged_tests(query_str, database).await?;
+        let mut resources = ResourceCoordinator::new(database, git_repository);
+        let transaction = resources.git().await;
         let metadata = transaction.metadata().clone();
+        let branch_name = metadata.new_branch_name(RoswaalOwnedBranchKind::RemoveTests);
         let edit_result = EditGitRepositoryStatus::from_editing_new_branch(
             &branch_name,
             transaction,
             pr_open,
             async {
                 let removed_test_names = Self::remove_test_names(&test_names, &metadata).await?;
-                let pr = metadata.remove_tests_pull_request(&test_names, &branch_name);
-                Ok((pr, removed_test_names))
+                let removed_tests = Self::tests_named(&merged_tests, &removed_test_names);
+                let pr =
+                    metadata.remove_tests_pull_request(&test_names, &removed_tests, &branch_name);
+                Ok((pr, (removed_test_names, removed_tests)))
             },
         )
         .await;
@@ -57,20 +89,42 @@ impl RemoveTestsStatus {
         match edit_result {
             Ok(EditGitRepositoryStatus::Success {
                 did_delete_branch,
-                value: removed_test_names,
+                value: (removed_test_names, removed_tests),
             }) => {
-                let mut transaction = sqlite.transaction().await?;
+                let mut transaction = resources.database().await?;
                 with_transaction!(transaction, async {
                     transaction
                         .stage_test_removals(&test_names, &branch_name)
-                        .await?;
-                    Ok(Self::Success {
-                        removed_test_names,
-                        should_warn_undeleted_branch: !did_delete_branch,
-                    })
+                        .await
+                })?;
+                let target_edits = TargetRepositoryEdit::from_editing_targets(
+                    &branch_name,
+                    target_repositories,
+                    pr_open,
+                    |metadata| {
+                        let (test_names, removed_tests, branch_name) =
+                            (&test_names, &removed_tests, &branch_name);
+                        async move {
+                            Self::remove_test_names(test_names, &metadata).await?;
+                            Ok(metadata.remove_tests_pull_request(
+                                test_names,
+                                removed_tests,
+                                branch_name,
+                            ))
+                        }
+                    },
+                )
+                .await;
+                Ok(Self::Success {
+                    removed_test_names,
+                    removed_tests,
+                    should_warn_undeleted_branch: !did_delete_branch,
+                    branch_name: Some(branch_name),
+                    target_edits,
                 })
             }
             Ok(EditGitRepositoryStatus::MergeConflict) => Ok(Self::MergeConflict),
+            Ok(EditGitRepositoryStatus::RemoteUnavailable) => Ok(Self::RemoteUnavailable),
             Ok(EditGitRepositoryStatus::FailedToOpenPullRequest) => {
                 Ok(Self::FailedToOpenPullRequest)
             }
@@ -78,12 +132,36 @@ impl RemoveTestsStatus {
                 let _: NoTestsToRemoveError = err.downcast()?;
                 Ok(Self::Success {
                     removed_test_names: vec![],
+                    removed_tests: vec![],
                     should_warn_undeleted_branch: true,
+                    branch_name: None,
+                    target_edits: vec![],
                 })
             }
         }
     }
 
+    async fn merged_tests(query_str: &str, database: &RoswaalDatabase) -> Result<Vec<RoswaalTest>> {
+        let query = RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new(query_str));
+        let mut transaction = database.read_transaction().await?;
+        let tests = with_transaction!(transaction, async {
+            transaction.tests_in_alphabetical_order(&query).await
+        })?;
+        Ok(tests
+            .into_iter()
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .collect())
+    }
+
+    /// Returns the tests in `tests` whose names match one of `names` regardless of case.
+    fn tests_named(tests: &[RoswaalTest], names: &[String]) -> Vec<RoswaalTest> {
+        tests
+            .iter()
+            .filter(|t| names.iter().any(|n| n.eq_ignore_ascii_case(t.name())))
+            .cloned()
+            .collect()
+    }
+
     async fn remove_test_names(
         test_names: &RoswaalTestNamesString<'_>,
         metadata: &RoswaalGitRepositoryMetadata,
@@ -134,16 +212,18 @@ mod tests {
             },
         },
         operations::{add_tests::AddTestsStatus, merge_branch::MergeBranchStatus},
-        utils::sqlite::RoswaalSqlite,
+        runner::webhook::test_support::NO_TEST_RUNNER,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
     async fn reports_no_test_removed_when_empty_query_string() {
         let status = RemoveTestsStatus::from_removing_tests(
             "",
-            &RoswaalSqlite::in_memory().await.unwrap(),
+            &RoswaalDatabase::in_memory().await.unwrap(),
             &RoswaalGitRepository::noop().await.unwrap(),
             &TestGithubPullRequestOpen::new(false),
+            &[],
         )
         .await
         .unwrap();
@@ -153,12 +233,12 @@ mod tests {
     #[tokio::test]
     async fn removes_code_generated_by_adding_test() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let metadata = RoswaalGitRepositoryMetadata::for_testing();
             let repo = RoswaalGitRepository::noop().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
-            add_and_merge_blob(&sqlite, &repo, &pr_open).await?;
-            remove_blob(&sqlite, &repo, &pr_open).await?;
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            remove_blob(&database, &repo, &pr_open).await?;
             assert!(!try_exists(metadata.relative_path("roswaal/blob")).await?);
             Ok(())
         })
@@ -169,10 +249,10 @@ mod tests {
     #[tokio::test]
     async fn pr_not_opened_when_no_test_names_exist() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let repo = RoswaalGitRepository::noop().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
-            _ = remove_blob(&sqlite, &repo, &pr_open).await?;
+            _ = remove_blob(&database, &repo, &pr_open).await?;
             assert!(pr_open.most_recent_pr().await.is_none());
             Ok(())
         })
@@ -183,11 +263,11 @@ mod tests {
     #[tokio::test]
     async fn pr_opened_when_test_cases_removed() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let repo = RoswaalGitRepository::noop().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
-            add_and_merge_blob(&sqlite, &repo, &pr_open).await?;
-            _ = remove_blob(&sqlite, &repo, &pr_open).await?;
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            _ = remove_blob(&database, &repo, &pr_open).await?;
             assert!(pr_open
                 .most_recent_pr()
                 .await
@@ -203,20 +283,29 @@ mod tests {
     #[tokio::test]
     async fn reports_tests_names_that_were_removed_in_success_status() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let repo = RoswaalGitRepository::noop().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
-            add_and_merge_blob(&sqlite, &repo, &pr_open).await?;
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            let merged_tests = all_tests(&database).await?;
             let test_names_str = "\
 Blob
 Zanza The Divine
 ";
-            let status =
-                RemoveTestsStatus::from_removing_tests(test_names_str, &sqlite, &repo, &pr_open)
-                    .await?;
+            let status = RemoveTestsStatus::from_removing_tests(
+                test_names_str,
+                &database,
+                &repo,
+                &pr_open,
+                &[],
+            )
+            .await?;
             let expected_status = RemoveTestsStatus::Success {
                 removed_test_names: vec!["Blob".to_string()],
+                removed_tests: merged_tests,
                 should_warn_undeleted_branch: false,
+                branch_name: pr_open.most_recent_head_branch_name().await,
+                target_edits: vec![],
             };
             assert_eq!(status, expected_status);
             assert!(pr_open
@@ -234,11 +323,11 @@ Zanza The Divine
     #[tokio::test]
     async fn reports_pr_open_failed_status_when_failing_to_open_pr() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(true);
             let repo = RoswaalGitRepository::noop().await?;
-            add_and_merge_blob(&sqlite, &repo, &pr_open).await?;
-            let status = remove_blob(&sqlite, &repo, &pr_open).await?;
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            let status = remove_blob(&database, &repo, &pr_open).await?;
             assert_eq!(status, RemoveTestsStatus::FailedToOpenPullRequest);
             Ok(())
         })
@@ -249,10 +338,10 @@ Zanza The Divine
     #[tokio::test]
     async fn reports_merge_conflict_status_when_merge_conflict_occurs() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop_ensuring_merge_conflicts().await?;
-            let status = remove_blob(&sqlite, &repo, &pr_open).await?;
+            let status = remove_blob(&database, &repo, &pr_open).await?;
             assert_eq!(status, RemoveTestsStatus::MergeConflict);
             Ok(())
         })
@@ -263,7 +352,7 @@ Zanza The Divine
     #[tokio::test]
     async fn does_not_include_duplicate_removed_test_names() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop().await?;
             let tests_str = "\
@@ -278,14 +367,22 @@ Step 1: Step A
 Requirement 1: Requirement A
 ```
 ";
-            add_and_merge(tests_str, &sqlite, &repo, &pr_open).await?;
-            let status =
-                RemoveTestsStatus::from_removing_tests("Blob\nBlob", &sqlite, &repo, &pr_open)
-                    .await?;
+            add_and_merge(tests_str, &database, &repo, &pr_open).await?;
+            let status = RemoveTestsStatus::from_removing_tests(
+                "Blob\nBlob",
+                &database,
+                &repo,
+                &pr_open,
+                &[],
+            )
+            .await?;
             match status {
                 RemoveTestsStatus::Success {
                     removed_test_names,
+                    removed_tests: _,
                     should_warn_undeleted_branch: _,
+                    branch_name: _,
+                    target_edits: _,
                 } => {
                     assert_eq!(removed_test_names, vec!["Blob"])
                 }
@@ -297,8 +394,48 @@ Requirement 1: Requirement A
         .unwrap()
     }
 
+    #[tokio::test]
+    async fn pr_body_includes_spec_of_each_removed_test() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let pr_open = TestGithubPullRequestOpen::new(false);
+            add_and_merge_blob(&database, &repo, &pr_open).await?;
+            remove_blob(&database, &repo, &pr_open).await?;
+            let expected_spec = "\
+<details>
+<summary>Blob</summary>
+
+```
+New Test: Blob
+Step 1: Do the thing
+Requirement 1: Do the thing
+```
+
+</details>";
+            assert!(pr_open
+                .most_recent_pr()
+                .await
+                .unwrap()
+                .body()
+                .contains(expected_spec));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn all_tests(database: &RoswaalDatabase) -> Result<Vec<RoswaalTest>> {
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async {
+            transaction
+                .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                .await
+        })
+    }
+
     async fn add_and_merge_blob(
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
         repo: &RoswaalGitRepository<NoopGitRepositoryClient>,
         pr_open: &TestGithubPullRequestOpen,
     ) -> Result<()> {
@@ -309,29 +446,30 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-        add_and_merge(tests_str, sqlite, repo, pr_open).await
+        add_and_merge(tests_str, database, repo, pr_open).await
     }
 
     async fn add_and_merge(
         tests_str: &str,
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
         repo: &RoswaalGitRepository<NoopGitRepositoryClient>,
         pr_open: &TestGithubPullRequestOpen,
     ) -> Result<()> {
-        AddTestsStatus::from_adding_tests(tests_str, sqlite, pr_open, repo).await?;
+        AddTestsStatus::from_adding_tests(tests_str, database, pr_open, repo, &[], None).await?;
         MergeBranchStatus::from_merging_branch_with_name(
             &pr_open.most_recent_head_branch_name().await.unwrap(),
-            &sqlite,
+            database,
+            NO_TEST_RUNNER,
         )
         .await?;
         Ok(())
     }
 
     async fn remove_blob(
-        sqlite: &RoswaalSqlite,
+        database: &RoswaalDatabase,
         repo: &RoswaalGitRepository<NoopGitRepositoryClient>,
         pr_open: &TestGithubPullRequestOpen,
     ) -> Result<RemoveTestsStatus> {
-        RemoveTestsStatus::from_removing_tests("Blob", sqlite, repo, pr_open).await
+        RemoveTestsStatus::from_removing_tests("Blob", database, repo, pr_open, &[]).await
     }
 }
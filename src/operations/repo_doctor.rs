@@ -0,0 +1,293 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::{
+    git::{
+        branch_name::RoswaalOwnedGitBranchName,
+        clean::RoswaalLargeFileDeletion,
+        health::RoswaalGitRepositoryHealth,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    slack::users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// A repair that can be performed on every local clone.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumString, EnumIter, Display)]
+pub enum RepoDoctorAction {
+    /// Discards uncommitted changes, and switches back to the base branch.
+    #[strum(serialize = "reset")]
+    Reset,
+    /// Aborts any merge that was left in progress by a merge conflict.
+    #[strum(serialize = "abort-merge")]
+    AbortMerge,
+    /// Deletes the local branches created by this tool that no longer exist on the remote.
+    #[strum(serialize = "prune")]
+    Prune,
+    /// Deletes the local clone, and clones it again from the remote.
+    #[strum(serialize = "reclone")]
+    Reclone,
+}
+
+/// A flag that can be given along with `RepoDoctorAction::Reset` to also delete untracked files
+/// above the maximum size of the `RoswaalGitCleanPolicy` of each clone.
+pub const DELETE_LARGE_FILES_FLAG: &str = "--delete-large-files";
+
+/// The state of a local clone after the doctor has visited it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RoswaalRepositoryCheckup {
+    name: String,
+    health: RoswaalGitRepositoryHealth,
+    pruned_branch_names: Vec<String>,
+}
+
+impl RoswaalRepositoryCheckup {
+    pub fn new(
+        name: String,
+        health: RoswaalGitRepositoryHealth,
+        pruned_branch_names: Vec<String>,
+    ) -> Self {
+        Self {
+            name,
+            health,
+            pruned_branch_names,
+        }
+    }
+}
+
+impl RoswaalRepositoryCheckup {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn health(&self) -> &RoswaalGitRepositoryHealth {
+        &self.health
+    }
+
+    /// Returns the names of the local branches that were deleted by `RepoDoctorAction::Prune`.
+    pub fn pruned_branch_names(&self) -> &[String] {
+        &self.pruned_branch_names
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RepoDoctorStatus {
+    Success {
+        action: Option<RepoDoctorAction>,
+        checkups: Vec<RoswaalRepositoryCheckup>,
+    },
+    InvalidAction(String),
+    NotAuthorized {
+        roster: RoswaalSlackUserRoster,
+    },
+}
+
+impl RepoDoctorStatus {
+    /// Reports the health of the frontend repository and every test target repository after
+    /// performing the `RepoDoctorAction` named by `action_str` on each of them.
+    ///
+    /// An empty action string only reports the health of each repository. Only infra owners are
+    /// allowed to run the doctor, since repairs discard any work left in the local clones.
+    ///
+    /// Resetting fails if it would delete a large untracked file, unless `action_str` also
+    /// contains `DELETE_LARGE_FILES_FLAG`.
+    pub async fn from_running_doctor<Client: RoswaalGitRepositoryClient>(
+        action_str: &str,
+        user_id: &str,
+        database: &RoswaalDatabase,
+        git_repository: &RoswaalGitRepository<Client>,
+        target_repositories: &[RoswaalGitRepository<Client>],
+    ) -> Result<Self> {
+        let deletion = if action_str
+            .split_whitespace()
+            .any(|word| word == DELETE_LARGE_FILES_FLAG)
+        {
+            RoswaalLargeFileDeletion::Allow
+        } else {
+            RoswaalLargeFileDeletion::Refuse
+        };
+        let action_str = action_str.replace(DELETE_LARGE_FILES_FLAG, "");
+        let action_str = action_str.trim();
+        let action = if action_str.is_empty() {
+            None
+        } else {
+            match RepoDoctorAction::from_str(action_str) {
+                Ok(action) => Some(action),
+                Err(_) => return Ok(Self::InvalidAction(action_str.to_string())),
+            }
+        };
+        let mut transaction = database.transaction().await?;
+        let roster =
+            with_transaction!(transaction, async { transaction.slack_user_roster().await })?;
+        if !roster.has_role(user_id, RoswaalSlackUserRole::InfraOwner) {
+            return Ok(Self::NotAuthorized { roster });
+        }
+        let mut checkups = Vec::new();
+        for repository in std::iter::once(git_repository).chain(target_repositories) {
+            checkups.push(Self::checkup(action, deletion, repository).await?);
+        }
+        Ok(Self::Success { action, checkups })
+    }
+
+    async fn checkup(
+        action: Option<RepoDoctorAction>,
+        deletion: RoswaalLargeFileDeletion,
+        repository: &RoswaalGitRepository<impl RoswaalGitRepositoryClient>,
+    ) -> Result<RoswaalRepositoryCheckup> {
+        let mut transaction = repository.transaction().await;
+        let mut pruned_branch_names = Vec::new();
+        match action {
+            Some(RepoDoctorAction::Reset) => {
+                transaction.hard_reset_to_head().await?;
+                transaction.clean_all_untracked(deletion).await?;
+                let base_branch_name = transaction.metadata().base_branch_name().to_string();
+                transaction.switch_branch(&base_branch_name).await?;
+            }
+            Some(RepoDoctorAction::AbortMerge) => {
+                let is_merging = transaction.health().await?.is_merging();
+                if is_merging {
+                    transaction.abort_merge().await?;
+                }
+            }
+            Some(RepoDoctorAction::Prune) => {
+                let health = transaction.health().await?;
+                let remote_names = transaction.remote_branch_names().await?;
+                for name in transaction.local_branch_names().await? {
+                    if remote_names.contains(&name) || health.current_branch_name() == Some(&name) {
+                        continue;
+                    }
+                    let Ok(branch_name) = serde_json::from_value::<RoswaalOwnedGitBranchName>(
+                        serde_json::Value::String(name.clone()),
+                    ) else {
+                        continue;
+                    };
+                    if transaction.delete_local_branch(&branch_name).await? {
+                        pruned_branch_names.push(name)
+                    }
+                }
+            }
+            Some(RepoDoctorAction::Reclone) => transaction.reclone().await?,
+            None => {}
+        }
+        Ok(RoswaalRepositoryCheckup::new(
+            transaction.metadata().name().to_string(),
+            transaction.health().await?,
+            pruned_branch_names,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::config::RoswaalConfig;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_health_of_every_repository() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let repo = RoswaalGitRepository::noop().await.unwrap();
+        let unhealthy_health =
+            RoswaalGitRepositoryHealth::new(None, vec!["test.txt".to_string()], true);
+        let target = RoswaalGitRepository::noop_with_health(unhealthy_health.clone())
+            .await
+            .unwrap();
+        let status = RepoDoctorStatus::from_running_doctor(
+            " ",
+            RoswaalConfig::current().maintainer_slack_user_id(),
+            &database,
+            &repo,
+            &[target],
+        )
+        .await
+        .unwrap();
+        let expected_checkups = vec![
+            RoswaalRepositoryCheckup::new(
+                "FitnessProjectTest".to_string(),
+                RoswaalGitRepositoryHealth::clean("main"),
+                vec![],
+            ),
+            RoswaalRepositoryCheckup::new(
+                "FitnessProjectTest".to_string(),
+                unhealthy_health,
+                vec![],
+            ),
+        ];
+        assert_eq!(
+            status,
+            RepoDoctorStatus::Success {
+                action: None,
+                checkups: expected_checkups
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn prunes_roswaal_branches_missing_from_remote() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let stale_name = RoswaalOwnedGitBranchName::new("stale").to_string();
+        let pushed_name = RoswaalOwnedGitBranchName::new("pushed").to_string();
+        let repo = RoswaalGitRepository::noop_with_local_branch_names(&[
+            "main",
+            "feature",
+            &stale_name,
+            &pushed_name,
+        ])
+        .await
+        .unwrap();
+        repo.transaction()
+            .await
+            .set_remote_branch_names(vec!["main".to_string(), pushed_name]);
+        let status = RepoDoctorStatus::from_running_doctor(
+            "prune",
+            RoswaalConfig::current().maintainer_slack_user_id(),
+            &database,
+            &repo,
+            &[],
+        )
+        .await
+        .unwrap();
+        let RepoDoctorStatus::Success { action, checkups } = status else {
+            panic!("Expected the doctor to run.")
+        };
+        assert_eq!(action, Some(RepoDoctorAction::Prune));
+        assert_eq!(checkups[0].pruned_branch_names(), &[stale_name])
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_action() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let repo = RoswaalGitRepository::noop().await.unwrap();
+        let status = RepoDoctorStatus::from_running_doctor(
+            "amputate",
+            RoswaalConfig::current().maintainer_slack_user_id(),
+            &database,
+            &repo,
+            &[],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            RepoDoctorStatus::InvalidAction("amputate".to_string())
+        )
+    }
+
+    #[tokio::test]
+    async fn only_infra_owners_can_run_doctor() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let repo = RoswaalGitRepository::noop().await.unwrap();
+        let status = RepoDoctorStatus::from_running_doctor("reclone", "U1", &database, &repo, &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            RepoDoctorStatus::NotAuthorized {
+                roster: RoswaalSlackUserRoster::default()
+            }
+        )
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use crate::{runner::run::RoswaalTestRun, utils::db::RoswaalDatabase, with_transaction};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReportTestRunStatus {
+    Reported(RoswaalTestRun),
+    UnknownRun(i64),
+}
+
+impl ReportTestRunStatus {
+    /// Records the link that a CI runner called back with for the run with `run_id`.
+    pub async fn from_runner_callback(
+        run_id: i64,
+        url: &str,
+        database: &RoswaalDatabase,
+    ) -> Result<Self> {
+        let mut transaction = database.transaction().await?;
+        let run = with_transaction!(transaction, async {
+            transaction.save_test_run_url(run_id, url).await
+        })?;
+        match run {
+            Some(run) => Ok(Self::Reported(run)),
+            None => Ok(Self::UnknownRun(run_id)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_run_with_url() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let run = transaction
+            .save_test_run(
+                &RoswaalOwnedGitBranchName::new("test"),
+                &["Join an event".to_string()],
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let url = "https://ci.example.com/runs/1";
+        let status = ReportTestRunStatus::from_runner_callback(run.id(), url, &database)
+            .await
+            .unwrap();
+        let expected_run = RoswaalTestRun::new(
+            run.id(),
+            run.branch_name().clone(),
+            run.test_names().to_vec(),
+            Some(url.to_string()),
+        );
+        assert_eq!(status, ReportTestRunStatus::Reported(expected_run))
+    }
+
+    #[tokio::test]
+    async fn reports_unknown_run() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = ReportTestRunStatus::from_runner_callback(
+            10,
+            "https://ci.example.com/runs/10",
+            &database,
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, ReportTestRunStatus::UnknownRun(10))
+    }
+}
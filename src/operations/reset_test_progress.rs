@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+use crate::{
+    tests_data::query::RoswaalTestNamesString, utils::db::RoswaalDatabase, with_transaction,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ResetTestProgressStatus {
+    Success { reset_test_names: Vec<String> },
+    NoTestsReset,
+}
+
+impl ResetTestProgressStatus {
+    /// Clears the failures and errors of each merged test named in `query_str`, or of every merged
+    /// test if `query_str` is empty, so that the tests can be run again from a clean slate.
+    pub async fn from_resetting_progress(
+        query_str: &str,
+        database: &RoswaalDatabase,
+    ) -> Result<Self> {
+        let test_names = RoswaalTestNamesString::new(query_str);
+        let mut transaction = database.transaction().await?;
+        let reset_test_names = with_transaction!(transaction, async {
+            transaction.reset_test_progress(&test_names).await
+        })?;
+        if reset_test_names.is_empty() {
+            Ok(Self::NoTestsReset)
+        } else {
+            Ok(Self::Success { reset_test_names })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName, language::test::RoswaalCompiledTest,
+        tests_data::ordinal::RoswaalTestCommandOrdinal,
+        tests_data::progress::RoswaalTestProgressUpload,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_no_tests_reset_when_no_merged_tests_match() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = ResetTestProgressStatus::from_resetting_progress("", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, ResetTestProgressStatus::NoTestsReset)
+    }
+
+    #[tokio::test]
+    async fn resets_the_named_merged_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = vec![
+            RoswaalCompiledTest::new("Blob".to_string(), None, vec![]),
+            RoswaalCompiledTest::new("Other".to_string(), None, vec![]),
+        ];
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let progress = vec![RoswaalTestProgressUpload::new(
+            "Blob".to_string(),
+            Some(RoswaalTestCommandOrdinal::new(0)),
+            None,
+        )];
+        transaction.save_test_progess(&progress).await.unwrap();
+        transaction.commit().await.unwrap();
+        let status = ResetTestProgressStatus::from_resetting_progress("blob\nMissing", &database)
+            .await
+            .unwrap();
+        let expected_status = ResetTestProgressStatus::Success {
+            reset_test_names: vec!["Blob".to_string()],
+        };
+        assert_eq!(status, expected_status)
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+
+use crate::{
+    git::repo::{
+        RoswaalGitRepository, RoswaalGitRepositoryClient, RoswaalGitRepositoryTransaction,
+    },
+    utils::db::{RoswaalDatabase, RoswaalDatabaseTransaction},
+};
+
+/// A type for acquiring the database and git locks of an operation in a canonical order.
+///
+/// When an operation needs to hold both locks at the same time, the git lock is always acquired
+/// before the database lock. If 2 concurrent operations acquired them in opposite orders, each
+/// could end up waiting on the lock that the other holds forever.
+///
+/// Sqlite transactions borrow the coordinator mutably, so the compiler rejects any attempt to
+/// lock the git repository while a database transaction from the same coordinator is in progress.
+pub struct ResourceCoordinator<'a, Client: RoswaalGitRepositoryClient> {
+    database: &'a RoswaalDatabase,
+    git_repository: &'a RoswaalGitRepository<Client>,
+}
+
+impl<'a, Client: RoswaalGitRepositoryClient> ResourceCoordinator<'a, Client> {
+    pub fn new(
+        database: &'a RoswaalDatabase,
+        git_repository: &'a RoswaalGitRepository<Client>,
+    ) -> Self {
+        Self {
+            database,
+            git_repository,
+        }
+    }
+}
+
+impl<'a, Client: RoswaalGitRepositoryClient> ResourceCoordinator<'a, Client> {
+    /// Starts a database transaction.
+    ///
+    /// A git transaction that was already acquired may still be held while this transaction is in
+    /// progress.
+    pub async fn database(&mut self) -> Result<RoswaalDatabaseTransaction<'_>> {
+        self.database.transaction().await
+    }
+
+    /// Starts a git transaction.
+    pub async fn git(&self) -> RoswaalGitRepositoryTransaction<Client> {
+        self.git_repository.transaction().await
+    }
+
+    /// Starts a git transaction, and then a database transaction.
+    ///
+    /// Use this when data read from the database must not change before the git repository is
+    /// edited.
+    pub async fn git_and_database(
+        &mut self,
+    ) -> Result<(
+        RoswaalGitRepositoryTransaction<Client>,
+        RoswaalDatabaseTransaction<'_>,
+    )> {
+        let git_transaction = self.git().await;
+        let transaction = self.database().await?;
+        Ok((git_transaction, transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use crate::{
+        git::{repo::RoswaalGitRepository, test_support::with_clean_test_repo_access},
+        utils::db::RoswaalDatabase,
+        with_transaction,
+    };
+
+    use super::ResourceCoordinator;
+
+    #[tokio::test]
+    async fn concurrent_operations_holding_both_locks_do_not_deadlock() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let repo = RoswaalGitRepository::noop().await?;
+            let operation = || async {
+                let mut resources = ResourceCoordinator::new(&database, &repo);
+                let (git_transaction, mut transaction) = resources.git_and_database().await?;
+                let names = with_transaction!(transaction, async {
+                    transaction.merged_test_names().await
+                })?;
+                tokio::task::yield_now().await;
+                drop(git_transaction);
+                let mut transaction = resources.database().await?;
+                with_transaction!(transaction, async { transaction.merged_test_names().await })?;
+                anyhow::Ok(names)
+            };
+            let operations = async { tokio::try_join!(operation(), operation(), operation()) };
+            let results = timeout(Duration::from_secs(5), operations).await?;
+            assert!(results.is_ok());
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+}
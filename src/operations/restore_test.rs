@@ -0,0 +1,188 @@
+use anyhow::Result;
+
+use crate::{
+    git::{
+        branch_name::RoswaalOwnedGitBranchName,
+        pull_request::GithubPullRequestOpen,
+        repo::{RoswaalGitRepository, RoswaalGitRepositoryClient},
+    },
+    tests_data::archive::RoswaalArchivedTest,
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+use super::add_tests::AddTestsStatus;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RestoreTestStatus<'r> {
+    /// The archived test was given to the same flow as `/add-tests`.
+    Restored(AddTestsStatus<'r>),
+    NoTestName,
+    NotArchived {
+        name: String,
+    },
+}
+
+impl<'r> RestoreTestStatus<'r> {
+    /// Returns the branch that the archived test was restored on, if any.
+    pub fn branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
+        match self {
+            Self::Restored(status) => status.branch_name(),
+            _ => None,
+        }
+    }
+
+    /// Returns the status of adding the archived test back, if it was archived.
+    pub fn add_tests_status(&self) -> Option<&AddTestsStatus<'r>> {
+        match self {
+            Self::Restored(status) => Some(status),
+            _ => None,
+        }
+    }
+}
+
+impl<'r> RestoreTestStatus<'r> {
+    /// Returns the most recently archived version of the removed test named in `name_str`, if any.
+    pub async fn archived_test(
+        name_str: &str,
+        database: &RoswaalDatabase,
+    ) -> Result<Option<RoswaalArchivedTest>> {
+        let name = name_str.trim();
+        if name.is_empty() {
+            return Ok(None);
+        }
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async { transaction.archived_test(name).await })
+    }
+
+    /// Adds `archived_test` back through the same flow as `/add-tests`, which compiles its
+    /// syntax again against the current locations, and opens a PR that adds its code.
+    ///
+    /// The restored test is attributed to the slack user that originally added it, falling back
+    /// to `restored_by_slack_user_id`.
+    pub async fn from_restoring_test<Client: RoswaalGitRepositoryClient>(
+        name_str: &str,
+        archived_test: Option<&'r RoswaalArchivedTest>,
+        database: &RoswaalDatabase,
+        pr_open: &impl GithubPullRequestOpen,
+        git_repository: &RoswaalGitRepository<Client>,
+        target_repositories: &[RoswaalGitRepository<Client>],
+        restored_by_slack_user_id: Option<&str>,
+    ) -> Result<Self> {
+        let name = name_str.trim();
+        if name.is_empty() {
+            return Ok(Self::NoTestName);
+        }
+        let Some(archived_test) = archived_test else {
+            return Ok(Self::NotArchived {
+                name: name.to_string(),
+            });
+        };
+        let added_by_slack_user_id = archived_test
+            .added_by_slack_user_id()
+            .or(restored_by_slack_user_id);
+        let status = AddTestsStatus::from_adding_tests(
+            archived_test.tests_str(),
+            database,
+            pr_open,
+            git_repository,
+            target_repositories,
+            added_by_slack_user_id,
+        )
+        .await?;
+        Ok(Self::Restored(status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::test_support::{with_clean_test_repo_access, TestGithubPullRequestOpen},
+        language::compiler::{RoswaalCompile, RoswaalCompileContext},
+        language::test::RoswaalCompiledTest,
+        tests_data::query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+    };
+
+    use super::*;
+
+    const TEST_SYNTAX: &str = "\
+New Test: Join an event
+Step 1: Join the event
+Requirement 1: Tap the join button";
+
+    #[tokio::test]
+    async fn reports_not_archived_for_tests_that_were_never_removed() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let archived_test = RestoreTestStatus::archived_test("Join an event", &database)
+            .await
+            .unwrap();
+        let status = RestoreTestStatus::from_restoring_test(
+            "Join an event",
+            archived_test.as_ref(),
+            &database,
+            &TestGithubPullRequestOpen::new(false),
+            &RoswaalGitRepository::noop().await.unwrap(),
+            &[],
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            status,
+            RestoreTestStatus::NotArchived {
+                name: "Join an event".to_string()
+            }
+        )
+    }
+
+    #[tokio::test]
+    async fn restores_removed_test_through_the_add_tests_flow() {
+        with_clean_test_repo_access(async {
+            let database = RoswaalDatabase::in_memory().await?;
+            let add_branch = RoswaalOwnedGitBranchName::new("add");
+            let remove_branch = RoswaalOwnedGitBranchName::new("remove");
+            let mut transaction = database.transaction().await?;
+            let test =
+                RoswaalCompiledTest::compile(TEST_SYNTAX, RoswaalCompileContext::new(&vec![]))
+                    .unwrap();
+            transaction
+                .save_tests_added_by(&vec![test], &add_branch, Some("U123"))
+                .await?;
+            transaction.merge_unmerged_tests(&add_branch).await?;
+            transaction
+                .stage_test_removals(
+                    &RoswaalTestNamesString::new("Join an event"),
+                    &remove_branch,
+                )
+                .await?;
+            transaction.merge_test_removals(&remove_branch).await?;
+            transaction.commit().await?;
+
+            let archived_test =
+                RestoreTestStatus::archived_test("join an event", &database).await?;
+            let status = RestoreTestStatus::from_restoring_test(
+                "join an event",
+                archived_test.as_ref(),
+                &database,
+                &TestGithubPullRequestOpen::new(false),
+                &RoswaalGitRepository::noop().await?,
+                &[],
+                Some("U456"),
+            )
+            .await?;
+            let Some(AddTestsStatus::Success { results, .. }) = status.add_tests_status() else {
+                panic!("The archived test was not restored.")
+            };
+            assert_eq!(results.tests()[0].name(), "Join an event");
+            let mut transaction = database.transaction().await?;
+            let tests = transaction
+                .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                .await?;
+            assert_eq!(tests.len(), 1);
+            assert_eq!(tests[0].added_by_slack_user_id(), Some(&"U123".to_string()));
+            Ok(())
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -1,15 +1,195 @@
 use anyhow::Result;
 
 use crate::{
-    tests_data::progress::RoswaalTestProgressUpload, utils::sqlite::RoswaalSqlite, with_transaction,
+    slack::{
+        message::{SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL},
+        test_failure_view::TestFailureView,
+    },
+    tests_data::{
+        progress::{RoswaalTestProgressUpload, RoswaalTestProgressUploadResult},
+        query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+        test::RoswaalTest,
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
 };
 
+/// Saves `progress`, and returns the outcome of each entry in the same order.
 pub async fn save_test_progress(
     progress: &Vec<RoswaalTestProgressUpload>,
-    sqlite: &RoswaalSqlite,
-) -> Result<()> {
-    let mut transaction = sqlite.transaction().await?;
+    database: &RoswaalDatabase,
+) -> Result<Vec<RoswaalTestProgressUploadResult>> {
+    let mut transaction = database.transaction().await?;
     with_transaction!(transaction, async {
         transaction.save_test_progess(progress).await
     })
 }
+
+/// Posts a summary of the failing step and error message of each test that failed in `progress`
+/// to the specified slack channel.
+///
+/// Tests with muted failures are left out of the summary, and nothing is posted if every failing
+/// test is muted. This must be called after the progress has been saved.
+pub async fn notify_test_failures(
+    progress: &[RoswaalTestProgressUpload],
+    channel_id: &str,
+    database: &RoswaalDatabase,
+    messenger: &impl SlackSendMessage,
+) -> Result<()> {
+    let failing_tests = unmuted_failing_tests(progress, database).await?;
+    if failing_tests.is_empty() {
+        return Ok(());
+    }
+    let message = SlackMessage::new(
+        channel_id,
+        &TestFailureView::new(failing_tests),
+        SLACK_POST_MESSAGE_URL,
+    );
+    messenger.send(&message).await?;
+    Ok(())
+}
+
+async fn unmuted_failing_tests(
+    progress: &[RoswaalTestProgressUpload],
+    database: &RoswaalDatabase,
+) -> Result<Vec<RoswaalTest>> {
+    let mut transaction = database.read_transaction().await?;
+    let muted_names = with_transaction!(transaction, async {
+        transaction.muted_test_failure_names().await
+    })?;
+    let failing_names = progress
+        .iter()
+        .filter(|p| p.command_failure_ordinal().is_some())
+        .map(|p| p.test_name())
+        .filter(|name| !muted_names.contains(&name.to_lowercase()))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    if failing_names.is_empty() {
+        return Ok(vec![]);
+    }
+    let query = RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new(&failing_names));
+    let mut transaction = database.read_transaction().await?;
+    let tests = with_transaction!(transaction, async {
+        transaction.tests_in_alphabetical_order(&query).await
+    })?;
+    Ok(tests
+        .into_iter()
+        .filter(|t| t.unmerged_branch_name().is_none() && t.command_failure_ordinal().is_some())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        tests_data::{
+            ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUploadErrorDescription,
+        },
+    };
+
+    use super::*;
+
+    struct TestSlackMessager {
+        messages: Mutex<Vec<SlackMessage>>,
+    }
+
+    impl SlackSendMessage for TestSlackMessager {
+        async fn send(&self, message: &SlackMessage) -> Result<Option<String>> {
+            self.messages.lock().await.push(message.clone());
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn posts_failing_tests_that_are_not_muted() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Blob", "Jr", "Passing"])
+            .await
+            .unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.mute_test_failures("jr").await.unwrap();
+        transaction.commit().await.unwrap();
+        let progress = vec![
+            failing_progress("Blob"),
+            failing_progress("Jr"),
+            RoswaalTestProgressUpload::new("Passing".to_string(), None, None),
+        ];
+        save_test_progress(&progress, &database).await.unwrap();
+        let messenger = TestSlackMessager {
+            messages: Mutex::new(vec![]),
+        };
+        notify_test_failures(&progress, "C123", &database, &messenger)
+            .await
+            .unwrap();
+        let mut transaction = database.read_transaction().await.unwrap();
+        let failing_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::TestNames(
+                RoswaalTestNamesString::new("Blob"),
+            ))
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let expected_message = SlackMessage::new(
+            "C123",
+            &TestFailureView::new(failing_tests),
+            SLACK_POST_MESSAGE_URL,
+        );
+        let messages = messenger.messages.lock().await;
+        assert_eq!(*messages, vec![expected_message])
+    }
+
+    #[tokio::test]
+    async fn posts_nothing_when_all_failing_tests_are_muted() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Blob"]).await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.mute_test_failures("Blob").await.unwrap();
+        transaction.commit().await.unwrap();
+        let progress = vec![failing_progress("Blob")];
+        save_test_progress(&progress, &database).await.unwrap();
+        let messenger = TestSlackMessager {
+            messages: Mutex::new(vec![]),
+        };
+        notify_test_failures(&progress, "C123", &database, &messenger)
+            .await
+            .unwrap();
+        assert!(messenger.messages.lock().await.is_empty())
+    }
+
+    fn failing_progress(test_name: &str) -> RoswaalTestProgressUpload {
+        RoswaalTestProgressUpload::new(
+            test_name.to_string(),
+            Some(RoswaalTestCommandOrdinal::new(0)),
+            Some(RoswaalTestProgressUploadErrorDescription::new(
+                "Button not found".to_string(),
+                "stack".to_string(),
+            )),
+        )
+    }
+
+    async fn save_merged_tests(database: &RoswaalDatabase, names: &[&str]) -> Result<()> {
+        let tests = names
+            .iter()
+            .map(|name| {
+                RoswaalCompiledTest::new(
+                    name.to_string(),
+                    None,
+                    vec![RoswaalCompiledTestCommand::Step {
+                        label: "Step 1".to_string(),
+                        name: "Tap the button".to_string(),
+                        requirement: "Tap the button".to_string(),
+                        retries: None,
+                    }],
+                )
+            })
+            .collect();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let mut transaction = database.transaction().await?;
+        transaction.save_tests(&tests, &branch_name).await?;
+        transaction.merge_unmerged_tests(&branch_name).await?;
+        transaction.commit().await
+    }
+}
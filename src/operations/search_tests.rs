@@ -2,7 +2,7 @@ use anyhow::Result;
 
 use crate::{
     tests_data::{query::RoswaalSearchTestsQuery, test::RoswaalTest},
-    utils::sqlite::RoswaalSqlite,
+    utils::db::RoswaalDatabase,
     with_transaction,
 };
 
@@ -13,8 +13,8 @@ pub enum SearchTestsStatus {
 }
 
 impl SearchTestsStatus {
-    pub async fn from_searching_tests(query_str: &str, sqlite: &RoswaalSqlite) -> Result<Self> {
-        let mut transaction = sqlite.transaction().await?;
+    pub async fn from_searching_tests(query_str: &str, database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
         with_transaction!(transaction, async {
             let query = RoswaalSearchTestsQuery::new(query_str);
             let tests = transaction.tests_in_alphabetical_order(&query).await?;
@@ -40,14 +40,15 @@ mod tests {
             merge_branch::MergeBranchStatus, remove_tests::RemoveTestsStatus,
             save_progress::save_test_progress,
         },
+        runner::webhook::test_support::NO_TEST_RUNNER,
         tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
-        utils::sqlite::RoswaalSqlite,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
     async fn reports_no_tests_when_no_tests_saved() {
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let status = SearchTestsStatus::from_searching_tests("", &sqlite)
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = SearchTestsStatus::from_searching_tests("", &database)
             .await
             .unwrap();
         assert_eq!(status, SearchTestsStatus::NoTests)
@@ -56,7 +57,7 @@ mod tests {
     #[tokio::test]
     async fn reports_success_with_saved_tests() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let tests_str = "\
 ```
 New Test: ABC
@@ -71,12 +72,14 @@ Requirement 1: Do the thing
 ";
             _ = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &TestGithubPullRequestOpen::new(false),
                 &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
             )
             .await?;
-            let status = SearchTestsStatus::from_searching_tests("", &sqlite).await?;
+            let status = SearchTestsStatus::from_searching_tests("", &database).await?;
             match status {
                 SearchTestsStatus::Success(tests) => {
                     assert_eq!(tests[0].name(), "ABC");
@@ -93,7 +96,7 @@ Requirement 1: Do the thing
     #[tokio::test]
     async fn reports_success_with_specific_test_names_in_query_str() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await.unwrap();
+            let database = RoswaalDatabase::in_memory().await.unwrap();
             let tests_str = "\
 ```
 New Test: Bob
@@ -108,13 +111,15 @@ Requirement 1: Do the thing
 ";
             _ = AddTestsStatus::from_adding_tests(
                 tests_str,
-                &sqlite,
+                &database,
                 &TestGithubPullRequestOpen::new(false),
                 &RoswaalGitRepository::noop().await?,
+                &[],
+                None,
             )
             .await?;
             let query_str = "bob";
-            let status = SearchTestsStatus::from_searching_tests(query_str, &sqlite)
+            let status = SearchTestsStatus::from_searching_tests(query_str, &database)
                 .await
                 .unwrap();
             match status {
@@ -135,7 +140,7 @@ Requirement 1: Do the thing
     #[tokio::test]
     async fn does_not_report_added_and_merged_test_after_removal() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop().await?;
             let tests_str = "\
@@ -145,14 +150,26 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            _ = AddTestsStatus::from_adding_tests(tests_str, &sqlite, &pr_open, &repo).await?;
+            _ = AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
             let mut branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            _ = MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
-            _ = RemoveTestsStatus::from_removing_tests("bob", &sqlite, &repo, &pr_open).await?;
+            _ = MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
+            _ = RemoveTestsStatus::from_removing_tests("bob", &database, &repo, &pr_open, &[])
+                .await?;
             branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            _ = MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
+            _ = MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
             let query_str = "bob";
-            let status = SearchTestsStatus::from_searching_tests(query_str, &sqlite)
+            let status = SearchTestsStatus::from_searching_tests(query_str, &database)
                 .await
                 .unwrap();
             assert_eq!(status, SearchTestsStatus::NoTests);
@@ -165,7 +182,7 @@ Requirement 1: Do the thing
     #[tokio::test]
     async fn reports_test_is_present_when_removal_branch_merged_after_being_closed() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop().await?;
             let tests_str = "\
@@ -175,15 +192,26 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            AddTestsStatus::from_adding_tests(tests_str, &sqlite, &pr_open, &repo).await?;
+            AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
             let mut branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
-            RemoveTestsStatus::from_removing_tests("bob", &sqlite, &repo, &pr_open).await?;
+            MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
+            RemoveTestsStatus::from_removing_tests("bob", &database, &repo, &pr_open, &[]).await?;
             branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            CloseBranchStatus::from_closing_branch(&branch_name, &sqlite).await?;
-            MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
+            CloseBranchStatus::from_closing_branch(&branch_name, &database).await?;
+            MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
             let query_str = "bob";
-            let status = SearchTestsStatus::from_searching_tests(query_str, &sqlite)
+            let status = SearchTestsStatus::from_searching_tests(query_str, &database)
                 .await
                 .unwrap();
             match status {
@@ -201,7 +229,7 @@ Requirement 1: Do the thing
     #[tokio::test]
     async fn reports_no_tests_when_no_merged_tests_and_all_unmerged_tests_closed() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop().await?;
             let tests_str = "\
@@ -211,11 +239,12 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            AddTestsStatus::from_adding_tests(tests_str, &sqlite, &pr_open, &repo).await?;
+            AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
             let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            CloseBranchStatus::from_closing_branch(&branch_name, &sqlite).await?;
+            CloseBranchStatus::from_closing_branch(&branch_name, &database).await?;
             let query_str = "bob";
-            let status = SearchTestsStatus::from_searching_tests(query_str, &sqlite)
+            let status = SearchTestsStatus::from_searching_tests(query_str, &database)
                 .await
                 .unwrap();
             assert_eq!(status, SearchTestsStatus::NoTests);
@@ -228,7 +257,7 @@ Requirement 1: Do the thing
     #[tokio::test]
     async fn reports_tests_with_updated_progress() {
         with_clean_test_repo_access(async {
-            let sqlite = RoswaalSqlite::in_memory().await?;
+            let database = RoswaalDatabase::in_memory().await?;
             let pr_open = TestGithubPullRequestOpen::new(false);
             let repo = RoswaalGitRepository::noop().await?;
             let tests_str = "\
@@ -238,17 +267,23 @@ Step 1: Do the thing
 Requirement 1: Do the thing
 ```
 ";
-            AddTestsStatus::from_adding_tests(tests_str, &sqlite, &pr_open, &repo).await?;
+            AddTestsStatus::from_adding_tests(tests_str, &database, &pr_open, &repo, &[], None)
+                .await?;
             let branch_name = pr_open.most_recent_head_branch_name().await.unwrap();
-            MergeBranchStatus::from_merging_branch_with_name(&branch_name, &sqlite).await?;
+            MergeBranchStatus::from_merging_branch_with_name(
+                &branch_name,
+                &database,
+                NO_TEST_RUNNER,
+            )
+            .await?;
             let progress = vec![RoswaalTestProgressUpload::new(
                 "Bob".to_string(),
                 Some(RoswaalTestCommandOrdinal::new(0)),
                 None,
             )];
-            save_test_progress(&progress, &sqlite).await?;
+            save_test_progress(&progress, &database).await?;
             let query_str = "bob";
-            let status = SearchTestsStatus::from_searching_tests(query_str, &sqlite)
+            let status = SearchTestsStatus::from_searching_tests(query_str, &database)
                 .await
                 .unwrap();
             match status {
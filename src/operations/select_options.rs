@@ -0,0 +1,97 @@
+use anyhow::Result;
+
+use crate::{
+    location::storage::LoadLocationsFilter, slack::select_menu::RoswaalSlackSelectMenu,
+    utils::db::RoswaalDatabase, with_transaction,
+};
+
+/// Returns the merged names offered by `menu` that contain `query`, ignoring casing, in
+/// alphabetical order.
+pub async fn load_select_option_names(
+    menu: RoswaalSlackSelectMenu,
+    query: &str,
+    database: &RoswaalDatabase,
+) -> Result<Vec<String>> {
+    let mut transaction = database.read_transaction().await?;
+    let mut names = with_transaction!(transaction, async {
+        match menu {
+            RoswaalSlackSelectMenu::TestNames => transaction.merged_test_names().await,
+            RoswaalSlackSelectMenu::LocationNames => transaction
+                .location_names_in_alphabetical_order(LoadLocationsFilter::MergedOnly)
+                .await
+                .map(|names| names.iter().map(|n| n.raw_name().to_string()).collect()),
+        }
+    })?;
+    let query = query.trim().to_lowercase();
+    names.retain(|name| name.to_lowercase().contains(&query));
+    names.sort_by_key(|name| name.to_lowercase());
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName, language::test::RoswaalCompiledTest,
+        location::location::RoswaalLocation,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_merged_test_names_containing_the_query() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = ["Leave an event", "Join an event", "Sign in"]
+            .iter()
+            .map(|name| RoswaalCompiledTest::new(name.to_string(), None, vec![]))
+            .collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction
+            .save_tests(
+                &vec![RoswaalCompiledTest::new(
+                    "Edit an event".to_string(),
+                    None,
+                    vec![],
+                )],
+                &RoswaalOwnedGitBranchName::new("unmerged"),
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let names =
+            load_select_option_names(RoswaalSlackSelectMenu::TestNames, " EVENT", &database)
+                .await
+                .unwrap();
+        assert_eq!(names, vec!["Join an event", "Leave an event"])
+    }
+
+    #[tokio::test]
+    async fn loads_merged_location_names_containing_the_query() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let locations = ["San Francisco", "Oakland", "San Jose"]
+            .iter()
+            .map(|name| RoswaalLocation::new_without_validation(name, 10.0, 10.0))
+            .collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_locations(&locations, &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_locations(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let names =
+            load_select_option_names(RoswaalSlackSelectMenu::LocationNames, "san", &database)
+                .await
+                .unwrap();
+        assert_eq!(names, vec!["San Francisco", "San Jose"])
+    }
+}
@@ -0,0 +1,197 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    tests_data::{
+        query::RoswaalSearchTestsQuery,
+        test::{RoswaalTest, RoswaalTestProgressStatus},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// The number of days since a test was last ran before it is considered idle.
+pub const IDLE_TEST_DAYS: i64 = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TestDigestStatus {
+    Success {
+        failing_tests: Vec<RoswaalTest>,
+        idle_tests: Vec<RoswaalTest>,
+    },
+    AllTestsPassing,
+    NoTests,
+}
+
+impl TestDigestStatus {
+    /// Collects the merged tests that are failing, and the merged tests that have not been ran
+    /// within the last `IDLE_TEST_DAYS` days relative to `now`.
+    pub async fn from_digesting_tests(
+        database: &RoswaalDatabase,
+        now: DateTime<Utc>,
+    ) -> Result<Self> {
+        let mut transaction = database.transaction().await?;
+        let tests = with_transaction!(transaction, async {
+            transaction
+                .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+                .await
+        })?;
+        let merged_tests = tests
+            .into_iter()
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .collect::<Vec<RoswaalTest>>();
+        if merged_tests.is_empty() {
+            return Ok(Self::NoTests);
+        }
+        let idle_date = now - Duration::days(IDLE_TEST_DAYS);
+        let (failing_tests, other_tests): (Vec<_>, Vec<_>) = merged_tests
+            .into_iter()
+            .partition(|t| t.progress_status() == RoswaalTestProgressStatus::Failed);
+        let idle_tests = other_tests
+            .into_iter()
+            .filter(|t| t.last_run_date().map(|d| d < idle_date).unwrap_or(true))
+            .collect::<Vec<RoswaalTest>>();
+        if failing_tests.is_empty() && idle_tests.is_empty() {
+            Ok(Self::AllTestsPassing)
+        } else {
+            Ok(Self::Success {
+                failing_tests,
+                idle_tests,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        operations::test_digest::{TestDigestStatus, IDLE_TEST_DAYS},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
+        utils::db::RoswaalDatabase,
+    };
+
+    #[tokio::test]
+    async fn reports_no_tests_when_no_merged_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(
+                &vec![compiled_test("Unmerged")],
+                &RoswaalOwnedGitBranchName::new("test"),
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        let status = TestDigestStatus::from_digesting_tests(&database, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(status, TestDigestStatus::NoTests)
+    }
+
+    #[tokio::test]
+    async fn reports_all_tests_passing_when_tests_recently_passed() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Passing"]).await;
+        upload_progress(&database, "Passing", None).await;
+        let status = TestDigestStatus::from_digesting_tests(&database, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(status, TestDigestStatus::AllTestsPassing)
+    }
+
+    #[tokio::test]
+    async fn reports_failing_and_idle_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Failing", "Never Ran", "Passing"]).await;
+        upload_progress(
+            &database,
+            "Failing",
+            Some(RoswaalTestCommandOrdinal::new(0)),
+        )
+        .await;
+        upload_progress(&database, "Passing", None).await;
+        let status = TestDigestStatus::from_digesting_tests(&database, Utc::now())
+            .await
+            .unwrap();
+        let (failing_tests, idle_tests) = match status {
+            TestDigestStatus::Success {
+                failing_tests,
+                idle_tests,
+            } => (failing_tests, idle_tests),
+            _ => panic!("Expected a successful digest."),
+        };
+        assert_eq!(
+            failing_tests
+                .iter()
+                .map(|t| t.name())
+                .collect::<Vec<&str>>(),
+            vec!["Failing"]
+        );
+        assert_eq!(
+            idle_tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["Never Ran"]
+        )
+    }
+
+    #[tokio::test]
+    async fn reports_passing_tests_that_have_not_ran_recently_as_idle() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &["Passing"]).await;
+        upload_progress(&database, "Passing", None).await;
+        let now = Utc::now() + Duration::days(IDLE_TEST_DAYS + 1);
+        let status = TestDigestStatus::from_digesting_tests(&database, now)
+            .await
+            .unwrap();
+        let idle_tests = match status {
+            TestDigestStatus::Success {
+                failing_tests,
+                idle_tests,
+            } if failing_tests.is_empty() => idle_tests,
+            _ => panic!("Expected a successful digest with no failing tests."),
+        };
+        assert_eq!(
+            idle_tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["Passing"]
+        )
+    }
+
+    async fn save_merged_tests(database: &RoswaalDatabase, names: &[&str]) {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = names.iter().map(|name| compiled_test(name)).collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+    }
+
+    async fn upload_progress(
+        database: &RoswaalDatabase,
+        name: &str,
+        failure_ordinal: Option<RoswaalTestCommandOrdinal>,
+    ) {
+        let upload = RoswaalTestProgressUpload::new(name.to_string(), failure_ordinal, None);
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_test_progess(&vec![upload]).await.unwrap();
+        transaction.commit().await.unwrap();
+    }
+
+    fn compiled_test(name: &str) -> RoswaalCompiledTest {
+        RoswaalCompiledTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+    }
+}
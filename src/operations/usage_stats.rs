@@ -0,0 +1,8 @@
+use anyhow::Result;
+
+use crate::{stats::usage::RoswaalUsageStats, utils::db::RoswaalDatabase, with_transaction};
+
+pub async fn load_usage_stats(database: &RoswaalDatabase) -> Result<RoswaalUsageStats> {
+    let mut transaction = database.transaction().await?;
+    with_transaction!(transaction, async { transaction.usage_stats().await })
+}
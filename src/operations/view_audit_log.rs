@@ -0,0 +1,70 @@
+use anyhow::Result;
+
+use crate::{
+    audit::storage::RoswaalStoredAuditLogEntry, utils::db::RoswaalDatabase, with_transaction,
+};
+
+/// The maximum number of entries shown by `/audit`.
+const MAX_AUDIT_LOG_ENTRIES: u32 = 20;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ViewAuditLogStatus {
+    Success(Vec<RoswaalStoredAuditLogEntry>),
+    NoEntries,
+}
+
+impl ViewAuditLogStatus {
+    /// Loads the most recently recorded audit log entries, newest first.
+    pub async fn from_recent_entries(database: &RoswaalDatabase) -> Result<Self> {
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async {
+            transaction
+                .recent_audit_log_entries(MAX_AUDIT_LOG_ENTRIES)
+                .await
+                .map(|entries| {
+                    if entries.is_empty() {
+                        Self::NoEntries
+                    } else {
+                        Self::Success(entries)
+                    }
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::{entry::RoswaalAuditLogEntry, recorder::RoswaalRecordAuditLog};
+
+    #[tokio::test]
+    async fn reports_no_entries_when_nothing_was_recorded() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let status = ViewAuditLogStatus::from_recent_entries(&database)
+            .await
+            .unwrap();
+        assert_eq!(status, ViewAuditLogStatus::NoEntries)
+    }
+
+    #[tokio::test]
+    async fn only_loads_the_most_recent_entries() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        for i in 0..(MAX_AUDIT_LOG_ENTRIES + 5) {
+            database
+                .record(RoswaalAuditLogEntry::new(
+                    "U1",
+                    "/view-tests",
+                    &i.to_string(),
+                ))
+                .await;
+        }
+        let status = ViewAuditLogStatus::from_recent_entries(&database)
+            .await
+            .unwrap();
+        let ViewAuditLogStatus::Success(entries) = status else {
+            panic!()
+        };
+        assert_eq!(entries.len(), MAX_AUDIT_LOG_ENTRIES as usize);
+        assert_eq!(entries[0].entry().arguments(), "24")
+    }
+}
@@ -0,0 +1,257 @@
+use anyhow::Result;
+
+use crate::{
+    tests_data::{
+        query::RoswaalSearchTestsQuery,
+        test::{RoswaalTest, RoswaalTestProgressStatus},
+    },
+    utils::db::RoswaalDatabase,
+    with_transaction,
+};
+
+/// The number of merged tests in a suite for each progress status.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalTestSuiteSummary {
+    name: String,
+    passed_count: usize,
+    failed_count: usize,
+    idle_count: usize,
+}
+
+impl RoswaalTestSuiteSummary {
+    pub fn new(name: String, tests: &[RoswaalTest]) -> Self {
+        let count = |status: RoswaalTestProgressStatus| {
+            tests
+                .iter()
+                .filter(|t| t.progress_status() == status)
+                .count()
+        };
+        Self {
+            name,
+            passed_count: count(RoswaalTestProgressStatus::Passed),
+            failed_count: count(RoswaalTestProgressStatus::Failed),
+            idle_count: count(RoswaalTestProgressStatus::Idle),
+        }
+    }
+}
+
+impl RoswaalTestSuiteSummary {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn passed_count(&self) -> usize {
+        self.passed_count
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.failed_count
+    }
+
+    pub fn idle_count(&self) -> usize {
+        self.idle_count
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ViewSuitesStatus {
+    Summaries(Vec<RoswaalTestSuiteSummary>),
+    Suite {
+        name: String,
+        tests: Vec<RoswaalTest>,
+    },
+    SuiteNotFound(String),
+    NoSuites,
+}
+
+impl ViewSuitesStatus {
+    /// Summarizes the progress of the merged tests in every suite when `suite_str` is empty,
+    /// otherwise lists the merged tests in the suite named by `suite_str`.
+    pub async fn from_viewing_suites(suite_str: &str, database: &RoswaalDatabase) -> Result<Self> {
+        let suite_name = suite_str.trim();
+        let mut transaction = database.read_transaction().await?;
+        with_transaction!(transaction, async {
+            if !suite_name.is_empty() {
+                let tests = transaction
+                    .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::Suite(suite_name))
+                    .await?;
+                let tests = merged_tests(tests);
+                if tests.is_empty() {
+                    return Ok(Self::SuiteNotFound(suite_name.to_string()));
+                }
+                return Ok(Self::Suite {
+                    name: suite_name.to_string(),
+                    tests,
+                });
+            }
+            let mut summaries = Vec::new();
+            for name in transaction.merged_suite_names().await? {
+                let tests = transaction
+                    .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::Suite(&name))
+                    .await?;
+                summaries.push(RoswaalTestSuiteSummary::new(name, &merged_tests(tests)));
+            }
+            if summaries.is_empty() {
+                Ok(Self::NoSuites)
+            } else {
+                Ok(Self::Summaries(summaries))
+            }
+        })
+    }
+}
+
+fn merged_tests(tests: Vec<RoswaalTest>) -> Vec<RoswaalTest> {
+    tests
+        .into_iter()
+        .filter(|t| t.unmerged_branch_name().is_none())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_no_suites_when_no_merged_tests_have_suites() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .save_tests(
+                &vec![compiled_test("Unmerged", Some("Events"))],
+                &RoswaalOwnedGitBranchName::new("test"),
+            )
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        save_merged_tests(&database, &[("No Suite", None)]).await;
+        let status = ViewSuitesStatus::from_viewing_suites("", &database)
+            .await
+            .unwrap();
+        assert_eq!(status, ViewSuitesStatus::NoSuites)
+    }
+
+    #[tokio::test]
+    async fn summarizes_progress_of_each_suite() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(
+            &database,
+            &[
+                ("Create Event", Some("Events")),
+                ("Join Event", Some("Events")),
+                ("Leave Event", Some("Events")),
+                ("Sign Up", Some("Onboarding")),
+                ("No Suite", None),
+            ],
+        )
+        .await;
+        upload_progress(&database, "Create Event", None).await;
+        upload_progress(
+            &database,
+            "Join Event",
+            Some(RoswaalTestCommandOrdinal::new(0)),
+        )
+        .await;
+        let status = ViewSuitesStatus::from_viewing_suites("", &database)
+            .await
+            .unwrap();
+        let expected_summaries = vec![
+            RoswaalTestSuiteSummary {
+                name: "Events".to_string(),
+                passed_count: 1,
+                failed_count: 1,
+                idle_count: 1,
+            },
+            RoswaalTestSuiteSummary {
+                name: "Onboarding".to_string(),
+                passed_count: 0,
+                failed_count: 0,
+                idle_count: 1,
+            },
+        ];
+        assert_eq!(status, ViewSuitesStatus::Summaries(expected_summaries))
+    }
+
+    #[tokio::test]
+    async fn lists_merged_tests_in_suite() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(
+            &database,
+            &[
+                ("Join Event", Some("Events")),
+                ("Create Event", Some("Events")),
+                ("Sign Up", Some("Onboarding")),
+            ],
+        )
+        .await;
+        let status = ViewSuitesStatus::from_viewing_suites(" events ", &database)
+            .await
+            .unwrap();
+        let ViewSuitesStatus::Suite { name, tests } = status else {
+            panic!("Expected the tests in the suite.")
+        };
+        assert_eq!(name, "events");
+        assert_eq!(
+            tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["Create Event", "Join Event"]
+        )
+    }
+
+    #[tokio::test]
+    async fn reports_suite_not_found_for_unknown_suite() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        save_merged_tests(&database, &[("Sign Up", Some("Onboarding"))]).await;
+        let status = ViewSuitesStatus::from_viewing_suites("Events", &database)
+            .await
+            .unwrap();
+        assert_eq!(
+            status,
+            ViewSuitesStatus::SuiteNotFound("Events".to_string())
+        )
+    }
+
+    async fn save_merged_tests(database: &RoswaalDatabase, tests: &[(&str, Option<&str>)]) {
+        let branch_name = RoswaalOwnedGitBranchName::new("merged");
+        let tests = tests
+            .iter()
+            .map(|(name, suite)| compiled_test(name, *suite))
+            .collect();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+    }
+
+    async fn upload_progress(
+        database: &RoswaalDatabase,
+        name: &str,
+        failure_ordinal: Option<RoswaalTestCommandOrdinal>,
+    ) {
+        let upload = RoswaalTestProgressUpload::new(name.to_string(), failure_ordinal, None);
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.save_test_progess(&vec![upload]).await.unwrap();
+        transaction.commit().await.unwrap();
+    }
+
+    fn compiled_test(name: &str, suite: Option<&str>) -> RoswaalCompiledTest {
+        RoswaalCompiledTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "A".to_string(),
+                requirement: "B".to_string(),
+                retries: None,
+            }],
+        )
+        .with_suite(suite.map(|s| s.to_string()))
+    }
+}
@@ -0,0 +1,3 @@
+pub mod run;
+pub mod storage;
+pub mod webhook;
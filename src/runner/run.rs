@@ -0,0 +1,49 @@
+use serde::Serialize;
+
+use crate::git::branch_name::RoswaalOwnedGitBranchName;
+
+/// A CI run of the tests that were added by a merged branch.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct RoswaalTestRun {
+    #[serde(rename = "run_id")]
+    id: i64,
+    branch_name: RoswaalOwnedGitBranchName,
+    test_names: Vec<String>,
+    #[serde(skip)]
+    url: Option<String>,
+}
+
+impl RoswaalTestRun {
+    pub fn new(
+        id: i64,
+        branch_name: RoswaalOwnedGitBranchName,
+        test_names: Vec<String>,
+        url: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            branch_name,
+            test_names,
+            url,
+        }
+    }
+}
+
+impl RoswaalTestRun {
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn branch_name(&self) -> &RoswaalOwnedGitBranchName {
+        &self.branch_name
+    }
+
+    pub fn test_names(&self) -> &[String] {
+        &self.test_names
+    }
+
+    /// Returns the link to the run, which is only known once the runner has called back.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use sqlx::FromRow;
+
+use crate::{
+    git::branch_name::RoswaalOwnedGitBranchName,
+    utils::db::{
+        query::{query, query_as, query_scalar},
+        RoswaalDatabaseTransaction,
+    },
+};
+
+use super::run::RoswaalTestRun;
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Saves a new run of the specified tests that were added by `branch_name`.
+    pub async fn save_test_run(
+        &mut self,
+        branch_name: &RoswaalOwnedGitBranchName,
+        test_names: &[String],
+    ) -> Result<RoswaalTestRun> {
+        let id = query_scalar::<i64>(statements::INSERT_TEST_RUN)
+            .bind(branch_name)
+            .bind(test_names.join("\n"))
+            .fetch_one(self.connection())
+            .await?;
+        Ok(RoswaalTestRun::new(
+            id,
+            branch_name.clone(),
+            test_names.to_vec(),
+            None,
+        ))
+    }
+
+    /// Records the link to the run with `id`, and returns the run if it exists.
+    pub async fn save_test_run_url(
+        &mut self,
+        id: i64,
+        url: &str,
+    ) -> Result<Option<RoswaalTestRun>> {
+        query(statements::UPDATE_TEST_RUN_URL)
+            .bind(url)
+            .bind(id)
+            .execute(self.connection())
+            .await?;
+        self.test_run(id).await
+    }
+
+    pub async fn test_run(&mut self, id: i64) -> Result<Option<RoswaalTestRun>> {
+        let run = query_as::<SqliteTestRun>(statements::SELECT_TEST_RUN)
+            .bind(id)
+            .fetch_optional(self.connection())
+            .await?
+            .map(|row| {
+                let test_names = row.test_names.lines().map(str::to_string).collect();
+                RoswaalTestRun::new(row.id, row.branch_name, test_names, row.url)
+            });
+        Ok(run)
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteTestRun {
+    id: i64,
+    branch_name: RoswaalOwnedGitBranchName,
+    test_names: String,
+    url: Option<String>,
+}
+
+mod statements {
+    pub const INSERT_TEST_RUN: &str =
+        "INSERT INTO TestRuns (branch_name, test_names) VALUES (?, ?) RETURNING id;";
+
+    pub const UPDATE_TEST_RUN_URL: &str = "UPDATE TestRuns SET url = ? WHERE id = ?;";
+
+    pub const SELECT_TEST_RUN: &str =
+        "SELECT id, branch_name, test_names, url FROM TestRuns WHERE id = ?;";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{git::branch_name::RoswaalOwnedGitBranchName, utils::db::RoswaalDatabase};
+
+    #[tokio::test]
+    async fn saves_test_run_and_its_url() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let test_names = vec!["Join an event".to_string(), "Leave an event".to_string()];
+        let run = transaction
+            .save_test_run(&branch_name, &test_names)
+            .await
+            .unwrap();
+        assert_eq!(
+            transaction.test_run(run.id()).await.unwrap(),
+            Some(run.clone())
+        );
+        let run = transaction
+            .save_test_run_url(run.id(), "https://ci.example.com/runs/1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.test_names(), &test_names);
+        assert_eq!(run.url(), Some("https://ci.example.com/runs/1"));
+        let missing_run = transaction
+            .save_test_run_url(run.id() + 1, "https://ci.example.com/runs/2")
+            .await
+            .unwrap();
+        assert_eq!(missing_run, None)
+    }
+}
@@ -0,0 +1,83 @@
+use std::{future::Future, sync::Arc};
+
+use anyhow::Result;
+use reqwest::Client;
+
+use super::run::RoswaalTestRun;
+
+/// A trait for asking a CI runner to run tests.
+pub trait RoswaalEnqueueTestRun {
+    /// Enqueues the specified run.
+    ///
+    /// The runner is expected to call back with the link to the run once it has started.
+    fn enqueue(&self, run: &RoswaalTestRun) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// A CI runner that is triggered by posting a run as JSON to a webhook url.
+pub struct RoswaalTestRunnerWebhook {
+    client: Arc<Client>,
+    url: String,
+}
+
+impl RoswaalTestRunnerWebhook {
+    pub fn new(client: Arc<Client>, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+impl RoswaalEnqueueTestRun for RoswaalTestRunnerWebhook {
+    async fn enqueue(&self, run: &RoswaalTestRun) -> Result<()> {
+        self.client
+            .post(&self.url)
+            .json(run)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use anyhow::Result;
+    use tokio::sync::Mutex;
+
+    use crate::{runner::run::RoswaalTestRun, utils::test_error::TestError};
+
+    use super::RoswaalEnqueueTestRun;
+
+    /// Used in place of a runner when a test does not care about test runs.
+    pub const NO_TEST_RUNNER: Option<&TestRoswaalTestRunner> = None;
+
+    /// A `RoswaalEnqueueTestRun` that records each run that it is given.
+    pub struct TestRoswaalTestRunner {
+        runs: Mutex<Vec<RoswaalTestRun>>,
+        should_fail: bool,
+    }
+
+    impl TestRoswaalTestRunner {
+        pub fn new(should_fail: bool) -> Self {
+            Self {
+                runs: Mutex::new(vec![]),
+                should_fail,
+            }
+        }
+
+        pub async fn runs(&self) -> Vec<RoswaalTestRun> {
+            self.runs.lock().await.clone()
+        }
+    }
+
+    impl RoswaalEnqueueTestRun for TestRoswaalTestRunner {
+        async fn enqueue(&self, run: &RoswaalTestRun) -> Result<()> {
+            if self.should_fail {
+                return Err(anyhow::Error::new(TestError));
+            }
+            self.runs.lock().await.push(run.clone());
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use log::{error, info};
+use tokio::time::sleep;
+
+use crate::{
+    http::server_environment::ServerEnvironment,
+    operations::deprecate_tests::remove_expired_deprecated_tests,
+    slack::{
+        message::{SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL},
+        remove_tests_view::RemoveTestsView,
+    },
+    utils::config::RoswaalConfig,
+};
+
+/// Opens a PR that removes the deprecated tests whose grace period has ended each time the
+/// deprecated tests schedule of the environment runs, and posts the result to the pull request
+/// channel.
+pub async fn run_deprecated_tests_scheduler(environment: Arc<ServerEnvironment>) {
+    let schedule = environment.deprecated_tests_schedule();
+    loop {
+        sleep(schedule.duration_until_next_run(Utc::now())).await;
+        if let Err(err) = remove_deprecated_tests(&environment).await {
+            error!("Failed to remove deprecated tests {}.", err);
+        }
+    }
+}
+
+async fn remove_deprecated_tests(environment: &ServerEnvironment) -> Result<()> {
+    let config = RoswaalConfig::current();
+    let status = remove_expired_deprecated_tests(
+        environment.database().as_ref(),
+        environment.git_repository(),
+        environment.github_pull_request_open(),
+        environment.test_target_repositories(),
+        Utc::now(),
+        config.deprecation_grace_period(),
+    )
+    .await?;
+    let Some(status) = status else {
+        info!("No deprecated tests have reached the end of their grace period.");
+        return Ok(());
+    };
+    let message = SlackMessage::new(
+        config.pull_request_channel_id(),
+        &RemoveTestsView::new(status),
+        SLACK_POST_MESSAGE_URL,
+    );
+    environment.slack_messenger().send(&message).await?;
+    Ok(())
+}
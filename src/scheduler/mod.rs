@@ -0,0 +1,3 @@
+pub mod deprecated_tests;
+pub mod schedule;
+pub mod test_digest;
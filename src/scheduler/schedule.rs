@@ -0,0 +1,79 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+
+/// A schedule that runs once every day at a fixed time in UTC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RoswaalDailySchedule {
+    time: NaiveTime,
+}
+
+impl RoswaalDailySchedule {
+    /// Returns a schedule that runs at the specified hour and minute in UTC.
+    ///
+    /// Panics if the hour or minute is out of range.
+    pub fn utc(hour: u32, minute: u32) -> Self {
+        Self {
+            time: NaiveTime::from_hms_opt(hour, minute, 0)
+                .expect("The hour or minute of a daily schedule is out of range."),
+        }
+    }
+}
+
+impl RoswaalDailySchedule {
+    /// Returns the next date after `now` at which this schedule runs.
+    pub fn next_run_date(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let run_date = now.date_naive().and_time(self.time).and_utc();
+        if run_date > now {
+            run_date
+        } else {
+            run_date + Duration::days(1)
+        }
+    }
+
+    /// Returns the amount of time from `now` until the next run of this schedule.
+    pub fn duration_until_next_run(&self, now: DateTime<Utc>) -> std::time::Duration {
+        (self.next_run_date(now) - now)
+            .to_std()
+            .unwrap_or(std::time::Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_run_date_is_later_on_the_same_day() {
+        let schedule = RoswaalDailySchedule::utc(4, 30);
+        let now = date("2024-07-24T01:00:00+0000");
+        assert_eq!(
+            schedule.next_run_date(now),
+            date("2024-07-24T04:30:00+0000")
+        )
+    }
+
+    #[test]
+    fn next_run_date_is_the_next_day_when_time_has_passed() {
+        let schedule = RoswaalDailySchedule::utc(4, 30);
+        let dates = ["2024-07-24T04:30:00+0000", "2024-07-24T23:59:00+0000"];
+        for now in dates {
+            assert_eq!(
+                schedule.next_run_date(date(now)),
+                date("2024-07-25T04:30:00+0000")
+            )
+        }
+    }
+
+    #[test]
+    fn duration_until_next_run() {
+        let schedule = RoswaalDailySchedule::utc(4, 0);
+        let now = date("2024-07-24T03:00:00+0000");
+        assert_eq!(
+            schedule.duration_until_next_run(now),
+            std::time::Duration::from_secs(60 * 60)
+        )
+    }
+
+    fn date(string: &str) -> DateTime<Utc> {
+        string.parse::<DateTime<Utc>>().unwrap()
+    }
+}
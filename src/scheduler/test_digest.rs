@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::{error, info};
+use tokio::time::sleep;
+
+use crate::{
+    http::server_environment::ServerEnvironment,
+    operations::test_digest::TestDigestStatus,
+    slack::{
+        message::{SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL},
+        test_digest_view::TestDigestView,
+    },
+    utils::db::RoswaalDatabase,
+};
+
+/// Posts a digest of the failing and idle tests to the test digest channel of the environment
+/// each time its test digest schedule runs.
+///
+/// Returns immediately if the environment does not have a test digest channel.
+pub async fn run_test_digest_scheduler(environment: Arc<ServerEnvironment>) {
+    let channel_id = match environment.test_digest_channel_id() {
+        Some(channel_id) => channel_id.to_string(),
+        None => {
+            info!("No test digest channel was specified, test digests will not be sent.");
+            return;
+        }
+    };
+    let schedule = environment.test_digest_schedule();
+    let messenger = environment.slack_messenger();
+    loop {
+        sleep(schedule.duration_until_next_run(Utc::now())).await;
+        let database = environment.database();
+        let result = send_test_digest(&channel_id, &database, messenger.as_ref(), Utc::now()).await;
+        if let Err(err) = result {
+            error!("Failed to send the test digest {}.", err);
+        }
+    }
+}
+
+/// Sends a digest of the failing and idle tests at `now` to the specified channel.
+pub async fn send_test_digest(
+    channel_id: &str,
+    database: &RoswaalDatabase,
+    messenger: &impl SlackSendMessage,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let status = TestDigestStatus::from_digesting_tests(database, now).await?;
+    let message = SlackMessage::new(
+        channel_id,
+        &TestDigestView::new(status),
+        SLACK_POST_MESSAGE_URL,
+    );
+    messenger.send(&message).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use chrono::Utc;
+    use tokio::sync::Mutex;
+
+    use crate::{
+        operations::test_digest::TestDigestStatus,
+        slack::{
+            message::{SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL},
+            test_digest_view::TestDigestView,
+        },
+        utils::db::RoswaalDatabase,
+    };
+
+    use super::send_test_digest;
+
+    struct TestSlackMessager {
+        messages: Mutex<Vec<SlackMessage>>,
+    }
+
+    impl SlackSendMessage for TestSlackMessager {
+        async fn send(&self, message: &SlackMessage) -> Result<Option<String>> {
+            self.messages.lock().await.push(message.clone());
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_digest_to_channel() {
+        let messenger = TestSlackMessager {
+            messages: Mutex::new(vec![]),
+        };
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        send_test_digest("C123", &database, &messenger, Utc::now())
+            .await
+            .unwrap();
+        let expected_message = SlackMessage::new(
+            "C123",
+            &TestDigestView::new(TestDigestStatus::NoTests),
+            SLACK_POST_MESSAGE_URL,
+        );
+        let messages = messenger.messages.lock().await;
+        assert_eq!(*messages, vec![expected_message])
+    }
+}
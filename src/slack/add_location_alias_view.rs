@@ -0,0 +1,90 @@
+use std::borrow::Borrow;
+
+use crate::operations::add_location_alias::AddLocationAliasStatus;
+
+use super::ui_lib::{
+    block_kit_views::{SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for adding an alternate name that tests can use for a location.
+pub struct AddLocationAliasView {
+    status: AddLocationAliasStatus,
+}
+
+impl AddLocationAliasView {
+    pub fn new(status: AddLocationAliasStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for AddLocationAliasView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Add Location Alias").flat_chain_block(self.status_view())
+    }
+}
+
+impl AddLocationAliasView {
+    fn status_view(&self) -> impl SlackView {
+        let text = match self.status.borrow() {
+            AddLocationAliasStatus::Success(alias) => format!(
+                "✅ Tests can now use *{}* to set their location to *{}*!",
+                alias.alias().raw_name(),
+                alias.location_name().raw_name()
+            ),
+            AddLocationAliasStatus::InvalidFormat => {
+                "🔴 Write the alias and the location name separated by a comma, like `SF, San Francisco`."
+                    .to_string()
+            }
+            AddLocationAliasStatus::InvalidAliasName(name) => format!(
+                "🔴 *{}* is not a valid location naaaaame!",
+                name
+            ),
+            AddLocationAliasStatus::UnknownLocationName(name) => format!(
+                "🔴 *{}* is not a merged location. Use `/view-locations` to see the locations I knoooooow!",
+                name
+            ),
+            AddLocationAliasStatus::AliasIsLocationName(name) => format!(
+                "🔴 *{}* is already the name of a locaaaaaation!",
+                name.raw_name()
+            ),
+        };
+        SlackSection::from_markdown(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        location::{alias::RoswaalLocationAlias, name::RoswaalLocationName},
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::*;
+
+    #[test]
+    fn success_snapshot() {
+        let alias = RoswaalLocationAlias::new(
+            RoswaalLocationName::from_str("SF").unwrap(),
+            RoswaalLocationName::from_str("San Francisco").unwrap(),
+        );
+        assert_slack_view_snapshot(
+            "add-location-alias-success",
+            &AddLocationAliasView::new(AddLocationAliasStatus::Success(alias)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn unknown_location_name_snapshot() {
+        assert_slack_view_snapshot(
+            "add-location-alias-unknown-location-name",
+            &AddLocationAliasView::new(AddLocationAliasStatus::UnknownLocationName(
+                "San Francisco".to_string(),
+            )),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -1,31 +1,45 @@
 use std::borrow::Borrow;
 
 use crate::{
-    location::location::{RoswaalLocationStringError, RoswaalStringLocations},
+    location::location::{RoswaalLocation, RoswaalStringLocations},
     operations::add_locations::AddLocationsStatus,
+    utils::config::RoswaalConfig,
 };
 
 use super::{
     merge_conflict_view::MergeConflictView,
     pr_open_fail_view::FailedToOpenPullRequestView,
+    remote_unavailable_view::RemoteUnavailableView,
     ui_lib::{
         block_kit_views::{SlackDivider, SlackHeader, SlackSection},
         empty_view::EmptySlackView,
         if_view::If,
         slack_view::SlackView,
     },
-    users::MATTHEW_SLACK_USER_ID,
+    users::RoswaalSlackUserRoster,
     warn_undeleted_branch_view::WarnUndeletedBranchView,
 };
 
 /// A view for adding locations.
 pub struct AddLocationsView {
     status: AddLocationsStatus,
+    roster: RoswaalSlackUserRoster,
 }
 
 impl AddLocationsView {
     pub fn new(status: AddLocationsStatus) -> Self {
-        Self { status }
+        Self {
+            status,
+            roster: RoswaalSlackUserRoster::default(),
+        }
+    }
+
+    /// Uses `roster` to decide who to ping about a merge conflict.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
     }
 }
 
@@ -38,23 +52,23 @@ impl SlackView for AddLocationsView {
 impl AddLocationsView {
     fn status_view(&self) -> impl SlackView {
         match self.status.borrow() {
-            AddLocationsStatus::Success { locations, did_delete_branch } => {
-                If::is_true(
-                    locations.has_valid_locations(),
-                    || self.success_locations_view(locations)
-                )
+            AddLocationsStatus::Success { locations, did_delete_branch, branch_name: _ } => {
+                self.changed_locations_view(locations)
                 .flat_chain_block(
                     If::is_true(locations.has_errors(), || self.failure_locations_view(locations))
                 )
                 .flat_chain_block(
                     If::is_true(
-                        locations.has_valid_locations(),
+                        locations.has_changes(),
                         || {
                             SlackDivider.flat_chain_block(
                                 SlackHeader::new("Next Steps")
                                     .flat_chain_block(
                                         SlackSection::from_markdown(
-                                            "Approve the PR found in <#C01B7FFKDCP> to finish the adding the locaaaaaations!"
+                                            &format!(
+                                                "Approve the PR found in <#{}> to finish the adding the locaaaaaations!",
+                                                RoswaalConfig::current().pull_request_channel_id()
+                                            )
                                         )
                                     )
                             )
@@ -73,49 +87,104 @@ impl AddLocationsView {
                 SlackSection::from_markdown("No Locations were aaaaaaaaaaadded.")
                     .erase_to_any_view()
             },
+            AddLocationsStatus::NoChanges { locations } => {
+                SlackSection::from_markdown(
+                    "Every location was already added with the same coordinates, so nothing chaaaaaanged."
+                )
+                .flat_chain_block(self.unchanged_locations_view(locations))
+                .flat_chain_block(
+                    If::is_true(locations.has_errors(), || self.failure_locations_view(locations))
+                )
+                .erase_to_any_view()
+            },
+            AddLocationsStatus::FailedToImportFile { url } => {
+                SlackSection::from_markdown(
+                    &format!("🔴 The locations file at {} could not be doooooownloaded or read.", url)
+                )
+                .erase_to_any_view()
+            },
             AddLocationsStatus::FailedToOpenPullRequest => {
                 FailedToOpenPullRequestView.erase_to_any_view()
             },
+            AddLocationsStatus::RemoteUnavailable => {
+                RemoteUnavailableView.erase_to_any_view()
+            },
             AddLocationsStatus::MergeConflict => {
-                MergeConflictView::new(MATTHEW_SLACK_USER_ID).erase_to_any_view()
+                MergeConflictView::new(&self.roster).erase_to_any_view()
             },
         }
     }
 
-    fn success_locations_view(&self, locations: &RoswaalStringLocations) -> impl SlackView {
-        let mut body = "✅ *The following locations were added succeeeeeeesfully!*\n".to_string();
-        for location in locations.locations() {
-            let line = format!(
-                "- *{}* (Latitude: {:.8}, Longitude: {:.8})\n",
-                location.name().raw_name(),
-                location.coordinate().latitude(),
-                location.coordinate().longitude()
-            );
-            body.push_str(&line)
-        }
-        SlackSection::from_markdown(&body)
+    fn changed_locations_view(&self, locations: &RoswaalStringLocations) -> impl SlackView {
+        let added_locations = locations.added_locations();
+        let updated_locations = locations.updated_locations();
+        If::is_true(
+            !added_locations.is_empty(),
+            move || locations_list_view(
+                "✅ *The following locations were added succeeeeeeesfully!*",
+                &added_locations
+            )
+        )
+        .flat_chain_block(
+            If::is_true(
+                !updated_locations.is_empty(),
+                move || locations_list_view(
+                    "🔄 *The following locations were already added, and were updated with new coordinaaaaaates!*",
+                    &updated_locations
+                )
+            )
+        )
+        .flat_chain_block(self.unchanged_locations_view(locations))
+    }
+
+    fn unchanged_locations_view(&self, locations: &RoswaalStringLocations) -> impl SlackView {
+        let unchanged_locations = locations.unchanged_locations();
+        If::is_true(
+            !unchanged_locations.is_empty(),
+            move || locations_list_view(
+                "ℹ️ *The following locations were already added, and were left unchaaaaaanged.*",
+                &unchanged_locations
+            )
+        )
     }
 
     fn failure_locations_view(&self, string_locations: &RoswaalStringLocations) -> impl SlackView {
         let mut body = "⚠️ *The following locations were invaaaaaaalid...*\n".to_string();
         for error in string_locations.errors() {
-            body.push_str(&format!("- *{}* ", error.raw_associated_location_name()));
-            match error {
-                RoswaalLocationStringError::InvalidName(_, _) => body.push_str("(Invalid Name)"),
-                RoswaalLocationStringError::InvalidCoordinate { name: _ } => {
-                    body.push_str("(Invalid Coordinate)")
-                }
-            };
-            body.push_str("\n")
+            body.push_str(&format!(
+                "- *{}* ({})\n",
+                error.raw_associated_location_name(),
+                error.reason()
+            ))
         }
         SlackSection::from_markdown(&body)
     }
 }
 
+fn locations_list_view(header: &str, locations: &[RoswaalLocation]) -> impl SlackView {
+    let mut body = format!("{}\n", header);
+    for location in locations {
+        let region = location
+            .region()
+            .map(|region| format!(", Region: {}", region.raw_name()))
+            .unwrap_or_default();
+        let line = format!(
+            "- *{}* (Latitude: {:.8}, Longitude: {:.8}{})\n",
+            location.name().raw_name(),
+            location.coordinate().latitude(),
+            location.coordinate().longitude(),
+            region
+        );
+        body.push_str(&line)
+    }
+    SlackSection::from_markdown(&body)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        location::location::RoswaalStringLocations,
+        git::branch_name::RoswaalOwnedGitBranchName,
+        location::location::{RoswaalLocation, RoswaalStringLocations},
         operations::add_locations::AddLocationsStatus,
         slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
     };
@@ -135,6 +204,27 @@ New York
             &AddLocationsView::new(AddLocationsStatus::Success {
                 locations,
                 did_delete_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_with_regions_snapshot() {
+        let string = "\
+Antarctica, 50.20982098092, 50.09830883
+Region: Bay Area
+Oakland, 37.8044, -122.2712
+Region: Bay-Area
+";
+        let locations = RoswaalStringLocations::from_roswaal_locations_str(string);
+        assert_slack_view_snapshot(
+            "add-locations-success-with-regions",
+            &AddLocationsView::new(AddLocationsStatus::Success {
+                locations,
+                did_delete_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
             }),
             SnapshotMode::Comparing,
         )
@@ -153,6 +243,7 @@ New York
             &AddLocationsView::new(AddLocationsStatus::Success {
                 locations,
                 did_delete_branch: false,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
             }),
             SnapshotMode::Comparing,
         )
@@ -169,6 +260,7 @@ Antarctica, 50.20982098092, 50.09830883
             &AddLocationsView::new(AddLocationsStatus::Success {
                 locations,
                 did_delete_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
             }),
             SnapshotMode::Comparing,
         )
@@ -185,11 +277,70 @@ Antarctica, 50.20982098092, 50.09830883
             &AddLocationsView::new(AddLocationsStatus::Success {
                 locations,
                 did_delete_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
             }),
             SnapshotMode::Comparing,
         )
     }
 
+    #[test]
+    fn success_with_out_of_range_coordinate_snapshot() {
+        let string = "\
+Antarctica, 50.20982098092, 50.09830883
+San Francisco, 122.39, 37.77
+";
+        let locations = RoswaalStringLocations::from_roswaal_locations_str(string);
+        assert_slack_view_snapshot(
+            "add-locations-out-of-range-coordinate",
+            &AddLocationsView::new(AddLocationsStatus::Success {
+                locations,
+                did_delete_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_with_updated_and_unchanged_locations_snapshot() {
+        let string = "\
+Antarctica, 50.20982098092, 50.09830883
+Oakland, 37.8044, -122.2712
+Berkeley, 37.8715, -122.273
+";
+        let merged_locations = [
+            RoswaalLocation::new_without_validation("Oakland", 37.8, -122.27),
+            RoswaalLocation::new_without_validation("Berkeley", 37.8715, -122.273),
+        ];
+        let locations = RoswaalStringLocations::from_roswaal_locations_str(string)
+            .with_merged_locations(&merged_locations.iter().collect::<Vec<&RoswaalLocation>>());
+        assert_slack_view_snapshot(
+            "add-locations-success-with-updated-and-unchanged",
+            &AddLocationsView::new(AddLocationsStatus::Success {
+                locations,
+                did_delete_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_adding_locations(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_changes_snapshot() {
+        let string = "\
+Berkeley, 37.8715, -122.273
+12.298739, 122.2989379
+";
+        let merged_locations = [RoswaalLocation::new_without_validation("Berkeley", 37.8715, -122.273)];
+        let locations = RoswaalStringLocations::from_roswaal_locations_str(string)
+            .with_merged_locations(&merged_locations.iter().collect::<Vec<&RoswaalLocation>>());
+        assert_slack_view_snapshot(
+            "add-locations-no-changes",
+            &AddLocationsView::new(AddLocationsStatus::NoChanges { locations }),
+            SnapshotMode::Comparing,
+        )
+    }
+
     #[test]
     fn no_locations_snapshot() {
         assert_slack_view_snapshot(
@@ -199,6 +350,17 @@ Antarctica, 50.20982098092, 50.09830883
         )
     }
 
+    #[test]
+    fn failed_to_import_file_snapshot() {
+        assert_slack_view_snapshot(
+            "add-locations-failed-to-import-file",
+            &AddLocationsView::new(AddLocationsStatus::FailedToImportFile {
+                url: "https://files.slack.com/files-pri/T0-F0/locations.gpx".to_string(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
     #[test]
     fn pr_fail_snapshot() {
         assert_slack_view_snapshot(
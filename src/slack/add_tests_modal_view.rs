@@ -0,0 +1,39 @@
+use super::{
+    modal::{ADD_TESTS_INPUT_ACTION_ID, ADD_TESTS_INPUT_BLOCK_ID},
+    ui_lib::{
+        block_kit_views::{SlackPlainTextInput, SlackSection},
+        slack_view::SlackView,
+    },
+};
+
+/// The contents of the modal that is opened when `/add-tests` is sent without any tests.
+pub struct AddTestsModalView;
+
+impl SlackView for AddTestsModalView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown(
+            "✍️ Write the teeeeeests to add below! Start each test with `New Test:`, and separate multiple tests with a code block (```) around eaaaaaach one. Add `--overwrite` outside of the code blocks to replace merged tests with the same naaaaame.",
+        )
+        .flat_chain_block(
+            SlackPlainTextInput::new(ADD_TESTS_INPUT_BLOCK_ID, ADD_TESTS_INPUT_ACTION_ID, "Tests")
+                .multiline()
+                .placeholder("New Test: Join an event\nStep 1: Roswaal joins the event\nRequirement 1: Tap join"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::AddTestsModalView;
+
+    #[test]
+    fn snapshot() {
+        assert_slack_view_snapshot(
+            "add-tests-modal",
+            &AddTestsModalView,
+            SnapshotMode::Comparing,
+        )
+    }
+}
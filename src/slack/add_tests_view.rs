@@ -1,45 +1,87 @@
 use std::borrow::Borrow;
 
 use crate::{
+    generation::test_case::RoswaalActionNames,
     language::{
         ast::RoswaalTestSyntax,
         compilation_results::RoswaalTestCompilationFailure,
         compiler::{
             RoswaalCompilationDuplicateErrorCode, RoswaalCompilationError,
-            RoswaalCompilationErrorCode,
+            RoswaalCompilationErrorCode, RoswaalCompilationWarningCode,
+            MAX_STEP_DESCRIPTION_LENGTH,
         },
+        duration::RoswaalWaitDurationParsingError,
         test::RoswaalCompiledTest,
     },
     location::name::RoswaalLocationNameParsingError,
-    operations::add_tests::AddTestsStatus,
+    operations::add_tests::{AddTestsStatus, OVERWRITE_FLAG},
+    tests_data::estimate::RoswaalSuiteRuntimeOverrun,
+    utils::config::RoswaalConfig,
 };
 
 use super::{
+    catalog::RoswaalMessageId,
+    interaction::RoswaalSlackAction,
     merge_conflict_view::MergeConflictView,
     pr_open_fail_view::FailedToOpenPullRequestView,
+    remote_unavailable_view::RemoteUnavailableView,
+    search_tests_view::format_suite_duration,
+    target_repositories_view::TargetRepositoriesView,
     ui_lib::{
-        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        block_kit_views::{SlackActions, SlackDivider, SlackHeader, SlackSection},
         for_each_view::ForEachView,
         if_view::If,
         slack_view::SlackView,
+        syntax_view::{SlackTestSyntaxMode, SlackTestSyntaxView},
     },
-    users::MATTHEW_SLACK_USER_ID,
+    users::RoswaalSlackUserRoster,
     warn_undeleted_branch_view::WarnUndeletedBranchView,
 };
 
 pub struct AddTestsView<'r> {
     status: AddTestsStatus<'r>,
+    tests_str: Option<&'r str>,
+    roster: RoswaalSlackUserRoster,
+    syntax_mode: SlackTestSyntaxMode,
 }
 
 impl<'r> AddTestsView<'r> {
     pub fn new(status: AddTestsStatus<'r>) -> Self {
-        Self { status }
+        Self {
+            status,
+            tests_str: None,
+            roster: RoswaalSlackUserRoster::default(),
+            syntax_mode: SlackTestSyntaxMode::default(),
+        }
+    }
+
+    /// Shows a button to compile the specified tests string again when no pull request was opened.
+    pub fn recompilable_from(self, tests_str: &'r str) -> Self {
+        Self {
+            tests_str: Some(tests_str),
+            ..self
+        }
+    }
+
+    /// Uses `roster` to decide who to ping when adding the tests runs into a merge conflict.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
+    }
+
+    /// Shows the source code of each test in `syntax_mode` instead of a code block.
+    pub fn with_syntax_mode(self, syntax_mode: SlackTestSyntaxMode) -> Self {
+        Self { syntax_mode, ..self }
     }
 }
 
 impl<'r> SlackView for AddTestsView<'r> {
     fn slack_body(&self) -> impl SlackView {
-        SlackHeader::new("Add Tests").flat_chain_block(self.status_view())
+        SlackHeader::new(RoswaalMessageId::AddTestsHeader.text())
+            .flat_chain_block(self.status_view())
+            .flat_chain_block(self.actions_view())
     }
 }
 
@@ -49,11 +91,18 @@ impl<'r> AddTestsView<'r> {
             AddTestsStatus::Success {
                 results,
                 should_warn_undeleted_branch,
+                branch_name: _,
+                target_edits,
+                name_conflicts,
+                suite_runtime_overrun,
             } => {
                 If::is_true(
                     results.has_compiling_tests(),
                     || self.compiling_tests_view(&results.tests_with_syntax())
                 )
+                .flat_chain_block(self.warnings_view(&results.tests()))
+                .flat_chain_block(self.name_conflicts_view(name_conflicts))
+                .flat_chain_block(suite_runtime_overrun.map(suite_runtime_overrun_view))
                 .flat_chain_block(
                     If::is_true(
                         results.has_non_compiling_tests(),
@@ -65,10 +114,12 @@ impl<'r> AddTestsView<'r> {
                         results.has_compiling_tests(),
                         || {
                             SlackDivider.flat_chain_block(
-                                SlackHeader::new("Next Steps")
+                                SlackHeader::new(RoswaalMessageId::NextSteps.text())
                                     .flat_chain_block(
                                         SlackSection::from_markdown(
-                                            "Approve the PR found in <#C01B7FFKDCP> to finish the adding the teeeeeeeests!"
+                                            &RoswaalMessageId::AddTestsApprovePullRequest.format(
+                                                &[&RoswaalConfig::current().pull_request_channel_id()]
+                                            )
                                         )
                                     )
                             )
@@ -81,22 +132,56 @@ impl<'r> AddTestsView<'r> {
                         || SlackDivider.flat_chain_block(WarnUndeletedBranchView)
                     )
                 )
+                .flat_chain_block(TargetRepositoriesView::new(target_edits).with_roster(&self.roster))
                 .erase_to_any_view()
             }
             AddTestsStatus::NoTestsFound => {
-                SlackSection::from_markdown("🔴 No tests were fooooooound.").erase_to_any_view()
+                SlackSection::from_markdown(RoswaalMessageId::NoTestsFound.text()).erase_to_any_view()
             }
             AddTestsStatus::MergeConflict => {
-                MergeConflictView::new(MATTHEW_SLACK_USER_ID).erase_to_any_view()
+                MergeConflictView::new(&self.roster).erase_to_any_view()
             }
             AddTestsStatus::FailedToOpenPullRequest => {
                 FailedToOpenPullRequestView.erase_to_any_view()
             }
+            AddTestsStatus::RemoteUnavailable => {
+                RemoteUnavailableView.erase_to_any_view()
+            }
         }
     }
 }
 
+fn suite_runtime_overrun_view(overrun: RoswaalSuiteRuntimeOverrun) -> impl SlackView {
+    let message = RoswaalMessageId::AddTestsSuiteRuntimeOverBudget.format(&[
+        &format_suite_duration(overrun.runtime()),
+        &format_suite_duration(overrun.budget()),
+    ]);
+    SlackDivider.flat_chain_block(SlackSection::from_markdown(&message))
+}
+
 impl<'r> AddTestsView<'r> {
+    fn actions_view(&self) -> Option<impl SlackView> {
+        let button = match &self.status {
+            AddTestsStatus::Success {
+                results: _,
+                should_warn_undeleted_branch: _,
+                branch_name: Some(branch_name),
+                target_edits: _,
+                name_conflicts: _,
+                suite_runtime_overrun: _,
+            } => RoswaalSlackAction::CloseBranch
+                .button(RoswaalMessageId::AddTestsCloseBranchButton.text(), &branch_name.to_string())
+                .map(|b| b.danger()),
+            AddTestsStatus::NoTestsFound => None,
+            _ => self.tests_str.and_then(|tests_str| {
+                RoswaalSlackAction::RecompileTests
+                    .button(RoswaalMessageId::AddTestsRecompileButton.text(), tests_str)
+                    .map(|b| b.primary())
+            }),
+        };
+        button.map(|button| SlackDivider.flat_chain_block(SlackActions::new(vec![button])))
+    }
+
     fn compiling_tests_view(
         &self,
         tests_with_syntax: &Vec<(RoswaalCompiledTest, RoswaalTestSyntax<'r>)>,
@@ -106,20 +191,90 @@ impl<'r> AddTestsView<'r> {
             .map(|e| e.clone())
             .enumerate()
             .map(|(index, value)| (index < tests_with_syntax.len() - 1, value));
-        SlackSection::from_markdown("✅ *The following tests were compiled succeeeeeeeeessfully!*")
+        let syntax_mode = self.syntax_mode;
+        SlackSection::from_markdown(RoswaalMessageId::AddTestsCompiledSuccessfully.text())
             .flat_chain_block(ForEachView::new(
                 iter,
-                |(is_showing_divider, (test, syntax))| {
+                move |(is_showing_divider, (test, syntax))| {
                     SlackSection::from_markdown(&format!("📝 *{}*", test.name()))
-                        .flat_chain_block(SlackSection::from_markdown(
-                            &syntax.markdown_code_block(),
-                        ))
+                        .flat_chain_block(SlackTestSyntaxView::new(syntax, syntax_mode))
                         .flat_chain_block(If::is_true(*is_showing_divider, || SlackDivider))
                 },
             ))
             .erase_to_any_view()
     }
 
+    fn warnings_view(&self, tests: &[RoswaalCompiledTest]) -> impl SlackView {
+        let bodies = tests
+            .iter()
+            .map(|test| (test, RoswaalActionNames::new(test.commands())))
+            .filter(|(test, action_names)| {
+                !test.warnings().is_empty() || !action_names.collisions().is_empty()
+            })
+            .map(|(test, action_names)| {
+                let mut body = format!("📝 *{}*\n", test.name());
+                for warning in test.warnings() {
+                    body.push_str(
+                        &format!(
+                            "- {}\n",
+                            RoswaalMessageId::CompilationWarningLine.format(
+                                &[&compilation_warning_message(warning.code()), &warning.line_number()]
+                            )
+                        )
+                    )
+                }
+                for collision in action_names.collisions() {
+                    body.push_str(
+                        &format!(
+                            "- {}\n",
+                            RoswaalMessageId::WarningActionNameCollision.format(
+                                &[
+                                    &collision.requirement(),
+                                    &collision.colliding_requirement(),
+                                    &collision.renamed_name()
+                                ]
+                            )
+                        )
+                    )
+                }
+                body
+            })
+            .collect::<Vec<String>>();
+        If::is_true(
+            !bodies.is_empty(),
+            || {
+                SlackDivider.flat_chain_block(
+                    SlackSection::from_markdown(RoswaalMessageId::AddTestsCompiledWithWarnings.text())
+                )
+            }
+        )
+        .flat_chain_block(
+            ForEachView::new(bodies.into_iter(), |body| SlackSection::from_markdown(body))
+        )
+    }
+
+    fn name_conflicts_view(&self, name_conflicts: &[String]) -> impl SlackView {
+        let names = name_conflicts
+            .iter()
+            .map(|name| format!("- *{}*", name))
+            .collect::<Vec<String>>()
+            .join("\n");
+        If::is_true(
+            !name_conflicts.is_empty(),
+            move || {
+                SlackDivider.flat_chain_block(
+                    SlackSection::from_markdown(RoswaalMessageId::AddTestsNameConflicts.text())
+                )
+                .flat_chain_block(SlackSection::from_markdown(&names))
+                .flat_chain_block(
+                    SlackSection::from_markdown(
+                        &RoswaalMessageId::AddTestsNameConflictsHint.format(&[&OVERWRITE_FLAG])
+                    )
+                )
+            }
+        )
+    }
+
     fn non_compiling_tests_view(
         &self,
         failures: &Vec<RoswaalTestCompilationFailure<'r>>,
@@ -129,140 +284,216 @@ impl<'r> AddTestsView<'r> {
             .map(|e| e.clone())
             .enumerate()
             .map(|(index, value)| (index < failures.len() - 1, value));
-        SlackSection::from_markdown(
-            "⚠️ *The following tests did not compile succeeeeeessfully. They are only listed by the number based on the compilation oooooooooorder!*"
-        )
+        let syntax_mode = self.syntax_mode;
+        SlackSection::from_markdown(RoswaalMessageId::AddTestsNotCompiled.text())
         .flat_chain_block(
-            ForEachView::new(iter, |(is_showing_divider, failure)| {
-                NonCompilingTestView {
-                    test_number: failure.test_number(),
-                    errors: failure.errors().to_vec(),
-                    syntax_markdown: failure.syntax().markdown_code_block()
-                }
-                .flat_chain_block(If::is_true(*is_showing_divider, || SlackDivider))
+            ForEachView::new(iter, move |(is_showing_divider, failure)| {
+                NonCompilingTestView::new(failure)
+                    .with_syntax_mode(syntax_mode)
+                    .flat_chain_block(If::is_true(*is_showing_divider, || SlackDivider))
             })
         )
         .erase_to_any_view()
     }
 }
 
-struct NonCompilingTestView {
+/// A view that shows the source code and compilation errors of a test that did not compile.
+pub(super) struct NonCompilingTestView {
     test_number: usize,
     errors: Vec<RoswaalCompilationError>,
-    syntax_markdown: String,
+    source_code: String,
+    syntax_mode: SlackTestSyntaxMode,
+}
+
+impl NonCompilingTestView {
+    pub(super) fn new(failure: &RoswaalTestCompilationFailure) -> Self {
+        Self {
+            test_number: failure.test_number(),
+            errors: failure.errors().to_vec(),
+            source_code: failure.syntax().source_code().to_string(),
+            syntax_mode: SlackTestSyntaxMode::default(),
+        }
+    }
+
+    pub(super) fn with_syntax_mode(self, syntax_mode: SlackTestSyntaxMode) -> Self {
+        Self { syntax_mode, ..self }
+    }
 }
 
 impl SlackView for NonCompilingTestView {
     fn slack_body(&self) -> impl SlackView {
-        SlackSection::from_markdown(&format!("❗️ *Test {}*", self.test_number))
-            .flat_chain_block(SlackSection::from_markdown(&self.syntax_markdown))
+        SlackSection::from_markdown(&RoswaalMessageId::AddTestsFailureTitle.format(&[&self.test_number]))
+            .flat_chain_block(SlackTestSyntaxView::new(
+                &RoswaalTestSyntax::from(self.source_code.as_str()),
+                self.syntax_mode,
+            ))
             .flat_chain_block(ForEachView::new(self.errors.iter(), |error| {
                 CompilationErrorView { error }
             }))
     }
 }
 
+/// Returns the markdown message that describes a compilation warning without its line number.
+pub fn compilation_warning_message(code: &RoswaalCompilationWarningCode) -> String {
+    match code {
+        RoswaalCompilationWarningCode::LongStepDescription { step_name, length } => {
+            RoswaalMessageId::WarningLongStepDescription.format(
+                &[step_name, length, &MAX_STEP_DESCRIPTION_LENGTH]
+            )
+        },
+        RoswaalCompilationWarningCode::AbstractOverwritten => {
+            RoswaalMessageId::WarningAbstractOverwritten.text().to_string()
+        },
+        RoswaalCompilationWarningCode::SuiteOverwritten => {
+            RoswaalMessageId::WarningSuiteOverwritten.text().to_string()
+        },
+        RoswaalCompilationWarningCode::LocationNameCasingMismatch { name, known_name } => {
+            RoswaalMessageId::WarningLocationNameCasingMismatch.format(&[name, known_name])
+        },
+        RoswaalCompilationWarningCode::StepLabelRenumbered { step_name, renumbered_name } => {
+            RoswaalMessageId::WarningStepLabelRenumbered.format(&[step_name, renumbered_name])
+        },
+    }
+}
+
 struct CompilationErrorView<'v> {
     error: &'v RoswaalCompilationError,
 }
 
 impl<'v> SlackView for CompilationErrorView<'v> {
     fn slack_body(&self) -> impl SlackView {
-        let mut body = String::new();
-        match self.error.code() {
-            RoswaalCompilationErrorCode::NoTestName => {
-                body.push_str("No test name was speeeeeeecified.")
-            },
-            RoswaalCompilationErrorCode::NoTestSteps => {
-                body.push_str("No test steps were speeeeeeeecified.")
-            },
-            RoswaalCompilationErrorCode::NoCommandDescription { command_name } => {
-                body.push_str(&format!("No command description was specified for \"{}\".", command_name))
-            },
-            RoswaalCompilationErrorCode::NoStepRequirement { step_name, step_description } => {
-                body.push_str(
-                    &format!(
-                        "\"{}: {}\" has no matching requiremeeeeeeeeeeent.",
-                        step_name,
-                        step_description
-                    )
-                )
-            },
-            RoswaalCompilationErrorCode::NoRequirementStep { requirement_name, requirement_description } => {
-                body.push_str(
-                    &format!(
-                        "\"{}: {}\" has no matching steeeeeeeeeeep.",
-                        requirement_name,
-                        requirement_description
-                    )
-                )
-            },
-            RoswaalCompilationErrorCode::UnknownLocationName(name) => {
-                body.push_str(
-                    &format!(
-                        "\"{}\" is an unknown location naaaaaaaame. Add it using the `/add-locations` commaaaaaand!",
-                        name
-                    )
+        SlackSection::from_markdown(&compilation_error_line_message(self.error))
+    }
+}
+
+/// Returns the markdown message that describes a compilation error along with its line number.
+pub fn compilation_error_line_message(error: &RoswaalCompilationError) -> String {
+    RoswaalMessageId::CompilationErrorLine.format(
+        &[&compilation_error_message(error), &error.line_number()]
+    )
+}
+
+/// Returns the markdown message that describes a compilation error without its line number.
+pub fn compilation_error_message(error: &RoswaalCompilationError) -> String {
+    let mut body = String::new();
+    match error.code() {
+        RoswaalCompilationErrorCode::NoTestName => {
+            body.push_str(RoswaalMessageId::ErrorNoTestName.text())
+        },
+        RoswaalCompilationErrorCode::NoTestSteps => {
+            body.push_str(RoswaalMessageId::ErrorNoTestSteps.text())
+        },
+        RoswaalCompilationErrorCode::NoCommandDescription { command_name } => {
+            body.push_str(&RoswaalMessageId::ErrorNoCommandDescription.format(&[command_name]))
+        },
+        RoswaalCompilationErrorCode::NoStepRequirement { step_name, step_description } => {
+            body.push_str(
+                &RoswaalMessageId::ErrorNoStepRequirement.format(&[step_name, step_description])
+            )
+        },
+        RoswaalCompilationErrorCode::NoRequirementStep { requirement_name, requirement_description } => {
+            body.push_str(
+                &RoswaalMessageId::ErrorNoRequirementStep.format(
+                    &[requirement_name, requirement_description]
                 )
-            },
-            RoswaalCompilationErrorCode::InvalidLocationName(name, error) => {
-                match error {
-                    // NB: This case is treated as the "NoCommandDescription" error.
-                    RoswaalLocationNameParsingError::Empty => {},
-                    RoswaalLocationNameParsingError::InvalidFormat => {
-                        body.push_str(
-                            &format!(
-                                "\"{}\" was in an invalid foooooormat. Make sure you don't include any special characters in the location naaaaaaaaaame.",
-                                name
-                            )
-                        )
-                    }
+            )
+        },
+        RoswaalCompilationErrorCode::NoUsingVerification { using_name, using_description } => {
+            body.push_str(
+                &RoswaalMessageId::ErrorNoUsingVerification.format(&[using_name, using_description])
+            )
+        },
+        RoswaalCompilationErrorCode::UnknownLocationName(name) => {
+            body.push_str(&RoswaalMessageId::ErrorUnknownLocationName.format(&[name]))
+        },
+        RoswaalCompilationErrorCode::InvalidLocationName(name, error) => {
+            match error {
+                // NB: This case is treated as the "NoCommandDescription" error.
+                RoswaalLocationNameParsingError::Empty => {},
+                RoswaalLocationNameParsingError::InvalidFormat => {
+                    body.push_str(&RoswaalMessageId::ErrorInvalidLocationName.format(&[name]))
                 }
-            },
-            RoswaalCompilationErrorCode::InvalidCommandName(name) => {
-                body.push_str(
-                    &format!(
-                        "\"{}\" has valid command syyyyyntax, but it is not a known comaaaaand.",
-                        name
-                    )
-                )
-            },
-            RoswaalCompilationErrorCode::Duplicate { name, code } => {
-                match code {
-                    RoswaalCompilationDuplicateErrorCode::StepLabel => {
-                        body.push_str(
-                            &format!(
-                                "Mutliple steps named \"{}\" were fooooound! Make sure there is only ooooone!",
-                                name
-                            )
-                        )
-                    },
-                    RoswaalCompilationDuplicateErrorCode::RequirementLabel => {
-                        body.push_str(
-                            &format!(
-                                "Mutliple requirements named \"{}\" were fooooound! Make sure there is only ooooone!",
-                                name
-                            )
-                        )
-                    }
+            }
+        },
+        RoswaalCompilationErrorCode::InvalidWaitDuration(duration, error) => {
+            match error {
+                // NB: This case is treated as the "NoCommandDescription" error.
+                RoswaalWaitDurationParsingError::Empty => {},
+                RoswaalWaitDurationParsingError::InvalidFormat => {
+                    body.push_str(&RoswaalMessageId::ErrorInvalidWaitDuration.format(&[duration]))
+                },
+                RoswaalWaitDurationParsingError::Zero => {
+                    body.push_str(&RoswaalMessageId::ErrorWaitDurationTooShort.format(&[duration]))
+                },
+                RoswaalWaitDurationParsingError::TooLong => {
+                    body.push_str(&RoswaalMessageId::ErrorWaitDurationTooLong.format(&[duration]))
                 }
-            },
-            RoswaalCompilationErrorCode::TestNameAlreadyDeclared => {
-                body.push_str("This test has multiple \"New Test\" commaaaaaaands. Make sure there is only oooooooone!")
-            },
-        }
-        body.push_str("\n");
-        body.push_str(&format!("*Line: {}*", self.error.line_number()));
-        SlackSection::from_markdown(&body)
+            }
+        },
+        RoswaalCompilationErrorCode::UnknownPlatform(name) => {
+            body.push_str(&RoswaalMessageId::ErrorUnknownPlatform.format(&[name]))
+        },
+        RoswaalCompilationErrorCode::InvalidVariable(declaration) => {
+            body.push_str(&RoswaalMessageId::ErrorInvalidVariable.format(&[declaration]))
+        },
+        RoswaalCompilationErrorCode::UndeclaredVariable(name) => {
+            body.push_str(&RoswaalMessageId::ErrorUndeclaredVariable.format(&[name]))
+        },
+        RoswaalCompilationErrorCode::InvalidCommandName { name, did_you_mean } => {
+            body.push_str(&RoswaalMessageId::ErrorInvalidCommandName.format(&[name]));
+            if let Some(suggestion) = did_you_mean {
+                body.push(' ');
+                body.push_str(&RoswaalMessageId::ErrorInvalidCommandNameSuggestion.format(&[suggestion]))
+            }
+        },
+        RoswaalCompilationErrorCode::Duplicate { name, code } => {
+            let message = match code {
+                RoswaalCompilationDuplicateErrorCode::StepLabel => {
+                    RoswaalMessageId::ErrorDuplicateStep
+                },
+                RoswaalCompilationDuplicateErrorCode::RequirementLabel => {
+                    RoswaalMessageId::ErrorDuplicateRequirement
+                },
+                RoswaalCompilationDuplicateErrorCode::VerifyLabel => {
+                    RoswaalMessageId::ErrorDuplicateVerification
+                },
+                RoswaalCompilationDuplicateErrorCode::UsingLabel => {
+                    RoswaalMessageId::ErrorDuplicateUsing
+                }
+            };
+            body.push_str(&message.format(&[name]))
+        },
+        RoswaalCompilationErrorCode::TestNameAlreadyDeclared => {
+            body.push_str(RoswaalMessageId::ErrorTestNameAlreadyDeclared.text())
+        },
+        RoswaalCompilationErrorCode::UnknownRequiredTest(name) => {
+            body.push_str(&RoswaalMessageId::ErrorUnknownRequiredTest.format(&[name]))
+        },
+        RoswaalCompilationErrorCode::CircularTestDependency(name) => {
+            body.push_str(&RoswaalMessageId::ErrorCircularTestDependency.format(&[name]))
+        },
+        RoswaalCompilationErrorCode::PlannedTestCommand { command_name } => {
+            body.push_str(&RoswaalMessageId::ErrorPlannedTestCommand.format(&[command_name]))
+        },
     }
+    body
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::{
         language::{ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults},
         operations::add_tests::AddTestsStatus,
-        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        slack::ui_lib::{
+            syntax_view::SlackTestSyntaxMode,
+            test_support::{
+                assert_slack_view_snapshot, assert_slack_view_snapshot_with_limits,
+                SlackBlockLimits, SnapshotMode,
+            },
+        },
+        tests_data::estimate::RoswaalSuiteRuntimeOverrun,
     };
 
     use super::AddTestsView;
@@ -292,17 +523,55 @@ Requirement 1: Chungus
 ",
             ),
         ];
-        let results = RoswaalTestCompilationResults::compile(&tests, &vec![]);
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
         assert_slack_view_snapshot(
             "add-tests-success-no-compilation-errors",
             &AddTestsView::new(AddTestsStatus::Success {
                 results,
                 should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
             }),
             SnapshotMode::Comparing,
         )
     }
 
+    #[test]
+    fn success_highlighted_syntax_snapshot() {
+        let tests = vec![
+            RoswaalTestSyntax::from(
+                "\
+New Test: Big Chungus
+Abstract: Chungus goes big & returns
+Step 1: Big (retries: 2)
+Requirement 1: Chungus
+",
+            ),
+            RoswaalTestSyntax::from(
+                "\
+New Test: Big Chungus II
+Step 1: Big
+",
+            ),
+        ];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        assert_slack_view_snapshot(
+            "add-tests-success-highlighted-syntax",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            })
+            .with_syntax_mode(SlackTestSyntaxMode::Highlighted),
+            SnapshotMode::Comparing,
+        )
+    }
+
     #[test]
     fn success_all_compile_errors_snapshot() {
         let tests = vec![
@@ -370,12 +639,72 @@ Fake Command: This is a fake command!
 ",
             ),
         ];
-        let results = RoswaalTestCompilationResults::compile(&tests, &vec![]);
-        assert_slack_view_snapshot(
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        // NB: The errors of this many tests render more blocks than slack allows in a message, so
+        // the snapshot is only checked against the larger limits of a modal.
+        assert_slack_view_snapshot_with_limits(
             "add-tests-success-all-compilation-errors",
             &AddTestsView::new(AddTestsStatus::Success {
                 results,
                 should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+            SlackBlockLimits::SURFACE,
+        )
+    }
+
+    #[test]
+    fn success_wait_duration_compile_errors_snapshot() {
+        let tests = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Big Chungus Waits
+Step 1: Big
+Requirement 1: Chungus
+Wait: A really long time
+Wait: 0 seconds
+Wait: 1 hour
+Wait: 6 minutes
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        assert_slack_view_snapshot(
+            "add-tests-success-wait-duration-compilation-errors",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_misspelled_command_compile_errors_snapshot() {
+        let tests = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Big Chungus Typos
+Steb 1: Big
+Requirment 1: Chungus
+Set Locaton: Antarctica
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        assert_slack_view_snapshot(
+            "add-tests-success-misspelled-command-compilation-errors",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
             }),
             SnapshotMode::Comparing,
         )
@@ -398,12 +727,81 @@ Requirement 1: Thhing
 ",
             ),
         ];
-        let results = RoswaalTestCompilationResults::compile(&tests, &vec![]);
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
         assert_slack_view_snapshot(
             "add-tests-success-mixed-compilation-results",
             &AddTestsView::new(AddTestsStatus::Success {
                 results,
                 should_warn_undeleted_branch: true,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_compilation_warnings_snapshot() {
+        let tests = vec![
+            RoswaalTestSyntax::from(
+                "\
+New Test: Big Chungus
+Abstract: Big
+Abstract: Chungus
+Step 1: Big Chungus walks all the way across the entire length of the circus while juggling several flaming torches
+Requirement 1: Chungus
+Set Location: new york
+",
+            ),
+            RoswaalTestSyntax::from(
+                "\
+New Test: Big Chungus II
+Step 1: Big
+Requirement 1: Chungus
+",
+            ),
+        ];
+        let location_names = vec!["New York".parse().unwrap()];
+        let results = RoswaalTestCompilationResults::compile(tests, &location_names);
+        assert_slack_view_snapshot(
+            "add-tests-success-compilation-warnings",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_action_name_collisions_snapshot() {
+        let tests = vec![
+            RoswaalTestSyntax::from(
+                "\
+New Test: Tap the button twice
+Step 1: Johnny taps the button
+Step 2: Johnny taps the button again
+Requirement 1: Tap the button
+Requirement 2: Tap the button!
+",
+            ),
+        ];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        assert_slack_view_snapshot(
+            "add-tests-success-action-name-collisions",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
             }),
             SnapshotMode::Comparing,
         )
@@ -418,17 +816,116 @@ Step 1: Big
 Requirement 1: Chungus
 ",
         )];
-        let results = RoswaalTestCompilationResults::compile(&tests, &vec![]);
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
         assert_slack_view_snapshot(
             "add-tests-success-warn-undeleted-branch-errors",
             &AddTestsView::new(AddTestsStatus::Success {
                 results,
                 should_warn_undeleted_branch: true,
+                branch_name: None,
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_close_branch_action_snapshot() {
+        let tests = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Big Chungus
+Step 1: Big
+Requirement 1: Chungus
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        let branch_name = serde_json::from_str("\"roswaal-add-tests-0123456789\"").unwrap();
+        assert_slack_view_snapshot(
+            "add-tests-success-close-branch-action",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: Some(branch_name),
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_name_conflicts_snapshot() {
+        let tests = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Big Chungus
+Step 1: Big
+Requirement 1: Chungus
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        let branch_name = serde_json::from_str("\"roswaal-add-tests-0123456789\"").unwrap();
+        assert_slack_view_snapshot(
+            "add-tests-success-name-conflicts",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: Some(branch_name),
+                target_edits: vec![],
+                name_conflicts: vec!["Big Chungus II".to_string(), "Big Chungus III".to_string()],
+                suite_runtime_overrun: None,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_suite_runtime_over_budget_snapshot() {
+        let tests = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Big Chungus
+Step 1: Big
+Requirement 1: Chungus
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        let branch_name = serde_json::from_str("\"roswaal-add-tests-0123456789\"").unwrap();
+        let overrun = RoswaalSuiteRuntimeOverrun::new(
+            Duration::from_secs(65 * 60 + 30),
+            Duration::from_secs(60 * 60),
+        );
+        assert_slack_view_snapshot(
+            "add-tests-success-suite-runtime-over-budget",
+            &AddTestsView::new(AddTestsStatus::Success {
+                results,
+                should_warn_undeleted_branch: false,
+                branch_name: Some(branch_name),
+                target_edits: vec![],
+                name_conflicts: vec![],
+                suite_runtime_overrun: Some(overrun),
             }),
             SnapshotMode::Comparing,
         )
     }
 
+    #[test]
+    fn merge_conflict_recompile_action_snapshot() {
+        let tests_str = "\
+```
+New Test: Big Chungus
+Step 1: Big
+Requirement 1: Chungus
+```
+";
+        assert_slack_view_snapshot(
+            "add-tests-merge-conflict-recompile-action",
+            &AddTestsView::new(AddTestsStatus::MergeConflict).recompilable_from(tests_str),
+            SnapshotMode::Comparing,
+        )
+    }
+
     #[test]
     fn no_tests_found_snapshot() {
         assert_slack_view_snapshot(
@@ -0,0 +1,105 @@
+use std::borrow::Borrow;
+
+use crate::operations::assign_failure::AssignFailureStatus;
+
+use super::ui_lib::{
+    block_kit_views::{SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for showing who was assigned to triage the failure of a test.
+pub struct AssignFailureView {
+    status: AssignFailureStatus,
+}
+
+impl AssignFailureView {
+    pub fn new(status: AssignFailureStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for AssignFailureView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Assign Failure").flat_chain_block(self.status_view())
+    }
+}
+
+impl AssignFailureView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            AssignFailureStatus::Success {
+                test_name,
+                assignee_slack_user_id,
+            } => SlackSection::from_markdown(&format!(
+                "🧑‍🔧 <@{}> is now triaging the failure of *{}*! The assignment will be cleared once the test passes agaaaaaain.",
+                assignee_slack_user_id, test_name
+            )),
+            AssignFailureStatus::MissingTestName => SlackSection::from_markdown(
+                "⚠️ Name the test before mentioning the user who should triage it, like `/assign-failure Join an event @roswaal`!",
+            ),
+            AssignFailureStatus::InvalidUser(mention) => SlackSection::from_markdown(&format!(
+                "⚠️ *{}* is not a slack user. End the command with a mention of the user who should triage the failure!",
+                mention
+            )),
+            AssignFailureStatus::UnknownTest(test_name) => SlackSection::from_markdown(&format!(
+                "⚠️ No merged test named *{}* was found. Use `/view-tests` to check the names of the tests!",
+                test_name
+            )),
+            AssignFailureStatus::TestNotFailing(test_name) => {
+                SlackSection::from_markdown(&format!(
+                    "✅ *{}* is not failing, so there is nothing to triaaaaage!",
+                    test_name
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::assign_failure::AssignFailureStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::AssignFailureView;
+
+    #[test]
+    fn success_snapshot() {
+        let status = AssignFailureStatus::Success {
+            test_name: "Join an event".to_string(),
+            assignee_slack_user_id: "U04K0DX9HC6".to_string(),
+        };
+        assert_slack_view_snapshot(
+            "assign-failure-success",
+            &AssignFailureView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn error_snapshots() {
+        let statuses = [
+            ("missing-test-name", AssignFailureStatus::MissingTestName),
+            (
+                "invalid-user",
+                AssignFailureStatus::InvalidUser("@roswaal".to_string()),
+            ),
+            (
+                "unknown-test",
+                AssignFailureStatus::UnknownTest("Join an event".to_string()),
+            ),
+            (
+                "test-not-failing",
+                AssignFailureStatus::TestNotFailing("Join an event".to_string()),
+            ),
+        ];
+        for (name, status) in statuses {
+            assert_slack_view_snapshot(
+                &format!("assign-failure-{}", name),
+                &AssignFailureView::new(status),
+                SnapshotMode::Comparing,
+            )
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::borrow::Borrow;
+
+use crate::{
+    audit::{
+        entry::{RoswaalAuditStatus, GITHUB_AUDIT_USER, HTTP_AUDIT_USER},
+        storage::RoswaalStoredAuditLogEntry,
+    },
+    operations::view_audit_log::ViewAuditLogStatus,
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    for_each_view::ForEachView,
+    slack_view::SlackView,
+};
+
+/// The maximum number of characters of the arguments of an entry that are shown.
+const MAX_ARGUMENTS_PREVIEW_LENGTH: usize = 100;
+
+/// A view for showing the most recent entries in the audit log.
+pub struct AuditLogView {
+    status: ViewAuditLogStatus,
+}
+
+impl AuditLogView {
+    pub fn new(status: ViewAuditLogStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for AuditLogView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Audit Log").flat_chain_block(self.status_view())
+    }
+}
+
+impl AuditLogView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            ViewAuditLogStatus::Success(entries) => SlackSection::from_markdown(&format!(
+                "🕵️ *Here are the {} most recent operaaaaaations!*",
+                entries.len()
+            ))
+            .flat_chain_block(ForEachView::new(entries.iter(), |entry| {
+                SlackDivider.flat_chain_block(Self::entry_view(entry))
+            }))
+            .erase_to_any_view(),
+            ViewAuditLogStatus::NoEntries => SlackSection::from_markdown(
+                "✅ Nothing has been recorded in the audit log yeeeeeet!",
+            )
+            .erase_to_any_view(),
+        }
+    }
+
+    fn entry_view(stored_entry: &RoswaalStoredAuditLogEntry) -> impl SlackView {
+        let entry = stored_entry.entry();
+        let emoji = match entry.status() {
+            RoswaalAuditStatus::Succeeded => "🟢",
+            RoswaalAuditStatus::Failed { message: _ } => "🔴",
+        };
+        let user = match entry.user() {
+            HTTP_AUDIT_USER | GITHUB_AUDIT_USER => format!("*{}*", entry.user()),
+            user => format!("<@{}>", user),
+        };
+        let mut body = format!(
+            "{} `{}` by {} _({})_",
+            emoji,
+            entry.operation(),
+            user,
+            stored_entry.creation_date().format("%Y-%m-%d %H:%M:%S")
+        );
+        let arguments = arguments_preview(entry.arguments());
+        if !arguments.is_empty() {
+            body.push_str(&format!("\n> {}", arguments));
+        }
+        if let Some(branch_name) = entry.branch_name() {
+            body.push_str(&format!("\n🌿 `{}`", branch_name.to_string()));
+        }
+        if let RoswaalAuditStatus::Failed { message } = entry.status() {
+            body.push_str(&format!("\n_Error: {}_", message));
+        }
+        SlackSection::from_markdown(&body)
+    }
+}
+
+fn arguments_preview(arguments: &str) -> String {
+    // NB: Unbalanced code fences in a truncated preview would swallow the rest of the message.
+    let arguments = arguments
+        .replace("```", "")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    if arguments.chars().count() > MAX_ARGUMENTS_PREVIEW_LENGTH {
+        let preview = arguments
+            .chars()
+            .take(MAX_ARGUMENTS_PREVIEW_LENGTH)
+            .collect::<String>();
+        format!("{}…", preview)
+    } else {
+        arguments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        audit::{
+            entry::{RoswaalAuditLogEntry, RoswaalAuditStatus, HTTP_AUDIT_USER},
+            storage::RoswaalStoredAuditLogEntry,
+        },
+        operations::view_audit_log::ViewAuditLogStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::AuditLogView;
+
+    #[test]
+    fn success_snapshot() {
+        let date = Utc.with_ymd_and_hms(2024, 6, 1, 12, 30, 0).unwrap();
+        let branch_name = serde_json::from_str("\"roswaal-add-tests-0123456789\"").unwrap();
+        let tests_str = "\
+```
+New Test: Justin leaves an event after joining it because he realizes that he has other plans for the evening
+Step 1: Justin joins the event
+```";
+        let entries = vec![
+            RoswaalAuditLogEntry::new("U01234567", "/add-tests", tests_str)
+                .with_branch_name(Some(&branch_name)),
+            RoswaalAuditLogEntry::new(
+                HTTP_AUDIT_USER,
+                "POST /merge",
+                "roswaal-add-tests-0123456789",
+            )
+            .with_status(RoswaalAuditStatus::Failed {
+                message: "database is locked".to_string(),
+            }),
+            RoswaalAuditLogEntry::new("U01234567", "/view-locations", ""),
+        ]
+        .into_iter()
+        .map(|entry| RoswaalStoredAuditLogEntry::new(entry, date))
+        .collect();
+        assert_slack_view_snapshot(
+            "audit-log-success",
+            &AuditLogView::new(ViewAuditLogStatus::Success(entries)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_entries_snapshot() {
+        assert_slack_view_snapshot(
+            "audit-log-no-entries",
+            &AuditLogView::new(ViewAuditLogStatus::NoEntries),
+            SnapshotMode::Comparing,
+        )
+    }
+}
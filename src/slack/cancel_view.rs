@@ -0,0 +1,84 @@
+use super::{
+    command::RoswaalSlackCommand,
+    ui_lib::{block_kit_views::SlackSection, slack_view::SlackView},
+};
+
+/// A view for the response to `/roswaal-cancel` with the command that was cancelled, if any.
+pub struct CancelView {
+    cancelled_command: Option<RoswaalSlackCommand>,
+}
+
+impl CancelView {
+    pub fn new(cancelled_command: Option<RoswaalSlackCommand>) -> Self {
+        Self { cancelled_command }
+    }
+}
+
+impl SlackView for CancelView {
+    fn slack_body(&self) -> impl SlackView {
+        let text = match self.cancelled_command {
+            Some(command) => format!(
+                "🛑 Your latest `{}` is being cancelled. I'll reply in its thread once it has stopped!",
+                command
+            ),
+            None => {
+                "🤷 You have no commands running in this channel to caaaaaaancel.".to_string()
+            }
+        };
+        SlackSection::from_markdown(&text)
+    }
+}
+
+/// A view for indicating that the handling of a command was stopped by `/roswaal-cancel`.
+pub struct OperationCancelledView {
+    command: RoswaalSlackCommand,
+}
+
+impl OperationCancelledView {
+    pub fn new(command: RoswaalSlackCommand) -> Self {
+        Self { command }
+    }
+}
+
+impl SlackView for OperationCancelledView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown(&format!(
+            "🛑 *This `{}` was cancelled.* Any unfinished changes to the repo will be cleaned up by the next command that edits it.",
+            self.command
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::*;
+
+    #[test]
+    fn cancelled_snapshot() {
+        assert_slack_view_snapshot(
+            "cancel-cancelled",
+            &CancelView::new(Some(RoswaalSlackCommand::AddTests)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn nothing_to_cancel_snapshot() {
+        assert_slack_view_snapshot(
+            "cancel-nothing-to-cancel",
+            &CancelView::new(None),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn operation_cancelled_snapshot() {
+        assert_slack_view_snapshot(
+            "cancel-operation-cancelled",
+            &OperationCancelledView::new(RoswaalSlackCommand::AddTests),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,478 @@
+use std::fmt::Display;
+
+use strum_macros::EnumIter;
+
+use crate::utils::config::{RoswaalConfig, RoswaalSlackLanguage};
+
+/// The ids of the messages that are localized to the language of the slack workspace.
+///
+/// Messages can contain `{}` placeholders that are filled in order by `RoswaalMessageId::format`.
+/// Every translation of a message must contain the same number of placeholders.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, EnumIter)]
+pub enum RoswaalMessageId {
+    NoTestsFound,
+    NextSteps,
+    StackTraceTitle,
+    AddTestsHeader,
+    AddTestsApprovePullRequest,
+    AddTestsCloseBranchButton,
+    AddTestsRecompileButton,
+    AddTestsCompiledSuccessfully,
+    AddTestsCompiledWithWarnings,
+    AddTestsNameConflicts,
+    AddTestsNameConflictsHint,
+    AddTestsSuiteRuntimeOverBudget,
+    AddTestsNotCompiled,
+    AddTestsFailureTitle,
+    CompilationWarningLine,
+    CompilationErrorLine,
+    WarningLongStepDescription,
+    WarningAbstractOverwritten,
+    WarningSuiteOverwritten,
+    WarningLocationNameCasingMismatch,
+    WarningStepLabelRenumbered,
+    WarningActionNameCollision,
+    ErrorNoTestName,
+    ErrorNoTestSteps,
+    ErrorNoCommandDescription,
+    ErrorNoStepRequirement,
+    ErrorNoRequirementStep,
+    ErrorNoUsingVerification,
+    ErrorUnknownLocationName,
+    ErrorInvalidLocationName,
+    ErrorInvalidWaitDuration,
+    ErrorWaitDurationTooShort,
+    ErrorWaitDurationTooLong,
+    ErrorUnknownPlatform,
+    ErrorInvalidVariable,
+    ErrorUndeclaredVariable,
+    ErrorInvalidCommandName,
+    ErrorInvalidCommandNameSuggestion,
+    ErrorDuplicateStep,
+    ErrorDuplicateRequirement,
+    ErrorDuplicateVerification,
+    ErrorDuplicateUsing,
+    ErrorTestNameAlreadyDeclared,
+    ErrorUnknownRequiredTest,
+    ErrorCircularTestDependency,
+    ErrorPlannedTestCommand,
+    SearchTestsHeader,
+    SearchTestsStatusCountOne,
+    SearchTestsStatusCountMany,
+    SearchTestsLastRan,
+    SearchTestsNeverRan,
+    SearchTestsErrorMessageTitle,
+    SearchTestsTotalRuntime,
+    SearchTestsSlowestStep,
+    SearchTestsSuiteRuntime,
+    SearchTestsScreenshot,
+    SearchTestsPlanned,
+    SearchTestsAddedBy,
+    SearchTestsFailureAssignee,
+    CommandBeforeLaunch,
+    CommandSetLocation,
+    CommandWait,
+    CommandRequiresTest,
+    CommandUnknown,
+    StatusPassing,
+    StatusFailing,
+    StatusIdle,
+    ErrorViewHeader,
+    ErrorViewTitle,
+    ErrorViewStackTraceTruncated,
+    FailedToOpenPullRequestTitle,
+    FailedToOpenPullRequestBody,
+    RemoteUnavailableTitle,
+    RemoteUnavailableBody,
+    GitOperationTimedOutTitle,
+    GitOperationTimedOutBody,
+    MessageTruncated,
+    MessageTruncatedReportLink,
+    MessageTruncatedReportPath,
+}
+
+impl RoswaalMessageId {
+    /// Returns the text of this message in the slack language of the current config.
+    pub fn text(&self) -> &'static str {
+        self.text_in(RoswaalConfig::current().slack_language())
+    }
+
+    /// Returns the text of this message with its placeholders replaced by `args` in the slack
+    /// language of the current config.
+    pub fn format(&self, args: &[&dyn Display]) -> String {
+        self.format_in(RoswaalConfig::current().slack_language(), args)
+    }
+
+    pub fn text_in(&self, language: RoswaalSlackLanguage) -> &'static str {
+        match language {
+            RoswaalSlackLanguage::English => self.english_text(),
+            RoswaalSlackLanguage::Japanese => self.japanese_text(),
+        }
+    }
+
+    pub fn format_in(&self, language: RoswaalSlackLanguage, args: &[&dyn Display]) -> String {
+        let mut parts = self.text_in(language).split("{}");
+        let mut formatted = parts.next().unwrap_or_default().to_string();
+        for (index, part) in parts.enumerate() {
+            if let Some(arg) = args.get(index) {
+                formatted.push_str(&arg.to_string());
+            }
+            formatted.push_str(part);
+        }
+        formatted
+    }
+}
+
+impl RoswaalMessageId {
+    fn english_text(&self) -> &'static str {
+        match self {
+            Self::NoTestsFound => "🔴 No tests were fooooooound.",
+            Self::NextSteps => "Next Steps",
+            Self::StackTraceTitle => "⚠️ *Stack Trace*",
+            Self::AddTestsHeader => "Add Tests",
+            Self::AddTestsApprovePullRequest => {
+                "Approve the PR found in <#{}> to finish the adding the teeeeeeeests!"
+            }
+            Self::AddTestsCloseBranchButton => "Close Branch",
+            Self::AddTestsRecompileButton => "Re-run Compile",
+            Self::AddTestsCompiledSuccessfully => {
+                "✅ *The following tests were compiled succeeeeeeeeessfully!*"
+            }
+            Self::AddTestsCompiledWithWarnings => {
+                "🟡 *The following tests compiled with waaaaaarnings. They were still added, but you may want to fix them!*"
+            }
+            Self::AddTestsNameConflicts => {
+                "🟡 *The following tests were not added because tests with the same names already exiiiiiist!*"
+            }
+            Self::AddTestsNameConflictsHint => {
+                "_Use `/edit-test` to change an existing test, or add `{}` outside of the code blocks to replace iiiiiit._"
+            }
+            Self::AddTestsSuiteRuntimeOverBudget => {
+                "⏱️ *Once these tests are merged, the suite is estimated to take {}, which is ooooover its time budget of {}!* Consider trimming or deprecating slow tests."
+            }
+            Self::AddTestsNotCompiled => {
+                "⚠️ *The following tests did not compile succeeeeeessfully. They are only listed by the number based on the compilation oooooooooorder!*"
+            }
+            Self::AddTestsFailureTitle => "❗️ *Test {}*",
+            Self::CompilationWarningLine => "{} _(Line {})_",
+            Self::CompilationErrorLine => "{}\n*Line: {}*",
+            Self::WarningLongStepDescription => {
+                "\"{}\" has a description that is {} characters looooooong. Keep it under {} characters so that the generated code stays readable."
+            }
+            Self::WarningAbstractOverwritten => {
+                "This test has multiple \"Abstract\" commaaaaaands. Only the last one was used."
+            }
+            Self::WarningSuiteOverwritten => {
+                "This test has multiple \"Suite\" commaaaaaands. Only the last one was used."
+            }
+            Self::WarningLocationNameCasingMismatch => {
+                "\"{}\" was matched to the location \"{}\". Use the exact naaaaaame as it appears in `/view-locations`."
+            }
+            Self::WarningStepLabelRenumbered => {
+                "\"{}\" was out of ordeeeeer, so it was renumbered to \"{}\"."
+            }
+            Self::WarningActionNameCollision => {
+                "\"{}\" generates the same action name as \"{}\", so its action was renamed to `{}`. Reword the requirement if you want a clearer naaaaaame."
+            }
+            Self::ErrorNoTestName => "No test name was speeeeeeecified.",
+            Self::ErrorNoTestSteps => "No test steps were speeeeeeeecified.",
+            Self::ErrorNoCommandDescription => "No command description was specified for \"{}\".",
+            Self::ErrorNoStepRequirement => "\"{}: {}\" has no matching requiremeeeeeeeeeeent.",
+            Self::ErrorNoRequirementStep => "\"{}: {}\" has no matching steeeeeeeeeeep.",
+            Self::ErrorNoUsingVerification => "\"{}: {}\" has no matching verificaaaaaaation.",
+            Self::ErrorUnknownLocationName => {
+                "\"{}\" is an unknown location naaaaaaaame. Add it using the `/add-locations` commaaaaaand!"
+            }
+            Self::ErrorInvalidLocationName => {
+                "\"{}\" was in an invalid foooooormat. Make sure you don't include any special characters in the location naaaaaaaaaame."
+            }
+            Self::ErrorInvalidWaitDuration => {
+                "\"{}\" is not a valid duraaaaaation. Write it as an amount followed by a unit, like \"5 seconds\" or \"500ms\"."
+            }
+            Self::ErrorWaitDurationTooShort => {
+                "\"{}\" is too shoooooort. Wait for at least 1 millisecond."
+            }
+            Self::ErrorWaitDurationTooLong => "\"{}\" is too looooooong. Wait for at most 5 minutes.",
+            Self::ErrorUnknownPlatform => {
+                "\"{}\" is not a platform I knoooooow. Use \"ios\" or \"android\"."
+            }
+            Self::ErrorInvalidVariable => {
+                "\"{}\" is not a valid variaaaaable. Declare each variable like \"eventName = Pickup Basketball\"."
+            }
+            Self::ErrorUndeclaredVariable => {
+                "The variable \"{}\" is used in a placeholder, but it was never declaaaaared! Add it to the \"Variables\" command."
+            }
+            Self::ErrorInvalidCommandName => {
+                "\"{}\" has valid command syyyyyntax, but it is not a known comaaaaand."
+            }
+            Self::ErrorInvalidCommandNameSuggestion => "Did you mean '{}'?",
+            Self::ErrorDuplicateStep => {
+                "Mutliple steps named \"{}\" were fooooound! Make sure there is only ooooone!"
+            }
+            Self::ErrorDuplicateRequirement => {
+                "Mutliple requirements named \"{}\" were fooooound! Make sure there is only ooooone!"
+            }
+            Self::ErrorDuplicateVerification => {
+                "Mutliple verifications named \"{}\" were fooooound! Make sure there is only ooooone!"
+            }
+            Self::ErrorDuplicateUsing => {
+                "Mutliple usings named \"{}\" were fooooound! Make sure there is only ooooone!"
+            }
+            Self::ErrorTestNameAlreadyDeclared => {
+                "This test has multiple \"New Test\" commaaaaaaands. Make sure there is only oooooooone!"
+            }
+            Self::ErrorUnknownRequiredTest => {
+                "\"{}\" is not a test I knoooooow. Use `/view-tests` to find the naaaaaame of the test to require."
+            }
+            Self::ErrorCircularTestDependency => {
+                "\"{}\" already requires this test, so requiring it would loop foreeeeeever!"
+            }
+            Self::ErrorPlannedTestCommand => {
+                "\"{}\" can't be used in a planned teeeeest. Change \"Planned Test\" to \"New Test\" once the steps are ready!"
+            }
+            Self::SearchTestsHeader => "Test Progress",
+            Self::SearchTestsStatusCountOne => "{} *{} Test {}*",
+            Self::SearchTestsStatusCountMany => "{} *{} Tests {}*",
+            Self::SearchTestsLastRan => "_Last Ran: {}_",
+            Self::SearchTestsNeverRan => "_This test has never been run._",
+            Self::SearchTestsErrorMessageTitle => "⚠️ *Error Message*",
+            Self::SearchTestsTotalRuntime => "⏱️ *Total Runtime:* {}",
+            Self::SearchTestsSlowestStep => "🐢 *Slowest Step:* {} ({})",
+            Self::SearchTestsSuiteRuntime => {
+                "⏱️ *Estimated Suite Runtime:* {} _({} measured, {} estimated)_"
+            }
+            Self::SearchTestsScreenshot => "📷 Screenshot",
+            Self::SearchTestsPlanned => "📋 _Planned_",
+            Self::SearchTestsAddedBy => "_Added by <@{}>_",
+            Self::SearchTestsFailureAssignee => "🧑‍🔧 *Assigned To:* <@{}>",
+            Self::CommandBeforeLaunch => "Before Launch",
+            Self::CommandSetLocation => "Set Location",
+            Self::CommandWait => "Wait",
+            Self::CommandRequiresTest => "Requires Test",
+            Self::CommandUnknown => "Unknown Step",
+            Self::StatusPassing => "Passing",
+            Self::StatusFailing => "Failing",
+            Self::StatusIdle => "Idle",
+            Self::ErrorViewHeader => "An Error Occurred",
+            Self::ErrorViewTitle => "🔴 *Error*",
+            Self::ErrorViewStackTraceTruncated => {
+                "🟡 _Stack Trace truncated due to {} character limit..._"
+            }
+            Self::FailedToOpenPullRequestTitle => "🔴 *Error: Failed to open Pull Request*",
+            Self::FailedToOpenPullRequestBody => {
+                "_The pull request could not be opeeeeened. Check the logs for deeeeeeetails._"
+            }
+            Self::RemoteUnavailableTitle => "🟡 *Error: Remote Unavailable*",
+            Self::RemoteUnavailableBody => {
+                "_GitHub could not be reaaaaached after several attempts. Try the command again in a bit._"
+            }
+            Self::GitOperationTimedOutTitle => "🟡 *Error: Git Operation Timed Out*",
+            Self::GitOperationTimedOutBody => {
+                "_`{}` was still running after {} seconds, so it was cancelled. The remote may be unreachaaaaable, try the command again in a bit._"
+            }
+            Self::MessageTruncated => {
+                "✂️ *{} more blocks* did not fit in this message, so they were cut ooooooff."
+            }
+            Self::MessageTruncatedReportLink => "<{}|View the full report>",
+            Self::MessageTruncatedReportPath => "The full report is at `/reports/{}`.",
+        }
+    }
+
+    fn japanese_text(&self) -> &'static str {
+        match self {
+            Self::NoTestsFound => "🔴 テストが見つかりませんでしたよぉ〜。",
+            Self::NextSteps => "次のステップ",
+            Self::StackTraceTitle => "⚠️ *スタックトレース*",
+            Self::AddTestsHeader => "テストの追加",
+            Self::AddTestsApprovePullRequest => {
+                "<#{}> にあるPRを承認して、テストの追加を完了させてくださいねぇ〜！"
+            }
+            Self::AddTestsCloseBranchButton => "ブランチを閉じる",
+            Self::AddTestsRecompileButton => "再コンパイル",
+            Self::AddTestsCompiledSuccessfully => "✅ *以下のテストのコンパイルに成功しましたぁ〜！*",
+            Self::AddTestsCompiledWithWarnings => {
+                "🟡 *以下のテストは警告付きでコンパイルされましたぁ〜。追加はされましたが、直したほうがいいかもしれませんねぇ！*"
+            }
+            Self::AddTestsNameConflicts => {
+                "🟡 *同じ名前のテストがすでに存在するため、以下のテストは追加されませんでしたぁ〜！*"
+            }
+            Self::AddTestsNameConflictsHint => {
+                "_既存のテストを変更するには `/edit-test` を使うか、コードブロックの外に `{}` を追加して置き換えてくださいねぇ〜。_"
+            }
+            Self::AddTestsSuiteRuntimeOverBudget => {
+                "⏱️ *これらのテストがマージされると、スイートの推定実行時間は {} になり、時間予算の {} を超えてしまいますよぉ〜！* 遅いテストを減らすか非推奨にすることを検討してくださいねぇ。"
+            }
+            Self::AddTestsNotCompiled => {
+                "⚠️ *以下のテストはコンパイルに失敗しましたぁ〜。コンパイルされた順番の番号でのみ表示されていますよぉ！*"
+            }
+            Self::AddTestsFailureTitle => "❗️ *テスト {}*",
+            Self::CompilationWarningLine => "{} _({}行目)_",
+            Self::CompilationErrorLine => "{}\n*{}行目*",
+            Self::WarningLongStepDescription => {
+                "\"{}\" の説明は {} 文字もありますねぇ〜。生成されるコードを読みやすく保つため、{} 文字未満にしてください。"
+            }
+            Self::WarningAbstractOverwritten => {
+                "このテストには \"Abstract\" コマンドが複数ありますねぇ〜。最後のものだけが使われました。"
+            }
+            Self::WarningSuiteOverwritten => {
+                "このテストには \"Suite\" コマンドが複数ありますねぇ〜。最後のものだけが使われました。"
+            }
+            Self::WarningLocationNameCasingMismatch => {
+                "\"{}\" は場所 \"{}\" として扱われました。`/view-locations` に表示される名前をそのまま使ってくださいねぇ〜。"
+            }
+            Self::WarningStepLabelRenumbered => {
+                "\"{}\" の番号が順番通りではなかったので、\"{}\" に振り直しましたよぉ〜。"
+            }
+            Self::WarningActionNameCollision => {
+                "\"{}\" は \"{}\" と同じアクション名になるので、アクション名を `{}` に変えましたよぉ〜。わかりやすい名前にしたい場合は要件を書き直してくださいねぇ。"
+            }
+            Self::ErrorNoTestName => "テスト名が指定されていませんよぉ〜。",
+            Self::ErrorNoTestSteps => "テストのステップが指定されていませんよぉ〜。",
+            Self::ErrorNoCommandDescription => "\"{}\" のコマンドの説明が指定されていません。",
+            Self::ErrorNoStepRequirement => "\"{}: {}\" に対応する要件がありませんよぉ〜。",
+            Self::ErrorNoRequirementStep => "\"{}: {}\" に対応するステップがありませんよぉ〜。",
+            Self::ErrorNoUsingVerification => "\"{}: {}\" に対応する検証がありませんよぉ〜。",
+            Self::ErrorUnknownLocationName => {
+                "\"{}\" は知らない場所の名前ですねぇ〜。`/add-locations` コマンドで追加してくださいねぇ！"
+            }
+            Self::ErrorInvalidLocationName => {
+                "\"{}\" の形式が無効ですよぉ〜。場所の名前に特殊文字を含めないでくださいねぇ。"
+            }
+            Self::ErrorInvalidWaitDuration => {
+                "\"{}\" は有効な時間ではありませんよぉ〜。\"5 seconds\" や \"500ms\" のように、数の後に単位を書いてください。"
+            }
+            Self::ErrorWaitDurationTooShort => {
+                "\"{}\" は短すぎますねぇ〜。少なくとも1ミリ秒は待ってください。"
+            }
+            Self::ErrorWaitDurationTooLong => {
+                "\"{}\" は長すぎますねぇ〜。待つのは最大5分までにしてください。"
+            }
+            Self::ErrorUnknownPlatform => {
+                "\"{}\" は私の知らないプラットフォームですねぇ〜。\"ios\" か \"android\" を使ってください。"
+            }
+            Self::ErrorInvalidVariable => {
+                "\"{}\" は正しい変数ではありませんねぇ〜。\"eventName = Pickup Basketball\" のように宣言してください。"
+            }
+            Self::ErrorUndeclaredVariable => {
+                "変数 \"{}\" がプレースホルダーで使われていますが、宣言されていませんねぇ〜！\"Variables\" コマンドに追加してください。"
+            }
+            Self::ErrorInvalidCommandName => {
+                "\"{}\" はコマンドの構文としては正しいですが、知らないコマンドですねぇ〜。"
+            }
+            Self::ErrorInvalidCommandNameSuggestion => "もしかして '{}' のことですかぁ〜？",
+            Self::ErrorDuplicateStep => {
+                "\"{}\" という名前のステップが複数見つかりましたぁ〜！1つだけにしてくださいねぇ！"
+            }
+            Self::ErrorDuplicateRequirement => {
+                "\"{}\" という名前の要件が複数見つかりましたぁ〜！1つだけにしてくださいねぇ！"
+            }
+            Self::ErrorDuplicateVerification => {
+                "\"{}\" という名前の検証が複数見つかりましたぁ〜！1つだけにしてくださいねぇ！"
+            }
+            Self::ErrorDuplicateUsing => {
+                "\"{}\" という名前の Using が複数見つかりましたぁ〜！1つだけにしてくださいねぇ！"
+            }
+            Self::ErrorTestNameAlreadyDeclared => {
+                "このテストには \"New Test\" コマンドが複数ありますねぇ〜。1つだけにしてくださいねぇ！"
+            }
+            Self::ErrorUnknownRequiredTest => {
+                "\"{}\" は私の知らないテストですねぇ〜。`/view-tests` で必要なテストの名前を探してください。"
+            }
+            Self::ErrorCircularTestDependency => {
+                "\"{}\" はすでにこのテストを必要としているので、必要とすると永遠にループしてしまいますよぉ〜！"
+            }
+            Self::ErrorPlannedTestCommand => {
+                "\"{}\" は計画中のテストでは使えませんよぉ〜。ステップの準備ができたら \"Planned Test\" を \"New Test\" に変えてくださいねぇ！"
+            }
+            Self::SearchTestsHeader => "テストの進捗",
+            Self::SearchTestsStatusCountOne | Self::SearchTestsStatusCountMany => {
+                "{} *{}件 {}*"
+            }
+            Self::SearchTestsLastRan => "_最終実行: {}_",
+            Self::SearchTestsNeverRan => "_このテストはまだ一度も実行されていませんよぉ〜。_",
+            Self::SearchTestsErrorMessageTitle => "⚠️ *エラーメッセージ*",
+            Self::SearchTestsTotalRuntime => "⏱️ *合計実行時間:* {}",
+            Self::SearchTestsSlowestStep => "🐢 *最も遅いステップ:* {} ({})",
+            Self::SearchTestsSuiteRuntime => {
+                "⏱️ *スイートの推定実行時間:* {} _(計測済み {}件、推定 {}件)_"
+            }
+            Self::SearchTestsScreenshot => "📷 スクリーンショット",
+            Self::SearchTestsPlanned => "📋 _計画中_",
+            Self::SearchTestsAddedBy => "_追加した人: <@{}>_",
+            Self::SearchTestsFailureAssignee => "🧑‍🔧 *担当者:* <@{}>",
+            Self::CommandBeforeLaunch => "起動前",
+            Self::CommandSetLocation => "場所の設定",
+            Self::CommandWait => "待機",
+            Self::CommandRequiresTest => "必要なテスト",
+            Self::CommandUnknown => "不明なステップ",
+            Self::StatusPassing => "成功",
+            Self::StatusFailing => "失敗",
+            Self::StatusIdle => "待機中",
+            Self::ErrorViewHeader => "エラーが発生しました",
+            Self::ErrorViewTitle => "🔴 *エラー*",
+            Self::ErrorViewStackTraceTruncated => {
+                "🟡 _{}文字の制限のため、スタックトレースは省略されました..._"
+            }
+            Self::FailedToOpenPullRequestTitle => "🔴 *エラー: プルリクエストを開けませんでした*",
+            Self::FailedToOpenPullRequestBody => {
+                "_プルリクエストを開けませんでしたぁ〜。詳しくはログを確認してくださいねぇ。_"
+            }
+            Self::RemoteUnavailableTitle => "🟡 *エラー: リモートに接続できません*",
+            Self::RemoteUnavailableBody => {
+                "_何度か試しましたが GitHub に接続できませんでしたぁ〜。少し待ってからもう一度コマンドを試してくださいねぇ。_"
+            }
+            Self::GitOperationTimedOutTitle => "🟡 *エラー: Git の操作がタイムアウトしました*",
+            Self::GitOperationTimedOutBody => {
+                "_`{}` が {} 秒経っても終わらなかったので中止しましたぁ〜。リモートに接続できないかもしれません。少し待ってからもう一度コマンドを試してくださいねぇ。_"
+            }
+            Self::MessageTruncated => {
+                "✂️ *残りの {} ブロック* はこのメッセージに収まらなかったので省略しましたよぉ〜。"
+            }
+            Self::MessageTruncatedReportLink => "<{}|レポート全文を見る>",
+            Self::MessageTruncatedReportPath => "レポート全文は `/reports/{}` にありますよぉ。",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn every_translation_has_the_same_placeholders() {
+        for id in RoswaalMessageId::iter() {
+            let english = id.text_in(RoswaalSlackLanguage::English);
+            let japanese = id.text_in(RoswaalSlackLanguage::Japanese);
+            assert_eq!(
+                english.matches("{}").count(),
+                japanese.matches("{}").count(),
+                "{:?} has mismatched placeholders.",
+                id
+            )
+        }
+    }
+
+    #[test]
+    fn formats_placeholders_in_order() {
+        let text = RoswaalMessageId::ErrorNoStepRequirement.format_in(
+            RoswaalSlackLanguage::English,
+            &[&"Step 1", &"Join the event"],
+        );
+        assert_eq!(
+            text,
+            "\"Step 1: Join the event\" has no matching requiremeeeeeeeeeeent."
+        );
+        let text = RoswaalMessageId::CompilationErrorLine
+            .format_in(RoswaalSlackLanguage::Japanese, &[&"Error", &3]);
+        assert_eq!(text, "Error\n*3行目*")
+    }
+
+    #[test]
+    fn leaves_placeholders_without_arguments_empty() {
+        let text =
+            RoswaalMessageId::AddTestsFailureTitle.format_in(RoswaalSlackLanguage::English, &[]);
+        assert_eq!(text, "❗️ *Test *")
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    git::branch_name::RoswaalOwnedBranchKind, operations::close_branch::CloseBranchStatus,
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for the result of closing a branch from slack.
+pub struct CloseBranchView<'a> {
+    status: CloseBranchStatus<'a>,
+}
+
+impl<'a> CloseBranchView<'a> {
+    pub fn new(status: CloseBranchStatus<'a>) -> Self {
+        Self { status }
+    }
+}
+
+impl<'a> SlackView for CloseBranchView<'a> {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Close Branch").flat_chain_block(self.status_view())
+    }
+}
+
+impl<'a> CloseBranchView<'a> {
+    fn status_view(&self) -> impl SlackView {
+        match &self.status {
+            CloseBranchStatus::Closed(kind) => {
+                let kind_name = match kind {
+                    RoswaalOwnedBranchKind::AddTests => "adding tests",
+                    RoswaalOwnedBranchKind::AddLocations => "adding locations",
+                    RoswaalOwnedBranchKind::RemoveTests => "removing tests",
+                    RoswaalOwnedBranchKind::RemoveLocations => "removing locations",
+                    RoswaalOwnedBranchKind::RemoveOrphanedTests => {
+                        "removing orphaned test directories"
+                    }
+                };
+                SlackSection::from_markdown(&format!(
+                    "✅ The branch for {} was closed succeeeeeessfully!",
+                    kind_name
+                ))
+                .flat_chain_block(SlackSection::from_markdown(
+                    "_Make sure to close its pull request on GitHub as weeeeeell._",
+                ))
+                .erase_to_any_view()
+            }
+            CloseBranchStatus::UnknownBranchKind(branch_name) => {
+                SlackSection::from_markdown(&format!(
+                    "🔴 `{}` is not a branch that I creeeeeeated.",
+                    branch_name.to_string()
+                ))
+                .erase_to_any_view()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::{RoswaalOwnedBranchKind, RoswaalOwnedGitBranchName},
+        operations::close_branch::CloseBranchStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::CloseBranchView;
+
+    #[test]
+    fn closed_snapshot() {
+        assert_slack_view_snapshot(
+            "close-branch-closed",
+            &CloseBranchView::new(CloseBranchStatus::Closed(RoswaalOwnedBranchKind::AddTests)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn unknown_branch_kind_snapshot() {
+        let branch_name: RoswaalOwnedGitBranchName =
+            serde_json::from_str("\"roswaal-blob-0123456789\"").unwrap();
+        assert_slack_view_snapshot(
+            "close-branch-unknown-branch-kind",
+            &CloseBranchView::new(CloseBranchStatus::UnknownBranchKind(&branch_name)),
+            SnapshotMode::Comparing,
+        )
+    }
+}
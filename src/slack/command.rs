@@ -7,18 +7,60 @@ use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
 
 /// The slack slash commands that this tool must respond to.
-#[derive(Debug, PartialEq, Eq, EnumString, EnumIter, IntoStaticStr, Display, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, EnumString, EnumIter, IntoStaticStr, Display, Clone, Copy)]
 pub enum RoswaalSlackCommand {
     #[strum(serialize = "/view-tests")]
     ViewTests,
     #[strum(serialize = "/add-tests")]
     AddTests,
+    #[strum(serialize = "/lint-tests")]
+    LintTests,
+    #[strum(serialize = "/my-failed-tests")]
+    MyFailedTests,
     #[strum(serialize = "/remove-tests")]
     RemoveTests,
+    #[strum(serialize = "/restore-test")]
+    RestoreTest,
+    #[strum(serialize = "/deprecate-tests")]
+    DeprecateTests,
+    #[strum(serialize = "/reset-test")]
+    ResetTestProgress,
+    #[strum(serialize = "/assign-failure")]
+    AssignFailure,
+    #[strum(serialize = "/edit-test")]
+    EditTest,
     #[strum(serialize = "/view-locations")]
     ViewLocations,
     #[strum(serialize = "/add-locations")]
     AddLocations,
+    #[strum(serialize = "/remove-locations")]
+    RemoveLocations,
+    #[strum(serialize = "/add-location-alias")]
+    AddLocationAlias,
+    #[strum(serialize = "/branches")]
+    ListBranches,
+    #[strum(serialize = "/open-pending-prs")]
+    OpenPendingPullRequests,
+    #[strum(serialize = "/audit")]
+    ViewAuditLog,
+    #[strum(serialize = "/roswaal-owners")]
+    EditRoster,
+    #[strum(serialize = "/roswaal-grant")]
+    GrantCommand,
+    #[strum(serialize = "/view-suites")]
+    ViewSuites,
+    #[strum(serialize = "/flaky-tests")]
+    FlakyTests,
+    #[strum(serialize = "/export-tests")]
+    ExportTests,
+    #[strum(serialize = "/repo-doctor")]
+    RepoDoctor,
+    #[strum(serialize = "/reconcile-tests")]
+    ReconcileTests,
+    #[strum(serialize = "/roswaal-cancel")]
+    Cancel,
+    #[strum(serialize = "/roswaal-help")]
+    Help,
 }
 
 impl RoswaalSlackCommand {
@@ -29,10 +71,143 @@ impl RoswaalSlackCommand {
     /// should return true from this method.
     pub fn is_long_running(&self) -> bool {
         match self {
-            Self::AddTests | Self::AddLocations | Self::RemoveTests => true,
+            Self::AddTests
+            | Self::AddLocations
+            | Self::RemoveTests
+            | Self::RestoreTest
+            | Self::EditTest
+            | Self::RemoveLocations
+            | Self::ListBranches
+            | Self::OpenPendingPullRequests
+            | Self::RepoDoctor
+            | Self::ReconcileTests => true,
             _ => false,
         }
     }
+
+    /// Returns true if this command fetches the files linked in its text by itself, rather than
+    /// having their contents substituted into the text before it is handled.
+    ///
+    /// `/add-locations` does this so that it can parse CSV and GPX files by their format.
+    pub fn imports_slack_files(&self) -> bool {
+        *self == Self::AddLocations
+    }
+}
+
+impl RoswaalSlackCommand {
+    /// Returns the arguments that this command accepts after its name.
+    pub fn syntax(&self) -> &'static str {
+        match self {
+            Self::ViewTests | Self::ExportTests => {
+                "[test name | tag:<tag> | suite:<suite> | contains:<phrase> | by:<@user>]..."
+            }
+            Self::AddTests | Self::LintTests => "[--overwrite] [--normalize-steps] <tests>",
+            Self::RemoveTests | Self::DeprecateTests => "<test name>...",
+            Self::RestoreTest => "<test name>",
+            Self::ResetTestProgress => "[test name]...",
+            Self::AssignFailure => "<test name> <@user>",
+            Self::EditTest => "[--normalize-steps] <test>",
+            Self::ViewLocations
+            | Self::ListBranches
+            | Self::OpenPendingPullRequests
+            | Self::ViewAuditLog
+            | Self::FlakyTests
+            | Self::MyFailedTests
+            | Self::Cancel => "",
+            Self::AddLocations => "<name>, <latitude>, <longitude>...",
+            Self::RemoveLocations => "<location name>...",
+            Self::AddLocationAlias => "<alias>, <location name>",
+            Self::EditRoster => "[<role> @user...]",
+            Self::GrantCommand => "[<command> [@user | @group]...]",
+            Self::ViewSuites => "[suite name]",
+            Self::RepoDoctor => "[reset [--delete-large-files] | abort-merge | prune | reclone]",
+            Self::ReconcileTests => "[cleanup]",
+            Self::Help => "[command]",
+        }
+    }
+
+    /// Returns a one line description of what this command does.
+    pub fn summary(&self) -> &'static str {
+        match self {
+            Self::ViewTests => "Views the progress of tests.",
+            Self::AddTests => "Compiles tests, and opens a PR to add them.",
+            Self::LintTests => "Compiles tests without opening a PR or saving them.",
+            Self::RemoveTests => "Opens a PR to remove tests, one test name per line.",
+            Self::RestoreTest => "Opens a PR that adds a removed test back from the archive.",
+            Self::DeprecateTests => {
+                "Hides tests from test runs, and removes them after a grace period."
+            }
+            Self::ResetTestProgress => {
+                "Clears the failures of tests so they can be re-run, or of every test if none are named."
+            }
+            Self::AssignFailure => {
+                "Assigns a user to triage a failing test until the test passes again."
+            }
+            Self::EditTest => "Opens a PR that replaces an existing test with an updated version.",
+            Self::ViewLocations => "Views all known locations.",
+            Self::AddLocations => "Opens a PR to add locations, one location per line.",
+            Self::RemoveLocations => "Opens a PR to remove locations, one name per line.",
+            Self::AddLocationAlias => "Lets tests set their location with another name.",
+            Self::ListBranches => "Views the open branches created by this tool.",
+            Self::OpenPendingPullRequests => {
+                "Opens the PRs that were deferred by the staged PR mode."
+            }
+            Self::ViewAuditLog => "Views the most recent commands and events.",
+            Self::MyFailedTests => {
+                "Views the tests you most recently failed to add, along with their errors."
+            }
+            Self::EditRoster => "Views or replaces the users assigned to a role.",
+            Self::GrantCommand => "Views or replaces the users allowed to run a command.",
+            Self::ViewSuites => "Views the progress of each suite, or the tests in a suite.",
+            Self::FlakyTests => {
+                "Ranks the tests that failed during their recent runs by flakiness."
+            }
+            Self::ExportTests => "Rebuilds the syntax of stored tests.",
+            Self::RepoDoctor => "Checks the local clones, and optionally repairs them.",
+            Self::ReconcileTests => "Finds orphaned test directories, and optionally removes them.",
+            Self::Cancel => "Cancels your latest long running command in this channel.",
+            Self::Help => "Views the syntax and examples of each command.",
+        }
+    }
+
+    /// Returns example command texts for this command.
+    pub fn examples(&self) -> &'static [&'static str] {
+        match self {
+            Self::ViewTests => &["Join an event", "tag:smoke", "contains:sign in"],
+            Self::AddTests | Self::LintTests => &[
+                "New Test: Join an event\nStep 1: Roswaal joins the event\nRequirement 1: Tap join",
+            ],
+            Self::RemoveTests | Self::DeprecateTests => &["Join an event\nLeave an event"],
+            Self::RestoreTest => &["Join an event"],
+            Self::ResetTestProgress => &["", "Join an event"],
+            Self::AssignFailure => &["Join an event @roswaal"],
+            Self::EditTest => &[
+                "New Test: Join an event\nStep 1: Roswaal joins the event\nRequirement 1: Tap the join button",
+            ],
+            Self::ViewLocations
+            | Self::ListBranches
+            | Self::OpenPendingPullRequests
+            | Self::ViewAuditLog
+            | Self::FlakyTests
+            | Self::MyFailedTests
+            | Self::Cancel => &[""],
+            Self::AddLocations => &["New York, 50.0, 50.0\nAntarctica, -12.1, -12.1"],
+            Self::RemoveLocations => &["New York\nAntarctica"],
+            Self::AddLocationAlias => &["SF, San Francisco"],
+            Self::EditRoster => &["", "merge-conflict-owner @roswaal"],
+            Self::GrantCommand => &["", "/remove-tests @roswaal @qa-team"],
+            Self::ViewSuites => &["", "Events"],
+            Self::ExportTests => &["Join an event", "suite:Events"],
+            Self::RepoDoctor => &["", "reset", "reset --delete-large-files"],
+            Self::ReconcileTests => &["", "cleanup"],
+            Self::Help => &["", "/add-tests"],
+        }
+    }
+
+    /// Returns true if the text of this command is written in the test language.
+    pub fn uses_test_language(&self) -> bool {
+        matches!(self, Self::AddTests | Self::LintTests | Self::EditTest)
+    }
 }
 
 impl Serialize for RoswaalSlackCommand {
@@ -0,0 +1,86 @@
+use std::borrow::Borrow;
+
+use crate::operations::deprecate_tests::DeprecateTestsStatus;
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for listing the tests that were deprecated, and when their code will be removed.
+pub struct DeprecateTestsView {
+    status: DeprecateTestsStatus,
+}
+
+impl DeprecateTestsView {
+    pub fn new(status: DeprecateTestsStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for DeprecateTestsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Deprecate Tests").flat_chain_block(self.status_view())
+    }
+}
+
+impl DeprecateTestsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            DeprecateTestsStatus::Success {
+                deprecated_test_names,
+                removal_date,
+            } => {
+                let mut body = "🗑 The following tests were deprecated!\n".to_string();
+                for name in deprecated_test_names {
+                    body.push_str(&format!("- *{}*\n", name))
+                }
+                SlackSection::from_markdown(&body)
+                    .flat_chain_block(SlackDivider)
+                    .flat_chain_block(SlackSection::from_markdown(&format!(
+                        "Deprecated tests are skipped during test runs, and a PR to remove their code will be opened on or after *{}*. Use `/add-tests --overwrite` to bring a test back before then!",
+                        removal_date.format("%Y-%m-%d")
+                    )))
+                    .erase_to_any_view()
+            }
+            DeprecateTestsStatus::NoTestsDeprecated => SlackSection::from_markdown(
+                "⚠️ No merged tests that have not already been deprecated were found. Use `/view-tests` to check the names of the tests!",
+            )
+            .erase_to_any_view(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use crate::{
+        operations::deprecate_tests::DeprecateTestsStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::DeprecateTestsView;
+
+    #[test]
+    fn success_snapshot() {
+        let status = DeprecateTestsStatus::Success {
+            deprecated_test_names: vec!["Join an event".to_string(), "Leave an event".to_string()],
+            removal_date: DateTime::from_timestamp(1_701_209_600, 0).unwrap(),
+        };
+        assert_slack_view_snapshot(
+            "deprecate-tests-success",
+            &DeprecateTestsView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_tests_deprecated_snapshot() {
+        assert_slack_view_snapshot(
+            "deprecate-tests-no-tests-deprecated",
+            &DeprecateTestsView::new(DeprecateTestsStatus::NoTestsDeprecated),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,211 @@
+use crate::{
+    language::diff::RoswaalCompiledTestDiff, operations::edit_tests::EditTestStatus,
+    utils::config::RoswaalConfig,
+};
+
+use super::{
+    add_tests_view::NonCompilingTestView,
+    interaction::RoswaalSlackAction,
+    merge_conflict_view::MergeConflictView,
+    pr_open_fail_view::FailedToOpenPullRequestView,
+    remote_unavailable_view::RemoteUnavailableView,
+    target_repositories_view::TargetRepositoriesView,
+    ui_lib::{
+        block_kit_views::{SlackActions, SlackDivider, SlackHeader, SlackSection},
+        if_view::If,
+        slack_view::SlackView,
+    },
+    users::RoswaalSlackUserRoster,
+    warn_undeleted_branch_view::WarnUndeletedBranchView,
+};
+
+/// A view for editing an existing test.
+pub struct EditTestView<'r> {
+    status: EditTestStatus<'r>,
+    roster: RoswaalSlackUserRoster,
+}
+
+impl<'r> EditTestView<'r> {
+    pub fn new(status: EditTestStatus<'r>) -> Self {
+        Self {
+            status,
+            roster: RoswaalSlackUserRoster::default(),
+        }
+    }
+
+    /// Uses `roster` to decide who to ping when the edit runs into a merge conflict.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
+    }
+}
+
+impl<'r> SlackView for EditTestView<'r> {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Edit Test")
+            .flat_chain_block(self.status_view())
+            .flat_chain_block(self.actions_view())
+    }
+}
+
+impl<'r> EditTestView<'r> {
+    fn status_view(&self) -> impl SlackView {
+        match &self.status {
+            EditTestStatus::Success {
+                diff,
+                should_warn_undeleted_branch,
+                branch_name: _,
+                target_edits,
+            } => self
+                .diff_view(diff)
+                .flat_chain_block(SlackDivider)
+                .flat_chain_block(SlackHeader::new("Next Steps"))
+                .flat_chain_block(SlackSection::from_markdown(&format!(
+                    "Approve the PR found in <#{}> to finish editing the teeeeeeest!",
+                    RoswaalConfig::current().pull_request_channel_id()
+                )))
+                .flat_chain_block(If::is_true(*should_warn_undeleted_branch, || {
+                    SlackDivider.flat_chain_block(WarnUndeletedBranchView)
+                }))
+                .flat_chain_block(
+                    TargetRepositoriesView::new(target_edits).with_roster(&self.roster),
+                )
+                .erase_to_any_view(),
+            EditTestStatus::NoTestsFound => {
+                SlackSection::from_markdown("🔴 No tests were fooooooound.").erase_to_any_view()
+            }
+            EditTestStatus::MultipleTestsFound => {
+                SlackSection::from_markdown("🔴 Only 1 test can be edited at a tiiiiiime.")
+                    .erase_to_any_view()
+            }
+            EditTestStatus::CompilationFailed(failure) => SlackSection::from_markdown(
+                "⚠️ *The edited test did not compile succeeeeeessfully!*",
+            )
+            .flat_chain_block(NonCompilingTestView::new(failure))
+            .erase_to_any_view(),
+            EditTestStatus::TestNotFound { name } => SlackSection::from_markdown(&format!(
+                "🔴 No test named *{}* was fooooound. Use `/add-tests` to add it insteeeeead.",
+                name
+            ))
+            .erase_to_any_view(),
+            EditTestStatus::NoChanges => SlackSection::from_markdown(
+                "✅ The test is already up to daaaaaate, so no PR was opened.",
+            )
+            .erase_to_any_view(),
+            EditTestStatus::MergeConflict => {
+                MergeConflictView::new(&self.roster).erase_to_any_view()
+            }
+            EditTestStatus::FailedToOpenPullRequest => {
+                FailedToOpenPullRequestView.erase_to_any_view()
+            }
+            EditTestStatus::RemoteUnavailable => RemoteUnavailableView.erase_to_any_view(),
+        }
+    }
+
+    fn diff_view(&self, diff: &RoswaalCompiledTestDiff) -> impl SlackView {
+        SlackSection::from_markdown(&format!(
+            "✏️ *{}* was edited with the following chaaaaanges!",
+            diff.test_name()
+        ))
+        .flat_chain_block(SlackSection::from_markdown(&format!(
+            "```\n{}\n```",
+            diff.unified_diff()
+        )))
+        .flat_chain_block(diff.changed_description().map(|description| {
+            SlackSection::from_markdown(&format!(
+                "📝 The description was changed to: {}",
+                description.map(|d| d.as_str()).unwrap_or("_(None)_")
+            ))
+        }))
+    }
+
+    fn actions_view(&self) -> Option<impl SlackView> {
+        let EditTestStatus::Success { branch_name, .. } = &self.status else {
+            return None;
+        };
+        RoswaalSlackAction::CloseBranch
+            .button("Close Branch", &branch_name.to_string())
+            .map(|b| SlackDivider.flat_chain_block(SlackActions::new(vec![b.danger()])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        language::{
+            ast::RoswaalTestSyntax,
+            compilation_results::RoswaalTestCompilationResults,
+            diff::RoswaalCompiledTestDiff,
+            test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+        },
+        operations::edit_tests::EditTestStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::EditTestView;
+
+    #[test]
+    fn success_snapshot() {
+        let old_commands = vec![step(1, "Do the thing"), step(2, "Do the last thing")];
+        let test = RoswaalCompiledTest::new(
+            "Edit Me".to_string(),
+            Some("A better description".to_string()),
+            vec![
+                step(1, "Do the thing"),
+                step(2, "Do the new thing"),
+                step(3, "Do the last thing"),
+            ],
+        );
+        let status = EditTestStatus::Success {
+            diff: RoswaalCompiledTestDiff::new(None, &old_commands, &test),
+            should_warn_undeleted_branch: true,
+            branch_name: serde_json::from_str("\"roswaal-add-tests-0123456789\"").unwrap(),
+            target_edits: vec![],
+        };
+        assert_slack_view_snapshot(
+            "edit-test-success",
+            &EditTestView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn compilation_failed_snapshot() {
+        let syntax = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Edit Me
+Step 1: Do the thing
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(syntax, &vec![]);
+        let status = EditTestStatus::CompilationFailed(results.failures().remove(0));
+        assert_slack_view_snapshot(
+            "edit-test-compilation-failed",
+            &EditTestView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn test_not_found_snapshot() {
+        let status = EditTestStatus::TestNotFound {
+            name: "Edit Me".to_string(),
+        };
+        assert_slack_view_snapshot(
+            "edit-test-not-found",
+            &EditTestView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn step(number: usize, name: &str) -> RoswaalCompiledTestCommand {
+        RoswaalCompiledTestCommand::Step {
+            label: format!("Step {}", number),
+            name: name.to_string(),
+            requirement: name.to_string(),
+            retries: None,
+        }
+    }
+}
@@ -2,10 +2,16 @@ use std::{backtrace::Backtrace, cmp::min};
 
 use anyhow::Error;
 
-use super::ui_lib::{
-    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
-    if_view::If,
-    slack_view::SlackView,
+use crate::git::timeout::GitOperationTimedOut;
+
+use super::{
+    catalog::RoswaalMessageId,
+    git_timeout_view::GitOperationTimedOutView,
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        if_view::If,
+        slack_view::SlackView,
+    },
 };
 
 pub struct ErrorView {
@@ -22,14 +28,23 @@ const SECTION_MAX_CHARACTERS: usize = 3000;
 
 impl SlackView for ErrorView {
     fn slack_body(&self) -> impl SlackView {
+        if let Some(error) = GitOperationTimedOut::from_error(&self.error) {
+            return SlackHeader::new(RoswaalMessageId::ErrorViewHeader.text())
+                .flat_chain_block(GitOperationTimedOutView::new(error))
+                .erase_to_any_view();
+        }
         let backtrace_string = self.error.backtrace().to_string();
         let len = min(backtrace_string.len(), SECTION_MAX_CHARACTERS);
-        SlackHeader::new("An Error Occurred")
-            .flat_chain_block(SlackSection::from_markdown("🔴 *Error*"))
+        SlackHeader::new(RoswaalMessageId::ErrorViewHeader.text())
+            .flat_chain_block(SlackSection::from_markdown(
+                RoswaalMessageId::ErrorViewTitle.text(),
+            ))
             .flat_chain_block(
                 SlackSection::from_plaintext(&self.error.to_string()).emoji_enabled(false),
             )
-            .flat_chain_block(SlackSection::from_markdown("⚠️ *Stack Trace*"))
+            .flat_chain_block(SlackSection::from_markdown(
+                RoswaalMessageId::StackTraceTitle.text(),
+            ))
             .flat_chain_block(
                 SlackSection::from_plaintext(&backtrace_string[0..len]).emoji_enabled(false),
             )
@@ -37,21 +52,24 @@ impl SlackView for ErrorView {
                 backtrace_string.len() > SECTION_MAX_CHARACTERS,
                 || {
                     SlackDivider.flat_chain_block(SlackSection::from_markdown(
-                        "🟡 _Stack Trace truncated due to 3000 character limit..._",
+                        &RoswaalMessageId::ErrorViewStackTraceTruncated
+                            .format(&[&SECTION_MAX_CHARACTERS]),
                     ))
                 },
             ))
+            .erase_to_any_view()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::env;
+    use std::{env, time::Duration};
 
     use anyhow::Error;
     use dotenv::dotenv;
 
     use crate::{
+        git::timeout::GitOperationTimedOut,
         slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
         utils::test_error::TestError,
     };
@@ -69,4 +87,14 @@ mod tests {
             SnapshotMode::Comparing,
         )
     }
+
+    #[test]
+    fn git_operation_timed_out_snapshot() {
+        let error = GitOperationTimedOut::new("git pull origin main", Duration::from_secs(120));
+        assert_slack_view_snapshot(
+            "error-view-git-operation-timed-out",
+            &ErrorView::new(Error::new(error)),
+            SnapshotMode::Comparing,
+        )
+    }
 }
@@ -0,0 +1,79 @@
+use std::borrow::Borrow;
+
+use crate::operations::export_tests::ExportTestsStatus;
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    for_each_view::ForEachView,
+    slack_view::SlackView,
+};
+
+/// A view for recovering the roswaal syntax of existing tests.
+pub struct ExportTestsView {
+    status: ExportTestsStatus,
+}
+
+impl ExportTestsView {
+    pub fn new(status: ExportTestsStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for ExportTestsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Export Tests").flat_chain_block(self.status_view())
+    }
+}
+
+impl ExportTestsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            ExportTestsStatus::Success(syntaxes) => ForEachView::new(
+                syntaxes.iter().cloned(),
+                |syntax| SlackSection::from_markdown(&format!("```{}```", syntax)),
+            )
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackHeader::new("Next Steps"))
+            .flat_chain_block(SlackSection::from_markdown(
+                "Copy a test into `/edit-test` to chaaaaaange it! Tags and suites are not exported, so add them back before submitting.",
+            ))
+            .erase_to_any_view(),
+            ExportTestsStatus::NoTests => {
+                SlackSection::from_markdown("🔴 No tests were fooooooound.").erase_to_any_view()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::export_tests::ExportTestsStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::ExportTestsView;
+
+    #[test]
+    fn success_snapshot() {
+        let syntaxes = vec![
+            "New Test: Join an event\nStep 1: Join the event\nRequirement 1: Tap join".to_string(),
+            "New Test: Leave an event\nStep 1: Leave the event\nRequirement 1: Tap leave"
+                .to_string(),
+        ];
+        assert_slack_view_snapshot(
+            "export-tests-success",
+            &ExportTestsView::new(ExportTestsStatus::Success(syntaxes)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_tests_snapshot() {
+        assert_slack_view_snapshot(
+            "export-tests-no-tests",
+            &ExportTestsView::new(ExportTestsStatus::NoTests),
+            SnapshotMode::Comparing,
+        )
+    }
+}
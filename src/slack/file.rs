@@ -0,0 +1,218 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+};
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+
+/// The url of the slack API method for looking up the metadata of an uploaded file.
+const SLACK_FILES_INFO_URL: &str = "https://slack.com/api/files.info";
+
+/// A trait for fetching the text contents of a file that was uploaded to slack.
+pub trait SlackFetchFile {
+    fn fetch_file(&self, file_id: &str) -> impl Future<Output = Result<String>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackFilesInfoResponse {
+    error: Option<String>,
+    file: Option<SlackFileInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackFileInfo {
+    url_private_download: Option<String>,
+    url_private: Option<String>,
+}
+
+#[derive(Debug)]
+struct SlackFileFetchingError {
+    message: String,
+}
+
+impl Display for SlackFileFetchingError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack File Fetching Error: {}", self.message)
+    }
+}
+
+impl Error for SlackFileFetchingError {}
+
+impl SlackFetchFile for Client {
+    async fn fetch_file(&self, file_id: &str) -> Result<String> {
+        let token = env::var("SLACK_BOT_TOKEN").expect(
+            "SLACK_BOT_TOKEN not found in .env, you can get one from the slack app console.",
+        );
+        let info = self
+            .get(SLACK_FILES_INFO_URL)
+            .query(&[("file", file_id)])
+            .bearer_auth(&token)
+            .send()
+            .await?
+            .json::<SlackFilesInfoResponse>()
+            .await?;
+        if let Some(error) = info.error {
+            log::error!("A Slack API error occured {}.", error);
+            return Err(anyhow::Error::new(SlackFileFetchingError {
+                message: error,
+            }));
+        }
+        let url = info
+            .file
+            .and_then(|f| f.url_private_download.or(f.url_private))
+            .ok_or_else(|| SlackFileFetchingError {
+                message: format!("File {} has no download url.", file_id),
+            })?;
+        let resp = self
+            .get(url)
+            .bearer_auth(token)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(resp.text().await?)
+    }
+}
+
+/// Returns true if any line of `text` only contains a link to a file uploaded to slack.
+pub fn contains_slack_file_link(text: &str) -> bool {
+    text.lines().any(|line| slack_file_id(line).is_some())
+}
+
+/// Replaces each line of `text` that only contains a link to a file uploaded to slack with the
+/// contents of that file.
+///
+/// This allows commands to read text that is longer than what slack allows in a slash command.
+pub async fn with_slack_file_contents(text: &str, fetch: &impl SlackFetchFile) -> Result<String> {
+    let mut lines = Vec::<String>::new();
+    for line in text.lines() {
+        match slack_file_id(line) {
+            Some(file_id) => lines.push(fetch.fetch_file(file_id).await?),
+            None => lines.push(line.to_string()),
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Returns the url of a line that only contains a link.
+///
+/// Slack formats links as `<url>` or `<url|label>`, so those brackets are stripped.
+pub fn slack_link_url(line: &str) -> &str {
+    let line = line.trim();
+    line.strip_prefix('<')
+        .and_then(|l| l.strip_suffix('>'))
+        .map(|l| l.split('|').next().unwrap_or(l))
+        .unwrap_or(line)
+}
+
+/// Returns the id of the file if the line only contains a link to a file uploaded to slack.
+///
+/// Permalinks have the form `https://<team>.slack.com/files/<user id>/<file id>/<name>`, and
+/// private urls have the form `https://files.slack.com/files-pri/<team id>-<file id>/<name>`.
+pub fn slack_file_id(line: &str) -> Option<&str> {
+    let path = slack_link_url(line).strip_prefix("https://")?;
+    let (host, path) = path.split_once('/')?;
+    if host != "slack.com" && !host.ends_with(".slack.com") {
+        return None;
+    }
+    let mut segments = path.split('/');
+    let file_id = match segments.next()? {
+        "files" => segments.nth(1)?,
+        "files-pri" => segments.next()?.split_once('-')?.1,
+        _ => return None,
+    };
+    let is_file_id = file_id.len() > 1
+        && file_id.starts_with('F')
+        && file_id
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    is_file_id.then_some(file_id)
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use std::collections::HashMap;
+
+    use anyhow::Result;
+
+    use crate::utils::test_error::TestError;
+
+    use super::SlackFetchFile;
+
+    /// A `SlackFetchFile` that returns the contents of files by their id, and fails for any
+    /// unknown file.
+    pub struct TestSlackFileFetch {
+        files: HashMap<String, String>,
+    }
+
+    impl TestSlackFileFetch {
+        pub fn new(files: &[(&str, &str)]) -> Self {
+            Self {
+                files: files
+                    .iter()
+                    .map(|(id, contents)| (id.to_string(), contents.to_string()))
+                    .collect(),
+            }
+        }
+    }
+
+    impl SlackFetchFile for TestSlackFileFetch {
+        async fn fetch_file(&self, file_id: &str) -> Result<String> {
+            self.files
+                .get(file_id)
+                .cloned()
+                .ok_or_else(|| anyhow::Error::new(TestError))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_support::TestSlackFileFetch, *};
+
+    #[test]
+    fn parses_file_ids_from_slack_file_links() {
+        let lines = [
+            (
+                "<https://tif.slack.com/files/U01234567/F0123ABCD/tests.txt>",
+                Some("F0123ABCD"),
+            ),
+            (
+                " https://tif.slack.com/files/U01234567/F0123ABCD/tests.txt ",
+                Some("F0123ABCD"),
+            ),
+            (
+                "<https://files.slack.com/files-pri/T0123-F0123ABCD/tests.txt|tests.txt>",
+                Some("F0123ABCD"),
+            ),
+            (
+                "<https://example.com/files/U01234567/F0123ABCD/tests.txt>",
+                None,
+            ),
+            ("<https://tif.slack.com/archives/C0123/p123>", None),
+            ("New Test: Slack files", None),
+        ];
+        for (line, id) in lines {
+            assert_eq!(slack_file_id(line), id)
+        }
+    }
+
+    #[tokio::test]
+    async fn replaces_file_links_with_file_contents() {
+        let fetch = TestSlackFileFetch::new(&[("F0123ABCD", "```\nNew Test: A\n```")]);
+        let text = "--overwrite\n<https://tif.slack.com/files/U01234567/F0123ABCD/tests.txt>";
+        assert!(contains_slack_file_link(text));
+        let text = with_slack_file_contents(text, &fetch).await.unwrap();
+        assert_eq!(text, "--overwrite\n```\nNew Test: A\n```")
+    }
+
+    #[tokio::test]
+    async fn fails_when_file_cannot_be_fetched() {
+        let fetch = TestSlackFileFetch::new(&[]);
+        let text = "<https://tif.slack.com/files/U01234567/F0123ABCD/tests.txt>";
+        assert!(with_slack_file_contents(text, &fetch).await.is_err())
+    }
+}
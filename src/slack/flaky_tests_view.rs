@@ -0,0 +1,111 @@
+use std::borrow::Borrow;
+
+use crate::{operations::flaky_tests::FlakyTestsStatus, tests_data::test::RoswaalTest};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for ranking the tests that failed during their recent runs by their flakiness.
+pub struct FlakyTestsView {
+    status: FlakyTestsStatus,
+}
+
+impl FlakyTestsView {
+    pub fn new(status: FlakyTestsStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for FlakyTestsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Flaky Tests").flat_chain_block(self.status_view())
+    }
+}
+
+impl FlakyTestsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            FlakyTestsStatus::Success(tests) => {
+                SlackSection::from_markdown(&Self::tests_body(tests))
+                    .flat_chain_block(SlackDivider)
+                    .flat_chain_block(SlackSection::from_markdown(
+                        "Tests that fail on and off rank higher than tests that fail every run. Use `/view-tests <test name>` to see where a test failed!",
+                    ))
+                    .erase_to_any_view()
+            }
+            FlakyTestsStatus::NoFlakyTests => SlackSection::from_markdown(
+                "✅ No tests failed during their recent runs, nothing is flaaaaaaky!",
+            )
+            .erase_to_any_view(),
+            FlakyTestsStatus::NoTests => SlackSection::from_markdown(
+                "🔘 There are no tests yet! Use `/add-tests` to add some.",
+            )
+            .erase_to_any_view(),
+        }
+    }
+
+    fn tests_body(tests: &[RoswaalTest]) -> String {
+        let mut body = String::new();
+        for (index, test) in tests.iter().enumerate() {
+            let flakiness = test.flakiness();
+            body.push_str(&format!(
+                "{}. *{}* _(Score: {:.2}, {} of the last {} runs failed)_\n",
+                index + 1,
+                test.name(),
+                flakiness.score(),
+                flakiness.failure_count(),
+                flakiness.run_count()
+            ))
+        }
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::flaky_tests::FlakyTestsStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        tests_data::{flakiness::RoswaalTestFlakiness, test::RoswaalTest},
+    };
+
+    use super::FlakyTestsView;
+
+    #[test]
+    fn success_snapshot() {
+        let tests = vec![
+            test("Join an event", vec![true, false, true, false]),
+            test("Leave an event", vec![false, true, true, true]),
+        ];
+        assert_slack_view_snapshot(
+            "flaky-tests-success",
+            &FlakyTestsView::new(FlakyTestsStatus::Success(tests)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_flaky_tests_snapshot() {
+        assert_slack_view_snapshot(
+            "flaky-tests-no-flaky-tests",
+            &FlakyTestsView::new(FlakyTestsStatus::NoFlakyTests),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_tests_snapshot() {
+        assert_slack_view_snapshot(
+            "flaky-tests-no-tests",
+            &FlakyTestsView::new(FlakyTestsStatus::NoTests),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn test(name: &str, failures: Vec<bool>) -> RoswaalTest {
+        RoswaalTest::new(name.to_string(), None, vec![], None, None, None, None, None)
+            .with_flakiness(RoswaalTestFlakiness::new(failures))
+    }
+}
@@ -0,0 +1,50 @@
+use crate::git::timeout::GitOperationTimedOut;
+
+use super::{
+    catalog::RoswaalMessageId,
+    ui_lib::{block_kit_views::SlackSection, slack_view::SlackView},
+};
+
+/// A view for indicating that a git operation was cancelled because it ran for longer than the
+/// git operation timeout.
+pub struct GitOperationTimedOutView<'e> {
+    error: &'e GitOperationTimedOut,
+}
+
+impl<'e> GitOperationTimedOutView<'e> {
+    pub fn new(error: &'e GitOperationTimedOut) -> Self {
+        Self { error }
+    }
+}
+
+impl<'e> SlackView for GitOperationTimedOutView<'e> {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown(RoswaalMessageId::GitOperationTimedOutTitle.text())
+            .flat_chain_block(SlackSection::from_markdown(
+                &RoswaalMessageId::GitOperationTimedOutBody
+                    .format(&[&self.error.operation(), &self.error.timeout().as_secs()]),
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        git::timeout::GitOperationTimedOut,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::GitOperationTimedOutView;
+
+    #[test]
+    fn snapshot() {
+        let error = GitOperationTimedOut::new("git push origin main", Duration::from_secs(120));
+        assert_slack_view_snapshot(
+            "git-operation-timed-out",
+            &GitOperationTimedOutView::new(&error),
+            SnapshotMode::Comparing,
+        )
+    }
+}
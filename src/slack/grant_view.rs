@@ -0,0 +1,140 @@
+use std::borrow::Borrow;
+
+use crate::operations::grant_command::GrantCommandStatus;
+
+use super::{
+    permissions::RoswaalSlackCommandPermissions,
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        slack_view::SlackView,
+    },
+    users::RoswaalSlackUserRole,
+};
+
+/// A view for showing and editing which users are allowed to run each slack command.
+pub struct GrantView {
+    status: GrantCommandStatus,
+}
+
+impl GrantView {
+    pub fn new(status: GrantCommandStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for GrantView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Command Permissions").flat_chain_block(self.status_view())
+    }
+}
+
+impl GrantView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            GrantCommandStatus::Permissions(permissions) => SlackSection::from_markdown(
+                "🔐 *Here's who can run eaaaaaach command!*",
+            )
+            .flat_chain_block(Self::permissions_view(permissions))
+            .erase_to_any_view(),
+            GrantCommandStatus::Success {
+                command,
+                permissions,
+            } => {
+                let text = if permissions.subjects(*command).is_empty() {
+                    format!("✅ *Anyone can run `{}` agaaaaain!*", command)
+                } else {
+                    format!("✅ *The permissions of `{}` were updaaaaaated!*", command)
+                };
+                SlackSection::from_markdown(&text)
+                    .flat_chain_block(Self::permissions_view(permissions))
+                    .erase_to_any_view()
+            }
+            GrantCommandStatus::InvalidCommand(name) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not a command that can be granted. Use `/roswaal-help` to list the commaaaaaands.",
+                name
+            ))
+            .erase_to_any_view(),
+            GrantCommandStatus::InvalidSubject(mention) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not a slack user or user group. Mention them with `@` to grant them the commaaaaaand.",
+                mention
+            ))
+            .erase_to_any_view(),
+            GrantCommandStatus::NotAuthorized { roster } => SlackSection::from_markdown(&format!(
+                "🔴 Only {} can grant commands. Ask them niiiiiicely!",
+                roster.mentions(RoswaalSlackUserRole::InfraOwner)
+            ))
+            .erase_to_any_view(),
+        }
+    }
+
+    fn permissions_view(permissions: &RoswaalSlackCommandPermissions) -> impl SlackView {
+        let restricted_commands = permissions.restricted_commands();
+        let body = if restricted_commands.is_empty() {
+            "Anyone can run every command.".to_string()
+        } else {
+            let mut body = String::new();
+            for (command, subjects) in restricted_commands {
+                let mentions = subjects
+                    .iter()
+                    .map(|subject| subject.mention())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                body.push_str(&format!("*{}*: {}\n", command, mentions));
+            }
+            body.push_str("_Infra owners can run every command, and anyone can run the rest._");
+            body
+        };
+        SlackDivider.flat_chain_block(SlackSection::from_markdown(&body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::{
+        command::RoswaalSlackCommand,
+        permissions::RoswaalSlackPermissionSubject,
+        ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        users::RoswaalSlackUserRoster,
+    };
+
+    use super::*;
+
+    #[test]
+    fn permissions_snapshot() {
+        let permissions = RoswaalSlackCommandPermissions::default().with_subjects(
+            RoswaalSlackCommand::RemoveTests,
+            vec![
+                RoswaalSlackPermissionSubject::User("U1".to_string()),
+                RoswaalSlackPermissionSubject::UserGroup("S1".to_string()),
+            ],
+        );
+        assert_slack_view_snapshot(
+            "grant-permissions",
+            &GrantView::new(GrantCommandStatus::Permissions(permissions)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn reopened_snapshot() {
+        assert_slack_view_snapshot(
+            "grant-reopened",
+            &GrantView::new(GrantCommandStatus::Success {
+                command: RoswaalSlackCommand::RemoveTests,
+                permissions: RoswaalSlackCommandPermissions::default(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn not_authorized_snapshot() {
+        assert_slack_view_snapshot(
+            "grant-not-authorized",
+            &GrantView::new(GrantCommandStatus::NotAuthorized {
+                roster: RoswaalSlackUserRoster::default(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+}
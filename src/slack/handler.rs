@@ -1,94 +1,477 @@
-use std::{future::Future, sync::Arc};
+use std::{
+    convert::Infallible,
+    future::{pending, Future},
+    sync::Arc,
+};
 
 use super::{
+    cancel_view::{CancelView, OperationCancelledView},
     command::RoswaalSlackCommand,
     error_view::ErrorView,
-    message::{SlackMessage, SlackSendMessage},
+    file::{contains_slack_file_link, with_slack_file_contents, SlackFetchFile},
+    in_flight::RoswaalInFlightOperations,
+    interaction::{RoswaalSlackAction, RoswaalSlackInteraction},
+    long_command_text_view::LongCommandTextView,
+    message::{SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL, SLACK_UPDATE_MESSAGE_URL},
     message_view::MessageView,
+    modal::{RoswaalSlackModal, RoswaalSlackModalSubmission},
     pending_view::PendingView,
+    permission_denied_view::PermissionDeniedView,
     ui_lib::{
+        any_view::AnySlackView,
         blocks::SlackBlocks,
+        modal::SlackModal,
         slack_view::{render_slack_view, SlackView},
     },
 };
+use crate::{
+    audit::{
+        entry::{RoswaalAuditLogEntry, RoswaalAuditStatus},
+        recorder::RoswaalRecordAuditLog,
+    },
+    git::branch_name::RoswaalOwnedGitBranchName,
+    operations::check_permission::CheckPermissionStatus,
+    utils::stage::RoswaalOperationStage,
+};
 use anyhow::Error;
 use serde::{Deserialize, Serialize};
-use tokio::spawn;
+use tokio::sync::watch;
+use tokio_util::task::TaskTracker;
+
+/// The length at which the text of a slash command is considered to be cut off.
+///
+/// Slack truncates command texts to 4000 characters, so a text this close to the limit was most
+/// likely longer than what slack sent.
+pub const TRUNCATED_COMMAND_TEXT_LENGTH: usize = 3900;
 
 /// A request from slack.
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
 pub struct RoswaalSlackRequest {
     channel_id: String,
+    user_id: String,
     text: String,
     command: RoswaalSlackCommand,
     response_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trigger_id: Option<String>,
 }
 
 impl RoswaalSlackRequest {
     pub fn new(
         channel_id: String,
+        user_id: String,
         text: String,
         command: RoswaalSlackCommand,
         response_url: String,
     ) -> Self {
         Self {
             channel_id,
+            user_id,
             text,
             command,
             response_url,
+            trigger_id: None,
+        }
+    }
+}
+
+impl RoswaalSlackRequest {
+    /// Returns the id of the slack user that sent the command.
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Returns true if slack likely cut off the text of this request.
+    ///
+    /// Texts that link to an uploaded file are never considered cut off, since the file holds
+    /// the bulk of the text.
+    pub fn is_text_truncated(&self) -> bool {
+        self.text.chars().count() >= TRUNCATED_COMMAND_TEXT_LENGTH
+            && !contains_slack_file_link(&self.text)
+    }
+
+    /// Returns the modal to open in place of handling this request alongside the trigger id
+    /// needed to open it.
+    ///
+    /// A modal is opened when a command that takes a large text (eg. `/add-tests`) is sent
+    /// without any text. The modal remembers the channel of this request so that the submitted
+    /// text can be handled in the same channel.
+    pub fn modal_to_open(&self) -> Option<(SlackModal, &str)> {
+        if !self.text.trim().is_empty() {
+            return None;
+        }
+        let modal = RoswaalSlackModal::for_empty_command(&self.command)?;
+        self.trigger_id
+            .as_deref()
+            .map(|trigger_id| (modal.slack_modal(&self.channel_id), trigger_id))
+    }
+}
+
+/// The response to a slack command.
+pub struct RoswaalSlackCommandResponse<View: SlackView> {
+    view: View,
+    branch_name: Option<RoswaalOwnedGitBranchName>,
+}
+
+impl<View: SlackView> RoswaalSlackCommandResponse<View> {
+    pub fn new(view: View) -> Self {
+        Self {
+            view,
+            branch_name: None,
+        }
+    }
+
+    /// Specifies the branch that was created when handling the command so that it can be
+    /// recorded in the audit log.
+    pub fn with_branch_name(self, branch_name: Option<&RoswaalOwnedGitBranchName>) -> Self {
+        Self {
+            branch_name: branch_name.cloned(),
+            ..self
         }
     }
 }
 
 /// A trait for handling slack commands.
-pub trait RoswaalSlackHandler: Sized + 'static {
-    /// Handles the specified command and command text, and returns a `SlackView` with the contents
-    /// of the response to the command.
+///
+/// Handlers also fetch the files uploaded to slack that are linked in a command text, since
+/// commands like `/add-tests` can be longer than what slack allows in a slash command.
+pub trait RoswaalSlackHandler: SlackFetchFile + Sized + 'static {
+    /// Handles the specified command and command text sent by the slack user with `user_id`, and
+    /// returns a response containing a `SlackView` with the contents of the response to the
+    /// command.
+    ///
+    /// Any line of the command text that linked to a slack file is replaced with the contents of
+    /// that file before the command is handled.
     fn handle_command(
         &self,
         command: &RoswaalSlackCommand,
         command_text: &str,
+        user_id: &str,
+    ) -> impl Future<Output = Result<RoswaalSlackCommandResponse<impl SlackView + Send>, Error>> + Send;
+
+    /// Checks whether the slack user with `user_id` is allowed to run the specified command.
+    fn check_permission(
+        &self,
+        command: &RoswaalSlackCommand,
+        user_id: &str,
+    ) -> impl Future<Output = Result<CheckPermissionStatus, Error>> + Send;
+
+    /// Handles the specified action triggered by an interactive element (eg. a button) with its
+    /// associated value, and returns a `SlackView` with the contents of the response to the
+    /// action.
+    fn handle_action(
+        &self,
+        action: &RoswaalSlackAction,
+        value: &str,
     ) -> impl Future<Output = Result<impl SlackView + Send, Error>> + Send;
 }
 
 /// Handles a `RoswaalSlackRequest` and returns the `SlackBlocks` that form the content of the
 /// response.
 ///
-/// If the command in the request is long running, then the function immediately returns a
-/// message to indicating that the request is being handled. In the meantime, the request is
-/// being handled on a background task, and it the returned message will be sent to slack in
-/// the background via `messenger` when the handling of the request is finished.
+/// If the command in the request is long running, then the function immediately returns None so
+/// that the request can be acknowledged without a response. In the meantime, the request is
+/// being handled on a background task tracked by `tasks`, which posts a message indicating that
+/// the request is being handled, and replies to it in a thread via `messenger` when the handling
+/// of the request is finished.
+///
+/// Commands that link to a slack file are also handled in the background, since the file must be
+/// downloaded first. If the command text was likely cut off by slack, then the command is not
+/// handled, and a message asking the user to upload the text as a file is returned instead.
+///
+/// Requests handled in the background are registered in `in_flight` until they finish, so that
+/// `/roswaal-cancel` can stop the most recent one sent by the same user in the same channel.
+/// `/roswaal-cancel` itself is handled here rather than by `handler`, since it needs the channel
+/// of the request.
+///
+/// Users that are not allowed to run the command of the request (see `/roswaal-grant`) are sent
+/// a `PermissionDeniedView` instead, and the command is never handled. Requests handled in the
+/// background are checked before their pending message is posted.
+///
+/// Once the request has been handled, the user, command, command text, resulting branch, and
+/// whether or not the command failed are recorded in `audit_log`.
 pub async fn handle_slack_request(
     handler: Arc<impl RoswaalSlackHandler + Send + Sync>,
     request: RoswaalSlackRequest,
     messenger: Arc<(impl SlackSendMessage + Send + Sync + 'static)>,
-) -> SlackBlocks {
-    if request.command.is_long_running() {
+    audit_log: Arc<impl RoswaalRecordAuditLog + Send + Sync + 'static>,
+    tasks: &TaskTracker,
+    in_flight: &RoswaalInFlightOperations,
+) -> Option<SlackBlocks> {
+    if request.is_text_truncated() {
+        return Some(render_slack_view(&MessageView::new(&LongCommandTextView)));
+    }
+    if request.command != RoswaalSlackCommand::Cancel
+        && (request.command.is_long_running() || contains_slack_file_link(&request.text))
+    {
         // NB: A long running command must spin up an unstructered background task since we
         // have to send an ack response to slack within 3 seconds.
-        spawn(async move {
-            let view = view_for_request(handler.as_ref(), &request).await;
+        spawn_request(handler, request, messenger, audit_log, tasks, in_flight);
+        return None;
+    }
+    if let Some(view) =
+        request_permission_denied_view(handler.as_ref(), &request, audit_log.as_ref()).await
+    {
+        return Some(render_slack_view(&MessageView::new(&view)));
+    }
+    if request.command == RoswaalSlackCommand::Cancel {
+        let cancelled_command = in_flight.cancel_latest(&request.user_id, &request.channel_id);
+        let entry =
+            RoswaalAuditLogEntry::new(&request.user_id, request.command.into(), &request.text);
+        audit_log.record(entry).await;
+        return Some(render_slack_view(&MessageView::new(&CancelView::new(
+            cancelled_command,
+        ))));
+    }
+    Some(render_slack_view(&MessageView::new(
+        &view_for_request(handler.as_ref(), &request, audit_log.as_ref()).await,
+    )))
+}
+
+/// Handles the text submitted through a modal in the background on a task tracked by `tasks`.
+///
+/// The submitted text is handled by the command of the modal in the same manner as if the text
+/// was sent with the command directly, and the resulting view is sent in a thread in the channel
+/// that the modal was opened from via `messenger`.
+pub fn handle_slack_modal_submission(
+    handler: Arc<impl RoswaalSlackHandler + Send + Sync>,
+    submission: RoswaalSlackModalSubmission,
+    messenger: Arc<impl SlackSendMessage + Send + Sync + 'static>,
+    audit_log: Arc<impl RoswaalRecordAuditLog + Send + Sync + 'static>,
+    tasks: &TaskTracker,
+    in_flight: &RoswaalInFlightOperations,
+) {
+    spawn_request(
+        handler,
+        submission.request(),
+        messenger,
+        audit_log,
+        tasks,
+        in_flight,
+    )
+}
+
+fn spawn_request(
+    handler: Arc<impl RoswaalSlackHandler + Send + Sync>,
+    request: RoswaalSlackRequest,
+    messenger: Arc<impl SlackSendMessage + Send + Sync + 'static>,
+    audit_log: Arc<impl RoswaalRecordAuditLog + Send + Sync + 'static>,
+    tasks: &TaskTracker,
+    in_flight: &RoswaalInFlightOperations,
+) {
+    let operation = in_flight.start(&request.user_id, &request.channel_id, request.command);
+    tasks.spawn(async move {
+        if let Some(view) =
+            request_permission_denied_view(handler.as_ref(), &request, audit_log.as_ref()).await
+        {
+            drop(operation);
             let message = SlackMessage::new(&request.channel_id, &view, &request.response_url);
-            messenger.send(&message).await
-        });
-        render_slack_view(&PendingView)
-    } else {
-        render_slack_view(&MessageView::new(
-            &view_for_request(handler.as_ref(), &request).await,
-        ))
+            return messenger.send(&message).await;
+        }
+        let pending_message = SlackMessage::new(
+            &request.channel_id,
+            &PendingView::new(),
+            SLACK_POST_MESSAGE_URL,
+        );
+        // NB: Posting to a channel fails when the bot is not a member of it, in which case the
+        // result is sent to the response url of the request without a thread.
+        let thread_ts = match messenger.send(&pending_message).await {
+            Ok(thread_ts) => thread_ts,
+            Err(error) => {
+                log::warn!("Failed to post the pending message. {}", error);
+                None
+            }
+        };
+        let (handling, stages) = RoswaalOperationStage::reporting(view_for_request(
+            handler.as_ref(),
+            &request,
+            audit_log.as_ref(),
+        ));
+        // NB: Dropping the handling of a cancelled request stops it at its next await point, and
+        // releases anything it holds (eg. a git child process, or a libgit2 request). An edit
+        // that is dropped deletes its new branch before it releases the repository lock.
+        let view = tokio::select! {
+            view = handling => view.erase_to_any_view(),
+            _ = operation.cancelled() => {
+                let entry = RoswaalAuditLogEntry::new(
+                    &request.user_id,
+                    request.command.into(),
+                    &request.text,
+                )
+                .with_status(RoswaalAuditStatus::Failed {
+                    message: "Cancelled with /roswaal-cancel.".to_string(),
+                });
+                audit_log.record(entry).await;
+                OperationCancelledView::new(request.command).erase_to_any_view()
+            }
+            never = update_pending_message(
+                messenger.as_ref(),
+                &request.channel_id,
+                thread_ts.as_deref(),
+                stages,
+            ) => match never {}
+        };
+        drop(operation);
+        let message = match thread_ts {
+            Some(thread_ts) => {
+                SlackMessage::new(&request.channel_id, &view, SLACK_POST_MESSAGE_URL)
+                    .in_thread(&thread_ts)
+            }
+            None => SlackMessage::new(&request.channel_id, &view, &request.response_url),
+        };
+        messenger.send(&message).await
+    });
+}
+
+/// Edits the pending message with the timestamp `pending_ts` to show each stage that the
+/// operation handling a request reports.
+///
+/// This never finishes, and is instead dropped once the handling of the request finishes.
+async fn update_pending_message(
+    messenger: &impl SlackSendMessage,
+    channel_id: &str,
+    pending_ts: Option<&str>,
+    mut stages: watch::Receiver<Option<RoswaalOperationStage>>,
+) -> Infallible {
+    if let Some(pending_ts) = pending_ts {
+        while stages.changed().await.is_ok() {
+            let Some(stage) = *stages.borrow_and_update() else {
+                continue;
+            };
+            let view = PendingView::at_stage(stage);
+            let message =
+                SlackMessage::new(channel_id, &view, SLACK_UPDATE_MESSAGE_URL).updating(pending_ts);
+            if let Err(error) = messenger.send(&message).await {
+                log::warn!("Failed to update the pending message. {}", error);
+            }
+        }
     }
+    pending().await
+}
+
+/// Handles a `RoswaalSlackInteraction` in the background on a task tracked by `tasks`.
+///
+/// Slack requires interactions to be acknowledged within 3 seconds, and actions typically
+/// interact with git or github, so the resulting view is always sent via `messenger` to the
+/// response url of the interaction once the handling of the action is finished.
+///
+/// The user of the interaction must be allowed to run the command that the action belongs to (see
+/// `RoswaalSlackAction::command`), and the action is recorded in `audit_log` like a command.
+pub fn handle_slack_interaction(
+    handler: Arc<impl RoswaalSlackHandler + Send + Sync>,
+    interaction: RoswaalSlackInteraction,
+    messenger: Arc<impl SlackSendMessage + Send + Sync + 'static>,
+    audit_log: Arc<impl RoswaalRecordAuditLog + Send + Sync + 'static>,
+    tasks: &TaskTracker,
+) {
+    tasks.spawn(async move {
+        let entry = RoswaalAuditLogEntry::new(
+            interaction.user_id(),
+            interaction.action().into(),
+            interaction.value(),
+        );
+        let denied_view = permission_denied_view(
+            handler.as_ref(),
+            &interaction.action().command(),
+            interaction.user_id(),
+            entry.clone(),
+            audit_log.as_ref(),
+        )
+        .await;
+        let view = match denied_view {
+            Some(view) => view,
+            None => {
+                let result = handler
+                    .handle_action(interaction.action(), interaction.value())
+                    .await;
+                audit_log.record(entry.with_result(&result)).await;
+                match result {
+                    Ok(view) => view.erase_to_any_view(),
+                    Err(error) => ErrorView::new(error).erase_to_any_view(),
+                }
+            }
+        };
+        let message =
+            SlackMessage::new(interaction.channel_id(), &view, interaction.response_url());
+        messenger.send(&message).await
+    });
+}
+
+/// Returns the view to respond with if the user of `request` is not allowed to run its command,
+/// and records the denial in `audit_log`.
+async fn request_permission_denied_view(
+    handler: &impl RoswaalSlackHandler,
+    request: &RoswaalSlackRequest,
+    audit_log: &impl RoswaalRecordAuditLog,
+) -> Option<AnySlackView> {
+    let entry = RoswaalAuditLogEntry::new(&request.user_id, request.command.into(), &request.text);
+    permission_denied_view(
+        handler,
+        &request.command,
+        &request.user_id,
+        entry,
+        audit_log,
+    )
+    .await
+}
+
+/// Returns the view to respond with if `user_id` is not allowed to run `command`, and records
+/// `entry` as failed in `audit_log` when that is the case.
+async fn permission_denied_view(
+    handler: &impl RoswaalSlackHandler,
+    command: &RoswaalSlackCommand,
+    user_id: &str,
+    entry: RoswaalAuditLogEntry,
+    audit_log: &impl RoswaalRecordAuditLog,
+) -> Option<AnySlackView> {
+    let (message, view) = match handler.check_permission(command, user_id).await {
+        Ok(CheckPermissionStatus::Allowed) => return None,
+        Ok(status) => (
+            "Not allowed to run this command.".to_string(),
+            PermissionDeniedView::new(status).erase_to_any_view(),
+        ),
+        Err(error) => (error.to_string(), ErrorView::new(error).erase_to_any_view()),
+    };
+    let entry = entry.with_status(RoswaalAuditStatus::Failed { message });
+    audit_log.record(entry).await;
+    Some(view)
 }
 
 async fn view_for_request(
     handler: &impl RoswaalSlackHandler,
     request: &RoswaalSlackRequest,
+    audit_log: &impl RoswaalRecordAuditLog,
 ) -> impl SlackView {
-    match handler
-        .handle_command(&request.command, &request.text)
-        .await
+    let entry = RoswaalAuditLogEntry::new(&request.user_id, request.command.into(), &request.text);
+    let text = if !request.command.imports_slack_files() && contains_slack_file_link(&request.text)
     {
-        Ok(view) => view.erase_to_any_view(),
-        Err(error) => ErrorView::new(error).erase_to_any_view(),
+        match with_slack_file_contents(&request.text, handler).await {
+            Ok(text) => text,
+            Err(error) => {
+                let entry = entry.with_status(RoswaalAuditStatus::Failed {
+                    message: error.to_string(),
+                });
+                audit_log.record(entry).await;
+                return ErrorView::new(error).erase_to_any_view();
+            }
+        }
+    } else {
+        request.text.clone()
+    };
+    let result = handler
+        .handle_command(&request.command, &text, &request.user_id)
+        .await;
+    let entry = entry.with_result(&result);
+    match result {
+        Ok(response) => {
+            let entry = entry.with_branch_name(response.branch_name.as_ref());
+            audit_log.record(entry).await;
+            response.view.erase_to_any_view()
+        }
+        Err(error) => {
+            audit_log.record(entry).await;
+            ErrorView::new(error).erase_to_any_view()
+        }
     }
 }
 
@@ -101,32 +484,68 @@ mod tests {
 
     use crate::{
         slack::{
-            message::{SlackMessage, SlackSendMessage},
+            file::test_support::TestSlackFileFetch,
+            message::{
+                SlackMessage, SlackSendMessage, SLACK_POST_MESSAGE_URL, SLACK_UPDATE_MESSAGE_URL,
+            },
             pending_view::PendingView,
+            permissions::RoswaalSlackPermissionSubject,
             ui_lib::{block_kit_views::SlackDivider, empty_view::EmptySlackView},
+            users::RoswaalSlackUserRoster,
         },
-        utils::test_error::TestError,
+        utils::{stage::RoswaalOperationStage, test_error::TestError},
     };
 
     use super::*;
 
+    const TEST_THREAD_TS: &str = "1700000000.000100";
+
     struct TestSlackMessager {
         messages: Arc<Mutex<Vec<SlackMessage>>>,
+        thread_ts: Option<String>,
     }
 
     impl TestSlackMessager {
         fn new() -> Self {
             Self {
                 messages: Arc::new(Mutex::new(vec![])),
+                thread_ts: Some(TEST_THREAD_TS.to_string()),
+            }
+        }
+
+        /// A messenger that never responds with a timestamp, as if each message was sent to a
+        /// response url.
+        fn without_thread_ts() -> Self {
+            Self {
+                thread_ts: None,
+                ..Self::new()
             }
         }
     }
 
     impl SlackSendMessage for TestSlackMessager {
-        async fn send(&self, message: &SlackMessage) -> Result<(), Error> {
+        async fn send(&self, message: &SlackMessage) -> Result<Option<String>, Error> {
             let mut messages = self.messages.lock().await;
             (*messages).push(message.clone());
-            Ok(())
+            Ok(self.thread_ts.clone())
+        }
+    }
+
+    struct TestAuditLog {
+        entries: Mutex<Vec<RoswaalAuditLogEntry>>,
+    }
+
+    impl TestAuditLog {
+        fn new() -> Self {
+            Self {
+                entries: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    impl RoswaalRecordAuditLog for TestAuditLog {
+        async fn record(&self, entry: RoswaalAuditLogEntry) {
+            self.entries.lock().await.push(entry)
         }
     }
 
@@ -134,11 +553,108 @@ mod tests {
 
     struct SuccessfulHandler;
 
+    impl SlackFetchFile for SuccessfulHandler {
+        async fn fetch_file(&self, _: &str) -> Result<String, Error> {
+            Err(Error::new(TestError))
+        }
+    }
+
     impl RoswaalSlackHandler for SuccessfulHandler {
         async fn handle_command(
             &self,
             _: &RoswaalSlackCommand,
             _: &str,
+            _: &str,
+        ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+            Ok(RoswaalSlackCommandResponse::new(TEST_VIEW))
+        }
+
+        async fn check_permission(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+        ) -> Result<CheckPermissionStatus, Error> {
+            Ok(CheckPermissionStatus::Allowed)
+        }
+
+        async fn handle_action(
+            &self,
+            _: &RoswaalSlackAction,
+            _: &str,
+        ) -> Result<impl SlackView, Error> {
+            Ok(TEST_VIEW)
+        }
+    }
+
+    struct SlowHandler;
+
+    impl SlackFetchFile for SlowHandler {
+        async fn fetch_file(&self, _: &str) -> Result<String, Error> {
+            Err(Error::new(TestError))
+        }
+    }
+
+    impl RoswaalSlackHandler for SlowHandler {
+        async fn handle_command(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+            _: &str,
+        ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+            sleep(Duration::from_millis(50)).await;
+            Ok(RoswaalSlackCommandResponse::new(TEST_VIEW))
+        }
+
+        async fn check_permission(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+        ) -> Result<CheckPermissionStatus, Error> {
+            Ok(CheckPermissionStatus::Allowed)
+        }
+
+        async fn handle_action(
+            &self,
+            _: &RoswaalSlackAction,
+            _: &str,
+        ) -> Result<impl SlackView, Error> {
+            sleep(Duration::from_millis(50)).await;
+            Ok(TEST_VIEW)
+        }
+    }
+
+    struct StagedHandler;
+
+    impl SlackFetchFile for StagedHandler {
+        async fn fetch_file(&self, _: &str) -> Result<String, Error> {
+            Err(Error::new(TestError))
+        }
+    }
+
+    impl RoswaalSlackHandler for StagedHandler {
+        async fn handle_command(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+            _: &str,
+        ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+            RoswaalOperationStage::Pushing.report();
+            sleep(Duration::from_millis(1)).await;
+            Ok(RoswaalSlackCommandResponse::new(TEST_VIEW))
+        }
+
+        async fn check_permission(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+        ) -> Result<CheckPermissionStatus, Error> {
+            Ok(CheckPermissionStatus::Allowed)
+        }
+
+        async fn handle_action(
+            &self,
+            _: &RoswaalSlackAction,
+            _: &str,
         ) -> Result<impl SlackView, Error> {
             Ok(TEST_VIEW)
         }
@@ -146,23 +662,146 @@ mod tests {
 
     struct FailingHandler;
 
+    impl SlackFetchFile for FailingHandler {
+        async fn fetch_file(&self, _: &str) -> Result<String, Error> {
+            Err(Error::new(TestError))
+        }
+    }
+
     impl RoswaalSlackHandler for FailingHandler {
         async fn handle_command(
             &self,
             _: &RoswaalSlackCommand,
             _: &str,
+            _: &str,
+        ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+            Err::<RoswaalSlackCommandResponse<EmptySlackView>, Error>(Error::new(TestError))
+        }
+
+        async fn check_permission(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+        ) -> Result<CheckPermissionStatus, Error> {
+            Ok(CheckPermissionStatus::Allowed)
+        }
+
+        async fn handle_action(
+            &self,
+            _: &RoswaalSlackAction,
+            _: &str,
         ) -> Result<impl SlackView, Error> {
             Err::<EmptySlackView, Error>(Error::new(TestError))
         }
     }
 
+    /// A handler that never allows the user to run a command.
+    struct DeniedHandler;
+
+    impl DeniedHandler {
+        fn denied_status() -> CheckPermissionStatus {
+            CheckPermissionStatus::Denied {
+                command: RoswaalSlackCommand::RemoveTests,
+                subjects: vec![RoswaalSlackPermissionSubject::User("U1".to_string())],
+                roster: RoswaalSlackUserRoster::default(),
+            }
+        }
+    }
+
+    impl SlackFetchFile for DeniedHandler {
+        async fn fetch_file(&self, _: &str) -> Result<String, Error> {
+            Err(Error::new(TestError))
+        }
+    }
+
+    impl RoswaalSlackHandler for DeniedHandler {
+        async fn handle_command(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+            _: &str,
+        ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+            Ok(RoswaalSlackCommandResponse::new(TEST_VIEW))
+        }
+
+        async fn check_permission(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+        ) -> Result<CheckPermissionStatus, Error> {
+            Ok(Self::denied_status())
+        }
+
+        async fn handle_action(
+            &self,
+            _: &RoswaalSlackAction,
+            _: &str,
+        ) -> Result<impl SlackView, Error> {
+            Ok(TEST_VIEW)
+        }
+    }
+
+    /// A handler that records the command texts it receives after slack files are fetched.
+    struct FileReadingHandler {
+        files: TestSlackFileFetch,
+        command_texts: Mutex<Vec<String>>,
+    }
+
+    impl FileReadingHandler {
+        fn new(files: &[(&str, &str)]) -> Self {
+            Self {
+                files: TestSlackFileFetch::new(files),
+                command_texts: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    impl SlackFetchFile for FileReadingHandler {
+        async fn fetch_file(&self, file_id: &str) -> Result<String, Error> {
+            self.files.fetch_file(file_id).await
+        }
+    }
+
+    impl RoswaalSlackHandler for FileReadingHandler {
+        async fn handle_command(
+            &self,
+            _: &RoswaalSlackCommand,
+            command_text: &str,
+            _: &str,
+        ) -> Result<RoswaalSlackCommandResponse<impl SlackView>, Error> {
+            self.command_texts
+                .lock()
+                .await
+                .push(command_text.to_string());
+            Ok(RoswaalSlackCommandResponse::new(TEST_VIEW))
+        }
+
+        async fn check_permission(
+            &self,
+            _: &RoswaalSlackCommand,
+            _: &str,
+        ) -> Result<CheckPermissionStatus, Error> {
+            Ok(CheckPermissionStatus::Allowed)
+        }
+
+        async fn handle_action(
+            &self,
+            _: &RoswaalSlackAction,
+            _: &str,
+        ) -> Result<impl SlackView, Error> {
+            Ok(TEST_VIEW)
+        }
+    }
+
     impl RoswaalSlackRequest {
         fn for_testing(command: RoswaalSlackCommand) -> Self {
             Self {
                 channel_id: "bob".to_string(),
+                user_id: "U01234567".to_string(),
                 text: "abc, 12.080282, 120.298722".to_string(),
                 command,
                 response_url: "https://api.slack.com/chat.postMessage".to_string(),
+                trigger_id: None,
             }
         }
     }
@@ -174,25 +813,95 @@ mod tests {
             Arc::new(SuccessfulHandler),
             RoswaalSlackRequest::for_testing(RoswaalSlackCommand::ViewLocations),
             messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
         )
         .await;
-        assert_eq!(blocks, render_slack_view(&MessageView::new(&TEST_VIEW)));
+        assert_eq!(
+            blocks,
+            Some(render_slack_view(&MessageView::new(&TEST_VIEW)))
+        );
         assert!(messenger.messages.lock().await.is_empty())
     }
 
     #[tokio::test]
-    async fn long_running_command_sends_a_deffered_message() {
+    async fn long_running_command_replies_in_the_thread_of_a_pending_message() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        let expected_messages = vec![
+            SlackMessage::new(
+                &request.channel_id,
+                &PendingView::new(),
+                SLACK_POST_MESSAGE_URL,
+            ),
+            SlackMessage::new(&request.channel_id, &TEST_VIEW, SLACK_POST_MESSAGE_URL)
+                .in_thread(TEST_THREAD_TS),
+        ];
+        let blocks = handle_slack_request(
+            Arc::new(SuccessfulHandler),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        assert_eq!(blocks, None);
+        wait().await;
+        let messages = messenger.messages.lock().await;
+        let messages = (*messages).clone();
+        assert_eq!(messages, expected_messages)
+    }
+
+    #[tokio::test]
+    async fn long_running_command_edits_the_pending_message_with_reported_stages() {
         let messenger = Arc::new(TestSlackMessager::new());
         let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        let pushing_view = PendingView::at_stage(RoswaalOperationStage::Pushing);
+        let expected_messages = vec![
+            SlackMessage::new(
+                &request.channel_id,
+                &PendingView::new(),
+                SLACK_POST_MESSAGE_URL,
+            ),
+            SlackMessage::new(&request.channel_id, &pushing_view, SLACK_UPDATE_MESSAGE_URL)
+                .updating(TEST_THREAD_TS),
+            SlackMessage::new(&request.channel_id, &TEST_VIEW, SLACK_POST_MESSAGE_URL)
+                .in_thread(TEST_THREAD_TS),
+        ];
+        handle_slack_request(
+            Arc::new(StagedHandler),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        wait().await;
+        let messages = messenger.messages.lock().await;
+        assert_eq!(*messages, expected_messages)
+    }
+
+    #[tokio::test]
+    async fn long_running_command_sends_a_deffered_message_when_pending_message_has_no_thread() {
+        let messenger = Arc::new(TestSlackMessager::without_thread_ts());
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
         let expected_message =
             SlackMessage::new(&request.channel_id, &TEST_VIEW, &request.response_url);
-        let blocks =
-            handle_slack_request(Arc::new(SuccessfulHandler), request, messenger.clone()).await;
-        assert_eq!(blocks, render_slack_view(&PendingView));
+        handle_slack_request(
+            Arc::new(SuccessfulHandler),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
         wait().await;
         let messages = messenger.messages.lock().await;
-        let messages = (*messages).clone();
-        assert_eq!(messages, vec![expected_message])
+        assert_eq!((*messages).last(), Some(&expected_message))
     }
 
     #[tokio::test]
@@ -202,9 +911,12 @@ mod tests {
             Arc::new(FailingHandler),
             RoswaalSlackRequest::for_testing(RoswaalSlackCommand::ViewLocations),
             messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
         )
         .await;
-        assert_error_blocks(&blocks);
+        assert_error_blocks(&blocks.unwrap());
         assert!(messenger.messages.lock().await.is_empty())
     }
 
@@ -212,14 +924,448 @@ mod tests {
     async fn long_running_command_sends_a_deffered_error_message_when_failure_occurs() {
         let messenger = Arc::new(TestSlackMessager::new());
         let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
-        let blocks =
-            handle_slack_request(Arc::new(FailingHandler), request, messenger.clone()).await;
-        assert_eq!(blocks, render_slack_view(&PendingView));
+        let blocks = handle_slack_request(
+            Arc::new(FailingHandler),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        assert_eq!(blocks, None);
+        wait().await;
+        let messages = messenger.messages.lock().await;
+        assert_error_blocks((*messages).last().unwrap())
+    }
+
+    #[tokio::test]
+    async fn interaction_sends_a_deferred_message_to_response_url() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let interaction =
+            RoswaalSlackInteraction::for_testing(RoswaalSlackAction::RecompileTests, "abc");
+        let expected_message = SlackMessage::new(
+            interaction.channel_id(),
+            &TEST_VIEW,
+            interaction.response_url(),
+        );
+        let expected_entry =
+            RoswaalAuditLogEntry::new(interaction.user_id(), "recompile-tests", "abc");
+        handle_slack_interaction(
+            Arc::new(SuccessfulHandler),
+            interaction,
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+        );
+        wait().await;
+        let messages = messenger.messages.lock().await;
+        let messages = (*messages).clone();
+        assert_eq!(messages, vec![expected_message]);
+        let entries = audit_log.entries.lock().await;
+        assert_eq!(*entries, vec![expected_entry])
+    }
+
+    #[tokio::test]
+    async fn interaction_responds_with_denial_when_user_is_not_allowed() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let interaction = RoswaalSlackInteraction::for_testing(
+            RoswaalSlackAction::CloseBranch,
+            "roswaal-add-tests-0123456789",
+        );
+        let expected_message = SlackMessage::new(
+            interaction.channel_id(),
+            &PermissionDeniedView::new(DeniedHandler::denied_status()),
+            interaction.response_url(),
+        );
+        let expected_entry = RoswaalAuditLogEntry::new(
+            interaction.user_id(),
+            "close-branch",
+            "roswaal-add-tests-0123456789",
+        )
+        .with_status(RoswaalAuditStatus::Failed {
+            message: "Not allowed to run this command.".to_string(),
+        });
+        handle_slack_interaction(
+            Arc::new(DeniedHandler),
+            interaction,
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+        );
+        wait().await;
+        let messages = messenger.messages.lock().await;
+        let messages = (*messages).clone();
+        assert_eq!(messages, vec![expected_message]);
+        let entries = audit_log.entries.lock().await;
+        assert_eq!(*entries, vec![expected_entry])
+    }
+
+    #[tokio::test]
+    async fn interaction_sends_a_deferred_error_message_when_failure_occurs() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let interaction =
+            RoswaalSlackInteraction::for_testing(RoswaalSlackAction::CloseBranch, "abc");
+        handle_slack_interaction(
+            Arc::new(FailingHandler),
+            interaction,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+        );
         wait().await;
         let messages = messenger.messages.lock().await;
         assert_error_blocks((*messages).first().unwrap())
     }
 
+    #[tokio::test]
+    async fn closing_tasks_waits_for_deferred_messages() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let tasks = TaskTracker::new();
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        handle_slack_request(
+            Arc::new(SlowHandler),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &tasks,
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        tasks.close();
+        tasks.wait().await;
+        let messages = messenger.messages.lock().await;
+        assert_eq!(messages.len(), 2)
+    }
+
+    #[tokio::test]
+    async fn records_handled_commands_in_the_audit_log() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::ViewLocations);
+        handle_slack_request(
+            Arc::new(SuccessfulHandler),
+            request.clone(),
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        handle_slack_request(
+            Arc::new(FailingHandler),
+            request.clone(),
+            messenger,
+            audit_log.clone(),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        let entries = audit_log.entries.lock().await;
+        let expected_entry =
+            RoswaalAuditLogEntry::new(&request.user_id, "/view-locations", &request.text);
+        assert_eq!(
+            *entries,
+            vec![
+                expected_entry.clone(),
+                expected_entry.with_status(RoswaalAuditStatus::Failed {
+                    message: TestError.to_string()
+                })
+            ]
+        )
+    }
+
+    #[tokio::test]
+    async fn does_not_handle_commands_that_slack_likely_cut_off() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        request.text = "a".repeat(TRUNCATED_COMMAND_TEXT_LENGTH);
+        let blocks = handle_slack_request(
+            Arc::new(SuccessfulHandler),
+            request,
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        assert_eq!(
+            blocks,
+            Some(render_slack_view(&MessageView::new(&LongCommandTextView)))
+        );
+        wait().await;
+        assert!(messenger.messages.lock().await.is_empty());
+        assert!(audit_log.entries.lock().await.is_empty())
+    }
+
+    #[tokio::test]
+    async fn handles_commands_with_the_contents_of_linked_slack_files_in_the_background() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let handler = Arc::new(FileReadingHandler::new(&[(
+            "F0123ABCD",
+            &"a".repeat(TRUNCATED_COMMAND_TEXT_LENGTH),
+        )]));
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::LintTests);
+        request.text = "<https://tif.slack.com/files/U01234567/F0123ABCD/tests.txt>".to_string();
+        let blocks = handle_slack_request(
+            handler.clone(),
+            request.clone(),
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        assert_eq!(blocks, None);
+        wait().await;
+        assert_eq!(
+            *handler.command_texts.lock().await,
+            vec!["a".repeat(TRUNCATED_COMMAND_TEXT_LENGTH)]
+        );
+        assert_eq!(messenger.messages.lock().await.len(), 2);
+        let entries = audit_log.entries.lock().await;
+        let expected_entry =
+            RoswaalAuditLogEntry::new(&request.user_id, "/lint-tests", &request.text);
+        assert_eq!(*entries, vec![expected_entry])
+    }
+
+    #[tokio::test]
+    async fn leaves_linked_slack_files_to_commands_that_import_them() {
+        let handler = Arc::new(FileReadingHandler::new(&[("F0123ABCD", "a, 1.0, 1.0")]));
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddLocations);
+        request.text = "<https://tif.slack.com/files/U01234567/F0123ABCD/a.csv>".to_string();
+        handle_slack_request(
+            handler.clone(),
+            request.clone(),
+            Arc::new(TestSlackMessager::new()),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        wait().await;
+        let command_texts = handler.command_texts.lock().await;
+        assert_eq!(*command_texts, vec![request.text])
+    }
+
+    #[tokio::test]
+    async fn sends_error_message_when_linked_slack_file_cannot_be_fetched() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let handler = Arc::new(FileReadingHandler::new(&[]));
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        request.text = "<https://tif.slack.com/files/U01234567/F0123ABCD/tests.txt>".to_string();
+        handle_slack_request(
+            handler.clone(),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        wait().await;
+        assert!(handler.command_texts.lock().await.is_empty());
+        let messages = messenger.messages.lock().await;
+        assert_error_blocks((*messages).last().unwrap())
+    }
+
+    #[tokio::test]
+    async fn modal_submission_sends_the_handled_text_to_the_channel_of_the_modal() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let handler = Arc::new(FileReadingHandler::new(&[]));
+        let submission = RoswaalSlackModalSubmission::for_testing(
+            RoswaalSlackModal::AddTests,
+            "New Test: Join an event",
+        );
+        handle_slack_modal_submission(
+            handler.clone(),
+            submission,
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        );
+        wait().await;
+        assert_eq!(
+            *handler.command_texts.lock().await,
+            vec!["New Test: Join an event".to_string()]
+        );
+        let expected_messages = vec![
+            SlackMessage::new("bob", &PendingView::new(), SLACK_POST_MESSAGE_URL),
+            SlackMessage::new("bob", &TEST_VIEW, SLACK_POST_MESSAGE_URL).in_thread(TEST_THREAD_TS),
+        ];
+        assert_eq!(*messenger.messages.lock().await, expected_messages);
+        let expected_entry =
+            RoswaalAuditLogEntry::new("U01234567", "/add-tests", "New Test: Join an event");
+        let entries = audit_log.entries.lock().await;
+        assert_eq!(*entries, vec![expected_entry])
+    }
+
+    #[tokio::test]
+    async fn cancel_command_stops_the_latest_command_in_the_channel() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let in_flight = RoswaalInFlightOperations::default();
+        let tasks = TaskTracker::new();
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        handle_slack_request(
+            Arc::new(SlowHandler),
+            request.clone(),
+            messenger.clone(),
+            audit_log.clone(),
+            &tasks,
+            &in_flight,
+        )
+        .await;
+        let mut cancel_request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::Cancel);
+        cancel_request.text = String::new();
+        let blocks = handle_slack_request(
+            Arc::new(SuccessfulHandler),
+            cancel_request.clone(),
+            messenger.clone(),
+            audit_log.clone(),
+            &tasks,
+            &in_flight,
+        )
+        .await;
+        assert_eq!(
+            blocks,
+            Some(render_slack_view(&MessageView::new(&CancelView::new(
+                Some(RoswaalSlackCommand::AddTests)
+            ))))
+        );
+        tasks.close();
+        tasks.wait().await;
+        let expected_message = SlackMessage::new(
+            &request.channel_id,
+            &OperationCancelledView::new(RoswaalSlackCommand::AddTests),
+            SLACK_POST_MESSAGE_URL,
+        )
+        .in_thread(TEST_THREAD_TS);
+        assert_eq!(
+            messenger.messages.lock().await.last(),
+            Some(&expected_message)
+        );
+        assert_eq!(in_flight.len(), 0);
+        let entries = audit_log.entries.lock().await;
+        let expected_entries = vec![
+            RoswaalAuditLogEntry::new(&request.user_id, "/roswaal-cancel", ""),
+            RoswaalAuditLogEntry::new(&request.user_id, "/add-tests", &request.text).with_status(
+                RoswaalAuditStatus::Failed {
+                    message: "Cancelled with /roswaal-cancel.".to_string(),
+                },
+            ),
+        ];
+        assert_eq!(*entries, expected_entries)
+    }
+
+    #[tokio::test]
+    async fn cancel_command_responds_when_there_is_nothing_to_cancel() {
+        let in_flight = RoswaalInFlightOperations::default();
+        let tasks = TaskTracker::new();
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        request.channel_id = "other".to_string();
+        handle_slack_request(
+            Arc::new(SlowHandler),
+            request,
+            Arc::new(TestSlackMessager::new()),
+            Arc::new(TestAuditLog::new()),
+            &tasks,
+            &in_flight,
+        )
+        .await;
+        let blocks = handle_slack_request(
+            Arc::new(SuccessfulHandler),
+            RoswaalSlackRequest::for_testing(RoswaalSlackCommand::Cancel),
+            Arc::new(TestSlackMessager::new()),
+            Arc::new(TestAuditLog::new()),
+            &tasks,
+            &in_flight,
+        )
+        .await;
+        assert_eq!(
+            blocks,
+            Some(render_slack_view(&MessageView::new(&CancelView::new(None))))
+        )
+    }
+
+    #[test]
+    fn opens_modal_only_for_empty_add_tests_commands_with_a_trigger_id() {
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::AddTests);
+        request.trigger_id = Some("123.456".to_string());
+        assert_eq!(request.modal_to_open(), None);
+        request.text = "  ".to_string();
+        request.trigger_id = None;
+        assert_eq!(request.modal_to_open(), None);
+        request.trigger_id = Some("123.456".to_string());
+        assert_eq!(
+            request.modal_to_open(),
+            Some((RoswaalSlackModal::AddTests.slack_modal("bob"), "123.456"))
+        );
+        let mut request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::ViewTests);
+        request.trigger_id = Some("123.456".to_string());
+        request.text = String::new();
+        assert_eq!(request.modal_to_open(), None)
+    }
+
+    #[tokio::test]
+    async fn responds_with_denial_when_user_is_not_allowed_to_run_command() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let audit_log = Arc::new(TestAuditLog::new());
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::ViewLocations);
+        let blocks = handle_slack_request(
+            Arc::new(DeniedHandler),
+            request.clone(),
+            messenger.clone(),
+            audit_log.clone(),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        let denied_view = PermissionDeniedView::new(DeniedHandler::denied_status());
+        assert_eq!(
+            blocks,
+            Some(render_slack_view(&MessageView::new(&denied_view)))
+        );
+        assert!(messenger.messages.lock().await.is_empty());
+        let expected_entry =
+            RoswaalAuditLogEntry::new(&request.user_id, "/view-locations", &request.text)
+                .with_status(RoswaalAuditStatus::Failed {
+                    message: "Not allowed to run this command.".to_string(),
+                });
+        let entries = audit_log.entries.lock().await;
+        assert_eq!(*entries, vec![expected_entry])
+    }
+
+    #[tokio::test]
+    async fn long_running_command_sends_only_the_denial_when_user_is_not_allowed() {
+        let messenger = Arc::new(TestSlackMessager::new());
+        let request = RoswaalSlackRequest::for_testing(RoswaalSlackCommand::RemoveTests);
+        let expected_message = SlackMessage::new(
+            &request.channel_id,
+            &PermissionDeniedView::new(DeniedHandler::denied_status()),
+            &request.response_url,
+        );
+        let blocks = handle_slack_request(
+            Arc::new(DeniedHandler),
+            request,
+            messenger.clone(),
+            Arc::new(TestAuditLog::new()),
+            &TaskTracker::new(),
+            &RoswaalInFlightOperations::default(),
+        )
+        .await;
+        assert_eq!(blocks, None);
+        wait().await;
+        let messages = messenger.messages.lock().await;
+        assert_eq!(*messages, vec![expected_message])
+    }
+
     fn assert_error_blocks(blocks: &impl Serialize) {
         let json = serde_json::to_string(&blocks).unwrap();
         assert!(json.contains("An Error Occurred"));
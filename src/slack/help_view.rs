@@ -0,0 +1,208 @@
+use std::str::FromStr;
+
+use strum::IntoEnumIterator;
+
+use super::{
+    command::RoswaalSlackCommand,
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        for_each_view::ForEachView,
+        slack_view::SlackView,
+    },
+};
+
+/// A summary of each line that can appear in a test, paired with what the line does.
+const TEST_LANGUAGE_GRAMMAR: [(&str, &str); 14] = [
+    ("New Test: <name>", "Starts a new test."),
+    (
+        "Abstract: <description>",
+        "Describes the scenario of the test.",
+    ),
+    (
+        "Step <n>: <description> (retries: <count>)",
+        "Describes what a user does, the retries are optional.",
+    ),
+    (
+        "Requirement <n>: <description>",
+        "Describes how to perform the step with the same number.",
+    ),
+    (
+        "Verify <label>: <outcome>",
+        "Checks that something happened.",
+    ),
+    (
+        "Using <label>: <description>",
+        "Describes how to check the verification with the same label.",
+    ),
+    (
+        "Set Location: <location name>",
+        "Sets the location of the app to a location from `/view-locations`.",
+    ),
+    (
+        "Wait: <amount> <ms | seconds | minutes>",
+        "Pauses the test for up to 5 minutes.",
+    ),
+    (
+        "Before Launch: <description>",
+        "Describes setup work to perform before the app launches.",
+    ),
+    (
+        "Requires Test: <test name>",
+        "Runs the steps of a merged test first.",
+    ),
+    ("Tags: <tag>, <tag>", "Labels the test with tags."),
+    ("Suite: <name>", "Places the test in a suite."),
+    (
+        "Platforms: <platform>, <platform>",
+        "Only runs the test on ios or android.",
+    ),
+    (
+        "Variables: <name> = <value>, <name> = <value>",
+        "Declares values for the `{{name}}` placeholders in the steps.",
+    ),
+];
+
+/// A view that explains how to use each command.
+pub struct HelpView {
+    topic: HelpTopic,
+}
+
+enum HelpTopic {
+    AllCommands,
+    Command(RoswaalSlackCommand),
+    UnknownCommand(String),
+}
+
+impl HelpView {
+    /// Creates a help view for the command named by `command_text`, or for every command if the
+    /// text is empty.
+    ///
+    /// The leading slash of the command name is optional.
+    pub fn from_command_text(command_text: &str) -> Self {
+        let name = command_text.trim();
+        let topic = if name.is_empty() {
+            HelpTopic::AllCommands
+        } else {
+            let slash_name = format!("/{}", name.trim_start_matches('/'));
+            match RoswaalSlackCommand::from_str(&slash_name) {
+                Ok(command) => HelpTopic::Command(command),
+                Err(_) => HelpTopic::UnknownCommand(name.to_string()),
+            }
+        };
+        Self { topic }
+    }
+}
+
+impl SlackView for HelpView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Roswaal Help").flat_chain_block(self.topic_view())
+    }
+}
+
+impl HelpView {
+    fn topic_view(&self) -> impl SlackView {
+        match &self.topic {
+            HelpTopic::AllCommands => SlackSection::from_markdown(&format!(
+                "Heeeeeeere are all the commands I know! Use `{} <command>` to see examples.",
+                RoswaalSlackCommand::Help
+            ))
+            .flat_chain_block(ForEachView::new(RoswaalSlackCommand::iter(), |command| {
+                SlackSection::from_markdown(&format!(
+                    "*{}*\n{}",
+                    Self::usage(*command),
+                    command.summary()
+                ))
+            }))
+            .erase_to_any_view(),
+            HelpTopic::Command(command) => SlackSection::from_markdown(&format!(
+                "*{}*\n{}",
+                Self::usage(*command),
+                command.summary()
+            ))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackHeader::new("Examples"))
+            .flat_chain_block(ForEachView::new(command.examples().iter(), |example| {
+                let text = if example.is_empty() {
+                    command.to_string()
+                } else {
+                    format!("{}\n{}", command, example)
+                };
+                SlackSection::from_markdown(&format!("```{}```", text))
+            }))
+            .flat_chain_block(command.uses_test_language().then(Self::grammar_view))
+            .erase_to_any_view(),
+            HelpTopic::UnknownCommand(name) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not a command I knoooooow. Use `{}` to see every command.",
+                name,
+                RoswaalSlackCommand::Help
+            ))
+            .erase_to_any_view(),
+        }
+    }
+
+    fn grammar_view() -> impl SlackView {
+        let lines = TEST_LANGUAGE_GRAMMAR
+            .iter()
+            .map(|(syntax, description)| format!("`{}` {}", syntax, description))
+            .collect::<Vec<String>>()
+            .join("\n");
+        SlackDivider
+            .flat_chain_block(SlackHeader::new("Test Language"))
+            .flat_chain_block(SlackSection::from_markdown(&format!(
+                "Put the tests in a code block (```), and separate tests with a `New Test` line.\n{}",
+                lines
+            )))
+    }
+
+    fn usage(command: RoswaalSlackCommand) -> String {
+        let syntax = command.syntax();
+        if syntax.is_empty() {
+            format!("`{}`", command)
+        } else {
+            format!("`{} {}`", command, syntax)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::HelpView;
+
+    #[test]
+    fn all_commands_snapshot() {
+        assert_slack_view_snapshot(
+            "help-all-commands",
+            &HelpView::from_command_text(" "),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn test_language_command_snapshot() {
+        assert_slack_view_snapshot(
+            "help-add-tests",
+            &HelpView::from_command_text("add-tests"),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn command_snapshot() {
+        assert_slack_view_snapshot(
+            "help-roswaal-owners",
+            &HelpView::from_command_text("/roswaal-owners"),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn unknown_command_snapshot() {
+        assert_slack_view_snapshot(
+            "help-unknown-command",
+            &HelpView::from_command_text("/juggle"),
+            SnapshotMode::Comparing,
+        )
+    }
+}
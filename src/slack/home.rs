@@ -0,0 +1,172 @@
+use std::{env, future::Future};
+
+use anyhow::Result;
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    message::{SlackMessageSendingError, SlackResponse},
+    ui_lib::home_tab::SlackHomeTab,
+};
+
+/// The url of the slack API method for publishing the Home tab of a user.
+pub const SLACK_PUBLISH_VIEW_URL: &str = "https://slack.com/api/views.publish";
+
+/// An event that slack sends to the events endpoint.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalSlackEvent {
+    /// Sent when the events url is saved in the slack app console. The challenge must be sent
+    /// back in the response.
+    UrlVerification { challenge: String },
+    /// Sent when the user with `user_id` opens the Home tab of the app.
+    AppHomeOpened { user_id: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct EventPayload {
+    #[serde(rename = "type")]
+    _type: String,
+    challenge: Option<String>,
+    event: Option<EventPayloadEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EventPayloadEvent {
+    #[serde(rename = "type")]
+    _type: String,
+    user: Option<String>,
+    tab: Option<String>,
+}
+
+impl RoswaalSlackEvent {
+    /// Parses an event from the JSON body sent by slack.
+    ///
+    /// Returns None for events that this tool does not handle (including `app_home_opened`
+    /// events for the Messages tab). An error is returned if the body is not valid JSON.
+    pub fn from_json(body: &str) -> serde_json::Result<Option<Self>> {
+        let payload = serde_json::from_str::<EventPayload>(body)?;
+        match (payload._type.as_str(), payload.challenge, payload.event) {
+            ("url_verification", Some(challenge), _) => {
+                Ok(Some(Self::UrlVerification { challenge }))
+            }
+            ("event_callback", _, Some(event)) => {
+                let is_home_tab = event.tab.as_deref() == Some("home");
+                match (event._type.as_str(), event.user) {
+                    ("app_home_opened", Some(user_id)) if is_home_tab => {
+                        Ok(Some(Self::AppHomeOpened { user_id }))
+                    }
+                    _ => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// A trait for publishing the Home tab of a user.
+pub trait SlackPublishHomeTab {
+    /// Replaces the Home tab of the user with `user_id` with `home_tab`.
+    fn publish_home_tab(
+        &self,
+        user_id: &str,
+        home_tab: &SlackHomeTab,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
+
+#[derive(Serialize)]
+struct PublishViewRequest<'a> {
+    user_id: &'a str,
+    view: &'a SlackHomeTab,
+}
+
+impl SlackPublishHomeTab for Client {
+    async fn publish_home_tab(&self, user_id: &str, home_tab: &SlackHomeTab) -> Result<()> {
+        let token = env::var("SLACK_BOT_TOKEN").expect(
+            "SLACK_BOT_TOKEN not found in .env, you can get one from the slack app console.",
+        );
+        let resp = self
+            .post(SLACK_PUBLISH_VIEW_URL)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&PublishViewRequest {
+                user_id,
+                view: home_tab,
+            })
+            .bearer_auth(token)
+            .send()
+            .await?;
+        let slack_resp = resp.json::<SlackResponse>().await?;
+        match slack_resp.error {
+            Some(error) => {
+                log::error!(
+                    "A Slack API error occured when publishing the home tab {}.",
+                    error
+                );
+                Err(anyhow::Error::new(SlackMessageSendingError {
+                    message: error,
+                }))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_url_verification() {
+        let body = json!({
+            "type": "url_verification",
+            "token": "abc",
+            "challenge": "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P"
+        });
+        assert_eq!(
+            RoswaalSlackEvent::from_json(&body.to_string()).unwrap(),
+            Some(RoswaalSlackEvent::UrlVerification {
+                challenge: "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P".to_string()
+            })
+        )
+    }
+
+    #[test]
+    fn parses_app_home_opened_for_home_tab() {
+        let event = |tab: &str| {
+            json!({
+                "type": "event_callback",
+                "event": {
+                    "type": "app_home_opened",
+                    "user": "U123",
+                    "channel": "D123",
+                    "tab": tab
+                }
+            })
+            .to_string()
+        };
+        assert_eq!(
+            RoswaalSlackEvent::from_json(&event("home")).unwrap(),
+            Some(RoswaalSlackEvent::AppHomeOpened {
+                user_id: "U123".to_string()
+            })
+        );
+        assert_eq!(
+            RoswaalSlackEvent::from_json(&event("messages")).unwrap(),
+            None
+        )
+    }
+
+    #[test]
+    fn ignores_other_events() {
+        let body = json!({
+            "type": "event_callback",
+            "event": { "type": "app_mention", "user": "U123" }
+        });
+        assert_eq!(
+            RoswaalSlackEvent::from_json(&body.to_string()).unwrap(),
+            None
+        );
+        assert!(RoswaalSlackEvent::from_json("explode").is_err())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    operations::home_summary::RoswaalHomeSummary, tests_data::test::RoswaalTestProgressStatus,
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for the Home tab of the app that gives an overview of the tests and pending branches.
+pub struct HomeView {
+    summary: RoswaalHomeSummary,
+}
+
+impl HomeView {
+    pub fn new(summary: RoswaalHomeSummary) -> Self {
+        Self { summary }
+    }
+}
+
+impl SlackView for HomeView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Acceptance Tests")
+            .flat_chain_block(SlackSection::from_markdown(&self.counts_body()))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackHeader::new("Recently Failed Tests"))
+            .flat_chain_block(SlackSection::from_markdown(&self.failed_tests_body()))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackHeader::new("Pending Branches"))
+            .flat_chain_block(SlackSection::from_markdown(&self.branches_body()))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackSection::from_markdown(
+                "Use `/view-tests` to view the progress of each test, and `/branches` to close a pending branch!",
+            ))
+    }
+}
+
+impl HomeView {
+    fn counts_body(&self) -> String {
+        format!(
+            "{} *{}* Passing   {} *{}* Failing   {} *{}* Idle",
+            RoswaalTestProgressStatus::Passed.emoji(),
+            self.summary.passed_count(),
+            RoswaalTestProgressStatus::Failed.emoji(),
+            self.summary.failed_count(),
+            RoswaalTestProgressStatus::Idle.emoji(),
+            self.summary.idle_count()
+        )
+    }
+
+    fn failed_tests_body(&self) -> String {
+        let tests = self.summary.recently_failed_tests();
+        if tests.is_empty() {
+            return "✅ No tests are faaaaaailing!".to_string();
+        }
+        let mut body = String::new();
+        for test in tests {
+            let detail = test
+                .error_message()
+                .map(|message| format!(" _({})_", message))
+                .unwrap_or_default();
+            let date = test
+                .last_run_date()
+                .map(|date| format!(" - {}", date.format("%Y-%m-%d %H:%M:%S")))
+                .unwrap_or_default();
+            body.push_str(&format!("- *{}*{}{}\n", test.name(), date, detail))
+        }
+        body
+    }
+
+    fn branches_body(&self) -> String {
+        let names = self.summary.unmerged_branch_names();
+        if names.is_empty() {
+            return "✅ There are no unmerged braaaaaanches!".to_string();
+        }
+        let mut body = String::new();
+        for name in names {
+            body.push_str(&format!("- `{}`\n", name.to_string()))
+        }
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::test::RoswaalCompiledTestCommand,
+        operations::home_summary::RoswaalHomeSummary,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, test::RoswaalTest},
+    };
+
+    use super::HomeView;
+
+    #[test]
+    fn snapshot() {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let earlier_date = "2024-07-20T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let tests = vec![
+            test("Passing", None, None, Some(date)),
+            test(
+                "Failing Earlier",
+                Some(RoswaalTestCommandOrdinal::new(0)),
+                None,
+                Some(earlier_date),
+            ),
+            test(
+                "Failing",
+                Some(RoswaalTestCommandOrdinal::new(0)),
+                Some("Everyone Died"),
+                Some(date),
+            ),
+            test("Idle", None, None, None),
+        ];
+        let summary = RoswaalHomeSummary::new(
+            &tests,
+            vec![
+                branch_name("roswaal-add-tests-0123456789"),
+                branch_name("roswaal-add-locations-Lklj839sda"),
+            ],
+        );
+        assert_slack_view_snapshot(
+            "home-success",
+            &HomeView::new(summary),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn empty_snapshot() {
+        assert_slack_view_snapshot(
+            "home-empty",
+            &HomeView::new(RoswaalHomeSummary::new(&[], vec![])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn test(
+        name: &str,
+        failure_ordinal: Option<RoswaalTestCommandOrdinal>,
+        error_message: Option<&str>,
+        date: Option<DateTime<Utc>>,
+    ) -> RoswaalTest {
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            }],
+            failure_ordinal,
+            error_message.map(|m| m.to_string()),
+            None,
+            None,
+            date,
+        )
+    }
+
+    fn branch_name(name: &str) -> RoswaalOwnedGitBranchName {
+        serde_json::from_str(&format!("\"{}\"", name)).unwrap()
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::{Arc, Mutex};
+
+use tokio_util::sync::CancellationToken;
+
+use super::command::RoswaalSlackCommand;
+
+/// A registry of the slack commands that are being handled in the background.
+///
+/// Each operation is registered under the user and channel that sent its command, so that
+/// `/roswaal-cancel` only cancels the operations started by the same user in the same channel.
+/// Cloning the registry shares the same operations.
+#[derive(Debug, Clone, Default)]
+pub struct RoswaalInFlightOperations {
+    state: Arc<Mutex<InFlightOperationsState>>,
+}
+
+#[derive(Debug, Default)]
+struct InFlightOperationsState {
+    next_id: u64,
+    operations: Vec<InFlightOperation>,
+}
+
+#[derive(Debug)]
+struct InFlightOperation {
+    id: u64,
+    user_id: String,
+    channel_id: String,
+    command: RoswaalSlackCommand,
+    token: CancellationToken,
+}
+
+impl RoswaalInFlightOperations {
+    /// Registers an operation for `command`, and returns a handle that removes the operation
+    /// from this registry when dropped.
+    pub fn start(
+        &self,
+        user_id: &str,
+        channel_id: &str,
+        command: RoswaalSlackCommand,
+    ) -> RoswaalInFlightOperation {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id;
+        state.next_id += 1;
+        let token = CancellationToken::new();
+        state.operations.push(InFlightOperation {
+            id,
+            user_id: user_id.to_string(),
+            channel_id: channel_id.to_string(),
+            command,
+            token: token.clone(),
+        });
+        RoswaalInFlightOperation {
+            id,
+            token,
+            operations: self.clone(),
+        }
+    }
+
+    /// Cancels the most recently started operation of the user in the channel that has not
+    /// already been cancelled, and returns its command.
+    ///
+    /// Returns None if the user has no operations to cancel in the channel.
+    pub fn cancel_latest(&self, user_id: &str, channel_id: &str) -> Option<RoswaalSlackCommand> {
+        let state = self.state.lock().unwrap();
+        let operation = state.operations.iter().rev().find(|operation| {
+            operation.user_id == user_id
+                && operation.channel_id == channel_id
+                && !operation.token.is_cancelled()
+        })?;
+        operation.token.cancel();
+        Some(operation.command)
+    }
+
+    /// Returns the number of operations that have not finished yet.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().operations.len()
+    }
+
+    /// Returns true if every operation has finished.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn finish(&self, id: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .operations
+            .retain(|operation| operation.id != id)
+    }
+}
+
+/// A handle to an operation in a `RoswaalInFlightOperations` registry.
+///
+/// The operation is removed from the registry when this handle is dropped.
+#[derive(Debug)]
+pub struct RoswaalInFlightOperation {
+    id: u64,
+    token: CancellationToken,
+    operations: RoswaalInFlightOperations,
+}
+
+impl RoswaalInFlightOperation {
+    /// Waits until this operation is cancelled.
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
+}
+
+impl Drop for RoswaalInFlightOperation {
+    fn drop(&mut self) {
+        self.operations.finish(self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+
+    #[test]
+    fn cancels_the_latest_operation_of_the_user_in_the_channel() {
+        let operations = RoswaalInFlightOperations::default();
+        let _add_tests = operations.start("U1", "C1", RoswaalSlackCommand::AddTests);
+        let _remove_tests = operations.start("U1", "C1", RoswaalSlackCommand::RemoveTests);
+        let _other_channel = operations.start("U1", "C2", RoswaalSlackCommand::EditTest);
+        let _other_user = operations.start("U2", "C1", RoswaalSlackCommand::AddLocations);
+        assert_eq!(
+            operations.cancel_latest("U1", "C1"),
+            Some(RoswaalSlackCommand::RemoveTests)
+        );
+        assert_eq!(
+            operations.cancel_latest("U1", "C1"),
+            Some(RoswaalSlackCommand::AddTests)
+        );
+        assert_eq!(operations.cancel_latest("U1", "C1"), None)
+    }
+
+    #[tokio::test]
+    async fn cancelling_wakes_up_the_operation() {
+        let operations = RoswaalInFlightOperations::default();
+        let operation = operations.start("U1", "C1", RoswaalSlackCommand::AddTests);
+        operations.cancel_latest("U1", "C1");
+        let result = timeout(Duration::from_secs(1), operation.cancelled()).await;
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn removes_operations_once_their_handles_are_dropped() {
+        let operations = RoswaalInFlightOperations::default();
+        let operation = operations.start("U1", "C1", RoswaalSlackCommand::AddTests);
+        assert_eq!(operations.len(), 1);
+        drop(operation);
+        assert_eq!(operations.len(), 0);
+        assert_eq!(operations.cancel_latest("U1", "C1"), None)
+    }
+}
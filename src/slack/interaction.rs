@@ -0,0 +1,250 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use strum_macros::{Display, EnumString, IntoStaticStr};
+
+use super::{command::RoswaalSlackCommand, ui_lib::block_kit_views::SlackButton};
+
+/// The maximum length of the value of a slack button.
+pub const MAX_ACTION_VALUE_LENGTH: usize = 2000;
+
+/// The interactive actions (eg. button presses) that this tool must respond to.
+#[derive(Debug, PartialEq, Eq, EnumString, IntoStaticStr, Display, Clone, Copy)]
+pub enum RoswaalSlackAction {
+    /// Closes the branch specified by the value of the action.
+    #[strum(serialize = "close-branch")]
+    CloseBranch,
+    /// Adds the tests specified by the value of the action again.
+    #[strum(serialize = "recompile-tests")]
+    RecompileTests,
+    /// Stops posting the failures of the test named by the value of the action.
+    #[strum(serialize = "mute-test-failures")]
+    MuteTestFailures,
+    /// Resumes posting the failures of the test named by the value of the action.
+    #[strum(serialize = "unmute-test-failures")]
+    UnmuteTestFailures,
+}
+
+impl RoswaalSlackAction {
+    /// Returns a button that triggers this action with the specified value when pressed.
+    ///
+    /// Returns None if the value is longer than the `MAX_ACTION_VALUE_LENGTH` allowed by slack.
+    pub fn button(&self, text: &str, value: &str) -> Option<SlackButton> {
+        if value.chars().count() > MAX_ACTION_VALUE_LENGTH {
+            None
+        } else {
+            Some(SlackButton::new(text, self.into(), value))
+        }
+    }
+
+    /// Returns the slash command whose permissions (see `/roswaal-grant`) apply to this action.
+    ///
+    /// Branches are closed from `/branches`, and test failures are muted alongside the other
+    /// test failure controls of `/assign-failure`.
+    pub fn command(&self) -> RoswaalSlackCommand {
+        match self {
+            Self::CloseBranch => RoswaalSlackCommand::ListBranches,
+            Self::RecompileTests => RoswaalSlackCommand::AddTests,
+            Self::MuteTestFailures | Self::UnmuteTestFailures => RoswaalSlackCommand::AssignFailure,
+        }
+    }
+}
+
+/// The form body of an interaction request from slack.
+#[derive(Debug, Deserialize)]
+pub struct RoswaalSlackInteractionForm {
+    payload: String,
+}
+
+/// An action that a user performed on a message sent by this tool.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalSlackInteraction {
+    channel_id: String,
+    user_id: String,
+    response_url: String,
+    action: RoswaalSlackAction,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockActionsPayload {
+    #[serde(rename = "type")]
+    _type: String,
+    channel: Option<PayloadChannel>,
+    user: Option<PayloadUser>,
+    response_url: Option<String>,
+    #[serde(default)]
+    actions: Vec<PayloadAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadChannel {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadUser {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadAction {
+    action_id: String,
+    #[serde(default)]
+    value: String,
+}
+
+impl RoswaalSlackInteractionForm {
+    /// Returns the JSON payload sent by slack.
+    pub(super) fn payload(&self) -> &str {
+        &self.payload
+    }
+}
+
+impl RoswaalSlackInteraction {
+    /// Parses an interaction from the form body sent by slack.
+    ///
+    /// Returns None if the payload is not a `block_actions` payload, if it does not contain an
+    /// action known by this tool, or if it is missing the channel, user, or response url. An
+    /// error is returned if the payload is not valid JSON.
+    pub fn from_form(form: &RoswaalSlackInteractionForm) -> serde_json::Result<Option<Self>> {
+        let payload = serde_json::from_str::<BlockActionsPayload>(&form.payload)?;
+        if payload._type != "block_actions" {
+            return Ok(None);
+        }
+        let (channel, user, response_url) =
+            match (payload.channel, payload.user, payload.response_url) {
+                (Some(channel), Some(user), Some(response_url)) => (channel, user, response_url),
+                _ => return Ok(None),
+            };
+        let interaction = payload.actions.into_iter().find_map(|action| {
+            RoswaalSlackAction::from_str(&action.action_id)
+                .ok()
+                .map(|known_action| Self {
+                    channel_id: channel.id.clone(),
+                    user_id: user.id.clone(),
+                    response_url: response_url.clone(),
+                    action: known_action,
+                    value: action.value,
+                })
+        });
+        Ok(interaction)
+    }
+}
+
+impl RoswaalSlackInteraction {
+    pub fn channel_id(&self) -> &str {
+        &self.channel_id
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    pub fn response_url(&self) -> &str {
+        &self.response_url
+    }
+
+    pub fn action(&self) -> &RoswaalSlackAction {
+        &self.action
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+impl RoswaalSlackInteraction {
+    pub fn for_testing(action: RoswaalSlackAction, value: &str) -> Self {
+        Self {
+            channel_id: "bob".to_string(),
+            user_id: "U01234567".to_string(),
+            response_url: "https://hooks.slack.com/actions/T0/1/abc".to_string(),
+            action,
+            value: value.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl RoswaalSlackInteractionForm {
+    pub fn for_testing(payload: &serde_json::Value) -> Self {
+        Self {
+            payload: payload.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn button_is_none_when_value_too_long() {
+        let value = "a".repeat(MAX_ACTION_VALUE_LENGTH + 1);
+        assert_eq!(
+            RoswaalSlackAction::RecompileTests.button("Re-run", &value),
+            None
+        );
+        let value = "a".repeat(MAX_ACTION_VALUE_LENGTH);
+        assert!(RoswaalSlackAction::RecompileTests
+            .button("Re-run", &value)
+            .is_some())
+    }
+
+    #[test]
+    fn parses_known_block_action() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_actions",
+            "channel": { "id": "C123", "name": "acceptance-tests" },
+            "user": { "id": "U123", "username": "bob" },
+            "response_url": "https://hooks.slack.com/actions/T0/1/abc",
+            "actions": [
+                { "action_id": "close-branch", "value": "roswaal-add-tests-0123456789" }
+            ]
+        }));
+        let interaction = RoswaalSlackInteraction::from_form(&form).unwrap();
+        let expected = RoswaalSlackInteraction {
+            channel_id: "C123".to_string(),
+            user_id: "U123".to_string(),
+            response_url: "https://hooks.slack.com/actions/T0/1/abc".to_string(),
+            action: RoswaalSlackAction::CloseBranch,
+            value: "roswaal-add-tests-0123456789".to_string(),
+        };
+        assert_eq!(interaction, Some(expected))
+    }
+
+    #[test]
+    fn ignores_unknown_actions_and_payload_types() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_actions",
+            "channel": { "id": "C123" },
+            "user": { "id": "U123" },
+            "response_url": "https://hooks.slack.com/actions/T0/1/abc",
+            "actions": [{ "action_id": "explode", "value": "now" }]
+        }));
+        assert_eq!(RoswaalSlackInteraction::from_form(&form).unwrap(), None);
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_actions",
+            "channel": { "id": "C123" },
+            "response_url": "https://hooks.slack.com/actions/T0/1/abc",
+            "actions": [{ "action_id": "close-branch", "value": "roswaal-add-tests-0123456789" }]
+        }));
+        assert_eq!(RoswaalSlackInteraction::from_form(&form).unwrap(), None);
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "view_submission"
+        }));
+        assert_eq!(RoswaalSlackInteraction::from_form(&form).unwrap(), None)
+    }
+
+    #[test]
+    fn errors_when_payload_is_not_json() {
+        let form = RoswaalSlackInteractionForm {
+            payload: "i am not json".to_string(),
+        };
+        assert!(RoswaalSlackInteraction::from_form(&form).is_err())
+    }
+}
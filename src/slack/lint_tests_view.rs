@@ -0,0 +1,145 @@
+use crate::{
+    language::compilation_results::RoswaalTestCompilationResults,
+    operations::lint_tests::LintTestsStatus,
+};
+
+use super::{
+    add_tests_view::NonCompilingTestView,
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        for_each_view::ForEachView,
+        if_view::If,
+        slack_view::SlackView,
+    },
+};
+
+/// A view for checking whether or not tests compile without adding them.
+pub struct LintTestsView<'r> {
+    status: LintTestsStatus<'r>,
+}
+
+impl<'r> LintTestsView<'r> {
+    pub fn new(status: LintTestsStatus<'r>) -> Self {
+        Self { status }
+    }
+}
+
+impl<'r> SlackView for LintTestsView<'r> {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Lint Tests").flat_chain_block(self.status_view())
+    }
+}
+
+impl<'r> LintTestsView<'r> {
+    fn status_view(&self) -> impl SlackView {
+        match &self.status {
+            LintTestsStatus::Success { results } => {
+                If::is_true(results.has_compiling_tests(), || {
+                    self.compiling_tests_view(results)
+                })
+                .flat_chain_block(If::is_true(results.has_non_compiling_tests(), || {
+                    self.non_compiling_tests_view(results)
+                }))
+                .flat_chain_block(SlackDivider)
+                .flat_chain_block(SlackHeader::new("Next Steps"))
+                .flat_chain_block(SlackSection::from_markdown(
+                    "No PR was opened. Use `/add-tests` with the same tests to aaaaaaadd them!",
+                ))
+                .erase_to_any_view()
+            }
+            LintTestsStatus::NoTestsFound => {
+                SlackSection::from_markdown("🔴 No tests were fooooooound.").erase_to_any_view()
+            }
+        }
+    }
+
+    fn compiling_tests_view(&self, results: &RoswaalTestCompilationResults<'r>) -> impl SlackView {
+        let mut body = "✅ *The following tests compile succeeeeeeeeessfully!*\n".to_string();
+        for test in results.tests() {
+            body.push_str(&format!("- {}\n", test.name()))
+        }
+        SlackSection::from_markdown(&body)
+    }
+
+    fn non_compiling_tests_view(
+        &self,
+        results: &RoswaalTestCompilationResults<'r>,
+    ) -> impl SlackView {
+        let failures = results.failures();
+        let iter = failures
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, value)| (index < failures.len() - 1, value));
+        SlackSection::from_markdown(
+            "⚠️ *The following tests did not compile succeeeeeessfully. They are only listed by the number based on the compilation oooooooooorder!*",
+        )
+        .flat_chain_block(ForEachView::new(iter, |(is_showing_divider, failure)| {
+            NonCompilingTestView::new(failure)
+                .flat_chain_block(If::is_true(*is_showing_divider, || SlackDivider))
+        }))
+        .erase_to_any_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        language::{ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults},
+        operations::lint_tests::LintTestsStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::LintTestsView;
+
+    #[test]
+    fn success_mixed_compilation_results_snapshot() {
+        let tests = vec![
+            RoswaalTestSyntax::from(
+                "\
+New Test: Big Chungus III
+Step 1: Big
+",
+            ),
+            RoswaalTestSyntax::from(
+                "\
+New Test: This is a Valid Test
+Step 1: Thing
+Requirement 1: Thhing
+",
+            ),
+        ];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        assert_slack_view_snapshot(
+            "lint-tests-success-mixed-compilation-results",
+            &LintTestsView::new(LintTestsStatus::Success { results }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_no_compile_errors_snapshot() {
+        let tests = vec![RoswaalTestSyntax::from(
+            "\
+New Test: Big Chungus
+Step 1: Big
+Requirement 1: Chungus
+",
+        )];
+        let results = RoswaalTestCompilationResults::compile(tests, &vec![]);
+        assert_slack_view_snapshot(
+            "lint-tests-success-no-compile-errors",
+            &LintTestsView::new(LintTestsStatus::Success { results }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_tests_found_snapshot() {
+        assert_slack_view_snapshot(
+            "lint-tests-no-tests-found",
+            &LintTestsView::new(LintTestsStatus::NoTestsFound),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,123 @@
+use std::borrow::Borrow;
+
+use crate::operations::list_branches::{ListBranchesStatus, RoswaalListedBranch};
+
+use super::{
+    interaction::RoswaalSlackAction,
+    ui_lib::{
+        block_kit_views::{SlackActions, SlackDivider, SlackHeader, SlackSection},
+        for_each_view::ForEachView,
+        slack_view::SlackView,
+    },
+};
+
+/// A view for listing the unmerged branches created by this tool.
+pub struct ListBranchesView {
+    status: ListBranchesStatus,
+}
+
+impl ListBranchesView {
+    pub fn new(status: ListBranchesStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for ListBranchesView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Branches").flat_chain_block(self.status_view())
+    }
+}
+
+impl ListBranchesView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            ListBranchesStatus::Success { branches } => SlackSection::from_markdown(&format!(
+                "🌿 *There are {} unmerged braaaaaanches!*",
+                branches.len()
+            ))
+            .flat_chain_block(ForEachView::new(branches.iter(), |branch| {
+                Self::branch_view(branch)
+            }))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackHeader::new("Next Steps"))
+            .flat_chain_block(SlackSection::from_markdown(
+                "Closing a branch removes its unmerged tests, locations, and staged removals. Make sure to close its pull request on GitHub as weeeeeell.",
+            ))
+            .erase_to_any_view(),
+            ListBranchesStatus::NoBranches => {
+                SlackSection::from_markdown("✅ There are no unmerged braaaaaanches!")
+                    .erase_to_any_view()
+            }
+        }
+    }
+
+    fn branch_view(branch: &RoswaalListedBranch) -> impl SlackView {
+        let location = match (branch.is_stored(), branch.is_on_remote()) {
+            (true, true) => "Unmerged changes, pushed to GitHub",
+            (true, false) => "Unmerged changes, not on GitHub",
+            _ => "Only on GitHub, delete it there to clean it up",
+        };
+        let button = if branch.is_stored() {
+            RoswaalSlackAction::CloseBranch
+                .button("Close Branch", &branch.name().to_string())
+                .map(|b| b.danger())
+        } else {
+            None
+        };
+        SlackDivider
+            .flat_chain_block(SlackSection::from_markdown(&format!(
+                "`{}`\n_({})_",
+                branch.name().to_string(),
+                location
+            )))
+            .flat_chain_block(button.map(|button| SlackActions::new(vec![button])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        operations::list_branches::{ListBranchesStatus, RoswaalListedBranch},
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::ListBranchesView;
+
+    #[test]
+    fn success_snapshot() {
+        let status = ListBranchesStatus::Success {
+            branches: vec![
+                RoswaalListedBranch::new(
+                    branch_name("roswaal-add-locations-Lklj839sda"),
+                    true,
+                    false,
+                ),
+                RoswaalListedBranch::new(branch_name("roswaal-add-tests-0123456789"), true, true),
+                RoswaalListedBranch::new(
+                    branch_name("roswaal-remove-tests-G983j839s4"),
+                    false,
+                    true,
+                ),
+            ],
+        };
+        assert_slack_view_snapshot(
+            "list-branches-success",
+            &ListBranchesView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_branches_snapshot() {
+        assert_slack_view_snapshot(
+            "list-branches-no-branches",
+            &ListBranchesView::new(ListBranchesStatus::NoBranches),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn branch_name(name: &str) -> RoswaalOwnedGitBranchName {
+        serde_json::from_str(&format!("\"{}\"", name)).unwrap()
+    }
+}
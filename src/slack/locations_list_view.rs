@@ -1,7 +1,7 @@
 use std::borrow::Borrow;
 
 use crate::{
-    location::storage::RoswaalStoredLocation,
+    location::{location::group_by_region, storage::RoswaalStoredLocation},
     operations::load_all_locations::LoadAllLocationsStatus,
 };
 
@@ -37,13 +37,36 @@ impl LocationsListView {
     fn status_view(&self) -> impl SlackView {
         match self.status.borrow() {
             LoadAllLocationsStatus::Success(locations) => {
-                ForEachView::new(locations.iter().enumerate(), |(index, location)| {
-                    LocationView { location }
-                        .flat_chain_block(If::is_true(*index < locations.len() - 1, || {
-                            SlackDivider
-                        }))
-                })
-                .erase_to_any_view()
+                let summary = SlackSection::from_markdown(&format!(
+                    "📍 *{}* merged, *{}* pending in unmerged branches.",
+                    self.status.merged_count(),
+                    self.status.pending_count()
+                ));
+                let groups = group_by_region(locations, |l| l.location());
+                let group_count = groups.len();
+                summary
+                    .flat_chain_block(SlackDivider)
+                    .flat_chain_block(ForEachView::new(
+                        groups.into_iter().enumerate(),
+                        move |(group_index, (region, locations))| {
+                            let location_count = locations.len();
+                            IfLet::some(*region, |region| SlackHeader::new(region.raw_name()))
+                                .flat_chain_block(ForEachView::new(
+                                    locations.iter().copied().enumerate(),
+                                    move |(index, location)| {
+                                        LocationView { location }.flat_chain_block(If::is_true(
+                                            *index < location_count - 1,
+                                            || SlackDivider,
+                                        ))
+                                    },
+                                ))
+                                .flat_chain_block(If::is_true(
+                                    *group_index < group_count - 1,
+                                    || SlackDivider,
+                                ))
+                        },
+                    ))
+                    .erase_to_any_view()
             }
             LoadAllLocationsStatus::NoLocations => {
                 SlackSection::from_markdown("No locations were fooooound!").erase_to_any_view()
@@ -75,8 +98,12 @@ impl<'l> SlackView for LocationView<'l> {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use crate::{
-        location::{location::RoswaalLocation, storage::RoswaalStoredLocation},
+        location::{
+            location::RoswaalLocation, name::RoswaalLocationName, storage::RoswaalStoredLocation,
+        },
         operations::load_all_locations::LoadAllLocationsStatus,
         slack::{
             test_support::SlackTestConstantBranches,
@@ -106,6 +133,37 @@ mod tests {
         )
     }
 
+    #[test]
+    fn success_with_regions_snapshot() {
+        let region = |name: &str| Some(RoswaalLocationName::from_str(name).unwrap());
+        let locations = vec![
+            RoswaalStoredLocation::new(
+                RoswaalLocation::new_without_validation("Antarctica", 10.0, 10.0),
+                None,
+            ),
+            RoswaalStoredLocation::new(
+                RoswaalLocation::new_without_validation("Berkeley", 37.9, -122.3)
+                    .with_region(region("Bay Area")),
+                None,
+            ),
+            RoswaalStoredLocation::new(
+                RoswaalLocation::new_without_validation("Manhattan", 40.7, -74.0)
+                    .with_region(region("New York")),
+                None,
+            ),
+            RoswaalStoredLocation::new(
+                RoswaalLocation::new_without_validation("Oakland", 37.8, -122.3)
+                    .with_region(region("Bay Area")),
+                None,
+            ),
+        ];
+        assert_slack_view_snapshot(
+            "locations-list-success-with-regions",
+            &LocationsListView::new(LoadAllLocationsStatus::Success(locations)),
+            SnapshotMode::Comparing,
+        )
+    }
+
     #[test]
     fn no_locations_snapshot() {
         assert_slack_view_snapshot(
@@ -0,0 +1,30 @@
+use super::ui_lib::{block_kit_views::SlackSection, slack_view::SlackView};
+
+/// A view for indicating that slack likely cut off the text of a command.
+pub struct LongCommandTextView;
+
+impl SlackView for LongCommandTextView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown("✂️ *This command is toooooo long!*").flat_chain_block(
+            SlackSection::from_markdown(
+                "_Slack cuts off commands after 4000 characters, so I didn't run it. Upload the text as a file in slack, and send the command with a link to the file instead._",
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::LongCommandTextView;
+
+    #[test]
+    fn snapshot() {
+        assert_slack_view_snapshot(
+            "long-command-text",
+            &LongCommandTextView,
+            SnapshotMode::Comparing,
+        )
+    }
+}
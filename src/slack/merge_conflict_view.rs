@@ -1,14 +1,19 @@
-use super::ui_lib::{block_kit_views::SlackSection, slack_view::SlackView};
+use super::{
+    ui_lib::{block_kit_views::SlackSection, slack_view::SlackView},
+    users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+};
 
 /// A view for representing a merge conflict from an operation.
+///
+/// The merge conflict owners in the roster are pinged to fix the conflict.
 pub struct MergeConflictView {
-    slack_user_id: String,
+    slack_user_mentions: String,
 }
 
 impl MergeConflictView {
-    pub fn new(slack_user_id: &str) -> Self {
+    pub fn new(roster: &RoswaalSlackUserRoster) -> Self {
         Self {
-            slack_user_id: slack_user_id.to_string(),
+            slack_user_mentions: roster.mentions(RoswaalSlackUserRole::MergeConflictOwner),
         }
     }
 }
@@ -19,8 +24,8 @@ impl SlackView for MergeConflictView {
             .flat_chain_block(
                 SlackSection::from_markdown(
                     &format!(
-                        "_Fixing this requires manual interveeeeeeention, which requires <@{}> to be useful for ooooonce!_",
-                        self.slack_user_id
+                        "_Fixing this requires manual interveeeeeeention, which requires {} to be useful for ooooonce!_",
+                        self.slack_user_mentions
                     )
                 )
             )
@@ -31,7 +36,7 @@ impl SlackView for MergeConflictView {
 mod tests {
     use crate::slack::{
         ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
-        users::MATTHEW_SLACK_USER_ID,
+        users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
     };
 
     use super::MergeConflictView;
@@ -40,7 +45,18 @@ mod tests {
     fn snapshot() {
         assert_slack_view_snapshot(
             "merge-conflict",
-            &MergeConflictView::new(MATTHEW_SLACK_USER_ID),
+            &MergeConflictView::new(&RoswaalSlackUserRoster::default()),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn multiple_owners_snapshot() {
+        let roster = RoswaalSlackUserRoster::default()
+            .with_user_ids(RoswaalSlackUserRole::MergeConflictOwner, &["U1", "U2"]);
+        assert_slack_view_snapshot(
+            "merge-conflict-multiple-owners",
+            &MergeConflictView::new(&roster),
             SnapshotMode::Comparing,
         )
     }
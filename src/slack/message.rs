@@ -17,6 +17,16 @@ use super::{
     },
 };
 
+/// The url of the slack API method for posting a message to a channel.
+///
+/// Messages that are not responses to a command or interaction (eg. scheduled messages) are
+/// sent to this url instead of a response url.
+pub const SLACK_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+
+/// The url of the slack API method for editing a message that was previously posted to
+/// `SLACK_POST_MESSAGE_URL`.
+pub const SLACK_UPDATE_MESSAGE_URL: &str = "https://slack.com/api/chat.update";
+
 /// A slack message.
 ///
 /// A slack message is created from a `SlackView` and a string channel identifier.
@@ -25,6 +35,10 @@ pub struct SlackMessage {
     #[serde(rename = "channel")]
     channel_id: String,
     blocks: SlackBlocks,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thread_ts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ts: Option<String>,
     #[serde(skip)]
     response_url: String,
 }
@@ -34,24 +48,62 @@ impl SlackMessage {
         Self {
             channel_id: channel_id.to_string(),
             blocks: render_slack_view(&MessageView::new(view)),
+            thread_ts: None,
+            ts: None,
             response_url: response_url.to_string(),
         }
     }
+
+    /// Returns this message as a reply in the thread of the message with the specified timestamp.
+    ///
+    /// Slack only threads messages that are posted to `SLACK_POST_MESSAGE_URL`.
+    pub fn in_thread(self, thread_ts: &str) -> Self {
+        Self {
+            thread_ts: Some(thread_ts.to_string()),
+            ..self
+        }
+    }
+
+    /// Returns this message as an edit that replaces the contents of the message with the
+    /// specified timestamp.
+    ///
+    /// Slack only edits messages that are sent to `SLACK_UPDATE_MESSAGE_URL`.
+    pub fn updating(self, ts: &str) -> Self {
+        Self {
+            ts: Some(ts.to_string()),
+            ..self
+        }
+    }
+
+    pub fn blocks(&self) -> &SlackBlocks {
+        &self.blocks
+    }
+
+    /// Returns this message with its blocks replaced by `blocks`.
+    pub fn with_blocks(self, blocks: SlackBlocks) -> Self {
+        Self { blocks, ..self }
+    }
 }
 
 /// A trait for sending a slack message.
 pub trait SlackSendMessage {
-    fn send(&self, message: &SlackMessage) -> impl Future<Output = Result<()>> + Send;
+    /// Sends the specified message, and returns the timestamp that slack identifies the sent
+    /// message by.
+    ///
+    /// Slack only responds with a timestamp for messages posted to `SLACK_POST_MESSAGE_URL`, so
+    /// None is returned for messages sent to a response url.
+    fn send(&self, message: &SlackMessage) -> impl Future<Output = Result<Option<String>>> + Send;
 }
 
 #[derive(Debug, Deserialize)]
-struct SlackResponse {
-    error: Option<String>,
+pub(super) struct SlackResponse {
+    pub(super) error: Option<String>,
+    pub(super) ts: Option<String>,
 }
 
 #[derive(Debug)]
-struct SlackMessageSendingError {
-    message: String,
+pub(super) struct SlackMessageSendingError {
+    pub(super) message: String,
 }
 
 impl Display for SlackMessageSendingError {
@@ -63,7 +115,7 @@ impl Display for SlackMessageSendingError {
 impl Error for SlackMessageSendingError {}
 
 impl SlackSendMessage for Client {
-    async fn send(&self, message: &SlackMessage) -> Result<()> {
+    async fn send(&self, message: &SlackMessage) -> Result<Option<String>> {
         let token = env::var("SLACK_BOT_TOKEN").expect(
             "SLACK_BOT_TOKEN not found in .env, you can get one from the slack app console.",
         );
@@ -82,7 +134,35 @@ impl SlackSendMessage for Client {
                     message: error,
                 }))
             }
-            None => Ok(()),
+            None => Ok(slack_resp.ts),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::slack::pending_view::PendingView;
+
+    use super::*;
+
+    #[test]
+    fn only_serializes_thread_ts_for_replies() {
+        let message = SlackMessage::new("C123", &PendingView::new(), SLACK_POST_MESSAGE_URL);
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json.get("thread_ts"), None);
+        let json = serde_json::to_value(message.in_thread("1700000000.000100")).unwrap();
+        assert_eq!(json.get("thread_ts"), Some(&json!("1700000000.000100")))
+    }
+
+    #[test]
+    fn only_serializes_ts_for_edits() {
+        let message = SlackMessage::new("C123", &PendingView::new(), SLACK_UPDATE_MESSAGE_URL);
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json.get("ts"), None);
+        let json = serde_json::to_value(message.updating("1700000000.000100")).unwrap();
+        assert_eq!(json.get("ts"), Some(&json!("1700000000.000100")));
+        assert_eq!(json.get("channel"), Some(&json!("C123")))
+    }
+}
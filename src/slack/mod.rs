@@ -1,19 +1,63 @@
+pub mod add_location_alias_view;
 pub mod add_locations_view;
+pub mod add_tests_modal_view;
 pub mod add_tests_view;
+pub mod assign_failure_view;
+pub mod audit_log_view;
 pub mod branch_name_view;
+pub mod cancel_view;
+pub mod catalog;
+pub mod close_branch_view;
 pub mod command;
+pub mod deprecate_tests_view;
+pub mod edit_test_view;
 pub mod error_view;
+pub mod export_tests_view;
+pub mod file;
+pub mod flaky_tests_view;
+pub mod git_timeout_view;
+pub mod grant_view;
 pub mod handler;
+pub mod help_view;
+pub mod home;
+pub mod home_view;
+pub mod in_flight;
+pub mod interaction;
+pub mod lint_tests_view;
+pub mod list_branches_view;
 pub mod locations_list_view;
+pub mod long_command_text_view;
 pub mod merge_conflict_view;
 pub mod message;
 pub mod message_view;
+pub mod modal;
+pub mod my_failed_tests_view;
+pub mod open_pending_prs_view;
 pub mod pending_view;
+pub mod permission_denied_view;
+pub mod permissions;
 pub mod pr_open_fail_view;
+pub mod reconcile_view;
+pub mod remote_unavailable_view;
+pub mod remove_locations_view;
+pub mod remove_tests_modal_view;
 pub mod remove_tests_view;
+pub mod repo_doctor_view;
+pub mod report;
+pub mod reset_test_progress_view;
+pub mod restore_test_view;
+pub mod roster_view;
 pub mod search_tests_view;
+pub mod select_menu;
+pub mod slow_down_view;
+pub mod suites_view;
+pub mod target_repositories_view;
+pub mod test_digest_view;
+pub mod test_failure_view;
+pub mod test_run_view;
 #[cfg(test)]
 pub mod test_support;
+pub mod truncated_report_view;
 pub mod ui_lib;
 pub mod users;
 pub mod warn_undeleted_branch_view;
@@ -0,0 +1,342 @@
+use std::{env, future::Future, str::FromStr};
+
+use anyhow::Result;
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, IntoStaticStr};
+
+use super::{
+    add_tests_modal_view::AddTestsModalView,
+    command::RoswaalSlackCommand,
+    handler::RoswaalSlackRequest,
+    interaction::RoswaalSlackInteractionForm,
+    message::{SlackMessageSendingError, SlackResponse, SLACK_POST_MESSAGE_URL},
+    remove_tests_modal_view::RemoveTestsModalView,
+    select_menu::RoswaalSlackSelectMenu,
+    ui_lib::modal::SlackModal,
+};
+
+/// The url of the slack API method for opening a modal.
+pub const SLACK_OPEN_VIEW_URL: &str = "https://slack.com/api/views.open";
+
+/// The id of the input block that holds the tests in the modal for adding tests.
+pub const ADD_TESTS_INPUT_BLOCK_ID: &str = "tests";
+
+/// The id of the input that holds the tests in the modal for adding tests.
+pub const ADD_TESTS_INPUT_ACTION_ID: &str = "tests-input";
+
+/// The id of the input block that holds the menu of tests in the modal for removing tests.
+///
+/// The menu itself is identified by the action id of `RoswaalSlackSelectMenu::TestNames`.
+pub const REMOVE_TESTS_INPUT_BLOCK_ID: &str = "tests";
+
+/// The modals that this tool can open in place of typing the text of a command.
+#[derive(Debug, PartialEq, Eq, EnumString, IntoStaticStr, Display, Clone, Copy)]
+pub enum RoswaalSlackModal {
+    #[strum(serialize = "add-tests")]
+    AddTests,
+    #[strum(serialize = "remove-tests")]
+    RemoveTests,
+}
+
+impl RoswaalSlackModal {
+    /// Returns the modal that is opened when `command` is sent without any text, if any.
+    pub fn for_empty_command(command: &RoswaalSlackCommand) -> Option<Self> {
+        match command {
+            RoswaalSlackCommand::AddTests => Some(Self::AddTests),
+            RoswaalSlackCommand::RemoveTests => Some(Self::RemoveTests),
+            _ => None,
+        }
+    }
+
+    /// Returns the command that the submitted text of this modal is handled by.
+    pub fn command(&self) -> RoswaalSlackCommand {
+        match self {
+            Self::AddTests => RoswaalSlackCommand::AddTests,
+            Self::RemoveTests => RoswaalSlackCommand::RemoveTests,
+        }
+    }
+
+    /// Returns the modal to open for a command that was sent in the channel with `channel_id`.
+    ///
+    /// The channel id is stored in the modal so that the result of handling the submitted text
+    /// can be sent to the same channel.
+    pub fn slack_modal(&self, channel_id: &str) -> SlackModal {
+        match self {
+            Self::AddTests => SlackModal::new(self.into(), "Add Tests", &AddTestsModalView)
+                .submit_text("Add Tests")
+                .with_private_metadata(channel_id),
+            Self::RemoveTests => {
+                SlackModal::new(self.into(), "Remove Tests", &RemoveTestsModalView)
+                    .submit_text("Remove Tests")
+                    .with_private_metadata(channel_id)
+            }
+        }
+    }
+}
+
+/// A trait for opening a slack modal.
+pub trait SlackOpenModal {
+    /// Opens `modal` for the user that performed the interaction with `trigger_id`.
+    ///
+    /// Trigger ids expire after 3 seconds, so this must be called before acknowledging the
+    /// interaction.
+    fn open_modal(
+        &self,
+        trigger_id: &str,
+        modal: &SlackModal,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
+
+#[derive(Serialize)]
+struct OpenViewRequest<'a> {
+    trigger_id: &'a str,
+    view: &'a SlackModal,
+}
+
+impl SlackOpenModal for Client {
+    async fn open_modal(&self, trigger_id: &str, modal: &SlackModal) -> Result<()> {
+        let token = env::var("SLACK_BOT_TOKEN").expect(
+            "SLACK_BOT_TOKEN not found in .env, you can get one from the slack app console.",
+        );
+        let resp = self
+            .post(SLACK_OPEN_VIEW_URL)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&OpenViewRequest {
+                trigger_id,
+                view: modal,
+            })
+            .bearer_auth(token)
+            .send()
+            .await?;
+        let slack_resp = resp.json::<SlackResponse>().await?;
+        match slack_resp.error {
+            Some(error) => {
+                log::error!("A Slack API error occured when opening a modal {}.", error);
+                Err(anyhow::Error::new(SlackMessageSendingError {
+                    message: error,
+                }))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// The text that a user submitted through a modal opened by this tool.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalSlackModalSubmission {
+    modal: RoswaalSlackModal,
+    channel_id: String,
+    user_id: String,
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewSubmissionPayload {
+    #[serde(rename = "type")]
+    _type: String,
+    user: Option<PayloadUser>,
+    view: Option<PayloadView>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadUser {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadView {
+    callback_id: String,
+    private_metadata: String,
+    state: PayloadViewState,
+}
+
+#[derive(Debug, Deserialize)]
+struct PayloadViewState {
+    values: serde_json::Value,
+}
+
+impl RoswaalSlackModalSubmission {
+    /// Parses a modal submission from the form body sent by slack.
+    ///
+    /// Returns None if the payload is not a `view_submission` payload, or if it was not sent by a
+    /// modal opened by this tool. An error is returned if the payload is not valid JSON.
+    pub fn from_form(form: &RoswaalSlackInteractionForm) -> serde_json::Result<Option<Self>> {
+        let payload = serde_json::from_str::<ViewSubmissionPayload>(form.payload())?;
+        if payload._type != "view_submission" {
+            return Ok(None);
+        }
+        let (Some(user), Some(view)) = (payload.user, payload.view) else {
+            return Ok(None);
+        };
+        let Ok(modal) = RoswaalSlackModal::from_str(&view.callback_id) else {
+            return Ok(None);
+        };
+        let values = &view.state.values;
+        let text = match modal {
+            RoswaalSlackModal::AddTests => values[ADD_TESTS_INPUT_BLOCK_ID]
+                [ADD_TESTS_INPUT_ACTION_ID]["value"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            RoswaalSlackModal::RemoveTests => {
+                let action_id: &str = RoswaalSlackSelectMenu::TestNames.into();
+                values[REMOVE_TESTS_INPUT_BLOCK_ID][action_id]["selected_options"]
+                    .as_array()
+                    .map(|options| {
+                        options
+                            .iter()
+                            .filter_map(|option| option["value"].as_str())
+                            .collect::<Vec<&str>>()
+                            .join("\n")
+                    })
+                    .unwrap_or_default()
+            }
+        };
+        Ok(Some(Self {
+            modal,
+            channel_id: view.private_metadata,
+            user_id: user.id,
+            text,
+        }))
+    }
+}
+
+impl RoswaalSlackModalSubmission {
+    /// Returns a request for handling the submitted text with the command of the modal.
+    ///
+    /// Since modals have no response url, the result of the request is posted to the channel
+    /// that the modal was opened from.
+    pub fn request(&self) -> RoswaalSlackRequest {
+        RoswaalSlackRequest::new(
+            self.channel_id.clone(),
+            self.user_id.clone(),
+            self.text.clone(),
+            self.modal.command(),
+            SLACK_POST_MESSAGE_URL.to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+impl RoswaalSlackModalSubmission {
+    pub fn for_testing(modal: RoswaalSlackModal, text: &str) -> Self {
+        Self {
+            modal,
+            channel_id: "bob".to_string(),
+            user_id: "U01234567".to_string(),
+            text: text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_add_tests_submission() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "view_submission",
+            "user": { "id": "U123" },
+            "view": {
+                "callback_id": "add-tests",
+                "private_metadata": "C123",
+                "state": {
+                    "values": {
+                        "tests": {
+                            "tests-input": {
+                                "type": "plain_text_input",
+                                "value": "New Test: Join an event"
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+        let submission = RoswaalSlackModalSubmission::from_form(&form)
+            .unwrap()
+            .unwrap();
+        let expected_request = RoswaalSlackRequest::new(
+            "C123".to_string(),
+            "U123".to_string(),
+            "New Test: Join an event".to_string(),
+            RoswaalSlackCommand::AddTests,
+            SLACK_POST_MESSAGE_URL.to_string(),
+        );
+        assert_eq!(submission.request(), expected_request)
+    }
+
+    #[test]
+    fn parses_remove_tests_submission_as_selected_test_names() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "view_submission",
+            "user": { "id": "U123" },
+            "view": {
+                "callback_id": "remove-tests",
+                "private_metadata": "C123",
+                "state": {
+                    "values": {
+                        "tests": {
+                            "test-names": {
+                                "type": "multi_external_select",
+                                "selected_options": [
+                                    { "text": { "type": "plain_text", "text": "Join an event" }, "value": "Join an event" },
+                                    { "text": { "type": "plain_text", "text": "Leave an event" }, "value": "Leave an event" }
+                                ]
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+        let submission = RoswaalSlackModalSubmission::from_form(&form)
+            .unwrap()
+            .unwrap();
+        let expected_request = RoswaalSlackRequest::new(
+            "C123".to_string(),
+            "U123".to_string(),
+            "Join an event\nLeave an event".to_string(),
+            RoswaalSlackCommand::RemoveTests,
+            SLACK_POST_MESSAGE_URL.to_string(),
+        );
+        assert_eq!(submission.request(), expected_request)
+    }
+
+    #[test]
+    fn ignores_unknown_modals_and_payload_types() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "view_submission",
+            "user": { "id": "U123" },
+            "view": {
+                "callback_id": "explode",
+                "private_metadata": "",
+                "state": { "values": {} }
+            }
+        }));
+        assert_eq!(RoswaalSlackModalSubmission::from_form(&form).unwrap(), None);
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_actions",
+            "actions": []
+        }));
+        assert_eq!(RoswaalSlackModalSubmission::from_form(&form).unwrap(), None)
+    }
+
+    #[test]
+    fn only_add_tests_and_remove_tests_open_a_modal_for_empty_commands() {
+        assert_eq!(
+            RoswaalSlackModal::for_empty_command(&RoswaalSlackCommand::AddTests),
+            Some(RoswaalSlackModal::AddTests)
+        );
+        assert_eq!(
+            RoswaalSlackModal::for_empty_command(&RoswaalSlackCommand::RemoveTests),
+            Some(RoswaalSlackModal::RemoveTests)
+        );
+        assert_eq!(
+            RoswaalSlackModal::for_empty_command(&RoswaalSlackCommand::ViewTests),
+            None
+        )
+    }
+}
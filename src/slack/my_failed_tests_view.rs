@@ -0,0 +1,124 @@
+use std::borrow::Borrow;
+
+use crate::{
+    language::{ast::RoswaalTestSyntax, storage::RoswaalStoredCompilationFailure},
+    operations::my_failed_tests::MyFailedTestsStatus,
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    for_each_view::ForEachView,
+    slack_view::SlackView,
+};
+
+/// A view for showing the tests that a user recently failed to compile with `/add-tests`.
+pub struct MyFailedTestsView {
+    status: MyFailedTestsStatus,
+}
+
+impl MyFailedTestsView {
+    pub fn new(status: MyFailedTestsStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for MyFailedTestsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("My Failed Tests").flat_chain_block(self.status_view())
+    }
+}
+
+impl MyFailedTestsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            MyFailedTestsStatus::Success(failures) => SlackSection::from_markdown(&format!(
+                "📝 *Here are the {} tests that you most recently failed to compile!* Fix the errors, and then send the tests to `/add-tests` agaaaaaain.",
+                failures.len()
+            ))
+            .flat_chain_block(ForEachView::new(failures.iter(), |failure| {
+                SlackDivider.flat_chain_block(Self::failure_view(failure))
+            }))
+            .erase_to_any_view(),
+            MyFailedTestsStatus::NoFailedTests => SlackSection::from_markdown(
+                "✅ None of the tests that you sent to `/add-tests` have failed to compile recentlyyyyyy!",
+            )
+            .erase_to_any_view(),
+        }
+    }
+
+    fn failure_view(failure: &RoswaalStoredCompilationFailure) -> impl SlackView {
+        let syntax = RoswaalTestSyntax::from(failure.syntax());
+        SlackSection::from_markdown(&format!(
+            "🔴 _Submitted {}_",
+            failure.creation_date().format("%Y-%m-%d %H:%M:%S")
+        ))
+        .flat_chain_block(SlackSection::from_markdown(&syntax.markdown_code_block()))
+        .flat_chain_block(ForEachView::new(
+            failure.error_messages().to_vec().into_iter(),
+            |message| SlackSection::from_markdown(message),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        language::{
+            compiler::{RoswaalCompilationError, RoswaalCompilationErrorCode},
+            storage::RoswaalStoredCompilationFailure,
+        },
+        operations::my_failed_tests::MyFailedTestsStatus,
+        slack::{
+            add_tests_view::compilation_error_line_message,
+            ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        },
+    };
+
+    use super::MyFailedTestsView;
+
+    #[test]
+    fn success_snapshot() {
+        let date = Utc.with_ymd_and_hms(2024, 6, 1, 12, 30, 0).unwrap();
+        let failures = vec![
+            RoswaalStoredCompilationFailure::new(
+                "New Test: Justin joins an event\nStep 1: Justin joins the event",
+                error_messages(vec![RoswaalCompilationError::new(
+                    2,
+                    RoswaalCompilationErrorCode::NoStepRequirement {
+                        step_name: "Step 1".to_string(),
+                        step_description: "Justin joins the event".to_string(),
+                    },
+                )]),
+                date,
+            ),
+            RoswaalStoredCompilationFailure::new(
+                "",
+                error_messages(vec![
+                    RoswaalCompilationError::new(1, RoswaalCompilationErrorCode::NoTestName),
+                    RoswaalCompilationError::new(1, RoswaalCompilationErrorCode::NoTestSteps),
+                ]),
+                date,
+            ),
+        ];
+        assert_slack_view_snapshot(
+            "my-failed-tests-success",
+            &MyFailedTestsView::new(MyFailedTestsStatus::Success(failures)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_failed_tests_snapshot() {
+        assert_slack_view_snapshot(
+            "my-failed-tests-no-failed-tests",
+            &MyFailedTestsView::new(MyFailedTestsStatus::NoFailedTests),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn error_messages(errors: Vec<RoswaalCompilationError>) -> Vec<String> {
+        errors.iter().map(compilation_error_line_message).collect()
+    }
+}
@@ -0,0 +1,128 @@
+use std::borrow::Borrow;
+
+use crate::{
+    git::pull_request::GithubPullRequest,
+    operations::open_pending_prs::OpenPendingPullRequestsStatus,
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    for_each_view::ForEachView,
+    slack_view::SlackView,
+};
+
+/// A view for the results of opening the PRs that were deferred by the staged PR mode.
+pub struct OpenPendingPullRequestsView {
+    status: OpenPendingPullRequestsStatus,
+}
+
+impl OpenPendingPullRequestsView {
+    pub fn new(status: OpenPendingPullRequestsStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for OpenPendingPullRequestsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Pending Pull Requests").flat_chain_block(self.status_view())
+    }
+}
+
+impl OpenPendingPullRequestsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            OpenPendingPullRequestsStatus::Opened { opened, failed } => {
+                let opened_view = if opened.is_empty() {
+                    None
+                } else {
+                    Some(
+                        SlackSection::from_markdown(&format!(
+                            "✅ *Opened {} pending PRs on GitHub!*",
+                            opened.len()
+                        ))
+                        .flat_chain_block(Self::pull_requests_view(opened)),
+                    )
+                };
+                let failed_view = if failed.is_empty() {
+                    None
+                } else {
+                    Some(
+                        SlackSection::from_markdown(&format!(
+                            "⚠️ *Failed to open {} pending PRs.* They are still peeeeeending, so run `/open-pending-prs` again to retry them.",
+                            failed.len()
+                        ))
+                        .flat_chain_block(Self::pull_requests_view(failed)),
+                    )
+                };
+                opened_view
+                    .flat_chain_block(failed_view)
+                    .erase_to_any_view()
+            }
+            OpenPendingPullRequestsStatus::NoPendingPullRequests => {
+                SlackSection::from_markdown("✅ There are no pending PRs to oooooooopen!")
+                    .erase_to_any_view()
+            }
+        }
+    }
+
+    fn pull_requests_view(pull_requests: &[GithubPullRequest]) -> impl SlackView + '_ {
+        ForEachView::new(pull_requests.iter(), |pull_request| {
+            SlackDivider.flat_chain_block(SlackSection::from_markdown(&format!(
+                "*{}*\n`{}/{}` from `{}`",
+                pull_request.title(),
+                pull_request.owner(),
+                pull_request.repo(),
+                pull_request.head_branch().to_string()
+            )))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::{branch_name::RoswaalOwnedGitBranchName, pull_request::GithubPullRequest},
+        operations::open_pending_prs::OpenPendingPullRequestsStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::OpenPendingPullRequestsView;
+
+    #[test]
+    fn opened_snapshot() {
+        let status = OpenPendingPullRequestsStatus::Opened {
+            opened: vec![pull_request(
+                "Add Tests \"Join an event\"",
+                "roswaal-add-tests-0123456789",
+            )],
+            failed: vec![pull_request(
+                "Add Locations (New York)",
+                "roswaal-add-locations-Lklj839sda",
+            )],
+        };
+        assert_slack_view_snapshot(
+            "open-pending-prs-opened",
+            &OpenPendingPullRequestsView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_pending_pull_requests_snapshot() {
+        assert_slack_view_snapshot(
+            "open-pending-prs-none",
+            &OpenPendingPullRequestsView::new(OpenPendingPullRequestsStatus::NoPendingPullRequests),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn pull_request(title: &str, branch_name: &str) -> GithubPullRequest {
+        let branch_name: RoswaalOwnedGitBranchName =
+            serde_json::from_str(&format!("\"{}\"", branch_name)).unwrap();
+        GithubPullRequest::new(title, "", &branch_name).in_repository(
+            "tifapp",
+            "FitnessProjectIOS",
+            "main",
+        )
+    }
+}
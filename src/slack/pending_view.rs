@@ -1,21 +1,64 @@
-use super::ui_lib::{block_kit_views::SlackSection, slack_view::SlackView};
+use crate::utils::stage::RoswaalOperationStage;
 
-pub struct PendingView;
+use super::ui_lib::{block_kit_views::SlackSection, if_let_view::IfLet, slack_view::SlackView};
+
+/// A view for the message that is posted while a command is handled, which is edited to show
+/// the stage of the operation as it progresses.
+#[derive(Default)]
+pub struct PendingView {
+    stage: Option<RoswaalOperationStage>,
+}
+
+impl PendingView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn at_stage(stage: RoswaalOperationStage) -> Self {
+        Self { stage: Some(stage) }
+    }
+}
 
 impl SlackView for PendingView {
     fn slack_body(&self) -> impl SlackView {
         SlackSection::from_markdown("⏳ I am ooooooooooon that!")
+            .flat_chain_block(IfLet::some(self.stage.as_ref(), |stage| {
+                SlackSection::from_markdown(&format!("_{}_", stage_text(*stage)))
+            }))
+    }
+}
+
+fn stage_text(stage: RoswaalOperationStage) -> &'static str {
+    match stage {
+        RoswaalOperationStage::Cloning => "Cloning the repoooooo...",
+        RoswaalOperationStage::Pulling => "Pulling the latest chaaaaanges...",
+        RoswaalOperationStage::Compiling => "Compiling the teeeeeests...",
+        RoswaalOperationStage::Generating => "Generating the coooooode...",
+        RoswaalOperationStage::Pushing => "Pushing the braaaaanch...",
+        RoswaalOperationStage::OpeningPullRequest => "Opening the pull requeeeeest...",
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+    use crate::{
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        utils::stage::RoswaalOperationStage,
+    };
 
     use super::PendingView;
 
     #[test]
     fn snapshot() {
-        assert_slack_view_snapshot("pending", &PendingView, SnapshotMode::Comparing)
+        assert_slack_view_snapshot("pending", &PendingView::new(), SnapshotMode::Comparing)
+    }
+
+    #[test]
+    fn pushing_snapshot() {
+        assert_slack_view_snapshot(
+            "pending-pushing",
+            &PendingView::at_stage(RoswaalOperationStage::Pushing),
+            SnapshotMode::Comparing,
+        )
     }
 }
@@ -0,0 +1,72 @@
+use crate::operations::check_permission::CheckPermissionStatus;
+
+use super::{
+    ui_lib::{block_kit_views::SlackSection, empty_view::EmptySlackView, slack_view::SlackView},
+    users::RoswaalSlackUserRole,
+};
+
+/// A view for telling a user that they are not allowed to run a command, and who to ask for
+/// access.
+pub struct PermissionDeniedView {
+    status: CheckPermissionStatus,
+}
+
+impl PermissionDeniedView {
+    pub fn new(status: CheckPermissionStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for PermissionDeniedView {
+    fn slack_body(&self) -> impl SlackView {
+        match &self.status {
+            CheckPermissionStatus::Allowed => EmptySlackView.erase_to_any_view(),
+            CheckPermissionStatus::Denied {
+                command,
+                subjects,
+                roster,
+            } => {
+                let mentions = subjects
+                    .iter()
+                    .map(|subject| subject.mention())
+                    .collect::<Vec<String>>()
+                    .join(" or ");
+                SlackSection::from_markdown(&format!(
+                    "🔒 Sorry, only {} can run `{}`. Ask {} niiiiiicely to grant it to you with `/roswaal-grant`!",
+                    mentions,
+                    command,
+                    roster.mentions(RoswaalSlackUserRole::InfraOwner)
+                ))
+                .erase_to_any_view()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::{
+        command::RoswaalSlackCommand,
+        permissions::RoswaalSlackPermissionSubject,
+        ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        users::RoswaalSlackUserRoster,
+    };
+
+    use super::*;
+
+    #[test]
+    fn denied_snapshot() {
+        assert_slack_view_snapshot(
+            "permission-denied",
+            &PermissionDeniedView::new(CheckPermissionStatus::Denied {
+                command: RoswaalSlackCommand::RemoveTests,
+                subjects: vec![
+                    RoswaalSlackPermissionSubject::User("U1".to_string()),
+                    RoswaalSlackPermissionSubject::UserGroup("S1".to_string()),
+                ],
+                roster: RoswaalSlackUserRoster::default(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+}
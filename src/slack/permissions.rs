@@ -0,0 +1,354 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+    str::FromStr,
+};
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use sqlx::FromRow;
+use strum::IntoEnumIterator;
+
+use crate::utils::db::{
+    query::{query, query_as},
+    RoswaalDatabaseTransaction,
+};
+
+use super::{command::RoswaalSlackCommand, users::slack_user_id_from_mention};
+
+/// The url of the slack API method for listing the users in a user group.
+const SLACK_USERGROUPS_USERS_LIST_URL: &str = "https://slack.com/api/usergroups.users.list";
+
+/// A slack user or user group that is allowed to run a restricted command.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalSlackPermissionSubject {
+    User(String),
+    UserGroup(String),
+}
+
+impl RoswaalSlackPermissionSubject {
+    /// Parses a subject from a mention in a slash command's text.
+    ///
+    /// Users are parsed in the same manner as `slack_user_id_from_mention`, and slack sends user
+    /// group mentions as `<!subteam^S123>` or `<!subteam^S123|@handle>`.
+    pub fn from_mention(mention: &str) -> Option<Self> {
+        if let Some(user_id) = slack_user_id_from_mention(mention) {
+            return Some(Self::User(user_id));
+        }
+        let id = mention
+            .strip_prefix("<!subteam^")
+            .and_then(|m| m.strip_suffix('>'))
+            .map(|m| m.split('|').next().unwrap_or(m))?;
+        let is_user_group_id = id.len() > 1
+            && id.starts_with('S')
+            && id
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        is_user_group_id.then(|| Self::UserGroup(id.to_string()))
+    }
+
+    /// Returns slack markdown that mentions this subject.
+    pub fn mention(&self) -> String {
+        match self {
+            Self::User(id) => format!("<@{}>", id),
+            Self::UserGroup(id) => format!("<!subteam^{}>", id),
+        }
+    }
+}
+
+/// The users and user groups that are allowed to run each restricted `RoswaalSlackCommand`.
+///
+/// A command without any subjects can be run by anyone in the workspace. Infra owners can run
+/// every command regardless of its subjects.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RoswaalSlackCommandPermissions {
+    subjects: HashMap<RoswaalSlackCommand, Vec<RoswaalSlackPermissionSubject>>,
+}
+
+impl RoswaalSlackCommandPermissions {
+    /// Returns a copy of these permissions where the subjects of `command` are replaced with
+    /// `subjects`.
+    pub fn with_subjects(
+        mut self,
+        command: RoswaalSlackCommand,
+        subjects: Vec<RoswaalSlackPermissionSubject>,
+    ) -> Self {
+        self.subjects.insert(command, subjects);
+        self
+    }
+}
+
+impl RoswaalSlackCommandPermissions {
+    /// Returns the users and user groups that are allowed to run `command`.
+    ///
+    /// The returned slice is empty when anyone can run `command`.
+    pub fn subjects(&self, command: RoswaalSlackCommand) -> &[RoswaalSlackPermissionSubject] {
+        self.subjects
+            .get(&command)
+            .map(|subjects| subjects.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns each command that only some users can run alongside its subjects, in the order
+    /// that the commands are declared.
+    pub fn restricted_commands(
+        &self,
+    ) -> Vec<(RoswaalSlackCommand, &[RoswaalSlackPermissionSubject])> {
+        RoswaalSlackCommand::iter()
+            .map(|command| (command, self.subjects(command)))
+            .filter(|(_, subjects)| !subjects.is_empty())
+            .collect()
+    }
+}
+
+/// A trait for fetching the ids of the users in a slack user group.
+pub trait SlackFetchUserGroupMembers {
+    fn fetch_user_group_members(
+        &self,
+        user_group_id: &str,
+    ) -> impl Future<Output = Result<Vec<String>>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackUserGroupsUsersListResponse {
+    error: Option<String>,
+    #[serde(default)]
+    users: Vec<String>,
+}
+
+#[derive(Debug)]
+struct SlackUserGroupFetchingError {
+    message: String,
+}
+
+impl Display for SlackUserGroupFetchingError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Slack User Group Fetching Error: {}", self.message)
+    }
+}
+
+impl Error for SlackUserGroupFetchingError {}
+
+impl SlackFetchUserGroupMembers for Client {
+    async fn fetch_user_group_members(&self, user_group_id: &str) -> Result<Vec<String>> {
+        let token = env::var("SLACK_BOT_TOKEN").expect(
+            "SLACK_BOT_TOKEN not found in .env, you can get one from the slack app console.",
+        );
+        let resp = self
+            .get(SLACK_USERGROUPS_USERS_LIST_URL)
+            .query(&[("usergroup", user_group_id)])
+            .bearer_auth(token)
+            .send()
+            .await?
+            .json::<SlackUserGroupsUsersListResponse>()
+            .await?;
+        match resp.error {
+            Some(error) => {
+                log::error!("A Slack API error occured {}.", error);
+                Err(anyhow::Error::new(SlackUserGroupFetchingError {
+                    message: error,
+                }))
+            }
+            None => Ok(resp.users),
+        }
+    }
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    pub async fn slack_command_permissions(&mut self) -> Result<RoswaalSlackCommandPermissions> {
+        let rows =
+            query_as::<SqliteSlackCommandPermission>(statements::SELECT_SLACK_COMMAND_PERMISSIONS)
+                .fetch_all(self.connection())
+                .await?;
+        let mut permissions = RoswaalSlackCommandPermissions::default();
+        for row in rows {
+            // NB: Rows for commands that no longer exist are ignored rather than failing every
+            // command that is checked against the permissions.
+            let Ok(command) = RoswaalSlackCommand::from_str(&row.command) else {
+                continue;
+            };
+            let subject = if row.is_user_group {
+                RoswaalSlackPermissionSubject::UserGroup(row.subject_id)
+            } else {
+                RoswaalSlackPermissionSubject::User(row.subject_id)
+            };
+            permissions
+                .subjects
+                .entry(command)
+                .or_default()
+                .push(subject);
+        }
+        Ok(permissions)
+    }
+
+    /// Replaces the users and user groups that are allowed to run `command` with `subjects`.
+    pub async fn set_slack_command_subjects(
+        &mut self,
+        command: RoswaalSlackCommand,
+        subjects: &[RoswaalSlackPermissionSubject],
+    ) -> Result<()> {
+        let command: &str = command.into();
+        query(statements::DELETE_SLACK_COMMAND_PERMISSIONS)
+            .bind(command)
+            .execute(self.connection())
+            .await?;
+        for (ordinal, subject) in subjects.iter().enumerate() {
+            let (subject_id, is_user_group) = match subject {
+                RoswaalSlackPermissionSubject::User(id) => (id, false),
+                RoswaalSlackPermissionSubject::UserGroup(id) => (id, true),
+            };
+            query(statements::INSERT_SLACK_COMMAND_PERMISSION)
+                .bind(command)
+                .bind(subject_id)
+                .bind(is_user_group)
+                .bind(ordinal as i64)
+                .execute(self.connection())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteSlackCommandPermission {
+    command: String,
+    subject_id: String,
+    is_user_group: bool,
+}
+
+mod statements {
+    pub const SELECT_SLACK_COMMAND_PERMISSIONS: &str = "
+SELECT command, subject_id, is_user_group
+FROM SlackCommandPermissions
+ORDER BY command, ordinal;
+";
+
+    pub const DELETE_SLACK_COMMAND_PERMISSIONS: &str =
+        "DELETE FROM SlackCommandPermissions WHERE command = ?;";
+
+    pub const INSERT_SLACK_COMMAND_PERMISSION: &str = "
+INSERT INTO SlackCommandPermissions (command, subject_id, is_user_group, ordinal)
+VALUES (?, ?, ?, ?)
+ON CONFLICT (command, subject_id) DO NOTHING;
+";
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use std::collections::HashMap;
+
+    use anyhow::Result;
+
+    use crate::utils::test_error::TestError;
+
+    use super::SlackFetchUserGroupMembers;
+
+    /// A `SlackFetchUserGroupMembers` that returns the members of user groups by their id, and
+    /// fails for any unknown user group.
+    pub struct TestSlackUserGroupFetch {
+        members: HashMap<String, Vec<String>>,
+    }
+
+    impl TestSlackUserGroupFetch {
+        pub fn new(groups: &[(&str, &[&str])]) -> Self {
+            Self {
+                members: groups
+                    .iter()
+                    .map(|(id, members)| {
+                        (
+                            id.to_string(),
+                            members.iter().map(|m| m.to_string()).collect(),
+                        )
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    impl SlackFetchUserGroupMembers for TestSlackUserGroupFetch {
+        async fn fetch_user_group_members(&self, user_group_id: &str) -> Result<Vec<String>> {
+            self.members
+                .get(user_group_id)
+                .cloned()
+                .ok_or_else(|| anyhow::Error::new(TestError))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{utils::db::RoswaalDatabase, with_transaction};
+
+    use super::*;
+
+    #[test]
+    fn parses_subjects_from_mentions() {
+        let mentions = [
+            (
+                "<@U01234567|matthew>",
+                Some(RoswaalSlackPermissionSubject::User("U01234567".to_string())),
+            ),
+            (
+                "<!subteam^S01234567|@qa>",
+                Some(RoswaalSlackPermissionSubject::UserGroup(
+                    "S01234567".to_string(),
+                )),
+            ),
+            (
+                "<!subteam^S01234567>",
+                Some(RoswaalSlackPermissionSubject::UserGroup(
+                    "S01234567".to_string(),
+                )),
+            ),
+            ("<!subteam^U01234567>", None),
+            ("<!here>", None),
+            ("@qa", None),
+        ];
+        for (mention, subject) in mentions {
+            assert_eq!(
+                RoswaalSlackPermissionSubject::from_mention(mention),
+                subject
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn stores_and_replaces_subjects_of_commands() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let subjects = vec![
+            RoswaalSlackPermissionSubject::UserGroup("S1".to_string()),
+            RoswaalSlackPermissionSubject::User("U1".to_string()),
+        ];
+        let permissions = with_transaction!(transaction, async {
+            transaction
+                .set_slack_command_subjects(RoswaalSlackCommand::RemoveTests, &subjects)
+                .await?;
+            transaction
+                .set_slack_command_subjects(
+                    RoswaalSlackCommand::AddLocations,
+                    &[RoswaalSlackPermissionSubject::User("U2".to_string())],
+                )
+                .await?;
+            transaction
+                .set_slack_command_subjects(RoswaalSlackCommand::AddLocations, &[])
+                .await?;
+            transaction.slack_command_permissions().await
+        })
+        .unwrap();
+        let expected_permissions = RoswaalSlackCommandPermissions::default()
+            .with_subjects(RoswaalSlackCommand::RemoveTests, subjects);
+        assert_eq!(permissions, expected_permissions);
+        assert_eq!(
+            permissions.restricted_commands(),
+            vec![(
+                RoswaalSlackCommand::RemoveTests,
+                permissions.subjects(RoswaalSlackCommand::RemoveTests)
+            )]
+        )
+    }
+}
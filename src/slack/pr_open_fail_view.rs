@@ -1,15 +1,17 @@
-use super::ui_lib::{block_kit_views::SlackSection, slack_view::SlackView};
+use super::{
+    catalog::RoswaalMessageId,
+    ui_lib::{block_kit_views::SlackSection, slack_view::SlackView},
+};
 
 /// A view for indicating that a pull request couldn't be opened.
 pub struct FailedToOpenPullRequestView;
 
 impl SlackView for FailedToOpenPullRequestView {
     fn slack_body(&self) -> impl SlackView {
-        SlackSection::from_markdown("🔴 *Error: Failed to open Pull Request*").flat_chain_block(
-            SlackSection::from_markdown(
-                "_The pull request could not be opeeeeened. Check the logs for deeeeeeetails._",
-            ),
-        )
+        SlackSection::from_markdown(RoswaalMessageId::FailedToOpenPullRequestTitle.text())
+            .flat_chain_block(SlackSection::from_markdown(
+                RoswaalMessageId::FailedToOpenPullRequestBody.text(),
+            ))
     }
 }
 
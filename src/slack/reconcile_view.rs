@@ -0,0 +1,180 @@
+use std::borrow::Borrow;
+
+use crate::{
+    operations::reconcile::{
+        ReconcileTestsStatus, RoswaalTestDirectoryReconciliation, RECONCILE_TESTS_CLEANUP_ACTION,
+    },
+    utils::config::RoswaalConfig,
+};
+
+use super::{
+    merge_conflict_view::MergeConflictView,
+    pr_open_fail_view::FailedToOpenPullRequestView,
+    remote_unavailable_view::RemoteUnavailableView,
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        if_view::If,
+        slack_view::SlackView,
+    },
+    users::RoswaalSlackUserRoster,
+    warn_undeleted_branch_view::WarnUndeletedBranchView,
+};
+
+/// A view for reporting the generated test directories that do not match the stored tests.
+pub struct ReconcileTestsView {
+    status: ReconcileTestsStatus,
+    roster: RoswaalSlackUserRoster,
+}
+
+impl ReconcileTestsView {
+    pub fn new(status: ReconcileTestsStatus) -> Self {
+        Self {
+            status,
+            roster: RoswaalSlackUserRoster::default(),
+        }
+    }
+
+    /// Pings the users in `roster` when something needs to be fixed by hand.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
+    }
+}
+
+impl SlackView for ReconcileTestsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Reconcile Tests").flat_chain_block(self.status_view())
+    }
+}
+
+impl ReconcileTestsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            ReconcileTestsStatus::Report(reconciliation) => {
+                let has_orphans = !reconciliation.orphaned_directory_names().is_empty();
+                Self::reconciliation_view(reconciliation)
+                    .flat_chain_block(If::is_true(has_orphans, || {
+                        SlackDivider
+                            .flat_chain_block(SlackHeader::new("Next Steps"))
+                            .flat_chain_block(SlackSection::from_markdown(&format!(
+                                "Use `/reconcile-tests {}` to open a PR that removes the orphaned directoooooories!",
+                                RECONCILE_TESTS_CLEANUP_ACTION
+                            )))
+                    }))
+                    .erase_to_any_view()
+            }
+            ReconcileTestsStatus::CleanedUp {
+                reconciliation,
+                should_warn_undeleted_branch,
+                branch_name: _,
+            } => Self::reconciliation_view(reconciliation)
+                .flat_chain_block(SlackDivider)
+                .flat_chain_block(SlackHeader::new("Next Steps"))
+                .flat_chain_block(SlackSection::from_markdown(&format!(
+                    "Approve the PR found in <#{}> to remove the orphaned directoooooories!",
+                    RoswaalConfig::current().pull_request_channel_id()
+                )))
+                .flat_chain_block(If::is_true(*should_warn_undeleted_branch, || {
+                    SlackDivider.flat_chain_block(WarnUndeletedBranchView)
+                }))
+                .erase_to_any_view(),
+            ReconcileTestsStatus::InvalidAction(action) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not an action I know. Use `{}`, or no action to only see the repoooooort.",
+                action, RECONCILE_TESTS_CLEANUP_ACTION
+            ))
+            .erase_to_any_view(),
+            ReconcileTestsStatus::FailedToOpenPullRequest => {
+                FailedToOpenPullRequestView.erase_to_any_view()
+            }
+            ReconcileTestsStatus::MergeConflict => {
+                MergeConflictView::new(&self.roster).erase_to_any_view()
+            }
+            ReconcileTestsStatus::RemoteUnavailable => RemoteUnavailableView.erase_to_any_view(),
+        }
+    }
+
+    fn reconciliation_view(reconciliation: &RoswaalTestDirectoryReconciliation) -> impl SlackView {
+        if reconciliation.is_reconciled() {
+            return SlackSection::from_markdown(
+                "✅ Every test directory belongs to a stored teeeeeest!",
+            )
+            .erase_to_any_view();
+        }
+        let mut body = String::new();
+        let orphaned_directory_names = reconciliation.orphaned_directory_names();
+        if !orphaned_directory_names.is_empty() {
+            body.push_str("🔴 *These directories do not belong to any stored test:*\n");
+            for name in orphaned_directory_names {
+                body.push_str(&format!("- `{}`\n", name))
+            }
+        }
+        let missing_test_names = reconciliation.missing_test_names();
+        if !missing_test_names.is_empty() {
+            body.push_str("⚠️ *These merged tests have no generated directory:*\n");
+            for name in missing_test_names {
+                body.push_str(&format!("- {}\n", name))
+            }
+        }
+        SlackSection::from_markdown(&body).erase_to_any_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        tests_data::test::RoswaalTest,
+    };
+
+    use super::*;
+
+    #[test]
+    fn report_snapshot() {
+        let tests = vec![RoswaalTest::new(
+            "Leave an event".to_string(),
+            None,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )];
+        let directory_names = vec!["join-an-event".to_string(), "view-the-map".to_string()];
+        assert_slack_view_snapshot(
+            "reconcile-tests-report",
+            &ReconcileTestsView::new(ReconcileTestsStatus::Report(
+                RoswaalTestDirectoryReconciliation::new(&directory_names, &tests),
+            )),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn cleaned_up_snapshot() {
+        let directory_names = vec!["join-an-event".to_string()];
+        assert_slack_view_snapshot(
+            "reconcile-tests-cleaned-up",
+            &ReconcileTestsView::new(ReconcileTestsStatus::CleanedUp {
+                reconciliation: RoswaalTestDirectoryReconciliation::new(&directory_names, &[]),
+                should_warn_undeleted_branch: false,
+                branch_name: RoswaalOwnedGitBranchName::for_removing_orphaned_tests(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn reconciled_snapshot() {
+        assert_slack_view_snapshot(
+            "reconcile-tests-reconciled",
+            &ReconcileTestsView::new(ReconcileTestsStatus::Report(
+                RoswaalTestDirectoryReconciliation::default(),
+            )),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,33 @@
+use super::{
+    catalog::RoswaalMessageId,
+    ui_lib::{block_kit_views::SlackSection, slack_view::SlackView},
+};
+
+/// A view for indicating that the remote repository or GitHub could not be reached, even after
+/// retrying.
+pub struct RemoteUnavailableView;
+
+impl SlackView for RemoteUnavailableView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown(RoswaalMessageId::RemoteUnavailableTitle.text())
+            .flat_chain_block(SlackSection::from_markdown(
+                RoswaalMessageId::RemoteUnavailableBody.text(),
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::RemoteUnavailableView;
+
+    #[test]
+    fn snapshot() {
+        assert_slack_view_snapshot(
+            "remote-unavailable",
+            &RemoteUnavailableView,
+            SnapshotMode::Comparing,
+        )
+    }
+}
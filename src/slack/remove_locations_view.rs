@@ -0,0 +1,168 @@
+use std::borrow::Borrow;
+
+use crate::{
+    location::name::RoswaalLocationName, operations::remove_locations::RemoveLocationsStatus,
+    utils::config::RoswaalConfig,
+};
+
+use super::{
+    merge_conflict_view::MergeConflictView,
+    pr_open_fail_view::FailedToOpenPullRequestView,
+    remote_unavailable_view::RemoteUnavailableView,
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        if_view::If,
+        slack_view::SlackView,
+    },
+    users::RoswaalSlackUserRoster,
+    warn_undeleted_branch_view::WarnUndeletedBranchView,
+};
+
+/// A view for removing locations.
+pub struct RemoveLocationsView {
+    status: RemoveLocationsStatus,
+    roster: RoswaalSlackUserRoster,
+}
+
+impl RemoveLocationsView {
+    pub fn new(status: RemoveLocationsStatus) -> Self {
+        Self {
+            status,
+            roster: RoswaalSlackUserRoster::default(),
+        }
+    }
+
+    /// Pings the users in `roster` when something needs to be fixed by hand.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
+    }
+}
+
+impl SlackView for RemoveLocationsView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Remove Locations").flat_chain_block(self.status_view())
+    }
+}
+
+impl RemoveLocationsView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            RemoveLocationsStatus::Success {
+                removed_location_names,
+                should_warn_undeleted_branch,
+                branch_name: _,
+            } => self
+                .location_names_view(removed_location_names)
+                .flat_chain_block(SlackDivider)
+                .flat_chain_block(SlackHeader::new("Next Steps"))
+                .flat_chain_block(SlackSection::from_markdown(
+                    &format!(
+                        "Approve the PR found in <#{}> to finish the remooooooval!",
+                        RoswaalConfig::current().pull_request_channel_id()
+                    ),
+                ))
+                .flat_chain_block(If::is_true(*should_warn_undeleted_branch, || {
+                    SlackDivider.flat_chain_block(WarnUndeletedBranchView)
+                }))
+                .erase_to_any_view(),
+            RemoveLocationsStatus::NoLocationsRemoved => SlackSection::from_markdown(
+                "🔴 No locations were staged for remoooooooval! Use `/view-locations` to see the names of all locations.",
+            )
+            .erase_to_any_view(),
+            RemoveLocationsStatus::FailedToOpenPullRequest => {
+                FailedToOpenPullRequestView.erase_to_any_view()
+            }
+            RemoveLocationsStatus::RemoteUnavailable => {
+                RemoteUnavailableView.erase_to_any_view()
+            }
+            RemoveLocationsStatus::MergeConflict => {
+                MergeConflictView::new(&self.roster).erase_to_any_view()
+            }
+        }
+    }
+
+    fn location_names_view(&self, names: &Vec<RoswaalLocationName>) -> impl SlackView {
+        let mut body = "🗑️ *The following locations were staged for remoooooooval!*\n".to_string();
+        for name in names {
+            body.push_str(&format!("- {}\n", name.raw_name()))
+        }
+        SlackSection::from_markdown(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        location::name::RoswaalLocationName,
+        operations::remove_locations::RemoveLocationsStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::RemoveLocationsView;
+
+    #[test]
+    fn success_snapshot() {
+        assert_slack_view_snapshot(
+            "remove-locations-success",
+            &RemoveLocationsView::new(RemoveLocationsStatus::Success {
+                removed_location_names: location_names(),
+                should_warn_undeleted_branch: false,
+                branch_name: RoswaalOwnedGitBranchName::for_removing_locations(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_warn_undeleted_branch_snapshot() {
+        assert_slack_view_snapshot(
+            "remove-locations-success-warn-undeleted-branch",
+            &RemoveLocationsView::new(RemoveLocationsStatus::Success {
+                removed_location_names: location_names(),
+                should_warn_undeleted_branch: true,
+                branch_name: RoswaalOwnedGitBranchName::for_removing_locations(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn none_removed_snapshot() {
+        assert_slack_view_snapshot(
+            "remove-locations-none-removed",
+            &RemoveLocationsView::new(RemoveLocationsStatus::NoLocationsRemoved),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn merge_conflict_snapshot() {
+        assert_slack_view_snapshot(
+            "remove-locations-merge-conflict",
+            &RemoveLocationsView::new(RemoveLocationsStatus::MergeConflict),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn pr_open_failed_snapshot() {
+        assert_slack_view_snapshot(
+            "remove-locations-pr-fail",
+            &RemoveLocationsView::new(RemoveLocationsStatus::FailedToOpenPullRequest),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn location_names() -> Vec<RoswaalLocationName> {
+        vec![
+            RoswaalLocationName::from_str("Antarctica").unwrap(),
+            RoswaalLocationName::from_str("New York").unwrap(),
+        ]
+    }
+}
@@ -0,0 +1,43 @@
+use super::{
+    modal::REMOVE_TESTS_INPUT_BLOCK_ID,
+    select_menu::RoswaalSlackSelectMenu,
+    ui_lib::{
+        block_kit_views::{SlackMultiExternalSelectInput, SlackSection},
+        slack_view::SlackView,
+    },
+};
+
+/// The contents of the modal that is opened when `/remove-tests` is sent without any test names.
+pub struct RemoveTestsModalView;
+
+impl SlackView for RemoveTestsModalView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown(
+            "🗑 Pick the merged teeeeeests to remove below! A PR that removes their code will be opened once you submit.",
+        )
+        .flat_chain_block(
+            SlackMultiExternalSelectInput::new(
+                REMOVE_TESTS_INPUT_BLOCK_ID,
+                RoswaalSlackSelectMenu::TestNames.into(),
+                "Tests",
+            )
+            .placeholder("Search for tests"),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::RemoveTestsModalView;
+
+    #[test]
+    fn snapshot() {
+        assert_slack_view_snapshot(
+            "remove-tests-modal",
+            &RemoveTestsModalView,
+            SnapshotMode::Comparing,
+        )
+    }
+}
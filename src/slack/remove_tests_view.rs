@@ -1,26 +1,40 @@
 use std::borrow::Borrow;
 
-use crate::operations::remove_tests::RemoveTestsStatus;
+use crate::{operations::remove_tests::RemoveTestsStatus, utils::config::RoswaalConfig};
 
 use super::{
     merge_conflict_view::MergeConflictView,
     pr_open_fail_view::FailedToOpenPullRequestView,
+    remote_unavailable_view::RemoteUnavailableView,
+    target_repositories_view::TargetRepositoriesView,
     ui_lib::{
         block_kit_views::{SlackDivider, SlackHeader, SlackSection},
         if_view::If,
         slack_view::SlackView,
     },
-    users::MATTHEW_SLACK_USER_ID,
+    users::RoswaalSlackUserRoster,
     warn_undeleted_branch_view::WarnUndeletedBranchView,
 };
 
 pub struct RemoveTestsView {
     status: RemoveTestsStatus,
+    roster: RoswaalSlackUserRoster,
 }
 
 impl RemoveTestsView {
     pub fn new(status: RemoveTestsStatus) -> Self {
-        Self { status }
+        Self {
+            status,
+            roster: RoswaalSlackUserRoster::default(),
+        }
+    }
+
+    /// Pings the users in `roster` when something needs to be fixed by hand.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
     }
 }
 
@@ -35,17 +49,24 @@ impl RemoveTestsView {
         match self.status.borrow() {
             RemoveTestsStatus::Success {
                 removed_test_names,
+                removed_tests: _,
                 should_warn_undeleted_branch,
+                branch_name: _,
+                target_edits,
             } => self
                 .test_names_view(removed_test_names)
                 .flat_chain_block(SlackDivider)
                 .flat_chain_block(SlackHeader::new("Next Steps"))
-                .flat_chain_block(SlackSection::from_markdown(
-                    "Approve the PR found in <#C01B7FFKDCP> to finish the remooooooval!",
-                ))
+                .flat_chain_block(SlackSection::from_markdown(&format!(
+                    "Approve the PR found in <#{}> to finish the remooooooval!",
+                    RoswaalConfig::current().pull_request_channel_id()
+                )))
                 .flat_chain_block(If::is_true(*should_warn_undeleted_branch, || {
                     SlackDivider.flat_chain_block(WarnUndeletedBranchView)
                 }))
+                .flat_chain_block(
+                    TargetRepositoriesView::new(target_edits).with_roster(&self.roster),
+                )
                 .erase_to_any_view(),
             RemoveTestsStatus::NoTestsRemoved => {
                 SlackSection::from_markdown("🔴 No tests were staged for remoooooooval!")
@@ -54,8 +75,9 @@ impl RemoveTestsView {
             RemoveTestsStatus::FailedToOpenPullRequest => {
                 FailedToOpenPullRequestView.erase_to_any_view()
             }
+            RemoveTestsStatus::RemoteUnavailable => RemoteUnavailableView.erase_to_any_view(),
             RemoveTestsStatus::MergeConflict => {
-                MergeConflictView::new(MATTHEW_SLACK_USER_ID).erase_to_any_view()
+                MergeConflictView::new(&self.roster).erase_to_any_view()
             }
         }
     }
@@ -89,7 +111,10 @@ mod tests {
             "remove-tests-success",
             &RemoveTestsView::new(RemoveTestsStatus::Success {
                 removed_test_names,
+                removed_tests: vec![],
                 should_warn_undeleted_branch: false,
+                branch_name: None,
+                target_edits: vec![],
             }),
             SnapshotMode::Comparing,
         )
@@ -106,7 +131,10 @@ mod tests {
             "remove-tests-success-warn-undeleted-branch",
             &RemoveTestsView::new(RemoveTestsStatus::Success {
                 removed_test_names,
+                removed_tests: vec![],
                 should_warn_undeleted_branch: true,
+                branch_name: None,
+                target_edits: vec![],
             }),
             SnapshotMode::Comparing,
         )
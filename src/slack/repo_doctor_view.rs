@@ -0,0 +1,221 @@
+use std::borrow::Borrow;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    operations::repo_doctor::{RepoDoctorAction, RepoDoctorStatus, RoswaalRepositoryCheckup},
+    slack::users::RoswaalSlackUserRole,
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    for_each_view::ForEachView,
+    slack_view::SlackView,
+};
+
+/// The maximum number of dirty paths to list for a single repository.
+const MAX_LISTED_DIRTY_PATHS: usize = 5;
+
+/// A view for diagnosing and repairing the local clones of each repository.
+pub struct RepoDoctorView {
+    status: RepoDoctorStatus,
+}
+
+impl RepoDoctorView {
+    pub fn new(status: RepoDoctorStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for RepoDoctorView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Repo Doctor").flat_chain_block(self.status_view())
+    }
+}
+
+impl RepoDoctorView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            RepoDoctorStatus::Success { action, checkups } => {
+                let summary = match action {
+                    Some(action) => format!("🩺 Ran `{}` on every repository.", action),
+                    None => "🩺 Examined every repository.".to_string(),
+                };
+                let needs_repair = checkups.iter().any(|c| !c.health().is_healthy());
+                SlackSection::from_markdown(&summary)
+                    .flat_chain_block(ForEachView::new(checkups.iter(), |checkup| {
+                        SlackSection::from_markdown(&Self::checkup_body(checkup))
+                    }))
+                    .flat_chain_block(needs_repair.then(|| {
+                        SlackDivider
+                            .flat_chain_block(SlackHeader::new("Next Steps"))
+                            .flat_chain_block(SlackSection::from_markdown(&format!(
+                                "Use {} to nuuuuurse the repositories back to health!",
+                                Self::action_list("/repo-doctor ")
+                            )))
+                    }))
+                    .erase_to_any_view()
+            }
+            RepoDoctorStatus::InvalidAction(action) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not a treatment I know. Use {}.",
+                action,
+                Self::action_list("")
+            ))
+            .erase_to_any_view(),
+            RepoDoctorStatus::NotAuthorized { roster } => SlackSection::from_markdown(&format!(
+                "🔴 Only {} can run the repo doctor. Ask them niiiiiicely!",
+                roster.mentions(RoswaalSlackUserRole::InfraOwner)
+            ))
+            .erase_to_any_view(),
+        }
+    }
+
+    fn checkup_body(checkup: &RoswaalRepositoryCheckup) -> String {
+        let health = checkup.health();
+        let mut body = match (health.is_healthy(), health.current_branch_name()) {
+            (true, Some(branch_name)) => {
+                format!("✅ *{}* is healthy on `{}`.\n", checkup.name(), branch_name)
+            }
+            (false, Some(branch_name)) => format!(
+                "🔴 *{}* needs attention on `{}`.\n",
+                checkup.name(),
+                branch_name
+            ),
+            (_, None) => format!(
+                "🔴 *{}* needs attention, HEAD is detached.\n",
+                checkup.name()
+            ),
+        };
+        if health.is_merging() {
+            body.push_str("- A merge is in progress.\n")
+        }
+        let dirty_paths = health.dirty_paths();
+        if !dirty_paths.is_empty() {
+            let listed_paths = dirty_paths
+                .iter()
+                .take(MAX_LISTED_DIRTY_PATHS)
+                .map(|path| format!("`{}`", path))
+                .collect::<Vec<String>>()
+                .join(", ");
+            let unlisted_count = dirty_paths.len().saturating_sub(MAX_LISTED_DIRTY_PATHS);
+            body.push_str(&format!(
+                "- {} uncommitted file(s): {}",
+                dirty_paths.len(),
+                listed_paths
+            ));
+            if unlisted_count > 0 {
+                body.push_str(&format!(", and {} more", unlisted_count))
+            }
+            body.push('\n')
+        }
+        if !checkup.pruned_branch_names().is_empty() {
+            body.push_str(&format!(
+                "- Pruned {} branch(es): {}\n",
+                checkup.pruned_branch_names().len(),
+                checkup
+                    .pruned_branch_names()
+                    .iter()
+                    .map(|name| format!("`{}`", name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+        }
+        body
+    }
+
+    fn action_list(prefix: &str) -> String {
+        RepoDoctorAction::iter()
+            .map(|action| format!("`{}{}`", prefix, action))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::health::RoswaalGitRepositoryHealth,
+        operations::repo_doctor::{RepoDoctorAction, RepoDoctorStatus, RoswaalRepositoryCheckup},
+        slack::{
+            ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+            users::RoswaalSlackUserRoster,
+        },
+    };
+
+    use super::RepoDoctorView;
+
+    #[test]
+    fn healthy_snapshot() {
+        let status = RepoDoctorStatus::Success {
+            action: Some(RepoDoctorAction::Prune),
+            checkups: vec![
+                RoswaalRepositoryCheckup::new(
+                    "FitnessProject".to_string(),
+                    RoswaalGitRepositoryHealth::clean("development"),
+                    vec!["roswaal-add-tests-abc".to_string()],
+                ),
+                RoswaalRepositoryCheckup::new(
+                    "TiFShared".to_string(),
+                    RoswaalGitRepositoryHealth::clean("main"),
+                    vec![],
+                ),
+            ],
+        };
+        assert_slack_view_snapshot(
+            "repo-doctor-healthy",
+            &RepoDoctorView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn unhealthy_snapshot() {
+        let dirty_paths = (1..=7)
+            .map(|i| format!("roswaal/test-{}/test.spec.ts", i))
+            .collect();
+        let status = RepoDoctorStatus::Success {
+            action: None,
+            checkups: vec![
+                RoswaalRepositoryCheckup::new(
+                    "FitnessProject".to_string(),
+                    RoswaalGitRepositoryHealth::new(
+                        Some("roswaal-add-tests-abc".to_string()),
+                        dirty_paths,
+                        true,
+                    ),
+                    vec![],
+                ),
+                RoswaalRepositoryCheckup::new(
+                    "TiFShared".to_string(),
+                    RoswaalGitRepositoryHealth::new(None, vec![], false),
+                    vec![],
+                ),
+            ],
+        };
+        assert_slack_view_snapshot(
+            "repo-doctor-unhealthy",
+            &RepoDoctorView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn invalid_action_snapshot() {
+        assert_slack_view_snapshot(
+            "repo-doctor-invalid-action",
+            &RepoDoctorView::new(RepoDoctorStatus::InvalidAction("amputate".to_string())),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn not_authorized_snapshot() {
+        assert_slack_view_snapshot(
+            "repo-doctor-not-authorized",
+            &RepoDoctorView::new(RepoDoctorStatus::NotAuthorized {
+                roster: RoswaalSlackUserRoster::default(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use log::error;
+use nanoid::nanoid;
+
+use crate::{
+    utils::db::{
+        query::{query, query_scalar},
+        RoswaalDatabase, RoswaalDatabaseTransaction,
+    },
+    with_transaction,
+};
+
+use super::{
+    message::{SlackMessage, SlackSendMessage},
+    truncated_report_view::TruncatedReportView,
+    ui_lib::blocks::{SlackBlocks, SLACK_MESSAGE_MAX_BLOCKS},
+};
+
+/// Returns `blocks` cut down to the number of blocks that slack allows in a message, or None if
+/// `blocks` already fits.
+///
+/// The full blocks are saved as a report in `database`, and the cut off blocks are replaced by a
+/// `TruncatedReportView` that links to the report. Failing to save the report is logged, and the
+/// blocks are still cut down without a link, since slack rejects the message otherwise.
+pub async fn truncate_to_slack_message(
+    blocks: &SlackBlocks,
+    database: &RoswaalDatabase,
+) -> Option<SlackBlocks> {
+    if blocks.count() <= SLACK_MESSAGE_MAX_BLOCKS {
+        return None;
+    }
+    let report_id = match save_slack_report(blocks, database).await {
+        Ok(id) => Some(id),
+        Err(err) => {
+            error!(
+                "Failed to save the full report of a truncated message {}.",
+                err
+            );
+            None
+        }
+    };
+    blocks.truncated(SLACK_MESSAGE_MAX_BLOCKS, |truncated_count| {
+        TruncatedReportView::new(truncated_count, report_id.as_deref())
+    })
+}
+
+async fn save_slack_report(blocks: &SlackBlocks, database: &RoswaalDatabase) -> Result<String> {
+    let mut transaction = database.transaction().await?;
+    with_transaction!(transaction, async {
+        transaction.save_slack_report(blocks).await
+    })
+}
+
+/// A `SlackSendMessage` that truncates messages with more blocks than slack allows before they
+/// are sent, and saves their full blocks as a report that can be viewed at `/reports/<id>`.
+pub struct TruncatingSlackSendMessage<Messenger: SlackSendMessage> {
+    messenger: Arc<Messenger>,
+    database: Arc<RoswaalDatabase>,
+}
+
+impl<Messenger: SlackSendMessage> TruncatingSlackSendMessage<Messenger> {
+    pub fn new(messenger: Arc<Messenger>, database: Arc<RoswaalDatabase>) -> Self {
+        Self {
+            messenger,
+            database,
+        }
+    }
+}
+
+impl<Messenger: SlackSendMessage + Send + Sync> SlackSendMessage
+    for TruncatingSlackSendMessage<Messenger>
+{
+    async fn send(&self, message: &SlackMessage) -> Result<Option<String>> {
+        match truncate_to_slack_message(message.blocks(), &self.database).await {
+            Some(blocks) => {
+                let message = message.clone().with_blocks(blocks);
+                self.messenger.send(&message).await
+            }
+            None => self.messenger.send(message).await,
+        }
+    }
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Saves `blocks` as a report, and returns the id that the report can be loaded with.
+    pub async fn save_slack_report(&mut self, blocks: &SlackBlocks) -> Result<String> {
+        let id = nanoid!();
+        query(statements::INSERT_SLACK_REPORT)
+            .bind(&id)
+            .bind(serde_json::to_string(blocks)?)
+            .execute(self.connection())
+            .await?;
+        Ok(id)
+    }
+
+    /// Returns the blocks of the report with `id`, if any.
+    pub async fn slack_report(&mut self, id: &str) -> Result<Option<SlackBlocks>> {
+        let blocks = query_scalar::<String>(statements::SELECT_SLACK_REPORT)
+            .bind(id)
+            .fetch_optional(self.connection())
+            .await?;
+        match blocks {
+            Some(blocks) => Ok(Some(serde_json::from_str(&blocks)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+mod statements {
+    pub const INSERT_SLACK_REPORT: &str = "INSERT INTO SlackReports (id, blocks) VALUES (?, ?);";
+
+    pub const SELECT_SLACK_REPORT: &str = "SELECT blocks FROM SlackReports WHERE id = ?;";
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::Mutex;
+
+    use crate::slack::{
+        message::SLACK_POST_MESSAGE_URL,
+        ui_lib::{
+            block_kit_views::SlackSection,
+            for_each_view::ForEachView,
+            slack_view::{render_slack_view, SlackView},
+        },
+    };
+
+    use super::*;
+
+    struct TestSlackMessager {
+        messages: Mutex<Vec<SlackMessage>>,
+    }
+
+    impl SlackSendMessage for TestSlackMessager {
+        async fn send(&self, message: &SlackMessage) -> Result<Option<String>> {
+            self.messages.lock().await.push(message.clone());
+            Ok(None)
+        }
+    }
+
+    fn sections(count: usize) -> impl SlackView {
+        ForEachView::new(0..count, |index| {
+            SlackSection::from_markdown(&format!("Error {}", index))
+        })
+    }
+
+    #[tokio::test]
+    async fn does_not_truncate_blocks_that_fit_in_a_message() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let blocks = render_slack_view(&sections(SLACK_MESSAGE_MAX_BLOCKS));
+        assert_eq!(truncate_to_slack_message(&blocks, &database).await, None)
+    }
+
+    #[tokio::test]
+    async fn truncates_blocks_and_saves_the_full_report() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let blocks = render_slack_view(&sections(60));
+        let truncated = truncate_to_slack_message(&blocks, &database).await.unwrap();
+        assert_eq!(truncated.count(), SLACK_MESSAGE_MAX_BLOCKS);
+        let transcript = truncated.transcript();
+        assert!(transcript.contains("Error 48"));
+        assert!(!transcript.contains("Error 49"));
+        assert!(transcript.contains("11 more blocks"));
+        let report_id = transcript
+            .split("/reports/")
+            .nth(1)
+            .and_then(|path| path.split('.').next())
+            .unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let report = transaction.slack_report(report_id).await.unwrap();
+        assert_eq!(report, Some(blocks))
+    }
+
+    #[tokio::test]
+    async fn sends_truncated_messages() {
+        let database = Arc::new(RoswaalDatabase::in_memory().await.unwrap());
+        let messenger = Arc::new(TestSlackMessager {
+            messages: Mutex::new(vec![]),
+        });
+        let truncating = TruncatingSlackSendMessage::new(messenger.clone(), database);
+        let short_message = SlackMessage::new("C123", &sections(3), SLACK_POST_MESSAGE_URL);
+        let long_message = SlackMessage::new("C123", &sections(80), SLACK_POST_MESSAGE_URL);
+        truncating.send(&short_message).await.unwrap();
+        truncating.send(&long_message).await.unwrap();
+        let messages = messenger.messages.lock().await;
+        assert_eq!(messages[0], short_message);
+        assert_eq!(messages[1].blocks().count(), SLACK_MESSAGE_MAX_BLOCKS)
+    }
+
+    #[tokio::test]
+    async fn returns_none_for_unknown_reports() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        assert_eq!(transaction.slack_report("unknown").await.unwrap(), None)
+    }
+}
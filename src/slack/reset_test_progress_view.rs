@@ -0,0 +1,75 @@
+use std::borrow::Borrow;
+
+use crate::operations::reset_test_progress::ResetTestProgressStatus;
+
+use super::ui_lib::{
+    block_kit_views::{SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for listing the tests whose progress was cleared.
+pub struct ResetTestProgressView {
+    status: ResetTestProgressStatus,
+}
+
+impl ResetTestProgressView {
+    pub fn new(status: ResetTestProgressStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for ResetTestProgressView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Reset Test Progress").flat_chain_block(self.status_view())
+    }
+}
+
+impl ResetTestProgressView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            ResetTestProgressStatus::Success { reset_test_names } => {
+                let mut body =
+                    "🔄 The progress of the following tests was reset, and they will show as idle until their next run!\n"
+                        .to_string();
+                for name in reset_test_names {
+                    body.push_str(&format!("- *{}*\n", name))
+                }
+                SlackSection::from_markdown(&body)
+            }
+            ResetTestProgressStatus::NoTestsReset => SlackSection::from_markdown(
+                "⚠️ No merged tests were found. Use `/view-tests` to check the names of the tests!",
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::reset_test_progress::ResetTestProgressStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::ResetTestProgressView;
+
+    #[test]
+    fn success_snapshot() {
+        let status = ResetTestProgressStatus::Success {
+            reset_test_names: vec!["Join an event".to_string(), "Leave an event".to_string()],
+        };
+        assert_slack_view_snapshot(
+            "reset-test-progress-success",
+            &ResetTestProgressView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_tests_reset_snapshot() {
+        assert_slack_view_snapshot(
+            "reset-test-progress-no-tests-reset",
+            &ResetTestProgressView::new(ResetTestProgressStatus::NoTestsReset),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,84 @@
+use crate::operations::restore_test::RestoreTestStatus;
+
+use super::{
+    add_tests_view::AddTestsView,
+    ui_lib::{
+        any_view::AnySlackView,
+        block_kit_views::{SlackHeader, SlackSection},
+        slack_view::SlackView,
+    },
+    users::RoswaalSlackUserRoster,
+};
+
+/// A view for the response to `/restore-test`, which is the same as the response to `/add-tests`
+/// once the archived test is added back.
+pub enum RestoreTestView<'r> {
+    Restored(AddTestsView<'r>),
+    NoTestName,
+    NotArchived { name: String },
+}
+
+impl<'r> RestoreTestView<'r> {
+    pub fn new(status: RestoreTestStatus<'r>) -> Self {
+        match status {
+            RestoreTestStatus::Restored(status) => Self::Restored(AddTestsView::new(status)),
+            RestoreTestStatus::NoTestName => Self::NoTestName,
+            RestoreTestStatus::NotArchived { name } => Self::NotArchived { name },
+        }
+    }
+
+    /// Pings the users in `roster` when something needs to be fixed by hand.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        match self {
+            Self::Restored(view) => Self::Restored(view.with_roster(roster)),
+            view => view,
+        }
+    }
+}
+
+impl<'r> SlackView for RestoreTestView<'r> {
+    fn slack_body(&self) -> impl SlackView {
+        match self {
+            Self::Restored(view) => AnySlackView::erasing_ref(view),
+            Self::NoTestName => SlackHeader::new("Restore Test")
+                .flat_chain_block(SlackSection::from_markdown(
+                    "🔴 Include the name of the removed test to restoooooore!",
+                ))
+                .erase_to_any_view(),
+            Self::NotArchived { name } => SlackHeader::new("Restore Test")
+                .flat_chain_block(SlackSection::from_markdown(&format!(
+                    "🔴 No removed test named *{}* was found in the aaaaaarchive.",
+                    name
+                )))
+                .erase_to_any_view(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::*;
+
+    #[test]
+    fn no_test_name_snapshot() {
+        assert_slack_view_snapshot(
+            "restore-test-no-test-name",
+            &RestoreTestView::new(RestoreTestStatus::NoTestName),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn not_archived_snapshot() {
+        let status = RestoreTestStatus::NotArchived {
+            name: "Join an event".to_string(),
+        };
+        assert_slack_view_snapshot(
+            "restore-test-not-archived",
+            &RestoreTestView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+}
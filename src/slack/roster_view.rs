@@ -0,0 +1,141 @@
+use std::borrow::Borrow;
+
+use strum::IntoEnumIterator;
+
+use crate::operations::edit_roster::EditRosterStatus;
+
+use super::{
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        slack_view::SlackView,
+    },
+    users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+};
+
+/// A view for showing and editing the roster of slack users to ping.
+pub struct RosterView {
+    status: EditRosterStatus,
+}
+
+impl RosterView {
+    pub fn new(status: EditRosterStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for RosterView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Owners").flat_chain_block(self.status_view())
+    }
+}
+
+impl RosterView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            EditRosterStatus::Roster(roster) => {
+                SlackSection::from_markdown("👥 *Here's who gets pinged for eaaaaaach role!*")
+                    .flat_chain_block(Self::roster_view(roster))
+                    .erase_to_any_view()
+            }
+            EditRosterStatus::Success { role, roster } => {
+                SlackSection::from_markdown(&format!("✅ *The `{}` role was updaaaaaated!*", role))
+                    .flat_chain_block(Self::roster_view(roster))
+                    .erase_to_any_view()
+            }
+            EditRosterStatus::InvalidRole(name) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not a role. Use one of {}.",
+                name,
+                RoswaalSlackUserRole::iter()
+                    .map(|role| format!("`{}`", role))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+            .erase_to_any_view(),
+            EditRosterStatus::InvalidUser(mention) => SlackSection::from_markdown(&format!(
+                "🔴 `{}` is not a slack user. Mention users with `@` to add them to a roooooole.",
+                mention
+            ))
+            .erase_to_any_view(),
+            EditRosterStatus::NotAuthorized { roster } => SlackSection::from_markdown(&format!(
+                "🔴 Only {} can change the roster. Ask them niiiiiicely!",
+                roster.mentions(RoswaalSlackUserRole::InfraOwner)
+            ))
+            .erase_to_any_view(),
+        }
+    }
+
+    fn roster_view(roster: &RoswaalSlackUserRoster) -> impl SlackView {
+        let mut body = String::new();
+        for role in RoswaalSlackUserRole::iter() {
+            body.push_str(&format!("*{}*: {}\n", role, roster.mentions(role)));
+        }
+        SlackDivider.flat_chain_block(SlackSection::from_markdown(&body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::edit_roster::EditRosterStatus,
+        slack::{
+            ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+            users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+        },
+    };
+
+    use super::RosterView;
+
+    #[test]
+    fn roster_snapshot() {
+        let roster = RoswaalSlackUserRoster::default()
+            .with_user_ids(RoswaalSlackUserRole::MergeConflictOwner, &["U1", "U2"]);
+        assert_slack_view_snapshot(
+            "roster",
+            &RosterView::new(EditRosterStatus::Roster(roster)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_snapshot() {
+        let roster = RoswaalSlackUserRoster::default()
+            .with_user_ids(RoswaalSlackUserRole::InfraOwner, &["U1"]);
+        assert_slack_view_snapshot(
+            "roster-success",
+            &RosterView::new(EditRosterStatus::Success {
+                role: RoswaalSlackUserRole::InfraOwner,
+                roster,
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn invalid_role_snapshot() {
+        assert_slack_view_snapshot(
+            "roster-invalid-role",
+            &RosterView::new(EditRosterStatus::InvalidRole("owner".to_string())),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn invalid_user_snapshot() {
+        assert_slack_view_snapshot(
+            "roster-invalid-user",
+            &RosterView::new(EditRosterStatus::InvalidUser("@justin".to_string())),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn not_authorized_snapshot() {
+        assert_slack_view_snapshot(
+            "roster-not-authorized",
+            &RosterView::new(EditRosterStatus::NotAuthorized {
+                roster: RoswaalSlackUserRoster::default(),
+            }),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -1,10 +1,12 @@
-use std::borrow::Borrow;
+use std::{borrow::Borrow, time::Duration};
 
 use crate::{
     language::test::RoswaalCompiledTestCommand,
     operations::search_tests::SearchTestsStatus,
     tests_data::{
+        estimate::RoswaalTestRuntimeEstimator,
         ordinal::RoswaalTestCommandOrdinal,
+        progress::RoswaalTestStepScreenshot,
         test::{
             RoswaalTest, RoswaalTestCommand, RoswaalTestCommandStatus, RoswaalTestProgressStatus,
         },
@@ -13,6 +15,7 @@ use crate::{
 
 use super::{
     branch_name_view::OptionalBranchNameView,
+    catalog::RoswaalMessageId,
     ui_lib::{
         block_kit_views::{SlackDivider, SlackHeader, SlackSection},
         for_each_view::ForEachView,
@@ -34,7 +37,8 @@ impl SearchTestsView {
 
 impl SlackView for SearchTestsView {
     fn slack_body(&self) -> impl SlackView {
-        SlackHeader::new("Test Progress").flat_chain_block(self.status_view())
+        SlackHeader::new(RoswaalMessageId::SearchTestsHeader.text())
+            .flat_chain_block(self.status_view())
     }
 }
 
@@ -53,6 +57,7 @@ impl SearchTestsView {
                 count: count_progress_status(tests, RoswaalTestProgressStatus::Idle),
                 status: RoswaalTestProgressStatus::Idle,
             })
+            .flat_chain_block(suite_runtime_view(tests))
             .flat_chain_block(SlackDivider)
             .flat_chain_block(ForEachView::new(
                 tests.iter().map(|t| t.clone()).enumerate(),
@@ -63,12 +68,30 @@ impl SearchTestsView {
             ))
             .erase_to_any_view(),
             SearchTestsStatus::NoTests => {
-                SlackSection::from_markdown("🔴 No tests were fooooooound.").erase_to_any_view()
+                SlackSection::from_markdown(RoswaalMessageId::NoTestsFound.text())
+                    .erase_to_any_view()
             }
         }
     }
 }
 
+/// The estimated runtime of the tests that the test runner would run out of `tests`.
+///
+/// Nothing is shown when fewer than 2 tests would run, since each test already shows its own
+/// runtime.
+fn suite_runtime_view(tests: &[RoswaalTest]) -> Option<impl SlackView> {
+    let estimate = RoswaalTestRuntimeEstimator::new(tests).suite_estimate(tests);
+    if estimate.tests().len() < 2 {
+        return None;
+    }
+    let message = RoswaalMessageId::SearchTestsSuiteRuntime.format(&[
+        &format_suite_duration(estimate.total_duration()),
+        &estimate.measured_test_count(),
+        &estimate.unmeasured_test_count(),
+    ]);
+    Some(SlackSection::from_markdown(&message))
+}
+
 fn count_progress_status(tests: &Vec<RoswaalTest>, status: RoswaalTestProgressStatus) -> usize {
     tests
         .iter()
@@ -84,14 +107,16 @@ struct ProgressStatusCountView {
 impl SlackView for ProgressStatusCountView {
     fn slack_body(&self) -> impl SlackView {
         If::is_true(self.count > 0, || {
-            let word = if self.count == 1 { "Test" } else { "Tests" };
-            SlackSection::from_markdown(&format!(
-                "{} *{} {} {}*",
-                self.status.emoji(),
-                self.count,
-                word,
-                self.status.text()
-            ))
+            let message = if self.count == 1 {
+                RoswaalMessageId::SearchTestsStatusCountOne
+            } else {
+                RoswaalMessageId::SearchTestsStatusCountMany
+            };
+            SlackSection::from_markdown(&message.format(&[
+                &self.status.emoji(),
+                &self.count,
+                &self.status.text(),
+            ]))
         })
     }
 }
@@ -103,28 +128,47 @@ struct TestView {
 impl SlackView for TestView {
     fn slack_body(&self) -> impl SlackView {
         SlackSection::from_markdown(&format!(
-            "📝 *{}* ({} {})",
+            "📝 *{}* ({} {}){}{}{}",
             self.test.name(),
             self.test.progress_status().emoji(),
-            self.test.progress_status().text()
+            self.test.progress_status().text(),
+            self.platform_badges(),
+            if self.test.is_planned() {
+                format!(" {}", RoswaalMessageId::SearchTestsPlanned.text())
+            } else {
+                String::new()
+            },
+            if self.test.is_deprecated() {
+                " 🗑 _Deprecated_"
+            } else {
+                ""
+            }
         ))
         .flat_chain_block(IfLet::some(self.test.description(), |text| {
             SlackSection::from_plaintext(text)
         }))
+        .flat_chain_block(IfLet::some(self.test.added_by_slack_user_id(), |user_id| {
+            SlackSection::from_markdown(&RoswaalMessageId::SearchTestsAddedBy.format(&[user_id]))
+        }))
+        .flat_chain_block(IfLet::some(
+            self.test.failure_assignee_slack_user_id(),
+            |user_id| {
+                let message = RoswaalMessageId::SearchTestsFailureAssignee.format(&[user_id]);
+                SlackSection::from_markdown(&message)
+            },
+        ))
         .flat_chain_block(match self.test.last_run_date() {
             Some(date) => {
                 let formatted_date = date.format("%Y-%m-%d %H:%M:%S").to_string();
-                let message = format!("_Last Ran: {}_", formatted_date);
+                let message = RoswaalMessageId::SearchTestsLastRan.format(&[&formatted_date]);
                 SlackSection::from_markdown(&message)
             }
-            None => SlackSection::from_markdown("_This test has never been run._"),
+            None => SlackSection::from_markdown(RoswaalMessageId::SearchTestsNeverRan.text()),
         })
-        .flat_chain_block(SlackSection::from_markdown(&format!(
-            "{} *Before Launch*",
-            self.test
-                .command_status(RoswaalTestCommandOrdinal::for_before_launch())
-                .emoji()
-        )))
+        .flat_chain_block(self.runtime_view())
+        .flat_chain_block(If::is_true(!self.test.is_planned(), || {
+            self.before_launch_view()
+        }))
         .flat_chain_block(ForEachView::new(
             self.test.commands().iter().map(|e| e.clone()),
             |stored_command| CommandView {
@@ -132,11 +176,19 @@ impl SlackView for TestView {
             },
         ))
         .flat_chain_block(IfLet::some(self.test.error_message(), |message| {
-            let message = format!("⚠️ *Error Message*\n{}", message);
+            let message = format!(
+                "{}\n{}",
+                RoswaalMessageId::SearchTestsErrorMessageTitle.text(),
+                message
+            );
             SlackSection::from_markdown(&message)
         }))
         .flat_chain_block(IfLet::some(self.test.error_stack_trace(), |stack_trace| {
-            let stack_trace = format!("⚠️ *Stack Trace*\n{}", stack_trace);
+            let stack_trace = format!(
+                "{}\n{}",
+                RoswaalMessageId::StackTraceTitle.text(),
+                stack_trace
+            );
             SlackSection::from_markdown(&stack_trace)
         }))
         .flat_chain_block(OptionalBranchNameView::new(
@@ -145,6 +197,90 @@ impl SlackView for TestView {
     }
 }
 
+impl TestView {
+    /// Returns a badge for each platform that the test is restricted to, or an empty string if
+    /// the test runs on every platform.
+    fn platform_badges(&self) -> String {
+        self.test
+            .platforms()
+            .iter()
+            .map(|platform| format!(" `{}`", platform))
+            .collect()
+    }
+
+    /// The status of the implicit before launch command, which planned tests do not have since
+    /// they never run.
+    fn before_launch_view(&self) -> impl SlackView {
+        let ordinal = RoswaalTestCommandOrdinal::for_before_launch();
+        SlackSection::from_markdown(&format!(
+            "{} *{}*{}",
+            self.test.command_status(ordinal).emoji(),
+            RoswaalMessageId::CommandBeforeLaunch.text(),
+            screenshot_link(
+                self.test.command_status(ordinal),
+                self.test.screenshot(ordinal)
+            )
+        ))
+    }
+
+    fn runtime_view(&self) -> Option<impl SlackView> {
+        let total_duration = self.test.total_duration()?;
+        let mut message =
+            RoswaalMessageId::SearchTestsTotalRuntime.format(&[&format_duration(total_duration)]);
+        if let Some(slowest) = self.test.slowest_duration() {
+            message.push('\n');
+            message.push_str(&RoswaalMessageId::SearchTestsSlowestStep.format(&[
+                &self.command_title(slowest.command_ordinal()),
+                &format_duration(slowest.duration()),
+            ]));
+        }
+        Some(SlackSection::from_markdown(&message))
+    }
+
+    fn command_title(&self, ordinal: RoswaalTestCommandOrdinal) -> String {
+        if ordinal == RoswaalTestCommandOrdinal::for_before_launch() {
+            return RoswaalMessageId::CommandBeforeLaunch.text().to_string();
+        }
+        let command = self
+            .test
+            .ordinal_commands()
+            .into_iter()
+            .find(|(command_ordinal, _)| *command_ordinal == ordinal)
+            .map(|(_, command)| command);
+        match command {
+            Some(RoswaalCompiledTestCommand::Step { label, .. })
+            | Some(RoswaalCompiledTestCommand::Verify { label, .. }) => label.clone(),
+            Some(RoswaalCompiledTestCommand::SetLocation { .. }) => {
+                RoswaalMessageId::CommandSetLocation.text().to_string()
+            }
+            Some(RoswaalCompiledTestCommand::Wait { .. }) => {
+                RoswaalMessageId::CommandWait.text().to_string()
+            }
+            Some(RoswaalCompiledTestCommand::RequiresTest { .. }) => {
+                RoswaalMessageId::CommandRequiresTest.text().to_string()
+            }
+            Some(RoswaalCompiledTestCommand::BeforeLaunch { .. }) | None => {
+                RoswaalMessageId::CommandUnknown.text().to_string()
+            }
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1}s", duration.as_secs_f64())
+}
+
+/// Formats the runtime of a suite of tests, which is usually minutes long, without sub-second
+/// precision.
+pub(super) fn format_suite_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match (seconds / 3600, seconds % 3600 / 60, seconds % 60) {
+        (0, 0, seconds) => format!("{}s", seconds),
+        (0, minutes, seconds) => format!("{}m {}s", minutes, seconds),
+        (hours, minutes, _) => format!("{}h {}m", hours, minutes),
+    }
+}
+
 struct CommandView {
     command: RoswaalTestCommand,
 }
@@ -156,21 +292,60 @@ impl SlackView for CommandView {
                 label,
                 name,
                 requirement,
+                retries: _,
+            }
+            | RoswaalCompiledTestCommand::Verify {
+                label,
+                name,
+                requirement,
             } => {
                 let body = format!(
-                    "{} *{}:* {} _({})_\n",
+                    "{} *{}:* {} _({})_{}\n",
                     self.command.status().emoji(),
                     label,
                     name,
-                    requirement
+                    requirement,
+                    self.screenshot_link()
                 );
                 SlackSection::from_markdown(&body)
             }
             RoswaalCompiledTestCommand::SetLocation { location_name } => {
                 let body = format!(
-                    "{} *Set Location:* {}\n",
+                    "{} *{}:* {}{}\n",
+                    self.command.status().emoji(),
+                    RoswaalMessageId::CommandSetLocation.text(),
+                    location_name.raw_name(),
+                    self.screenshot_link()
+                );
+                SlackSection::from_markdown(&body)
+            }
+            RoswaalCompiledTestCommand::Wait { duration } => {
+                let body = format!(
+                    "{} *{}:* {}{}\n",
+                    self.command.status().emoji(),
+                    RoswaalMessageId::CommandWait.text(),
+                    duration,
+                    self.screenshot_link()
+                );
+                SlackSection::from_markdown(&body)
+            }
+            RoswaalCompiledTestCommand::BeforeLaunch { name } => {
+                let body = format!(
+                    "{} *{}:* {}{}\n",
+                    self.command.status().emoji(),
+                    RoswaalMessageId::CommandBeforeLaunch.text(),
+                    name,
+                    self.screenshot_link()
+                );
+                SlackSection::from_markdown(&body)
+            }
+            RoswaalCompiledTestCommand::RequiresTest { test_name, .. } => {
+                let body = format!(
+                    "{} *{}:* {}{}\n",
                     self.command.status().emoji(),
-                    location_name.raw_name()
+                    RoswaalMessageId::CommandRequiresTest.text(),
+                    test_name,
+                    self.screenshot_link()
                 );
                 SlackSection::from_markdown(&body)
             }
@@ -178,8 +353,32 @@ impl SlackView for CommandView {
     }
 }
 
+impl CommandView {
+    fn screenshot_link(&self) -> String {
+        screenshot_link(self.command.status(), self.command.screenshot())
+    }
+}
+
+/// Returns a link to the screenshot of a failing command, or an empty string if the command did
+/// not fail or has no screenshot url.
+fn screenshot_link(
+    status: RoswaalTestCommandStatus,
+    screenshot: Option<&RoswaalTestStepScreenshot>,
+) -> String {
+    match screenshot.and_then(|s| s.url()) {
+        Some(url) if status == RoswaalTestCommandStatus::Failed => {
+            format!(
+                " <{}|{}>",
+                url,
+                RoswaalMessageId::SearchTestsScreenshot.text()
+            )
+        }
+        _ => String::new(),
+    }
+}
+
 impl RoswaalTestCommandStatus {
-    fn emoji(&self) -> &'static str {
+    pub(super) fn emoji(&self) -> &'static str {
         match self {
             Self::Passed => "✅",
             Self::Failed => "🔴",
@@ -187,11 +386,11 @@ impl RoswaalTestCommandStatus {
         }
     }
 
-    fn text(&self) -> &'static str {
+    pub(super) fn text(&self) -> &'static str {
         match self {
-            RoswaalTestCommandStatus::Passed => "Passing",
-            RoswaalTestCommandStatus::Failed => "Failing",
-            RoswaalTestCommandStatus::Idle => "Idle",
+            RoswaalTestCommandStatus::Passed => RoswaalMessageId::StatusPassing.text(),
+            RoswaalTestCommandStatus::Failed => RoswaalMessageId::StatusFailing.text(),
+            RoswaalTestCommandStatus::Idle => RoswaalMessageId::StatusIdle.text(),
         }
     }
 }
@@ -203,14 +402,18 @@ mod tests {
     use chrono::{DateTime, Utc};
 
     use crate::{
-        language::test::RoswaalCompiledTestCommand,
+        language::{platform::RoswaalTestPlatform, test::RoswaalCompiledTestCommand},
         location::name::RoswaalLocationName,
         operations::search_tests::SearchTestsStatus,
         slack::{
             test_support::SlackTestConstantBranches,
             ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
         },
-        tests_data::{ordinal::RoswaalTestCommandOrdinal, test::RoswaalTest},
+        tests_data::{
+            ordinal::RoswaalTestCommandOrdinal,
+            progress::{RoswaalTestStepDuration, RoswaalTestStepScreenshot},
+            test::RoswaalTest,
+        },
     };
 
     use super::SearchTestsView;
@@ -227,6 +430,7 @@ mod tests {
                     label: "Step A".to_string(),
                     name: "Do the thing".to_string(),
                     requirement: "Do the thing".to_string(),
+                    retries: None,
                 }],
                 None,
                 None,
@@ -241,6 +445,7 @@ mod tests {
                     label: "Step A".to_string(),
                     name: "Do the thing".to_string(),
                     requirement: "Do the thing".to_string(),
+                    retries: None,
                 }],
                 None,
                 None,
@@ -259,6 +464,7 @@ mod tests {
                         label: "Step 1".to_string(),
                         name: "Do the thing".to_string(),
                         requirement: "Do the thing".to_string(),
+                        retries: None,
                     },
                 ],
                 None,
@@ -278,6 +484,7 @@ mod tests {
                         label: "Step 1".to_string(),
                         name: "Do the thing".to_string(),
                         requirement: "Do the thing".to_string(),
+                        retries: None,
                     },
                 ],
                 Some(RoswaalTestCommandOrdinal::for_before_launch()),
@@ -297,11 +504,13 @@ mod tests {
                         label: "Step 1".to_string(),
                         name: "Do the thing".to_string(),
                         requirement: "Do the thing".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::Step {
                         label: "Step 2".to_string(),
                         name: "I am the fucking strong".to_string(),
                         requirement: "So that's what I'll do".to_string(),
+                        retries: None,
                     },
                 ],
                 Some(RoswaalTestCommandOrdinal::new(1)),
@@ -318,6 +527,216 @@ mod tests {
         )
     }
 
+    #[test]
+    fn failing_test_with_screenshots_snapshot() {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let test = RoswaalTest::new(
+            "Test Failing With Screenshots".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::SetLocation {
+                    location_name: RoswaalLocationName::from_str("San Jose").unwrap(),
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Do the thing".to_string(),
+                    requirement: "Do the thing".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 2".to_string(),
+                    name: "Do the other thing".to_string(),
+                    requirement: "Do the other thing".to_string(),
+                    retries: None,
+                },
+            ],
+            Some(RoswaalTestCommandOrdinal::new(1)),
+            Some("Could not find the thing".to_string()),
+            None,
+            None,
+            Some(date),
+        )
+        .with_screenshots(vec![
+            RoswaalTestStepScreenshot::new(
+                RoswaalTestCommandOrdinal::new(0),
+                Some("https://example.com/set-location.png".to_string()),
+                None,
+            ),
+            RoswaalTestStepScreenshot::new(
+                RoswaalTestCommandOrdinal::new(1),
+                Some("https://example.com/step-1.png".to_string()),
+                None,
+            ),
+            RoswaalTestStepScreenshot::new(
+                RoswaalTestCommandOrdinal::new(2),
+                None,
+                Some("aGVsbG8=".to_string()),
+            ),
+        ]);
+        assert_slack_view_snapshot(
+            "search-tests-failing-with-screenshots",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn test_with_durations_snapshot() {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let test = RoswaalTest::new(
+            "Test With Durations".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::SetLocation {
+                    location_name: RoswaalLocationName::from_str("San Jose").unwrap(),
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Do the thing".to_string(),
+                    requirement: "Do the thing".to_string(),
+                    retries: None,
+                },
+            ],
+            None,
+            None,
+            None,
+            None,
+            Some(date),
+        )
+        .with_durations(vec![
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::for_before_launch(), 2100),
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(0), 450),
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(1), 7250),
+        ]);
+        assert_slack_view_snapshot(
+            "search-tests-with-durations",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn deprecated_test_snapshot() {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let test = RoswaalTest::new(
+            "Test Deprecated".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            Some(date),
+        )
+        .with_deprecation_date(Some(date));
+        assert_slack_view_snapshot(
+            "search-tests-deprecated",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn planned_test_snapshot() {
+        let test = RoswaalTest::new(
+            "Test Planned".to_string(),
+            Some("Users should be able to join events from the map.".to_string()),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .with_planned(true);
+        assert_slack_view_snapshot(
+            "search-tests-planned",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn added_by_test_snapshot() {
+        let test = RoswaalTest::new(
+            "Test Added By".to_string(),
+            Some("Users should be able to join events from the map.".to_string()),
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .with_added_by_slack_user_id(Some("U04K0DX9HC6".to_string()));
+        assert_slack_view_snapshot(
+            "search-tests-added-by",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn platforms_test_snapshot() {
+        let test = RoswaalTest::new(
+            "Test Platforms".to_string(),
+            Some("Users should be able to join events from the map.".to_string()),
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .with_platforms(vec![RoswaalTestPlatform::Ios, RoswaalTestPlatform::Android]);
+        assert_slack_view_snapshot(
+            "search-tests-platforms",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn failure_assignee_test_snapshot() {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let test = RoswaalTest::new(
+            "Test Failure Assignee".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            }],
+            Some(RoswaalTestCommandOrdinal::new(0)),
+            Some("Could not find the thing".to_string()),
+            None,
+            None,
+            Some(date),
+        )
+        .with_failure_assignee_slack_user_id(Some("U04K0DX9HC6".to_string()));
+        assert_slack_view_snapshot(
+            "search-tests-failure-assignee",
+            &SearchTestsView::new(SearchTestsStatus::Success(vec![test])),
+            SnapshotMode::Comparing,
+        )
+    }
+
     #[test]
     fn no_tests_found_snapshot() {
         assert_slack_view_snapshot(
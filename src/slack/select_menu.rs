@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use strum_macros::{Display, EnumString, IntoStaticStr};
+
+use super::{
+    interaction::RoswaalSlackInteractionForm,
+    ui_lib::block_kit_views::{
+        SlackSelectOption, SlackSelectOptions, MAX_SELECT_OPTION_VALUE_LENGTH,
+    },
+};
+
+/// The maximum number of options that slack shows in a select menu.
+pub const MAX_SELECT_OPTIONS: usize = 100;
+
+/// The select menus whose options this tool loads as the user types into them.
+///
+/// The menus are identified by their `action_id`.
+#[derive(Debug, PartialEq, Eq, EnumString, IntoStaticStr, Display, Clone, Copy)]
+pub enum RoswaalSlackSelectMenu {
+    /// A menu of the names of the merged tests.
+    #[strum(serialize = "test-names")]
+    TestNames,
+    /// A menu of the names of the merged locations.
+    #[strum(serialize = "location-names")]
+    LocationNames,
+}
+
+/// A request from slack for the options of a select menu that match the text typed into it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalSlackSelectOptionsRequest {
+    menu: RoswaalSlackSelectMenu,
+    query: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlockSuggestionPayload {
+    #[serde(rename = "type")]
+    _type: String,
+    #[serde(default)]
+    action_id: String,
+    #[serde(default)]
+    value: String,
+}
+
+impl RoswaalSlackSelectOptionsRequest {
+    /// Parses a request from the form body sent by slack to the options load url of the app.
+    ///
+    /// Returns None if the payload is not a `block_suggestion` payload, or if it was not sent by
+    /// a menu known by this tool. An error is returned if the payload is not valid JSON.
+    pub fn from_form(form: &RoswaalSlackInteractionForm) -> serde_json::Result<Option<Self>> {
+        let payload = serde_json::from_str::<BlockSuggestionPayload>(form.payload())?;
+        if payload._type != "block_suggestion" {
+            return Ok(None);
+        }
+        let request = RoswaalSlackSelectMenu::from_str(&payload.action_id)
+            .ok()
+            .map(|menu| Self {
+                menu,
+                query: payload.value,
+            });
+        Ok(request)
+    }
+}
+
+impl RoswaalSlackSelectOptionsRequest {
+    pub fn menu(&self) -> RoswaalSlackSelectMenu {
+        self.menu
+    }
+
+    /// The text that the user typed into the menu.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+/// Returns the options for a menu of `names`, where each option is submitted as its name.
+///
+/// Only the first `MAX_SELECT_OPTIONS` names are included, and names that are too long to be
+/// the value of an option are left out.
+pub fn select_options_from_names(names: &[String]) -> SlackSelectOptions {
+    let options = names
+        .iter()
+        .filter(|name| name.chars().count() <= MAX_SELECT_OPTION_VALUE_LENGTH)
+        .take(MAX_SELECT_OPTIONS)
+        .map(|name| SlackSelectOption::new(name, name))
+        .collect();
+    SlackSelectOptions::new(options)
+}
+
+#[cfg(test)]
+impl RoswaalSlackSelectOptionsRequest {
+    pub fn for_testing(menu: RoswaalSlackSelectMenu, query: &str) -> Self {
+        Self {
+            menu,
+            query: query.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_block_suggestion_for_known_menu() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_suggestion",
+            "action_id": "test-names",
+            "block_id": "tests",
+            "value": "join"
+        }));
+        let request = RoswaalSlackSelectOptionsRequest::from_form(&form).unwrap();
+        assert_eq!(
+            request,
+            Some(RoswaalSlackSelectOptionsRequest::for_testing(
+                RoswaalSlackSelectMenu::TestNames,
+                "join"
+            ))
+        )
+    }
+
+    #[test]
+    fn ignores_unknown_menus_and_payload_types() {
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_suggestion",
+            "action_id": "explode",
+            "value": "join"
+        }));
+        assert_eq!(
+            RoswaalSlackSelectOptionsRequest::from_form(&form).unwrap(),
+            None
+        );
+        let form = RoswaalSlackInteractionForm::for_testing(&json!({
+            "type": "block_actions",
+            "actions": []
+        }));
+        assert_eq!(
+            RoswaalSlackSelectOptionsRequest::from_form(&form).unwrap(),
+            None
+        )
+    }
+
+    #[test]
+    fn select_options_skip_names_that_are_too_long_and_cap_the_count() {
+        let mut names = vec!["a".repeat(MAX_SELECT_OPTION_VALUE_LENGTH + 1)];
+        names.extend((0..MAX_SELECT_OPTIONS + 5).map(|i| format!("Test {}", i)));
+        let options = serde_json::to_value(select_options_from_names(&names)).unwrap();
+        let values = options["options"].as_array().unwrap();
+        assert_eq!(values.len(), MAX_SELECT_OPTIONS);
+        assert_eq!(values[0]["value"], "Test 0")
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use super::ui_lib::{block_kit_views::SlackSection, slack_view::SlackView};
+
+/// A view for indicating that a user has run too many commands in a short period of time.
+pub struct SlowDownView {
+    retry_after: Duration,
+}
+
+impl SlowDownView {
+    pub fn new(retry_after: Duration) -> Self {
+        Self { retry_after }
+    }
+}
+
+impl SlackView for SlowDownView {
+    fn slack_body(&self) -> impl SlackView {
+        let message = format!(
+            "_You have run too many commands in a short periooooood. Try again in {} second(s)._",
+            self.retry_after.as_secs().max(1)
+        );
+        SlackSection::from_markdown("🐢 *Slow dooooown!*")
+            .flat_chain_block(SlackSection::from_markdown(&message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::SlowDownView;
+
+    #[test]
+    fn snapshot() {
+        assert_slack_view_snapshot(
+            "slow-down",
+            &SlowDownView::new(Duration::from_secs(12)),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -0,0 +1,182 @@
+use std::borrow::Borrow;
+
+use crate::{
+    operations::view_suites::{RoswaalTestSuiteSummary, ViewSuitesStatus},
+    tests_data::test::{RoswaalTest, RoswaalTestProgressStatus},
+};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view for summarizing the progress of each test suite.
+pub struct SuitesView {
+    status: ViewSuitesStatus,
+}
+
+impl SuitesView {
+    pub fn new(status: ViewSuitesStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for SuitesView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Test Suites").flat_chain_block(self.status_view())
+    }
+}
+
+impl SuitesView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            ViewSuitesStatus::Summaries(summaries) => {
+                SlackSection::from_markdown(&Self::summaries_body(summaries))
+                    .flat_chain_block(SlackDivider)
+                    .flat_chain_block(SlackSection::from_markdown(
+                        "Use `/view-suites <suite>` to see the tests in a suite!",
+                    ))
+                    .erase_to_any_view()
+            }
+            ViewSuitesStatus::Suite { name, tests } => {
+                SlackSection::from_markdown(&Self::suite_body(name, tests))
+                    .flat_chain_block(SlackDivider)
+                    .flat_chain_block(SlackSection::from_markdown(&format!(
+                        "Use `/view-tests suite:{}` to see the steps of each test!",
+                        name
+                    )))
+                    .erase_to_any_view()
+            }
+            ViewSuitesStatus::SuiteNotFound(name) => SlackSection::from_markdown(&format!(
+                "🔴 No merged tests are in the `{}` suite. Use `/view-suites` to see aaaaaall suites.",
+                name
+            ))
+            .erase_to_any_view(),
+            ViewSuitesStatus::NoSuites => SlackSection::from_markdown(
+                "🔘 There are no suites yet! Add a `Suite:` line to a test to group it into one.",
+            )
+            .erase_to_any_view(),
+        }
+    }
+
+    fn summaries_body(summaries: &[RoswaalTestSuiteSummary]) -> String {
+        let mut body = String::new();
+        for summary in summaries {
+            body.push_str(&format!(
+                "*{}*: {} {} Passing, {} {} Failing, {} {} Idle\n",
+                summary.name(),
+                RoswaalTestProgressStatus::Passed.emoji(),
+                summary.passed_count(),
+                RoswaalTestProgressStatus::Failed.emoji(),
+                summary.failed_count(),
+                RoswaalTestProgressStatus::Idle.emoji(),
+                summary.idle_count()
+            ))
+        }
+        body
+    }
+
+    fn suite_body(name: &str, tests: &[RoswaalTest]) -> String {
+        let mut body = format!("📁 *{}*\n", name);
+        for test in tests {
+            let status = test.progress_status();
+            body.push_str(&format!(
+                "{} {} _({})_\n",
+                status.emoji(),
+                test.name(),
+                status.text()
+            ))
+        }
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        language::test::RoswaalCompiledTestCommand,
+        operations::view_suites::{RoswaalTestSuiteSummary, ViewSuitesStatus},
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, test::RoswaalTest},
+    };
+
+    use super::SuitesView;
+
+    #[test]
+    fn summaries_snapshot() {
+        let summaries = vec![
+            RoswaalTestSuiteSummary::new(
+                "Events".to_string(),
+                &[
+                    test("Create Event", Some(None)),
+                    test("Join Event", Some(Some(1))),
+                    test("Leave Event", None),
+                ],
+            ),
+            RoswaalTestSuiteSummary::new("Onboarding".to_string(), &[test("Sign Up", None)]),
+        ];
+        assert_slack_view_snapshot(
+            "suites-summaries",
+            &SuitesView::new(ViewSuitesStatus::Summaries(summaries)),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn suite_snapshot() {
+        let status = ViewSuitesStatus::Suite {
+            name: "Events".to_string(),
+            tests: vec![
+                test("Create Event", Some(None)),
+                test("Join Event", Some(Some(1))),
+                test("Leave Event", None),
+            ],
+        };
+        assert_slack_view_snapshot(
+            "suites-suite",
+            &SuitesView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn suite_not_found_snapshot() {
+        assert_slack_view_snapshot(
+            "suites-suite-not-found",
+            &SuitesView::new(ViewSuitesStatus::SuiteNotFound("Events".to_string())),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_suites_snapshot() {
+        assert_slack_view_snapshot(
+            "suites-no-suites",
+            &SuitesView::new(ViewSuitesStatus::NoSuites),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    /// Creates a test that has never ran when `run` is `None`, otherwise a test that ran and
+    /// failed at the ordinal in `run` if present.
+    fn test(name: &str, run: Option<Option<i32>>) -> RoswaalTest {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            }],
+            run.flatten().map(RoswaalTestCommandOrdinal::new),
+            None,
+            None,
+            None,
+            run.map(|_| date),
+        )
+    }
+}
@@ -0,0 +1,119 @@
+use crate::git::edit::{TargetRepositoryEdit, TargetRepositoryEditStatus};
+
+use super::{
+    ui_lib::{
+        block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+        if_view::If,
+        slack_view::SlackView,
+    },
+    users::{RoswaalSlackUserRole, RoswaalSlackUserRoster},
+};
+
+/// A view for showing the result of mirroring an operation onto each additional target
+/// repository.
+///
+/// Nothing is rendered when there are no target repositories.
+pub struct TargetRepositoriesView {
+    edits: Vec<TargetRepositoryEdit>,
+    roster: RoswaalSlackUserRoster,
+}
+
+impl TargetRepositoriesView {
+    pub fn new(edits: &[TargetRepositoryEdit]) -> Self {
+        Self {
+            edits: edits.to_vec(),
+            roster: RoswaalSlackUserRoster::default(),
+        }
+    }
+
+    /// Mentions the merge conflict owners in `roster` for each repository with a merge conflict.
+    pub fn with_roster(self, roster: &RoswaalSlackUserRoster) -> Self {
+        Self {
+            roster: roster.clone(),
+            ..self
+        }
+    }
+}
+
+impl SlackView for TargetRepositoriesView {
+    fn slack_body(&self) -> impl SlackView {
+        If::is_true(!self.edits.is_empty(), || {
+            SlackDivider
+                .flat_chain_block(SlackHeader::new("Other Repositories"))
+                .flat_chain_block(SlackSection::from_markdown(&self.edits_markdown()))
+        })
+    }
+}
+
+impl TargetRepositoriesView {
+    fn edits_markdown(&self) -> String {
+        let mut body = String::new();
+        for edit in self.edits.iter() {
+            let message = match edit.status() {
+                TargetRepositoryEditStatus::Success {
+                    should_warn_undeleted_branch: false,
+                } => "✅ Pull request opened.".to_string(),
+                TargetRepositoryEditStatus::Success {
+                    should_warn_undeleted_branch: true,
+                } => "✅ Pull request opened, but the local branch was not deleted.".to_string(),
+                TargetRepositoryEditStatus::FailedToOpenPullRequest => {
+                    "🔴 The pull request could not be opeeeeened.".to_string()
+                }
+                TargetRepositoryEditStatus::MergeConflict => format!(
+                    "🔴 Merge conflict detected, {} needs to fix it.",
+                    self.roster
+                        .mentions(RoswaalSlackUserRole::MergeConflictOwner)
+                ),
+                TargetRepositoryEditStatus::RemoteUnavailable => {
+                    "🟡 GitHub could not be reaaaaached, try again in a bit.".to_string()
+                }
+                TargetRepositoryEditStatus::Failed => {
+                    "🔴 Failed to apply the chaaaaanges. Check the logs for deeeeeeetails."
+                        .to_string()
+                }
+            };
+            body.push_str(&format!("*{}*: {}\n", edit.repository_name(), message))
+        }
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::edit::{TargetRepositoryEdit, TargetRepositoryEditStatus},
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::TargetRepositoriesView;
+
+    #[test]
+    fn snapshot() {
+        let edits = vec![
+            TargetRepositoryEdit::new(
+                "FitnessProject",
+                TargetRepositoryEditStatus::Success {
+                    should_warn_undeleted_branch: false,
+                },
+            ),
+            TargetRepositoryEdit::new(
+                "tiF-Web",
+                TargetRepositoryEditStatus::Success {
+                    should_warn_undeleted_branch: true,
+                },
+            ),
+            TargetRepositoryEdit::new(
+                "tiF-Android",
+                TargetRepositoryEditStatus::FailedToOpenPullRequest,
+            ),
+            TargetRepositoryEdit::new("tiF-Admin", TargetRepositoryEditStatus::MergeConflict),
+            TargetRepositoryEdit::new("tiF-Docs", TargetRepositoryEditStatus::Failed),
+            TargetRepositoryEdit::new("tiF-iOS", TargetRepositoryEditStatus::RemoteUnavailable),
+        ];
+        assert_slack_view_snapshot(
+            "target-repositories",
+            &TargetRepositoriesView::new(&edits),
+            SnapshotMode::Comparing,
+        )
+    }
+}
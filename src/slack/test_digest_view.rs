@@ -0,0 +1,185 @@
+use std::borrow::Borrow;
+
+use crate::{operations::test_digest::TestDigestStatus, tests_data::test::RoswaalTest};
+
+use super::ui_lib::{
+    block_kit_views::{SlackDivider, SlackHeader, SlackSection},
+    if_view::If,
+    slack_view::SlackView,
+};
+
+/// A view for the scheduled digest of failing and idle tests.
+pub struct TestDigestView {
+    status: TestDigestStatus,
+}
+
+impl TestDigestView {
+    pub fn new(status: TestDigestStatus) -> Self {
+        Self { status }
+    }
+}
+
+impl SlackView for TestDigestView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackHeader::new("Nightly Test Digest").flat_chain_block(self.status_view())
+    }
+}
+
+impl TestDigestView {
+    fn status_view(&self) -> impl SlackView {
+        match self.status.borrow() {
+            TestDigestStatus::Success {
+                failing_tests,
+                idle_tests,
+            } => If::is_true(!failing_tests.is_empty(), || {
+                SlackSection::from_markdown(&Self::tests_body(
+                    "🔴",
+                    "Failing",
+                    failing_tests,
+                    |test| {
+                        test.error_message()
+                            .map(|message| format!(" _({})_", message))
+                            .unwrap_or_default()
+                    },
+                ))
+            })
+            .flat_chain_block(If::is_true(!idle_tests.is_empty(), || {
+                SlackSection::from_markdown(&Self::tests_body("🔘", "Idle", idle_tests, |test| {
+                    match test.last_run_date() {
+                        Some(date) => {
+                            format!(" _(Last Ran: {})_", date.format("%Y-%m-%d %H:%M:%S"))
+                        }
+                        None => " _(Never Ran)_".to_string(),
+                    }
+                }))
+            }))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackHeader::new("Next Steps"))
+            .flat_chain_block(SlackSection::from_markdown(
+                "Use `/view-tests` to view the progress of each test!",
+            ))
+            .erase_to_any_view(),
+            TestDigestStatus::AllTestsPassing => {
+                SlackSection::from_markdown("✅ All tests are paaaaaassing!").erase_to_any_view()
+            }
+            TestDigestStatus::NoTests => SlackSection::from_markdown(
+                "🔘 There are no tests yet! Use `/add-tests` to add some.",
+            )
+            .erase_to_any_view(),
+        }
+    }
+
+    fn tests_body(
+        emoji: &str,
+        status_text: &str,
+        tests: &[RoswaalTest],
+        detail: impl Fn(&RoswaalTest) -> String,
+    ) -> String {
+        let word = if tests.len() == 1 { "Test" } else { "Tests" };
+        let mut body = format!("{} *{} {} {}*\n", emoji, tests.len(), word, status_text);
+        for test in tests {
+            body.push_str(&format!("- {}{}\n", test.name(), detail(test)))
+        }
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        language::test::RoswaalCompiledTestCommand,
+        operations::test_digest::TestDigestStatus,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, test::RoswaalTest},
+    };
+
+    use super::TestDigestView;
+
+    #[test]
+    fn success_snapshot() {
+        let date = "2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap();
+        let status = TestDigestStatus::Success {
+            failing_tests: vec![
+                failing_test("Test Failing", Some("Everyone Died"), date),
+                failing_test("Test Failing Without Error", None, date),
+            ],
+            idle_tests: vec![
+                idle_test("Test Never Ran", None),
+                idle_test("Test Not Ran Recently", Some(date)),
+            ],
+        };
+        assert_slack_view_snapshot(
+            "test-digest-success",
+            &TestDigestView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn success_only_idle_snapshot() {
+        let status = TestDigestStatus::Success {
+            failing_tests: vec![],
+            idle_tests: vec![idle_test("Test Never Ran", None)],
+        };
+        assert_slack_view_snapshot(
+            "test-digest-success-only-idle",
+            &TestDigestView::new(status),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn all_tests_passing_snapshot() {
+        assert_slack_view_snapshot(
+            "test-digest-all-tests-passing",
+            &TestDigestView::new(TestDigestStatus::AllTestsPassing),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn no_tests_snapshot() {
+        assert_slack_view_snapshot(
+            "test-digest-no-tests",
+            &TestDigestView::new(TestDigestStatus::NoTests),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn failing_test(name: &str, error_message: Option<&str>, date: DateTime<Utc>) -> RoswaalTest {
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            commands(),
+            Some(RoswaalTestCommandOrdinal::new(1)),
+            error_message.map(|m| m.to_string()),
+            None,
+            None,
+            Some(date),
+        )
+    }
+
+    fn idle_test(name: &str, date: Option<DateTime<Utc>>) -> RoswaalTest {
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            commands(),
+            None,
+            None,
+            None,
+            None,
+            date,
+        )
+    }
+
+    fn commands() -> Vec<RoswaalCompiledTestCommand> {
+        vec![RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Do the thing".to_string(),
+            requirement: "Do the thing".to_string(),
+            retries: None,
+        }]
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{language::test::RoswaalCompiledTestCommand, tests_data::test::RoswaalTest};
+
+use super::{
+    interaction::RoswaalSlackAction,
+    ui_lib::{
+        block_kit_views::{SlackActions, SlackDivider, SlackHeader, SlackSection},
+        for_each_view::ForEachView,
+        slack_view::SlackView,
+    },
+};
+
+/// A view for the tests that failed in a progress upload from the test runner.
+///
+/// Each test has a button that mutes its failures.
+pub struct TestFailureView {
+    tests: Vec<RoswaalTest>,
+}
+
+impl TestFailureView {
+    pub fn new(tests: Vec<RoswaalTest>) -> Self {
+        Self { tests }
+    }
+}
+
+impl SlackView for TestFailureView {
+    fn slack_body(&self) -> impl SlackView {
+        let word = if self.tests.len() == 1 {
+            "Test"
+        } else {
+            "Tests"
+        };
+        SlackHeader::new("Test Failures")
+            .flat_chain_block(SlackSection::from_markdown(&format!(
+                "🔴 *{} {} just faaaaaailed!*",
+                self.tests.len(),
+                word
+            )))
+            .flat_chain_block(ForEachView::new(self.tests.iter(), |test| {
+                Self::test_view(test)
+            }))
+    }
+}
+
+impl TestFailureView {
+    fn test_view(test: &RoswaalTest) -> impl SlackView {
+        let step = failing_step(test)
+            .map(|step| format!("\n*Failing Step:* {}", step))
+            .unwrap_or_default();
+        let error_message = test
+            .error_message()
+            .map(|message| format!("\n*Error:* {}", message))
+            .unwrap_or_default();
+        SlackDivider
+            .flat_chain_block(SlackSection::from_markdown(&format!(
+                "*{}*{}{}",
+                test.name(),
+                step,
+                error_message
+            )))
+            .flat_chain_block(
+                RoswaalSlackAction::MuteTestFailures
+                    .button("Mute", test.name())
+                    .map(|button| SlackActions::new(vec![button])),
+            )
+    }
+}
+
+/// Returns the syntax of the command that `test` failed on, if it has a failure ordinal.
+///
+/// Failures on the before launch ordinal of a test without before launch commands are reported
+/// as a failure of the implicit before launch command.
+fn failing_step(test: &RoswaalTest) -> Option<String> {
+    let failure_ordinal = test.command_failure_ordinal()?;
+    let command = test
+        .ordinal_commands()
+        .into_iter()
+        .find(|(ordinal, _)| *ordinal == failure_ordinal)
+        .map(|(_, command)| command);
+    let step = match command {
+        Some(RoswaalCompiledTestCommand::Step { label, name, .. })
+        | Some(RoswaalCompiledTestCommand::Verify { label, name, .. }) => {
+            format!("{}: {}", label, name)
+        }
+        Some(RoswaalCompiledTestCommand::SetLocation { location_name }) => {
+            format!("Set Location: {}", location_name.raw_name())
+        }
+        Some(RoswaalCompiledTestCommand::Wait { duration }) => format!("Wait: {}", duration),
+        Some(RoswaalCompiledTestCommand::BeforeLaunch { name }) => {
+            format!("Before Launch: {}", name)
+        }
+        Some(RoswaalCompiledTestCommand::RequiresTest { test_name, .. }) => {
+            format!("Requires Test: {}", test_name)
+        }
+        None => "Before Launch".to_string(),
+    };
+    Some(step)
+}
+
+/// A view for the response to pressing the mute or unmute button of a failing test.
+pub struct TestFailureMuteView {
+    test_name: String,
+    is_muted: bool,
+}
+
+impl TestFailureMuteView {
+    pub fn new(test_name: &str, is_muted: bool) -> Self {
+        Self {
+            test_name: test_name.to_string(),
+            is_muted,
+        }
+    }
+}
+
+impl SlackView for TestFailureMuteView {
+    fn slack_body(&self) -> impl SlackView {
+        let (text, button) = if self.is_muted {
+            (
+                format!(
+                    "🔇 Failures of *{}* will no longer be posted heeeeere.",
+                    self.test_name
+                ),
+                RoswaalSlackAction::UnmuteTestFailures.button("Unmute", &self.test_name),
+            )
+        } else {
+            (
+                format!(
+                    "🔊 Failures of *{}* will be posted here agaaaaain.",
+                    self.test_name
+                ),
+                RoswaalSlackAction::MuteTestFailures.button("Mute", &self.test_name),
+            )
+        };
+        SlackSection::from_markdown(&text)
+            .flat_chain_block(button.map(|button| SlackActions::new(vec![button])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+        tests_data::ordinal::RoswaalTestCommandOrdinal,
+    };
+
+    use super::*;
+
+    #[test]
+    fn failures_snapshot() {
+        let tests = vec![
+            failing_test(
+                "Test Failing",
+                RoswaalTestCommandOrdinal::new(1),
+                Some("Everyone Died"),
+            ),
+            failing_test(
+                "Test Failing Before Launch",
+                RoswaalTestCommandOrdinal::for_before_launch(),
+                None,
+            ),
+        ];
+        assert_slack_view_snapshot(
+            "test-failure-failures",
+            &TestFailureView::new(tests),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn muted_snapshot() {
+        assert_slack_view_snapshot(
+            "test-failure-muted",
+            &TestFailureMuteView::new("Test Failing", true),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn unmuted_snapshot() {
+        assert_slack_view_snapshot(
+            "test-failure-unmuted",
+            &TestFailureMuteView::new("Test Failing", false),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    fn failing_test(
+        name: &str,
+        failure_ordinal: RoswaalTestCommandOrdinal,
+        error_message: Option<&str>,
+    ) -> RoswaalTest {
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Open the app".to_string(),
+                    requirement: "Open the app".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Verify {
+                    label: "Verify 1".to_string(),
+                    name: "The app is open".to_string(),
+                    requirement: "The app is open".to_string(),
+                },
+            ],
+            Some(failure_ordinal),
+            error_message.map(|m| m.to_string()),
+            None,
+            None,
+            Some("2024-07-24T00:00:00+0000".parse::<DateTime<Utc>>().unwrap()),
+        )
+    }
+}
@@ -0,0 +1,63 @@
+use crate::runner::run::RoswaalTestRun;
+
+use super::ui_lib::{
+    block_kit_views::{SlackHeader, SlackSection},
+    slack_view::SlackView,
+};
+
+/// A view that links to the CI run of the tests that were added by a merged branch.
+pub struct TestRunView {
+    run: RoswaalTestRun,
+}
+
+impl TestRunView {
+    pub fn new(run: RoswaalTestRun) -> Self {
+        Self { run }
+    }
+}
+
+impl SlackView for TestRunView {
+    fn slack_body(&self) -> impl SlackView {
+        let link = match self.run.url() {
+            Some(url) => format!("<{}|View the run>", url),
+            None => "The runner did not send a link.".to_string(),
+        };
+        let test_names = self
+            .run
+            .test_names()
+            .iter()
+            .map(|name| format!("- {}", name))
+            .collect::<Vec<String>>()
+            .join("\n");
+        SlackHeader::new("Test Run").flat_chain_block(SlackSection::from_markdown(&format!(
+            "🏃 The tests merged from `{}` are ruuuuuuunning! {}\n{}",
+            self.run.branch_name().to_string(),
+            link,
+            test_names
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        runner::run::RoswaalTestRun,
+        slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode},
+    };
+
+    use super::TestRunView;
+
+    #[test]
+    fn snapshot() {
+        let branch_name: RoswaalOwnedGitBranchName =
+            serde_json::from_str("\"roswaal-add-tests-0123456789\"").unwrap();
+        let run = RoswaalTestRun::new(
+            1,
+            branch_name,
+            vec!["Join an event".to_string(), "Leave an event".to_string()],
+            Some("https://ci.example.com/runs/1".to_string()),
+        );
+        assert_slack_view_snapshot("test-run", &TestRunView::new(run), SnapshotMode::Comparing)
+    }
+}
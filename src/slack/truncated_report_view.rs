@@ -0,0 +1,67 @@
+use crate::utils::config::RoswaalConfig;
+
+use super::{
+    catalog::RoswaalMessageId,
+    ui_lib::{block_kit_views::SlackSection, slack_view::SlackView},
+};
+
+/// A view that replaces the blocks that were cut off from a message with more blocks than slack
+/// allows, which links to the full report of the message when it was saved.
+pub struct TruncatedReportView<'a> {
+    truncated_count: usize,
+    report_id: Option<&'a str>,
+}
+
+impl<'a> TruncatedReportView<'a> {
+    pub fn new(truncated_count: usize, report_id: Option<&'a str>) -> Self {
+        Self {
+            truncated_count,
+            report_id,
+        }
+    }
+}
+
+impl<'a> SlackView for TruncatedReportView<'a> {
+    fn slack_body(&self) -> impl SlackView {
+        let mut text = RoswaalMessageId::MessageTruncated.format(&[&self.truncated_count]);
+        let public_url = RoswaalConfig::current().public_url();
+        match (self.report_id, public_url) {
+            (Some(id), Some(url)) => {
+                let report_url = format!("{}/reports/{}", url, id);
+                text.push(' ');
+                text.push_str(&RoswaalMessageId::MessageTruncatedReportLink.format(&[&report_url]))
+            }
+            (Some(id), None) => {
+                text.push(' ');
+                text.push_str(&RoswaalMessageId::MessageTruncatedReportPath.format(&[&id]))
+            }
+            (None, _) => {}
+        }
+        SlackSection::from_markdown(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::test_support::{assert_slack_view_snapshot, SnapshotMode};
+
+    use super::TruncatedReportView;
+
+    #[test]
+    fn snapshot() {
+        assert_slack_view_snapshot(
+            "truncated-report",
+            &TruncatedReportView::new(12, Some("V1StGXR8_Z5jdHi6B-myT")),
+            SnapshotMode::Comparing,
+        )
+    }
+
+    #[test]
+    fn unsaved_report_snapshot() {
+        assert_slack_view_snapshot(
+            "truncated-report-unsaved",
+            &TruncatedReportView::new(12, None),
+            SnapshotMode::Comparing,
+        )
+    }
+}
@@ -54,7 +54,7 @@ impl SlackSection {
 }
 
 /// Slack Text for use in a Section.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SlackText {
     _type: &'static str,
     text: String,
@@ -150,6 +150,251 @@ impl SlackView for SlackHeader {
     }
 }
 
+/// A Slack Button element for use in a `SlackActions` block.
+///
+/// When pressed, slack sends a `block_actions` interaction payload containing the `action_id` and
+/// `value` of the button.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+pub struct SlackButton {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    text: SlackText,
+    action_id: String,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<&'static str>,
+}
+
+impl SlackButton {
+    pub fn new(text: &str, action_id: &str, value: &str) -> Self {
+        Self {
+            _type: "button",
+            text: SlackText::plain(text),
+            action_id: action_id.to_string(),
+            value: value.to_string(),
+            style: None,
+        }
+    }
+}
+
+impl SlackButton {
+    /// Styles this button as the primary action.
+    pub fn primary(self) -> Self {
+        Self {
+            style: Some("primary"),
+            ..self
+        }
+    }
+
+    /// Styles this button as a destructive action.
+    pub fn danger(self) -> Self {
+        Self {
+            style: Some("danger"),
+            ..self
+        }
+    }
+}
+
+/// A Slack Actions block that holds interactive elements.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SlackActions {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    elements: Vec<SlackButton>,
+}
+
+impl SlackActions {
+    pub fn new(elements: Vec<SlackButton>) -> Self {
+        Self {
+            _type: "actions",
+            elements,
+        }
+    }
+}
+
+impl SlackView for SlackActions {
+    fn slack_body(&self) -> impl SlackView {
+        PrimitiveView::new(self)
+    }
+}
+
+/// A Slack Input block containing a plain text input element, for use in a `SlackModal`.
+///
+/// When the modal is submitted, slack sends the text of the input in the `view_submission`
+/// payload keyed by the `block_id` and `action_id` of the input.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SlackPlainTextInput {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    block_id: String,
+    label: SlackText,
+    element: SlackPlainTextInputElement,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct SlackPlainTextInputElement {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    action_id: String,
+    multiline: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    placeholder: Option<SlackText>,
+}
+
+impl SlackPlainTextInput {
+    pub fn new(block_id: &str, action_id: &str, label: &str) -> Self {
+        Self {
+            _type: "input",
+            block_id: block_id.to_string(),
+            label: SlackText::plain(label),
+            element: SlackPlainTextInputElement {
+                _type: "plain_text_input",
+                action_id: action_id.to_string(),
+                multiline: false,
+                placeholder: None,
+            },
+        }
+    }
+}
+
+impl SlackPlainTextInput {
+    /// Allows the input to span multiple lines.
+    pub fn multiline(self) -> Self {
+        Self {
+            element: SlackPlainTextInputElement {
+                multiline: true,
+                ..self.element
+            },
+            ..self
+        }
+    }
+
+    /// Shows the specified text in the input while it is empty.
+    pub fn placeholder(self, text: &str) -> Self {
+        Self {
+            element: SlackPlainTextInputElement {
+                placeholder: Some(SlackText::plain(text)),
+                ..self.element
+            },
+            ..self
+        }
+    }
+}
+
+impl SlackView for SlackPlainTextInput {
+    fn slack_body(&self) -> impl SlackView {
+        PrimitiveView::new(self)
+    }
+}
+
+/// The maximum number of characters that slack shows in the text of a select menu option.
+pub const MAX_SELECT_OPTION_TEXT_LENGTH: usize = 75;
+
+/// The maximum number of characters that slack allows in the value of a select menu option.
+pub const MAX_SELECT_OPTION_VALUE_LENGTH: usize = 150;
+
+/// An option of a Slack select menu.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+pub struct SlackSelectOption {
+    text: SlackText,
+    value: String,
+}
+
+impl SlackSelectOption {
+    /// Creates an option that shows `text`, and that is submitted as `value` when selected.
+    ///
+    /// Text longer than `MAX_SELECT_OPTION_TEXT_LENGTH` is cut off with an ellipsis.
+    pub fn new(text: &str, value: &str) -> Self {
+        let text = if text.chars().count() > MAX_SELECT_OPTION_TEXT_LENGTH {
+            let prefix = text
+                .chars()
+                .take(MAX_SELECT_OPTION_TEXT_LENGTH - 1)
+                .collect::<String>();
+            format!("{}…", prefix)
+        } else {
+            text.to_string()
+        };
+        Self {
+            text: SlackText::plain(&text),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// The options of an external select menu, which are sent as the response to the
+/// `block_suggestion` payload that slack sends as the user types into the menu.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SlackSelectOptions {
+    options: Vec<SlackSelectOption>,
+}
+
+impl SlackSelectOptions {
+    pub fn new(options: Vec<SlackSelectOption>) -> Self {
+        Self { options }
+    }
+}
+
+/// A Slack Input block containing a multi select menu whose options are loaded from the options
+/// load url of the slack app, for use in a `SlackModal`.
+///
+/// As the user types, slack sends a `block_suggestion` payload with the `action_id` of the menu
+/// and the typed text, and shows the `SlackSelectOptions` in the response. When the modal is
+/// submitted, the selected options are sent in the `view_submission` payload keyed by the
+/// `block_id` and `action_id` of the input.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct SlackMultiExternalSelectInput {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    block_id: String,
+    label: SlackText,
+    element: SlackMultiExternalSelectElement,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct SlackMultiExternalSelectElement {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    action_id: String,
+    min_query_length: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    placeholder: Option<SlackText>,
+}
+
+impl SlackMultiExternalSelectInput {
+    pub fn new(block_id: &str, action_id: &str, label: &str) -> Self {
+        Self {
+            _type: "input",
+            block_id: block_id.to_string(),
+            label: SlackText::plain(label),
+            element: SlackMultiExternalSelectElement {
+                _type: "multi_external_select",
+                action_id: action_id.to_string(),
+                min_query_length: 0,
+                placeholder: None,
+            },
+        }
+    }
+}
+
+impl SlackMultiExternalSelectInput {
+    /// Shows the specified text in the menu while nothing is selected.
+    pub fn placeholder(self, text: &str) -> Self {
+        Self {
+            element: SlackMultiExternalSelectElement {
+                placeholder: Some(SlackText::plain(text)),
+                ..self.element
+            },
+            ..self
+        }
+    }
+}
+
+impl SlackView for SlackMultiExternalSelectInput {
+    fn slack_body(&self) -> impl SlackView {
+        PrimitiveView::new(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::slack::ui_lib::test_support::assert_blocks_json;
@@ -176,6 +421,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn actions_with_buttons_json() {
+        let view = SlackActions::new(vec![
+            SlackButton::new("Do it", "do-it", "now").primary(),
+            SlackButton::new("Stop", "stop", "").danger(),
+            SlackButton::new("Maybe", "maybe", "later"),
+        ]);
+        assert_blocks_json(
+            &view,
+            r#"[{"elements":[{"action_id":"do-it","style":"primary","text":{"text":"Do it","type":"plain_text"},"type":"button","value":"now"},{"action_id":"stop","style":"danger","text":{"text":"Stop","type":"plain_text"},"type":"button","value":""},{"action_id":"maybe","text":{"text":"Maybe","type":"plain_text"},"type":"button","value":"later"}],"type":"actions"}]"#,
+        );
+    }
+
+    #[test]
+    fn plain_text_input_json() {
+        let view = SlackPlainTextInput::new("tests", "tests-input", "Tests")
+            .multiline()
+            .placeholder("New Test: Join an event");
+        assert_blocks_json(
+            &view,
+            r#"[{"block_id":"tests","element":{"action_id":"tests-input","multiline":true,"placeholder":{"text":"New Test: Join an event","type":"plain_text"},"type":"plain_text_input"},"label":{"text":"Tests","type":"plain_text"},"type":"input"}]"#,
+        );
+    }
+
+    #[test]
+    fn multi_external_select_input_json() {
+        let view = SlackMultiExternalSelectInput::new("tests", "test-names", "Tests")
+            .placeholder("Search for tests");
+        assert_blocks_json(
+            &view,
+            r#"[{"block_id":"tests","element":{"action_id":"test-names","min_query_length":0,"placeholder":{"text":"Search for tests","type":"plain_text"},"type":"multi_external_select"},"label":{"text":"Tests","type":"plain_text"},"type":"input"}]"#,
+        );
+    }
+
+    #[test]
+    fn select_options_truncate_long_text() {
+        let options = SlackSelectOptions::new(vec![
+            SlackSelectOption::new("Join an event", "Join an event"),
+            SlackSelectOption::new(&"a".repeat(80), "long"),
+        ]);
+        let expected_text = format!("{}…", "a".repeat(MAX_SELECT_OPTION_TEXT_LENGTH - 1));
+        assert_eq!(
+            serde_json::to_value(&options).unwrap(),
+            serde_json::json!({
+                "options": [
+                    { "text": { "text": "Join an event", "type": "plain_text" }, "value": "Join an event" },
+                    { "text": { "text": expected_text, "type": "plain_text" }, "value": "long" }
+                ]
+            })
+        )
+    }
+
     #[test]
     fn nested_view_flattens_to_proper_json() {
         assert_blocks_json(
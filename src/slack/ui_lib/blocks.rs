@@ -1,12 +1,19 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::{primitive_view::PrimitiveView, slack_view::SlackView};
+use super::{
+    primitive_view::PrimitiveView,
+    slack_view::{render_slack_view, SlackView},
+};
+
+/// The maximum number of blocks that slack allows in a message, or a response to a slash
+/// command.
+pub const SLACK_MESSAGE_MAX_BLOCKS: usize = 50;
 
 /// A struct containing a flat array of slack blocks.
 ///
 /// You create instances of this struct via the `render_slack_blocks` function which will convert
 /// a `SlackView` hierarchy into a flat array of JSON-serializeable slack blocks.
-#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct SlackBlocks(_SlackBlocksCollection);
 
 impl SlackBlocks {
@@ -19,13 +26,43 @@ impl SlackBlocks {
     pub(super) fn collection(&self) -> &_SlackBlocksCollection {
         &self.0
     }
+
+    /// Returns the number of blocks.
+    pub fn count(&self) -> usize {
+        self.0 .0.len()
+    }
+
+    /// Returns the raw JSON value of each block.
+    pub(super) fn values(&self) -> &[serde_json::Value] {
+        &self.0 .0
+    }
+
+    /// Cuts off the trailing blocks that do not fit in `max_blocks`, and replaces them with the
+    /// blocks of the view returned by `overflow_view`.
+    ///
+    /// `overflow_view` is given the number of blocks that were cut off, and must render the same
+    /// number of blocks regardless of that number. None is returned if all blocks already fit.
+    pub fn truncated<View: SlackView>(
+        &self,
+        max_blocks: usize,
+        overflow_view: impl Fn(usize) -> View,
+    ) -> Option<Self> {
+        if self.count() <= max_blocks {
+            return None;
+        }
+        let overflow_count = render_slack_view(&overflow_view(0)).count();
+        let kept_count = max_blocks.saturating_sub(overflow_count);
+        let mut blocks = _SlackBlocksCollection(self.0 .0[..kept_count].to_vec());
+        blocks.push_view(&overflow_view(self.count() - kept_count));
+        Some(Self(blocks))
+    }
 }
 
 /// A collection of slack blocks.
 ///
 /// This struct is an implementation detail of the library, and it could be removed or changed in
 /// the future. Do not depend on this struct directly.
-#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct _SlackBlocksCollection(Vec<serde_json::Value>);
 
 impl _SlackBlocksCollection {
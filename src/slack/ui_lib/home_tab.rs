@@ -0,0 +1,42 @@
+use serde::Serialize;
+
+use super::{
+    blocks::SlackBlocks,
+    slack_view::{render_slack_view, SlackView},
+};
+
+/// The Home tab of the app that renders the blocks of a `SlackView`.
+///
+/// Unlike a modal, the Home tab is published for a specific user, and stays in place until it is
+/// published again.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+pub struct SlackHomeTab {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    blocks: SlackBlocks,
+}
+
+impl SlackHomeTab {
+    pub fn new(view: &impl SlackView) -> Self {
+        Self {
+            _type: "home",
+            blocks: render_slack_view(view),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::block_kit_views::SlackSection;
+
+    use super::*;
+
+    #[test]
+    fn home_tab_json() {
+        let home_tab = SlackHomeTab::new(&SlackSection::from_markdown("Hello"));
+        assert_eq!(
+            serde_json::to_string(&home_tab).unwrap(),
+            r#"{"type":"home","blocks":[{"text":{"text":"Hello","type":"mrkdwn"},"type":"section"}]}"#
+        )
+    }
+}
@@ -4,10 +4,14 @@ pub mod blocks;
 pub mod empty_view;
 pub mod flat_chain_view;
 pub mod for_each_view;
+pub mod home_tab;
 pub mod if_let_view;
 pub mod if_view;
+pub mod modal;
 pub mod option_view;
 pub mod primitive_view;
 pub mod slack_view;
+pub mod syntax_view;
 #[cfg(test)]
 pub mod test_support;
+pub mod transcript;
@@ -0,0 +1,77 @@
+use serde::Serialize;
+
+use super::{
+    block_kit_views::SlackText,
+    blocks::SlackBlocks,
+    slack_view::{render_slack_view, SlackView},
+};
+
+/// A Slack modal that renders the blocks of a `SlackView`.
+///
+/// A modal is opened with the trigger id of an interaction (eg. a slash command), and slack sends
+/// a `view_submission` payload with the `callback_id` and `private_metadata` of the modal when it
+/// is submitted.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+pub struct SlackModal {
+    #[serde(rename = "type")]
+    _type: &'static str,
+    callback_id: String,
+    title: SlackText,
+    submit: SlackText,
+    close: SlackText,
+    private_metadata: String,
+    blocks: SlackBlocks,
+}
+
+impl SlackModal {
+    /// Creates a modal with the specified title that renders `view`.
+    ///
+    /// Slack truncates titles that are longer than 24 characters.
+    pub fn new(callback_id: &str, title: &str, view: &impl SlackView) -> Self {
+        Self {
+            _type: "modal",
+            callback_id: callback_id.to_string(),
+            title: SlackText::plain(title),
+            submit: SlackText::plain("Submit"),
+            close: SlackText::plain("Cancel"),
+            private_metadata: String::new(),
+            blocks: render_slack_view(view),
+        }
+    }
+}
+
+impl SlackModal {
+    /// Sets the text of the button that submits this modal.
+    pub fn submit_text(self, text: &str) -> Self {
+        Self {
+            submit: SlackText::plain(text),
+            ..self
+        }
+    }
+
+    /// Attaches a string to this modal that is sent back by slack when the modal is submitted.
+    pub fn with_private_metadata(self, metadata: &str) -> Self {
+        Self {
+            private_metadata: metadata.to_string(),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::slack::ui_lib::block_kit_views::SlackSection;
+
+    use super::*;
+
+    #[test]
+    fn modal_json() {
+        let modal = SlackModal::new("do-it", "Do It", &SlackSection::from_markdown("Hello"))
+            .submit_text("Go")
+            .with_private_metadata("C123");
+        assert_eq!(
+            serde_json::to_string(&modal).unwrap(),
+            r#"{"type":"modal","callback_id":"do-it","title":{"text":"Do It","type":"plain_text"},"submit":{"text":"Go","type":"plain_text"},"close":{"text":"Cancel","type":"plain_text"},"private_metadata":"C123","blocks":[{"text":{"text":"Hello","type":"mrkdwn"},"type":"section"}]}"#
+        )
+    }
+}
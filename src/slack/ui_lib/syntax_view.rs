@@ -0,0 +1,121 @@
+use crate::language::ast::{RoswaalTestSyntax, RoswaalTestSyntaxLineContent};
+
+use super::{block_kit_views::SlackSection, slack_view::SlackView};
+
+/// How a `SlackTestSyntaxView` shows the source code of a test.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SlackTestSyntaxMode {
+    /// A monochrome code block of the source code exactly as it was written.
+    #[default]
+    CodeBlock,
+    /// Markdown outside of a code block with bold command names and italic descriptions, which is
+    /// easier to scan than a code block.
+    Highlighted,
+}
+
+/// A section that shows the source code of a test.
+pub struct SlackTestSyntaxView {
+    markdown: String,
+}
+
+impl SlackTestSyntaxView {
+    pub fn new(syntax: &RoswaalTestSyntax, mode: SlackTestSyntaxMode) -> Self {
+        let markdown = match mode {
+            SlackTestSyntaxMode::CodeBlock => syntax.markdown_code_block(),
+            SlackTestSyntaxMode::Highlighted => highlighted_markdown(syntax),
+        };
+        Self { markdown }
+    }
+}
+
+impl SlackView for SlackTestSyntaxView {
+    fn slack_body(&self) -> impl SlackView {
+        SlackSection::from_markdown(&self.markdown)
+    }
+}
+
+/// Returns `syntax` as mrkdwn where each command name is bold and each description is italic.
+///
+/// Lines that are not commands are kept as they are, and blank lines are kept so that tests
+/// spaced out by their authors read the same way.
+fn highlighted_markdown(syntax: &RoswaalTestSyntax) -> String {
+    if syntax.source_code().trim().is_empty() {
+        return "_(Empty Source Code)_".to_string();
+    }
+    syntax
+        .source_code()
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            match RoswaalTestSyntaxLineContent::from(line) {
+                Some(RoswaalTestSyntaxLineContent::Command {
+                    name, description, ..
+                }) => highlighted_command_line(line, name, description),
+                Some(RoswaalTestSyntaxLineContent::Unknown(line)) => escaped(line),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn highlighted_command_line(line: &str, name: &str, description: &str) -> String {
+    let mut markdown = format!("*{}:*", escaped(name.trim()));
+    if !description.is_empty() {
+        markdown.push_str(&format!(" _{}_", escaped(description)));
+    }
+    // NB: Annotations such as "(retries: 2)" are not a part of the parsed description, but they
+    // still need to be shown after it.
+    let annotation = line[name.len() + 1..]
+        .trim()
+        .strip_prefix(description)
+        .unwrap_or_default()
+        .trim();
+    if !annotation.is_empty() {
+        markdown.push_str(&format!(" {}", escaped(annotation)));
+    }
+    markdown
+}
+
+/// Escapes the characters that slack treats as control characters in mrkdwn.
+fn escaped(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_command_names_and_descriptions() {
+        let syntax = RoswaalTestSyntax::from(
+            "\
+New Test: Join <an> event & leave
+
+Step 1: Join the event (retries: 2)
+Requirement 1: Ensure the user is in the event
+Before Launch:
+I am not a command",
+        );
+        let markdown = highlighted_markdown(&syntax);
+        let expected = "\
+*New Test:* _Join &lt;an&gt; event &amp; leave_
+
+*Step 1:* _Join the event_ (retries: 2)
+*Requirement 1:* _Ensure the user is in the event_
+*Before Launch:*
+I am not a command";
+        assert_eq!(markdown, expected)
+    }
+
+    #[test]
+    fn indicates_empty_source_code() {
+        let view = SlackTestSyntaxView::new(
+            &RoswaalTestSyntax::from(""),
+            SlackTestSyntaxMode::Highlighted,
+        );
+        assert_eq!(view.markdown, "_(Empty Source Code)_")
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use super::blocks::SlackBlocks;
 use super::slack_view::render_slack_view;
@@ -26,6 +27,77 @@ pub enum SnapshotMode {
     Comparing,
 }
 
+/// The limits that slack places on the blocks of a surface.
+///
+/// Slack rejects a surface that breaks any of these limits, so a view that renders fine in a
+/// snapshot may still fail to send.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SlackBlockLimits {
+    max_blocks: usize,
+    max_section_text_length: usize,
+    max_header_text_length: usize,
+}
+
+impl SlackBlockLimits {
+    /// The limits of a message, or a response to a slash command.
+    pub const MESSAGE: Self = Self {
+        max_blocks: 50,
+        max_section_text_length: 3000,
+        max_header_text_length: 150,
+    };
+
+    /// The limits of a modal, or the home tab.
+    pub const SURFACE: Self = Self {
+        max_blocks: 100,
+        ..Self::MESSAGE
+    };
+}
+
+/// Asserts that the blocks rendered by `view` are within `limits`.
+#[cfg(test)]
+pub fn assert_slack_view_within_limits(view: &impl SlackView, limits: SlackBlockLimits) {
+    let blocks = rendered_block_values(view);
+    assert!(
+        blocks.len() <= limits.max_blocks,
+        "The view rendered {} blocks, but slack only allows {}.",
+        blocks.len(),
+        limits.max_blocks
+    );
+    for (index, block) in blocks.iter().enumerate() {
+        let max_text_length = match block["type"].as_str() {
+            Some("section") => limits.max_section_text_length,
+            Some("header") => limits.max_header_text_length,
+            _ => continue,
+        };
+        let text_length = block["text"]["text"].as_str().unwrap_or("").chars().count();
+        assert!(
+            text_length <= max_text_length,
+            "Block {} has {} characters of text, but slack only allows {}.",
+            index,
+            text_length,
+            max_text_length
+        );
+    }
+}
+
+/// Returns a plain text transcript of the blocks rendered by `view`, which is roughly what a
+/// screen reader announces when reading the view.
+///
+/// Slack markdown is reduced to its plain text (eg. `*bold*` becomes `bold`, and `<url|label>`
+/// becomes `label`). Headers are prefixed with `#`, dividers become `---`, and buttons and inputs
+/// are written in brackets.
+#[cfg(test)]
+pub fn slack_view_transcript(view: &impl SlackView) -> String {
+    render_slack_view(view).transcript()
+}
+
+fn rendered_block_values(view: &impl SlackView) -> Vec<Value> {
+    match serde_json::to_value(render_slack_view(view)).unwrap() {
+        Value::Array(blocks) => blocks,
+        _ => vec![],
+    }
+}
+
 /// Asserts a snapshot of a `SlackView`.
 ///
 /// This function is useful for testing and iterating on the UI of complex `SlackView`s. This
@@ -39,26 +111,50 @@ pub enum SnapshotMode {
 /// snapshot will not be written to `slack-snapshots`, but rather the gitignored
 /// `slack-snapshots-diffs` directory. This directory is useful for comparing snapshots when a test
 /// failure occurs.
+///
+/// A `.txt` snapshot of the `slack_view_transcript` of the view is recorded and compared next to
+/// the json, so that changes to what the view says can be reviewed without reading json. The view
+/// must also be within the `SlackBlockLimits::MESSAGE` limits.
 #[cfg(test)]
 pub fn assert_slack_view_snapshot(name: &str, view: &impl SlackView, mode: SnapshotMode) {
-    let raw_path = format!("./slack-snapshots/{}.json", name);
-    let path = Path::new(&raw_path);
-    let is_recording = mode == SnapshotMode::Recording || !path.exists();
+    assert_slack_view_snapshot_with_limits(name, view, mode, SlackBlockLimits::MESSAGE)
+}
+
+/// Asserts a snapshot of a `SlackView` like `assert_slack_view_snapshot`, but checks the view
+/// against the specified `limits`.
+///
+/// Use `SlackBlockLimits::SURFACE` for views that are shown in a modal or the home tab.
+#[cfg(test)]
+pub fn assert_slack_view_snapshot_with_limits(
+    name: &str,
+    view: &impl SlackView,
+    mode: SnapshotMode,
+    limits: SlackBlockLimits,
+) {
     let blocks = BlockKitBuilderCompatibleBlocks {
         blocks: render_slack_view(view),
     };
     let blocks_json = serde_json::to_string(&blocks).unwrap();
+    assert_snapshot_file(name, "json", &blocks_json, mode);
+    assert_snapshot_file(name, "txt", &slack_view_transcript(view), mode);
+    assert_slack_view_within_limits(view, limits)
+}
+
+fn assert_snapshot_file(name: &str, extension: &str, contents: &str, mode: SnapshotMode) {
+    let raw_path = format!("./slack-snapshots/{}.{}", name, extension);
+    let path = Path::new(&raw_path);
+    let is_recording = mode == SnapshotMode::Recording || !path.exists();
     if is_recording {
         let mut file = File::create(path).unwrap();
-        _ = file.write(blocks_json.as_bytes());
+        _ = file.write(contents.as_bytes());
     } else {
-        let diff_path = format!("./slack-snapshot-diffs/{}.json", name);
+        let diff_path = format!("./slack-snapshot-diffs/{}.{}", name, extension);
         let mut file = File::create(&diff_path).unwrap();
-        _ = file.write(blocks_json.as_bytes());
+        _ = file.write(contents.as_bytes());
         file = File::open(path).unwrap();
-        let mut snapshot_json = String::new();
-        _ = file.read_to_string(&mut snapshot_json);
-        assert_eq!(blocks_json, snapshot_json)
+        let mut snapshot_contents = String::new();
+        _ = file.read_to_string(&mut snapshot_contents);
+        assert_eq!(contents, snapshot_contents)
     }
 }
 
@@ -69,9 +165,16 @@ struct BlockKitBuilderCompatibleBlocks {
 
 #[cfg(test)]
 mod tests {
-    use crate::slack::ui_lib::{block_kit_views::SlackSection, slack_view::SlackView};
+    use crate::slack::ui_lib::{
+        block_kit_views::{SlackActions, SlackButton, SlackDivider, SlackHeader, SlackSection},
+        for_each_view::ForEachView,
+        slack_view::SlackView,
+    };
 
-    use super::{assert_slack_view_snapshot, SnapshotMode};
+    use super::{
+        assert_slack_view_snapshot, assert_slack_view_within_limits, slack_view_transcript,
+        SlackBlockLimits, SnapshotMode,
+    };
 
     struct SomeView;
 
@@ -85,4 +188,63 @@ mod tests {
     fn record_snapshot() {
         assert_slack_view_snapshot("test-snapshot", &SomeView, SnapshotMode::Comparing)
     }
+
+    #[test]
+    fn transcript_reduces_markdown_to_plain_text() {
+        let view = SlackHeader::new("Remove Tests")
+            .flat_chain_block(SlackSection::from_markdown(
+                "✅ *Removed* `Blob` from _the tests_!\nApprove the PR in <#C123> or <https://github.com|on GitHub>, <@U1> <!subteam^S1>.",
+            ))
+            .flat_chain_block(SlackDivider)
+            .flat_chain_block(SlackActions::new(vec![
+                SlackButton::new("Close Branch", "close", "branch"),
+                SlackButton::new("Merge", "merge", "branch"),
+            ]));
+        let expected_transcript = "\
+# Remove Tests
+
+✅ Removed Blob from the tests!
+Approve the PR in #C123 or on GitHub, @U1 @S1.
+
+---
+
+[Close Branch] [Merge]";
+        assert_eq!(slack_view_transcript(&view), expected_transcript)
+    }
+
+    #[test]
+    fn transcript_keeps_underscores_and_asterisks_inside_words() {
+        let view = SlackSection::from_markdown("Set ROSWAAL_FORMATTER_COMMAND to 2*3");
+        assert_eq!(
+            slack_view_transcript(&view),
+            "Set ROSWAAL_FORMATTER_COMMAND to 2*3"
+        )
+    }
+
+    #[test]
+    fn views_within_limits() {
+        let sections = (0..50).map(|_| "a".repeat(3000)).collect::<Vec<String>>();
+        let view = ForEachView::new(sections.iter(), |text| SlackSection::from_markdown(text));
+        assert_slack_view_within_limits(&view, SlackBlockLimits::MESSAGE)
+    }
+
+    #[test]
+    #[should_panic(expected = "The view rendered 51 blocks, but slack only allows 50.")]
+    fn message_with_too_many_blocks() {
+        let view = ForEachView::new(0..51, |_| SlackDivider);
+        assert_slack_view_within_limits(&view, SlackBlockLimits::MESSAGE)
+    }
+
+    #[test]
+    fn surface_allows_more_blocks_than_message() {
+        let view = ForEachView::new(0..100, |_| SlackDivider);
+        assert_slack_view_within_limits(&view, SlackBlockLimits::SURFACE)
+    }
+
+    #[test]
+    #[should_panic(expected = "Block 0 has 151 characters of text, but slack only allows 150.")]
+    fn header_with_too_much_text() {
+        let view = SlackHeader::new(&"a".repeat(151));
+        assert_slack_view_within_limits(&view, SlackBlockLimits::MESSAGE)
+    }
 }
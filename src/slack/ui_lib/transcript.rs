@@ -0,0 +1,78 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+
+use super::blocks::SlackBlocks;
+
+impl SlackBlocks {
+    /// Returns a plain text transcript of these blocks, which is roughly what a screen reader
+    /// announces when reading them.
+    ///
+    /// Slack markdown is reduced to its plain text (eg. `*bold*` becomes `bold`, and
+    /// `<url|label>` becomes `label`). Headers are prefixed with `#`, dividers become `---`, and
+    /// buttons and inputs are written in brackets.
+    pub fn transcript(&self) -> String {
+        self.values()
+            .iter()
+            .map(block_transcript)
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+fn block_transcript(block: &Value) -> String {
+    let text = |value: &Value| value["text"].as_str().unwrap_or("").to_string();
+    match block["type"].as_str().unwrap_or("") {
+        "header" => format!("# {}", text(&block["text"])),
+        "section" => plain_text(&text(&block["text"])),
+        "divider" => "---".to_string(),
+        "actions" => block["elements"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|element| format!("[{}]", text(&element["text"])))
+            .collect::<Vec<String>>()
+            .join(" "),
+        "input" => match block["element"]["type"].as_str() {
+            Some("multi_external_select") => format!("[{}: multi select]", text(&block["label"])),
+            _ => format!("[{}: text field]", text(&block["label"])),
+        },
+        kind => format!("[{} block]", kind),
+    }
+}
+
+static MRKDWN_LINK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<(?:[^<>|]*\|)?(?<label>[^<>|]*)>").unwrap());
+
+static MRKDWN_CODE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)```(?<code>[^`]+)```|(?<literal>``+)|`(?<code_span>[^`\n]+)`").unwrap()
+});
+
+static MRKDWN_EMPHASIS_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)(?<before>^|[\s(])[*_~]+|[*_~]+(?<after>$|[\s).,!?:])").unwrap());
+
+/// Reduces slack markdown to the text that it displays.
+fn plain_text(mrkdwn: &str) -> String {
+    let text = MRKDWN_LINK_REGEX.replace_all(mrkdwn, |captures: &regex::Captures| {
+        let label = &captures["label"];
+        match label.strip_prefix("!subteam^") {
+            Some(group_id) => format!("@{}", group_id),
+            None => label
+                .strip_prefix('!')
+                .map_or(label.to_string(), |l| format!("@{}", l)),
+        }
+    });
+    let text = MRKDWN_CODE_REGEX
+        .replace_all(&text, "${code}${literal}${code_span}")
+        .to_string();
+    let mut previous_text = text;
+    loop {
+        let text = MRKDWN_EMPHASIS_REGEX
+            .replace_all(&previous_text, "${before}${after}")
+            .to_string();
+        if text == previous_text {
+            return text.trim_end().to_string();
+        }
+        previous_text = text;
+    }
+}
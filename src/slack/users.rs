@@ -1 +1,208 @@
-pub const MATTHEW_SLACK_USER_ID: &str = "U04K0DX9HC6";
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::Result;
+use sqlx::FromRow;
+use strum_macros::{Display, EnumIter, EnumString, IntoStaticStr};
+
+use crate::utils::{
+    config::RoswaalConfig,
+    db::{
+        query::{query, query_as},
+        RoswaalDatabaseTransaction,
+    },
+};
+
+/// The responsibilities that slack users can be pinged for.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, EnumString, EnumIter, IntoStaticStr, Display)]
+pub enum RoswaalSlackUserRole {
+    /// Fixes merge conflicts that the tool could not resolve on its own.
+    #[strum(serialize = "merge-conflict-owner")]
+    MergeConflictOwner,
+    /// Maintains the deployment of the tool, and is allowed to edit the roster.
+    #[strum(serialize = "infra-owner")]
+    InfraOwner,
+}
+
+/// The slack users that are responsible for each `RoswaalSlackUserRole`.
+///
+/// A role without any users falls back to the maintainer in the current `RoswaalConfig`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RoswaalSlackUserRoster {
+    user_ids: HashMap<RoswaalSlackUserRole, Vec<String>>,
+}
+
+impl RoswaalSlackUserRoster {
+    /// Returns a copy of this roster where the users of `role` are replaced with `user_ids`.
+    pub fn with_user_ids(mut self, role: RoswaalSlackUserRole, user_ids: &[&str]) -> Self {
+        self.user_ids
+            .insert(role, user_ids.iter().map(|id| id.to_string()).collect());
+        self
+    }
+}
+
+impl RoswaalSlackUserRoster {
+    /// Returns the ids of the users responsible for `role`.
+    pub fn user_ids(&self, role: RoswaalSlackUserRole) -> Vec<&str> {
+        match self.user_ids.get(&role) {
+            Some(ids) if !ids.is_empty() => ids.iter().map(|id| id.as_str()).collect(),
+            _ => vec![RoswaalConfig::current().maintainer_slack_user_id()],
+        }
+    }
+
+    /// Returns slack markdown that pings every user responsible for `role`.
+    pub fn mentions(&self, role: RoswaalSlackUserRole) -> String {
+        self.user_ids(role)
+            .iter()
+            .map(|id| format!("<@{}>", id))
+            .collect::<Vec<String>>()
+            .join(" or ")
+    }
+
+    /// Returns true if the user with `user_id` is responsible for `role`.
+    pub fn has_role(&self, user_id: &str, role: RoswaalSlackUserRole) -> bool {
+        self.user_ids(role).contains(&user_id)
+    }
+}
+
+/// Parses the id of a slack user from a mention in a slash command's text.
+///
+/// Slack sends mentions as `<@U123>` or `<@U123|name>` when it escapes usernames, and raw ids are
+/// accepted as well.
+pub fn slack_user_id_from_mention(mention: &str) -> Option<String> {
+    let id = mention
+        .strip_prefix("<@")
+        .and_then(|m| m.strip_suffix('>'))
+        .map(|m| m.split('|').next().unwrap_or(m))
+        .unwrap_or(mention);
+    let is_user_id = id.len() > 1
+        && (id.starts_with('U') || id.starts_with('W'))
+        && id
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+    is_user_id.then(|| id.to_string())
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    pub async fn slack_user_roster(&mut self) -> Result<RoswaalSlackUserRoster> {
+        let rows = query_as::<SqliteSlackUserRole>(statements::SELECT_SLACK_USER_ROLES)
+            .fetch_all(self.connection())
+            .await?;
+        let mut roster = RoswaalSlackUserRoster::default();
+        for row in rows {
+            // NB: Rows for roles that no longer exist are ignored rather than failing every
+            // command that needs to ping someone.
+            if let Ok(role) = RoswaalSlackUserRole::from_str(&row.role) {
+                roster.user_ids.entry(role).or_default().push(row.user_id);
+            }
+        }
+        Ok(roster)
+    }
+
+    /// Replaces the users responsible for `role` with `user_ids`.
+    pub async fn set_slack_user_ids(
+        &mut self,
+        role: RoswaalSlackUserRole,
+        user_ids: &[String],
+    ) -> Result<()> {
+        let role: &str = role.into();
+        query(statements::DELETE_SLACK_USER_ROLE)
+            .bind(role)
+            .execute(self.connection())
+            .await?;
+        for (ordinal, user_id) in user_ids.iter().enumerate() {
+            query(statements::INSERT_SLACK_USER_ROLE)
+                .bind(role)
+                .bind(user_id)
+                .bind(ordinal as i64)
+                .execute(self.connection())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteSlackUserRole {
+    role: String,
+    user_id: String,
+}
+
+mod statements {
+    pub const SELECT_SLACK_USER_ROLES: &str = "
+SELECT role, user_id FROM SlackUserRoles ORDER BY role, ordinal;
+";
+
+    pub const DELETE_SLACK_USER_ROLE: &str = "DELETE FROM SlackUserRoles WHERE role = ?;";
+
+    pub const INSERT_SLACK_USER_ROLE: &str = "
+INSERT INTO SlackUserRoles (role, user_id, ordinal) VALUES (?, ?, ?)
+ON CONFLICT (role, user_id) DO NOTHING;
+";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{utils::db::RoswaalDatabase, with_transaction};
+
+    use super::*;
+
+    #[test]
+    fn roles_without_users_fall_back_to_the_maintainer() {
+        let roster = RoswaalSlackUserRoster::default()
+            .with_user_ids(RoswaalSlackUserRole::InfraOwner, &["U1", "U2"]);
+        assert_eq!(
+            roster.user_ids(RoswaalSlackUserRole::MergeConflictOwner),
+            vec![RoswaalConfig::current().maintainer_slack_user_id()]
+        );
+        assert_eq!(
+            roster.mentions(RoswaalSlackUserRole::InfraOwner),
+            "<@U1> or <@U2>"
+        );
+        assert!(roster.has_role("U2", RoswaalSlackUserRole::InfraOwner));
+        assert!(!roster.has_role("U3", RoswaalSlackUserRole::InfraOwner))
+    }
+
+    #[test]
+    fn parses_user_ids_from_mentions() {
+        let mentions = [
+            ("<@U01234567>", Some("U01234567")),
+            ("<@U01234567|matthew>", Some("U01234567")),
+            ("W01234567", Some("W01234567")),
+            ("@matthew", None),
+            ("<#C01234567>", None),
+            ("U", None),
+        ];
+        for (mention, id) in mentions {
+            assert_eq!(
+                slack_user_id_from_mention(mention),
+                id.map(|id| id.to_string())
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn stores_and_replaces_users_of_roles() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let roster = with_transaction!(transaction, async {
+            transaction
+                .set_slack_user_ids(
+                    RoswaalSlackUserRole::MergeConflictOwner,
+                    &["U2".to_string(), "U1".to_string()],
+                )
+                .await?;
+            transaction
+                .set_slack_user_ids(RoswaalSlackUserRole::InfraOwner, &["U3".to_string()])
+                .await?;
+            transaction
+                .set_slack_user_ids(RoswaalSlackUserRole::InfraOwner, &["U4".to_string()])
+                .await?;
+            transaction.slack_user_roster().await
+        })
+        .unwrap();
+        let expected_roster = RoswaalSlackUserRoster::default()
+            .with_user_ids(RoswaalSlackUserRole::MergeConflictOwner, &["U2", "U1"])
+            .with_user_ids(RoswaalSlackUserRole::InfraOwner, &["U4"]);
+        assert_eq!(roster, expected_roster)
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use sqlx::FromRow;
+
+use crate::{
+    language::compilation_results::RoswaalTestCompilationResults,
+    utils::db::{
+        query::{query, query_as},
+        RoswaalDatabaseTransaction,
+    },
+};
+
+use super::usage::{RoswaalUsageStats, RoswaalWeeklyUsageStats};
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Records the number of tests and compilation errors in the text that was given to
+    /// `/add-tests`.
+    pub async fn save_test_submission(
+        &mut self,
+        results: &RoswaalTestCompilationResults<'_>,
+    ) -> Result<()> {
+        let failures = results.failures();
+        let compilation_error_count = failures
+            .iter()
+            .map(|failure| failure.errors().len())
+            .sum::<usize>();
+        query(statements::INSERT_TEST_SUBMISSION)
+            .bind((results.tests().len() + failures.len()) as i64)
+            .bind(compilation_error_count as i64)
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    /// Aggregates the merged tests, test run results, and test submissions into weekly usage
+    /// statistics.
+    ///
+    /// Only the most recent runs of each test are kept, so older weeks may report fewer runs
+    /// than were actually made.
+    pub async fn usage_stats(&mut self) -> Result<RoswaalUsageStats> {
+        let mut weeks = BTreeMap::<String, (u64, u64, u64)>::new();
+        let tests_added = query_as::<SqliteWeeklyCount>(statements::SELECT_WEEKLY_TESTS_ADDED)
+            .fetch_all(self.connection())
+            .await?;
+        for row in tests_added {
+            weeks.entry(row.week_start).or_default().0 = row.count as u64;
+        }
+        let run_results =
+            query_as::<SqliteWeeklyRunResults>(statements::SELECT_WEEKLY_TEST_RUN_RESULTS)
+                .fetch_all(self.connection())
+                .await?;
+        for row in run_results {
+            let week = weeks.entry(row.week_start).or_default();
+            week.1 = row.run_count as u64;
+            week.2 = row.failure_count as u64;
+        }
+        let submissions =
+            query_as::<SqliteSubmissionTotals>(statements::SELECT_TEST_SUBMISSION_TOTALS)
+                .fetch_one(self.connection())
+                .await?;
+        let weeks = weeks
+            .into_iter()
+            .map(|(week_start, (tests_added, run_count, failure_count))| {
+                RoswaalWeeklyUsageStats::new(&week_start, tests_added, run_count, failure_count)
+            })
+            .collect();
+        Ok(RoswaalUsageStats::new(
+            weeks,
+            submissions.submission_count as u64,
+            submissions.compilation_error_count as u64,
+        ))
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteWeeklyCount {
+    week_start: String,
+    count: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteWeeklyRunResults {
+    week_start: String,
+    run_count: i64,
+    failure_count: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteSubmissionTotals {
+    submission_count: i64,
+    compilation_error_count: i64,
+}
+
+mod statements {
+    use crate::utils::db::query::RoswaalSql;
+
+    pub const INSERT_TEST_SUBMISSION: &str =
+        "INSERT INTO TestSubmissions (test_count, compilation_error_count) VALUES (?, ?);";
+
+    pub const SELECT_WEEKLY_TESTS_ADDED: RoswaalSql = RoswaalSql::new(
+        "
+SELECT
+    date(creation_date, 'unixepoch', 'weekday 0', '-6 days') AS week_start,
+    COUNT(*) AS count
+FROM Tests
+WHERE unmerged_branch_name IS NULL
+GROUP BY week_start;
+",
+        "
+SELECT
+    to_char(date_trunc('week', creation_date AT TIME ZONE 'UTC'), 'YYYY-MM-DD') AS week_start,
+    COUNT(*) AS count
+FROM Tests
+WHERE unmerged_branch_name IS NULL
+GROUP BY week_start;
+",
+    );
+
+    pub const SELECT_WEEKLY_TEST_RUN_RESULTS: RoswaalSql = RoswaalSql::new(
+        "
+SELECT
+    date(creation_date, 'unixepoch', 'weekday 0', '-6 days') AS week_start,
+    COUNT(*) AS run_count,
+    SUM(did_fail) AS failure_count
+FROM TestRunResults
+GROUP BY week_start;
+",
+        "
+SELECT
+    to_char(date_trunc('week', creation_date AT TIME ZONE 'UTC'), 'YYYY-MM-DD') AS week_start,
+    COUNT(*) AS run_count,
+    SUM(CASE WHEN did_fail THEN 1 ELSE 0 END) AS failure_count
+FROM TestRunResults
+GROUP BY week_start;
+",
+    );
+
+    pub const SELECT_TEST_SUBMISSION_TOTALS: &str = "
+SELECT
+    COUNT(*) AS submission_count,
+    COALESCE(SUM(compilation_error_count), 0) AS compilation_error_count
+FROM TestSubmissions;
+";
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
+
+    use crate::{
+        git::branch_name::RoswaalOwnedGitBranchName,
+        language::{ast::RoswaalTestSyntax, compilation_results::RoswaalTestCompilationResults},
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestProgressUpload},
+        utils::db::{
+            query::{query, RoswaalTimestamp},
+            RoswaalDatabase,
+        },
+    };
+
+    #[tokio::test]
+    async fn aggregates_usage_stats_by_week() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests_syntax = vec![
+            RoswaalTestSyntax::from("New Test: A\nStep 1: Thing\nRequirement 1: Thing"),
+            RoswaalTestSyntax::from("New Test: B\nStep 1: Thing\nRequirement 1: Thing"),
+            RoswaalTestSyntax::from("New Test: C\nStep 1: Thing"),
+        ];
+        let results = RoswaalTestCompilationResults::compile(tests_syntax, &vec![]);
+        let branch_name = RoswaalOwnedGitBranchName::for_adding_tests();
+        transaction.save_test_submission(&results).await.unwrap();
+        transaction.save_test_submission(&results).await.unwrap();
+        transaction
+            .save_tests(&results.tests(), &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let progress = vec![
+            RoswaalTestProgressUpload::new("A".to_string(), None, None),
+            RoswaalTestProgressUpload::new(
+                "B".to_string(),
+                Some(RoswaalTestCommandOrdinal::for_before_launch()),
+                None,
+            ),
+        ];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let date = |date: &str| RoswaalTimestamp(date.parse::<DateTime<Utc>>().unwrap());
+        query("UPDATE Tests SET creation_date = ? WHERE name = 'A';")
+            .bind(date("2024-06-05T00:00:00Z"))
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        query("UPDATE Tests SET creation_date = ? WHERE name = 'B';")
+            .bind(date("2024-06-16T00:00:00Z"))
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        query("UPDATE TestRunResults SET creation_date = ?;")
+            .bind(date("2024-06-10T00:00:00Z"))
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        let stats = transaction.usage_stats().await.unwrap();
+        assert_eq!(
+            serde_json::to_value(&stats).unwrap(),
+            json!({
+                "weeks": [
+                    {
+                        "week_start": "2024-06-03",
+                        "tests_added": 1,
+                        "test_run_count": 0,
+                        "test_failure_count": 0,
+                        "failure_rate": null
+                    },
+                    {
+                        "week_start": "2024-06-10",
+                        "tests_added": 1,
+                        "test_run_count": 2,
+                        "test_failure_count": 1,
+                        "failure_rate": 0.5
+                    }
+                ],
+                "submission_count": 2,
+                "average_compilation_errors_per_submission": 2.0,
+                "test_run_count": 2,
+                "test_failure_count": 1,
+                "failure_rate": 0.5
+            })
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use serde::Serialize;
+
+/// Aggregated statistics about how the tool is used.
+///
+/// The statistics are anonymous, so they never include the names of users, tests, or branches.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RoswaalUsageStats {
+    weeks: Vec<RoswaalWeeklyUsageStats>,
+    submission_count: u64,
+    /// The average number of compilation errors in the text given to `/add-tests`, or None if
+    /// no tests have been submitted.
+    average_compilation_errors_per_submission: Option<f64>,
+    test_run_count: u64,
+    test_failure_count: u64,
+    failure_rate: Option<f64>,
+}
+
+impl RoswaalUsageStats {
+    pub fn new(
+        weeks: Vec<RoswaalWeeklyUsageStats>,
+        submission_count: u64,
+        compilation_error_count: u64,
+    ) -> Self {
+        let test_run_count = weeks.iter().map(|week| week.test_run_count).sum();
+        let test_failure_count = weeks.iter().map(|week| week.test_failure_count).sum();
+        let average_compilation_errors_per_submission = if submission_count == 0 {
+            None
+        } else {
+            Some(compilation_error_count as f64 / submission_count as f64)
+        };
+        Self {
+            weeks,
+            submission_count,
+            average_compilation_errors_per_submission,
+            test_run_count,
+            test_failure_count,
+            failure_rate: failure_rate(test_run_count, test_failure_count),
+        }
+    }
+}
+
+/// The usage statistics of the week starting on the monday given by `week_start`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RoswaalWeeklyUsageStats {
+    /// The date of the monday that starts the week in `YYYY-MM-DD` format.
+    week_start: String,
+    tests_added: u64,
+    test_run_count: u64,
+    test_failure_count: u64,
+    failure_rate: Option<f64>,
+}
+
+impl RoswaalWeeklyUsageStats {
+    pub fn new(
+        week_start: &str,
+        tests_added: u64,
+        test_run_count: u64,
+        test_failure_count: u64,
+    ) -> Self {
+        Self {
+            week_start: week_start.to_string(),
+            tests_added,
+            test_run_count,
+            test_failure_count,
+            failure_rate: failure_rate(test_run_count, test_failure_count),
+        }
+    }
+}
+
+fn failure_rate(run_count: u64, failure_count: u64) -> Option<f64> {
+    if run_count == 0 {
+        None
+    } else {
+        Some(failure_count as f64 / run_count as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_rates_from_weekly_totals() {
+        let stats = RoswaalUsageStats::new(
+            vec![
+                RoswaalWeeklyUsageStats::new("2024-06-03", 2, 4, 1),
+                RoswaalWeeklyUsageStats::new("2024-06-10", 1, 0, 0),
+            ],
+            4,
+            6,
+        );
+        assert_eq!(stats.average_compilation_errors_per_submission, Some(1.5));
+        assert_eq!(stats.failure_rate, Some(0.25));
+        assert_eq!(stats.weeks[1].failure_rate, None)
+    }
+
+    #[test]
+    fn has_no_rates_without_submissions_or_runs() {
+        let stats = RoswaalUsageStats::new(vec![], 0, 0);
+        assert_eq!(stats.average_compilation_errors_per_submission, None);
+        assert_eq!(stats.failure_rate, None)
+    }
+}
@@ -0,0 +1,264 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::{
+    git::branch_name::RoswaalOwnedGitBranchName,
+    language::test::RoswaalCompiledTestCommand,
+    utils::db::{
+        query::{query, query_as},
+        RoswaalDatabaseTransaction,
+    },
+};
+
+use super::test::RoswaalTest;
+
+/// A snapshot of a merged test that was taken when the test was removed, so that it can be
+/// restored after its data is deleted.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalArchivedTest {
+    name: String,
+    description: Option<String>,
+    commands: Vec<RoswaalCompiledTestCommand>,
+    syntax: String,
+    tests_str: String,
+    added_by_slack_user_id: Option<String>,
+    removed_branch_name: RoswaalOwnedGitBranchName,
+    archive_date: DateTime<Utc>,
+}
+
+impl RoswaalArchivedTest {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn commands(&self) -> &[RoswaalCompiledTestCommand] {
+        &self.commands
+    }
+
+    /// Returns the roswaal syntax of this test as it was last submitted before it was removed.
+    pub fn syntax(&self) -> &str {
+        &self.syntax
+    }
+
+    pub fn added_by_slack_user_id(&self) -> Option<&str> {
+        self.added_by_slack_user_id.as_deref()
+    }
+
+    /// Returns the branch that removed this test.
+    pub fn removed_branch_name(&self) -> &RoswaalOwnedGitBranchName {
+        &self.removed_branch_name
+    }
+
+    pub fn archive_date(&self) -> DateTime<Utc> {
+        self.archive_date
+    }
+
+    /// Returns the syntax of this test in a code block, which is the format of the text given
+    /// to `/add-tests`.
+    pub fn tests_str(&self) -> &str {
+        &self.tests_str
+    }
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Archives the compiled commands and metadata of each test in `tests` that were removed on
+    /// the branch with `branch_name`.
+    pub async fn archive_tests(
+        &mut self,
+        tests: &[RoswaalTest],
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> Result<()> {
+        for test in tests {
+            let commands = test
+                .ordinal_commands()
+                .into_iter()
+                .map(|(_, command)| command)
+                .collect::<Vec<&RoswaalCompiledTestCommand>>();
+            query(statements::INSERT_ARCHIVED_TEST)
+                .bind(test.name())
+                .bind(test.description())
+                .bind(serde_json::to_string(&commands)?)
+                .bind(test.original_syntax())
+                .bind(test.added_by_slack_user_id())
+                .bind(branch_name)
+                .execute(self.connection())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the most recently archived test named `name` regardless of case, if any.
+    pub async fn archived_test(&mut self, name: &str) -> Result<Option<RoswaalArchivedTest>> {
+        let row = query_as::<SqliteArchivedTestRow>(statements::SELECT_ARCHIVED_TEST)
+            .bind(name)
+            .fetch_optional(self.connection())
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        Ok(Some(RoswaalArchivedTest {
+            name: row.name,
+            description: row.description,
+            commands: serde_json::from_str(&row.commands)?,
+            tests_str: format!("```\n{}\n```", row.syntax),
+            syntax: row.syntax,
+            added_by_slack_user_id: row.added_by_slack_user_id,
+            removed_branch_name: row.removed_branch_name,
+            archive_date: row.archive_date,
+        }))
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteArchivedTestRow {
+    name: String,
+    description: Option<String>,
+    commands: String,
+    syntax: String,
+    added_by_slack_user_id: Option<String>,
+    removed_branch_name: RoswaalOwnedGitBranchName,
+    archive_date: DateTime<Utc>,
+}
+
+mod statements {
+    pub const INSERT_ARCHIVED_TEST: &str = "
+INSERT INTO ArchivedTests (
+    name,
+    description,
+    commands,
+    syntax,
+    added_by_slack_user_id,
+    removed_branch_name
+) VALUES (?, ?, ?, ?, ?, ?);
+";
+
+    pub const SELECT_ARCHIVED_TEST: &str = "
+SELECT
+    name,
+    description,
+    commands,
+    syntax,
+    added_by_slack_user_id,
+    removed_branch_name,
+    archive_date
+FROM ArchivedTests
+WHERE LOWER(name) = LOWER(?)
+ORDER BY id DESC
+LIMIT 1;
+";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        language::{
+            compiler::{RoswaalCompile, RoswaalCompileContext},
+            test::RoswaalCompiledTest,
+        },
+        tests_data::query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+        utils::db::RoswaalDatabase,
+    };
+
+    use super::*;
+
+    const TEST_SYNTAX: &str = "\
+New Test: Join an event
+Abstract: Users should be able to join events near them.
+Step 1: Join the event
+Requirement 1: Tap the join button";
+
+    #[tokio::test]
+    async fn archives_removed_tests_when_merging_removals() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let add_branch = RoswaalOwnedGitBranchName::new("add");
+        let remove_branch = RoswaalOwnedGitBranchName::new("remove");
+        let test = compile(TEST_SYNTAX);
+        transaction
+            .save_tests_added_by(&vec![test.clone()], &add_branch, Some("U123"))
+            .await
+            .unwrap();
+        transaction.merge_unmerged_tests(&add_branch).await.unwrap();
+        transaction
+            .stage_test_removals(
+                &RoswaalTestNamesString::new("join an event"),
+                &remove_branch,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            transaction.archived_test("Join an event").await.unwrap(),
+            None
+        );
+        transaction
+            .merge_test_removals(&remove_branch)
+            .await
+            .unwrap();
+        let tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(tests, vec![]);
+        let archived_test = transaction
+            .archived_test("JOIN AN EVENT")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived_test.name(), "Join an event");
+        assert_eq!(
+            archived_test.description(),
+            Some("Users should be able to join events near them.")
+        );
+        assert_eq!(archived_test.commands(), test.commands().as_slice());
+        assert_eq!(archived_test.syntax(), TEST_SYNTAX);
+        assert_eq!(archived_test.added_by_slack_user_id(), Some("U123"));
+        assert_eq!(archived_test.removed_branch_name(), &remove_branch)
+    }
+
+    #[tokio::test]
+    async fn returns_the_most_recently_archived_version_of_a_test() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let first_test = RoswaalTest::new(
+            "Blob".to_string(),
+            Some("First".to_string()),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let second_test = RoswaalTest::new(
+            "Blob".to_string(),
+            Some("Second".to_string()),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let second_branch = RoswaalOwnedGitBranchName::new("second");
+        transaction
+            .archive_tests(&[first_test], &RoswaalOwnedGitBranchName::new("first"))
+            .await
+            .unwrap();
+        transaction
+            .archive_tests(&[second_test], &second_branch)
+            .await
+            .unwrap();
+        let archived_test = transaction.archived_test("blob").await.unwrap().unwrap();
+        assert_eq!(archived_test.description(), Some("Second"));
+        assert_eq!(archived_test.removed_branch_name(), &second_branch)
+    }
+
+    fn compile(syntax: &str) -> RoswaalCompiledTest {
+        RoswaalCompiledTest::compile(syntax, RoswaalCompileContext::new(&vec![])).unwrap()
+    }
+}
@@ -0,0 +1,282 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::language::test::RoswaalCompiledTest;
+
+use super::test::RoswaalTest;
+
+/// The time that each step is assumed to take when no test has recorded any durations.
+pub const DEFAULT_STEP_DURATION: Duration = Duration::from_secs(5);
+
+/// Estimates how long tests take to run from the durations recorded during their most recent
+/// runs.
+///
+/// A test that has recorded durations is estimated to take as long as its most recent run. A test
+/// that has never reported its durations is estimated from the average recorded step duration of
+/// all tests, multiplied by the number of steps that it runs.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RoswaalTestRuntimeEstimator {
+    average_step_duration: Duration,
+}
+
+impl RoswaalTestRuntimeEstimator {
+    pub fn new(tests: &[RoswaalTest]) -> Self {
+        let (total_duration, step_count) = tests
+            .iter()
+            .filter_map(|test| Some((test.total_duration()?, test.durations().len())))
+            .fold((Duration::ZERO, 0), |(duration, count), (d, c)| {
+                (duration + d, count + c)
+            });
+        let average_step_duration = if step_count == 0 {
+            DEFAULT_STEP_DURATION
+        } else {
+            total_duration / step_count as u32
+        };
+        Self {
+            average_step_duration,
+        }
+    }
+}
+
+impl RoswaalTestRuntimeEstimator {
+    /// Returns the estimated runtime of `test`.
+    pub fn test_estimate(&self, test: &RoswaalTest) -> RoswaalTestRuntimeEstimate {
+        match test.total_duration() {
+            Some(duration) => RoswaalTestRuntimeEstimate::new(test.name(), duration, true),
+            None => {
+                let command_count = test
+                    .ordinal_commands()
+                    .iter()
+                    .filter(|(_, c)| !c.is_before_launch())
+                    .count();
+                let duration = self.steps_duration(step_count(command_count));
+                RoswaalTestRuntimeEstimate::new(test.name(), duration, false)
+            }
+        }
+    }
+
+    /// Returns the estimated runtime of a newly compiled test, which has never been run.
+    pub fn compiled_test_estimate(&self, test: &RoswaalCompiledTest) -> RoswaalTestRuntimeEstimate {
+        let command_count = test
+            .commands()
+            .iter()
+            .filter(|c| !c.is_before_launch())
+            .count();
+        let duration = self.steps_duration(step_count(command_count));
+        RoswaalTestRuntimeEstimate::new(test.name(), duration, false)
+    }
+
+    /// Returns the estimated runtime of the suite made of the tests in `tests` that are run by
+    /// the test runner, which excludes planned and deprecated tests.
+    pub fn suite_estimate(&self, tests: &[RoswaalTest]) -> RoswaalSuiteRuntimeEstimate {
+        RoswaalSuiteRuntimeEstimate::new(
+            tests
+                .iter()
+                .filter(|t| !t.is_planned() && !t.is_deprecated())
+                .map(|t| self.test_estimate(t))
+                .collect(),
+        )
+    }
+
+    fn steps_duration(&self, step_count: usize) -> Duration {
+        self.average_step_duration * step_count as u32
+    }
+}
+
+/// Returns the number of steps reported by the test runner for a test with `command_count`
+/// commands, which includes its before launch step.
+fn step_count(command_count: usize) -> usize {
+    command_count + 1
+}
+
+/// The estimated runtime of a single test.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoswaalTestRuntimeEstimate {
+    name: String,
+    milliseconds: u64,
+    /// Whether the estimate is the recorded runtime of the test rather than a prediction.
+    is_measured: bool,
+}
+
+impl RoswaalTestRuntimeEstimate {
+    fn new(name: &str, duration: Duration, is_measured: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            milliseconds: duration.as_millis() as u64,
+            is_measured,
+        }
+    }
+}
+
+impl RoswaalTestRuntimeEstimate {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.milliseconds)
+    }
+
+    pub fn is_measured(&self) -> bool {
+        self.is_measured
+    }
+}
+
+/// The estimated runtime of a suite of tests that are run one after another.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoswaalSuiteRuntimeEstimate {
+    total_milliseconds: u64,
+    tests: Vec<RoswaalTestRuntimeEstimate>,
+}
+
+impl RoswaalSuiteRuntimeEstimate {
+    pub fn new(tests: Vec<RoswaalTestRuntimeEstimate>) -> Self {
+        Self {
+            total_milliseconds: tests.iter().map(|t| t.milliseconds).sum(),
+            tests,
+        }
+    }
+
+    /// Returns this estimate with `test` added to the suite, replacing any test with the same
+    /// name.
+    pub fn with_test(mut self, test: RoswaalTestRuntimeEstimate) -> Self {
+        self.tests.retain(|t| t.name != test.name);
+        self.tests.push(test);
+        Self::new(self.tests)
+    }
+}
+
+impl RoswaalSuiteRuntimeEstimate {
+    pub fn total_duration(&self) -> Duration {
+        Duration::from_millis(self.total_milliseconds)
+    }
+
+    pub fn tests(&self) -> &[RoswaalTestRuntimeEstimate] {
+        &self.tests
+    }
+
+    pub fn measured_test_count(&self) -> usize {
+        self.tests.iter().filter(|t| t.is_measured).count()
+    }
+
+    pub fn unmeasured_test_count(&self) -> usize {
+        self.tests.len() - self.measured_test_count()
+    }
+
+    /// Returns the amount of time that this suite is estimated to run for beyond `budget`, or
+    /// None if it fits in the budget.
+    pub fn overrun(&self, budget: Duration) -> Option<RoswaalSuiteRuntimeOverrun> {
+        let runtime = self.total_duration();
+        if runtime > budget {
+            Some(RoswaalSuiteRuntimeOverrun { runtime, budget })
+        } else {
+            None
+        }
+    }
+}
+
+/// A suite whose estimated runtime exceeds the configured time budget.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RoswaalSuiteRuntimeOverrun {
+    runtime: Duration,
+    budget: Duration,
+}
+
+impl RoswaalSuiteRuntimeOverrun {
+    pub fn new(runtime: Duration, budget: Duration) -> Self {
+        Self { runtime, budget }
+    }
+
+    pub fn runtime(&self) -> Duration {
+        self.runtime
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        language::test::RoswaalCompiledTestCommand,
+        tests_data::{ordinal::RoswaalTestCommandOrdinal, progress::RoswaalTestStepDuration},
+    };
+
+    use super::*;
+
+    #[test]
+    fn estimates_unmeasured_tests_from_the_average_step_duration() {
+        let tests = vec![
+            test("Measured", 2).with_durations(vec![
+                RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::for_before_launch(), 1000),
+                RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(0), 2000),
+                RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(1), 3000),
+            ]),
+            test("Unmeasured", 3),
+            test("Planned", 1).with_planned(true),
+        ];
+        let estimator = RoswaalTestRuntimeEstimator::new(&tests);
+        let estimate = estimator.suite_estimate(&tests);
+        assert_eq!(estimate.total_duration(), Duration::from_secs(14));
+        assert_eq!(estimate.measured_test_count(), 1);
+        assert_eq!(estimate.unmeasured_test_count(), 1);
+        assert_eq!(
+            estimator.test_estimate(&tests[1]),
+            RoswaalTestRuntimeEstimate::new("Unmeasured", Duration::from_secs(8), false)
+        )
+    }
+
+    #[test]
+    fn uses_default_step_duration_when_nothing_was_measured() {
+        let tests = vec![test("Blob", 1)];
+        let estimator = RoswaalTestRuntimeEstimator::new(&tests);
+        let estimate = estimator.suite_estimate(&tests);
+        assert_eq!(estimate.total_duration(), DEFAULT_STEP_DURATION * 2)
+    }
+
+    #[test]
+    fn reports_overrun_when_added_test_exceeds_budget() {
+        let tests = vec![test("Blob", 1)];
+        let estimator = RoswaalTestRuntimeEstimator::new(&tests);
+        let estimate = estimator.suite_estimate(&tests);
+        let budget = Duration::from_secs(15);
+        assert_eq!(estimate.overrun(budget), None);
+        let compiled_test = RoswaalCompiledTest::new("Other".to_string(), None, step_commands(1));
+        let estimate = estimate.with_test(estimator.compiled_test_estimate(&compiled_test));
+        assert_eq!(
+            estimate.overrun(budget),
+            Some(RoswaalSuiteRuntimeOverrun::new(
+                Duration::from_secs(20),
+                budget
+            ))
+        )
+    }
+
+    fn test(name: &str, step_count: usize) -> RoswaalTest {
+        RoswaalTest::new(
+            name.to_string(),
+            None,
+            step_commands(step_count),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    fn step_commands(count: usize) -> Vec<RoswaalCompiledTestCommand> {
+        (0..count)
+            .map(|i| RoswaalCompiledTestCommand::Step {
+                label: i.to_string(),
+                name: "Do the thing".to_string(),
+                requirement: "Do the thing".to_string(),
+                retries: None,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,84 @@
+/// The number of most recent runs of a test that its flakiness is scored from.
+pub const FLAKINESS_RUN_WINDOW: usize = 20;
+
+/// The outcomes of the most recent runs of a test, from which its flakiness is scored.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct RoswaalTestFlakiness {
+    failures: Vec<bool>,
+}
+
+impl RoswaalTestFlakiness {
+    /// Creates a flakiness from whether or not each run of a test failed, ordered from the oldest
+    /// run to the most recent run.
+    ///
+    /// Only the last `FLAKINESS_RUN_WINDOW` runs are kept.
+    pub fn new(mut failures: Vec<bool>) -> Self {
+        if failures.len() > FLAKINESS_RUN_WINDOW {
+            failures.drain(..failures.len() - FLAKINESS_RUN_WINDOW);
+        }
+        Self { failures }
+    }
+}
+
+impl RoswaalTestFlakiness {
+    pub fn run_count(&self) -> usize {
+        self.failures.len()
+    }
+
+    pub fn failure_count(&self) -> usize {
+        self.failures.iter().filter(|f| **f).count()
+    }
+
+    /// Returns a score between 0 and 1 where higher scores indicate a flakier test.
+    ///
+    /// The score is the failure rate of the recorded runs, except that each failure that
+    /// continues a streak of failures counts half as much as the failure before it. A test that
+    /// fails on and off therefore outranks a test that fails every run, since the latter is
+    /// broken rather than flaky.
+    pub fn score(&self) -> f64 {
+        if self.failures.is_empty() {
+            return 0.0;
+        }
+        let mut weight = 1.0;
+        let mut weighted_failures = 0.0;
+        for failed in self.failures.iter() {
+            if *failed {
+                weighted_failures += weight;
+                weight /= 2.0;
+            } else {
+                weight = 1.0;
+            }
+        }
+        weighted_failures / self.failures.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_zero_without_failures() {
+        assert_eq!(RoswaalTestFlakiness::default().score(), 0.0);
+        assert_eq!(RoswaalTestFlakiness::new(vec![false, false]).score(), 0.0)
+    }
+
+    #[test]
+    fn intermittent_failures_outrank_consistent_failures() {
+        let intermittent = RoswaalTestFlakiness::new(vec![true, false, true, false]);
+        let consistent = RoswaalTestFlakiness::new(vec![true, true, true, true]);
+        assert_eq!(intermittent.score(), 0.5);
+        assert_eq!(consistent.score(), 1.875 / 4.0);
+        assert!(intermittent.score() > consistent.score());
+        assert_eq!(consistent.failure_count(), 4)
+    }
+
+    #[test]
+    fn keeps_only_the_most_recent_runs() {
+        let mut failures = vec![true; 5];
+        failures.extend(vec![false; FLAKINESS_RUN_WINDOW]);
+        let flakiness = RoswaalTestFlakiness::new(failures);
+        assert_eq!(flakiness.run_count(), FLAKINESS_RUN_WINDOW);
+        assert_eq!(flakiness.failure_count(), 0)
+    }
+}
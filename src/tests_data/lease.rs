@@ -0,0 +1,156 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use crate::utils::db::{
+    query::{query, query_as, RoswaalTimestamp},
+    RoswaalDatabaseTransaction,
+};
+
+use super::query::RoswaalTestNamesString;
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Returns the lowercased names of the tests that are leased to any runner after `date`.
+    pub async fn leased_test_names(&mut self, date: DateTime<Utc>) -> Result<Vec<String>> {
+        let sqlite_names = query_as::<SqliteTestLeaseName>(statements::SELECT_LEASED_NAMES)
+            .bind(RoswaalTimestamp(date))
+            .fetch_all(self.connection())
+            .await?;
+        Ok(sqlite_names.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Leases each test in `test_names` to the runner with `runner_id` until `expiration_date`,
+    /// replacing any existing lease of the test.
+    pub async fn lease_tests(
+        &mut self,
+        test_names: &[&str],
+        runner_id: &str,
+        expiration_date: DateTime<Utc>,
+    ) -> Result<()> {
+        for name in test_names {
+            query(statements::UPSERT_LEASE)
+                .bind(name)
+                .bind(runner_id)
+                .bind(RoswaalTimestamp(expiration_date))
+                .execute(self.connection())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the leases held by the runner with `runner_id` on each test named in `test_names`,
+    /// or on every test if `test_names` is empty, and returns the lowercased names of the tests
+    /// that were released.
+    pub async fn release_test_leases(
+        &mut self,
+        runner_id: &str,
+        test_names: &RoswaalTestNamesString<'_>,
+    ) -> Result<Vec<String>> {
+        let names = test_names
+            .iter()
+            .map(|name| name.to_lowercase())
+            .collect::<Vec<String>>();
+        let statement = statements::delete_leases(names.len());
+        let mut delete_query = query_as::<SqliteTestLeaseName>(&statement).bind(runner_id);
+        for name in names.iter() {
+            delete_query = delete_query.bind(name);
+        }
+        let mut names = delete_query
+            .fetch_all(self.connection())
+            .await?
+            .into_iter()
+            .map(|n| n.name)
+            .collect::<Vec<String>>();
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteTestLeaseName {
+    name: String,
+}
+
+mod statements {
+    use crate::utils::db::array_fields;
+
+    pub const SELECT_LEASED_NAMES: &str =
+        "SELECT name FROM TestLeases WHERE expiration_date > ? ORDER BY name;";
+
+    pub const UPSERT_LEASE: &str = "
+INSERT INTO TestLeases (name, runner_id, expiration_date) VALUES (LOWER(?), ?, ?)
+ON CONFLICT (name) DO UPDATE
+SET runner_id = excluded.runner_id, expiration_date = excluded.expiration_date;
+";
+
+    pub fn delete_leases(count: usize) -> String {
+        if count == 0 {
+            "DELETE FROM TestLeases WHERE runner_id = ? RETURNING name;".to_string()
+        } else {
+            format!(
+                "DELETE FROM TestLeases WHERE runner_id = ? AND name IN {} RETURNING name;",
+                array_fields(count)
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::utils::db::RoswaalDatabase;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_returns_unexpired_leases() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let now = Utc::now();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .lease_tests(&["Blob"], "runner-1", now + Duration::minutes(5))
+            .await
+            .unwrap();
+        transaction
+            .lease_tests(&["Other"], "runner-2", now - Duration::minutes(5))
+            .await
+            .unwrap();
+        let names = transaction.leased_test_names(now).await.unwrap();
+        assert_eq!(names, vec!["blob".to_string()]);
+        transaction
+            .lease_tests(&["other"], "runner-1", now + Duration::minutes(5))
+            .await
+            .unwrap();
+        let names = transaction.leased_test_names(now).await.unwrap();
+        assert_eq!(names, vec!["blob".to_string(), "other".to_string()])
+    }
+
+    #[tokio::test]
+    async fn only_releases_leases_held_by_the_runner() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let now = Utc::now();
+        let expiration_date = now + Duration::minutes(5);
+        let mut transaction = database.transaction().await.unwrap();
+        transaction
+            .lease_tests(&["Blob", "Other"], "runner-1", expiration_date)
+            .await
+            .unwrap();
+        transaction
+            .lease_tests(&["Third"], "runner-2", expiration_date)
+            .await
+            .unwrap();
+        let names = transaction
+            .release_test_leases("runner-1", &RoswaalTestNamesString::new("blob\nthird"))
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["blob".to_string()]);
+        let names = transaction
+            .release_test_leases("runner-2", &RoswaalTestNamesString::new(""))
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["third".to_string()]);
+        let names = transaction.leased_test_names(now).await.unwrap();
+        assert_eq!(names, vec!["other".to_string()])
+    }
+}
@@ -1,3 +1,7 @@
+pub mod archive;
+pub mod estimate;
+pub mod flakiness;
+pub mod lease;
 pub mod ordinal;
 pub mod progress;
 pub mod query;
@@ -1,11 +1,17 @@
-use serde::Deserialize;
-use sqlx::{prelude::Type, sqlite::SqliteTypeInfo, Decode, Encode, Sqlite};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    postgres::PgTypeInfo, prelude::Type, sqlite::SqliteTypeInfo, Decode, Encode, Postgres, Sqlite,
+};
+
+use crate::language::test::RoswaalCompiledTestCommand;
 
 /// An ordinal that represents the index of a step in a test case.
 ///
 /// Each test case has a before launch step which gets the special zero ordinal that can be
 /// obtained through the `for_before_launch` constructor.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Encode, Decode, PartialOrd, Ord)]
+#[derive(
+    Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Encode, Decode, PartialOrd, Ord,
+)]
 pub struct RoswaalTestCommandOrdinal(i32);
 
 impl RoswaalTestCommandOrdinal {
@@ -28,3 +34,154 @@ impl Type<Sqlite> for RoswaalTestCommandOrdinal {
         <i32 as Type<Sqlite>>::type_info()
     }
 }
+
+impl Type<Postgres> for RoswaalTestCommandOrdinal {
+    fn type_info() -> PgTypeInfo {
+        <i32 as Type<Postgres>>::type_info()
+    }
+}
+
+/// Maps the ordinals of the commands in a previous version of a test to the ordinals of the same
+/// commands in its current version.
+///
+/// Commands are matched regardless of their labels, so a step keeps its progress when the steps
+/// around it are reordered. A command that appears more than once is matched to its occurrences in
+/// order, and the before launch ordinal always maps to itself.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalTestCommandOrdinalRemapping {
+    ordinals: Vec<(RoswaalTestCommandOrdinal, RoswaalTestCommandOrdinal)>,
+}
+
+impl RoswaalTestCommandOrdinalRemapping {
+    pub fn new(
+        previous_commands: &[(RoswaalTestCommandOrdinal, &RoswaalCompiledTestCommand)],
+        current_commands: &[(RoswaalTestCommandOrdinal, &RoswaalCompiledTestCommand)],
+    ) -> Self {
+        let before_launch = RoswaalTestCommandOrdinal::for_before_launch();
+        let mut ordinals = vec![(before_launch, before_launch)];
+        let mut unmatched_commands = current_commands
+            .iter()
+            .filter(|(ordinal, _)| *ordinal != before_launch)
+            .collect::<Vec<_>>();
+        for (previous_ordinal, previous_command) in previous_commands {
+            if *previous_ordinal == before_launch {
+                continue;
+            }
+            let index = unmatched_commands
+                .iter()
+                .position(|(_, command)| command.is_same_command_as(previous_command));
+            if let Some(index) = index {
+                let (current_ordinal, _) = unmatched_commands.remove(index);
+                ordinals.push((*previous_ordinal, *current_ordinal));
+            }
+        }
+        Self { ordinals }
+    }
+
+    /// Returns the ordinal of the command in the current version of the test that matches the
+    /// command at `ordinal` in its previous version, if the command still exists.
+    pub fn remapped(
+        &self,
+        ordinal: RoswaalTestCommandOrdinal,
+    ) -> Option<RoswaalTestCommandOrdinal> {
+        self.ordinals
+            .iter()
+            .find(|(previous_ordinal, _)| *previous_ordinal == ordinal)
+            .map(|(_, current_ordinal)| *current_ordinal)
+    }
+
+    /// Returns each previous ordinal paired with its remapped ordinal.
+    pub fn ordinals(&self) -> &[(RoswaalTestCommandOrdinal, RoswaalTestCommandOrdinal)] {
+        &self.ordinals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaps_reordered_steps_regardless_of_labels() {
+        let previous = vec![
+            step("Step 1", "A"),
+            step("Step 2", "B"),
+            RoswaalCompiledTestCommand::BeforeLaunch {
+                name: "Sign in".to_string(),
+            },
+            step("Step 3", "C"),
+        ];
+        let current = vec![
+            step("Step 1", "C"),
+            step("Step 2", "A"),
+            step("Step 3", "D"),
+        ];
+        let remapping = RoswaalTestCommandOrdinalRemapping::new(
+            &ordinal_commands(&previous),
+            &ordinal_commands(&current),
+        );
+        let before_launch = RoswaalTestCommandOrdinal::for_before_launch();
+        assert_eq!(remapping.remapped(before_launch), Some(before_launch));
+        assert_eq!(
+            remapping.remapped(RoswaalTestCommandOrdinal::new(0)),
+            Some(RoswaalTestCommandOrdinal::new(1))
+        );
+        assert_eq!(remapping.remapped(RoswaalTestCommandOrdinal::new(1)), None);
+        assert_eq!(
+            remapping.remapped(RoswaalTestCommandOrdinal::new(2)),
+            Some(RoswaalTestCommandOrdinal::new(0))
+        );
+    }
+
+    #[test]
+    fn matches_repeated_commands_in_order() {
+        let previous = vec![
+            step("Step 1", "A"),
+            step("Step 2", "A"),
+            step("Step 3", "B"),
+        ];
+        let current = vec![
+            step("Step 1", "B"),
+            step("Step 2", "A"),
+            step("Step 3", "A"),
+        ];
+        let remapping = RoswaalTestCommandOrdinalRemapping::new(
+            &ordinal_commands(&previous),
+            &ordinal_commands(&current),
+        );
+        let expected_ordinals = [(0, 1), (1, 2), (2, 0)]
+            .map(|(previous, current)| {
+                (
+                    RoswaalTestCommandOrdinal::new(previous),
+                    RoswaalTestCommandOrdinal::new(current),
+                )
+            })
+            .to_vec();
+        assert_eq!(remapping.ordinals()[1..].to_vec(), expected_ordinals)
+    }
+
+    fn step(label: &str, name: &str) -> RoswaalCompiledTestCommand {
+        RoswaalCompiledTestCommand::Step {
+            label: label.to_string(),
+            name: name.to_string(),
+            requirement: format!("Requirement {}", name),
+            retries: None,
+        }
+    }
+
+    fn ordinal_commands(
+        commands: &[RoswaalCompiledTestCommand],
+    ) -> Vec<(RoswaalTestCommandOrdinal, &RoswaalCompiledTestCommand)> {
+        let mut index = 0;
+        commands
+            .iter()
+            .map(|command| {
+                if command.is_before_launch() {
+                    return (RoswaalTestCommandOrdinal::for_before_launch(), command);
+                }
+                let ordinal = RoswaalTestCommandOrdinal::new(index);
+                index += 1;
+                (ordinal, command)
+            })
+            .collect()
+    }
+}
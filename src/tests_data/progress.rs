@@ -1,4 +1,6 @@
-use serde::Deserialize;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
 
 use super::ordinal::RoswaalTestCommandOrdinal;
 
@@ -12,6 +14,10 @@ pub struct RoswaalTestProgressUpload {
     test_name: String,
     command_failure_ordinal: Option<RoswaalTestCommandOrdinal>,
     error: Option<RoswaalTestProgressUploadErrorDescription>,
+    #[serde(default)]
+    screenshots: Vec<RoswaalTestStepScreenshot>,
+    #[serde(default)]
+    durations: Vec<RoswaalTestStepDuration>,
 }
 
 impl RoswaalTestProgressUpload {
@@ -24,8 +30,22 @@ impl RoswaalTestProgressUpload {
             test_name,
             command_failure_ordinal,
             error,
+            screenshots: vec![],
+            durations: vec![],
         }
     }
+
+    /// Returns this upload with the specified screenshots taken during the test run.
+    pub fn with_screenshots(mut self, screenshots: Vec<RoswaalTestStepScreenshot>) -> Self {
+        self.screenshots = screenshots;
+        self
+    }
+
+    /// Returns this upload with the specified time spent running each command.
+    pub fn with_durations(mut self, durations: Vec<RoswaalTestStepDuration>) -> Self {
+        self.durations = durations;
+        self
+    }
 }
 
 impl RoswaalTestProgressUpload {
@@ -44,6 +64,14 @@ impl RoswaalTestProgressUpload {
     pub fn error_stack_trace(&self) -> Option<&String> {
         self.error.as_ref().map(|e| &e.stack_trace)
     }
+
+    pub fn screenshots(&self) -> &[RoswaalTestStepScreenshot] {
+        &self.screenshots
+    }
+
+    pub fn durations(&self) -> &[RoswaalTestStepDuration] {
+        &self.durations
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Clone)]
@@ -61,3 +89,112 @@ impl RoswaalTestProgressUploadErrorDescription {
         }
     }
 }
+
+/// What happened to a single entry of a progress upload when it was saved.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum RoswaalTestProgressUploadOutcome {
+    /// The progress of the merged test was updated.
+    Updated,
+    /// No test with the name exists.
+    UnknownTest,
+    /// The test only exists on an unmerged branch, so its progress was not saved.
+    UnmergedOnly,
+}
+
+/// The outcome of saving the progress of the test with `test_name`.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoswaalTestProgressUploadResult {
+    test_name: String,
+    outcome: RoswaalTestProgressUploadOutcome,
+}
+
+impl RoswaalTestProgressUploadResult {
+    pub fn new(test_name: String, outcome: RoswaalTestProgressUploadOutcome) -> Self {
+        Self { test_name, outcome }
+    }
+}
+
+impl RoswaalTestProgressUploadResult {
+    pub fn test_name(&self) -> &str {
+        &self.test_name
+    }
+
+    pub fn outcome(&self) -> RoswaalTestProgressUploadOutcome {
+        self.outcome
+    }
+}
+
+/// A screenshot that the test runner took after running a command in a test.
+///
+/// The screenshot is either uploaded as a url to an image hosted elsewhere, or as a base64 encoded
+/// image. Only screenshots with a url can be linked to from slack.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoswaalTestStepScreenshot {
+    command_ordinal: RoswaalTestCommandOrdinal,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base64_data: Option<String>,
+}
+
+impl RoswaalTestStepScreenshot {
+    pub fn new(
+        command_ordinal: RoswaalTestCommandOrdinal,
+        url: Option<String>,
+        base64_data: Option<String>,
+    ) -> Self {
+        Self {
+            command_ordinal,
+            url,
+            base64_data,
+        }
+    }
+}
+
+impl RoswaalTestStepScreenshot {
+    pub fn command_ordinal(&self) -> RoswaalTestCommandOrdinal {
+        self.command_ordinal
+    }
+
+    pub fn url(&self) -> Option<&String> {
+        self.url.as_ref()
+    }
+
+    pub fn base64_data(&self) -> Option<&String> {
+        self.base64_data.as_ref()
+    }
+}
+
+/// The amount of time that the test runner spent running a command in a test.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct RoswaalTestStepDuration {
+    command_ordinal: RoswaalTestCommandOrdinal,
+    milliseconds: u32,
+}
+
+impl RoswaalTestStepDuration {
+    pub fn new(command_ordinal: RoswaalTestCommandOrdinal, milliseconds: u32) -> Self {
+        Self {
+            command_ordinal,
+            milliseconds,
+        }
+    }
+}
+
+impl RoswaalTestStepDuration {
+    pub fn command_ordinal(&self) -> RoswaalTestCommandOrdinal {
+        self.command_ordinal
+    }
+
+    pub fn milliseconds(&self) -> u32 {
+        self.milliseconds
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.milliseconds as u64)
+    }
+}
@@ -1,23 +1,59 @@
+use crate::slack::users::slack_user_id_from_mention;
+
 /// A type for representing a user entered query for a list of test names.
 ///
 /// Users will enter test names with each test name being on a separate line. An empty string
-/// indicates that *all* tests should be covered by this query.
+/// indicates that *all* tests should be covered by this query, a string of the form
+/// `tag:<tag>` covers all tests labeled with the tag, a string of the form `suite:<suite>` covers
+/// all tests in the suite, and a string of the form
+/// `contains:<phrase>` covers all tests whose name, description, steps, or requirements contain
+/// the phrase. A string of the form `by:<@user>` covers all tests added by the mentioned slack
+/// user.
 #[derive(Debug, PartialEq, Eq)]
 pub enum RoswaalSearchTestsQuery<'a> {
     TestNames(RoswaalTestNamesString<'a>),
+    Tag(&'a str),
+    Suite(&'a str),
+    Contains(&'a str),
+    /// The tests added by the slack user with the specified id.
+    AddedBy(String),
     AllTests,
 }
 
+const TAG_QUERY_PREFIX: &str = "tag:";
+const SUITE_QUERY_PREFIX: &str = "suite:";
+const CONTAINS_QUERY_PREFIX: &str = "contains:";
+const ADDED_BY_QUERY_PREFIX: &str = "by:";
+
 impl<'a> RoswaalSearchTestsQuery<'a> {
     pub fn new(string: &'a str) -> Self {
+        let trimmed = string.trim();
         if string.is_empty() {
             Self::AllTests
+        } else if let Some(tag) = prefixed_value(trimmed, TAG_QUERY_PREFIX) {
+            Self::Tag(tag)
+        } else if let Some(suite) = prefixed_value(trimmed, SUITE_QUERY_PREFIX) {
+            Self::Suite(suite)
+        } else if let Some(phrase) = prefixed_value(trimmed, CONTAINS_QUERY_PREFIX) {
+            Self::Contains(phrase)
+        } else if let Some(user_id) =
+            prefixed_value(trimmed, ADDED_BY_QUERY_PREFIX).and_then(slack_user_id_from_mention)
+        {
+            Self::AddedBy(user_id)
         } else {
             Self::TestNames(RoswaalTestNamesString::new(string))
         }
     }
 }
 
+fn prefixed_value<'a>(string: &'a str, prefix: &str) -> Option<&'a str> {
+    string
+        .get(..prefix.len())
+        .filter(|p| p.eq_ignore_ascii_case(prefix))
+        .map(|_| string[prefix.len()..].trim())
+        .filter(|value| !value.is_empty())
+}
+
 /// A list of stringified test names.
 #[derive(Debug, PartialEq, Eq)]
 pub struct RoswaalTestNamesString<'a>(&'a str);
@@ -46,6 +82,17 @@ impl<'a> RoswaalTestNamesString<'a> {
     }
 }
 
+/// The order in which the tests covered by a `RoswaalSearchTestsQuery` are returned.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum RoswaalTestsOrdering {
+    /// Orders tests by name, or by relevance for `contains:` queries.
+    #[default]
+    Alphabetical,
+    /// Orders tests from the highest to the lowest flakiness score, with tests that have the
+    /// same score kept in alphabetical order.
+    MostFlaky,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +127,80 @@ The 5 Hounds are too OP Plz Nerf
         assert_eq!(test_names, expected_names)
     }
 
+    #[test]
+    fn tag_prefix_denotes_tag_query() {
+        let strings = vec![
+            ("tag:smoke", "smoke"),
+            ("  TAG: Events \n", "Events"),
+            ("Tag:onboarding", "onboarding"),
+        ];
+        for (string, tag) in strings {
+            assert_eq!(
+                RoswaalSearchTestsQuery::new(string),
+                RoswaalSearchTestsQuery::Tag(tag)
+            )
+        }
+    }
+
+    #[test]
+    fn suite_prefix_denotes_suite_query() {
+        let strings = vec![
+            ("suite:Events", "Events"),
+            ("  SUITE: Event Details \n", "Event Details"),
+        ];
+        for (string, suite) in strings {
+            assert_eq!(
+                RoswaalSearchTestsQuery::new(string),
+                RoswaalSearchTestsQuery::Suite(suite)
+            )
+        }
+    }
+
+    #[test]
+    fn contains_prefix_denotes_contains_query() {
+        let strings = vec![
+            ("contains:sign in", "sign in"),
+            ("  CONTAINS: Join the event \n", "Join the event"),
+        ];
+        for (string, phrase) in strings {
+            assert_eq!(
+                RoswaalSearchTestsQuery::new(string),
+                RoswaalSearchTestsQuery::Contains(phrase)
+            )
+        }
+        assert_eq!(
+            RoswaalSearchTestsQuery::new("contains:"),
+            RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new("contains:"))
+        )
+    }
+
+    #[test]
+    fn by_prefix_denotes_added_by_query() {
+        let strings = vec![
+            ("by:<@U01234567>", "U01234567"),
+            ("  BY: <@U01234567|matthew> \n", "U01234567"),
+            ("by:U01234567", "U01234567"),
+        ];
+        for (string, user_id) in strings {
+            assert_eq!(
+                RoswaalSearchTestsQuery::new(string),
+                RoswaalSearchTestsQuery::AddedBy(user_id.to_string())
+            )
+        }
+        assert_eq!(
+            RoswaalSearchTestsQuery::new("by:matthew"),
+            RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new("by:matthew"))
+        )
+    }
+
+    #[test]
+    fn empty_tag_denotes_test_names() {
+        assert_eq!(
+            RoswaalSearchTestsQuery::new("tag:  "),
+            RoswaalSearchTestsQuery::TestNames(RoswaalTestNamesString::new("tag:  "))
+        )
+    }
+
     #[test]
     fn is_empty() {
         let strings = vec![("", true), ("    ", true), ("\n\n  \n", true), ("h", false)];
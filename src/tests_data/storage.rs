@@ -2,26 +2,59 @@ use std::iter::zip;
 
 use crate::{
     git::branch_name::RoswaalOwnedGitBranchName,
-    language::test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
-    utils::sqlite::{sqlite_repeat, RoswaalSqliteTransaction},
+    language::{
+        dependencies::RoswaalTestDependencyGraph,
+        platform::RoswaalTestPlatform,
+        test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+    },
+    utils::db::{
+        query::{query, query_as, query_scalar, RoswaalTimestamp},
+        repeat, RoswaalDatabaseTransaction,
+    },
 };
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::{query, query_as, FromRow, Sqlite};
+use sqlx::FromRow;
 
 use super::{
-    ordinal::RoswaalTestCommandOrdinal,
-    progress::RoswaalTestProgressUpload,
-    query::{RoswaalSearchTestsQuery, RoswaalTestNamesString},
+    flakiness::{RoswaalTestFlakiness, FLAKINESS_RUN_WINDOW},
+    ordinal::{RoswaalTestCommandOrdinal, RoswaalTestCommandOrdinalRemapping},
+    progress::{
+        RoswaalTestProgressUpload, RoswaalTestProgressUploadOutcome,
+        RoswaalTestProgressUploadResult, RoswaalTestStepDuration, RoswaalTestStepScreenshot,
+    },
+    query::{RoswaalSearchTestsQuery, RoswaalTestNamesString, RoswaalTestsOrdering},
     test::RoswaalTest,
 };
 
-impl<'a> RoswaalSqliteTransaction<'a> {
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Saves the progress of each merged test in `progress`, and returns the outcome of each entry
+    /// in the same order.
+    ///
+    /// Entries for tests that do not exist, or that only exist on an unmerged branch, are skipped.
     pub async fn save_test_progess(
         &mut self,
         progress: &Vec<RoswaalTestProgressUpload>,
-    ) -> Result<()> {
-        sqlite_repeat(statements::UPDATE_TEST_PROGRESS, progress)
+    ) -> Result<Vec<RoswaalTestProgressUploadResult>> {
+        let mut results = Vec::<RoswaalTestProgressUploadResult>::new();
+        for upload in progress.iter() {
+            let merge_states = query_scalar::<bool>(statements::SELECT_TEST_MERGE_STATES)
+                .bind(upload.test_name())
+                .fetch_all(self.connection())
+                .await?;
+            let outcome = if merge_states.iter().any(|is_merged| *is_merged) {
+                RoswaalTestProgressUploadOutcome::Updated
+            } else if merge_states.is_empty() {
+                RoswaalTestProgressUploadOutcome::UnknownTest
+            } else {
+                RoswaalTestProgressUploadOutcome::UnmergedOnly
+            };
+            results.push(RoswaalTestProgressUploadResult::new(
+                upload.test_name().to_string(),
+                outcome,
+            ))
+        }
+        repeat(statements::UPDATE_TEST_PROGRESS, progress)
             .bind_to_query(|q, progress| {
                 Ok(q.bind(progress.command_failure_ordinal())
                     .bind(progress.error_message())
@@ -30,14 +63,74 @@ impl<'a> RoswaalSqliteTransaction<'a> {
             })?
             .execute(self.connection())
             .await?;
-        Ok(())
+        let passing_test_names = progress
+            .iter()
+            .filter(|p| p.command_failure_ordinal().is_none())
+            .map(|p| p.test_name())
+            .collect::<Vec<&str>>();
+        if !passing_test_names.is_empty() {
+            repeat(
+                statements::DELETE_TEST_FAILURE_ASSIGNMENT,
+                &passing_test_names,
+            )
+            .bind_to_query(|q, test_name| Ok(q.bind(*test_name)))?
+            .execute(self.connection())
+            .await?;
+        }
+        repeat(statements::DELETE_TEST_STEP_ARTIFACTS, progress)
+            .bind_to_query(|q, progress| Ok(q.bind(progress.test_name())))?
+            .execute(self.connection())
+            .await?;
+        let screenshots = progress
+            .iter()
+            .flat_map(|p| p.screenshots().iter().map(|s| (p.test_name(), s)))
+            .collect::<Vec<(&str, &RoswaalTestStepScreenshot)>>();
+        if !screenshots.is_empty() {
+            repeat(statements::INSERT_TEST_STEP_ARTIFACT, &screenshots)
+                .bind_to_query(|q, (test_name, screenshot)| {
+                    Ok(q.bind(screenshot.command_ordinal())
+                        .bind(screenshot.url())
+                        .bind(screenshot.base64_data())
+                        .bind(*test_name))
+                })?
+                .execute(self.connection())
+                .await?;
+        }
+        repeat(statements::DELETE_TEST_STEP_DURATIONS, progress)
+            .bind_to_query(|q, progress| Ok(q.bind(progress.test_name())))?
+            .execute(self.connection())
+            .await?;
+        let durations = progress
+            .iter()
+            .flat_map(|p| p.durations().iter().map(|d| (p.test_name(), d)))
+            .collect::<Vec<(&str, &RoswaalTestStepDuration)>>();
+        if !durations.is_empty() {
+            repeat(statements::INSERT_TEST_STEP_DURATION, &durations)
+                .bind_to_query(|q, (test_name, duration)| {
+                    Ok(q.bind(duration.command_ordinal())
+                        .bind(i64::from(duration.milliseconds()))
+                        .bind(*test_name))
+                })?
+                .execute(self.connection())
+                .await?;
+        }
+        repeat(statements::INSERT_TEST_RUN_RESULT, progress)
+            .bind_to_query(|q, progress| {
+                Ok(q.bind(progress.command_failure_ordinal().is_some())
+                    .bind(progress.test_name())
+                    .bind(progress.test_name())
+                    .bind(FLAKINESS_RUN_WINDOW as i64))
+            })?
+            .execute(self.connection())
+            .await?;
+        Ok(results)
     }
 
     pub async fn close_remove_tests_branch(
         &mut self,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        query::<Sqlite>(statements::DELETE_STAGED_TEST_REMOVALS_WITH_BRANCH)
+        query(statements::DELETE_STAGED_TEST_REMOVALS_WITH_BRANCH)
             .bind(branch_name)
             .execute(self.connection())
             .await?;
@@ -48,7 +141,7 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         &mut self,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        query::<Sqlite>(statements::DELETE_UNMERGED_TESTS_WITH_BRANCH)
+        query(statements::DELETE_UNMERGED_TESTS_WITH_BRANCH)
             .bind(branch_name)
             .execute(self.connection())
             .await?;
@@ -63,7 +156,7 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         if test_names.is_empty() {
             return Ok(());
         }
-        sqlite_repeat(
+        repeat(
             statements::INSERT_STAGED_TEST_REMOVAL,
             &test_names.iter().collect(),
         )
@@ -77,13 +170,26 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         &mut self,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        let test_names =
-            query_as::<Sqlite, SqliteTestName>(statements::SELECT_STAGED_TEST_REMOVAL_NAMES)
-                .bind(branch_name)
-                .fetch_all(self.connection())
-                .await?;
+        let test_names = query_as::<SqliteTestName>(statements::SELECT_STAGED_TEST_REMOVAL_NAMES)
+            .bind(branch_name)
+            .fetch_all(self.connection())
+            .await?;
+        let names_str = test_names
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let removed_tests = self
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::TestNames(
+                RoswaalTestNamesString::new(&names_str),
+            ))
+            .await?
+            .into_iter()
+            .filter(|t| t.unmerged_branch_name().is_none())
+            .collect::<Vec<RoswaalTest>>();
+        self.archive_tests(&removed_tests, branch_name).await?;
         let delete_tests_statement = statements::delete_tests(test_names.iter().count());
-        let mut delete_query = query::<Sqlite>(&delete_tests_statement);
+        let mut delete_query = query(&delete_tests_statement);
         for sqlite_name in test_names.iter() {
             delete_query = delete_query.bind(&sqlite_name.name);
         }
@@ -92,16 +198,24 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         Ok(())
     }
 
+    /// Merges the tests that were added on the specified branch, and returns their names.
     pub async fn merge_unmerged_tests(
         &mut self,
         branch_name: &RoswaalOwnedGitBranchName,
-    ) -> Result<()> {
+    ) -> Result<Vec<String>> {
         let sqlite_location_names =
-            query_as::<Sqlite, SqliteTestName>(statements::SELECT_UNMERGED_TEST_NAMES)
+            query_as::<SqliteTestName>(statements::SELECT_UNMERGED_TEST_NAMES)
                 .bind(branch_name)
                 .fetch_all(self.connection())
                 .await?;
-        sqlite_repeat(statements::MERGE_UNMERGED_TESTS, &sqlite_location_names)
+        let test_names = sqlite_location_names
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect::<Vec<&str>>();
+        // NB: The merged tests may have run since their unmerged versions were saved.
+        self.migrate_merged_test_progress(&test_names, branch_name)
+            .await?;
+        repeat(statements::MERGE_UNMERGED_TESTS, &sqlite_location_names)
             .bind_to_query(|q, sqlite_name| {
                 Ok(q.bind(sqlite_name.name.clone())
                     .bind(branch_name)
@@ -109,68 +223,403 @@ impl<'a> RoswaalSqliteTransaction<'a> {
             })?
             .execute(self.connection())
             .await?;
+        Ok(sqlite_location_names.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Returns the names of all tests that have been merged.
+    pub async fn merged_test_names(&mut self) -> Result<Vec<String>> {
+        let sqlite_names = query_as::<SqliteTestName>(statements::SELECT_MERGED_TEST_NAMES)
+            .fetch_all(self.connection())
+            .await?;
+        Ok(sqlite_names.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Marks each merged test named in `test_names` as deprecated on `deprecation_date`, and
+    /// returns the names of the tests that were not already deprecated.
+    pub async fn deprecate_tests(
+        &mut self,
+        test_names: &RoswaalTestNamesString<'_>,
+        deprecation_date: DateTime<Utc>,
+    ) -> Result<Vec<String>> {
+        if test_names.is_empty() {
+            return Ok(vec![]);
+        }
+        let statement = statements::deprecate_tests(test_names.iter().count());
+        let mut deprecate_query =
+            query_as::<SqliteTestName>(&statement).bind(RoswaalTimestamp(deprecation_date));
+        for name in test_names.iter() {
+            deprecate_query = deprecate_query.bind(name.to_lowercase());
+        }
+        let mut names = deprecate_query
+            .fetch_all(self.connection())
+            .await?
+            .into_iter()
+            .map(|n| n.name)
+            .collect::<Vec<String>>();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Clears the failing command, error, last run date, screenshots, and durations of each merged
+    /// test named in `test_names`, or of every merged test if `test_names` is empty, and returns the
+    /// names of the tests that were reset.
+    ///
+    /// The run history used to measure flakiness is kept.
+    pub async fn reset_test_progress(
+        &mut self,
+        test_names: &RoswaalTestNamesString<'_>,
+    ) -> Result<Vec<String>> {
+        let names = test_names
+            .iter()
+            .map(|name| name.to_lowercase())
+            .collect::<Vec<String>>();
+        let filter = statements::merged_tests_filter(names.len());
+        for table in ["TestStepArtifacts", "TestStepDurations"] {
+            let statement = statements::delete_test_step_rows(table, &filter);
+            let mut delete_query = query(&statement);
+            for name in names.iter() {
+                delete_query = delete_query.bind(name);
+            }
+            delete_query.execute(self.connection()).await?;
+        }
+        let statement = statements::reset_test_progress(&filter);
+        let mut reset_query = query_as::<SqliteTestName>(&statement);
+        for name in names.iter() {
+            reset_query = reset_query.bind(name);
+        }
+        let mut names = reset_query
+            .fetch_all(self.connection())
+            .await?
+            .into_iter()
+            .map(|n| n.name)
+            .collect::<Vec<String>>();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Returns the names of the merged tests that were deprecated on or before `cutoff_date`, and
+    /// that do not already have a staged removal.
+    pub async fn expired_deprecated_test_names(
+        &mut self,
+        cutoff_date: DateTime<Utc>,
+    ) -> Result<Vec<String>> {
+        let sqlite_names =
+            query_as::<SqliteTestName>(statements::SELECT_EXPIRED_DEPRECATED_TEST_NAMES)
+                .bind(RoswaalTimestamp(cutoff_date))
+                .fetch_all(self.connection())
+                .await?;
+        Ok(sqlite_names.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Stops the failures of the test with the specified name from being posted to the test
+    /// failure channel.
+    pub async fn mute_test_failures(&mut self, test_name: &str) -> Result<()> {
+        query(statements::INSERT_MUTED_TEST_FAILURE)
+            .bind(test_name)
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    /// Resumes posting the failures of the test with the specified name to the test failure
+    /// channel.
+    pub async fn unmute_test_failures(&mut self, test_name: &str) -> Result<()> {
+        query(statements::DELETE_MUTED_TEST_FAILURE)
+            .bind(test_name)
+            .execute(self.connection())
+            .await?;
+        Ok(())
+    }
+
+    /// Assigns the slack user with `slack_user_id` to triage the failure of the test with the
+    /// specified name, replacing any existing assignee.
+    ///
+    /// The assignment is cleared when a passing run of the test is saved.
+    pub async fn assign_test_failure(
+        &mut self,
+        test_name: &str,
+        slack_user_id: &str,
+    ) -> Result<()> {
+        query(statements::UPSERT_TEST_FAILURE_ASSIGNMENT)
+            .bind(test_name)
+            .bind(slack_user_id)
+            .execute(self.connection())
+            .await?;
         Ok(())
     }
 
+    /// Returns the lowercased names of the tests whose failures are muted.
+    pub async fn muted_test_failure_names(&mut self) -> Result<Vec<String>> {
+        let sqlite_names = query_as::<SqliteTestName>(statements::SELECT_MUTED_TEST_FAILURE_NAMES)
+            .fetch_all(self.connection())
+            .await?;
+        Ok(sqlite_names.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Returns the merged tests that can be required by other tests.
+    pub async fn merged_test_dependency_graph(&mut self) -> Result<RoswaalTestDependencyGraph> {
+        let rows = query_as::<SqliteTestCommandRow>(statements::SELECT_MERGED_TEST_COMMANDS)
+            .fetch_all(self.connection())
+            .await?;
+        let mut tests = Vec::<(String, Vec<RoswaalCompiledTestCommand>)>::new();
+        for row in rows {
+            let command = serde_json::from_str::<RoswaalCompiledTestCommand>(&row.command_content)?;
+            match tests.last_mut() {
+                Some((name, commands)) if *name == row.test_name => commands.push(command),
+                _ => tests.push((row.test_name, vec![command])),
+            }
+        }
+        Ok(RoswaalTestDependencyGraph::new(tests))
+    }
+
     pub async fn save_tests(
         &mut self,
         tests: &Vec<RoswaalCompiledTest>,
         branch_name: &RoswaalOwnedGitBranchName,
     ) -> Result<()> {
-        let mut tests = tests.clone();
+        self.save_tests_added_by(tests, branch_name, None).await
+    }
+
+    /// Saves `tests` on the specified branch, and attributes them to the slack user with
+    /// `added_by_slack_user_id`.
+    ///
+    /// A test that replaces a merged test keeps the user that the merged test is attributed to,
+    /// so that editing or overwriting a test does not change who added it.
+    pub async fn save_tests_added_by(
+        &mut self,
+        tests: &[RoswaalCompiledTest],
+        branch_name: &RoswaalOwnedGitBranchName,
+        added_by_slack_user_id: Option<&str>,
+    ) -> Result<()> {
+        let mut tests = tests.to_vec();
         tests.reverse(); // NB: Ensure the last occurrence of each test is kept when dedupping.
         tests.dedup_by(|a, b| a.name() == b.name());
-        let id_rows = sqlite_repeat(statements::INSERT_TEST_RETURNING_ID, &tests)
+        let id_rows = repeat(statements::INSERT_TEST_RETURNING_ID, &tests)
             .bind_to_query_as::<SqliteTestID>(|q, test| {
                 Ok(q.bind(test.name())
+                    .bind(branch_name)
+                    .bind(test.name())
                     .bind(test.description())
-                    .bind(branch_name))
+                    .bind(branch_name)
+                    .bind(test.is_planned())
+                    .bind(sqlite_platforms(test.platforms()))
+                    .bind(serde_json::to_string(test.variables())?)
+                    .bind(test.source_code())
+                    .bind(test.name())
+                    .bind(added_by_slack_user_id))
             })?
             .fetch_all(self.connection())
             .await?;
-        sqlite_repeat(
-            statements::INSERT_TEST_STEP,
-            &(0..tests.iter().flat_map(|t| t.commands()).count()).collect(),
-        )
-        .bind_custom_values_to_query(
-            zip(tests.iter(), id_rows.iter()),
-            |mut q, (test, id_row)| {
-                for (raw_ordinal, command) in test.commands().iter().enumerate() {
-                    let ordinal = RoswaalTestCommandOrdinal::new(raw_ordinal as i32);
-                    q = q
-                        .bind(id_row.id)
-                        .bind(serde_json::to_string(&command)?)
-                        .bind(ordinal)
-                }
-                Ok(q)
-            },
-        )?
-        .execute(self.connection())
-        .await?;
+        let command_count = tests.iter().flat_map(|t| t.commands()).count();
+        // NB: Planned tests have no commands, so a batch of only planned tests has no steps.
+        if command_count > 0 {
+            repeat(statements::INSERT_TEST_STEP, &(0..command_count).collect())
+                .bind_custom_values_to_query(
+                    zip(tests.iter(), id_rows.iter()),
+                    |mut q, (test, id_row)| {
+                        for (raw_ordinal, command) in test.commands().iter().enumerate() {
+                            let ordinal = RoswaalTestCommandOrdinal::new(raw_ordinal as i32);
+                            q = q
+                                .bind(id_row.id)
+                                .bind(serde_json::to_string(&command)?)
+                                .bind(ordinal)
+                        }
+                        Ok(q)
+                    },
+                )?
+                .execute(self.connection())
+                .await?;
+        }
+        let tags = zip(tests.iter(), id_rows.iter())
+            .flat_map(|(test, id_row)| test.tags().iter().map(|tag| (id_row.id, tag)))
+            .collect::<Vec<(i32, &String)>>();
+        if !tags.is_empty() {
+            repeat(statements::INSERT_TEST_TAG, &tags)
+                .bind_to_query(|q, (id, tag)| Ok(q.bind(*id).bind(*tag)))?
+                .execute(self.connection())
+                .await?;
+        }
+        let suites = zip(tests.iter(), id_rows.iter())
+            .filter_map(|(test, id_row)| test.suite().map(|suite| (id_row.id, suite)))
+            .collect::<Vec<(i32, &str)>>();
+        if !suites.is_empty() {
+            repeat(statements::INSERT_TEST_SUITE, &suites)
+                .bind_to_query(|q, (id, suite)| Ok(q.bind(*suite).bind(*id).bind(*suite)))?
+                .execute(self.connection())
+                .await?;
+        }
+        let test_names = tests.iter().map(|t| t.name()).collect::<Vec<&str>>();
+        self.migrate_merged_test_progress(&test_names, branch_name)
+            .await?;
+        self.index_unindexed_tests().await
+    }
+
+    /// Copies the progress of the merged version of each test onto its unmerged version on the
+    /// specified branch, so that the progress survives when the unmerged version is merged.
+    ///
+    /// The failure ordinal, screenshots, and durations are remapped to the ordinals of the same
+    /// commands in the unmerged version. If the command that failed no longer exists in the
+    /// unmerged version, the failure cannot be attributed to any of its commands, so the unmerged
+    /// version is left without progress instead.
+    async fn migrate_merged_test_progress(
+        &mut self,
+        test_names: &[&str],
+        branch_name: &RoswaalOwnedGitBranchName,
+    ) -> Result<()> {
+        if test_names.is_empty() {
+            return Ok(());
+        }
+        let names_str = test_names.join("\n");
+        let stored_tests = self
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::TestNames(
+                RoswaalTestNamesString::new(&names_str),
+            ))
+            .await?;
+        for name in test_names {
+            let merged_test = stored_tests
+                .iter()
+                .find(|t| t.name() == *name && t.unmerged_branch_name().is_none());
+            let unmerged_test = stored_tests
+                .iter()
+                .find(|t| t.name() == *name && t.unmerged_branch_name() == Some(branch_name));
+            let (Some(merged_test), Some(unmerged_test)) = (merged_test, unmerged_test) else {
+                continue;
+            };
+            let remapping = RoswaalTestCommandOrdinalRemapping::new(
+                &merged_test.ordinal_commands(),
+                &unmerged_test.ordinal_commands(),
+            );
+            for statement in [
+                statements::DELETE_UNMERGED_TEST_STEP_ARTIFACTS,
+                statements::DELETE_UNMERGED_TEST_STEP_DURATIONS,
+            ] {
+                query(statement)
+                    .bind(*name)
+                    .bind(branch_name)
+                    .execute(self.connection())
+                    .await?;
+            }
+            let failure_ordinal = match merged_test.command_failure_ordinal() {
+                Some(ordinal) => match remapping.remapped(ordinal) {
+                    Some(ordinal) => Some(ordinal),
+                    None => {
+                        query(statements::CLEAR_UNMERGED_TEST_PROGRESS)
+                            .bind(*name)
+                            .bind(branch_name)
+                            .execute(self.connection())
+                            .await?;
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            query(statements::MIGRATE_MERGED_TEST_PROGRESS)
+                .bind(failure_ordinal)
+                .bind(*name)
+                .bind(*name)
+                .bind(branch_name)
+                .execute(self.connection())
+                .await?;
+            let ordinals = remapping.ordinals().to_vec();
+            for statement in [
+                statements::MIGRATE_MERGED_TEST_STEP_ARTIFACT,
+                statements::MIGRATE_MERGED_TEST_STEP_DURATION,
+            ] {
+                repeat(statement, &ordinals)
+                    .bind_to_query(|q, (previous_ordinal, current_ordinal)| {
+                        Ok(q.bind(*current_ordinal)
+                            .bind(branch_name)
+                            .bind(*name)
+                            .bind(*previous_ordinal))
+                    })?
+                    .execute(self.connection())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the names of all suites that contain at least 1 merged test in alphabetical order.
+    pub async fn merged_suite_names(&mut self) -> Result<Vec<String>> {
+        let sqlite_names = query_as::<SqliteTestName>(statements::SELECT_MERGED_SUITE_NAMES)
+            .fetch_all(self.connection())
+            .await?;
+        Ok(sqlite_names.into_iter().map(|n| n.name).collect())
+    }
+
+    /// Adds the name, description, steps, and requirements of every test that has not yet been
+    /// indexed to the full-text search table.
+    async fn index_unindexed_tests(&mut self) -> Result<()> {
+        query(statements::INSERT_UNINDEXED_TESTS_SEARCH)
+            .execute(self.connection())
+            .await?;
         Ok(())
     }
 
     pub async fn tests_in_alphabetical_order(
         &mut self,
         query: &RoswaalSearchTestsQuery<'_>,
+    ) -> Result<Vec<RoswaalTest>> {
+        self.tests_in_order(query, RoswaalTestsOrdering::Alphabetical)
+            .await
+    }
+
+    /// Returns the tests covered by `query` in the specified order.
+    ///
+    /// Merged tests include the screenshots and durations of their most recent run, the outcomes
+    /// of their last `FLAKINESS_RUN_WINDOW` runs, and the slack user assigned to their failure.
+    pub async fn tests_in_order(
+        &mut self,
+        query: &RoswaalSearchTestsQuery<'_>,
+        ordering: RoswaalTestsOrdering,
     ) -> Result<Vec<RoswaalTest>> {
         let sqlite_tests = match query {
             RoswaalSearchTestsQuery::TestNames(test_names) => {
                 let query_str =
                     statements::select_tests_in_alphabetical_order(test_names.iter().count());
-                let mut select_query = query_as::<Sqlite, SqliteStoredTestRow>(&query_str);
+                let mut select_query = query_as::<SqliteStoredTestRow>(&query_str);
                 for name in test_names.iter() {
                     select_query = select_query.bind(name.to_lowercase());
                 }
                 select_query.fetch_all(self.connection()).await?
             }
-            RoswaalSearchTestsQuery::AllTests => {
-                query_as::<Sqlite, SqliteStoredTestRow>(
-                    statements::SELECT_ALL_TESTS_IN_ALPHABETICAL_ORDER,
+            RoswaalSearchTestsQuery::Tag(tag) => {
+                query_as::<SqliteStoredTestRow>(
+                    statements::SELECT_TESTS_WITH_TAG_IN_ALPHABETICAL_ORDER,
+                )
+                .bind(tag.to_lowercase())
+                .fetch_all(self.connection())
+                .await?
+            }
+            RoswaalSearchTestsQuery::Suite(suite) => {
+                query_as::<SqliteStoredTestRow>(
+                    statements::SELECT_TESTS_IN_SUITE_IN_ALPHABETICAL_ORDER,
                 )
+                .bind(suite)
                 .fetch_all(self.connection())
                 .await?
             }
+            RoswaalSearchTestsQuery::Contains(phrase) => {
+                // NB: Tests saved before the search table existed are only indexed here.
+                self.index_unindexed_tests().await?;
+                query_as::<SqliteStoredTestRow>(statements::SELECT_TESTS_MATCHING_SEARCH)
+                    .bind(format!("\"{}\"", phrase.replace('"', "\"\"")))
+                    .fetch_all(self.connection())
+                    .await?
+            }
+            RoswaalSearchTestsQuery::AddedBy(user_id) => {
+                query_as::<SqliteStoredTestRow>(
+                    statements::SELECT_TESTS_ADDED_BY_IN_ALPHABETICAL_ORDER,
+                )
+                .bind(user_id)
+                .fetch_all(self.connection())
+                .await?
+            }
+            RoswaalSearchTestsQuery::AllTests => {
+                query_as::<SqliteStoredTestRow>(statements::SELECT_ALL_TESTS_IN_ALPHABETICAL_ORDER)
+                    .fetch_all(self.connection())
+                    .await?
+            }
         };
         if sqlite_tests.is_empty() {
             return Ok(vec![]);
@@ -178,22 +627,90 @@ impl<'a> RoswaalSqliteTransaction<'a> {
         let mut test = RoswaalTest::from_sqlite_row(&sqlite_tests[0], vec![]);
         let mut tests = Vec::<RoswaalTest>::new();
         for sqlite_test in sqlite_tests {
-            let command =
-                serde_json::from_str::<RoswaalCompiledTestCommand>(&sqlite_test.command_content)?;
+            // NB: Planned tests have no steps, so their only row has no command.
+            let command = sqlite_test
+                .command_content
+                .as_deref()
+                .map(serde_json::from_str::<RoswaalCompiledTestCommand>)
+                .transpose()?;
             if sqlite_test.is_separate_from(&test) {
                 tests.push(test);
-                test = RoswaalTest::from_sqlite_row(&sqlite_test, vec![command]);
-            } else {
+                test = RoswaalTest::from_sqlite_row(&sqlite_test, command.into_iter().collect());
+            } else if let Some(command) = command {
                 test.push_compiled_command(command)
             };
         }
         tests.push(test);
+        let artifacts =
+            query_as::<SqliteTestStepArtifactRow>(statements::SELECT_MERGED_TEST_STEP_ARTIFACTS)
+                .fetch_all(self.connection())
+                .await?;
+        let durations =
+            query_as::<SqliteTestStepDurationRow>(statements::SELECT_MERGED_TEST_STEP_DURATIONS)
+                .fetch_all(self.connection())
+                .await?;
+        let run_results =
+            query_as::<SqliteTestRunResultRow>(statements::SELECT_MERGED_TEST_RUN_RESULTS)
+                .fetch_all(self.connection())
+                .await?;
+        let assignments =
+            query_as::<SqliteTestFailureAssignmentRow>(statements::SELECT_TEST_FAILURE_ASSIGNMENTS)
+                .fetch_all(self.connection())
+                .await?;
+        if artifacts.is_empty()
+            && durations.is_empty()
+            && run_results.is_empty()
+            && assignments.is_empty()
+        {
+            return Ok(tests);
+        }
+        let mut tests = tests
+            .into_iter()
+            .map(|test| {
+                if test.unmerged_branch_name().is_some() {
+                    return test;
+                }
+                let screenshots = artifacts
+                    .iter()
+                    .filter(|a| a.test_name == test.name())
+                    .map(|a| {
+                        RoswaalTestStepScreenshot::new(
+                            a.command_ordinal,
+                            a.url.clone(),
+                            a.base64_data.clone(),
+                        )
+                    })
+                    .collect();
+                let durations = durations
+                    .iter()
+                    .filter(|d| d.test_name == test.name())
+                    .map(|d| RoswaalTestStepDuration::new(d.command_ordinal, d.milliseconds as u32))
+                    .collect();
+                let failures = run_results
+                    .iter()
+                    .filter(|r| r.test_name == test.name())
+                    .map(|r| r.did_fail)
+                    .collect();
+                let lowercased_name = test.name().to_lowercase();
+                let assignee = assignments
+                    .iter()
+                    .find(|a| a.name == lowercased_name)
+                    .map(|a| a.slack_user_id.clone());
+                test.with_screenshots(screenshots)
+                    .with_durations(durations)
+                    .with_flakiness(RoswaalTestFlakiness::new(failures))
+                    .with_failure_assignee_slack_user_id(assignee)
+            })
+            .collect::<Vec<RoswaalTest>>();
+        if ordering == RoswaalTestsOrdering::MostFlaky {
+            tests.sort_by(|a, b| b.flakiness().score().total_cmp(&a.flakiness().score()));
+        }
         Ok(tests)
     }
 }
 
 mod statements {
-    use crate::utils::sqlite::sqlite_array_fields;
+    use crate::utils::db::{array_fields, query::RoswaalSql};
 
     pub const INSERT_STAGED_TEST_REMOVAL: &str = "
 INSERT INTO StagedTestRemovals (
@@ -214,10 +731,303 @@ SELECT
     t.error_message,
     t.error_stack_trace,
     t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
     c.content AS command_content
 FROM Tests t
-INNER JOIN TestSteps c ON t.id = c.test_id
-ORDER BY test_name, c.ordinal;
+LEFT JOIN TestSteps c ON t.id = c.test_id
+ORDER BY test_name, t.id, c.ordinal;
+";
+
+    pub const SELECT_TESTS_MATCHING_SEARCH: RoswaalSql = RoswaalSql::new(
+        "
+SELECT
+    t.name AS test_name,
+    t.description,
+    t.unmerged_branch_name,
+    t.command_failure_ordinal,
+    t.error_message,
+    t.error_stack_trace,
+    t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
+    c.content AS command_content
+FROM TestsSearch
+INNER JOIN Tests t ON t.id = TestsSearch.rowid
+LEFT JOIN TestSteps c ON t.id = c.test_id
+WHERE TestsSearch MATCH ?
+ORDER BY TestsSearch.rank, test_name, t.id, c.ordinal;
+",
+        "
+WITH search AS (SELECT phraseto_tsquery('simple', ?) AS phrase)
+SELECT
+    t.name AS test_name,
+    t.description,
+    t.unmerged_branch_name,
+    t.command_failure_ordinal,
+    t.error_message,
+    t.error_stack_trace,
+    t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
+    c.content AS command_content
+FROM TestsSearch
+CROSS JOIN search
+INNER JOIN Tests t ON t.id = TestsSearch.test_id
+LEFT JOIN TestSteps c ON t.id = c.test_id
+WHERE
+    to_tsvector('simple', COALESCE(TestsSearch.name, '')) @@ search.phrase
+    OR to_tsvector('simple', COALESCE(TestsSearch.description, '')) @@ search.phrase
+    OR to_tsvector('simple', COALESCE(TestsSearch.steps, '')) @@ search.phrase
+    OR to_tsvector('simple', COALESCE(TestsSearch.requirements, '')) @@ search.phrase
+ORDER BY
+    ts_rank(
+        to_tsvector(
+            'simple',
+            concat_ws(
+                ' ',
+                TestsSearch.name,
+                TestsSearch.description,
+                TestsSearch.steps,
+                TestsSearch.requirements
+            )
+        ),
+        search.phrase
+    ) DESC,
+    test_name,
+    t.id,
+    c.ordinal;
+",
+    );
+
+    pub const INSERT_UNINDEXED_TESTS_SEARCH: RoswaalSql = RoswaalSql::new(
+        "
+INSERT INTO TestsSearch (rowid, name, description, steps, requirements)
+SELECT
+    t.id,
+    t.name,
+    t.description,
+    (
+        SELECT group_concat(
+            COALESCE(json_extract(c.content, '$.Step.name'), json_extract(c.content, '$.Verify.name')),
+            ' '
+        )
+        FROM TestSteps c WHERE c.test_id = t.id
+    ),
+    (
+        SELECT group_concat(
+            COALESCE(
+                json_extract(c.content, '$.Step.requirement'),
+                json_extract(c.content, '$.Verify.requirement')
+            ),
+            ' '
+        )
+        FROM TestSteps c WHERE c.test_id = t.id
+    )
+FROM Tests t
+WHERE t.id NOT IN (SELECT rowid FROM TestsSearch);
+",
+        "
+INSERT INTO TestsSearch (test_id, name, description, steps, requirements)
+SELECT
+    t.id,
+    t.name,
+    t.description,
+    (
+        SELECT string_agg(
+            COALESCE(c.content::json #>> '{Step,name}', c.content::json #>> '{Verify,name}'),
+            ' '
+            ORDER BY c.ordinal
+        )
+        FROM TestSteps c WHERE c.test_id = t.id
+    ),
+    (
+        SELECT string_agg(
+            COALESCE(
+                c.content::json #>> '{Step,requirement}',
+                c.content::json #>> '{Verify,requirement}'
+            ),
+            ' '
+            ORDER BY c.ordinal
+        )
+        FROM TestSteps c WHERE c.test_id = t.id
+    )
+FROM Tests t
+WHERE t.id NOT IN (SELECT test_id FROM TestsSearch);
+",
+    );
+
+    pub const SELECT_TESTS_WITH_TAG_IN_ALPHABETICAL_ORDER: &str = "
+SELECT
+    t.name AS test_name,
+    t.description,
+    t.unmerged_branch_name,
+    t.command_failure_ordinal,
+    t.error_message,
+    t.error_stack_trace,
+    t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
+    c.content AS command_content
+FROM Tests t
+LEFT JOIN TestSteps c ON t.id = c.test_id
+WHERE t.id IN (SELECT test_id FROM TestTags WHERE tag = ?)
+ORDER BY test_name, t.id, c.ordinal;
+";
+
+    pub const SELECT_TESTS_IN_SUITE_IN_ALPHABETICAL_ORDER: &str = "
+SELECT
+    t.name AS test_name,
+    t.description,
+    t.unmerged_branch_name,
+    t.command_failure_ordinal,
+    t.error_message,
+    t.error_stack_trace,
+    t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
+    c.content AS command_content
+FROM Tests t
+LEFT JOIN TestSteps c ON t.id = c.test_id
+INNER JOIN TestSuites ts ON t.id = ts.test_id
+INNER JOIN Suites s ON s.id = ts.suite_id
+WHERE LOWER(s.name) = LOWER(?)
+ORDER BY test_name, t.id, c.ordinal;
+";
+
+    pub const SELECT_TESTS_ADDED_BY_IN_ALPHABETICAL_ORDER: &str = "
+SELECT
+    t.name AS test_name,
+    t.description,
+    t.unmerged_branch_name,
+    t.command_failure_ordinal,
+    t.error_message,
+    t.error_stack_trace,
+    t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
+    c.content AS command_content
+FROM Tests t
+LEFT JOIN TestSteps c ON t.id = c.test_id
+WHERE t.added_by_slack_user_id = ?
+ORDER BY test_name, t.id, c.ordinal;
+";
+
+    pub const SELECT_MERGED_SUITE_NAMES: &str = "
+SELECT name
+FROM Suites
+WHERE id IN (
+    SELECT ts.suite_id
+    FROM TestSuites ts
+    INNER JOIN Tests t ON t.id = ts.test_id
+    WHERE t.unmerged_branch_name IS NULL
+)
+ORDER BY LOWER(name);
+";
+
+    pub const DELETE_TEST_STEP_ARTIFACTS: &str = "\
+DELETE FROM TestStepArtifacts
+WHERE test_id IN (SELECT id FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL);
+";
+
+    pub const INSERT_TEST_STEP_ARTIFACT: &str = "\
+INSERT INTO TestStepArtifacts (test_id, command_ordinal, url, base64_data)
+SELECT id, ?, ?, ? FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL;
+";
+
+    pub const SELECT_MERGED_TEST_STEP_ARTIFACTS: &str = "
+SELECT
+    t.name AS test_name,
+    a.command_ordinal,
+    a.url,
+    a.base64_data
+FROM TestStepArtifacts a
+INNER JOIN Tests t ON t.id = a.test_id
+WHERE t.unmerged_branch_name IS NULL
+ORDER BY a.command_ordinal;
+";
+
+    pub const DELETE_TEST_STEP_DURATIONS: &str = "\
+DELETE FROM TestStepDurations
+WHERE test_id IN (SELECT id FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL);
+";
+
+    pub const INSERT_TEST_STEP_DURATION: &str = "\
+INSERT INTO TestStepDurations (test_id, command_ordinal, milliseconds)
+SELECT id, ?, ? FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL
+ON CONFLICT (test_id, command_ordinal) DO UPDATE SET milliseconds = excluded.milliseconds;
+";
+
+    pub const SELECT_MERGED_TEST_STEP_DURATIONS: &str = "
+SELECT
+    t.name AS test_name,
+    d.command_ordinal,
+    d.milliseconds
+FROM TestStepDurations d
+INNER JOIN Tests t ON t.id = d.test_id
+WHERE t.unmerged_branch_name IS NULL
+ORDER BY d.command_ordinal;
+";
+
+    pub const INSERT_TEST_RUN_RESULT: RoswaalSql = RoswaalSql::new(
+        "\
+INSERT INTO TestRunResults (test_id, did_fail)
+SELECT id, ? FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL;
+DELETE FROM TestRunResults
+WHERE id IN (
+    SELECT r.id FROM TestRunResults r
+    INNER JOIN Tests t ON t.id = r.test_id
+    WHERE t.name = ? AND t.unmerged_branch_name IS NULL
+    ORDER BY r.id DESC
+    LIMIT -1 OFFSET ?
+);
+",
+        "\
+INSERT INTO TestRunResults (test_id, did_fail)
+SELECT id, ? FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL;
+DELETE FROM TestRunResults
+WHERE id IN (
+    SELECT r.id FROM TestRunResults r
+    INNER JOIN Tests t ON t.id = r.test_id
+    WHERE t.name = ? AND t.unmerged_branch_name IS NULL
+    ORDER BY r.id DESC
+    OFFSET ?
+);
+",
+    );
+
+    pub const SELECT_MERGED_TEST_RUN_RESULTS: &str = "
+SELECT
+    t.name AS test_name,
+    r.did_fail
+FROM TestRunResults r
+INNER JOIN Tests t ON t.id = r.test_id
+WHERE t.unmerged_branch_name IS NULL
+ORDER BY r.id;
 ";
 
     pub const MERGE_UNMERGED_TESTS: &str = "
@@ -226,7 +1036,47 @@ UPDATE Tests SET unmerged_branch_name = NULL WHERE unmerged_branch_name = ? AND
 ";
 
     pub const SELECT_UNMERGED_TEST_NAMES: &str =
-        "SELECT name FROM Tests WHERE unmerged_branch_name = ?;";
+        "SELECT name FROM Tests WHERE unmerged_branch_name = ? ORDER BY name;";
+
+    pub const SELECT_MERGED_TEST_COMMANDS: &str = "
+SELECT t.name AS test_name, c.content AS command_content
+FROM Tests t
+INNER JOIN TestSteps c ON t.id = c.test_id
+WHERE t.unmerged_branch_name IS NULL
+ORDER BY test_name, c.ordinal;
+";
+
+    pub const SELECT_MERGED_TEST_NAMES: &str =
+        "SELECT name FROM Tests WHERE unmerged_branch_name IS NULL ORDER BY name;";
+
+    pub const SELECT_EXPIRED_DEPRECATED_TEST_NAMES: &str = "
+SELECT name FROM Tests
+WHERE
+    unmerged_branch_name IS NULL
+    AND deprecation_date <= ?
+    AND LOWER(name) NOT IN (SELECT name FROM StagedTestRemovals)
+ORDER BY name;
+";
+
+    pub const INSERT_MUTED_TEST_FAILURE: &str =
+        "INSERT INTO MutedTestFailures (name) VALUES (LOWER(?)) ON CONFLICT (name) DO NOTHING;";
+
+    pub const DELETE_MUTED_TEST_FAILURE: &str =
+        "DELETE FROM MutedTestFailures WHERE name = LOWER(?);";
+
+    pub const UPSERT_TEST_FAILURE_ASSIGNMENT: &str = "
+INSERT INTO TestFailureAssignments (name, slack_user_id) VALUES (LOWER(?), ?)
+ON CONFLICT (name) DO UPDATE SET slack_user_id = excluded.slack_user_id;
+";
+
+    pub const DELETE_TEST_FAILURE_ASSIGNMENT: &str =
+        "DELETE FROM TestFailureAssignments WHERE name = LOWER(?);";
+
+    pub const SELECT_TEST_FAILURE_ASSIGNMENTS: &str =
+        "SELECT name, slack_user_id FROM TestFailureAssignments;";
+
+    pub const SELECT_MUTED_TEST_FAILURE_NAMES: &str =
+        "SELECT name FROM MutedTestFailures ORDER BY name;";
 
     pub const SELECT_STAGED_TEST_REMOVAL_NAMES: &str =
         "SELECT name FROM StagedTestRemovals WHERE unmerged_branch_name = ?";
@@ -234,24 +1084,106 @@ UPDATE Tests SET unmerged_branch_name = NULL WHERE unmerged_branch_name = ? AND
     pub const INSERT_TEST_STEP: &str =
         "INSERT INTO TestSteps (test_id, content, ordinal) VALUES (?, ?, ?);";
 
+    pub const INSERT_TEST_TAG: &str = "INSERT INTO TestTags (test_id, tag) VALUES (?, ?);";
+
+    pub const INSERT_TEST_SUITE: &str = "
+INSERT INTO Suites (name) VALUES (?) ON CONFLICT DO NOTHING;
+INSERT INTO TestSuites (test_id, suite_id) SELECT ?, id FROM Suites WHERE LOWER(name) = LOWER(?);
+";
+
     pub const INSERT_TEST_RETURNING_ID: &str = "\
-INSERT OR REPLACE INTO Tests (
+DELETE FROM Tests WHERE name = ? AND unmerged_branch_name = ?;
+INSERT INTO Tests (
     name,
     description,
-    unmerged_branch_name
+    unmerged_branch_name,
+    is_planned,
+    platforms,
+    variables,
+    source_code,
+    added_by_slack_user_id
 ) VALUES (
     ?,
     ?,
-    ?
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    COALESCE(
+        (SELECT added_by_slack_user_id FROM Tests WHERE name = ? AND unmerged_branch_name IS NULL),
+        ?
+    )
 ) RETURNING id;";
 
     pub const DELETE_STAGED_TEST_REMOVALS_WITH_BRANCH: &str =
         "DELETE FROM StagedTestRemovals WHERE unmerged_branch_name = ?;";
 
-    pub const DELETE_UNMERGED_TESTS_WITH_BRANCH: &str =
-        "DELETE FROM Tests WHERE unmerged_branch_name = ?;";
+    pub const DELETE_UNMERGED_TESTS_WITH_BRANCH: &str =
+        "DELETE FROM Tests WHERE unmerged_branch_name = ?;";
+
+    pub const DELETE_UNMERGED_TEST_STEP_ARTIFACTS: &str = "\
+DELETE FROM TestStepArtifacts
+WHERE test_id IN (SELECT id FROM Tests WHERE name = ? AND unmerged_branch_name = ?);
+";
+
+    pub const DELETE_UNMERGED_TEST_STEP_DURATIONS: &str = "\
+DELETE FROM TestStepDurations
+WHERE test_id IN (SELECT id FROM Tests WHERE name = ? AND unmerged_branch_name = ?);
+";
+
+    pub const CLEAR_UNMERGED_TEST_PROGRESS: &str = "\
+UPDATE Tests
+SET
+    command_failure_ordinal = NULL,
+    error_message = NULL,
+    error_stack_trace = NULL,
+    last_run_date = NULL
+WHERE
+    name = ? AND unmerged_branch_name = ?;
+";
+
+    pub const MIGRATE_MERGED_TEST_PROGRESS: &str = "\
+UPDATE Tests
+SET
+    command_failure_ordinal = ?,
+    error_message = m.error_message,
+    error_stack_trace = m.error_stack_trace,
+    last_run_date = m.last_run_date
+FROM (
+    SELECT error_message, error_stack_trace, last_run_date
+    FROM Tests
+    WHERE name = ? AND unmerged_branch_name IS NULL
+) AS m
+WHERE
+    Tests.name = ? AND Tests.unmerged_branch_name = ?;
+";
+
+    pub const MIGRATE_MERGED_TEST_STEP_ARTIFACT: &str = "\
+INSERT INTO TestStepArtifacts (test_id, command_ordinal, url, base64_data)
+SELECT u.id, ?, a.url, a.base64_data
+FROM TestStepArtifacts a
+INNER JOIN Tests m ON m.id = a.test_id
+INNER JOIN Tests u ON u.name = m.name AND u.unmerged_branch_name = ?
+WHERE m.name = ? AND m.unmerged_branch_name IS NULL AND a.command_ordinal = ?;
+";
+
+    pub const MIGRATE_MERGED_TEST_STEP_DURATION: &str = "\
+INSERT INTO TestStepDurations (test_id, command_ordinal, milliseconds)
+SELECT u.id, ?, d.milliseconds
+FROM TestStepDurations d
+INNER JOIN Tests m ON m.id = d.test_id
+INNER JOIN Tests u ON u.name = m.name AND u.unmerged_branch_name = ?
+WHERE m.name = ? AND m.unmerged_branch_name IS NULL AND d.command_ordinal = ?
+ON CONFLICT (test_id, command_ordinal) DO UPDATE SET milliseconds = excluded.milliseconds;
+";
+
+    pub const SELECT_TEST_MERGE_STATES: &str = "\
+SELECT unmerged_branch_name IS NULL FROM Tests WHERE name = ?;
+";
 
-    pub const UPDATE_TEST_PROGRESS: &str = "\
+    pub const UPDATE_TEST_PROGRESS: RoswaalSql = RoswaalSql::new(
+        "\
 UPDATE Tests
 SET
     command_failure_ordinal = ?,
@@ -260,7 +1192,18 @@ SET
     last_run_date = unixepoch()
 WHERE
     name = ? AND unmerged_branch_name IS NULL;
-";
+",
+        "\
+UPDATE Tests
+SET
+    command_failure_ordinal = ?,
+    error_message = ?,
+    error_stack_trace = ?,
+    last_run_date = now()
+WHERE
+    name = ? AND unmerged_branch_name IS NULL;
+",
+    );
 
     pub fn select_tests_in_alphabetical_order(count: usize) -> String {
         format!(
@@ -273,13 +1216,70 @@ SELECT
     t.error_message,
     t.error_stack_trace,
     t.last_run_date,
+    t.deprecation_date,
+    t.is_planned,
+    t.added_by_slack_user_id,
+    t.platforms,
+    t.variables,
+    t.source_code,
     c.content AS command_content
 FROM Tests t
-INNER JOIN TestSteps c ON t.id = c.test_id
-WHERE LOWER(test_name) IN {}
-ORDER BY test_name, c.ordinal;
+LEFT JOIN TestSteps c ON t.id = c.test_id
+WHERE LOWER(t.name) IN {}
+ORDER BY test_name, t.id, c.ordinal;
+",
+            array_fields(count)
+        )
+    }
+
+    pub fn deprecate_tests(count: usize) -> String {
+        format!(
+            "\
+UPDATE Tests
+SET deprecation_date = ?
+WHERE LOWER(name) IN {} AND unmerged_branch_name IS NULL AND deprecation_date IS NULL
+RETURNING name;
+",
+            array_fields(count)
+        )
+    }
+
+    pub fn delete_test_step_rows(table: &str, filter: &str) -> String {
+        format!(
+            "\
+DELETE FROM {}
+WHERE test_id IN (SELECT id FROM Tests WHERE {});
+",
+            table, filter
+        )
+    }
+
+    /// A condition that matches the merged tests with `count` lowercased names, or every merged
+    /// test if `count` is zero.
+    pub fn merged_tests_filter(count: usize) -> String {
+        if count == 0 {
+            "unmerged_branch_name IS NULL".to_string()
+        } else {
+            format!(
+                "LOWER(name) IN {} AND unmerged_branch_name IS NULL",
+                array_fields(count)
+            )
+        }
+    }
+
+    pub fn reset_test_progress(filter: &str) -> String {
+        format!(
+            "\
+UPDATE Tests
+SET
+    command_failure_ordinal = NULL,
+    error_message = NULL,
+    error_stack_trace = NULL,
+    last_run_date = NULL
+WHERE {}
+RETURNING name;
 ",
-            sqlite_array_fields(count)
+            filter
         )
     }
 
@@ -289,7 +1289,7 @@ ORDER BY test_name, c.ordinal;
 DELETE FROM Tests
 WHERE LOWER(name) IN {} AND unmerged_branch_name IS NULL
 ",
-            sqlite_array_fields(count)
+            array_fields(count)
         )
     }
 }
@@ -299,6 +1299,12 @@ struct SqliteTestName {
     name: String,
 }
 
+#[derive(Debug, FromRow)]
+struct SqliteTestCommandRow {
+    test_name: String,
+    command_content: String,
+}
+
 #[derive(Debug, FromRow, Clone)]
 struct SqliteTestID {
     id: i32,
@@ -309,11 +1315,44 @@ struct SqliteStoredTestRow {
     test_name: String,
     description: Option<String>,
     unmerged_branch_name: Option<RoswaalOwnedGitBranchName>,
-    command_content: String,
+    command_content: Option<String>,
     command_failure_ordinal: Option<RoswaalTestCommandOrdinal>,
     error_message: Option<String>,
     error_stack_trace: Option<String>,
     last_run_date: Option<DateTime<Utc>>,
+    deprecation_date: Option<DateTime<Utc>>,
+    is_planned: bool,
+    added_by_slack_user_id: Option<String>,
+    platforms: String,
+    variables: String,
+    source_code: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteTestStepArtifactRow {
+    test_name: String,
+    command_ordinal: RoswaalTestCommandOrdinal,
+    url: Option<String>,
+    base64_data: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteTestStepDurationRow {
+    test_name: String,
+    command_ordinal: RoswaalTestCommandOrdinal,
+    milliseconds: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteTestFailureAssignmentRow {
+    name: String,
+    slack_user_id: String,
+}
+
+#[derive(Debug, FromRow)]
+struct SqliteTestRunResultRow {
+    test_name: String,
+    did_fail: bool,
 }
 
 impl SqliteStoredTestRow {
@@ -338,9 +1377,31 @@ impl RoswaalTest {
             sqlite_test.unmerged_branch_name.clone(),
             sqlite_test.last_run_date,
         )
+        .with_deprecation_date(sqlite_test.deprecation_date)
+        .with_planned(sqlite_test.is_planned)
+        .with_added_by_slack_user_id(sqlite_test.added_by_slack_user_id.clone())
+        .with_platforms(
+            sqlite_test
+                .platforms
+                .split(',')
+                .filter_map(|platform| platform.parse().ok())
+                .collect(),
+        )
+        .with_variables(serde_json::from_str(&sqlite_test.variables).unwrap_or_default())
+        .with_source_code(sqlite_test.source_code.clone())
     }
 }
 
+/// Returns the comma separated names of `platforms` that are stored in the platforms column of a
+/// test.
+fn sqlite_platforms(platforms: &[RoswaalTestPlatform]) -> String {
+    platforms
+        .iter()
+        .map(|platform| platform.as_str())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -348,19 +1409,24 @@ mod tests {
     use super::*;
     use crate::{
         git::branch_name::RoswaalOwnedGitBranchName,
+        language::variable::RoswaalTestVariable,
         location::name::RoswaalLocationName,
         tests_data::{
             ordinal::RoswaalTestCommandOrdinal,
-            progress::RoswaalTestProgressUploadErrorDescription, test::RoswaalTestProgressStatus,
+            progress::{
+                RoswaalTestProgressUploadErrorDescription, RoswaalTestStepDuration,
+                RoswaalTestStepScreenshot,
+            },
+            test::RoswaalTestProgressStatus,
         },
-        utils::sqlite::RoswaalSqlite,
+        utils::db::RoswaalDatabase,
     };
 
     #[tokio::test]
     async fn test_store_and_retrieve_unmerged_tests() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let tests = vec![
             RoswaalCompiledTest::mock1("Test 1"),
             RoswaalCompiledTest::mock2("Test 2"),
@@ -379,6 +1445,7 @@ mod tests {
                         label: "Step 1".to_string(),
                         name: "Step 1".to_string(),
                         requirement: "Requirement 1".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::SetLocation {
                         location_name: RoswaalLocationName::from_str("test").unwrap(),
@@ -397,6 +1464,7 @@ mod tests {
                     label: "Step 1".to_string(),
                     name: "Step A".to_string(),
                     requirement: "Requirement A".to_string(),
+                    retries: None,
                 }],
                 None,
                 None,
@@ -408,11 +1476,87 @@ mod tests {
         assert_eq!(stored_tests, expected_tests)
     }
 
+    #[tokio::test]
+    async fn test_store_and_retrieve_step_retries() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let commands = vec![RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: "Step 1".to_string(),
+            requirement: "Requirement 1".to_string(),
+            retries: Some(2),
+        }];
+        let test = RoswaalCompiledTest::new("Test 1".to_string(), None, commands.clone());
+        transaction
+            .save_tests(&vec![test], &RoswaalOwnedGitBranchName::new("test"))
+            .await
+            .unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let stored_commands = stored_tests[0]
+            .commands()
+            .iter()
+            .map(|c| c.compiled_command().clone())
+            .collect::<Vec<RoswaalCompiledTestCommand>>();
+        assert_eq!(stored_commands, commands)
+    }
+
+    #[tokio::test]
+    async fn test_store_and_retrieve_variables() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let variables = vec![
+            RoswaalTestVariable::new("eventName", "Pickup Basketball"),
+            RoswaalTestVariable::new("host", "\"Roswaal\""),
+        ];
+        let tests = vec![
+            RoswaalCompiledTest::new("Test 1".to_string(), None, vec![])
+                .with_variables(variables.clone()),
+            RoswaalCompiledTest::new("Test 2".to_string(), None, vec![]),
+        ];
+        transaction
+            .save_tests(&tests, &RoswaalOwnedGitBranchName::new("test"))
+            .await
+            .unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(stored_tests[0].variables(), &variables);
+        assert!(stored_tests[1].variables().is_empty())
+    }
+
+    #[tokio::test]
+    async fn test_store_and_retrieve_source_code() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let source_code = "New Test: Test 1\nStep 1: A\nRequirement 1: B";
+        let tests = vec![
+            RoswaalCompiledTest::new("Test 1".to_string(), None, vec![])
+                .with_source_code(source_code),
+            RoswaalCompiledTest::new("Test 2".to_string(), None, vec![]),
+        ];
+        transaction
+            .save_tests(&tests, &RoswaalOwnedGitBranchName::new("test"))
+            .await
+            .unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(stored_tests[0].source_code(), Some(source_code));
+        assert_eq!(stored_tests[0].original_syntax(), source_code);
+        assert_eq!(stored_tests[1].source_code(), None);
+        assert_eq!(stored_tests[1].original_syntax(), stored_tests[1].syntax())
+    }
+
     #[tokio::test]
     async fn test_store_duplicate_named_tests_on_same_branch_replaces_initially_inserted_test() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let tests = vec![
             RoswaalCompiledTest::mock1("Test"),
             RoswaalCompiledTest::mock2("Test"),
@@ -429,6 +1573,7 @@ mod tests {
                 label: "Step 1".to_string(),
                 name: "Step A".to_string(),
                 requirement: "Requirement A".to_string(),
+                retries: None,
             }],
             None,
             None,
@@ -443,8 +1588,8 @@ mod tests {
     async fn test_store_and_retrieve_unmerged_tests_with_same_name_and_different_branches() {
         let branch_name1 = RoswaalOwnedGitBranchName::new("test");
         let branch_name2 = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![RoswaalCompiledTest::new(
             "Test".to_string(),
             None,
@@ -452,6 +1597,7 @@ mod tests {
                 label: "Step 1".to_string(),
                 name: "Step 1".to_string(),
                 requirement: "Requirement 1".to_string(),
+                retries: None,
             }],
         )];
         transaction.save_tests(&tests, &branch_name1).await.unwrap();
@@ -462,6 +1608,7 @@ mod tests {
                 label: "Step 1".to_string(),
                 name: "Step A".to_string(),
                 requirement: "Requirement A".to_string(),
+                retries: None,
             }],
         )];
         transaction.save_tests(&tests, &branch_name2).await.unwrap();
@@ -477,6 +1624,7 @@ mod tests {
                     label: "Step 1".to_string(),
                     name: "Step 1".to_string(),
                     requirement: "Requirement 1".to_string(),
+                    retries: None,
                 }],
                 None,
                 None,
@@ -491,6 +1639,7 @@ mod tests {
                     label: "Step 1".to_string(),
                     name: "Step A".to_string(),
                     requirement: "Requirement A".to_string(),
+                    retries: None,
                 }],
                 None,
                 None,
@@ -504,8 +1653,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_returns_empty_vector_when_no_inserted_tests() {
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let tests = transaction
             .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
             .await
@@ -516,8 +1665,8 @@ mod tests {
     #[tokio::test]
     async fn test_store_and_merge_tests_removes_branch_name_of_merged_branch() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![RoswaalCompiledTest::mock1("Test")];
         transaction.save_tests(&tests, &branch_name).await.unwrap();
         let branch_name2 = RoswaalOwnedGitBranchName::new("test-2");
@@ -541,8 +1690,8 @@ mod tests {
     #[tokio::test]
     async fn test_store_and_merge_tests_with_same_name_overwrites_previous_merged() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![RoswaalCompiledTest::mock1("Test")];
         transaction.save_tests(&tests, &branch_name).await.unwrap();
         transaction
@@ -557,6 +1706,7 @@ mod tests {
                 label: "Step 1".to_string(),
                 name: "Step A".to_string(),
                 requirement: "Requirement A".to_string(),
+                retries: None,
             }],
         )];
         transaction.save_tests(&tests, &branch_name2).await.unwrap();
@@ -575,6 +1725,7 @@ mod tests {
                 label: "Step 1".to_string(),
                 name: "Step A".to_string(),
                 requirement: "Requirement A".to_string(),
+                retries: None,
             }],
             None,
             None,
@@ -585,11 +1736,68 @@ mod tests {
         assert_eq!(stored_tests, expected_tests)
     }
 
+    #[tokio::test]
+    async fn merged_test_names_excludes_unmerged_tests() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock1("Test 1")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let branch_name2 = RoswaalOwnedGitBranchName::new("test-2");
+        let tests = vec![RoswaalCompiledTest::mock2("Test 2")];
+        transaction.save_tests(&tests, &branch_name2).await.unwrap();
+        let names = transaction.merged_test_names().await.unwrap();
+        assert_eq!(names, vec!["Test 1".to_string()])
+    }
+
+    #[tokio::test]
+    async fn merged_test_dependency_graph_contains_merged_tests_with_their_commands() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let sign_in = RoswaalCompiledTest::mock1("Sign in");
+        let requires_sign_in = RoswaalCompiledTestCommand::RequiresTest {
+            test_name: "Sign in".to_string(),
+            commands: sign_in.commands().clone(),
+        };
+        let join = RoswaalCompiledTest::new(
+            "Join an event".to_string(),
+            None,
+            vec![requires_sign_in.clone()],
+        );
+        transaction
+            .save_tests(&vec![sign_in.clone(), join], &branch_name)
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let branch_name2 = RoswaalOwnedGitBranchName::new("test-2");
+        let tests = vec![RoswaalCompiledTest::mock2("Leave an event")];
+        transaction.save_tests(&tests, &branch_name2).await.unwrap();
+        let graph = transaction.merged_test_dependency_graph().await.unwrap();
+        assert_eq!(
+            graph.test("Sign in"),
+            Some(("Sign in", sign_in.commands().as_slice()))
+        );
+        assert_eq!(
+            graph.test("Join an event"),
+            Some(("Join an event", [requires_sign_in].as_slice()))
+        );
+        assert_eq!(graph.test("Leave an event"), None);
+        assert!(graph.requires("Join an event", "Sign in"))
+    }
+
     #[tokio::test]
     async fn query_partial_tests() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let tests = vec![
             RoswaalCompiledTest::mock1("Dazai Is Insane"),
             RoswaalCompiledTest::mock2("Zanza The Divine"),
@@ -615,11 +1823,230 @@ l
         )
     }
 
+    #[tokio::test]
+    async fn load_tests_with_tag() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::mock2("Zanza The Divine")
+                .with_tags(vec!["smoke".to_string(), "events".to_string()]),
+            RoswaalCompiledTest::mock2("L").with_tags(vec!["events".to_string()]),
+            RoswaalCompiledTest::mock2("Bob").with_tags(vec!["smoke".to_string()]),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock2("Bob")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("tag:SMOKE"))
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["Zanza The Divine"]
+        )
+    }
+
+    #[tokio::test]
+    async fn store_and_retrieve_planned_tests_without_steps() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let planned_test = RoswaalCompiledTest::new(
+            "Join an event from the map".to_string(),
+            Some("Users should join events without leaving the map.".to_string()),
+            vec![],
+        )
+        .with_tags(vec!["events".to_string()])
+        .with_planned(true);
+        transaction
+            .save_tests(&vec![planned_test.clone()], &branch_name)
+            .await
+            .unwrap();
+        let tests = vec![RoswaalCompiledTest::mock2("Bob"), planned_test];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let stored_tests = stored_tests
+            .iter()
+            .map(|t| (t.name(), t.is_planned(), t.commands().len()))
+            .collect::<Vec<(&str, bool, usize)>>();
+        assert_eq!(
+            stored_tests,
+            vec![("Bob", false, 1), ("Join an event from the map", true, 0)]
+        );
+        let tagged_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("tag:events"))
+            .await
+            .unwrap();
+        assert!(tagged_tests[0].is_planned())
+    }
+
+    #[tokio::test]
+    async fn load_tests_added_by_slack_user_keeps_adder_of_merged_tests() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let tests = vec![
+            RoswaalCompiledTest::mock2("Bob"),
+            RoswaalCompiledTest::mock2("Zanza The Divine"),
+        ];
+        transaction
+            .save_tests_added_by(&tests, &branch_name, Some("U1"))
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let edit_branch_name = RoswaalOwnedGitBranchName::new("edit");
+        let tests = vec![
+            RoswaalCompiledTest::mock2("Bob"),
+            RoswaalCompiledTest::mock2("Lonely"),
+        ];
+        transaction
+            .save_tests_added_by(&tests, &edit_branch_name, Some("U2"))
+            .await
+            .unwrap();
+        transaction
+            .merge_unmerged_tests(&edit_branch_name)
+            .await
+            .unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let adders = stored_tests
+            .iter()
+            .map(|t| (t.name(), t.added_by_slack_user_id().map(String::as_str)))
+            .collect::<Vec<(&str, Option<&str>)>>();
+        assert_eq!(
+            adders,
+            vec![
+                ("Bob", Some("U1")),
+                ("Lonely", Some("U2")),
+                ("Zanza The Divine", Some("U1"))
+            ]
+        );
+        let added_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("by:<@U2|justin>"))
+            .await
+            .unwrap();
+        let names = added_tests.iter().map(|t| t.name()).collect::<Vec<&str>>();
+        assert_eq!(names, vec!["Lonely"])
+    }
+
+    #[tokio::test]
+    async fn load_tests_in_suite() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::mock2("Zanza The Divine").with_suite(Some("Events".to_string())),
+            RoswaalCompiledTest::mock2("L").with_suite(Some("Events".to_string())),
+            RoswaalCompiledTest::mock2("Bob").with_suite(Some("Onboarding".to_string())),
+            RoswaalCompiledTest::mock2("Lonely"),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock2("L")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("suite:EVENTS"))
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["Zanza The Divine"]
+        );
+        assert!(transaction.merged_suite_names().await.unwrap().is_empty());
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        assert_eq!(
+            transaction.merged_suite_names().await.unwrap(),
+            vec!["Events".to_string(), "Onboarding".to_string()]
+        )
+    }
+
+    #[tokio::test]
+    async fn load_tests_containing_phrase_ranked_by_relevance() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::new(
+                "A".to_string(),
+                Some("Justin wants to join an event after he signs in".to_string()),
+                vec![step("Justin is signed in", "Ensure Justin has signed in")],
+            ),
+            RoswaalCompiledTest::new(
+                "B".to_string(),
+                None,
+                vec![
+                    step(
+                        "Justin wants to join the event",
+                        "Have Justin join the event",
+                    ),
+                    step("Justin wants to join the event", "Join the event again"),
+                ],
+            ),
+            RoswaalCompiledTest::new(
+                "C".to_string(),
+                None,
+                vec![step("Justin leaves", "Have Justin leave the event")],
+            ),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("contains:JOIN"))
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["B", "A"]
+        );
+        assert_eq!(stored_tests[0].commands().len(), 2);
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("contains:the leave"))
+            .await
+            .unwrap();
+        assert_eq!(stored_tests, vec![]);
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("contains:\"leave the"))
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests.iter().map(|t| t.name()).collect::<Vec<&str>>(),
+            vec!["C"]
+        )
+    }
+
+    #[tokio::test]
+    async fn removed_tests_are_not_found_by_phrase() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock1("Test 1")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .close_add_tests_branch(&branch_name)
+            .await
+            .unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::new("contains:Test 1"))
+            .await
+            .unwrap();
+        assert_eq!(stored_tests, vec![])
+    }
+
     #[tokio::test]
     async fn stage_test_removals_does_not_remove_tests() {
         let mut branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let tests = vec![RoswaalCompiledTest::mock2("Zanza The Divine")];
         transaction.save_tests(&tests, &branch_name).await.unwrap();
         transaction
@@ -646,8 +2073,8 @@ l
     #[tokio::test]
     async fn remove_merged_tests_only_removes_merged_tests() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![
             RoswaalCompiledTest::mock1("Dazai Is Insane"),
             RoswaalCompiledTest::mock2("Zanza The Divine"),
@@ -687,8 +2114,8 @@ L
     #[tokio::test]
     async fn close_add_branch_removes_unmerged_tests_on_branch() {
         let mut branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![RoswaalCompiledTest::mock1("Zanza The Divine")];
         transaction.save_tests(&tests, &branch_name).await.unwrap();
         tests = vec![RoswaalCompiledTest::mock1("L")];
@@ -712,8 +2139,8 @@ L
     #[tokio::test]
     async fn close_remove_branch_removes_staged_test_removals() {
         let mut branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![RoswaalCompiledTest::mock1("Zanza The Divine")];
         transaction.save_tests(&tests, &branch_name).await.unwrap();
         transaction
@@ -753,8 +2180,8 @@ L
     #[tokio::test]
     async fn saves_test_progress_for_merged_tests() {
         let branch_name = RoswaalOwnedGitBranchName::new("test");
-        let sqlite = RoswaalSqlite::in_memory().await.unwrap();
-        let mut transaction = sqlite.transaction().await.unwrap();
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
         let mut tests = vec![
             RoswaalCompiledTest::mock1("Dazai Is Insane"),
             RoswaalCompiledTest::mock2("Zanza The Divine"),
@@ -807,6 +2234,536 @@ L
         assert!(stored_tests[2].last_run_date().is_none());
     }
 
+    #[tokio::test]
+    async fn saving_test_progress_returns_the_outcome_of_each_entry() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock1("Dazai Is Insane")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let tests = vec![RoswaalCompiledTest::mock2("Zanza The Divine")];
+        transaction
+            .save_tests(&tests, &RoswaalOwnedGitBranchName::new("test-2"))
+            .await
+            .unwrap();
+        let progress = vec![
+            RoswaalTestProgressUpload::new("Zanza The Divine".to_string(), None, None),
+            RoswaalTestProgressUpload::new("Dazai Is Insane".to_string(), None, None),
+            RoswaalTestProgressUpload::new("Blob Jr".to_string(), None, None),
+        ];
+        let results = transaction.save_test_progess(&progress).await.unwrap();
+        assert_eq!(
+            results,
+            vec![
+                RoswaalTestProgressUploadResult::new(
+                    "Zanza The Divine".to_string(),
+                    RoswaalTestProgressUploadOutcome::UnmergedOnly
+                ),
+                RoswaalTestProgressUploadResult::new(
+                    "Dazai Is Insane".to_string(),
+                    RoswaalTestProgressUploadOutcome::Updated
+                ),
+                RoswaalTestProgressUploadResult::new(
+                    "Blob Jr".to_string(),
+                    RoswaalTestProgressUploadOutcome::UnknownTest
+                )
+            ]
+        );
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert!(stored_tests[0].last_run_date().is_some());
+        assert!(stored_tests[1].last_run_date().is_none())
+    }
+
+    #[tokio::test]
+    async fn saves_screenshots_from_the_most_recent_progress_upload() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock1("Dazai Is Insane")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let old_screenshot = RoswaalTestStepScreenshot::new(
+            RoswaalTestCommandOrdinal::new(0),
+            Some("https://example.com/old.png".to_string()),
+            None,
+        );
+        let progress =
+            vec![
+                RoswaalTestProgressUpload::new("Dazai Is Insane".to_string(), None, None)
+                    .with_screenshots(vec![old_screenshot]),
+            ];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let screenshots = vec![
+            RoswaalTestStepScreenshot::new(
+                RoswaalTestCommandOrdinal::new(1),
+                None,
+                Some("aGVsbG8=".to_string()),
+            ),
+            RoswaalTestStepScreenshot::new(
+                RoswaalTestCommandOrdinal::new(0),
+                Some("https://example.com/new.png".to_string()),
+                None,
+            ),
+        ];
+        let progress = vec![RoswaalTestProgressUpload::new(
+            "Dazai Is Insane".to_string(),
+            Some(RoswaalTestCommandOrdinal::new(1)),
+            None,
+        )
+        .with_screenshots(screenshots.clone())];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests[0].screenshot(RoswaalTestCommandOrdinal::new(0)),
+            Some(&screenshots[1])
+        );
+        assert_eq!(
+            stored_tests[0].screenshot(RoswaalTestCommandOrdinal::new(1)),
+            Some(&screenshots[0])
+        );
+        assert_eq!(
+            stored_tests[0].screenshot(RoswaalTestCommandOrdinal::for_before_launch()),
+            None
+        )
+    }
+
+    #[tokio::test]
+    async fn remaps_progress_to_reordered_steps_when_merging() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock_steps("Dazai", &["A", "B"])];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let branch_name2 = RoswaalOwnedGitBranchName::new("test-2");
+        let tests = vec![RoswaalCompiledTest::mock_steps("Dazai", &["B", "C", "A"])];
+        transaction.save_tests(&tests, &branch_name2).await.unwrap();
+
+        let screenshot = RoswaalTestStepScreenshot::new(
+            RoswaalTestCommandOrdinal::new(1),
+            Some("https://example.com/b.png".to_string()),
+            None,
+        );
+        let progress = vec![RoswaalTestProgressUpload::new(
+            "Dazai".to_string(),
+            Some(RoswaalTestCommandOrdinal::new(1)),
+            Some(RoswaalTestProgressUploadErrorDescription::new(
+                "B failed".to_string(),
+                "Some stack trace...".to_string(),
+            )),
+        )
+        .with_screenshots(vec![screenshot])
+        .with_durations(vec![
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(0), 1000),
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(1), 2000),
+        ])];
+        transaction.save_test_progess(&progress).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name2)
+            .await
+            .unwrap();
+
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(stored_tests.len(), 1);
+        let test = &stored_tests[0];
+        assert_eq!(
+            test.command_failure_ordinal(),
+            Some(RoswaalTestCommandOrdinal::new(0))
+        );
+        assert_eq!(test.error_message(), Some(&"B failed".to_string()));
+        assert!(test.last_run_date().is_some());
+        assert_eq!(
+            test.screenshot(RoswaalTestCommandOrdinal::new(0))
+                .and_then(|s| s.url()),
+            Some(&"https://example.com/b.png".to_string())
+        );
+        assert_eq!(test.screenshot(RoswaalTestCommandOrdinal::new(1)), None);
+        let milliseconds = [0, 1, 2].map(|index| {
+            test.duration(RoswaalTestCommandOrdinal::new(index))
+                .map(|d| d.milliseconds())
+        });
+        assert_eq!(milliseconds, [Some(2000), None, Some(1000)])
+    }
+
+    #[tokio::test]
+    async fn clears_progress_when_the_failing_step_is_removed() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock_steps("Dazai", &["A", "B"])];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let progress = vec![RoswaalTestProgressUpload::new(
+            "Dazai".to_string(),
+            Some(RoswaalTestCommandOrdinal::new(1)),
+            Some(RoswaalTestProgressUploadErrorDescription::new(
+                "B failed".to_string(),
+                "Some stack trace...".to_string(),
+            )),
+        )
+        .with_durations(vec![RoswaalTestStepDuration::new(
+            RoswaalTestCommandOrdinal::new(0),
+            1000,
+        )])];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let branch_name2 = RoswaalOwnedGitBranchName::new("test-2");
+        let tests = vec![RoswaalCompiledTest::mock_steps("Dazai", &["A", "C"])];
+        transaction.save_tests(&tests, &branch_name2).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name2)
+            .await
+            .unwrap();
+
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let test = &stored_tests[0];
+        assert_eq!(test.command_failure_ordinal(), None);
+        assert_eq!(test.error_message(), None);
+        assert_eq!(test.progress_status(), RoswaalTestProgressStatus::Idle);
+        assert_eq!(test.duration(RoswaalTestCommandOrdinal::new(0)), None)
+    }
+
+    #[tokio::test]
+    async fn saves_durations_from_the_most_recent_progress_upload() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![RoswaalCompiledTest::mock1("Dazai Is Insane")];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let progress =
+            vec![
+                RoswaalTestProgressUpload::new("Dazai Is Insane".to_string(), None, None)
+                    .with_durations(vec![RoswaalTestStepDuration::new(
+                        RoswaalTestCommandOrdinal::new(1),
+                        9000,
+                    )]),
+            ];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let durations = vec![
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::for_before_launch(), 3000),
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(0), 1200),
+        ];
+        let progress =
+            vec![
+                RoswaalTestProgressUpload::new("Dazai Is Insane".to_string(), None, None)
+                    .with_durations(durations.clone()),
+            ];
+        transaction.save_test_progess(&progress).await.unwrap();
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests[0].duration(RoswaalTestCommandOrdinal::for_before_launch()),
+            Some(&durations[0])
+        );
+        assert_eq!(
+            stored_tests[0].duration(RoswaalTestCommandOrdinal::new(0)),
+            Some(&durations[1])
+        );
+        assert_eq!(
+            stored_tests[0].duration(RoswaalTestCommandOrdinal::new(1)),
+            None
+        )
+    }
+
+    #[tokio::test]
+    async fn orders_tests_by_flakiness_of_their_most_recent_runs() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::mock1("Broken"),
+            RoswaalCompiledTest::mock1("Flaky"),
+            RoswaalCompiledTest::mock1("Stable"),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        for run in 0..FLAKINESS_RUN_WINDOW + 2 {
+            let failure = Some(RoswaalTestCommandOrdinal::new(0));
+            let progress = vec![
+                RoswaalTestProgressUpload::new("Broken".to_string(), failure, None),
+                RoswaalTestProgressUpload::new(
+                    "Flaky".to_string(),
+                    failure.filter(|_| run % 2 == 0),
+                    None,
+                ),
+                RoswaalTestProgressUpload::new("Stable".to_string(), None, None),
+            ];
+            transaction.save_test_progess(&progress).await.unwrap();
+        }
+        let names = transaction
+            .tests_in_order(
+                &RoswaalSearchTestsQuery::AllTests,
+                RoswaalTestsOrdering::MostFlaky,
+            )
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["Flaky", "Broken", "Stable"]);
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_tests[0].flakiness().run_count(),
+            FLAKINESS_RUN_WINDOW
+        );
+        assert_eq!(
+            stored_tests[1].flakiness().failure_count(),
+            FLAKINESS_RUN_WINDOW / 2
+        );
+        assert_eq!(stored_tests[2].flakiness().score(), 0.0);
+        let run_count = query_as::<(i64,)>("SELECT COUNT(*) FROM TestRunResults;")
+            .fetch_one(transaction.connection())
+            .await
+            .unwrap();
+        assert_eq!(run_count.0, 3 * FLAKINESS_RUN_WINDOW as i64)
+    }
+
+    #[tokio::test]
+    async fn deprecates_merged_tests_that_are_not_already_deprecated() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::mock1("Hello"),
+            RoswaalCompiledTest::mock2("World"),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        transaction
+            .save_tests(
+                &vec![RoswaalCompiledTest::mock1("Unmerged")],
+                &RoswaalOwnedGitBranchName::new("unmerged"),
+            )
+            .await
+            .unwrap();
+        let deprecation_date = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let names = transaction
+            .deprecate_tests(
+                &RoswaalTestNamesString::new("hello\nUnmerged\nMissing"),
+                deprecation_date,
+            )
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["Hello"]);
+        let names = transaction
+            .deprecate_tests(
+                &RoswaalTestNamesString::new("Hello\nWorld"),
+                deprecation_date,
+            )
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["World"]);
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let deprecation_dates = stored_tests
+            .iter()
+            .map(|t| (t.name(), t.deprecation_date()))
+            .collect::<Vec<(&str, Option<DateTime<Utc>>)>>();
+        assert_eq!(
+            deprecation_dates,
+            vec![
+                ("Hello", Some(deprecation_date)),
+                ("Unmerged", None),
+                ("World", Some(deprecation_date))
+            ]
+        )
+    }
+
+    #[tokio::test]
+    async fn resets_the_progress_of_named_merged_tests_or_all_merged_tests() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::mock_steps("Dazai", &["A", "B"]),
+            RoswaalCompiledTest::mock_steps("Atsushi", &["A"]),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let progress = ["Dazai", "Atsushi"]
+            .iter()
+            .map(|name| {
+                RoswaalTestProgressUpload::new(
+                    name.to_string(),
+                    Some(RoswaalTestCommandOrdinal::new(1)),
+                    Some(RoswaalTestProgressUploadErrorDescription::new(
+                        "A failed".to_string(),
+                        "Some stack trace...".to_string(),
+                    )),
+                )
+                .with_durations(vec![RoswaalTestStepDuration::new(
+                    RoswaalTestCommandOrdinal::new(0),
+                    1000,
+                )])
+            })
+            .collect();
+        transaction.save_test_progess(&progress).await.unwrap();
+
+        let names = transaction
+            .reset_test_progress(&RoswaalTestNamesString::new("dazai\nMissing"))
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["Dazai"]);
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        let statuses = stored_tests
+            .iter()
+            .map(|t| (t.name(), t.command_failure_ordinal(), t.error_message()))
+            .collect::<Vec<_>>();
+        let error_message = "A failed".to_string();
+        assert_eq!(
+            statuses,
+            vec![
+                (
+                    "Atsushi",
+                    Some(RoswaalTestCommandOrdinal::new(1)),
+                    Some(&error_message)
+                ),
+                ("Dazai", None, None)
+            ]
+        );
+        assert_eq!(
+            stored_tests[1].progress_status(),
+            RoswaalTestProgressStatus::Idle
+        );
+        assert_eq!(
+            stored_tests[1].duration(RoswaalTestCommandOrdinal::new(0)),
+            None
+        );
+
+        let names = transaction
+            .reset_test_progress(&RoswaalTestNamesString::new(""))
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["Atsushi", "Dazai"]);
+        let stored_tests = transaction
+            .tests_in_alphabetical_order(&RoswaalSearchTestsQuery::AllTests)
+            .await
+            .unwrap();
+        assert!(stored_tests
+            .iter()
+            .all(|t| t.progress_status() == RoswaalTestProgressStatus::Idle));
+        let run_count = query_as::<(i64,)>("SELECT COUNT(*) FROM TestRunResults;")
+            .fetch_one(transaction.connection())
+            .await
+            .unwrap();
+        assert_eq!(run_count.0, 2)
+    }
+
+    #[tokio::test]
+    async fn expired_deprecated_tests_exclude_recent_deprecations_and_staged_removals() {
+        let branch_name = RoswaalOwnedGitBranchName::new("test");
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        let tests = vec![
+            RoswaalCompiledTest::mock1("Expired"),
+            RoswaalCompiledTest::mock1("Recent"),
+            RoswaalCompiledTest::mock1("Staged"),
+            RoswaalCompiledTest::mock2("Active"),
+        ];
+        transaction.save_tests(&tests, &branch_name).await.unwrap();
+        transaction
+            .merge_unmerged_tests(&branch_name)
+            .await
+            .unwrap();
+        let cutoff_date = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        transaction
+            .deprecate_tests(
+                &RoswaalTestNamesString::new("Expired\nStaged"),
+                cutoff_date - chrono::Duration::days(1),
+            )
+            .await
+            .unwrap();
+        transaction
+            .deprecate_tests(
+                &RoswaalTestNamesString::new("Recent"),
+                cutoff_date + chrono::Duration::days(1),
+            )
+            .await
+            .unwrap();
+        transaction
+            .stage_test_removals(
+                &RoswaalTestNamesString::new("Staged"),
+                &RoswaalOwnedGitBranchName::new("removal"),
+            )
+            .await
+            .unwrap();
+        let names = transaction
+            .expired_deprecated_test_names(cutoff_date)
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["Expired"])
+    }
+
+    #[tokio::test]
+    async fn mutes_and_unmutes_test_failures_case_insensitively() {
+        let database = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        transaction.mute_test_failures("Blob").await.unwrap();
+        transaction.mute_test_failures("BLOB").await.unwrap();
+        transaction.mute_test_failures("Jr").await.unwrap();
+        let names = transaction.muted_test_failure_names().await.unwrap();
+        assert_eq!(names, vec!["blob", "jr"]);
+        transaction.unmute_test_failures("bLoB").await.unwrap();
+        let names = transaction.muted_test_failure_names().await.unwrap();
+        assert_eq!(names, vec!["jr"])
+    }
+
+    fn step(name: &str, requirement: &str) -> RoswaalCompiledTestCommand {
+        RoswaalCompiledTestCommand::Step {
+            label: "Step 1".to_string(),
+            name: name.to_string(),
+            requirement: requirement.to_string(),
+            retries: None,
+        }
+    }
+
     impl RoswaalCompiledTest {
         fn mock1(name: &str) -> Self {
             Self::new(
@@ -817,6 +2774,7 @@ L
                         label: "Step 1".to_string(),
                         name: "Step 1".to_string(),
                         requirement: "Requirement 1".to_string(),
+                        retries: None,
                     },
                     RoswaalCompiledTestCommand::SetLocation {
                         location_name: RoswaalLocationName::from_str("test").unwrap(),
@@ -825,6 +2783,20 @@ L
             )
         }
 
+        fn mock_steps(name: &str, step_names: &[&str]) -> Self {
+            let commands = step_names
+                .iter()
+                .enumerate()
+                .map(|(index, step_name)| RoswaalCompiledTestCommand::Step {
+                    label: format!("Step {}", index + 1),
+                    name: step_name.to_string(),
+                    requirement: format!("Requirement {}", step_name),
+                    retries: None,
+                })
+                .collect();
+            Self::new(name.to_string(), None, commands)
+        }
+
         fn mock2(name: &str) -> Self {
             Self::new(
                 name.to_string(),
@@ -833,6 +2805,7 @@ L
                     label: "Step 1".to_string(),
                     name: "Step A".to_string(),
                     requirement: "Requirement A".to_string(),
+                    retries: None,
                 }],
             )
         }
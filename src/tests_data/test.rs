@@ -1,10 +1,21 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 
 use crate::{
-    git::branch_name::RoswaalOwnedGitBranchName, language::test::RoswaalCompiledTestCommand,
+    git::branch_name::RoswaalOwnedGitBranchName,
+    language::{
+        platform::RoswaalTestPlatform, test::RoswaalCompiledTestCommand,
+        variable::RoswaalTestVariable,
+    },
 };
 
-use super::ordinal::RoswaalTestCommandOrdinal;
+use super::{
+    flakiness::RoswaalTestFlakiness,
+    ordinal::RoswaalTestCommandOrdinal,
+    progress::{RoswaalTestStepDuration, RoswaalTestStepScreenshot},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RoswaalTest {
@@ -16,6 +27,16 @@ pub struct RoswaalTest {
     error_stack_trace: Option<String>,
     unmerged_branch_name: Option<RoswaalOwnedGitBranchName>,
     last_run_date: Option<DateTime<Utc>>,
+    screenshots: Vec<RoswaalTestStepScreenshot>,
+    durations: Vec<RoswaalTestStepDuration>,
+    flakiness: RoswaalTestFlakiness,
+    deprecation_date: Option<DateTime<Utc>>,
+    is_planned: bool,
+    added_by_slack_user_id: Option<String>,
+    platforms: Vec<RoswaalTestPlatform>,
+    variables: Vec<RoswaalTestVariable>,
+    failure_assignee_slack_user_id: Option<String>,
+    source_code: Option<String>,
 }
 
 impl RoswaalTest {
@@ -38,8 +59,78 @@ impl RoswaalTest {
             error_stack_trace,
             unmerged_branch_name,
             last_run_date,
+            screenshots: vec![],
+            durations: vec![],
+            flakiness: RoswaalTestFlakiness::default(),
+            deprecation_date: None,
+            is_planned: false,
+            added_by_slack_user_id: None,
+            platforms: vec![],
+            variables: vec![],
+            failure_assignee_slack_user_id: None,
+            source_code: None,
         }
     }
+
+    /// Returns this test with the screenshots taken during its most recent run.
+    pub fn with_screenshots(mut self, screenshots: Vec<RoswaalTestStepScreenshot>) -> Self {
+        self.screenshots = screenshots;
+        self
+    }
+
+    /// Returns this test with the time spent running each command during its most recent run.
+    pub fn with_durations(mut self, durations: Vec<RoswaalTestStepDuration>) -> Self {
+        self.durations = durations;
+        self
+    }
+
+    /// Returns this test with the outcomes of its most recent runs.
+    pub fn with_flakiness(mut self, flakiness: RoswaalTestFlakiness) -> Self {
+        self.flakiness = flakiness;
+        self
+    }
+
+    /// Returns this test with the date that it was deprecated on.
+    pub fn with_deprecation_date(mut self, deprecation_date: Option<DateTime<Utc>>) -> Self {
+        self.deprecation_date = deprecation_date;
+        self
+    }
+
+    /// Returns this test marked as a planned test, which only has a name and abstract.
+    pub fn with_planned(mut self, is_planned: bool) -> Self {
+        self.is_planned = is_planned;
+        self
+    }
+
+    /// Returns this test attributed to the slack user who added it.
+    pub fn with_added_by_slack_user_id(mut self, user_id: Option<String>) -> Self {
+        self.added_by_slack_user_id = user_id;
+        self
+    }
+
+    /// Returns this test with the slack user who is assigned to triage its failure.
+    pub fn with_failure_assignee_slack_user_id(mut self, user_id: Option<String>) -> Self {
+        self.failure_assignee_slack_user_id = user_id;
+        self
+    }
+
+    /// Returns this test restricted to the specified platforms.
+    pub fn with_platforms(mut self, platforms: Vec<RoswaalTestPlatform>) -> Self {
+        self.platforms = platforms;
+        self
+    }
+
+    /// Returns this test with the variables that replace the placeholders in its steps.
+    pub fn with_variables(mut self, variables: Vec<RoswaalTestVariable>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Returns this test with the source code that it was originally written in.
+    pub fn with_source_code(mut self, source_code: Option<String>) -> Self {
+        self.source_code = source_code;
+        self
+    }
 }
 
 impl RoswaalTest {
@@ -56,16 +147,159 @@ impl RoswaalTest {
     }
 
     pub fn commands(&self) -> Vec<RoswaalTestCommand> {
+        self.ordinal_commands()
+            .into_iter()
+            .map(|(ordinal, c)| RoswaalTestCommand {
+                status: self.command_status(ordinal),
+                command: c.clone(),
+                screenshot: self.screenshot(ordinal).cloned(),
+                duration: self.duration(ordinal).copied(),
+            })
+            .collect()
+    }
+
+    /// Returns each compiled command paired with the ordinal that the test runner reports its
+    /// progress with.
+    ///
+    /// Before launch commands run as a part of the before launch command of the test, so they
+    /// share its ordinal and do not advance the ordinals of the commands after them.
+    pub fn ordinal_commands(
+        &self,
+    ) -> Vec<(RoswaalTestCommandOrdinal, &RoswaalCompiledTestCommand)> {
+        let mut index = 0;
         self.commands
             .iter()
-            .enumerate()
-            .map(|(i, c)| RoswaalTestCommand {
-                status: self.command_status(RoswaalTestCommandOrdinal::new(i as i32)),
-                command: c.clone(),
+            .map(|c| {
+                if c.is_before_launch() {
+                    return (RoswaalTestCommandOrdinal::for_before_launch(), c);
+                }
+                let ordinal = RoswaalTestCommandOrdinal::new(index);
+                index += 1;
+                (ordinal, c)
             })
             .collect()
     }
 
+    /// Rebuilds the roswaal syntax of this test with its requirements and usings after all of its
+    /// other commands, which is the layout used by the specs in the README.
+    pub fn syntax(&self) -> String {
+        let command_name = if self.is_planned {
+            "Planned Test"
+        } else {
+            "New Test"
+        };
+        let mut lines = vec![format!("{}: {}", command_name, self.name())];
+        if let Some(description) = self.description() {
+            lines.push(format!("Abstract: {}", description));
+        }
+        if !self.platforms.is_empty() {
+            let platforms = self
+                .platforms
+                .iter()
+                .map(|p| p.as_str())
+                .collect::<Vec<&str>>();
+            lines.push(format!("Platforms: {}", platforms.join(", ")));
+        }
+        if !self.variables.is_empty() {
+            let variables = self
+                .variables
+                .iter()
+                .map(|v| v.syntax())
+                .collect::<Vec<String>>();
+            lines.push(format!("Variables: {}", variables.join(", ")));
+        }
+        let mut requirement_lines = Vec::new();
+        for command in self.ordinal_commands().into_iter().map(|(_, c)| c) {
+            match command {
+                RoswaalCompiledTestCommand::Step {
+                    label,
+                    name,
+                    requirement,
+                    retries,
+                } => {
+                    let retries = retries
+                        .map(|r| format!(" (retries: {})", r))
+                        .unwrap_or_default();
+                    lines.push(format!("{}: {}{}", label, name, retries));
+                    requirement_lines.push(format!(
+                        "Requirement{}: {}",
+                        label_suffix(label, "Step"),
+                        requirement
+                    ));
+                }
+                RoswaalCompiledTestCommand::SetLocation { location_name } => {
+                    lines.push(format!("Set Location: {}", location_name.raw_name()))
+                }
+                RoswaalCompiledTestCommand::Wait { duration } => {
+                    lines.push(format!("Wait: {}", duration))
+                }
+                RoswaalCompiledTestCommand::Verify {
+                    label,
+                    name,
+                    requirement,
+                } => {
+                    lines.push(format!("{}: {}", label, name));
+                    // NB: A verification without a "Using" line uses its own description as the
+                    // requirement.
+                    if requirement != name {
+                        requirement_lines.push(format!(
+                            "Using{}: {}",
+                            label_suffix(label, "Verify"),
+                            requirement
+                        ));
+                    }
+                }
+                RoswaalCompiledTestCommand::BeforeLaunch { name } => {
+                    lines.push(format!("Before Launch: {}", name))
+                }
+                RoswaalCompiledTestCommand::RequiresTest { test_name, .. } => {
+                    lines.push(format!("Requires Test: {}", test_name))
+                }
+            }
+        }
+        lines.append(&mut requirement_lines);
+        lines.join("\n")
+    }
+
+    /// Returns the screenshot taken after running the command with the specified ordinal during
+    /// the most recent run of this test.
+    pub fn screenshot(
+        &self,
+        ordinal: RoswaalTestCommandOrdinal,
+    ) -> Option<&RoswaalTestStepScreenshot> {
+        self.screenshots
+            .iter()
+            .find(|s| s.command_ordinal() == ordinal)
+    }
+
+    /// Returns the time spent running the command with the specified ordinal during the most
+    /// recent run of this test.
+    pub fn duration(&self, ordinal: RoswaalTestCommandOrdinal) -> Option<&RoswaalTestStepDuration> {
+        self.durations
+            .iter()
+            .find(|d| d.command_ordinal() == ordinal)
+    }
+
+    /// Returns the time spent running each command during the most recent run of this test.
+    pub fn durations(&self) -> &[RoswaalTestStepDuration] {
+        &self.durations
+    }
+
+    /// Returns the sum of the command durations from the most recent run of this test, or None if
+    /// no durations were reported.
+    pub fn total_duration(&self) -> Option<Duration> {
+        if self.durations.is_empty() {
+            return None;
+        }
+        Some(self.durations.iter().map(|d| d.duration()).sum())
+    }
+
+    /// Returns the duration of the command that took the longest to run during the most recent
+    /// run of this test.
+    pub fn slowest_duration(&self) -> Option<&RoswaalTestStepDuration> {
+        self.durations.iter().max_by_key(|d| d.milliseconds())
+    }
+
     pub fn command_status(&self, ordinal: RoswaalTestCommandOrdinal) -> RoswaalTestCommandStatus {
         match (self.last_run_date(), self.command_failure_ordinal()) {
             (None, _) => RoswaalTestCommandStatus::Idle,
@@ -92,10 +326,72 @@ impl RoswaalTest {
         self.last_run_date
     }
 
+    /// The date that this test was deprecated on, if it has been deprecated.
+    ///
+    /// Deprecated tests still exist in the frontend repo until their grace period ends.
+    pub fn deprecation_date(&self) -> Option<DateTime<Utc>> {
+        self.deprecation_date
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_date.is_some()
+    }
+
+    /// Returns true if this test was added with "Planned Test", and has no steps or generated
+    /// code yet.
+    pub fn is_planned(&self) -> bool {
+        self.is_planned
+    }
+
+    /// Returns the id of the slack user who added this test, if known.
+    ///
+    /// Tests added before their adder was recorded have no adder.
+    pub fn added_by_slack_user_id(&self) -> Option<&String> {
+        self.added_by_slack_user_id.as_ref()
+    }
+
+    /// Returns the id of the slack user who is assigned to triage the failure of this test, if
+    /// anyone.
+    pub fn failure_assignee_slack_user_id(&self) -> Option<&String> {
+        self.failure_assignee_slack_user_id.as_ref()
+    }
+
+    /// Returns the platforms that this test runs on, or an empty slice if it runs on every
+    /// platform.
+    pub fn platforms(&self) -> &[RoswaalTestPlatform] {
+        &self.platforms
+    }
+
+    /// Returns true if this test runs on `platform`.
+    pub fn runs_on(&self, platform: RoswaalTestPlatform) -> bool {
+        self.platforms.is_empty() || self.platforms.contains(&platform)
+    }
+
+    /// Returns the variables declared by this test in the order they were declared.
+    pub fn variables(&self) -> &[RoswaalTestVariable] {
+        &self.variables
+    }
+
+    /// Returns the source code that this test was originally written in, or None if the test
+    /// was saved before its source code was stored.
+    pub fn source_code(&self) -> Option<&str> {
+        self.source_code.as_deref()
+    }
+
+    /// Returns the source code that this test was originally written in, or its rebuilt syntax
+    /// if the source code was not stored.
+    pub fn original_syntax(&self) -> String {
+        self.source_code.clone().unwrap_or_else(|| self.syntax())
+    }
+
     pub fn unmerged_branch_name(&self) -> Option<&RoswaalOwnedGitBranchName> {
         self.unmerged_branch_name.as_ref()
     }
 
+    pub fn flakiness(&self) -> &RoswaalTestFlakiness {
+        &self.flakiness
+    }
+
     pub fn progress_status(&self) -> RoswaalTestProgressStatus {
         if self.last_run_date().is_none() {
             RoswaalTestProgressStatus::Idle
@@ -111,10 +407,45 @@ impl RoswaalTest {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Returns the label of a command without its command name (eg. " 1" for "Step 1").
+fn label_suffix<'a>(label: &'a str, command_name: &str) -> &'a str {
+    label.strip_prefix(command_name).unwrap_or(label)
+}
+
+/// Tests are serialized with camelCase keys to match the progress uploads sent by the test
+/// runner, and include the progress status of the test and each of its commands.
+impl Serialize for RoswaalTest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RoswaalTest", 11)?;
+        state.serialize_field("name", &self.name)?;
+        state.serialize_field("description", &self.description)?;
+        state.serialize_field("status", &self.progress_status())?;
+        state.serialize_field("commands", &self.commands())?;
+        state.serialize_field("commandFailureOrdinal", &self.command_failure_ordinal)?;
+        state.serialize_field("errorMessage", &self.error_message)?;
+        state.serialize_field("errorStackTrace", &self.error_stack_trace)?;
+        state.serialize_field("unmergedBranchName", &self.unmerged_branch_name)?;
+        state.serialize_field(
+            "lastRunDate",
+            &self.last_run_date.map(|date| date.to_rfc3339()),
+        )?;
+        state.serialize_field("platforms", &self.platforms)?;
+        state.serialize_field("variables", &self.variables)?;
+        state.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct RoswaalTestCommand {
     status: RoswaalTestCommandStatus,
     command: RoswaalCompiledTestCommand,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    screenshot: Option<RoswaalTestStepScreenshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<RoswaalTestStepDuration>,
 }
 
 impl RoswaalTestCommand {
@@ -125,9 +456,14 @@ impl RoswaalTestCommand {
     pub fn compiled_command(&self) -> &RoswaalCompiledTestCommand {
         &self.command
     }
+
+    pub fn screenshot(&self) -> Option<&RoswaalTestStepScreenshot> {
+        self.screenshot.as_ref()
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RoswaalTestCommandStatus {
     Passed,
     Failed,
@@ -138,12 +474,21 @@ pub type RoswaalTestProgressStatus = RoswaalTestCommandStatus;
 
 #[cfg(test)]
 mod tests {
-    use chrono::Utc;
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
 
     use crate::{
-        language::test::RoswaalCompiledTestCommand,
+        language::{
+            compiler::{RoswaalCompile, RoswaalCompileContext},
+            platform::RoswaalTestPlatform,
+            test::{RoswaalCompiledTest, RoswaalCompiledTestCommand},
+            variable::RoswaalTestVariable,
+        },
         tests_data::{
             ordinal::RoswaalTestCommandOrdinal,
+            progress::{RoswaalTestStepDuration, RoswaalTestStepScreenshot},
             test::{RoswaalTestCommand, RoswaalTestCommandStatus},
         },
     };
@@ -160,11 +505,13 @@ mod tests {
                     label: "Step 1".to_string(),
                     name: "Thing".to_string(),
                     requirement: "Thing".to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "Thing 2".to_string(),
                     requirement: "Thing 2".to_string(),
+                    retries: None,
                 },
             ],
             Some(RoswaalTestCommandOrdinal::new(1)),
@@ -197,11 +544,13 @@ mod tests {
                     label: "Step 1".to_string(),
                     name: "Thing".to_string(),
                     requirement: "Thing".to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "Thing 2".to_string(),
                     requirement: "Thing 2".to_string(),
+                    retries: None,
                 },
             ],
             None,
@@ -224,6 +573,107 @@ mod tests {
         )
     }
 
+    #[test]
+    fn syntax_with_quoted_labels_compiles_to_the_same_commands() {
+        let compiled_commands = vec![
+            RoswaalCompiledTestCommand::Step {
+                label: "Step \"sign in: email\"".to_string(),
+                name: "Sign in".to_string(),
+                requirement: "Tap sign in".to_string(),
+                retries: None,
+            },
+            RoswaalCompiledTestCommand::Verify {
+                label: "Verify \"signed: in\"".to_string(),
+                name: "I am signed in".to_string(),
+                requirement: "Check the profile".to_string(),
+            },
+        ];
+        let test = RoswaalTest::new(
+            "Test".to_string(),
+            None,
+            compiled_commands.clone(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let expected_syntax = "\
+New Test: Test
+Step \"sign in: email\": Sign in
+Verify \"signed: in\": I am signed in
+Requirement \"sign in: email\": Tap sign in
+Using \"signed: in\": Check the profile";
+        assert_eq!(test.syntax(), expected_syntax);
+        let compiled_test =
+            RoswaalCompiledTest::compile(&test.syntax(), RoswaalCompileContext::empty()).unwrap();
+        assert_eq!(compiled_test.commands(), &compiled_commands)
+    }
+
+    #[test]
+    fn syntax_with_platforms_compiles_to_the_same_platforms() {
+        let platforms = vec![RoswaalTestPlatform::Android, RoswaalTestPlatform::Ios];
+        let test = RoswaalTest::new(
+            "Test".to_string(),
+            Some("A test".to_string()),
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Thing".to_string(),
+                requirement: "Thing".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .with_platforms(platforms.clone());
+        let expected_syntax = "\
+New Test: Test
+Abstract: A test
+Platforms: android, ios
+Step 1: Thing
+Requirement 1: Thing";
+        assert_eq!(test.syntax(), expected_syntax);
+        let compiled_test =
+            RoswaalCompiledTest::compile(&test.syntax(), RoswaalCompileContext::empty()).unwrap();
+        assert_eq!(compiled_test.platforms(), &platforms)
+    }
+
+    #[test]
+    fn syntax_with_variables_compiles_to_the_same_variables() {
+        let variables = vec![
+            RoswaalTestVariable::new("eventName", "Pickup Basketball"),
+            RoswaalTestVariable::new("host", "Roswaal"),
+        ];
+        let test = RoswaalTest::new(
+            "Test".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Join {{eventName}}".to_string(),
+                requirement: "Join {{eventName}} hosted by {{host}}".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .with_variables(variables.clone());
+        let expected_syntax = "\
+New Test: Test
+Variables: eventName = Pickup Basketball, host = Roswaal
+Step 1: Join {{eventName}}
+Requirement 1: Join {{eventName}} hosted by {{host}}";
+        assert_eq!(test.syntax(), expected_syntax);
+        let compiled_test =
+            RoswaalCompiledTest::compile(&test.syntax(), RoswaalCompileContext::empty()).unwrap();
+        assert_eq!(compiled_test.variables(), &variables)
+    }
+
     #[test]
     fn commands() {
         let test = RoswaalTest::new(
@@ -234,11 +684,13 @@ mod tests {
                     label: "Step 1".to_string(),
                     name: "Thing".to_string(),
                     requirement: "Thing".to_string(),
+                    retries: None,
                 },
                 RoswaalCompiledTestCommand::Step {
                     label: "Step 2".to_string(),
                     name: "Thing 2".to_string(),
                     requirement: "Thing 2".to_string(),
+                    retries: None,
                 },
             ],
             Some(RoswaalTestCommandOrdinal::new(1)),
@@ -247,16 +699,167 @@ mod tests {
             None,
             Some(Utc::now()),
         );
+        let screenshot = RoswaalTestStepScreenshot::new(
+            RoswaalTestCommandOrdinal::new(1),
+            Some("https://example.com/screenshot.png".to_string()),
+            None,
+        );
+        let duration = RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(0), 1500);
+        let test = test
+            .with_screenshots(vec![screenshot.clone()])
+            .with_durations(vec![duration]);
         let expected_commands = vec![
             RoswaalTestCommand {
                 status: RoswaalTestCommandStatus::Passed,
                 command: test.commands[0].clone(),
+                screenshot: None,
+                duration: Some(duration),
             },
             RoswaalTestCommand {
                 status: RoswaalTestCommandStatus::Failed,
                 command: test.commands[1].clone(),
+                screenshot: Some(screenshot),
+                duration: None,
             },
         ];
         assert_eq!(test.commands(), expected_commands)
     }
+
+    #[test]
+    fn before_launch_commands_share_the_before_launch_ordinal() {
+        let test = RoswaalTest::new(
+            "Test".to_string(),
+            None,
+            vec![
+                RoswaalCompiledTestCommand::BeforeLaunch {
+                    name: "Reset permissions".to_string(),
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Thing".to_string(),
+                    requirement: "Thing".to_string(),
+                    retries: None,
+                },
+            ],
+            Some(RoswaalTestCommandOrdinal::new(0)),
+            None,
+            None,
+            None,
+            Some(Utc::now()),
+        );
+        let ordinals = test
+            .ordinal_commands()
+            .into_iter()
+            .map(|(ordinal, _)| ordinal)
+            .collect::<Vec<RoswaalTestCommandOrdinal>>();
+        assert_eq!(
+            ordinals,
+            vec![
+                RoswaalTestCommandOrdinal::for_before_launch(),
+                RoswaalTestCommandOrdinal::new(0)
+            ]
+        );
+        let statuses = test
+            .commands()
+            .iter()
+            .map(|c| c.status())
+            .collect::<Vec<RoswaalTestCommandStatus>>();
+        assert_eq!(
+            statuses,
+            vec![
+                RoswaalTestCommandStatus::Passed,
+                RoswaalTestCommandStatus::Failed
+            ]
+        )
+    }
+
+    #[test]
+    fn total_and_slowest_durations() {
+        let test = RoswaalTest::new(
+            "Test".to_string(),
+            None,
+            vec![RoswaalCompiledTestCommand::Step {
+                label: "Step 1".to_string(),
+                name: "Thing".to_string(),
+                requirement: "Thing".to_string(),
+                retries: None,
+            }],
+            None,
+            None,
+            None,
+            None,
+            Some(Utc::now()),
+        );
+        assert_eq!(test.total_duration(), None);
+        assert_eq!(test.slowest_duration(), None);
+        let durations = vec![
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::for_before_launch(), 4000),
+            RoswaalTestStepDuration::new(RoswaalTestCommandOrdinal::new(0), 2500),
+        ];
+        let test = test.with_durations(durations.clone());
+        assert_eq!(test.total_duration(), Some(Duration::from_millis(6500)));
+        assert_eq!(test.slowest_duration(), Some(&durations[0]))
+    }
+
+    #[test]
+    fn serialize() {
+        let last_run_date = DateTime::parse_from_rfc3339("2024-06-01T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let test = RoswaalTest::new(
+            "Test".to_string(),
+            Some("A test".to_string()),
+            vec![
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 1".to_string(),
+                    name: "Thing".to_string(),
+                    requirement: "Thing".to_string(),
+                    retries: None,
+                },
+                RoswaalCompiledTestCommand::Step {
+                    label: "Step 2".to_string(),
+                    name: "Thing 2".to_string(),
+                    requirement: "Thing 2".to_string(),
+                    retries: None,
+                },
+            ],
+            Some(RoswaalTestCommandOrdinal::new(1)),
+            Some("WTF".to_string()),
+            Some("Stack Trace".to_string()),
+            None,
+            Some(last_run_date),
+        )
+        .with_platforms(vec![RoswaalTestPlatform::Ios])
+        .with_variables(vec![RoswaalTestVariable::new(
+            "eventName",
+            "Pickup Basketball",
+        )]);
+        let expected_json = json!({
+            "name": "Test",
+            "description": "A test",
+            "status": "failed",
+            "commands": [
+                {
+                    "status": "passed",
+                    "command": {
+                        "Step": { "label": "Step 1", "name": "Thing", "requirement": "Thing" }
+                    }
+                },
+                {
+                    "status": "failed",
+                    "command": {
+                        "Step": { "label": "Step 2", "name": "Thing 2", "requirement": "Thing 2" }
+                    }
+                }
+            ],
+            "commandFailureOrdinal": 2,
+            "errorMessage": "WTF",
+            "errorStackTrace": "Stack Trace",
+            "unmergedBranchName": null,
+            "lastRunDate": "2024-06-01T12:00:00+00:00",
+            "platforms": ["ios"],
+            "variables": [{ "name": "eventName", "value": "Pickup Basketball" }]
+        });
+        assert_eq!(serde_json::to_value(&test).unwrap(), expected_json)
+    }
 }
@@ -0,0 +1,898 @@
+use std::{env, error::Error, fmt::Display, path::Path, str::FromStr, time::Duration};
+
+use anyhow::Result;
+use log::info;
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+use crate::git::branch_name::RoswaalGitBranchNamespace;
+
+use super::retry::RoswaalRetryPolicy;
+
+/// The path of the config file that is loaded at startup.
+pub const ROSWAAL_CONFIG_PATH: &str = "./roswaal.toml";
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+static CURRENT_CONFIG: OnceCell<RoswaalConfig> = OnceCell::new();
+
+/// Per-deployment configuration of the repositories, slack channels, and slack users that the
+/// tool works with.
+///
+/// The configuration is read from a `roswaal.toml` file, and each value can be overridden by an
+/// environment variable. Values that are set in neither place fall back to the TiF deployment.
+///
+/// ```toml
+/// [github]
+/// frontend_repository = "tifapp/FitnessProject"   # ROSWAAL_FRONTEND_REPOSITORY
+/// frontend_base_branch = "development"            # ROSWAAL_FRONTEND_BASE_BRANCH
+/// test_targets = ["tifapp/FitnessProjectAndroid@main"] # ROSWAAL_TEST_TARGETS (comma separated)
+/// pull_request_mode = "staged"                    # ROSWAAL_PULL_REQUEST_MODE
+/// branch_prefix = "bots/roswaal"                  # ROSWAAL_BRANCH_PREFIX
+/// branch_separator = "/"                          # ROSWAAL_BRANCH_SEPARATOR
+///
+/// [slack]
+/// pull_request_channel_id = "C01B7FFKDCP"         # SLACK_PULL_REQUEST_CHANNEL_ID
+/// test_digest_channel_id = "C0123456789"          # SLACK_TEST_DIGEST_CHANNEL_ID
+/// test_failure_channel_id = "C0987654321"         # SLACK_TEST_FAILURE_CHANNEL_ID
+/// maintainer_user_id = "U04K0DX9HC6"              # SLACK_MAINTAINER_USER_ID
+/// language = "ja"                                 # SLACK_LANGUAGE
+///
+/// [retry]
+/// max_attempts = 3                                # ROSWAAL_RETRY_MAX_ATTEMPTS
+/// initial_delay_ms = 500                          # ROSWAAL_RETRY_INITIAL_DELAY_MS
+///
+/// [git]
+/// operation_timeout_seconds = 120                 # ROSWAAL_GIT_OPERATION_TIMEOUT_SECONDS
+/// clean_ignore_patterns = ["*.mp4", "assets/"]    # ROSWAAL_GIT_CLEAN_IGNORE_PATTERNS (comma separated)
+/// max_clean_file_size_mb = 50                     # ROSWAAL_GIT_MAX_CLEAN_FILE_SIZE_MB
+///
+/// [tests]
+/// deprecation_grace_period_days = 14              # ROSWAAL_DEPRECATION_GRACE_PERIOD_DAYS
+/// suite_time_budget_minutes = 60                  # ROSWAAL_SUITE_TIME_BUDGET_MINUTES
+///
+/// [runner]
+/// webhook_url = "https://ci.example.com/roswaal"  # ROSWAAL_RUNNER_WEBHOOK_URL
+///
+/// [generation]
+/// formatter_command = "npx prettier --write"      # ROSWAAL_FORMATTER_COMMAND
+///
+/// [server]
+/// public_url = "https://roswaal.example.com"      # ROSWAAL_PUBLIC_URL
+///
+/// [pull_requests]
+/// draft = true                                    # ROSWAAL_PULL_REQUEST_DRAFT
+/// labels = ["acceptance-tests"]                   # ROSWAAL_PULL_REQUEST_LABELS (comma separated)
+/// assignees = ["roswaaltifbot"]                   # ROSWAAL_PULL_REQUEST_ASSIGNEES (comma separated)
+/// reviewers = ["mhayes853"]                       # ROSWAAL_PULL_REQUEST_REVIEWERS (comma separated)
+/// body_template = "{{summary}}"                   # ROSWAAL_PULL_REQUEST_BODY_TEMPLATE
+/// ```
+///
+/// Unknown tables and keys, and values of the wrong type, are rejected.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RoswaalConfig {
+    frontend_owner: String,
+    frontend_repo: String,
+    frontend_base_branch_name: String,
+    test_targets: Vec<String>,
+    pull_request_mode: RoswaalPullRequestMode,
+    branch_namespace: RoswaalGitBranchNamespace,
+    pull_request_channel_id: String,
+    test_digest_channel_id: Option<String>,
+    test_failure_channel_id: Option<String>,
+    maintainer_slack_user_id: String,
+    slack_language: RoswaalSlackLanguage,
+    retry_policy: RoswaalRetryPolicy,
+    git_operation_timeout: Duration,
+    git_clean_ignore_patterns: Vec<String>,
+    max_git_clean_file_size_mb: u32,
+    deprecation_grace_period: Duration,
+    suite_time_budget: Option<Duration>,
+    test_runner_webhook_url: Option<String>,
+    formatter_command: Option<String>,
+    public_url: Option<String>,
+    draft_pull_requests: bool,
+    pull_request_labels: Vec<String>,
+    pull_request_assignees: Vec<String>,
+    pull_request_reviewers: Vec<String>,
+    pull_request_body_template: Option<String>,
+}
+
+impl Default for RoswaalConfig {
+    fn default() -> Self {
+        Self {
+            frontend_owner: "tifapp".to_string(),
+            frontend_repo: "FitnessProject".to_string(),
+            frontend_base_branch_name: "development".to_string(),
+            test_targets: vec![],
+            pull_request_mode: RoswaalPullRequestMode::Immediate,
+            branch_namespace: RoswaalGitBranchNamespace::default(),
+            pull_request_channel_id: "C01B7FFKDCP".to_string(),
+            test_digest_channel_id: None,
+            test_failure_channel_id: None,
+            maintainer_slack_user_id: "U04K0DX9HC6".to_string(),
+            slack_language: RoswaalSlackLanguage::English,
+            retry_policy: RoswaalRetryPolicy::default(),
+            git_operation_timeout: Duration::from_secs(120),
+            git_clean_ignore_patterns: vec![],
+            max_git_clean_file_size_mb: 50,
+            deprecation_grace_period: Duration::from_secs(14 * SECONDS_PER_DAY),
+            suite_time_budget: None,
+            test_runner_webhook_url: None,
+            formatter_command: None,
+            public_url: None,
+            draft_pull_requests: false,
+            pull_request_labels: vec![],
+            pull_request_assignees: vec![],
+            pull_request_reviewers: vec![],
+            pull_request_body_template: None,
+        }
+    }
+}
+
+impl RoswaalConfig {
+    /// Loads the config file at `ROSWAAL_CONFIG_PATH` with environment variable overrides, and
+    /// makes it the current config.
+    ///
+    /// This must be called at startup before the config is used, and it is fine for the config
+    /// file to not exist.
+    pub async fn bootstrap() -> Result<()> {
+        let config = Self::load(ROSWAAL_CONFIG_PATH).await?;
+        _ = CURRENT_CONFIG.set(config);
+        Ok(())
+    }
+
+    /// Returns the current config, or the default config if `bootstrap` has not been called.
+    pub fn current() -> &'static Self {
+        CURRENT_CONFIG.get_or_init(Self::default)
+    }
+
+    async fn load(path: &str) -> Result<Self> {
+        let toml = if Path::new(path).exists() {
+            info!("Loading config from {}.", path);
+            tokio::fs::read_to_string(path).await?
+        } else {
+            String::new()
+        };
+        Ok(Self::from_toml_str(&toml, |name| env::var(name).ok())?)
+    }
+
+    /// Parses a config from the contents of a config file, where values returned by `env` for an
+    /// environment variable name take precedence over the values in the file.
+    pub fn from_toml_str(
+        toml: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, RoswaalConfigParsingError> {
+        let file = toml::from_str::<RoswaalConfigFile>(toml)
+            .map_err(|error| RoswaalConfigParsingError::invalid_toml(toml, error))?;
+        let mut config = Self::default();
+        let string = |value: Option<String>, env_name: &str| env(env_name).or(value);
+        if let Some(repository) = string(
+            file.github.frontend_repository,
+            "ROSWAAL_FRONTEND_REPOSITORY",
+        ) {
+            let (owner, repo) = repository
+                .split_once('/')
+                .filter(|(owner, repo)| !owner.is_empty() && !repo.is_empty())
+                .ok_or_else(|| RoswaalConfigParsingError::InvalidValue {
+                    key: "github.frontend_repository".to_string(),
+                })?;
+            config.frontend_owner = owner.to_string();
+            config.frontend_repo = repo.to_string();
+        }
+        if let Some(branch) = string(
+            file.github.frontend_base_branch,
+            "ROSWAAL_FRONTEND_BASE_BRANCH",
+        ) {
+            config.frontend_base_branch_name = branch;
+        }
+        if let Some(mode) = string(file.github.pull_request_mode, "ROSWAAL_PULL_REQUEST_MODE") {
+            config.pull_request_mode = match mode.trim() {
+                "immediate" => RoswaalPullRequestMode::Immediate,
+                "staged" => RoswaalPullRequestMode::Staged,
+                _ => {
+                    return Err(RoswaalConfigParsingError::InvalidValue {
+                        key: "github.pull_request_mode".to_string(),
+                    })
+                }
+            };
+        }
+        let branch_prefix = string(file.github.branch_prefix, "ROSWAAL_BRANCH_PREFIX");
+        let branch_separator = string(file.github.branch_separator, "ROSWAAL_BRANCH_SEPARATOR");
+        if branch_prefix.is_some() || branch_separator.is_some() {
+            let separator = match branch_separator.as_deref().map(str::trim) {
+                None => '-',
+                Some("-") => '-',
+                Some("/") => '/',
+                Some(_) => {
+                    return Err(RoswaalConfigParsingError::InvalidValue {
+                        key: "github.branch_separator".to_string(),
+                    })
+                }
+            };
+            let prefix = branch_prefix.unwrap_or_else(|| "roswaal".to_string());
+            config.branch_namespace = RoswaalGitBranchNamespace::new(prefix.trim(), separator)
+                .map_err(|_| RoswaalConfigParsingError::InvalidValue {
+                    key: "github.branch_prefix".to_string(),
+                })?;
+        }
+        if let Some(id) = string(
+            file.slack.pull_request_channel_id,
+            "SLACK_PULL_REQUEST_CHANNEL_ID",
+        ) {
+            config.pull_request_channel_id = id;
+        }
+        config.test_digest_channel_id = string(
+            file.slack.test_digest_channel_id,
+            "SLACK_TEST_DIGEST_CHANNEL_ID",
+        );
+        config.test_failure_channel_id = string(
+            file.slack.test_failure_channel_id,
+            "SLACK_TEST_FAILURE_CHANNEL_ID",
+        );
+        if let Some(id) = string(file.slack.maintainer_user_id, "SLACK_MAINTAINER_USER_ID") {
+            config.maintainer_slack_user_id = id;
+        }
+        if let Some(language) = string(file.slack.language, "SLACK_LANGUAGE") {
+            config.slack_language = match language.trim() {
+                "en" => RoswaalSlackLanguage::English,
+                "ja" => RoswaalSlackLanguage::Japanese,
+                _ => {
+                    return Err(RoswaalConfigParsingError::InvalidValue {
+                        key: "slack.language".to_string(),
+                    })
+                }
+            };
+        }
+        config.test_runner_webhook_url =
+            string(file.runner.webhook_url, "ROSWAAL_RUNNER_WEBHOOK_URL");
+        config.formatter_command = string(
+            file.generation.formatter_command,
+            "ROSWAAL_FORMATTER_COMMAND",
+        )
+        .map(|command| command.trim().to_string())
+        .filter(|command| !command.is_empty());
+        config.public_url = string(file.server.public_url, "ROSWAAL_PUBLIC_URL")
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty());
+        config.pull_request_body_template = string(
+            file.pull_requests.body_template,
+            "ROSWAAL_PULL_REQUEST_BODY_TEMPLATE",
+        )
+        .filter(|template| !template.trim().is_empty());
+        let max_attempts = env_or(
+            env("ROSWAAL_RETRY_MAX_ATTEMPTS"),
+            file.retry.max_attempts,
+            "retry.max_attempts",
+        )?;
+        let initial_delay_ms = env_or(
+            env("ROSWAAL_RETRY_INITIAL_DELAY_MS"),
+            file.retry.initial_delay_ms,
+            "retry.initial_delay_ms",
+        )?;
+        config.retry_policy = RoswaalRetryPolicy::new(
+            max_attempts.unwrap_or(config.retry_policy.max_attempts()),
+            initial_delay_ms
+                .map(|ms| Duration::from_millis(ms as u64))
+                .unwrap_or(config.retry_policy.initial_delay()),
+        );
+        let git_timeout_key = "git.operation_timeout_seconds";
+        match env_or(
+            env("ROSWAAL_GIT_OPERATION_TIMEOUT_SECONDS"),
+            file.git.operation_timeout_seconds,
+            git_timeout_key,
+        )? {
+            Some(0) => {
+                return Err(RoswaalConfigParsingError::InvalidValue {
+                    key: git_timeout_key.to_string(),
+                })
+            }
+            Some(seconds) => config.git_operation_timeout = Duration::from_secs(seconds as u64),
+            None => {}
+        }
+        let max_clean_file_size_key = "git.max_clean_file_size_mb";
+        match env_or(
+            env("ROSWAAL_GIT_MAX_CLEAN_FILE_SIZE_MB"),
+            file.git.max_clean_file_size_mb,
+            max_clean_file_size_key,
+        )? {
+            Some(0) => {
+                return Err(RoswaalConfigParsingError::InvalidValue {
+                    key: max_clean_file_size_key.to_string(),
+                })
+            }
+            Some(megabytes) => config.max_git_clean_file_size_mb = megabytes,
+            None => {}
+        }
+        if let Some(days) = env_or(
+            env("ROSWAAL_DEPRECATION_GRACE_PERIOD_DAYS"),
+            file.tests.deprecation_grace_period_days,
+            "tests.deprecation_grace_period_days",
+        )? {
+            config.deprecation_grace_period = Duration::from_secs(days as u64 * SECONDS_PER_DAY);
+        }
+        let suite_time_budget_key = "tests.suite_time_budget_minutes";
+        match env_or(
+            env("ROSWAAL_SUITE_TIME_BUDGET_MINUTES"),
+            file.tests.suite_time_budget_minutes,
+            suite_time_budget_key,
+        )? {
+            Some(0) => {
+                return Err(RoswaalConfigParsingError::InvalidValue {
+                    key: suite_time_budget_key.to_string(),
+                })
+            }
+            Some(minutes) => {
+                config.suite_time_budget = Some(Duration::from_secs(minutes as u64 * 60))
+            }
+            None => {}
+        }
+        if let Some(draft) = env_or(
+            env("ROSWAAL_PULL_REQUEST_DRAFT"),
+            file.pull_requests.draft,
+            "pull_requests.draft",
+        )? {
+            config.draft_pull_requests = draft;
+        }
+        let array = |items: Option<Vec<String>>, env_name: &str| match env(env_name) {
+            Some(value) => value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect(),
+            None => items.unwrap_or_default(),
+        };
+        config.test_targets = array(file.github.test_targets, "ROSWAAL_TEST_TARGETS");
+        config.git_clean_ignore_patterns = array(
+            file.git.clean_ignore_patterns,
+            "ROSWAAL_GIT_CLEAN_IGNORE_PATTERNS",
+        );
+        config.pull_request_labels =
+            array(file.pull_requests.labels, "ROSWAAL_PULL_REQUEST_LABELS");
+        config.pull_request_assignees = array(
+            file.pull_requests.assignees,
+            "ROSWAAL_PULL_REQUEST_ASSIGNEES",
+        );
+        config.pull_request_reviewers = array(
+            file.pull_requests.reviewers,
+            "ROSWAAL_PULL_REQUEST_REVIEWERS",
+        );
+        Ok(config)
+    }
+}
+
+impl RoswaalConfig {
+    /// The GitHub owner of the main frontend repo (eg. tifapp).
+    pub fn frontend_owner(&self) -> &str {
+        &self.frontend_owner
+    }
+
+    /// The name of the main frontend repo on GitHub, which is also the directory it is cloned to
+    /// (eg. FitnessProject).
+    pub fn frontend_repo(&self) -> &str {
+        &self.frontend_repo
+    }
+
+    /// The branch of the main frontend repo that PRs are opened against (eg. development).
+    pub fn frontend_base_branch_name(&self) -> &str {
+        &self.frontend_base_branch_name
+    }
+
+    /// The additional repos that test code is generated in, in the form
+    /// `<owner>/<repo>@<base_branch>`.
+    pub fn test_targets(&self) -> &[String] {
+        &self.test_targets
+    }
+
+    /// Whether PRs are opened as soon as their branch is pushed, or staged to be opened later.
+    pub fn pull_request_mode(&self) -> RoswaalPullRequestMode {
+        self.pull_request_mode
+    }
+
+    /// The prefix and separator of the names of the branches created by this tool.
+    pub fn branch_namespace(&self) -> &RoswaalGitBranchNamespace {
+        &self.branch_namespace
+    }
+
+    /// The id of the slack channel where PRs are posted for approval.
+    pub fn pull_request_channel_id(&self) -> &str {
+        &self.pull_request_channel_id
+    }
+
+    /// The id of the slack channel to send the test digest to, if any.
+    pub fn test_digest_channel_id(&self) -> Option<&str> {
+        self.test_digest_channel_id.as_deref()
+    }
+
+    /// The id of the slack channel to post the failures reported to `/progress` in, if any.
+    pub fn test_failure_channel_id(&self) -> Option<&str> {
+        self.test_failure_channel_id.as_deref()
+    }
+
+    /// The id of the slack user to mention when something needs to be fixed by hand.
+    pub fn maintainer_slack_user_id(&self) -> &str {
+        &self.maintainer_slack_user_id
+    }
+
+    /// The language of the slack workspace that responses are written in.
+    pub fn slack_language(&self) -> RoswaalSlackLanguage {
+        self.slack_language
+    }
+
+    /// The policy for retrying git and GitHub operations that fail because of a flaky network.
+    pub fn retry_policy(&self) -> &RoswaalRetryPolicy {
+        &self.retry_policy
+    }
+
+    /// The longest that a single git operation can run before it is cancelled, so that a hung
+    /// connection to the remote cannot hold the repository forever.
+    pub fn git_operation_timeout(&self) -> Duration {
+        self.git_operation_timeout
+    }
+
+    /// The patterns of the untracked files that are kept when a local clone is cleaned.
+    pub fn git_clean_ignore_patterns(&self) -> &[String] {
+        &self.git_clean_ignore_patterns
+    }
+
+    /// The size in megabytes above which untracked files are only deleted from a local clone when
+    /// explicitly requested.
+    pub fn max_git_clean_file_size_mb(&self) -> u32 {
+        self.max_git_clean_file_size_mb
+    }
+
+    /// How long deprecated tests are kept before their code is removed.
+    pub fn deprecation_grace_period(&self) -> Duration {
+        self.deprecation_grace_period
+    }
+
+    /// The estimated runtime that the suite of merged tests should fit in, if any.
+    ///
+    /// Adding tests that push the estimated runtime of the suite past this budget is allowed, but
+    /// the slack message warns about it.
+    pub fn suite_time_budget(&self) -> Option<Duration> {
+        self.suite_time_budget
+    }
+
+    /// The url that is called to run the tests added by a merged branch, if any.
+    pub fn test_runner_webhook_url(&self) -> Option<&str> {
+        self.test_runner_webhook_url.as_deref()
+    }
+
+    /// The command that formats the generated code in the main frontend repo (eg. `npx prettier
+    /// --write`), if any.
+    pub fn formatter_command(&self) -> Option<&str> {
+        self.formatter_command.as_deref()
+    }
+
+    /// The url that the http server can be reached at without a trailing slash, if any, which
+    /// is used to link to its endpoints from slack.
+    pub fn public_url(&self) -> Option<&str> {
+        self.public_url.as_deref()
+    }
+
+    /// Whether PRs are opened as drafts.
+    pub fn draft_pull_requests(&self) -> bool {
+        self.draft_pull_requests
+    }
+
+    /// The labels that are added to each opened PR.
+    pub fn pull_request_labels(&self) -> &[String] {
+        &self.pull_request_labels
+    }
+
+    /// The GitHub users that each opened PR is assigned to.
+    pub fn pull_request_assignees(&self) -> &[String] {
+        &self.pull_request_assignees
+    }
+
+    /// The GitHub users that are requested to review each opened PR.
+    pub fn pull_request_reviewers(&self) -> &[String] {
+        &self.pull_request_reviewers
+    }
+
+    /// The template that the body of each opened PR is rendered with, if any.
+    pub fn pull_request_body_template(&self) -> Option<&str> {
+        self.pull_request_body_template.as_deref()
+    }
+}
+
+/// When the PRs for the branches pushed by this tool are opened.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoswaalPullRequestMode {
+    /// PRs are opened right after their branch is pushed.
+    Immediate,
+    /// PRs are saved after their branch is pushed, and are only opened by `/open-pending-prs`,
+    /// so that the generated code can be reviewed locally first.
+    Staged,
+}
+
+/// The languages that slack responses can be written in.
+///
+/// Each deployment serves a single slack workspace, so the language applies to every response.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoswaalSlackLanguage {
+    /// `en`
+    English,
+    /// `ja`
+    Japanese,
+}
+
+/// An error that occurs when a config file is invalid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RoswaalConfigParsingError {
+    InvalidToml {
+        line_number: Option<usize>,
+        message: String,
+    },
+    InvalidValue {
+        key: String,
+    },
+}
+
+impl RoswaalConfigParsingError {
+    fn invalid_toml(toml: &str, error: toml::de::Error) -> Self {
+        let line_number = error
+            .span()
+            .map(|span| toml[..span.start].matches('\n').count() + 1);
+        Self::InvalidToml {
+            line_number,
+            message: error.message().trim().to_string(),
+        }
+    }
+}
+
+impl Display for RoswaalConfigParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidToml {
+                line_number: Some(line_number),
+                message,
+            } => {
+                write!(
+                    f,
+                    "Line {} of the config file is invalid ({}).",
+                    line_number, message
+                )
+            }
+            Self::InvalidToml {
+                line_number: None,
+                message,
+            } => write!(f, "The config file is invalid ({}).", message),
+            Self::InvalidValue { key } => write!(f, "The value of \"{}\" is invalid.", key),
+        }
+    }
+}
+
+impl Error for RoswaalConfigParsingError {}
+
+/// Returns the value of an environment variable parsed with `FromStr` if it is set, or the value
+/// in the config file otherwise.
+fn env_or<T: FromStr>(
+    env_value: Option<String>,
+    value: Option<T>,
+    key: &str,
+) -> Result<Option<T>, RoswaalConfigParsingError> {
+    match env_value {
+        Some(env_value) => env_value.trim().parse().map(Some).map_err(|_| {
+            RoswaalConfigParsingError::InvalidValue {
+                key: key.to_string(),
+            }
+        }),
+        None => Ok(value),
+    }
+}
+
+/// The tables of a config file, where unknown tables and keys are rejected so that typos don't
+/// silently fall back to the defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RoswaalConfigFile {
+    github: RoswaalGithubConfigTable,
+    slack: RoswaalSlackConfigTable,
+    retry: RoswaalRetryConfigTable,
+    git: RoswaalGitConfigTable,
+    tests: RoswaalTestsConfigTable,
+    runner: RoswaalRunnerConfigTable,
+    generation: RoswaalGenerationConfigTable,
+    server: RoswaalServerConfigTable,
+    pull_requests: RoswaalPullRequestsConfigTable,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalGithubConfigTable {
+    frontend_repository: Option<String>,
+    frontend_base_branch: Option<String>,
+    test_targets: Option<Vec<String>>,
+    pull_request_mode: Option<String>,
+    branch_prefix: Option<String>,
+    branch_separator: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalSlackConfigTable {
+    pull_request_channel_id: Option<String>,
+    test_digest_channel_id: Option<String>,
+    test_failure_channel_id: Option<String>,
+    maintainer_user_id: Option<String>,
+    language: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalRetryConfigTable {
+    max_attempts: Option<u32>,
+    initial_delay_ms: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalGitConfigTable {
+    operation_timeout_seconds: Option<u32>,
+    clean_ignore_patterns: Option<Vec<String>>,
+    max_clean_file_size_mb: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalTestsConfigTable {
+    deprecation_grace_period_days: Option<u32>,
+    suite_time_budget_minutes: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalRunnerConfigTable {
+    webhook_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalGenerationConfigTable {
+    formatter_command: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalServerConfigTable {
+    public_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoswaalPullRequestsConfigTable {
+    draft: Option<bool>,
+    labels: Option<Vec<String>>,
+    assignees: Option<Vec<String>>,
+    reviewers: Option<Vec<String>>,
+    body_template: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = RoswaalConfig::from_toml_str("", |_| None).unwrap();
+        assert_eq!(config, RoswaalConfig::default())
+    }
+
+    #[test]
+    fn parses_config_file() {
+        let toml = r#"
+# The repos to open PRs on.
+[github]
+frontend_repository = "example/App" # Comment
+frontend_base_branch = "main"
+test_targets = ["example/AppIOS@main", "example/AppAndroid@dev"]
+pull_request_mode = "staged"
+branch_prefix = "bots/roswaal"
+branch_separator = "/"
+
+[slack]
+pull_request_channel_id = "C1"
+test_digest_channel_id = "C2"
+test_failure_channel_id = "C3"
+maintainer_user_id = "U\"1"
+language = "ja"
+
+[retry]
+max_attempts = 5
+initial_delay_ms = 250 # Comment
+
+[git]
+operation_timeout_seconds = 30
+clean_ignore_patterns = ["*.mp4", "assets/"]
+max_clean_file_size_mb = 10
+
+[tests]
+deprecation_grace_period_days = 7
+suite_time_budget_minutes = 45
+
+[runner]
+webhook_url = "https://ci.example.com/roswaal"
+
+[generation]
+formatter_command = "npx prettier --write"
+
+[server]
+public_url = "https://roswaal.example.com/"
+
+[pull_requests]
+draft = true
+labels = ["acceptance-tests", "roswaal"]
+assignees = ["roswaaltifbot"]
+reviewers = []
+body_template = "{{summary}}\n\nCloses #1"
+"#;
+        let config = RoswaalConfig::from_toml_str(toml, |_| None).unwrap();
+        let expected_config = RoswaalConfig {
+            frontend_owner: "example".to_string(),
+            frontend_repo: "App".to_string(),
+            frontend_base_branch_name: "main".to_string(),
+            test_targets: vec![
+                "example/AppIOS@main".to_string(),
+                "example/AppAndroid@dev".to_string(),
+            ],
+            pull_request_mode: RoswaalPullRequestMode::Staged,
+            branch_namespace: RoswaalGitBranchNamespace::new("bots/roswaal", '/').unwrap(),
+            pull_request_channel_id: "C1".to_string(),
+            test_digest_channel_id: Some("C2".to_string()),
+            test_failure_channel_id: Some("C3".to_string()),
+            maintainer_slack_user_id: "U\"1".to_string(),
+            slack_language: RoswaalSlackLanguage::Japanese,
+            retry_policy: RoswaalRetryPolicy::new(5, Duration::from_millis(250)),
+            git_operation_timeout: Duration::from_secs(30),
+            git_clean_ignore_patterns: vec!["*.mp4".to_string(), "assets/".to_string()],
+            max_git_clean_file_size_mb: 10,
+            deprecation_grace_period: Duration::from_secs(7 * SECONDS_PER_DAY),
+            suite_time_budget: Some(Duration::from_secs(45 * 60)),
+            test_runner_webhook_url: Some("https://ci.example.com/roswaal".to_string()),
+            formatter_command: Some("npx prettier --write".to_string()),
+            public_url: Some("https://roswaal.example.com".to_string()),
+            draft_pull_requests: true,
+            pull_request_labels: vec!["acceptance-tests".to_string(), "roswaal".to_string()],
+            pull_request_assignees: vec!["roswaaltifbot".to_string()],
+            pull_request_reviewers: vec![],
+            pull_request_body_template: Some("{{summary}}\n\nCloses #1".to_string()),
+        };
+        assert_eq!(config, expected_config)
+    }
+
+    #[test]
+    fn environment_variables_override_config_file() {
+        let toml = r#"
+[github]
+frontend_base_branch = "main"
+test_targets = ["example/AppIOS@main"]
+
+[slack]
+maintainer_user_id = "U1"
+"#;
+        let env = |name: &str| match name {
+            "ROSWAAL_TEST_TARGETS" => Some("a/b@main, c/d@dev".to_string()),
+            "SLACK_MAINTAINER_USER_ID" => Some("U2".to_string()),
+            "ROSWAAL_RETRY_MAX_ATTEMPTS" => Some("1".to_string()),
+            "ROSWAAL_GIT_OPERATION_TIMEOUT_SECONDS" => Some("45".to_string()),
+            "ROSWAAL_DEPRECATION_GRACE_PERIOD_DAYS" => Some("0".to_string()),
+            "ROSWAAL_PULL_REQUEST_DRAFT" => Some("true".to_string()),
+            "ROSWAAL_PULL_REQUEST_REVIEWERS" => Some("mhayes853, ".to_string()),
+            "ROSWAAL_FORMATTER_COMMAND" => Some("  ".to_string()),
+            "ROSWAAL_GIT_CLEAN_IGNORE_PATTERNS" => Some("*.mov, build/".to_string()),
+            _ => None,
+        };
+        let config = RoswaalConfig::from_toml_str(toml, env).unwrap();
+        assert_eq!(config.frontend_base_branch_name(), "main");
+        assert_eq!(config.test_targets(), &["a/b@main", "c/d@dev"]);
+        assert_eq!(config.maintainer_slack_user_id(), "U2");
+        assert!(config.draft_pull_requests());
+        assert_eq!(config.pull_request_reviewers(), &["mhayes853"]);
+        assert_eq!(config.formatter_command(), None);
+        assert_eq!(config.git_clean_ignore_patterns(), &["*.mov", "build/"]);
+        assert_eq!(config.git_operation_timeout(), Duration::from_secs(45));
+        assert_eq!(config.deprecation_grace_period(), Duration::ZERO);
+        assert_eq!(
+            config.retry_policy(),
+            &RoswaalRetryPolicy::new(1, Duration::from_millis(500))
+        )
+    }
+
+    #[test]
+    fn parses_any_toml_syntax() {
+        let toml = r#"
+github.frontend_repository = 'example/App'
+slack = { maintainer_user_id = "U1", language = "ja" }
+
+[pull_requests]
+labels = [
+    "acceptance-tests", # Comment
+    'roswaal',
+]
+body_template = """
+{{summary}}"""
+"#;
+        let config = RoswaalConfig::from_toml_str(toml, |_| None).unwrap();
+        assert_eq!(config.frontend_owner(), "example");
+        assert_eq!(config.frontend_repo(), "App");
+        assert_eq!(config.maintainer_slack_user_id(), "U1");
+        assert_eq!(config.slack_language(), RoswaalSlackLanguage::Japanese);
+        assert_eq!(
+            config.pull_request_labels(),
+            &["acceptance-tests", "roswaal"]
+        );
+        assert_eq!(config.pull_request_body_template(), Some("{{summary}}"))
+    }
+
+    #[test]
+    fn invalid_config_files() {
+        let toml_errors = [
+            ("[github\n", 1),
+            ("[slack]\nmaintainer_user_id = U1", 2),
+            ("[github]\ntest_targets = [\"a/b@main\"", 2),
+            ("[github]\ntest_targets = \"a/b@main\"", 2),
+            ("[retry]\nmax_attempts = \"3\"", 2),
+            ("[retry]\nmax_attempts = -1", 2),
+            ("[retry]\ninitial_delay_ms = 5s", 2),
+            ("[pull_requests]\ndraft = \"yes\"", 2),
+            ("[pull_requests]\ndraft = truly", 2),
+            ("[slack]\nchannel_id = \"C1\"", 2),
+            ("[slack]\n\n[gihtub]\nfrontend_base_branch = \"main\"", 3),
+        ];
+        for (toml, expected_line_number) in toml_errors {
+            let error = RoswaalConfig::from_toml_str(toml, |_| None).unwrap_err();
+            let RoswaalConfigParsingError::InvalidToml { line_number, .. } = error else {
+                panic!("Expected a TOML error for {:?}, got {:?}.", toml, error)
+            };
+            assert_eq!(line_number, Some(expected_line_number), "{:?}", toml)
+        }
+        let value_errors = [
+            (
+                "[github]\nfrontend_repository = \"App\"",
+                "github.frontend_repository",
+            ),
+            (
+                "[git]\noperation_timeout_seconds = 0",
+                "git.operation_timeout_seconds",
+            ),
+            (
+                "[git]\nmax_clean_file_size_mb = 0",
+                "git.max_clean_file_size_mb",
+            ),
+            (
+                "[tests]\nsuite_time_budget_minutes = 0",
+                "tests.suite_time_budget_minutes",
+            ),
+            (
+                "[github]\npull_request_mode = \"later\"",
+                "github.pull_request_mode",
+            ),
+            (
+                "[github]\nbranch_prefix = \"roswaal bot\"",
+                "github.branch_prefix",
+            ),
+            (
+                "[github]\nbranch_separator = \"_\"",
+                "github.branch_separator",
+            ),
+            ("[slack]\nlanguage = \"fr\"", "slack.language"),
+        ];
+        for (toml, key) in value_errors {
+            assert_eq!(
+                RoswaalConfig::from_toml_str(toml, |_| None),
+                Err(RoswaalConfigParsingError::InvalidValue {
+                    key: key.to_string()
+                })
+            )
+        }
+        let env = |name: &str| (name == "ROSWAAL_PULL_REQUEST_DRAFT").then(|| "yes".to_string());
+        assert_eq!(
+            RoswaalConfig::from_toml_str("", env),
+            Err(RoswaalConfigParsingError::InvalidValue {
+                key: "pull_requests.draft".to_string()
+            })
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use std::{env, error::Error, fmt::Display, str::FromStr};
+
+use anyhow::Result;
+
+use super::db::RoswaalDatabase;
+
+/// The url of the database that stores tests and locations, which is set by the `DATABASE_URL`
+/// environment variable.
+///
+/// A Postgres database can be shared by multiple instances of the server, whereas a sqlite
+/// database can only be used by the instance that opened it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RoswaalDatabaseUrl {
+    Sqlite(String),
+    Postgres(String),
+}
+
+const SQLITE_IN_MEMORY_URL: &str = "sqlite::memory:";
+
+impl FromStr for RoswaalDatabaseUrl {
+    type Err = DatabaseUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == SQLITE_IN_MEMORY_URL {
+            Ok(Self::Sqlite(":memory:".to_string()))
+        } else if let Some(path) = s
+            .strip_prefix("sqlite://")
+            .or_else(|| s.strip_prefix("sqlite:"))
+            .filter(|path| !path.is_empty())
+        {
+            Ok(Self::Sqlite(path.to_string()))
+        } else if s.starts_with("postgres://") || s.starts_with("postgresql://") {
+            Ok(Self::Postgres(s.to_string()))
+        } else {
+            Err(DatabaseUrlError::InvalidUrl(s.to_string()))
+        }
+    }
+}
+
+impl RoswaalDatabaseUrl {
+    /// Returns the url in the `DATABASE_URL` environment variable, or a sqlite url with the
+    /// specified path if the variable is not set.
+    pub fn from_env_or_sqlite(default_sqlite_path: &str) -> Result<Self> {
+        match env::var("DATABASE_URL") {
+            Ok(url) => Ok(Self::from_str(&url)?),
+            Err(_) => Ok(Self::Sqlite(default_sqlite_path.to_string())),
+        }
+    }
+
+    /// Attempts to open the database at this url.
+    pub async fn open(&self) -> Result<RoswaalDatabase> {
+        match self {
+            Self::Sqlite(path) => RoswaalDatabase::open_sqlite(path).await,
+            Self::Postgres(url) => RoswaalDatabase::open_postgres(url).await,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DatabaseUrlError {
+    InvalidUrl(String),
+}
+
+impl Display for DatabaseUrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidUrl(url) => write!(
+                f,
+                "The DATABASE_URL \"{}\" must start with sqlite:, postgres://, or postgresql://.",
+                url
+            ),
+        }
+    }
+}
+
+impl Error for DatabaseUrlError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sqlite_urls() {
+        let urls = [
+            ("sqlite://./roswaal.sqlite", "./roswaal.sqlite"),
+            ("sqlite:roswaal.sqlite", "roswaal.sqlite"),
+            ("  sqlite::memory: ", ":memory:"),
+        ];
+        for (url, path) in urls {
+            assert_eq!(
+                RoswaalDatabaseUrl::from_str(url),
+                Ok(RoswaalDatabaseUrl::Sqlite(path.to_string()))
+            )
+        }
+    }
+
+    #[test]
+    fn parses_postgres_urls() {
+        let urls = [
+            "postgres://roswaal@localhost/roswaal",
+            "postgresql://roswaal@localhost:5432/roswaal",
+        ];
+        for url in urls {
+            assert_eq!(
+                RoswaalDatabaseUrl::from_str(url),
+                Ok(RoswaalDatabaseUrl::Postgres(url.to_string()))
+            )
+        }
+    }
+
+    #[test]
+    fn errors_for_invalid_urls() {
+        let urls = [
+            "",
+            "sqlite:",
+            "./roswaal.sqlite",
+            "mysql://localhost/roswaal",
+        ];
+        for url in urls {
+            assert_eq!(
+                RoswaalDatabaseUrl::from_str(url),
+                Err(DatabaseUrlError::InvalidUrl(url.to_string()))
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn opens_sqlite_database() {
+        let url = RoswaalDatabaseUrl::from_str(SQLITE_IN_MEMORY_URL).unwrap();
+        assert!(url.open().await.is_ok())
+    }
+
+    #[tokio::test]
+    async fn opens_postgres_database() {
+        let Ok(url) = env::var("ROSWAAL_TEST_POSTGRES_URL") else {
+            return;
+        };
+        let url = RoswaalDatabaseUrl::from_str(&url).unwrap();
+        let database = url.open().await.unwrap();
+        let mut transaction = database.transaction().await.unwrap();
+        assert!(transaction.data_revision().await.is_ok());
+        transaction.commit().await.unwrap()
+    }
+}
@@ -0,0 +1,462 @@
+mod postgres;
+pub mod query;
+mod sqlite;
+
+use anyhow::Result;
+use sqlx::postgres::{PgConnection, Postgres};
+use sqlx::sqlite::{Sqlite, SqliteConnection};
+use sqlx::{Pool, Transaction};
+
+use self::query::{query, query_scalar, RoswaalQuery, RoswaalQueryAs, RoswaalSql};
+use self::sqlite::{RoswaalSqlitePool, RoswaalSqlitePoolGuard, SQLITE_IN_MEMORY_PATH};
+
+/// The database that stores tests and locations.
+///
+/// A sqlite database serializes its transactions to prevent sqlite busy errors from ocurring,
+/// which only works when a single server uses it. A Postgres database can be shared by multiple
+/// servers, and its transactions that write serialize each other with an advisory lock instead.
+#[derive(Debug)]
+pub struct RoswaalDatabase {
+    pool: RoswaalDatabasePool,
+}
+
+#[derive(Debug)]
+enum RoswaalDatabasePool {
+    Sqlite(RoswaalSqlitePool),
+    Postgres(Pool<Postgres>),
+}
+
+impl RoswaalDatabase {
+    /// Attempts to open a new sqlite connection at the specified path.
+    pub async fn open_sqlite(path: &str) -> Result<Self> {
+        let pool = RoswaalSqlitePool::open(path).await?;
+        Ok(Self {
+            pool: RoswaalDatabasePool::Sqlite(pool),
+        })
+    }
+
+    /// Attempts to connect to the Postgres database at the specified url.
+    pub async fn open_postgres(url: &str) -> Result<Self> {
+        let pool = postgres::open(url).await?;
+        Ok(Self {
+            pool: RoswaalDatabasePool::Postgres(pool),
+        })
+    }
+
+    /// Attempts to open an in-memory sqlite connection.
+    ///
+    /// In unit tests, this opens a new schema in the Postgres database at
+    /// `ROSWAAL_TEST_POSTGRES_URL` instead when that variable is set.
+    pub async fn in_memory() -> Result<Self> {
+        #[cfg(test)]
+        if let Ok(url) = std::env::var(postgres::TEST_POSTGRES_URL_KEY) {
+            let pool = postgres::open_test_schema(&url).await?;
+            return Ok(Self {
+                pool: RoswaalDatabasePool::Postgres(pool),
+            });
+        }
+        Self::open_sqlite(SQLITE_IN_MEMORY_PATH).await
+    }
+
+    /// Attempts to open a transaction.
+    ///
+    /// The transaction waits for every other transaction that shares its pool to finish. On a
+    /// Postgres database, it only waits for the other transactions that were opened with this
+    /// method.
+    pub async fn transaction(&self) -> Result<RoswaalDatabaseTransaction<'_>> {
+        let transaction = match &self.pool {
+            RoswaalDatabasePool::Sqlite(pool) => {
+                let (guard, transaction) = pool.transaction().await?;
+                RoswaalTransaction::Sqlite { guard, transaction }
+            }
+            RoswaalDatabasePool::Postgres(pool) => {
+                RoswaalTransaction::Postgres(Box::new(postgres::transaction(pool).await?))
+            }
+        };
+        Ok(RoswaalDatabaseTransaction { transaction })
+    }
+
+    /// Attempts to open a read only transaction.
+    ///
+    /// The transaction does not wait for other read only transactions to finish, and on a database
+    /// that is not in-memory it does not wait for any transactions to finish, so use this for
+    /// operations that only view data.
+    pub async fn read_transaction(&self) -> Result<RoswaalDatabaseTransaction<'_>> {
+        let transaction = match &self.pool {
+            RoswaalDatabasePool::Sqlite(pool) => {
+                let (guard, transaction) = pool.read_transaction().await?;
+                RoswaalTransaction::Sqlite { guard, transaction }
+            }
+            RoswaalDatabasePool::Postgres(pool) => {
+                RoswaalTransaction::Postgres(Box::new(postgres::read_transaction(pool).await?))
+            }
+        };
+        Ok(RoswaalDatabaseTransaction { transaction })
+    }
+}
+
+/// The tables whose rows are served by the `/tests` and `/locations.geojson` endpoints.
+const REVISIONED_TABLES: [&str; 10] = [
+    "Tests",
+    "TestSteps",
+    "TestTags",
+    "Suites",
+    "TestSuites",
+    "TestStepArtifacts",
+    "TestStepDurations",
+    "TestRunResults",
+    "Locations",
+    "LocationAliases",
+];
+
+#[derive(Debug)]
+enum RoswaalTransaction<'a> {
+    Sqlite {
+        guard: RoswaalSqlitePoolGuard<'a>,
+        transaction: Transaction<'static, Sqlite>,
+    },
+    Postgres(Box<Transaction<'static, Postgres>>),
+}
+
+/// A database transaction runner.
+#[derive(Debug)]
+pub struct RoswaalDatabaseTransaction<'a> {
+    transaction: RoswaalTransaction<'a>,
+}
+
+/// The connection of a transaction, which runs the queries in the `query` module.
+#[derive(Debug)]
+pub enum RoswaalDatabaseConnection<'c> {
+    Sqlite(&'c mut SqliteConnection),
+    Postgres(&'c mut PgConnection),
+}
+
+impl<'a> RoswaalDatabaseTransaction<'a> {
+    /// Returns the underlying connection for this transaction.
+    pub fn connection(&mut self) -> RoswaalDatabaseConnection<'_> {
+        match &mut self.transaction {
+            RoswaalTransaction::Sqlite { transaction, .. } => {
+                RoswaalDatabaseConnection::Sqlite(transaction.as_mut())
+            }
+            RoswaalTransaction::Postgres(transaction) => {
+                RoswaalDatabaseConnection::Postgres(transaction.as_mut())
+            }
+        }
+    }
+
+    /// Returns the data revision, which increases whenever a stored test or location changes.
+    ///
+    /// The revision never decreases, so clients can cheaply check if anything has changed since
+    /// they last loaded the tests or locations.
+    pub async fn data_revision(&mut self) -> Result<i64> {
+        let revision = query_scalar::<i64>("SELECT revision FROM DataRevision;")
+            .fetch_one(self.connection())
+            .await?;
+        Ok(revision)
+    }
+
+    /// Performs a rollback.
+    pub async fn rollback(self) -> Result<()> {
+        match self.transaction {
+            RoswaalTransaction::Sqlite { guard, transaction } => {
+                transaction.rollback().await?;
+                drop(guard);
+            }
+            RoswaalTransaction::Postgres(transaction) => transaction.rollback().await?,
+        }
+        Ok(())
+    }
+
+    /// Performs a commit.
+    pub async fn commit(self) -> Result<()> {
+        match self.transaction {
+            RoswaalTransaction::Sqlite { guard, transaction } => {
+                transaction.commit().await?;
+                drop(guard);
+            }
+            RoswaalTransaction::Postgres(transaction) => transaction.commit().await?,
+        }
+        Ok(())
+    }
+}
+
+/// Runs the work inside the transaction, rolling back if the work returns an error, or committing
+/// if the work returns successfully.
+#[macro_export]
+macro_rules! with_transaction {
+    ($transaction:ident, $work:expr) => {
+        match $work.await {
+            Ok(value) => {
+                let result = $transaction.commit().await;
+                if let Err(err) = result {
+                    Err(err)
+                } else {
+                    Ok(value)
+                }
+            }
+            Err(error) => {
+                let result = $transaction.rollback().await;
+                if let Err(err) = result {
+                    Err(err)
+                } else {
+                    Err(error)
+                }
+            }
+        }
+    };
+}
+
+/// Returns a list of substitutable array parameters for use in queries that check if a value is
+/// contained within a list of values.
+///
+/// Postgres does not accept an empty list, so an empty list of values is written as `(NULL)`,
+/// which no value is contained within.
+///
+/// ```rs
+/// impl RoswaalDatabaseTransaction {
+///     pub async fn items(&mut self, item_names: &Vec<&str>) -> Result<Vec<Item>> {
+///         let select_query_statement = format!(
+///             "SELECT * FROM Items WHERE name in {};",
+///             array_fields(item_names.len())
+///         );
+///         // Execute query...
+///     }
+/// }
+/// ```
+pub fn array_fields(count: usize) -> String {
+    if count == 0 {
+        return "(NULL)".to_string();
+    }
+    format!(
+        "({})",
+        (0..count).map(|_| "?").collect::<Vec<&str>>().join(", ")
+    )
+}
+
+/// A repeated query.
+pub struct RoswaalRepeatedQuery<'q, Value> {
+    sqlite_statements: String,
+    postgres_statements: String,
+    values: &'q Vec<Value>,
+}
+
+/// Repeats the SQL statement for each value in `values`.
+pub fn repeat<'q, Value>(
+    statement: impl Into<RoswaalSql<'q>>,
+    values: &'q Vec<Value>,
+) -> RoswaalRepeatedQuery<'q, Value> {
+    let statement = statement.into();
+    let repeat = |statement: &str| {
+        values
+            .iter()
+            .map(|_| statement)
+            .collect::<Vec<&str>>()
+            .join("\n")
+    };
+    RoswaalRepeatedQuery {
+        sqlite_statements: repeat(statement.sqlite()),
+        postgres_statements: repeat(statement.postgres()),
+        values,
+    }
+}
+
+impl<'q, Value> RoswaalRepeatedQuery<'q, Value> {
+    fn sql(&'q self) -> RoswaalSql<'q> {
+        RoswaalSql::new(&self.sqlite_statements, &self.postgres_statements)
+    }
+
+    /// Given a function with a query and each value, binds the value to the query.
+    pub fn bind_to_query(
+        &'q self,
+        bind: impl Fn(RoswaalQuery<'q>, &'q Value) -> Result<RoswaalQuery<'q>>,
+    ) -> Result<RoswaalQuery<'q>> {
+        let mut query = query(self.sql());
+        for value in self.values.iter() {
+            query = bind(query, value)?;
+        }
+        Ok(query)
+    }
+
+    /// Given a function with a query and each value from `values`, binds the value to the query.
+    pub fn bind_custom_values_to_query<V>(
+        &'q self,
+        values: impl Iterator<Item = V>,
+        bind: impl Fn(RoswaalQuery<'q>, &V) -> Result<RoswaalQuery<'q>>,
+    ) -> Result<RoswaalQuery<'q>> {
+        let mut query = query(self.sql());
+        for value in values {
+            query = bind(query, &value)?;
+        }
+        Ok(query)
+    }
+
+    /// Given a function with q query and each value, binds the value to the query.
+    pub fn bind_to_query_as<Output>(
+        &'q self,
+        bind: impl Fn(RoswaalQueryAs<'q, Output>, &'q Value) -> Result<RoswaalQueryAs<'q, Output>>,
+    ) -> Result<RoswaalQueryAs<'q, Output>> {
+        let mut query = query::query_as::<Output>(self.sql());
+        for value in self.values.iter() {
+            query = bind(query, value)?;
+        }
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs::remove_file};
+
+    use nanoid::nanoid;
+    use sqlx::prelude::FromRow;
+
+    use super::query::query_as;
+    use super::*;
+
+    #[derive(FromRow, Debug, PartialEq, Eq)]
+    struct TestRecord {
+        id: i32,
+    }
+
+    #[tokio::test]
+    async fn test_basic_query() {
+        let sqlite = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = sqlite.transaction().await.unwrap();
+        _ = query("CREATE TABLE Test (id INTEGER NOT NULL PRIMARY KEY)")
+            .execute(transaction.connection())
+            .await;
+        _ = query("INSERT INTO Test (id) VALUES (1)")
+            .execute(transaction.connection())
+            .await;
+        let result: TestRecord = query_as("SELECT * FROM Test")
+            .fetch_one(transaction.connection())
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(result, TestRecord { id: 1 });
+    }
+
+    #[tokio::test]
+    async fn data_revision_increases_only_with_committed_changes() {
+        let sqlite = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = sqlite.transaction().await.unwrap();
+        let initial_revision = transaction.data_revision().await.unwrap();
+        let insert = "INSERT INTO Locations (latitude, longitude, name) VALUES (1.0, 1.0, 'A')";
+        query(insert)
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        transaction.rollback().await.unwrap();
+
+        let mut transaction = sqlite.transaction().await.unwrap();
+        assert_eq!(transaction.data_revision().await.unwrap(), initial_revision);
+        query(insert)
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        query("UPDATE Locations SET latitude = 2.0")
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        let audit_insert =
+            "INSERT INTO AuditLog (\"user\", operation, arguments) VALUES ('U1', '', '')";
+        query(audit_insert)
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = sqlite.read_transaction().await.unwrap();
+        assert_eq!(
+            transaction.data_revision().await.unwrap(),
+            initial_revision + 2
+        )
+    }
+
+    #[tokio::test]
+    async fn test_commit_and_rollback_on_failure() {
+        let sqlite = RoswaalDatabase::in_memory().await.unwrap();
+        let mut transaction = sqlite.transaction().await.unwrap();
+        _ = with_transaction!(transaction, async {
+            _ = query("CREATE TABLE Test (id INTEGER NOT NULL PRIMARY KEY)")
+                .execute(transaction.connection())
+                .await?;
+            _ = query("INSERT INTO Test (id) VALUES (1)")
+                .execute(transaction.connection())
+                .await?;
+            Ok(())
+        });
+        transaction = sqlite.transaction().await.unwrap();
+        let transaction_result = with_transaction!(transaction, async {
+            _ = query("INSERT INTO Test (id) VALUES (5)")
+                .execute(transaction.connection())
+                .await?;
+            _ = query("INSERT INTO Test (id) VALUES (1)")
+                .execute(transaction.connection())
+                .await?;
+            Ok(())
+        });
+        transaction = sqlite.transaction().await.unwrap();
+        assert!(transaction_result.is_err());
+        let result: Vec<TestRecord> = query_as("SELECT * FROM Test")
+            .fetch_all(transaction.connection())
+            .await
+            .unwrap();
+        assert_eq!(result, vec![TestRecord { id: 1 }]);
+    }
+
+    #[tokio::test]
+    async fn test_read_transaction_does_not_wait_for_write_transactions() {
+        let path = env::temp_dir().join(format!("roswaal-{}.sqlite", nanoid!(10)));
+        let sqlite = RoswaalDatabase::open_sqlite(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let mut transaction = sqlite.transaction().await.unwrap();
+        _ = query("CREATE TABLE Test (id INTEGER NOT NULL PRIMARY KEY)")
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        _ = query("INSERT INTO Test (id) VALUES (1)")
+            .execute(transaction.connection())
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut write_transaction = sqlite.transaction().await.unwrap();
+        _ = query("INSERT INTO Test (id) VALUES (2)")
+            .execute(write_transaction.connection())
+            .await
+            .unwrap();
+        let mut read_transaction = sqlite.read_transaction().await.unwrap();
+        let result: Vec<TestRecord> = query_as("SELECT * FROM Test")
+            .fetch_all(read_transaction.connection())
+            .await
+            .unwrap();
+        assert_eq!(result, vec![TestRecord { id: 1 }]);
+        let write_result = query("INSERT INTO Test (id) VALUES (3)")
+            .execute(read_transaction.connection())
+            .await;
+        assert!(write_result.is_err());
+        read_transaction.rollback().await.unwrap();
+        write_transaction.commit().await.unwrap();
+        _ = remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_read_transactions_do_not_wait_for_each_other() {
+        let sqlite = RoswaalDatabase::in_memory().await.unwrap();
+        let mut read_transaction1 = sqlite.read_transaction().await.unwrap();
+        let mut read_transaction2 = sqlite.read_transaction().await.unwrap();
+        let count1: i64 = query_scalar("SELECT COUNT(*) FROM Tests")
+            .fetch_one(read_transaction1.connection())
+            .await
+            .unwrap();
+        let count2: i64 = query_scalar("SELECT COUNT(*) FROM Tests")
+            .fetch_one(read_transaction2.connection())
+            .await
+            .unwrap();
+        read_transaction1.commit().await.unwrap();
+        read_transaction2.commit().await.unwrap();
+        assert_eq!((count1, count2), (0, 0));
+    }
+}
@@ -0,0 +1,320 @@
+use anyhow::Result;
+use sqlx::postgres::Postgres;
+use sqlx::{query, Executor, Pool, Transaction};
+
+use super::REVISIONED_TABLES;
+
+/// The key of the advisory lock that transactions which write hold until they finish, so that
+/// they run one at a time like they do on sqlite, even when multiple servers share the database.
+const WRITE_LOCK_KEY: i64 = 0x526f7377;
+
+/// The key of the advisory lock that is held while the schema is created, so that servers which
+/// start at the same time don't create it concurrently.
+const MIGRATION_LOCK_KEY: i64 = 0x526f7378;
+
+/// Attempts to connect to the Postgres database at the specified url, and creates its tables if
+/// they don't exist.
+pub(super) async fn open(url: &str) -> Result<Pool<Postgres>> {
+    let pool = Pool::<Postgres>::connect(url).await?;
+    migrate(&pool).await?;
+    Ok(pool)
+}
+
+/// Attempts to open a transaction that waits for every other transaction opened with this
+/// function to finish.
+pub(super) async fn transaction(pool: &Pool<Postgres>) -> Result<Transaction<'static, Postgres>> {
+    let mut transaction = pool.begin().await?;
+    query("SELECT pg_advisory_xact_lock($1);")
+        .bind(WRITE_LOCK_KEY)
+        .execute(transaction.as_mut())
+        .await?;
+    Ok(transaction)
+}
+
+/// Attempts to open a read only transaction, which does not wait for other transactions.
+pub(super) async fn read_transaction(
+    pool: &Pool<Postgres>,
+) -> Result<Transaction<'static, Postgres>> {
+    let mut transaction = pool.begin().await?;
+    transaction
+        .as_mut()
+        .execute("SET TRANSACTION READ ONLY;")
+        .await?;
+    Ok(transaction)
+}
+
+async fn migrate(pool: &Pool<Postgres>) -> Result<()> {
+    let mut transaction = pool.begin().await?;
+    query("SELECT pg_advisory_xact_lock($1);")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(transaction.as_mut())
+        .await?;
+    migrate_v1(&mut transaction).await?;
+    transaction.commit().await?;
+    Ok(())
+}
+
+/// Creates the Postgres schema that matches a sqlite database with every sqlite migration applied.
+///
+/// Dates are stored as `TIMESTAMPTZ` instead of unix timestamps, and tests are searched with the
+/// text search functions instead of an FTS5 table, so `TestsSearch` is a regular table of the
+/// text that each test is searched by.
+async fn migrate_v1(transaction: &mut Transaction<'static, Postgres>) -> Result<()> {
+    transaction
+        .as_mut()
+        .execute(
+            "
+CREATE TABLE IF NOT EXISTS Locations (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    latitude REAL NOT NULL,
+    longitude REAL NOT NULL,
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT,
+    region TEXT,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS Tests (
+    id SERIAL NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    unmerged_branch_name TEXT,
+    error_message TEXT,
+    error_stack_trace TEXT,
+    command_failure_ordinal INTEGER,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    last_run_date TIMESTAMPTZ,
+    deprecation_date TIMESTAMPTZ,
+    is_planned BOOLEAN NOT NULL DEFAULT FALSE,
+    added_by_slack_user_id TEXT,
+    platforms TEXT NOT NULL DEFAULT '',
+    variables TEXT NOT NULL DEFAULT '[]',
+    source_code TEXT,
+    UNIQUE(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS TestSteps (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    test_id INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    ordinal INTEGER NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE(test_id, content),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestTags (
+    test_id INTEGER NOT NULL,
+    tag TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(test_id, tag),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS Suites (
+    id SERIAL NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE UNIQUE INDEX IF NOT EXISTS suites_lower_name ON Suites (LOWER(name));
+CREATE TABLE IF NOT EXISTS TestSuites (
+    test_id INTEGER NOT NULL PRIMARY KEY,
+    suite_id INTEGER NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE,
+    CONSTRAINT fk_suite FOREIGN KEY(suite_id) REFERENCES Suites(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestStepArtifacts (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    test_id INTEGER NOT NULL,
+    command_ordinal INTEGER NOT NULL,
+    url TEXT,
+    base64_data TEXT,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestStepDurations (
+    test_id INTEGER NOT NULL,
+    command_ordinal INTEGER NOT NULL,
+    milliseconds BIGINT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(test_id, command_ordinal),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestsSearch (
+    test_id INTEGER NOT NULL PRIMARY KEY,
+    name TEXT,
+    description TEXT,
+    steps TEXT,
+    requirements TEXT,
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS StagedTestRemovals (
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS AuditLog (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    \"user\" TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    arguments TEXT NOT NULL,
+    branch_name TEXT,
+    error_message TEXT,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS StagedLocationRemovals (
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS SlackUserRoles (
+    role TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    ordinal INTEGER NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(role, user_id)
+);
+CREATE TABLE IF NOT EXISTS PendingPullRequests (
+    id BIGSERIAL NOT NULL,
+    owner TEXT NOT NULL,
+    repo TEXT NOT NULL,
+    head_branch_name TEXT NOT NULL,
+    base_branch_name TEXT NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    is_draft BOOLEAN NOT NULL DEFAULT FALSE,
+    labels TEXT NOT NULL DEFAULT '',
+    assignees TEXT NOT NULL DEFAULT '',
+    reviewers TEXT NOT NULL DEFAULT '',
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(owner, repo, head_branch_name)
+);
+CREATE TABLE IF NOT EXISTS TestRuns (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    branch_name TEXT NOT NULL,
+    test_names TEXT NOT NULL,
+    url TEXT,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS TestRunResults (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    test_id INTEGER NOT NULL,
+    did_fail BOOLEAN NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestSubmissions (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    test_count INTEGER NOT NULL,
+    compilation_error_count INTEGER NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS MutedTestFailures (
+    name TEXT NOT NULL PRIMARY KEY,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS SlackCommandPermissions (
+    command TEXT NOT NULL,
+    subject_id TEXT NOT NULL,
+    is_user_group BOOLEAN NOT NULL,
+    ordinal INTEGER NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY(command, subject_id)
+);
+CREATE TABLE IF NOT EXISTS LocationAliases (
+    normalized_alias TEXT NOT NULL PRIMARY KEY,
+    alias TEXT NOT NULL,
+    location_name TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS DataRevision (
+    id INTEGER NOT NULL PRIMARY KEY CHECK (id = 0),
+    revision BIGINT NOT NULL
+);
+INSERT INTO DataRevision (id, revision) VALUES (0, 0) ON CONFLICT DO NOTHING;
+CREATE OR REPLACE FUNCTION increment_data_revision() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE DataRevision SET revision = revision + 1;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+CREATE TABLE IF NOT EXISTS CompilationFailures (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    slack_user_id TEXT NOT NULL,
+    syntax TEXT NOT NULL,
+    error_messages TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS TestFailureAssignments (
+    name TEXT NOT NULL PRIMARY KEY,
+    slack_user_id TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS TestLeases (
+    name TEXT NOT NULL PRIMARY KEY,
+    runner_id TEXT NOT NULL,
+    expiration_date TIMESTAMPTZ NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ArchivedTests (
+    id BIGSERIAL NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT,
+    commands TEXT NOT NULL,
+    syntax TEXT NOT NULL,
+    added_by_slack_user_id TEXT,
+    removed_branch_name TEXT NOT NULL,
+    archive_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE TABLE IF NOT EXISTS SlackReports (
+    id TEXT NOT NULL PRIMARY KEY,
+    blocks TEXT NOT NULL,
+    creation_date TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+            ",
+        )
+        .await?;
+    for table in REVISIONED_TABLES {
+        transaction
+            .as_mut()
+            .execute(
+                format!(
+                    "
+DROP TRIGGER IF EXISTS {table}_DataRevision ON {table};
+CREATE TRIGGER {table}_DataRevision AFTER INSERT OR UPDATE OR DELETE ON {table}
+FOR EACH ROW EXECUTE FUNCTION increment_data_revision();
+                    ",
+                )
+                .as_str(),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// The environment variable with the url of a Postgres database that the unit tests run against
+/// instead of in-memory sqlite databases.
+#[cfg(test)]
+pub(super) const TEST_POSTGRES_URL_KEY: &str = "ROSWAAL_TEST_POSTGRES_URL";
+
+/// Creates a new schema in the Postgres database at the specified url, and opens a pool whose
+/// connections only use that schema, so that each test gets its own empty database.
+#[cfg(test)]
+pub(super) async fn open_test_schema(url: &str) -> Result<Pool<Postgres>> {
+    use std::str::FromStr;
+
+    use nanoid::nanoid;
+    use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+    let schema = format!("test_{}", nanoid!(16, &nanoid::alphabet::SAFE[12..38]));
+    let pool = Pool::<Postgres>::connect(url).await?;
+    pool.execute(format!("CREATE SCHEMA {};", schema).as_str())
+        .await?;
+    pool.close().await;
+    let options = PgConnectOptions::from_str(url)?.options([("search_path", schema.as_str())]);
+    let pool = PgPoolOptions::new()
+        .max_connections(3)
+        .connect_with(options)
+        .await?;
+    migrate(&pool).await?;
+    Ok(pool)
+}
@@ -0,0 +1,411 @@
+use std::marker::PhantomData;
+
+use chrono::{DateTime, Utc};
+use sqlx::encode::IsNull;
+use sqlx::postgres::{PgArgumentBuffer, PgArguments, PgRow, PgTypeInfo, Postgres};
+use sqlx::sqlite::{Sqlite, SqliteArgumentValue, SqliteArguments, SqliteRow, SqliteTypeInfo};
+use sqlx::{Arguments, Encode, FromRow, Type};
+
+use super::RoswaalDatabaseConnection;
+
+/// The SQL of a query, which is written separately for each database when a statement relies on
+/// a feature that sqlite and Postgres spell differently.
+///
+/// SQL that works on both databases converts from a `&str`, and uses `?` for its parameters on
+/// both of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoswaalSql<'q> {
+    sqlite: &'q str,
+    postgres: &'q str,
+}
+
+impl<'q> RoswaalSql<'q> {
+    pub const fn new(sqlite: &'q str, postgres: &'q str) -> Self {
+        Self { sqlite, postgres }
+    }
+
+    pub fn sqlite(&self) -> &'q str {
+        self.sqlite
+    }
+
+    pub fn postgres(&self) -> &'q str {
+        self.postgres
+    }
+}
+
+impl<'q> From<&'q str> for RoswaalSql<'q> {
+    fn from(sql: &'q str) -> Self {
+        Self::new(sql, sql)
+    }
+}
+
+impl<'q> From<&'q String> for RoswaalSql<'q> {
+    fn from(sql: &'q String) -> Self {
+        Self::new(sql, sql)
+    }
+}
+
+/// A value that can be bound to a parameter of a query on either database.
+pub trait RoswaalArgument<'q>: Send {
+    fn add_to_sqlite(self: Box<Self>, arguments: &mut SqliteArguments<'q>);
+    fn add_to_postgres(self: Box<Self>, arguments: &mut PgArguments);
+}
+
+impl<'q, T> RoswaalArgument<'q> for T
+where
+    T: 'q + Send + Encode<'q, Sqlite> + Type<Sqlite> + Encode<'q, Postgres> + Type<Postgres>,
+{
+    fn add_to_sqlite(self: Box<Self>, arguments: &mut SqliteArguments<'q>) {
+        arguments.add(*self)
+    }
+
+    fn add_to_postgres(self: Box<Self>, arguments: &mut PgArguments) {
+        <PgArguments as Arguments<'q>>::add(arguments, *self)
+    }
+}
+
+/// A date that is stored as a unix timestamp in sqlite, and as a `TIMESTAMPTZ` in Postgres.
+///
+/// Bind this instead of a `DateTime` to compare a date with the `DATETIME` columns of a sqlite
+/// database, since sqlx binds a `DateTime` to sqlite as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoswaalTimestamp(pub DateTime<Utc>);
+
+impl Type<Sqlite> for RoswaalTimestamp {
+    fn type_info() -> SqliteTypeInfo {
+        <i64 as Type<Sqlite>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for RoswaalTimestamp {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        <i64 as Encode<Sqlite>>::encode(self.0.timestamp(), buf)
+    }
+}
+
+impl Type<Postgres> for RoswaalTimestamp {
+    fn type_info() -> PgTypeInfo {
+        <DateTime<Utc> as Type<Postgres>>::type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for RoswaalTimestamp {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <DateTime<Utc> as Encode<Postgres>>::encode_by_ref(&self.0, buf)
+    }
+}
+
+/// The result of executing a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoswaalQueryResult {
+    rows_affected: u64,
+}
+
+impl RoswaalQueryResult {
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
+
+/// A query that runs on either database.
+///
+/// The SQL can contain multiple statements, which sqlite runs together. Postgres cannot prepare
+/// multiple statements at once, so they run one after another with the arguments for the
+/// parameters in each statement.
+pub struct RoswaalQuery<'q> {
+    sql: RoswaalSql<'q>,
+    arguments: Vec<Box<dyn RoswaalArgument<'q> + 'q>>,
+}
+
+/// Creates a query that runs on either database.
+pub fn query<'q>(sql: impl Into<RoswaalSql<'q>>) -> RoswaalQuery<'q> {
+    RoswaalQuery {
+        sql: sql.into(),
+        arguments: Vec::new(),
+    }
+}
+
+impl<'q> RoswaalQuery<'q> {
+    /// Binds a value to the next parameter of this query.
+    pub fn bind<T: RoswaalArgument<'q> + 'q>(mut self, value: T) -> Self {
+        self.arguments.push(Box::new(value));
+        self
+    }
+
+    /// Executes this query, and returns the number of rows that it affected.
+    pub async fn execute(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<RoswaalQueryResult, sqlx::Error> {
+        match connection {
+            RoswaalDatabaseConnection::Sqlite(connection) => {
+                let (sql, arguments) = self.sqlite_arguments();
+                let result = sqlx::query_with(sql, arguments).execute(connection).await?;
+                Ok(RoswaalQueryResult {
+                    rows_affected: result.rows_affected(),
+                })
+            }
+            RoswaalDatabaseConnection::Postgres(connection) => {
+                let mut rows_affected = 0;
+                for (statement, arguments) in self.postgres_arguments() {
+                    let result = sqlx::query_with(&statement, arguments)
+                        .execute(&mut *connection)
+                        .await?;
+                    rows_affected += result.rows_affected();
+                }
+                Ok(RoswaalQueryResult { rows_affected })
+            }
+        }
+    }
+
+    fn sqlite_arguments(self) -> (&'q str, SqliteArguments<'q>) {
+        let mut arguments = SqliteArguments::default();
+        for argument in self.arguments {
+            argument.add_to_sqlite(&mut arguments);
+        }
+        (self.sql.sqlite, arguments)
+    }
+
+    fn postgres_arguments(self) -> Vec<(String, PgArguments)> {
+        let mut arguments = self.arguments.into_iter();
+        postgres_statements(self.sql.postgres)
+            .into_iter()
+            .map(|(statement, parameter_count)| {
+                let mut statement_arguments = PgArguments::default();
+                for argument in arguments.by_ref().take(parameter_count) {
+                    argument.add_to_postgres(&mut statement_arguments);
+                }
+                (statement, statement_arguments)
+            })
+            .collect()
+    }
+}
+
+/// Splits `sql` into its statements, and replaces each `?` parameter in a statement with the
+/// numbered parameter that Postgres expects.
+///
+/// Returns each statement with the number of parameters in it.
+fn postgres_statements(sql: &str) -> Vec<(String, usize)> {
+    let mut statements = Vec::new();
+    let mut statement = String::new();
+    let mut parameter_count = 0;
+    let mut quote = None;
+    for character in sql.chars() {
+        match (quote, character) {
+            (Some(open_quote), _) => {
+                if character == open_quote {
+                    quote = None;
+                }
+                statement.push(character);
+            }
+            (None, '\'' | '"') => {
+                quote = Some(character);
+                statement.push(character);
+            }
+            (None, '?') => {
+                parameter_count += 1;
+                statement.push_str(&format!("${}", parameter_count));
+            }
+            (None, ';') => {
+                if !statement.trim().is_empty() {
+                    statements.push((statement, parameter_count));
+                }
+                statement = String::new();
+                parameter_count = 0;
+            }
+            (None, _) => statement.push(character),
+        }
+    }
+    if !statement.trim().is_empty() {
+        statements.push((statement, parameter_count));
+    }
+    statements
+}
+
+/// A query that decodes each row it returns into `Output`.
+pub struct RoswaalQueryAs<'q, Output> {
+    query: RoswaalQuery<'q>,
+    output: PhantomData<Output>,
+}
+
+/// Creates a query that runs on either database, and decodes each row it returns into `Output`.
+pub fn query_as<'q, Output>(sql: impl Into<RoswaalSql<'q>>) -> RoswaalQueryAs<'q, Output> {
+    RoswaalQueryAs {
+        query: query(sql),
+        output: PhantomData,
+    }
+}
+
+impl<'q, Output> RoswaalQueryAs<'q, Output>
+where
+    Output: Send + Unpin + for<'r> FromRow<'r, SqliteRow> + for<'r> FromRow<'r, PgRow>,
+{
+    /// Binds a value to the next parameter of this query.
+    pub fn bind<T: RoswaalArgument<'q> + 'q>(mut self, value: T) -> Self {
+        self.query = self.query.bind(value);
+        self
+    }
+
+    /// Returns every row that the statements in this query return.
+    pub async fn fetch_all(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<Vec<Output>, sqlx::Error> {
+        match connection {
+            RoswaalDatabaseConnection::Sqlite(connection) => {
+                let (sql, arguments) = self.query.sqlite_arguments();
+                sqlx::query_as_with(sql, arguments)
+                    .fetch_all(connection)
+                    .await
+            }
+            RoswaalDatabaseConnection::Postgres(connection) => {
+                let mut rows = Vec::new();
+                for (statement, arguments) in self.query.postgres_arguments() {
+                    let statement_rows = sqlx::query_as_with(&statement, arguments)
+                        .fetch_all(&mut *connection)
+                        .await?;
+                    rows.extend(statement_rows);
+                }
+                Ok(rows)
+            }
+        }
+    }
+
+    /// Returns the first row that this query returns, or an error if it returns no rows.
+    pub async fn fetch_one(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<Output, sqlx::Error> {
+        self.fetch_optional(connection)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Returns the first row that this query returns, or None if it returns no rows.
+    pub async fn fetch_optional(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<Option<Output>, sqlx::Error> {
+        match connection {
+            RoswaalDatabaseConnection::Sqlite(connection) => {
+                let (sql, arguments) = self.query.sqlite_arguments();
+                sqlx::query_as_with(sql, arguments)
+                    .fetch_optional(connection)
+                    .await
+            }
+            connection => Ok(self.fetch_all(connection).await?.into_iter().next()),
+        }
+    }
+}
+
+/// A query that decodes the first column of each row it returns into `Output`.
+pub struct RoswaalQueryScalar<'q, Output> {
+    query: RoswaalQuery<'q>,
+    output: PhantomData<Output>,
+}
+
+/// Creates a query that runs on either database, and decodes the first column of each row it
+/// returns into `Output`.
+pub fn query_scalar<'q, Output>(sql: impl Into<RoswaalSql<'q>>) -> RoswaalQueryScalar<'q, Output> {
+    RoswaalQueryScalar {
+        query: query(sql),
+        output: PhantomData,
+    }
+}
+
+impl<'q, Output> RoswaalQueryScalar<'q, Output>
+where
+    Output: Send + Unpin,
+    (Output,): for<'r> FromRow<'r, SqliteRow> + for<'r> FromRow<'r, PgRow>,
+{
+    /// Binds a value to the next parameter of this query.
+    pub fn bind<T: RoswaalArgument<'q> + 'q>(mut self, value: T) -> Self {
+        self.query = self.query.bind(value);
+        self
+    }
+
+    /// Returns the first column of every row that the statements in this query return.
+    pub async fn fetch_all(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<Vec<Output>, sqlx::Error> {
+        let rows = query_as_rows::<(Output,)>(self.query)
+            .fetch_all(connection)
+            .await?;
+        Ok(rows.into_iter().map(|(value,)| value).collect())
+    }
+
+    /// Returns the first column of the first row that this query returns, or an error if it
+    /// returns no rows.
+    pub async fn fetch_one(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<Output, sqlx::Error> {
+        let (value,) = query_as_rows::<(Output,)>(self.query)
+            .fetch_one(connection)
+            .await?;
+        Ok(value)
+    }
+
+    /// Returns the first column of the first row that this query returns, or None if it returns
+    /// no rows.
+    pub async fn fetch_optional(
+        self,
+        connection: RoswaalDatabaseConnection<'_>,
+    ) -> Result<Option<Output>, sqlx::Error> {
+        let row = query_as_rows::<(Output,)>(self.query)
+            .fetch_optional(connection)
+            .await?;
+        Ok(row.map(|(value,)| value))
+    }
+}
+
+fn query_as_rows<Output>(query: RoswaalQuery<'_>) -> RoswaalQueryAs<'_, Output> {
+    RoswaalQueryAs {
+        query,
+        output: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_postgres_parameters_in_each_statement() {
+        let statements = postgres_statements(
+            "
+INSERT INTO Tests (name) VALUES (?);
+INSERT INTO TestTags (test_id, tag) SELECT id, ? FROM Tests WHERE name = ?;
+",
+        );
+        let statements = statements
+            .iter()
+            .map(|(statement, count)| (statement.trim(), *count))
+            .collect::<Vec<(&str, usize)>>();
+        assert_eq!(
+            statements,
+            vec![
+                ("INSERT INTO Tests (name) VALUES ($1)", 1),
+                (
+                    "INSERT INTO TestTags (test_id, tag) SELECT id, $1 FROM Tests WHERE name = $2",
+                    2
+                )
+            ]
+        )
+    }
+
+    #[test]
+    fn ignores_question_marks_and_semicolons_in_quotes() {
+        let statements = postgres_statements(
+            "SELECT 'Why?; ''Because''' AS \"answer;?\" FROM Tests WHERE id = ?",
+        );
+        assert_eq!(
+            statements,
+            vec![(
+                "SELECT 'Why?; ''Because''' AS \"answer;?\" FROM Tests WHERE id = $1".to_string(),
+                1
+            )]
+        )
+    }
+}
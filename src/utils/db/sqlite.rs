@@ -0,0 +1,624 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::Result;
+use sqlx::sqlite::{Sqlite, SqliteConnectOptions, SqliteJournalMode};
+use sqlx::{query, query_scalar, Pool, Transaction};
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::REVISIONED_TABLES;
+
+/// A sqlite database that serializes its transactions to prevent sqlite busy errors from
+/// ocurring.
+///
+/// Databases on disk are opened in WAL mode, and read only transactions on them use a separate
+/// pool of read only connections that is not serialized with the other transactions. Read only
+/// transactions on an in-memory database share its only pool, so they can run at the same time as
+/// each other, but not while another transaction is in progress.
+#[derive(Debug)]
+pub(super) struct RoswaalSqlitePool {
+    pool: RwLock<Pool<Sqlite>>,
+    /// The pool of read only connections, or None if the database is in-memory.
+    read_only_pool: Option<Pool<Sqlite>>,
+}
+
+pub(super) const SQLITE_IN_MEMORY_PATH: &str = ":memory:";
+
+/// How long a connection waits for a lock held by another connection before failing with a busy
+/// error.
+const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl RoswaalSqlitePool {
+    /// Attempts to open a new sqlite connection at the specified path.
+    pub(super) async fn open(path: &str) -> Result<Self> {
+        let (pool, read_only_pool) = if path != SQLITE_IN_MEMORY_PATH {
+            let options = Self::connect_options(path)?
+                .create_if_missing(true)
+                .journal_mode(SqliteJournalMode::Wal);
+            let pool = Pool::<Sqlite>::connect_with(options).await?;
+            Self::migrate(&pool).await?;
+            let options = Self::connect_options(path)?.read_only(true);
+            (pool, Some(Pool::<Sqlite>::connect_with(options).await?))
+        } else {
+            // NB: Each in-memory pool has its own database, so reads need to share the pool that
+            // the database was created with.
+            let pool = Pool::<Sqlite>::connect_with(Self::connect_options(path)?).await?;
+            Self::migrate(&pool).await?;
+            (pool, None)
+        };
+        Ok(Self {
+            pool: RwLock::new(pool),
+            read_only_pool,
+        })
+    }
+
+    fn connect_options(path: &str) -> Result<SqliteConnectOptions> {
+        Ok(SqliteConnectOptions::from_str(path)?
+            .busy_timeout(SQLITE_BUSY_TIMEOUT)
+            .foreign_keys(true))
+    }
+
+    async fn migrate(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::migrate_v1(pool).await?;
+        Self::migrate_v2(pool).await?;
+        Self::migrate_v3(pool).await?;
+        Self::migrate_v4(pool).await?;
+        Self::migrate_v5(pool).await?;
+        Self::migrate_v6(pool).await?;
+        Self::migrate_v7(pool).await?;
+        Self::migrate_v8(pool).await?;
+        Self::migrate_v9(pool).await?;
+        Self::migrate_v10(pool).await?;
+        Self::migrate_v11(pool).await?;
+        Self::migrate_v12(pool).await?;
+        Self::migrate_v13(pool).await?;
+        Self::migrate_v14(pool).await?;
+        Self::migrate_v15(pool).await?;
+        Self::migrate_v16(pool).await?;
+        Self::migrate_v17(pool).await
+    }
+
+    async fn migrate_v1(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS Locations (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    latitude DOUBLE NOT NULL,
+    longitude DOUBLE NOT NULL,
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    UNIQUE(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS Tests (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    unmerged_branch_name TEXT,
+    error_message TEXT,
+    error_stack_trace TEXT,
+    command_failure_ordinal INTEGER,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    last_run_date DATETIME,
+    UNIQUE(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS TestSteps (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    test_id INTEGER NOT NULL,
+    content TEXT NOT_NULL,
+    ordinal INTEGER NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    UNIQUE(test_id, content),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestTags (
+    test_id INTEGER NOT NULL,
+    tag TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(test_id, tag),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS Suites (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+CREATE TABLE IF NOT EXISTS TestSuites (
+    test_id INTEGER NOT NULL PRIMARY KEY,
+    suite_id INTEGER NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE,
+    CONSTRAINT fk_suite FOREIGN KEY(suite_id) REFERENCES Suites(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestStepArtifacts (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    test_id INTEGER NOT NULL,
+    command_ordinal INTEGER NOT NULL,
+    url TEXT,
+    base64_data TEXT,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestStepDurations (
+    test_id INTEGER NOT NULL,
+    command_ordinal INTEGER NOT NULL,
+    milliseconds INTEGER NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(test_id, command_ordinal),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE VIRTUAL TABLE IF NOT EXISTS TestsSearch USING fts5(
+    name,
+    description,
+    steps,
+    requirements
+);
+CREATE TRIGGER IF NOT EXISTS delete_test_search AFTER DELETE ON Tests BEGIN
+    DELETE FROM TestsSearch WHERE rowid = old.id;
+END;
+CREATE TABLE IF NOT EXISTS StagedTestRemovals (
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS AuditLog (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    user TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    arguments TEXT NOT NULL,
+    branch_name TEXT,
+    error_message TEXT,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+CREATE TABLE IF NOT EXISTS StagedLocationRemovals (
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(name, unmerged_branch_name)
+);
+CREATE TABLE IF NOT EXISTS SlackUserRoles (
+    role TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    ordinal INTEGER NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(role, user_id)
+);
+CREATE TABLE IF NOT EXISTS PendingPullRequests (
+    owner TEXT NOT NULL,
+    repo TEXT NOT NULL,
+    head_branch_name TEXT NOT NULL,
+    base_branch_name TEXT NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    is_draft BOOLEAN NOT NULL DEFAULT FALSE,
+    labels TEXT NOT NULL DEFAULT '',
+    assignees TEXT NOT NULL DEFAULT '',
+    reviewers TEXT NOT NULL DEFAULT '',
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(owner, repo, head_branch_name)
+);
+CREATE TABLE IF NOT EXISTS TestRuns (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    branch_name TEXT NOT NULL,
+    test_names TEXT NOT NULL,
+    url TEXT,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+CREATE TABLE IF NOT EXISTS TestRunResults (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    test_id INTEGER NOT NULL,
+    did_fail BOOLEAN NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    CONSTRAINT fk_test FOREIGN KEY(test_id) REFERENCES Tests(id) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS TestSubmissions (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    test_count INTEGER NOT NULL,
+    compilation_error_count INTEGER NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the region column to locations in databases that were created before regions existed.
+    async fn migrate_v2(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(pool, "Locations", "region", "TEXT").await
+    }
+
+    /// Adds the deprecation date column to tests in databases that were created before tests
+    /// could be deprecated.
+    async fn migrate_v3(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(pool, "Tests", "deprecation_date", "DATETIME").await
+    }
+
+    /// Adds the table of tests whose failures are not posted to the test failure channel.
+    async fn migrate_v4(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS MutedTestFailures (
+    name TEXT NOT NULL PRIMARY KEY,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the column that marks tests which only have a name and abstract in databases that
+    /// were created before tests could be planned.
+    async fn migrate_v5(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(
+            pool,
+            "Tests",
+            "is_planned",
+            "BOOLEAN NOT NULL DEFAULT FALSE",
+        )
+        .await
+    }
+
+    /// Adds the table of users and user groups that are allowed to run restricted slack commands.
+    async fn migrate_v6(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS SlackCommandPermissions (
+    command TEXT NOT NULL,
+    subject_id TEXT NOT NULL,
+    is_user_group BOOLEAN NOT NULL,
+    ordinal INTEGER NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    PRIMARY KEY(command, subject_id)
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the table of alternate names that tests can use for locations.
+    ///
+    /// Aliases are keyed by their normalized name, so that adding an alias that only differs in
+    /// casing or whitespace replaces the existing alias.
+    async fn migrate_v7(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS LocationAliases (
+    normalized_alias TEXT NOT NULL PRIMARY KEY,
+    alias TEXT NOT NULL,
+    location_name TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the column that records the slack user who added each test.
+    async fn migrate_v8(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(pool, "Tests", "added_by_slack_user_id", "TEXT").await
+    }
+
+    /// Adds the data revision, and the triggers that increment it whenever a row in
+    /// `REVISIONED_TABLES` is inserted, updated, or deleted.
+    async fn migrate_v9(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS DataRevision (
+    id INTEGER NOT NULL PRIMARY KEY CHECK (id = 0),
+    revision INTEGER NOT NULL
+);
+INSERT OR IGNORE INTO DataRevision (id, revision) VALUES (0, 0);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        for table in REVISIONED_TABLES {
+            for operation in ["INSERT", "UPDATE", "DELETE"] {
+                query(&format!(
+                    "
+CREATE TRIGGER IF NOT EXISTS {table}_{operation}_DataRevision AFTER {operation} ON {table}
+BEGIN
+    UPDATE DataRevision SET revision = revision + 1;
+END;
+                    ",
+                ))
+                .execute(pool)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the table of tests that failed to compile when they were given to `/add-tests`, so
+    /// that the user who submitted them can look them up after the slack message is gone.
+    async fn migrate_v10(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS CompilationFailures (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    slack_user_id TEXT NOT NULL,
+    syntax TEXT NOT NULL,
+    error_messages TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the column that stores the comma separated platforms that each test runs on, where an
+    /// empty string means that the test runs on every platform.
+    async fn migrate_v11(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(pool, "Tests", "platforms", "TEXT NOT NULL DEFAULT ''").await
+    }
+
+    /// Adds the table of the slack users who are assigned to triage the failure of a test.
+    async fn migrate_v12(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS TestFailureAssignments (
+    name TEXT NOT NULL PRIMARY KEY,
+    slack_user_id TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the column that stores the JSON array of variables declared by each test.
+    async fn migrate_v13(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(pool, "Tests", "variables", "TEXT NOT NULL DEFAULT '[]'").await
+    }
+
+    /// Adds the table of the tests that are leased to a test runner until their expiration date.
+    async fn migrate_v14(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS TestLeases (
+    name TEXT NOT NULL PRIMARY KEY,
+    runner_id TEXT NOT NULL,
+    expiration_date DATETIME NOT NULL
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Adds the column that stores the source code that each test was compiled from, which is
+    /// NULL for tests saved before it was added.
+    async fn migrate_v15(pool: &Pool<Sqlite>) -> Result<()> {
+        Self::add_column_if_missing(pool, "Tests", "source_code", "TEXT").await
+    }
+
+    /// Adds the table of the tests that were removed, which stores a snapshot of each removed test
+    /// so that it can be restored with `/restore-test`.
+    async fn migrate_v16(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS ArchivedTests (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    commands TEXT NOT NULL,
+    syntax TEXT NOT NULL,
+    added_by_slack_user_id TEXT,
+    removed_branch_name TEXT NOT NULL,
+    archive_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn migrate_v17(pool: &Pool<Sqlite>) -> Result<()> {
+        query(
+            "
+CREATE TABLE IF NOT EXISTS SlackReports (
+    id TEXT NOT NULL PRIMARY KEY,
+    blocks TEXT NOT NULL,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch())
+);
+            ",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn add_column_if_missing(
+        pool: &Pool<Sqlite>,
+        table: &str,
+        column: &str,
+        column_type: &str,
+    ) -> Result<()> {
+        let column_count = query_scalar::<Sqlite, i64>(
+            "SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?;",
+        )
+        .bind(table)
+        .bind(column)
+        .fetch_one(pool)
+        .await?;
+        if column_count == 0 {
+            query(&format!(
+                "ALTER TABLE {} ADD COLUMN {} {};",
+                table, column, column_type
+            ))
+            .execute(pool)
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+impl RoswaalSqlitePool {
+    /// Attempts to open a transaction that waits for every other transaction that shares its pool
+    /// to finish.
+    pub(super) async fn transaction(
+        &self,
+    ) -> Result<(RoswaalSqlitePoolGuard<'_>, Transaction<'static, Sqlite>)> {
+        let pool = self.pool.write().await;
+        let transaction = pool.begin().await?;
+        Ok((RoswaalSqlitePoolGuard::Write { _pool: pool }, transaction))
+    }
+
+    /// Attempts to open a read only transaction.
+    pub(super) async fn read_transaction(
+        &self,
+    ) -> Result<(RoswaalSqlitePoolGuard<'_>, Transaction<'static, Sqlite>)> {
+        // NB: Sqlite transactions are deferred by default, so this is a BEGIN DEFERRED.
+        match &self.read_only_pool {
+            Some(read_only_pool) => Ok((
+                RoswaalSqlitePoolGuard::Unlocked,
+                read_only_pool.begin().await?,
+            )),
+            None => {
+                let pool = self.pool.read().await;
+                let transaction = pool.begin().await?;
+                Ok((RoswaalSqlitePoolGuard::Read { _pool: pool }, transaction))
+            }
+        }
+    }
+}
+
+/// The lock that a transaction holds on the pool until it finishes.
+#[derive(Debug)]
+pub(super) enum RoswaalSqlitePoolGuard<'a> {
+    Write {
+        _pool: RwLockWriteGuard<'a, Pool<Sqlite>>,
+    },
+    Read {
+        _pool: RwLockReadGuard<'a, Pool<Sqlite>>,
+    },
+    /// Read only transactions on the read only pool do not lock the pool.
+    Unlocked,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs::remove_file};
+
+    use nanoid::nanoid;
+
+    use crate::utils::db::{query::query_scalar, RoswaalDatabase};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_opens_database_in_wal_mode_with_foreign_keys() {
+        let path = env::temp_dir().join(format!("roswaal-{}.sqlite", nanoid!(10)));
+        let sqlite = RoswaalDatabase::open_sqlite(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let mut transaction = sqlite.read_transaction().await.unwrap();
+        let journal_mode: String = query_scalar("PRAGMA journal_mode")
+            .fetch_one(transaction.connection())
+            .await
+            .unwrap();
+        let foreign_keys: bool = query_scalar("PRAGMA foreign_keys")
+            .fetch_one(transaction.connection())
+            .await
+            .unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(journal_mode, "wal");
+        assert!(foreign_keys);
+        _ = remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_adds_region_column_to_existing_locations_table() {
+        let path = env::temp_dir().join(format!("roswaal-{}.sqlite", nanoid!(10)));
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let pool = Pool::<Sqlite>::connect_with(options).await.unwrap();
+        _ = query(
+            "
+CREATE TABLE Locations (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    latitude DOUBLE NOT NULL,
+    longitude DOUBLE NOT NULL,
+    name TEXT NOT NULL,
+    unmerged_branch_name TEXT,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    UNIQUE(name, unmerged_branch_name)
+);
+INSERT INTO Locations (latitude, longitude, name) VALUES (1.0, 1.0, 'Test');
+            ",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+
+        let sqlite = RoswaalDatabase::open_sqlite(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let mut transaction = sqlite.transaction().await.unwrap();
+        let regions: Vec<Option<String>> = query_scalar("SELECT region FROM Locations")
+            .fetch_all(transaction.connection())
+            .await
+            .unwrap();
+        assert_eq!(regions, vec![None]);
+        transaction.commit().await.unwrap();
+        _ = remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_adds_deprecation_date_column_to_existing_tests_table() {
+        let path = env::temp_dir().join(format!("roswaal-{}.sqlite", nanoid!(10)));
+        let options = SqliteConnectOptions::new()
+            .filename(&path)
+            .create_if_missing(true);
+        let pool = Pool::<Sqlite>::connect_with(options).await.unwrap();
+        _ = query(
+            "
+CREATE TABLE Tests (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    description TEXT,
+    unmerged_branch_name TEXT,
+    error_message TEXT,
+    error_stack_trace TEXT,
+    command_failure_ordinal INTEGER,
+    creation_date DATETIME NOT NULL DEFAULT (unixepoch()),
+    last_run_date DATETIME,
+    UNIQUE(name, unmerged_branch_name)
+);
+INSERT INTO Tests (name) VALUES ('Test');
+            ",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+
+        let sqlite = RoswaalDatabase::open_sqlite(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let mut transaction = sqlite.transaction().await.unwrap();
+        let dates: Vec<Option<i64>> = query_scalar("SELECT deprecation_date FROM Tests")
+            .fetch_all(transaction.connection())
+            .await
+            .unwrap();
+        assert_eq!(dates, vec![None]);
+        transaction.commit().await.unwrap();
+        _ = remove_file(path);
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
     fs::{read_dir, remove_dir},
     io::{self, Result},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 use tokio::task::spawn_blocking;
@@ -32,6 +32,23 @@ fn remove_dir_all_empty_sync(path: impl AsRef<Path>) -> Result<()> {
     Ok(())
 }
 
+/// Converts the raw bytes of a path (eg. from libgit2) into a `PathBuf` without requiring the
+/// bytes to be valid UTF8.
+#[cfg(unix)]
+pub fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+/// Converts the raw bytes of a path (eg. from libgit2) into a `PathBuf`.
+///
+/// libgit2 always encodes paths as UTF8 on Windows, so the bytes are only lossy if libgit2 was
+/// given an invalid path.
+#[cfg(windows)]
+pub fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
 // NB: Copied from tokio::fs.
 async fn asyncify<F, T>(f: F) -> io::Result<T>
 where
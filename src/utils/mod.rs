@@ -1,10 +1,14 @@
+pub mod config;
+pub mod database_url;
+pub mod db;
 pub mod dedup;
 pub mod env;
 pub mod fs;
 pub mod is_case;
 pub mod log;
 pub mod normalize;
-pub mod sqlite;
+pub mod retry;
+pub mod stage;
 pub mod string;
 #[cfg(test)]
 pub mod test_error;
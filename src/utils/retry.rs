@@ -0,0 +1,229 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    future::Future,
+    io::{self, ErrorKind},
+    time::Duration,
+};
+
+use anyhow::Result;
+use tokio::time::sleep;
+
+/// The longest delay between two attempts, regardless of how many attempts have failed.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// How many times an operation that can fail transiently is attempted, and how long to wait
+/// between attempts.
+///
+/// The delay doubles after each failed attempt, starting from the initial delay.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RoswaalRetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+}
+
+impl RoswaalRetryPolicy {
+    pub fn new(max_attempts: u32, initial_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_delay,
+        }
+    }
+}
+
+impl Default for RoswaalRetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500))
+    }
+}
+
+impl RoswaalRetryPolicy {
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    /// Returns how long to wait after the failed attempt with the 1-based `attempt` number.
+    pub fn delay_after_attempt(&self, attempt: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(multiplier)
+            .min(MAX_RETRY_DELAY)
+    }
+}
+
+/// An error for an operation that failed with a transient error on every attempt.
+#[derive(Debug)]
+pub struct RetriesExhaustedError {
+    attempts: u32,
+    source: anyhow::Error,
+}
+
+impl RetriesExhaustedError {
+    /// Returns true if `error` was returned by `retry` after every attempt failed transiently.
+    pub fn is_exhausted(error: &anyhow::Error) -> bool {
+        error.downcast_ref::<Self>().is_some()
+    }
+}
+
+impl Display for RetriesExhaustedError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Gave up after {} attempts: {}",
+            self.attempts, self.source
+        )
+    }
+}
+
+impl Error for RetriesExhaustedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Runs `operation` until it succeeds, fails with an error that `is_transient` rejects, or has
+/// been attempted as many times as the policy allows.
+///
+/// Permanent errors are returned as is, and the last transient error is wrapped in a
+/// `RetriesExhaustedError` so that callers can tell the two apart.
+pub async fn retry<T, Fut: Future<Output = Result<T>>>(
+    policy: &RoswaalRetryPolicy,
+    is_transient: impl Fn(&anyhow::Error) -> bool,
+    mut operation: impl FnMut() -> Fut,
+) -> Result<T> {
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) if !is_transient(&error) => return Err(error),
+            Err(error) if attempt >= policy.max_attempts => {
+                return Err(RetriesExhaustedError {
+                    attempts: attempt,
+                    source: error,
+                }
+                .into())
+            }
+            Err(error) => {
+                let delay = policy.delay_after_attempt(attempt);
+                log::warn!(
+                    "Attempt {} failed with a transient error, retrying in {:?}. {}",
+                    attempt,
+                    delay,
+                    error
+                );
+                sleep(delay).await;
+                attempt += 1
+            }
+        }
+    }
+}
+
+/// Returns true if any error in the chain of `error` is an io error caused by a flaky network
+/// connection.
+pub fn is_transient_io_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.downcast_ref::<io::Error>().is_some_and(|error| {
+            matches!(
+                error.kind(),
+                ErrorKind::ConnectionRefused
+                    | ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::NotConnected
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::TimedOut
+                    | ErrorKind::UnexpectedEof
+            )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use crate::utils::test_error::TestError;
+
+    use super::*;
+
+    #[test]
+    fn delay_doubles_after_each_attempt_up_to_max() {
+        let policy = RoswaalRetryPolicy::new(10, Duration::from_secs(1));
+        assert_eq!(policy.delay_after_attempt(1), Duration::from_secs(1));
+        assert_eq!(policy.delay_after_attempt(2), Duration::from_secs(2));
+        assert_eq!(policy.delay_after_attempt(3), Duration::from_secs(4));
+        assert_eq!(policy.delay_after_attempt(9), MAX_RETRY_DELAY);
+        assert_eq!(policy.delay_after_attempt(u32::MAX), MAX_RETRY_DELAY)
+    }
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let attempts = AtomicU32::new(0);
+        let value = retry(
+            &policy(3),
+            |_| true,
+            || async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(anyhow::Error::new(TestError))
+                } else {
+                    Ok(10)
+                }
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, 10);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3)
+    }
+
+    #[tokio::test]
+    async fn returns_exhausted_error_after_max_attempts() {
+        let attempts = AtomicU32::new(0);
+        let error = retry(
+            &policy(3),
+            |_| true,
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), anyhow::Error>(anyhow::Error::new(TestError))
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(RetriesExhaustedError::is_exhausted(&error));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3)
+    }
+
+    #[tokio::test]
+    async fn returns_permanent_errors_without_retrying() {
+        let attempts = AtomicU32::new(0);
+        let error = retry(
+            &policy(3),
+            |_| false,
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err::<(), anyhow::Error>(anyhow::Error::new(TestError))
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(!RetriesExhaustedError::is_exhausted(&error));
+        assert!(error.downcast_ref::<TestError>().is_some());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1)
+    }
+
+    #[test]
+    fn detects_transient_io_errors() {
+        let reset = anyhow::Error::new(io::Error::from(ErrorKind::ConnectionReset));
+        let not_found = anyhow::Error::new(io::Error::from(ErrorKind::NotFound));
+        assert!(is_transient_io_error(&reset));
+        assert!(is_transient_io_error(&reset.context("Pushing changes")));
+        assert!(!is_transient_io_error(&not_found))
+    }
+
+    fn policy(max_attempts: u32) -> RoswaalRetryPolicy {
+        RoswaalRetryPolicy::new(max_attempts, Duration::ZERO)
+    }
+}
@@ -0,0 +1,90 @@
+use std::future::Future;
+
+use tokio::sync::watch;
+
+tokio::task_local! {
+    static STAGE_REPORTER: watch::Sender<Option<RoswaalOperationStage>>;
+}
+
+/// A checkpoint in a long running operation, such as adding tests, that is reported so that
+/// users can see what the operation is currently doing.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoswaalOperationStage {
+    Cloning,
+    Pulling,
+    Compiling,
+    Generating,
+    Pushing,
+    OpeningPullRequest,
+}
+
+impl RoswaalOperationStage {
+    /// Reports that the operation running on the current task has reached this stage.
+    ///
+    /// Nothing is reported when the current task is not running inside
+    /// `RoswaalOperationStage::reporting`, or when the operation is already at this stage.
+    pub fn report(self) {
+        _ = STAGE_REPORTER.try_with(|reporter| {
+            reporter.send_if_modified(|stage| {
+                let is_modified = *stage != Some(self);
+                *stage = Some(self);
+                is_modified
+            })
+        });
+    }
+
+    /// Runs `future` such that every stage reported while it runs is sent to the returned
+    /// receiver.
+    ///
+    /// The sender side of the receiver is dropped alongside the returned future.
+    pub fn reporting<F: Future>(
+        future: F,
+    ) -> (
+        impl Future<Output = F::Output>,
+        watch::Receiver<Option<RoswaalOperationStage>>,
+    ) {
+        let (sender, receiver) = watch::channel(None);
+        (STAGE_REPORTER.scope(sender, future), receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_stages_to_the_receiver_of_the_running_future() {
+        let (future, receiver) = RoswaalOperationStage::reporting(async {
+            RoswaalOperationStage::Pulling.report();
+            RoswaalOperationStage::Generating.report();
+        });
+        future.await;
+        assert_eq!(*receiver.borrow(), Some(RoswaalOperationStage::Generating));
+    }
+
+    #[tokio::test]
+    async fn does_not_report_the_same_stage_twice() {
+        let (future, mut receiver) = RoswaalOperationStage::reporting(async {
+            RoswaalOperationStage::Pushing.report();
+            tokio::task::yield_now().await;
+            RoswaalOperationStage::Pushing.report();
+        });
+        let run = async move {
+            future.await;
+        };
+        let changes = async {
+            let mut count = 0;
+            while receiver.changed().await.is_ok() {
+                count += 1;
+            }
+            count
+        };
+        let (_, count) = tokio::join!(run, changes);
+        assert_eq!(count, 1)
+    }
+
+    #[test]
+    fn reporting_outside_of_a_reporting_future_does_nothing() {
+        RoswaalOperationStage::Cloning.report()
+    }
+}
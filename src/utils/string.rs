@@ -1,3 +1,7 @@
+use std::collections::HashSet;
+
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
 pub trait ToAsciiCamelCase {
     fn to_ascii_camel_case(&self) -> String;
 }
@@ -44,6 +48,115 @@ pub trait ToAsciiKebabCase: ToString {
 impl ToAsciiKebabCase for String {}
 impl ToAsciiKebabCase for &str {}
 
+/// Characters that are removed from identifiers without separating words, such that "Justin's"
+/// becomes "justins" rather than "justinS".
+const IDENTIFIER_REMOVED_CHARACTERS: &str = r#"()$@#*,".;:'!"#;
+
+/// Returns a camel cased identifier for `text` that is valid in typescript.
+///
+/// Accented latin letters are transliterated to ascii, and any other character that cannot be
+/// part of an identifier separates words. When nothing is left of `text`, such as when it only
+/// contains emojis, the identifier is derived from a stable hash of `text` instead.
+pub fn to_camel_case_identifier(text: &str) -> String {
+    let words = transliterate_to_ascii(text)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || IDENTIFIER_REMOVED_CHARACTERS.contains(c) {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect::<String>();
+    let mut identifier = words.to_ascii_camel_case();
+    identifier.retain(|c| c.is_ascii_alphanumeric() || c == '_');
+    if identifier.is_empty() {
+        return format!("action{:08x}", fnv1a_hash(text));
+    }
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+/// Replaces the accented latin letters in `text` with their closest ascii letters, leaving all
+/// other characters as they are.
+pub fn transliterate_to_ascii(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+    for c in text.nfkd().filter(|c| !is_combining_mark(*c)) {
+        match c {
+            'ß' => ascii.push_str("ss"),
+            'Æ' => ascii.push_str("AE"),
+            'æ' => ascii.push_str("ae"),
+            'Œ' => ascii.push_str("OE"),
+            'œ' => ascii.push_str("oe"),
+            'Þ' => ascii.push_str("Th"),
+            'þ' => ascii.push_str("th"),
+            'Ø' => ascii.push('O'),
+            'ø' => ascii.push('o'),
+            'Ł' => ascii.push('L'),
+            'ł' => ascii.push('l'),
+            'Đ' | 'Ð' => ascii.push('D'),
+            'đ' | 'ð' => ascii.push('d'),
+            'ı' => ascii.push('i'),
+            c => ascii.push(c),
+        }
+    }
+    ascii
+}
+
+/// A 32 bit FNV-1a hash, which unlike the hashers in the standard library is guaranteed to be
+/// the same across runs and toolchains, so generated identifiers never change.
+fn fnv1a_hash(text: &str) -> u32 {
+    text.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+/// A set of identifiers that hands out numeric suffixes to identifiers that were already taken.
+#[derive(Debug, Default)]
+pub struct UniqueIdentifiers {
+    taken: HashSet<String>,
+}
+
+impl UniqueIdentifiers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `identifier` if it has not been taken, otherwise returns `identifier` with the
+    /// smallest suffix starting from 2 that has not been taken.
+    pub fn unique(&mut self, identifier: &str) -> String {
+        let mut unique_identifier = identifier.to_string();
+        let mut suffix = 2;
+        while self.taken.contains(&unique_identifier) {
+            unique_identifier = format!("{}{}", identifier, suffix);
+            suffix += 1;
+        }
+        self.taken.insert(unique_identifier.clone());
+        unique_identifier
+    }
+}
+
+/// Returns the minimum number of single character insertions, deletions, and substitutions
+/// needed to turn `a` into `b`.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut distances = (0..=b.len()).collect::<Vec<usize>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_diagonal + if a_char == *b_char { 0 } else { 1 };
+            previous_diagonal = distances[j + 1];
+            distances[j + 1] = substitution
+                .min(distances[j] + 1)
+                .min(previous_diagonal + 1);
+        }
+    }
+    distances[b.len()]
+}
+
 #[cfg(test)]
 mod string_utils_tests {
     use super::*;
@@ -112,4 +225,88 @@ mod string_utils_tests {
             assert_eq!(before.to_ascii_kebab_case(), after.to_string())
         }
     }
+
+    #[test]
+    fn camel_case_identifier_is_the_same_as_camel_case_for_ascii_text() {
+        let strings = vec![
+            ("Hello world this is a test", "helloWorldThisIsATest"),
+            ("Justin's event!", "justinsEvent"),
+            (
+                "Type {{eventName}} in the search bar",
+                "typeEventnameInTheSearchBar",
+            ),
+            ("(Tap) the \"join\" button", "tapThejoinButton"),
+        ];
+        for (text, identifier) in strings {
+            assert_eq!(to_camel_case_identifier(text), identifier)
+        }
+    }
+
+    #[test]
+    fn camel_case_identifier_transliterates_accented_letters() {
+        let strings = vec![
+            ("Join Élodie's café", "joinElodiesCafe"),
+            ("Ångström Straße", "angstromStrasse"),
+            ("Visit Łódź with Søren", "visitLodzWithSoren"),
+            ("ＦＵＬＬ width", "fullWidth"),
+        ];
+        for (text, identifier) in strings {
+            assert_eq!(to_camel_case_identifier(text), identifier)
+        }
+    }
+
+    #[test]
+    fn camel_case_identifier_separates_words_with_unsupported_characters() {
+        let strings = vec![
+            ("Sign-in with email/password", "signInWithEmailPassword"),
+            ("Tap 🎉 the button", "tapTheButton"),
+            ("🎉Celebrate", "celebrate"),
+            ("Tap the 日本 button", "tapTheButton"),
+        ];
+        for (text, identifier) in strings {
+            assert_eq!(to_camel_case_identifier(text), identifier)
+        }
+    }
+
+    #[test]
+    fn camel_case_identifier_prefixes_leading_digits() {
+        assert_eq!(to_camel_case_identifier("3 users join"), "_3UsersJoin")
+    }
+
+    #[test]
+    fn camel_case_identifier_hashes_text_without_any_identifier_characters() {
+        let identifier = to_camel_case_identifier("🎉🎉🎉");
+        assert_eq!(identifier, to_camel_case_identifier("🎉🎉🎉"));
+        assert!(identifier.starts_with("action"));
+        assert_eq!(identifier.len(), "action".len() + 8);
+        assert_ne!(identifier, to_camel_case_identifier("日本語"));
+        assert_ne!(to_camel_case_identifier(""), to_camel_case_identifier("!!"))
+    }
+
+    #[test]
+    fn unique_identifiers_suffixes_taken_identifiers() {
+        let mut identifiers = UniqueIdentifiers::new();
+        assert_eq!(identifiers.unique("tapButton"), "tapButton");
+        assert_eq!(identifiers.unique("tapButton"), "tapButton2");
+        assert_eq!(identifiers.unique("tapButton2"), "tapButton22");
+        assert_eq!(identifiers.unique("tapButton"), "tapButton3");
+        assert_eq!(identifiers.unique("other"), "other")
+    }
+
+    #[test]
+    fn levenshtein_distance() {
+        let strings = vec![
+            ("", "", 0),
+            ("step", "step", 0),
+            ("steb", "step", 1),
+            ("", "wait", 4),
+            ("requirment", "requirement", 1),
+            ("kitten", "sitting", 3),
+            ("verify", "using", 5),
+        ];
+        for (a, b, distance) in strings {
+            assert_eq!(super::levenshtein_distance(a, b), distance);
+            assert_eq!(super::levenshtein_distance(b, a), distance)
+        }
+    }
 }